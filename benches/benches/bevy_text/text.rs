@@ -0,0 +1,147 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use bevy_asset::Assets;
+use bevy_math::Vec2;
+use bevy_render::texture::Image;
+use bevy_sprite::TextureAtlasLayout;
+use bevy_text::{
+    Font, FontAtlasSets, FontRenderMode, JustifyText, Text, TextLayoutOptions, TextMeasureInfo,
+    TextPipeline, TextSection, TextSettings, TextStyle, YAxisOrientation,
+};
+
+const FONT: &[u8] = include_bytes!("../../../crates/bevy_text/src/FiraMono-subset.ttf");
+
+fn paragraphs() -> Text {
+    let style = TextStyle {
+        font_size: 16.,
+        ..Default::default()
+    };
+    Text::from_sections((0..20).map(|index| {
+        TextSection::new(
+            format!("Section {index} of some text that wraps over a few lines. 0123456789\n"),
+            style.clone(),
+        )
+    }))
+}
+
+fn font_and_text() -> (Assets<Font>, Text) {
+    let mut fonts = Assets::<Font>::default();
+    let font = fonts.add(Font::try_from_bytes(FONT.to_vec()).unwrap());
+    let mut text = paragraphs();
+    for section in &mut text.sections {
+        section.style.font = font.clone();
+    }
+    (fonts, text)
+}
+
+fn layout(c: &mut Criterion) {
+    let font = Font::try_from_bytes(FONT.to_vec()).unwrap();
+    let text = paragraphs();
+    let fonts = vec![&font; text.sections.len()];
+    let mut group = c.benchmark_group("text_layout");
+    for (name, justify) in [
+        ("left", JustifyText::Left),
+        ("center", JustifyText::Center),
+        ("justified", JustifyText::JustifiedLeft),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                black_box(TextPipeline::layout_text(
+                    &fonts,
+                    &text.sections,
+                    1.,
+                    &TextLayoutOptions {
+                        justify,
+                        ..Default::default()
+                    },
+                    black_box(Vec2::new(300., f32::INFINITY)),
+                    YAxisOrientation::TopToBottom,
+                ))
+            });
+        });
+    }
+    group.finish();
+}
+
+fn queue_text(c: &mut Criterion) {
+    let (fonts, text) = font_and_text();
+    let settings = TextSettings::default();
+    let queue = |pipeline: &mut TextPipeline,
+                 font_atlas_sets: &mut FontAtlasSets,
+                 texture_atlases: &mut Assets<TextureAtlasLayout>,
+                 textures: &mut Assets<Image>| {
+        black_box(
+            pipeline
+                .queue_text(
+                    &fonts,
+                    &text.sections,
+                    1.,
+                    &TextLayoutOptions::default(),
+                    Vec2::new(300., f32::INFINITY),
+                    font_atlas_sets,
+                    texture_atlases,
+                    textures,
+                    &settings,
+                    YAxisOrientation::TopToBottom,
+                    FontRenderMode::Bitmap,
+                )
+                .unwrap(),
+        )
+    };
+
+    let mut group = c.benchmark_group("text_queue");
+    // Every glyph is rasterized and inserted into new atlases
+    group.bench_function("atlas_insertion", |b| {
+        b.iter_batched_ref(
+            || {
+                (
+                    TextPipeline::default(),
+                    FontAtlasSets::default(),
+                    Assets::<TextureAtlasLayout>::default(),
+                    Assets::<Image>::default(),
+                )
+            },
+            |(pipeline, font_atlas_sets, texture_atlases, textures)| {
+                queue(pipeline, font_atlas_sets, texture_atlases, textures)
+            },
+            BatchSize::SmallInput,
+        );
+    });
+    // Every glyph is already in the atlases
+    let mut pipeline = TextPipeline::default();
+    let mut font_atlas_sets = FontAtlasSets::default();
+    let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+    let mut textures = Assets::<Image>::default();
+    group.bench_function("cached_glyphs", |b| {
+        b.iter(|| {
+            queue(
+                &mut pipeline,
+                &mut font_atlas_sets,
+                &mut texture_atlases,
+                &mut textures,
+            )
+        });
+    });
+    group.finish();
+}
+
+fn measure(c: &mut Criterion) {
+    let (fonts, text) = font_and_text();
+    let mut group = c.benchmark_group("text_measure");
+    group.bench_function("from_text", |b| {
+        b.iter(|| black_box(TextMeasureInfo::from_text(&text, &fonts, 1.).unwrap()));
+    });
+    // The measure function of a UI text node is called with a few widths per layout
+    let info = TextMeasureInfo::from_text(&text, &fonts, 1.).unwrap();
+    group.bench_function("compute_size", |b| {
+        b.iter(|| {
+            for width in [100., 200., 400.] {
+                black_box(info.compute_size(black_box(Vec2::new(width, f32::INFINITY))));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, layout, queue_text, measure);
+criterion_main!(benches);
@@ -0,0 +1,227 @@
+//! Visualization of the geometry used by [`ui_focus_system`](bevy_ui::ui_focus_system) for hit testing.
+use std::fmt::Write;
+
+use bevy_color::{Color, Hsla};
+use bevy_core::Name;
+use bevy_ecs::prelude::*;
+use bevy_gizmos::prelude::Gizmos;
+use bevy_math::{Rect, Vec2};
+use bevy_render::view::{ViewVisibility, Visibility};
+use bevy_text::{Text, TextStyle};
+use bevy_transform::prelude::GlobalTransform;
+use bevy_ui::{
+    node_bundles::TextBundle, CalculatedClip, Interaction, Node, PositionType, Style, UiScale,
+    UiStack, Val, ZIndex,
+};
+use bevy_utils::default;
+use bevy_window::{PrimaryWindow, Window};
+
+use super::{inset::UiGizmosDebug, CameraQuery, LayoutRect, UiDebugOptions};
+
+/// Global [`ZIndex`] used to render the hit test label.
+pub const HIT_TEST_LABEL_ZINDEX: i32 = i32::MAX - 64;
+
+/// Half of the length of the lines of the cross drawn at the cursor position.
+const CURSOR_CROSS_HALF_SIZE: f32 = 8.;
+
+#[derive(Component)]
+pub(super) struct HitTestLabel;
+
+/// Returns the rect used by the focus system to test if the cursor is over the node,
+/// in logical UI coordinates.
+fn visible_rect(node: &Node, transform: &GlobalTransform, clip: Option<&CalculatedClip>) -> Rect {
+    let node_rect = node.logical_rect(transform);
+    clip.map(|clip| node_rect.intersect(clip.clip))
+        .unwrap_or(node_rect)
+}
+
+fn interaction_color(interaction: Interaction) -> Color {
+    match interaction {
+        Interaction::Pressed => Hsla::hsl(0., 1., 0.5).into(),
+        Interaction::Hovered => Hsla::hsl(60., 1., 0.5).into(),
+        Interaction::None => Hsla::hsl(0., 0., 0.6).into(),
+    }
+}
+
+type InteractionNodesQuery = (
+    Entity,
+    &'static Node,
+    &'static GlobalTransform,
+    &'static Interaction,
+    Option<&'static CalculatedClip>,
+    Option<&'static ViewVisibility>,
+    Option<&'static Name>,
+);
+
+/// Draws the clipped rect of every interactable node and a cross at the cursor position.
+///
+/// Nodes are drawn in the order they are hit tested by the focus system,
+/// so the outlines of nodes on top are drawn over the ones below them.
+pub(super) fn draw_hit_test_geometry(
+    mut draw: Gizmos<UiGizmosDebug>,
+    cam: CameraQuery,
+    options: Res<UiDebugOptions>,
+    ui_stack: Res<UiStack>,
+    ui_scale: Res<UiScale>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    nodes: Query<InteractionNodesQuery>,
+) {
+    if !options.enabled || !options.show_hit_test {
+        return;
+    }
+    let Ok(cam) = cam.get_single() else {
+        return;
+    };
+    let scale = ui_scale.0;
+    let to_viewport = |position: Vec2| {
+        cam.world_to_viewport(&GlobalTransform::IDENTITY, position.extend(0.))
+            .unwrap_or(position)
+    };
+
+    for (_, node, transform, interaction, clip, view_visibility, _) in
+        nodes.iter_many(&ui_stack.uinodes)
+    {
+        if view_visibility.is_some_and(|view_visibility| !view_visibility.get()) {
+            continue;
+        }
+        let rect = visible_rect(node, transform, clip);
+        if rect.is_empty() {
+            continue;
+        }
+        let rect = LayoutRect {
+            pos: rect.min * scale,
+            size: rect.size() * scale,
+        };
+        let (min, max) = (rect.pos, rect.pos + rect.size);
+        let strip = [
+            min,
+            Vec2::new(min.x, max.y),
+            max,
+            Vec2::new(max.x, min.y),
+            min,
+        ];
+        draw.linestrip_2d(strip.map(to_viewport), interaction_color(*interaction));
+    }
+
+    if let Some(cursor_position) = window.get_single().ok().and_then(Window::cursor_position) {
+        let color = Color::WHITE;
+        let (dx, dy) = (
+            Vec2::X * CURSOR_CROSS_HALF_SIZE,
+            Vec2::Y * CURSOR_CROSS_HALF_SIZE,
+        );
+        draw.line_2d(
+            to_viewport(cursor_position - dx),
+            to_viewport(cursor_position + dx),
+            color,
+        );
+        draw.line_2d(
+            to_viewport(cursor_position - dy),
+            to_viewport(cursor_position + dy),
+            color,
+        );
+    }
+}
+
+/// Spawns, shows or hides the hit test label when [`UiDebugOptions`] changes.
+pub(super) fn update_hit_test_label_visibility(
+    mut commands: Commands,
+    options: Res<UiDebugOptions>,
+    mut labels: Query<&mut Visibility, With<HitTestLabel>>,
+) {
+    if !options.is_changed() {
+        return;
+    }
+    let visible = options.enabled && options.show_hit_test;
+    if labels.is_empty() {
+        if visible {
+            commands.spawn((
+                TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        right: Val::Px(4.),
+                        bottom: Val::Px(4.),
+                        ..default()
+                    },
+                    z_index: ZIndex::Global(HIT_TEST_LABEL_ZINDEX),
+                    ..default()
+                },
+                HitTestLabel,
+                Name::new("Hit Test Debug Label"),
+            ));
+        }
+        return;
+    }
+    for mut visibility in &mut labels {
+        *visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Writes the cursor position and the hovered and pressed nodes to the hit test label.
+pub(super) fn update_hit_test_label(
+    options: Res<UiDebugOptions>,
+    ui_stack: Res<UiStack>,
+    ui_scale: Res<UiScale>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    nodes: Query<InteractionNodesQuery>,
+    mut labels: Query<&mut Text, With<HitTestLabel>>,
+) {
+    if !options.enabled || !options.show_hit_test {
+        return;
+    }
+    let mut value = String::new();
+    match window.get_single().ok().and_then(Window::cursor_position) {
+        Some(cursor_position) => {
+            let ui_position = cursor_position / ui_scale.0;
+            let _ = writeln!(
+                value,
+                "cursor: ({:.1}, {:.1})",
+                ui_position.x, ui_position.y
+            );
+        }
+        None => value.push_str("cursor: none\n"),
+    }
+
+    // Topmost nodes first, matching the order the focus system tests them in.
+    for (entity, node, transform, interaction, clip, _, name) in
+        nodes.iter_many(ui_stack.uinodes.iter().rev())
+    {
+        if *interaction == Interaction::None {
+            continue;
+        }
+        let rect = visible_rect(node, transform, clip);
+        let _ = write!(value, "{interaction:?}: ");
+        match name {
+            Some(name) => {
+                let _ = write!(value, "{name} ({entity:?})");
+            }
+            None => {
+                let _ = write!(value, "{entity:?}");
+            }
+        }
+        let _ = writeln!(
+            value,
+            " [{:.1}, {:.1}] - [{:.1}, {:.1}]",
+            rect.min.x, rect.min.y, rect.max.x, rect.max.y
+        );
+    }
+    let value = value.trim_end();
+
+    for mut text in &mut labels {
+        if text.sections.is_empty() {
+            *text = Text::from_section(
+                value,
+                TextStyle {
+                    font_size: 16.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            );
+        } else if text.sections[0].value != value {
+            text.sections[0].value = value.to_string();
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! A visual representation of UI node sizes.
 use std::any::{Any, TypeId};
 
-use bevy_app::{App, Plugin, PostUpdate};
+use bevy_app::{App, Plugin, PostUpdate, Update};
 use bevy_color::Hsla;
 use bevy_core::Name;
 use bevy_core_pipeline::core_2d::Camera2dBundle;
@@ -23,8 +23,11 @@ use inset::InsetGizmo;
 
 use self::inset::UiGizmosDebug;
 
+mod hit_test;
 mod inset;
 
+pub use hit_test::HIT_TEST_LABEL_ZINDEX;
+
 /// The [`Camera::order`] index used by the layout debug camera.
 pub const LAYOUT_DEBUG_CAMERA_ORDER: isize = 255;
 /// The [`RenderLayers`] used by the debug gizmos and the debug camera.
@@ -55,6 +58,9 @@ struct DebugOverlayCamera;
 pub struct UiDebugOptions {
     /// Whether the overlay is enabled.
     pub enabled: bool,
+    /// Whether to also draw the clipped rects used to hit test nodes with an [`Interaction`](bevy_ui::Interaction),
+    /// the cursor position and a label listing the hovered and pressed nodes.
+    pub show_hit_test: bool,
     layout_gizmos_camera: Option<Entity>,
 }
 impl UiDebugOptions {
@@ -62,6 +68,10 @@ impl UiDebugOptions {
     pub fn toggle(&mut self) {
         self.enabled = !self.enabled;
     }
+    /// This will toggle the `show_hit_test` field, setting it to false if true and true if false.
+    pub fn toggle_hit_test(&mut self) {
+        self.show_hit_test = !self.show_hit_test;
+    }
 }
 
 /// The system responsible to change the [`Camera`] config based on changes in [`UiDebugOptions`] and [`GizmoConfig`](bevy_gizmos::prelude::GizmoConfig).
@@ -269,12 +279,20 @@ impl Plugin for DebugUiPlugin {
                 PostUpdate,
                 (
                     update_debug_camera,
-                    outline_roots
+                    (outline_roots, hit_test::draw_hit_test_geometry)
                         .after(TransformSystem::TransformPropagate)
                         // This needs to run before VisibilityPropagate so it can relies on ViewVisibility
                         .before(VisibilitySystems::VisibilityPropagate),
                 )
                     .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    hit_test::update_hit_test_label_visibility,
+                    hit_test::update_hit_test_label,
+                )
+                    .chain(),
             );
     }
 }
@@ -66,6 +66,51 @@ impl DynamicTextureAtlasBuilder {
         }
     }
 
+    /// The total size of the atlas.
+    pub fn size(&self) -> UVec2 {
+        let size = self.atlas_allocator.size();
+        UVec2::new(size.width as u32, size.height as u32)
+    }
+
+    /// Grow the atlas to `size`, keeping all the textures already added in place.
+    ///
+    /// `atlas_layout` and `atlas_texture` are resized to match. Axes where `size` is smaller than
+    /// the current size are left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the new total size for the atlas
+    /// * `atlas_layout` - The atlas layout to resize.
+    /// * `atlas_texture` - The atlas texture to resize, its contents are copied to the same position.
+    pub fn grow(
+        &mut self,
+        size: UVec2,
+        atlas_layout: &mut TextureAtlasLayout,
+        atlas_texture: &mut Image,
+    ) {
+        let old_size = self.size();
+        let size = size.max(old_size);
+        if size == old_size {
+            return;
+        }
+        self.atlas_allocator.grow(to_size2(size));
+
+        let format_size = atlas_texture.texture_descriptor.format.pixel_size();
+        let old_row_len = atlas_texture.width() as usize * format_size;
+        let row_len = size.x as usize * format_size;
+        let mut data = vec![0; row_len * size.y as usize];
+        for (row, old_row) in data
+            .chunks_exact_mut(row_len)
+            .zip(atlas_texture.data.chunks_exact(old_row_len))
+        {
+            row[..old_row_len].copy_from_slice(old_row);
+        }
+        atlas_texture.texture_descriptor.size.width = size.x;
+        atlas_texture.texture_descriptor.size.height = size.y;
+        atlas_texture.data = data;
+        atlas_layout.size = size;
+    }
+
     fn place_texture(
         &mut self,
         atlas_texture: &mut Image,
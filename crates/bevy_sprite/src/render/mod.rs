@@ -140,6 +140,7 @@ bitflags::bitflags! {
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
         const DEBAND_DITHER                     = 1 << 2;
+        const SINGLE_CHANNEL_TEXTURE            = 1 << 3;
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
         const TONEMAP_METHOD_RESERVED_BITS      = Self::TONEMAP_METHOD_MASK_BITS << Self::TONEMAP_METHOD_SHIFT_BITS;
         const TONEMAP_METHOD_NONE               = 0 << Self::TONEMAP_METHOD_SHIFT_BITS;
@@ -225,6 +226,10 @@ impl SpecializedRenderPipeline for SpritePipeline {
             }
         }
 
+        if key.contains(SpritePipelineKey::SINGLE_CHANNEL_TEXTURE) {
+            shader_defs.push("SINGLE_CHANNEL_TEXTURE".into());
+        }
+
         let format = match key.contains(SpritePipelineKey::HDR) {
             true => ViewTarget::TEXTURE_FORMAT_HDR,
             false => TextureFormat::bevy_default(),
@@ -473,6 +478,7 @@ pub fn queue_sprites(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     extracted_sprites: Res<ExtractedSprites>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
     mut views: Query<(
         Entity,
@@ -517,6 +523,13 @@ pub fn queue_sprites(
         }
 
         let pipeline = pipelines.specialize(&pipeline_cache, &sprite_pipeline, view_key);
+        // Single channel textures, like font atlases storing the coverage of glyphs, are drawn as white with the
+        // channel as alpha
+        let single_channel_pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &sprite_pipeline,
+            view_key | SpritePipelineKey::SINGLE_CHANNEL_TEXTURE,
+        );
 
         view_entities.clear();
         view_entities.extend(
@@ -539,10 +552,18 @@ pub fn queue_sprites(
             // These items will be sorted by depth with other phase items
             let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
 
+            let single_channel = gpu_images
+                .get(extracted_sprite.image_handle_id)
+                .is_some_and(|gpu_image| gpu_image.texture_format.components() == 1);
+
             // Add the item to the render phase
             transparent_phase.add(Transparent2d {
                 draw_function: draw_sprite_function,
-                pipeline,
+                pipeline: if single_channel {
+                    single_channel_pipeline
+                } else {
+                    pipeline
+                },
                 entity: *entity,
                 sort_key,
                 // batch_range and dynamic_offset will be calculated in prepare_sprites
@@ -53,7 +53,12 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+    var texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+#ifdef SINGLE_CHANNEL_TEXTURE
+    // Single channel textures, like font atlases storing the coverage of glyphs, are white with the channel as alpha.
+    texture_color = vec4<f32>(1.0, 1.0, 1.0, texture_color.r);
+#endif
+    var color = in.color * texture_color;
 
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
@@ -1,4 +1,8 @@
-use ab_glyph::{FontArc, FontVec, InvalidFont, OutlinedGlyph};
+use crate::FontLoaderSettings;
+use ab_glyph::{
+    point, v2::GlyphImage, Font as _, FontArc, FontVec, Glyph, GlyphImageFormat, InvalidFont,
+    OutlinedGlyph, Rect, ScaleFont,
+};
 use bevy_asset::Asset;
 use bevy_reflect::TypePath;
 use bevy_render::{
@@ -7,16 +11,31 @@ use bevy_render::{
     texture::Image,
 };
 
+/// The font size, in pixels, at which glyphs drawn with [`FontRenderMode::Sdf`](crate::FontRenderMode::Sdf) are
+/// rasterized.
+pub const SDF_FONT_SIZE: f32 = 48.;
+
+/// The distance from the outline, in pixels at [`SDF_FONT_SIZE`], over which the signed distance fields of
+/// glyphs go from fully inside to fully outside.
+///
+/// This is also the width of the padding around each signed distance field glyph texture.
+pub const SDF_SPREAD: u32 = 6;
+
 #[derive(Asset, TypePath, Debug, Clone)]
 pub struct Font {
     pub font: FontArc,
+    /// The glyphs added to the glyph cache once the font has loaded.
+    pub(crate) preload: FontLoaderSettings,
 }
 
 impl Font {
     pub fn try_from_bytes(font_data: Vec<u8>) -> Result<Self, InvalidFont> {
         let font = FontVec::try_from_vec(font_data)?;
         let font = FontArc::new(font);
-        Ok(Font { font })
+        Ok(Font {
+            font,
+            preload: FontLoaderSettings::default(),
+        })
     }
 
     pub fn get_outlined_glyph_texture(outlined_glyph: OutlinedGlyph) -> Image {
@@ -50,4 +69,230 @@ impl Font {
             RenderAssetUsages::MAIN_WORLD,
         )
     }
+
+    /// Rasterizes a glyph into a signed distance field texture, padded by [`SDF_SPREAD`] pixels on each side.
+    ///
+    /// The alpha channel stores the distance to the glyph's outline, mapped so that `0.5` is on the outline,
+    /// `1.0` is [`SDF_SPREAD`] pixels inside of it and `0.0` is [`SDF_SPREAD`] pixels outside of it.
+    pub fn get_outlined_glyph_sdf(outlined_glyph: OutlinedGlyph) -> Image {
+        let bounds = outlined_glyph.px_bounds();
+        let spread = SDF_SPREAD as usize;
+        let width = bounds.width() as usize + 2 * spread;
+        let height = bounds.height() as usize + 2 * spread;
+        let mut coverage = vec![0.0; width * height];
+        outlined_glyph.draw(|x, y, v| {
+            coverage[(y as usize + spread) * width + x as usize + spread] = v;
+        });
+
+        let inside = |x: usize, y: usize| 0.5 <= coverage[y * width + x];
+        let alpha = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let v = coverage[y * width + x];
+                // Signed distance to the outline in pixels, negative inside of the glyph
+                let distance = if 0. < v && v < 1. {
+                    // Pixels on the outline are covered in proportion to how far inside of the glyph they are
+                    0.5 - v
+                } else {
+                    // Search the nearest pixel on the other side of the outline, within the spread
+                    let is_inside = inside(x, y);
+                    // Pixels further than the spread from the outline are clamped
+                    let mut nearest_squared = ((spread + 1) * (spread + 1)) as f32;
+                    for sy in y.saturating_sub(spread)..(y + spread + 1).min(height) {
+                        for sx in x.saturating_sub(spread)..(x + spread + 1).min(width) {
+                            if inside(sx, sy) != is_inside {
+                                let dx = sx as f32 - x as f32;
+                                let dy = sy as f32 - y as f32;
+                                nearest_squared = nearest_squared.min(dx * dx + dy * dy);
+                            }
+                        }
+                    }
+                    // The outline is between the centers of the two pixels
+                    let distance = nearest_squared.sqrt() - 0.5;
+                    if is_inside {
+                        -distance
+                    } else {
+                        distance
+                    }
+                };
+                (0.5 - distance / (2 * spread) as f32).clamp(0., 1.)
+            });
+
+        Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            alpha
+                .flat_map(|a| [255, 255, 255, (a * 255.0).round() as u8])
+                .collect::<Vec<u8>>(),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        )
+    }
+
+    /// Returns the pixel bounds of `glyph`, from its color image if it has one, or from its outline otherwise.
+    pub fn glyph_bounds(&self, glyph: Glyph) -> Option<Rect> {
+        match self.color_glyph_bounds(&glyph) {
+            Some(bounds) => Some(bounds),
+            None => Some(self.font.outline_glyph(glyph)?.px_bounds()),
+        }
+    }
+
+    /// Returns the pixel bounds of the color image of `glyph` scaled to its font size, if the font has one.
+    ///
+    /// See [`Font::get_color_glyph_texture`].
+    pub fn color_glyph_bounds(&self, glyph: &Glyph) -> Option<Rect> {
+        let (image, scale) = self.color_glyph_image(glyph)?;
+        Some(color_image_bounds(glyph, &image, scale))
+    }
+
+    /// Rasterizes the color image of `glyph`, like an emoji, scaled to its font size.
+    ///
+    /// Returns `None` if the font has no color image for the glyph. Images stored as PNGs are only decoded with
+    /// the `color_glyphs` feature. Like [`Font::get_outlined_glyph_texture`], the texture has a pixel wide
+    /// transparent border along its edges.
+    pub fn get_color_glyph_texture(&self, glyph: &Glyph) -> Option<Image> {
+        let (image, scale) = self.color_glyph_image(glyph)?;
+        let bounds = color_image_bounds(glyph, &image, scale);
+        let (image_width, image_height, pixels) = decode_color_image(&image)?;
+        let (width, height) = (bounds.width() as usize, bounds.height() as usize);
+        let pixels = resample(&pixels, image_width, image_height, width, height);
+
+        let padded_width = width + 2;
+        let mut data = vec![0; padded_width * (height + 2) * 4];
+        for (y, row) in pixels.chunks_exact(width * 4).enumerate() {
+            let start = ((y + 1) * padded_width + 1) * 4;
+            data[start..start + row.len()].copy_from_slice(row);
+        }
+        Some(Image::new(
+            Extent3d {
+                width: padded_width as u32,
+                height: height as u32 + 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        ))
+    }
+
+    /// Returns the color image of `glyph` in a format that can be decoded, and its scale to the glyph's font size.
+    fn color_glyph_image(&self, glyph: &Glyph) -> Option<(GlyphImage<'_>, f32)> {
+        let scale_factor = self.font.as_scaled(glyph.scale).scale_factor();
+        let pixels_per_em = scale_factor.vertical * self.font.units_per_em()?;
+        let image = self
+            .font
+            .glyph_raster_image2(glyph.id, pixels_per_em.round() as u16)?;
+        match image.format {
+            #[cfg(feature = "color_glyphs")]
+            GlyphImageFormat::Png => {}
+            GlyphImageFormat::BitmapPremulBgra32 => {}
+            _ => return None,
+        }
+        let scale = pixels_per_em / image.pixels_per_em.max(1) as f32;
+        Some((image, scale))
+    }
+}
+
+/// The pixel bounds of the color `image` of `glyph`, drawn at `scale`.
+///
+/// The origin of the image is the offset of its bottom left corner from the glyph's position on the baseline,
+/// with y pointing up.
+fn color_image_bounds(glyph: &Glyph, image: &GlyphImage, scale: f32) -> Rect {
+    let width = (image.width as f32 * scale).round().max(1.);
+    let height = (image.height as f32 * scale).round().max(1.);
+    let min_x = (glyph.position.x + image.origin.x * scale).round();
+    let max_y = (glyph.position.y - image.origin.y * scale).round();
+    Rect {
+        min: point(min_x, max_y - height),
+        max: point(min_x + width, max_y),
+    }
+}
+
+/// Decodes a color glyph image into its width, height and RGBA pixels with straight alpha.
+fn decode_color_image(image: &GlyphImage) -> Option<(usize, usize, Vec<u8>)> {
+    match image.format {
+        #[cfg(feature = "color_glyphs")]
+        GlyphImageFormat::Png => {
+            let decoded = image::load_from_memory_with_format(image.data, image::ImageFormat::Png)
+                .ok()?
+                .into_rgba8();
+            Some((
+                decoded.width() as usize,
+                decoded.height() as usize,
+                decoded.into_raw(),
+            ))
+        }
+        GlyphImageFormat::BitmapPremulBgra32 => {
+            let (width, height) = (image.width as usize, image.height as usize);
+            let pixels = image
+                .data
+                .get(..width * height * 4)?
+                .chunks_exact(4)
+                .flat_map(|pixel| {
+                    let [b, g, r, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+                    let unpremultiply = |c: u8| match a {
+                        0 => 0,
+                        _ => (c as u32 * 255 / a as u32).min(255) as u8,
+                    };
+                    [unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+                })
+                .collect();
+            Some((width, height, pixels))
+        }
+        _ => None,
+    }
+}
+
+/// Resamples RGBA `pixels` with straight alpha to `width` by `height`, averaging the premultiplied colors of the
+/// source pixels covered by each pixel.
+fn resample(
+    pixels: &[u8],
+    source_width: usize,
+    source_height: usize,
+    width: usize,
+    height: usize,
+) -> Vec<u8> {
+    let scale_x = source_width as f32 / width as f32;
+    let scale_y = source_height as f32 / height as f32;
+    // The source pixels covered by `start..end` along an axis, and how much of each is covered
+    let coverage = |start: f32, end: f32, len: usize| {
+        (start as usize..(end.ceil() as usize).min(len)).map(move |i| {
+            let covered = end.min(i as f32 + 1.) - start.max(i as f32);
+            (i, covered.max(0.))
+        })
+    };
+    let mut resampled = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        let (top, bottom) = (y as f32 * scale_y, (y + 1) as f32 * scale_y);
+        for x in 0..width {
+            let (left, right) = (x as f32 * scale_x, (x + 1) as f32 * scale_x);
+            let mut sum = [0.; 4];
+            let mut area = 0.;
+            for (sy, covered_y) in coverage(top, bottom, source_height) {
+                for (sx, covered_x) in coverage(left, right, source_width) {
+                    let weight = covered_x * covered_y;
+                    let pixel = &pixels[(sy * source_width + sx) * 4..][..4];
+                    let alpha = pixel[3] as f32 / 255. * weight;
+                    for (sum, &c) in sum.iter_mut().zip(&pixel[..3]) {
+                        *sum += c as f32 * alpha;
+                    }
+                    sum[3] += alpha;
+                    area += weight;
+                }
+            }
+            if sum[3] <= 0. {
+                resampled.extend([0; 4]);
+                continue;
+            }
+            let [r, g, b] = [0, 1, 2].map(|c| (sum[c] / sum[3]).round() as u8);
+            let a = (sum[3] / area * 255.).round() as u8;
+            resampled.extend([r, g, b, a]);
+        }
+    }
+    resampled
 }
@@ -4,23 +4,39 @@ use bevy_math::UVec2;
 use bevy_render::{
     render_asset::RenderAssetUsages,
     render_resource::{Extent3d, TextureDimension, TextureFormat},
-    texture::Image,
+    texture::{Image, TextureFormatPixelInfo},
 };
 use bevy_sprite::{DynamicTextureAtlasBuilder, TextureAtlasLayout};
 use bevy_utils::HashMap;
 
+/// The number of sub-pixel positions along each axis that glyphs are rasterized at.
+///
+/// Glyph positions are snapped to the nearest of these positions, so that each glyph is cached at most
+/// `SUBPIXEL_BINS * SUBPIXEL_BINS` times per font size.
+#[cfg(feature = "subpixel_glyph_atlas")]
+pub const SUBPIXEL_BINS: u8 = 4;
+
+/// The sub-pixel bin of a glyph's position along each axis.
 #[cfg(feature = "subpixel_glyph_atlas")]
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct SubpixelOffset {
-    x: u16,
-    y: u16,
+    x: u8,
+    y: u8,
+}
+
+#[cfg(feature = "subpixel_glyph_atlas")]
+impl SubpixelOffset {
+    /// Snaps a coordinate to the nearest sub-pixel bin.
+    pub fn snap(v: f32) -> f32 {
+        (v * SUBPIXEL_BINS as f32).round() / SUBPIXEL_BINS as f32
+    }
 }
 
 #[cfg(feature = "subpixel_glyph_atlas")]
 impl From<Point> for SubpixelOffset {
     fn from(p: Point) -> Self {
-        fn f(v: f32) -> u16 {
-            ((v % 1.) * (u16::MAX as f32)) as u16
+        fn f(v: f32) -> u8 {
+            (v.rem_euclid(1.) * SUBPIXEL_BINS as f32).round() as u8 % SUBPIXEL_BINS
         }
         Self {
             x: f(p.x),
@@ -54,13 +70,48 @@ pub struct FontAtlas {
     pub glyph_to_atlas_index: HashMap<PlacedGlyph, usize>,
     pub texture_atlas: Handle<TextureAtlasLayout>,
     pub texture: Handle<Image>,
+    /// The area in pixels of the glyphs added to the atlas
+    pub(crate) glyph_area: u64,
+    /// The format of the atlas texture
+    format: TextureFormat,
+    /// Whether the atlas stores color glyphs instead of the coverage of glyphs
+    color: bool,
 }
 
 impl FontAtlas {
+    /// Creates an atlas for glyphs stored as their coverage, in a texture of `size` and `format`.
     pub fn new(
         textures: &mut Assets<Image>,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         size: UVec2,
+        format: TextureFormat,
+    ) -> FontAtlas {
+        Self::with_kind(textures, texture_atlases, size, format, false)
+    }
+
+    /// Creates an atlas for color glyphs, like emoji, in an [`TextureFormat::Rgba8UnormSrgb`] texture of `size`.
+    ///
+    /// See [`Font::get_color_glyph_texture`](crate::Font::get_color_glyph_texture).
+    pub fn new_color(
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        size: UVec2,
+    ) -> FontAtlas {
+        Self::with_kind(
+            textures,
+            texture_atlases,
+            size,
+            TextureFormat::Rgba8UnormSrgb,
+            true,
+        )
+    }
+
+    fn with_kind(
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        size: UVec2,
+        format: TextureFormat,
+        color: bool,
     ) -> FontAtlas {
         let texture = textures.add(Image::new_fill(
             Extent3d {
@@ -70,7 +121,7 @@ impl FontAtlas {
             },
             TextureDimension::D2,
             &[0, 0, 0, 0],
-            TextureFormat::Rgba8UnormSrgb,
+            format,
             // Need to keep this image CPU persistent in order to add additional glyphs later on
             RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
         ));
@@ -80,9 +131,17 @@ impl FontAtlas {
             glyph_to_atlas_index: HashMap::default(),
             dynamic_texture_atlas_builder: DynamicTextureAtlasBuilder::new(size, 0),
             texture,
+            glyph_area: 0,
+            format,
+            color,
         }
     }
 
+    /// Returns `true` if the atlas stores color glyphs, see [`FontAtlas::new_color`].
+    pub fn is_color(&self) -> bool {
+        self.color
+    }
+
     pub fn get_glyph_index(&self, glyph: &PlacedGlyph) -> Option<usize> {
         self.glyph_to_atlas_index.get(glyph).copied()
     }
@@ -91,6 +150,38 @@ impl FontAtlas {
         self.glyph_to_atlas_index.contains_key(glyph)
     }
 
+    /// The size of the atlas texture.
+    pub fn size(&self) -> UVec2 {
+        self.dynamic_texture_atlas_builder.size()
+    }
+
+    /// The number of bytes used by the atlas texture.
+    pub fn texture_bytes(&self) -> usize {
+        self.format.pixel_size() * self.size().element_product() as usize
+    }
+
+    /// The fraction of the atlas texture covered by glyphs, from `0` to `1`.
+    pub fn occupancy(&self) -> f32 {
+        self.glyph_area as f32 / self.size().element_product() as f32
+    }
+
+    /// Grow the atlas texture and layout to `size`, keeping the glyphs already added in place.
+    pub fn grow(
+        &mut self,
+        textures: &mut Assets<Image>,
+        atlas_layouts: &mut Assets<TextureAtlasLayout>,
+        size: UVec2,
+    ) {
+        let (Some(atlas_layout), Some(atlas_texture)) = (
+            atlas_layouts.get_mut(&self.texture_atlas),
+            textures.get_mut(&self.texture),
+        ) else {
+            return;
+        };
+        self.dynamic_texture_atlas_builder
+            .grow(size, atlas_layout, atlas_texture);
+    }
+
     /// Add a glyph to the atlas, updating both its texture and layout.
     ///
     /// The glyph is represented by `glyph`, and its image content is `glyph_texture`.
@@ -121,6 +212,7 @@ impl FontAtlas {
             atlas_texture,
         ) {
             self.glyph_to_atlas_index.insert(*glyph, index);
+            self.glyph_area += glyph_texture.width() as u64 * glyph_texture.height() as u64;
             true
         } else {
             false
@@ -0,0 +1,51 @@
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+
+use crate::FontAtlasSets;
+
+/// Adds diagnostics for the font atlases to an App: the number of atlas textures, the bytes they use and the
+/// percentage of their area covered by glyphs, and the number of glyph overflows and font size evictions in each
+/// frame.
+///
+/// See [`FontAtlasOverflow`](crate::FontAtlasOverflow) to control what happens when an atlas overflows.
+#[derive(Default)]
+pub struct FontAtlasDiagnosticsPlugin;
+
+impl Plugin for FontAtlasDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ATLAS_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::TEXTURE_BYTES))
+            .register_diagnostic(Diagnostic::new(Self::OCCUPANCY).with_suffix("%"))
+            .register_diagnostic(Diagnostic::new(Self::OVERFLOWS))
+            .register_diagnostic(Diagnostic::new(Self::EVICTIONS))
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl FontAtlasDiagnosticsPlugin {
+    pub const ATLAS_COUNT: DiagnosticPath = DiagnosticPath::const_new("font_atlas/atlas_count");
+    pub const TEXTURE_BYTES: DiagnosticPath = DiagnosticPath::const_new("font_atlas/texture_bytes");
+    pub const OCCUPANCY: DiagnosticPath = DiagnosticPath::const_new("font_atlas/occupancy");
+    pub const OVERFLOWS: DiagnosticPath = DiagnosticPath::const_new("font_atlas/overflows");
+    pub const EVICTIONS: DiagnosticPath = DiagnosticPath::const_new("font_atlas/evictions");
+
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        font_atlas_sets: Res<FontAtlasSets>,
+        mut last_counts: Local<(u64, u64)>,
+    ) {
+        let overflows = font_atlas_sets.overflow_count();
+        let evictions = font_atlas_sets.eviction_count();
+        diagnostics.add_measurement(&Self::ATLAS_COUNT, || font_atlas_sets.atlas_count() as f64);
+        diagnostics.add_measurement(&Self::TEXTURE_BYTES, || {
+            font_atlas_sets.texture_bytes() as f64
+        });
+        diagnostics.add_measurement(&Self::OCCUPANCY, || {
+            100. * font_atlas_sets.occupancy() as f64
+        });
+        diagnostics.add_measurement(&Self::OVERFLOWS, || (overflows - last_counts.0) as f64);
+        diagnostics.add_measurement(&Self::EVICTIONS, || (evictions - last_counts.1) as f64);
+        *last_counts = (overflows, evictions);
+    }
+}
@@ -1,27 +1,189 @@
-use crate::{error::TextError, Font, FontAtlas, PlacedGlyph};
-use ab_glyph::{GlyphId, OutlinedGlyph, Point};
+use crate::{
+    error::TextError, scale_value, Font, FontAtlas, FontAtlasOverflow, PlacedGlyph, Text,
+    TextLayoutInfo, TextPipeline, TextSettings,
+};
+use ab_glyph::{point, Font as _, Glyph, GlyphId, OutlinedGlyph, Point};
 use bevy_asset::{AssetEvent, AssetId};
 use bevy_asset::{Assets, Handle};
+use bevy_color::{Alpha, LinearRgba};
 use bevy_ecs::prelude::*;
 use bevy_math::{FloatOrd, UVec2};
 use bevy_reflect::Reflect;
-use bevy_render::texture::Image;
+use bevy_render::{render_resource::TextureFormat, texture::Image};
 use bevy_sprite::TextureAtlasLayout;
-use bevy_utils::HashMap;
+use bevy_utils::{tracing::warn, warn_once, HashMap, HashSet};
+use bevy_window::{PrimaryWindow, Window};
 
 type FontSizeKey = FloatOrd;
 
+/// The glyph cache, shared by UI text and [`Text2dBundle`](crate::Text2dBundle)s.
+///
+/// Glyphs are rasterized once for each font, font size and, with the `subpixel_glyph_atlas` feature, sub-pixel
+/// bin, and packed into the atlases of a [`FontAtlasSet`] for the font.
 #[derive(Default, Resource)]
 pub struct FontAtlasSets {
     // PERF: in theory this could be optimized with Assets storage ... consider making some fast "simple" AssetMap
     pub(crate) sets: HashMap<AssetId<Font>, FontAtlasSet>,
+    /// Overflows and evictions of the sets that have been removed
+    removed_overflow_count: u64,
+    removed_eviction_count: u64,
+    /// The number of times [`evict_font_atlases`] has run, used to order the font sizes of all the sets by when
+    /// they were last used
+    frame: u64,
 }
 
 impl FontAtlasSets {
+    /// Removes the set of the font `id`, keeping its overflows and evictions in the totals.
+    fn remove(&mut self, id: AssetId<Font>) {
+        if let Some(set) = self.sets.remove(&id) {
+            self.removed_overflow_count += set.overflow_count;
+            self.removed_eviction_count += set.eviction_count;
+        }
+    }
+
     pub fn get(&self, id: impl Into<AssetId<Font>>) -> Option<&FontAtlasSet> {
         let id: AssetId<Font> = id.into();
         self.sets.get(&id)
     }
+
+    /// Returns the set of the font `id`, adding it if it doesn't exist, to look up or add glyphs in the current
+    /// frame.
+    pub(crate) fn entry(&mut self, id: AssetId<Font>) -> &mut FontAtlasSet {
+        let font_atlas_set = self.sets.entry(id).or_default();
+        font_atlas_set.frame = self.frame;
+        font_atlas_set
+    }
+
+    /// Adds the glyphs for each of the `chars` in `font` at `font_size` in physical pixels to the cache, so that
+    /// they don't have to be rasterized when text using them is first laid out.
+    ///
+    /// Returns the number of glyphs that were added. Glyphs that are already cached and characters without
+    /// outlines or color images, like whitespace, are skipped.
+    ///
+    /// See [`FontAtlasPrewarm`] to pre-warm the cache once fonts have loaded.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prewarm(
+        &mut self,
+        font_id: impl Into<AssetId<Font>>,
+        font: &Font,
+        font_size: f32,
+        chars: impl IntoIterator<Item = char>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        settings: &TextSettings,
+    ) -> Result<usize, TextError> {
+        let font_atlas_set = self.entry(font_id.into());
+        let mut added = 0;
+        for c in chars {
+            let glyph = font
+                .font
+                .glyph_id(c)
+                .with_scale_and_position(font_size, ab_glyph::point(0., 0.));
+            if font_atlas_set.has_glyph(glyph.id, glyph.position, font_size) {
+                continue;
+            }
+            if let Some(glyph_texture) = font.get_color_glyph_texture(&glyph) {
+                font_atlas_set.add_color_glyph_to_atlas(
+                    texture_atlases,
+                    textures,
+                    &glyph,
+                    glyph_texture,
+                    settings,
+                )?;
+                added += 1;
+            } else if let Some(outlined_glyph) = font.font.outline_glyph(glyph) {
+                font_atlas_set.add_glyph_to_atlas(
+                    texture_atlases,
+                    textures,
+                    outlined_glyph,
+                    settings,
+                )?;
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Returns the total number of font atlas textures.
+    pub fn atlas_count(&self) -> usize {
+        self.sets.values().map(FontAtlasSet::atlas_count).sum()
+    }
+
+    /// Returns the number of bytes used by all the font atlas textures.
+    pub fn texture_bytes(&self) -> usize {
+        self.sets.values().map(FontAtlasSet::texture_bytes).sum()
+    }
+
+    /// Returns the fraction of the area of all the font atlas textures that is covered by glyphs, from `0` to `1`.
+    ///
+    /// Returns `0` if there are no atlases.
+    pub fn occupancy(&self) -> f32 {
+        occupancy(self.sets.values().flat_map(FontAtlasSet::atlases))
+    }
+
+    /// Returns `true` if a font has more than `max_font_sizes` sizes, or the font atlas textures use more than
+    /// `max_bytes`.
+    fn exceeds(&self, max_font_sizes: usize, max_bytes: Option<usize>) -> bool {
+        self.sets.values().any(|set| max_font_sizes < set.len())
+            || max_bytes.is_some_and(|max_bytes| max_bytes < self.texture_bytes())
+    }
+
+    /// Removes the atlases of the least recently used font sizes until each font has at most `max_font_sizes`
+    /// sizes, and the font atlas textures of all fonts use at most `max_bytes`.
+    ///
+    /// Font sizes used in the current frame, and atlases with textures in `in_use`, are kept, so the limits can be
+    /// exceeded when the text being laid out needs more. The atlases of signed distance field glyphs are never
+    /// evicted.
+    ///
+    /// Returns `true` if the atlases are within the limits.
+    pub fn evict_least_recently_used(
+        &mut self,
+        max_font_sizes: usize,
+        max_bytes: Option<usize>,
+        in_use: &HashSet<AssetId<Image>>,
+    ) -> bool {
+        let mut texture_bytes = self.texture_bytes();
+        loop {
+            let over_max_bytes = max_bytes.is_some_and(|max_bytes| max_bytes < texture_bytes);
+            let Some((font_id, font_size)) = self
+                .sets
+                .iter()
+                .filter(|(_, set)| over_max_bytes || max_font_sizes < set.len())
+                .filter_map(|(font_id, set)| {
+                    set.least_recently_used(self.frame, in_use)
+                        .map(|(font_size, last_used)| (last_used, *font_id, font_size))
+                })
+                .min_by_key(|(last_used, ..)| *last_used)
+                .map(|(_, font_id, font_size)| (font_id, font_size))
+            else {
+                break;
+            };
+            texture_bytes -= self.sets.get_mut(&font_id).unwrap().evict(font_size);
+        }
+        !self.exceeds(max_font_sizes, max_bytes)
+    }
+
+    /// Returns the number of times a glyph didn't fit in the existing atlases for its font and size,
+    /// since the app started.
+    pub fn overflow_count(&self) -> u64 {
+        self.removed_overflow_count
+            + self
+                .sets
+                .values()
+                .map(|set| set.overflow_count)
+                .sum::<u64>()
+    }
+
+    /// Returns the number of font sizes evicted by [`FontAtlasOverflow::EvictLeastRecentlyUsed`],
+    /// since the app started.
+    pub fn eviction_count(&self) -> u64 {
+        self.removed_eviction_count
+            + self
+                .sets
+                .values()
+                .map(|set| set.eviction_count)
+                .sum::<u64>()
+    }
 }
 
 pub fn remove_dropped_font_atlas_sets(
@@ -31,13 +193,187 @@ pub fn remove_dropped_font_atlas_sets(
     // Clean up font atlas sets for removed fonts
     for event in font_events.read() {
         if let AssetEvent::Removed { id } = event {
-            font_atlas_sets.sets.remove(id);
+            font_atlas_sets.remove(*id);
         }
     }
 }
 
+/// With [`FontAtlasOverflow::EvictLeastRecentlyUsed`], evicts the least recently used font sizes at the end of
+/// each frame while a font has more sizes than [`TextSettings::soft_max_font_atlases`], or the font atlas textures
+/// use more than its `max_bytes`.
+///
+/// Font sizes used in the frame, and atlases with glyphs in the [`TextLayoutInfo`] of any text, are never evicted,
+/// so no text has to be laid out again. The evicted textures are freed after they are rendered in this frame.
+pub fn evict_font_atlases(
+    mut font_atlas_sets: ResMut<FontAtlasSets>,
+    text_settings: Res<TextSettings>,
+    text_layouts: Query<&TextLayoutInfo>,
+) {
+    let max_font_sizes = text_settings.soft_max_font_atlases.get();
+    if let FontAtlasOverflow::EvictLeastRecentlyUsed { max_bytes } = text_settings.atlas_overflow {
+        if font_atlas_sets.exceeds(max_font_sizes, max_bytes) {
+            let in_use = text_layouts
+                .iter()
+                .flat_map(|text_layout| &text_layout.glyphs)
+                .map(|glyph| glyph.atlas_info.texture.id())
+                .collect::<HashSet<_>>();
+            if !font_atlas_sets.evict_least_recently_used(max_font_sizes, max_bytes, &in_use) {
+                warn_once!(
+                    "The font sizes in use exceed `TextSettings::soft_max_font_atlases` or the `max_bytes` of `FontAtlasOverflow::EvictLeastRecentlyUsed`, so they can't be evicted"
+                );
+            }
+        }
+    }
+    font_atlas_sets.frame += 1;
+}
+
+/// Discards the glyphs cached for fonts that were modified, e.g. by hot-reloading, and lays out the [`Text`]s
+/// using them again with the modified font.
+///
+/// The atlases of other fonts are kept.
+pub fn reload_modified_fonts(
+    mut font_atlas_sets: ResMut<FontAtlasSets>,
+    mut text_pipeline: ResMut<TextPipeline>,
+    fonts: Res<Assets<Font>>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    mut texts: Query<&mut Text>,
+) {
+    let mut modified = HashSet::new();
+    for event in font_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            font_atlas_sets.remove(*id);
+            if let Some(font) = fonts.get(*id) {
+                text_pipeline.reload_font(*id, font);
+            }
+            modified.insert(*id);
+        }
+    }
+    if modified.is_empty() {
+        return;
+    }
+    for mut text in &mut texts {
+        if text
+            .sections
+            .iter()
+            .any(|section| modified.contains(&section.style.font.id()))
+        {
+            text.set_changed();
+        }
+    }
+}
+
+/// Characters to add to the glyph cache in [`FontAtlasSets`] before they are first used, to avoid hitches when
+/// text with new glyphs appears.
+///
+/// Each request is handled by [`prewarm_font_atlases`] once its font has loaded. Font sizes are in logical pixels
+/// and are scaled by the primary window's scale factor, so UI text with a `UiScale` other than `1`, or text on
+/// other windows, may use glyphs of a different size.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_text::{Font, FontAtlasPrewarm};
+/// # let font: Handle<Font> = Handle::default();
+/// let mut prewarm = FontAtlasPrewarm::default();
+/// prewarm.add(font, 24., FontAtlasPrewarm::ASCII);
+/// ```
+#[derive(Default, Resource)]
+pub struct FontAtlasPrewarm {
+    pending: Vec<(Handle<Font>, f32, String)>,
+}
+
+impl FontAtlasPrewarm {
+    /// The printable ASCII characters.
+    pub const ASCII: &'static str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+    /// Requests that the glyphs for `chars` in `font` at `font_size` are added to the glyph cache.
+    pub fn add(&mut self, font: Handle<Font>, font_size: f32, chars: impl Into<String>) {
+        self.pending.push((font, font_size, chars.into()));
+    }
+
+    /// Returns `true` if there are no requests waiting for their fonts to load.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Adds the glyphs requested with [`FontAtlasPrewarm`] to the glyph cache once their fonts have loaded, and the
+/// glyphs requested by the [`FontLoaderSettings`](crate::FontLoaderSettings) of fonts as they are loaded.
+#[allow(clippy::too_many_arguments)]
+pub fn prewarm_font_atlases(
+    mut prewarm: ResMut<FontAtlasPrewarm>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    fonts: Res<Assets<Font>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    text_settings: Res<TextSettings>,
+    mut font_atlas_sets: ResMut<FontAtlasSets>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut textures: ResMut<Assets<Image>>,
+) {
+    let loaded = font_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .filter(|id| {
+            fonts
+                .get(*id)
+                .is_some_and(|font| !font.preload.preload_sizes.is_empty())
+        })
+        .collect::<Vec<_>>();
+    if prewarm.is_empty() && loaded.is_empty() {
+        return;
+    }
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.0);
+    let mut prewarm_font = |font_id: AssetId<Font>, font: &Font, font_size: f32, chars: &str| {
+        if let Err(error) = font_atlas_sets.prewarm(
+            font_id,
+            font,
+            scale_value(font_size, scale_factor),
+            chars.chars(),
+            &mut texture_atlases,
+            &mut textures,
+            &text_settings,
+        ) {
+            warn!("Failed to pre-warm the glyph cache: {error}");
+        }
+    };
+
+    for font_id in loaded {
+        let font = fonts.get(font_id).unwrap();
+        let chars = font
+            .preload
+            .preload_chars
+            .as_deref()
+            .unwrap_or(FontAtlasPrewarm::ASCII);
+        for &font_size in &font.preload.preload_sizes {
+            prewarm_font(font_id, font, font_size, chars);
+        }
+    }
+
+    prewarm.pending.retain(|(font_handle, font_size, chars)| {
+        let Some(font) = fonts.get(font_handle) else {
+            return true;
+        };
+        prewarm_font(font_handle.id(), font, *font_size, chars);
+        false
+    });
+}
+
 pub struct FontAtlasSet {
     font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+    /// The atlases of the signed distance field glyphs, shared by all font sizes
+    sdf_atlases: Vec<FontAtlas>,
+    /// The frame and value of `use_count` when each font size was last used
+    last_used: HashMap<FontSizeKey, (u64, u64)>,
+    use_count: u64,
+    /// The current frame of the [`FontAtlasSets`]
+    frame: u64,
+    overflow_count: u64,
+    eviction_count: u64,
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -45,12 +381,34 @@ pub struct GlyphAtlasInfo {
     pub texture_atlas: Handle<TextureAtlasLayout>,
     pub texture: Handle<Image>,
     pub glyph_index: usize,
+    /// The glyph is a color image, like an emoji, stored in an atlas for color glyphs.
+    pub is_color: bool,
+}
+
+impl GlyphAtlasInfo {
+    /// Returns the color to draw the glyph with for text of `color`.
+    ///
+    /// Glyphs stored as their coverage are drawn in `color`, color glyphs in their own colors, with the alpha of
+    /// `color`.
+    pub fn tint(&self, color: LinearRgba) -> LinearRgba {
+        if self.is_color {
+            LinearRgba::WHITE.with_alpha(color.alpha())
+        } else {
+            color
+        }
+    }
 }
 
 impl Default for FontAtlasSet {
     fn default() -> Self {
         FontAtlasSet {
             font_atlases: HashMap::with_capacity_and_hasher(1, Default::default()),
+            sdf_atlases: Vec::new(),
+            last_used: HashMap::with_capacity_and_hasher(1, Default::default()),
+            use_count: 0,
+            frame: 0,
+            overflow_count: 0,
+            eviction_count: 0,
         }
     }
 }
@@ -74,11 +432,16 @@ impl FontAtlasSet {
             })
     }
 
+    /// Adds a glyph to the atlases for its font size.
+    ///
+    /// If the glyph doesn't fit in any of the existing atlases, [`TextSettings::atlas_overflow`] decides whether
+    /// the last atlas is grown or a new atlas is added.
     pub fn add_glyph_to_atlas(
         &mut self,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         textures: &mut Assets<Image>,
         outlined_glyph: OutlinedGlyph,
+        settings: &TextSettings,
     ) -> Result<GlyphAtlasInfo, TextError> {
         let glyph = outlined_glyph.glyph();
         let placed_glyph = PlacedGlyph {
@@ -89,37 +452,260 @@ impl FontAtlasSet {
         let font_atlases = self
             .font_atlases
             .entry(FloatOrd(font_size))
-            .or_insert_with(|| vec![FontAtlas::new(textures, texture_atlases, UVec2::splat(512))]);
+            .or_insert_with(|| Self::new_atlases(textures, texture_atlases, settings));
 
-        let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
+        let glyph_texture = coverage_texture(
+            Font::get_outlined_glyph_texture(outlined_glyph),
+            settings.atlas_texture_format(),
+        );
+        Self::add_glyph_texture(
+            font_atlases,
+            texture_atlases,
+            textures,
+            &placed_glyph,
+            &glyph_texture,
+            false,
+            settings,
+            &mut self.overflow_count,
+        )?;
+
+        Ok(self.get_glyph_atlas_info(font_size, &placed_glyph).unwrap())
+    }
+
+    /// Adds a color glyph, like an emoji, rasterized by [`Font::get_color_glyph_texture`] to the color atlases for
+    /// its font size.
+    ///
+    /// Color glyphs are stored in [`FontAtlas::new_color`] atlases, in the same way as
+    /// [`FontAtlasSet::add_glyph_to_atlas`] stores other glyphs.
+    pub fn add_color_glyph_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        glyph: &Glyph,
+        glyph_texture: Image,
+        settings: &TextSettings,
+    ) -> Result<GlyphAtlasInfo, TextError> {
+        let placed_glyph = PlacedGlyph {
+            glyph_id: glyph.id,
+            subpixel_offset: glyph.position.into(),
+        };
+        let font_size = glyph.scale.y;
+        let font_atlases = self
+            .font_atlases
+            .entry(FloatOrd(font_size))
+            .or_insert_with(|| Self::new_atlases(textures, texture_atlases, settings));
+        Self::add_glyph_texture(
+            font_atlases,
+            texture_atlases,
+            textures,
+            &placed_glyph,
+            &glyph_texture,
+            true,
+            settings,
+            &mut self.overflow_count,
+        )?;
+
+        Ok(self.get_glyph_atlas_info(font_size, &placed_glyph).unwrap())
+    }
+
+    /// Returns `true` if the signed distance field of the glyph is in the atlases.
+    pub fn has_sdf_glyph(&self, glyph_id: GlyphId) -> bool {
+        let placed_glyph = PlacedGlyph {
+            glyph_id,
+            subpixel_offset: point(0., 0.).into(),
+        };
+        self.sdf_atlases
+            .iter()
+            .any(|atlas| atlas.has_glyph(&placed_glyph))
+    }
+
+    /// Adds the signed distance field of a glyph, outlined at [`SDF_FONT_SIZE`](crate::SDF_FONT_SIZE), to the atlases for
+    /// [`FontRenderMode::Sdf`](crate::FontRenderMode::Sdf).
+    ///
+    /// If the glyph doesn't fit in any of the existing atlases, [`TextSettings::atlas_overflow`] decides whether
+    /// the last atlas is grown or a new atlas is added. These atlases are never evicted.
+    pub fn add_sdf_glyph_to_atlas(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        outlined_glyph: OutlinedGlyph,
+        settings: &TextSettings,
+    ) -> Result<GlyphAtlasInfo, TextError> {
+        let glyph_id = outlined_glyph.glyph().id;
+        let placed_glyph = PlacedGlyph {
+            glyph_id,
+            subpixel_offset: point(0., 0.).into(),
+        };
+        if self.sdf_atlases.is_empty() {
+            self.sdf_atlases = Self::new_atlases(textures, texture_atlases, settings);
+        }
+        let glyph_texture = coverage_texture(
+            Font::get_outlined_glyph_sdf(outlined_glyph),
+            settings.atlas_texture_format(),
+        );
+        Self::add_glyph_texture(
+            &mut self.sdf_atlases,
+            texture_atlases,
+            textures,
+            &placed_glyph,
+            &glyph_texture,
+            false,
+            settings,
+            &mut self.overflow_count,
+        )?;
+
+        Ok(self.get_sdf_glyph_atlas_info(glyph_id).unwrap())
+    }
+
+    /// Returns the atlas info of the signed distance field of a glyph, if it is in the atlases.
+    pub fn get_sdf_glyph_atlas_info(&self, glyph_id: GlyphId) -> Option<GlyphAtlasInfo> {
+        let placed_glyph = PlacedGlyph {
+            glyph_id,
+            subpixel_offset: point(0., 0.).into(),
+        };
+        Self::find_glyph(&self.sdf_atlases, &placed_glyph)
+    }
+
+    /// Creates the [`TextSettings::initial_atlas_count`] atlases for a new font size.
+    fn new_atlases(
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        settings: &TextSettings,
+    ) -> Vec<FontAtlas> {
+        let format = settings.atlas_texture_format();
+        (0..settings.initial_atlas_count.get())
+            .map(|_| FontAtlas::new(textures, texture_atlases, settings.atlas_size, format))
+            .collect()
+    }
+
+    /// Adds a glyph texture to the first of `font_atlases` of the same kind, color or not, with space for it,
+    /// growing the last atlas of that kind or adding a new one as specified by [`TextSettings::atlas_overflow`] if
+    /// none has.
+    #[allow(clippy::too_many_arguments)]
+    fn add_glyph_texture(
+        font_atlases: &mut Vec<FontAtlas>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        placed_glyph: &PlacedGlyph,
+        glyph_texture: &Image,
+        color: bool,
+        settings: &TextSettings,
+        overflow_count: &mut u64,
+    ) -> Result<(), TextError> {
         let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
-            atlas.add_glyph(textures, texture_atlases, &placed_glyph, &glyph_texture)
+            atlas.is_color() == color
+                && atlas.add_glyph(textures, texture_atlases, placed_glyph, glyph_texture)
         };
-        if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
+        if font_atlases.iter_mut().any(add_char_to_font_atlas) {
+            return Ok(());
+        }
+        // The first color glyph of a font size doesn't overflow, as there are no color atlases to begin with
+        if !color || font_atlases.iter().any(FontAtlas::is_color) {
+            *overflow_count += 1;
+        }
+        if !Self::grow_last_atlas(
+            font_atlases
+                .iter_mut()
+                .rev()
+                .find(|atlas| atlas.is_color() == color),
+            texture_atlases,
+            textures,
+            placed_glyph,
+            glyph_texture,
+            settings.atlas_overflow,
+        ) {
             // Find the largest dimension of the glyph, either its width or its height
             let glyph_max_size: u32 = glyph_texture
                 .texture_descriptor
                 .size
                 .height
                 .max(glyph_texture.width());
-            // Pick the higher of 512 or the smallest power of 2 greater than glyph_max_size
-            let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512);
-            font_atlases.push(FontAtlas::new(
-                textures,
-                texture_atlases,
-                UVec2::splat(containing),
-            ));
+            // Pick the larger of the atlas size or the smallest power of 2 greater than glyph_max_size
+            let containing = 1u32 << (32 - glyph_max_size.leading_zeros());
+            let size = UVec2::splat(containing).max(settings.atlas_size);
+            font_atlases.push(if color {
+                FontAtlas::new_color(textures, texture_atlases, size)
+            } else {
+                FontAtlas::new(
+                    textures,
+                    texture_atlases,
+                    size,
+                    settings.atlas_texture_format(),
+                )
+            });
             if !font_atlases.last_mut().unwrap().add_glyph(
                 textures,
                 texture_atlases,
-                &placed_glyph,
-                &glyph_texture,
+                placed_glyph,
+                glyph_texture,
             ) {
                 return Err(TextError::FailedToAddGlyph(placed_glyph.glyph_id));
             }
         }
+        Ok(())
+    }
 
-        Ok(self.get_glyph_atlas_info(font_size, &placed_glyph).unwrap())
+    /// With [`FontAtlasOverflow::Grow`], doubles the size of `last_atlas` until the glyph fits
+    /// or the maximum size is reached. Returns `true` if the glyph was added.
+    fn grow_last_atlas(
+        last_atlas: Option<&mut FontAtlas>,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        textures: &mut Assets<Image>,
+        placed_glyph: &PlacedGlyph,
+        glyph_texture: &Image,
+        overflow: FontAtlasOverflow,
+    ) -> bool {
+        let (FontAtlasOverflow::Grow { max_size }, Some(atlas)) = (overflow, last_atlas) else {
+            return false;
+        };
+        loop {
+            let size = atlas.size();
+            if max_size <= size.max_element() {
+                return false;
+            }
+            atlas.grow(
+                textures,
+                texture_atlases,
+                (2 * size).min(UVec2::splat(max_size)),
+            );
+            if atlas.add_glyph(textures, texture_atlases, placed_glyph, glyph_texture) {
+                return true;
+            }
+        }
+    }
+
+    /// Returns the least recently used font size that can be evicted, and the frame and value of `use_count` when
+    /// it was last used.
+    ///
+    /// Font sizes used in `frame`, or with an atlas texture in `in_use`, can't be evicted.
+    fn least_recently_used(
+        &self,
+        frame: u64,
+        in_use: &HashSet<AssetId<Image>>,
+    ) -> Option<(FontSizeKey, (u64, u64))> {
+        self.font_atlases
+            .iter()
+            .filter(|(_, font_atlases)| {
+                !font_atlases
+                    .iter()
+                    .any(|atlas| in_use.contains(&atlas.texture.id()))
+            })
+            .map(|(&font_size, _)| {
+                let last_used = self.last_used.get(&font_size).copied().unwrap_or((0, 0));
+                (font_size, last_used)
+            })
+            .filter(|(_, last_used)| last_used.0 < frame)
+            .min_by_key(|(_, last_used)| *last_used)
+    }
+
+    /// Removes the atlases of `font_size`, returning the number of bytes used by their textures.
+    fn evict(&mut self, font_size: FontSizeKey) -> usize {
+        self.last_used.remove(&font_size);
+        let Some(font_atlases) = self.font_atlases.remove(&font_size) else {
+            return 0;
+        };
+        self.eviction_count += 1;
+        font_atlases.iter().map(FontAtlas::texture_bytes).sum()
     }
 
     pub fn get_glyph_atlas_info(
@@ -127,26 +713,62 @@ impl FontAtlasSet {
         font_size: f32,
         placed_glyph: &PlacedGlyph,
     ) -> Option<GlyphAtlasInfo> {
+        self.use_count += 1;
+        self.last_used
+            .insert(FloatOrd(font_size), (self.frame, self.use_count));
         self.font_atlases
             .get(&FloatOrd(font_size))
-            .and_then(|font_atlases| {
-                font_atlases
-                    .iter()
-                    .find_map(|atlas| {
-                        atlas.get_glyph_index(placed_glyph).map(|glyph_index| {
-                            (
-                                glyph_index,
-                                atlas.texture_atlas.clone_weak(),
-                                atlas.texture.clone_weak(),
-                            )
-                        })
-                    })
-                    .map(|(glyph_index, texture_atlas, texture)| GlyphAtlasInfo {
-                        texture_atlas,
-                        texture,
+            .and_then(|font_atlases| Self::find_glyph(font_atlases, placed_glyph))
+    }
+
+    fn find_glyph(
+        font_atlases: &[FontAtlas],
+        placed_glyph: &PlacedGlyph,
+    ) -> Option<GlyphAtlasInfo> {
+        font_atlases
+            .iter()
+            .find_map(|atlas| {
+                atlas.get_glyph_index(placed_glyph).map(|glyph_index| {
+                    (
                         glyph_index,
-                    })
+                        atlas.texture_atlas.clone_weak(),
+                        atlas.texture.clone_weak(),
+                        atlas.is_color(),
+                    )
+                })
             })
+            .map(
+                |(glyph_index, texture_atlas, texture, is_color)| GlyphAtlasInfo {
+                    texture_atlas,
+                    texture,
+                    glyph_index,
+                    is_color,
+                },
+            )
+    }
+
+    /// Returns the number of atlas textures in this set, for all font sizes and signed distance field glyphs
+    pub fn atlas_count(&self) -> usize {
+        self.font_atlases.values().map(Vec::len).sum::<usize>() + self.sdf_atlases.len()
+    }
+
+    /// Returns the atlases in this set, for all font sizes and signed distance field glyphs
+    pub fn atlases(&self) -> impl Iterator<Item = &FontAtlas> {
+        self.font_atlases
+            .values()
+            .flatten()
+            .chain(&self.sdf_atlases)
+    }
+
+    /// Returns the number of bytes used by the atlas textures in this set
+    pub fn texture_bytes(&self) -> usize {
+        self.atlases().map(FontAtlas::texture_bytes).sum()
+    }
+
+    /// Returns the fraction of the area of the atlas textures in this set that is covered by glyphs, from `0` to
+    /// `1`, or `0` if there are no atlases
+    pub fn occupancy(&self) -> f32 {
+        occupancy(self.atlases())
     }
 
     /// Returns the number of font atlases in this set
@@ -159,3 +781,508 @@ impl FontAtlasSet {
         self.font_atlases.is_empty()
     }
 }
+
+/// Returns the fraction of the total area of the `atlases` that is covered by glyphs.
+fn occupancy<'a>(atlases: impl Iterator<Item = &'a FontAtlas>) -> f32 {
+    let (glyph_area, area) = atlases.fold((0, 0), |(glyph_area, area), atlas| {
+        (
+            glyph_area + atlas.glyph_area,
+            area + atlas.size().element_product() as u64,
+        )
+    });
+    if area == 0 {
+        0.
+    } else {
+        glyph_area as f32 / area as f32
+    }
+}
+
+/// Converts a glyph texture of white pixels with their coverage in the alpha channel to the atlas `format`.
+///
+/// The white pixels have the same bytes in all the supported 8 bit RGBA and BGRA formats, single channel formats
+/// only store the coverage.
+fn coverage_texture(mut glyph_texture: Image, format: TextureFormat) -> Image {
+    if format == TextureFormat::R8Unorm {
+        glyph_texture.data = glyph_texture
+            .data
+            .chunks_exact(4)
+            .map(|pixel| pixel[3])
+            .collect();
+    }
+    glyph_texture.texture_descriptor.format = format;
+    glyph_texture
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::{point, Font as _, GlyphId};
+    use bevy_asset::Assets;
+    use bevy_color::LinearRgba;
+    use bevy_math::{FloatOrd, UVec2};
+    use bevy_render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    };
+    use bevy_sprite::TextureAtlasLayout;
+    use std::num::NonZeroUsize;
+
+    use bevy_asset::AssetId;
+
+    use bevy_asset::AssetEvent;
+    use bevy_asset::Handle;
+    use bevy_ecs::{
+        entity::Entity, event::Events, query::Changed, schedule::Schedule, world::World,
+    };
+
+    use bevy_math::Vec2;
+    use bevy_utils::HashSet;
+
+    use super::{
+        evict_font_atlases, prewarm_font_atlases, reload_modified_fonts, FontAtlasPrewarm,
+        FontAtlasSet, FontAtlasSets, GlyphAtlasInfo,
+    };
+    use crate::{
+        Font, FontAtlasOverflow, FontLoaderSettings, PlacedGlyph, PositionedGlyph, Text,
+        TextLayoutInfo, TextPipeline, TextSettings, TextStyle, SDF_FONT_SIZE, SDF_SPREAD,
+    };
+
+    fn overflow_settings(atlas_overflow: FontAtlasOverflow) -> TextSettings {
+        TextSettings {
+            atlas_overflow,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the glyphs for `A` to `Z` to a new [`FontAtlasSet`] at each of the `font_sizes`.
+    fn add_glyphs(font_sizes: &[f32], settings: &TextSettings) -> (FontAtlasSet, Assets<Image>) {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+        let mut textures = Assets::<Image>::default();
+        let mut font_atlas_set = FontAtlasSet::default();
+        for &font_size in font_sizes {
+            for c in 'A'..='Z' {
+                let glyph = font
+                    .font
+                    .glyph_id(c)
+                    .with_scale_and_position(font_size, point(0., 0.));
+                let outlined_glyph = font.font.outline_glyph(glyph).unwrap();
+                font_atlas_set
+                    .add_glyph_to_atlas(
+                        &mut texture_atlases,
+                        &mut textures,
+                        outlined_glyph,
+                        settings,
+                    )
+                    .unwrap();
+            }
+        }
+        (font_atlas_set, textures)
+    }
+
+    #[test]
+    fn overflowing_atlases_are_added_or_grown() {
+        let (font_atlas_set, _) = add_glyphs(&[200.], &overflow_settings(FontAtlasOverflow::Warn));
+        assert!(font_atlas_set.overflow_count > 0);
+        assert!(font_atlas_set.atlas_count() > 1);
+
+        let (font_atlas_set, textures) = add_glyphs(
+            &[200.],
+            &overflow_settings(FontAtlasOverflow::Grow { max_size: 2048 }),
+        );
+        assert!(font_atlas_set.overflow_count > 0);
+        assert_eq!(font_atlas_set.atlas_count(), 1);
+        let atlas = &font_atlas_set.font_atlases.values().next().unwrap()[0];
+        assert!(atlas.size().max_element() > 512);
+        assert_eq!(textures.get(&atlas.texture).unwrap().size(), atlas.size());
+    }
+
+    #[test]
+    fn atlases_use_the_configured_size_count_and_format() {
+        let settings = TextSettings {
+            atlas_size: UVec2::new(256, 128),
+            initial_atlas_count: NonZeroUsize::new(2).unwrap(),
+            atlas_format: TextureFormat::Bgra8UnormSrgb,
+            ..Default::default()
+        };
+        let (font_atlas_set, textures) = add_glyphs(&[12.], &settings);
+        let atlases = font_atlas_set.font_atlases.values().next().unwrap();
+        assert_eq!(atlases.len(), 2);
+        for atlas in atlases {
+            let texture = textures.get(&atlas.texture).unwrap();
+            assert_eq!(texture.size(), UVec2::new(256, 128));
+            assert_eq!(
+                texture.texture_descriptor.format,
+                TextureFormat::Bgra8UnormSrgb
+            );
+        }
+
+        // Unsupported formats are replaced
+        let settings = TextSettings {
+            atlas_format: TextureFormat::Rgba16Float,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.atlas_texture_format(),
+            TextureFormat::Rgba8UnormSrgb
+        );
+    }
+
+    #[test]
+    fn single_channel_atlases_store_the_coverage_of_glyphs() {
+        let texture = |format| {
+            let settings = TextSettings {
+                atlas_format: format,
+                ..Default::default()
+            };
+            let (font_atlas_set, textures) = add_glyphs(&[12.], &settings);
+            let atlases = font_atlas_set.font_atlases.values().next().unwrap();
+            textures.get(&atlases[0].texture).unwrap().clone()
+        };
+        let rgba = texture(TextureFormat::Rgba8UnormSrgb);
+        let r8 = texture(TextureFormat::R8Unorm);
+        assert_eq!(r8.texture_descriptor.format, TextureFormat::R8Unorm);
+        assert_eq!(r8.size(), rgba.size());
+        let coverage = rgba.data.chunks_exact(4).map(|pixel| pixel[3]);
+        assert!(r8.data.iter().copied().eq(coverage));
+        assert!(r8.data.iter().any(|&coverage| coverage != 0));
+    }
+
+    #[test]
+    fn color_glyphs_are_stored_in_color_atlases() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let settings = TextSettings {
+            atlas_format: TextureFormat::R8Unorm,
+            ..Default::default()
+        };
+        let (mut font_atlas_set, mut textures) = add_glyphs(&[12.], &settings);
+        let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+        // The font has no color glyphs, stand in for one with a red square
+        let glyph = GlyphId(0).with_scale_and_position(12., point(0., 0.));
+        let glyph_texture = Image::new_fill(
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        let overflow_count = font_atlas_set.overflow_count;
+        let info = font_atlas_set
+            .add_color_glyph_to_atlas(
+                &mut texture_atlases,
+                &mut textures,
+                &glyph,
+                glyph_texture,
+                &settings,
+            )
+            .unwrap();
+
+        assert!(info.is_color);
+        assert_eq!(font_atlas_set.overflow_count, overflow_count);
+        let atlases = font_atlas_set.font_atlases.values().next().unwrap();
+        assert_eq!(atlases.len(), 2);
+        assert!(!atlases[0].is_color());
+        assert!(atlases[1].is_color());
+        assert_eq!(info.texture, atlases[1].texture);
+        assert_eq!(
+            textures
+                .get(&atlases[1].texture)
+                .unwrap()
+                .texture_descriptor
+                .format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+
+        // Color glyphs keep their colors, other glyphs are drawn in the color of their text
+        let color = LinearRgba::new(0., 0., 1., 0.5);
+        assert_eq!(info.tint(color), LinearRgba::new(1., 1., 1., 0.5));
+        let placed_glyph = PlacedGlyph {
+            glyph_id: font.font.glyph_id('B'),
+            subpixel_offset: point(0., 0.).into(),
+        };
+        let info = font_atlas_set
+            .get_glyph_atlas_info(12., &placed_glyph)
+            .unwrap();
+        assert!(!info.is_color);
+        assert_eq!(info.tint(color), color);
+    }
+
+    #[test]
+    fn least_recently_used_font_sizes_are_evicted_at_the_end_of_the_frame() {
+        let settings = TextSettings {
+            soft_max_font_atlases: NonZeroUsize::new(2).unwrap(),
+            ..overflow_settings(FontAtlasOverflow::EvictLeastRecentlyUsed { max_bytes: None })
+        };
+        let (mut font_atlas_set, textures) = add_glyphs(&[10., 11., 12., 13., 14.], &settings);
+        let placed_glyph = PlacedGlyph {
+            glyph_id: GlyphId(0),
+            subpixel_offset: point(0., 0.).into(),
+        };
+        // use the smallest size again
+        font_atlas_set.get_glyph_atlas_info(10., &placed_glyph);
+
+        let font_id = AssetId::<Font>::default();
+        let mut world = World::new();
+        // A text laid out with glyphs of size 12
+        let texture = font_atlas_set.font_atlases[&FloatOrd(12.)][0]
+            .texture
+            .clone_weak();
+        world.spawn(TextLayoutInfo {
+            glyphs: vec![PositionedGlyph {
+                position: Vec2::ZERO,
+                size: Vec2::ONE,
+                atlas_info: GlyphAtlasInfo {
+                    texture_atlas: Handle::default(),
+                    texture,
+                    glyph_index: 0,
+                    is_color: false,
+                },
+                section_index: 0,
+                byte_index: 0,
+            }],
+            ..Default::default()
+        });
+        let mut font_atlas_sets = FontAtlasSets::default();
+        font_atlas_sets.sets.insert(font_id, font_atlas_set);
+        world.insert_resource(font_atlas_sets);
+        world.insert_resource(settings);
+        world.insert_resource(textures);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(evict_font_atlases);
+
+        // Every size was used in the first frame
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<FontAtlasSets>().eviction_count(), 0);
+
+        world
+            .resource_mut::<FontAtlasSets>()
+            .entry(font_id)
+            .get_glyph_atlas_info(11., &placed_glyph);
+        schedule.run(&mut world);
+        // Size 11 was used in this frame, and size 12 is used by the text
+        let font_atlas_sets = world.resource::<FontAtlasSets>();
+        let font_atlas_set = font_atlas_sets.get(font_id).unwrap();
+        assert_eq!(font_atlas_sets.eviction_count(), 3);
+        assert_eq!(font_atlas_set.len(), 2);
+        assert!(font_atlas_set.font_atlases.contains_key(&FloatOrd(11.)));
+        assert!(font_atlas_set.font_atlases.contains_key(&FloatOrd(12.)));
+    }
+
+    #[test]
+    fn least_recently_used_font_sizes_of_any_font_are_evicted_to_fit() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let mut fonts = Assets::<Font>::default();
+        let [font_a, font_b] = [(); 2].map(|_| fonts.add(font.clone()).id());
+        let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+        let mut textures = Assets::<Image>::default();
+        let mut font_atlas_sets = FontAtlasSets::default();
+        for (font_id, font_size) in [(font_a, 10.), (font_b, 20.), (font_a, 30.)] {
+            font_atlas_sets
+                .prewarm(
+                    font_id,
+                    &font,
+                    font_size,
+                    "ABC".chars(),
+                    &mut texture_atlases,
+                    &mut textures,
+                    &TextSettings::default(),
+                )
+                .unwrap();
+        }
+        let atlas_bytes = 4 * 512 * 512;
+        assert_eq!(font_atlas_sets.texture_bytes(), 3 * atlas_bytes);
+        let occupancy = font_atlas_sets.occupancy();
+        assert!(0. < occupancy && occupancy < 1.);
+
+        let placed_glyph = PlacedGlyph {
+            glyph_id: font.font.glyph_id('A'),
+            subpixel_offset: point(0., 0.).into(),
+        };
+        let use_font_size = |font_atlas_sets: &mut FontAtlasSets, font_id, font_size| {
+            font_atlas_sets
+                .entry(font_id)
+                .get_glyph_atlas_info(font_size, &placed_glyph)
+                .unwrap();
+        };
+
+        // Every font size was used in the current frame
+        let in_use = HashSet::new();
+        assert!(!font_atlas_sets.evict_least_recently_used(usize::MAX, Some(0), &in_use));
+        assert_eq!(font_atlas_sets.atlas_count(), 3);
+        font_atlas_sets.frame += 1;
+
+        // use the smallest size again
+        use_font_size(&mut font_atlas_sets, font_a, 10.);
+        assert!(font_atlas_sets.evict_least_recently_used(
+            usize::MAX,
+            Some(2 * atlas_bytes),
+            &in_use
+        ));
+        assert_eq!(font_atlas_sets.eviction_count(), 1);
+        assert!(font_atlas_sets.get(font_b).unwrap().is_empty());
+        font_atlas_sets.frame += 1;
+
+        use_font_size(&mut font_atlas_sets, font_a, 30.);
+        assert!(!font_atlas_sets.evict_least_recently_used(usize::MAX, Some(0), &in_use));
+        assert_eq!(font_atlas_sets.eviction_count(), 2);
+        let font_atlas_set = font_atlas_sets.get(font_a).unwrap();
+        assert!(font_atlas_set.font_atlases.contains_key(&FloatOrd(30.)));
+        assert_eq!(font_atlas_sets.texture_bytes(), atlas_bytes);
+    }
+
+    #[test]
+    fn prewarmed_glyphs_are_cached() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let font_id = AssetId::<Font>::default();
+        let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+        let mut textures = Assets::<Image>::default();
+        let mut font_atlas_sets = FontAtlasSets::default();
+        let mut prewarm = |chars: &str| {
+            font_atlas_sets
+                .prewarm(
+                    font_id,
+                    &font,
+                    20.,
+                    chars.chars(),
+                    &mut texture_atlases,
+                    &mut textures,
+                    &TextSettings::default(),
+                )
+                .unwrap()
+        };
+
+        // Whitespace has no outline
+        assert_eq!(prewarm("AB C"), 3);
+        assert_eq!(prewarm("ABCD"), 1);
+
+        let font_atlas_set = font_atlas_sets.get(font_id).unwrap();
+        let glyph_id = font.font.glyph_id('D');
+        assert!(font_atlas_set.has_glyph(glyph_id, point(0., 0.), 20.));
+        assert!(!font_atlas_set.has_glyph(glyph_id, point(0., 0.), 10.));
+    }
+
+    #[test]
+    fn fonts_are_preloaded_from_their_settings() {
+        let mut world = World::new();
+        world.init_resource::<Events<AssetEvent<Font>>>();
+        world.init_resource::<FontAtlasPrewarm>();
+        world.init_resource::<TextSettings>();
+        world.init_resource::<FontAtlasSets>();
+        world.init_resource::<Assets<TextureAtlasLayout>>();
+        world.init_resource::<Assets<Image>>();
+        let mut font =
+            Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        font.preload = FontLoaderSettings {
+            preload_sizes: vec![12., 24.],
+            preload_chars: Some("AB".to_string()),
+        };
+        let glyph_id = font.font.glyph_id('B');
+        let mut fonts = Assets::<Font>::default();
+        let font_id = fonts.add(font).id();
+        world.insert_resource(fonts);
+        world.send_event(AssetEvent::Added { id: font_id });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(prewarm_font_atlases);
+        schedule.run(&mut world);
+
+        let font_atlas_set = world.resource::<FontAtlasSets>().get(font_id).unwrap();
+        assert_eq!(font_atlas_set.len(), 2);
+        assert!(font_atlas_set.has_glyph(glyph_id, point(0., 0.), 12.));
+        assert!(font_atlas_set.has_glyph(glyph_id, point(0., 0.), 24.));
+    }
+
+    #[test]
+    fn modified_fonts_are_invalidated() {
+        let mut world = World::new();
+        world.init_resource::<Events<AssetEvent<Font>>>();
+        world.init_resource::<FontAtlasSets>();
+        world.init_resource::<TextPipeline>();
+        let mut fonts = Assets::<Font>::default();
+        let [modified, unmodified] = [(); 2].map(|_| {
+            fonts.add(Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap())
+        });
+        world.insert_resource(fonts);
+        for font in [&modified, &unmodified] {
+            world
+                .resource_mut::<FontAtlasSets>()
+                .sets
+                .insert(font.id(), FontAtlasSet::default());
+        }
+        let text = |font: &Handle<Font>| {
+            Text::from_section(
+                "text",
+                TextStyle {
+                    font: font.clone(),
+                    ..Default::default()
+                },
+            )
+        };
+        let modified_text = world.spawn(text(&modified)).id();
+        world.spawn(text(&unmodified));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(reload_modified_fonts);
+        schedule.run(&mut world);
+        world.clear_trackers();
+
+        world.send_event(AssetEvent::Modified { id: modified.id() });
+        schedule.run(&mut world);
+        let font_atlas_sets = world.resource::<FontAtlasSets>();
+        assert!(font_atlas_sets.get(&modified).is_none());
+        assert!(font_atlas_sets.get(&unmodified).is_some());
+        let mut changed = world.query_filtered::<Entity, Changed<Text>>();
+        assert_eq!(changed.iter(&world).collect::<Vec<_>>(), [modified_text]);
+    }
+
+    #[test]
+    fn sdf_glyphs_are_padded_distance_fields() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let mut texture_atlases = Assets::<TextureAtlasLayout>::default();
+        let mut textures = Assets::<Image>::default();
+        let mut font_atlas_set = FontAtlasSet::default();
+        let glyph = font
+            .font
+            .glyph_id('I')
+            .with_scale_and_position(SDF_FONT_SIZE, point(0., 0.));
+        let outlined_glyph = font.font.outline_glyph(glyph.clone()).unwrap();
+        let bounds = outlined_glyph.px_bounds();
+        let atlas_info = font_atlas_set
+            .add_sdf_glyph_to_atlas(
+                &mut texture_atlases,
+                &mut textures,
+                outlined_glyph,
+                &TextSettings::default(),
+            )
+            .unwrap();
+
+        // Signed distance field glyphs are shared by all font sizes
+        assert!(font_atlas_set.has_sdf_glyph(glyph.id));
+        assert!(!font_atlas_set.has_glyph(glyph.id, point(0., 0.), SDF_FONT_SIZE));
+        assert!(font_atlas_set.is_empty());
+        assert_eq!(font_atlas_set.atlas_count(), 1);
+
+        let layout = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+        let rect = layout.textures[atlas_info.glyph_index];
+        assert_eq!(
+            rect.size(),
+            UVec2::new(bounds.width() as u32, bounds.height() as u32) + 2 * SDF_SPREAD
+        );
+        let texture = textures.get(&atlas_info.texture).unwrap();
+        let alpha = |x: u32, y: u32| {
+            let index = (rect.min.y + y) * layout.size.x + rect.min.x + x;
+            texture.data[4 * index as usize + 3]
+        };
+        let center = rect.size() / 2;
+        // The padding is entirely outside of the outline
+        assert_eq!(alpha(0, 0), 0);
+        assert!(alpha(center.x, 0) < 128);
+        // The distance increases towards the middle of the stem
+        assert!(128 < alpha(center.x, center.y));
+        assert!(alpha(center.x - 3, center.y) < alpha(center.x, center.y));
+    }
+}
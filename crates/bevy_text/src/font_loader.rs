@@ -1,10 +1,35 @@
 use crate::Font;
 use bevy_asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Default)]
 pub struct FontLoader;
 
+/// Settings for loading a [`Font`] with the [`FontLoader`], set in the font's `.meta` file or with
+/// `AssetServer::load_with_settings`.
+///
+/// The glyphs for the `preload_chars` are added to the glyph cache at each of the `preload_sizes` as soon as the
+/// font has loaded, by [`prewarm_font_atlases`](crate::prewarm_font_atlases), so that text doesn't hitch the
+/// first time it uses them. Like with [`FontAtlasPrewarm`](crate::FontAtlasPrewarm), the sizes are in logical
+/// pixels and are scaled by the primary window's scale factor.
+///
+/// ```
+/// # use bevy_text::FontLoaderSettings;
+/// let settings = FontLoaderSettings {
+///     preload_sizes: vec![16., 24.],
+///     preload_chars: Some("0123456789".to_string()),
+/// };
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FontLoaderSettings {
+    /// The font sizes to add glyphs to the glyph cache for, in logical pixels.
+    pub preload_sizes: Vec<f32>,
+    /// The characters to add to the glyph cache at each of the `preload_sizes`, or `None` for the printable ASCII
+    /// characters, [`FontAtlasPrewarm::ASCII`](crate::FontAtlasPrewarm::ASCII).
+    pub preload_chars: Option<String>,
+}
+
 /// Possible errors that can be produced by [`FontLoader`]
 #[non_exhaustive]
 #[derive(Debug, Error)]
@@ -19,17 +44,19 @@ pub enum FontLoaderError {
 
 impl AssetLoader for FontLoader {
     type Asset = Font;
-    type Settings = ();
+    type Settings = FontLoaderSettings;
     type Error = FontLoaderError;
     async fn load<'a>(
         &'a self,
         reader: &'a mut Reader<'_>,
-        _settings: &'a (),
+        settings: &'a FontLoaderSettings,
         _load_context: &'a mut LoadContext<'_>,
     ) -> Result<Font, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        Ok(Font::try_from_bytes(bytes)?)
+        let mut font = Font::try_from_bytes(bytes)?;
+        font.preload = settings.clone();
+        Ok(font)
     }
 
     fn extensions(&self) -> &[&str] {
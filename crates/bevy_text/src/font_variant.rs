@@ -0,0 +1,166 @@
+use ab_glyph::{Font as _, FontArc, GlyphId, Outline};
+
+use crate::{Font, LineHeight, TextSection};
+
+/// The spacing and features of a [`TextStyle`](crate::TextStyle) that change the advances of the glyphs of its
+/// font, relative to its font size so that a variant can be shared by all sizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct FontVariant {
+    /// The bits of the letter spacing in ems.
+    letter_spacing: u32,
+    /// The bits of the word spacing in ems.
+    word_spacing: u32,
+    tabular_numbers: bool,
+    /// The bits of the line height in ems, if it isn't the font's own.
+    line_height: Option<u32>,
+}
+
+impl FontVariant {
+    /// Returns the variant of the font of `section`. Placeholders always use the font itself.
+    pub(crate) fn of(section: &TextSection) -> Self {
+        let style = &section.style;
+        if section.placeholder.is_some() || style.font_size <= 0. {
+            return Self::default();
+        }
+        // Normalize negative zero, so equal spacings have the same key
+        let em = |spacing: f32| (spacing / style.font_size + 0.).to_bits();
+        Self {
+            letter_spacing: em(style.letter_spacing),
+            word_spacing: em(style.word_spacing),
+            tabular_numbers: style.font_features.tabular_numbers,
+            line_height: match style.line_height {
+                LineHeight::Auto => None,
+                LineHeight::RelativeToFont(line_height) => Some((line_height + 0.).to_bits()),
+                LineHeight::Px(line_height) => Some(em(line_height)),
+            },
+        }
+    }
+}
+
+impl Font {
+    /// Returns the font with the advances of its glyphs changed by `variant`.
+    pub(crate) fn with_variant(&self, variant: FontVariant) -> FontArc {
+        if variant == FontVariant::default() {
+            return self.font.clone();
+        }
+        FontArc::new(SpacedFont::new(self.font.clone(), variant))
+    }
+}
+
+/// A font with extra space after each of its glyphs, and optionally digits of equal width or another line height.
+struct SpacedFont {
+    font: FontArc,
+    /// The extra advance of every glyph, in font units.
+    letter_spacing: f32,
+    /// The extra advance of spaces, in font units.
+    word_spacing: f32,
+    space: GlyphId,
+    digits: [GlyphId; 10],
+    /// The advance of all digits, if they are tabular.
+    digit_advance: Option<f32>,
+    /// The line gap making the lines as tall as the line height, if it isn't the font's own.
+    line_gap: Option<f32>,
+}
+
+impl SpacedFont {
+    fn new(font: FontArc, variant: FontVariant) -> Self {
+        // Advances are in font units, and font sizes are the height of the font
+        let height = font.height_unscaled();
+        let digits = std::array::from_fn(|digit| font.glyph_id(char::from(b'0' + digit as u8)));
+        let digit_advance = variant.tabular_numbers.then(|| {
+            digits
+                .iter()
+                .map(|digit| font.h_advance_unscaled(*digit))
+                .fold(0., f32::max)
+        });
+        // Lines are laid out as tall as the font plus its line gap, which is negative for tighter lines
+        let line_gap = variant
+            .line_height
+            .map(|line_height| (f32::from_bits(line_height) * height).max(0.) - height);
+        Self {
+            letter_spacing: f32::from_bits(variant.letter_spacing) * height,
+            word_spacing: f32::from_bits(variant.word_spacing) * height,
+            space: font.glyph_id(' '),
+            digits,
+            digit_advance,
+            line_gap,
+            font,
+        }
+    }
+}
+
+impl ab_glyph::Font for SpacedFont {
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    fn line_gap_unscaled(&self) -> f32 {
+        self.line_gap
+            .unwrap_or_else(|| self.font.line_gap_unscaled())
+    }
+
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        let advance = match self.digit_advance {
+            Some(digit_advance) if self.digits.contains(&id) => digit_advance,
+            _ => self.font.h_advance_unscaled(id),
+        };
+        // Combining marks without an advance stay on their base character
+        if advance == 0. {
+            return 0.;
+        }
+        let word_spacing = if id == self.space {
+            self.word_spacing
+        } else {
+            0.
+        };
+        advance + self.letter_spacing + word_spacing
+    }
+
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        self.font.outline(id)
+    }
+
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    fn codepoint_ids(&self) -> ab_glyph::CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    fn glyph_raster_image2(
+        &self,
+        id: GlyphId,
+        pixel_size: u16,
+    ) -> Option<ab_glyph::v2::GlyphImage<'_>> {
+        self.font.glyph_raster_image2(id, pixel_size)
+    }
+}
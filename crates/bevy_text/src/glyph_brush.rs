@@ -1,18 +1,22 @@
-use ab_glyph::{Font as _, FontArc, Glyph, PxScaleFont, ScaleFont as _};
+use ab_glyph::{
+    point, Font as _, FontArc, Glyph, GlyphId, OutlinedGlyph, PxScaleFont, ScaleFont as _,
+};
 use bevy_asset::{AssetId, Assets};
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
 use bevy_utils::warn_once;
-use glyph_brush_layout::{
-    BuiltInLineBreaker, FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph,
-    SectionText, ToSectionText,
-};
+use glyph_brush_layout::{FontId, SectionGlyph, SectionText, ToSectionText};
+use std::ops::Range;
 
+#[cfg(feature = "subpixel_glyph_atlas")]
+use crate::SubpixelOffset;
 use crate::{
-    error::TextError, BreakLineOn, Font, FontAtlasSet, FontAtlasSets, GlyphAtlasInfo, JustifyText,
-    PlacedGlyph, TextSettings, YAxisOrientation,
+    error::TextError, line_break::layout_glyphs, vertical_layout::layout_vertical_glyphs,
+    BreakLineOn, Font, FontAtlasOverflow, FontAtlasSets, FontRenderMode, GlyphAtlasInfo,
+    JustifyText, OverflowBehavior, PlacedGlyph, TextSettings, WritingMode, YAxisOrientation,
+    SDF_FONT_SIZE, SDF_SPREAD,
 };
 
 pub struct GlyphBrush {
@@ -38,19 +42,174 @@ impl GlyphBrush {
         bounds: Vec2,
         text_alignment: JustifyText,
         linebreak_behavior: BreakLineOn,
+        paragraph_spacing: f32,
     ) -> Result<Vec<SectionGlyph>, TextError> {
-        let geom = SectionGeometry {
-            bounds: (bounds.x, bounds.y),
-            ..Default::default()
+        Ok(layout_glyphs(
+            &self.fonts,
+            sections,
+            bounds,
+            text_alignment,
+            linebreak_behavior,
+            paragraph_spacing,
+        ))
+    }
+
+    /// Lays out `sections` in columns for a vertical `writing_mode`, removing the glyphs that don't fit in
+    /// `bounds` as specified by `overflow`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_vertical_glyphs<S: ToSectionText>(
+        &self,
+        sections: &[S],
+        bounds: Vec2,
+        writing_mode: WritingMode,
+        text_alignment: JustifyText,
+        linebreak_behavior: BreakLineOn,
+        overflow: OverflowBehavior,
+        paragraph_spacing: f32,
+    ) -> Vec<SectionGlyph> {
+        layout_vertical_glyphs(
+            &self.fonts,
+            sections,
+            bounds,
+            writing_mode,
+            text_alignment,
+            linebreak_behavior,
+            overflow,
+            paragraph_spacing,
+        )
+    }
+
+    /// Removes the glyphs that don't fit in `bounds`, as specified by `overflow`.
+    ///
+    /// The glyphs must be laid out by [`GlyphBrush::compute_glyphs`] with the same `text_alignment`.
+    pub fn truncate_glyphs(
+        &self,
+        glyphs: &mut Vec<SectionGlyph>,
+        sections: &[SectionText],
+        bounds: Vec2,
+        text_alignment: JustifyText,
+        overflow: OverflowBehavior,
+    ) {
+        if overflow == OverflowBehavior::Visible || glyphs.is_empty() {
+            return;
+        }
+        let scaled_font = |section_index: usize| {
+            let section = &sections[section_index];
+            self.fonts[section.font_id.0].as_scaled(section.scale)
+        };
+        let glyph_end = |sg: &SectionGlyph| {
+            sg.glyph.position.x + scaled_font(sg.section_index).h_advance(sg.glyph.id)
+        };
+        let is_whitespace = |sg: &SectionGlyph| {
+            sections[sg.section_index].text[sg.byte_index..]
+                .chars()
+                .next()
+                .is_some_and(char::is_whitespace)
+        };
+
+        // Lines are laid out starting at 0, centered on 0 or ending at 0, depending on the alignment.
+        let alignment = text_alignment.fraction();
+        let (min_x, max_x) = if bounds.x.is_finite() {
+            (-alignment * bounds.x, (1. - alignment) * bounds.x)
+        } else {
+            (f32::NEG_INFINITY, f32::INFINITY)
         };
+        // Tolerance for rounding errors in the bounds
+        let max_x = max_x + 0.5;
+        let max_y = bounds.y + 0.5;
+
+        let mut lines: Vec<Vec<SectionGlyph>> = Vec::new();
+        for sg in glyphs.drain(..) {
+            match lines.last_mut() {
+                // The glyphs of a line share a baseline
+                Some(line) if line[0].glyph.position.y == sg.glyph.position.y => line.push(sg),
+                _ => lines.push(vec![sg]),
+            }
+        }
 
-        let lbb: BuiltInLineBreaker = linebreak_behavior.into();
+        let visible_lines = lines
+            .iter()
+            .take_while(|line| {
+                line.iter().all(|sg| {
+                    sg.glyph.position.y - scaled_font(sg.section_index).descent() <= max_y
+                })
+            })
+            .count()
+            .max(1);
+        let lines_removed = visible_lines < lines.len();
+        lines.truncate(visible_lines);
+
+        let last_line = lines.len() - 1;
+        for (line_index, line) in lines.iter_mut().enumerate() {
+            let start = line[0].glyph.position.x;
+            // Lines wider than the bounds start at the start of the bounds
+            let offset = if max_x - min_x < glyph_end(line.last().unwrap()) - start {
+                min_x - start
+            } else {
+                0.
+            };
+            let kept = line
+                .iter()
+                .take_while(|sg| glyph_end(sg) + offset <= max_x)
+                .count();
+            let mut removed = line.split_off(kept);
+            let add_ellipsis = overflow == OverflowBehavior::Ellipsis
+                && (!removed.is_empty() || (lines_removed && line_index == last_line));
+            if removed.is_empty() && !add_ellipsis {
+                continue;
+            }
+            for sg in line.iter_mut() {
+                sg.glyph.position.x += offset;
+            }
 
-        let section_glyphs = Layout::default()
-            .h_align(text_alignment.into())
-            .line_breaker(lbb)
-            .calculate_glyphs(&self.fonts, &geom, sections);
-        Ok(section_glyphs)
+            if add_ellipsis {
+                let reference = removed.first().or(line.last()).unwrap().clone();
+                let font = &self.fonts[sections[reference.section_index].font_id.0];
+                let (ellipsis_id, count) = match font.glyph_id('…') {
+                    // Fall back to three full stops if the font has no ellipsis glyph
+                    GlyphId(0) => (font.glyph_id('.'), 3),
+                    ellipsis_id => (ellipsis_id, 1),
+                };
+                let ellipsis_advance = scaled_font(reference.section_index).h_advance(ellipsis_id);
+                // Make room for the ellipsis, and don't leave whitespace before it
+                while let Some(sg) = line.last() {
+                    if !is_whitespace(sg)
+                        && glyph_end(sg) + count as f32 * ellipsis_advance <= max_x
+                    {
+                        break;
+                    }
+                    removed.insert(0, line.pop().unwrap());
+                }
+                let (section_index, byte_index) = removed
+                    .first()
+                    .map_or((reference.section_index, reference.byte_index), |sg| {
+                        (sg.section_index, sg.byte_index)
+                    });
+                let mut x = line.last().map_or(start + offset, glyph_end);
+                for _ in 0..count {
+                    line.push(SectionGlyph {
+                        section_index,
+                        byte_index,
+                        glyph: Glyph {
+                            id: ellipsis_id,
+                            scale: reference.glyph.scale,
+                            position: point(x, reference.glyph.position.y),
+                        },
+                        font_id: reference.font_id,
+                    });
+                    x += ellipsis_advance;
+                }
+            }
+
+            // Align the truncated line again
+            let start = line[0].glyph.position.x;
+            let width = glyph_end(line.last().unwrap()) - start;
+            let shift = -alignment * width - start;
+            for sg in line.iter_mut() {
+                sg.glyph.position.x += shift;
+            }
+        }
+        glyphs.extend(lines.into_iter().flatten());
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -65,6 +224,7 @@ impl GlyphBrush {
         text_settings: &TextSettings,
         y_axis_orientation: YAxisOrientation,
         h_anchor: f32,
+        render_mode: FontRenderMode,
     ) -> Result<Vec<PositionedGlyph>, TextError> {
         if glyphs.is_empty() {
             return Ok(Vec::new());
@@ -80,7 +240,8 @@ impl GlyphBrush {
                     asset_id,
                     font,
                     font_size,
-                    ab_glyph::Font::as_scaled(&font.font, font_size),
+                    // The advances of the brush's font include the section's spacing
+                    ab_glyph::Font::as_scaled(&self.fonts[section.font_id.0], font_size),
                 ))
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -88,6 +249,56 @@ impl GlyphBrush {
         let text_bounds = compute_text_bounds(&glyphs, |index| sections_data[index].3);
 
         let mut positioned_glyphs = Vec::new();
+        if render_mode == FontRenderMode::Sdf {
+            for sg in glyphs {
+                let (asset_id, font, font_size, _) = sections_data[sg.section_index];
+                let sdf_glyph = sg
+                    .glyph
+                    .id
+                    .with_scale_and_position(SDF_FONT_SIZE, point(0., 0.));
+                let Some(outlined_glyph) = font.font.outline_glyph(sdf_glyph) else {
+                    continue;
+                };
+                let sdf_bounds = outlined_glyph.px_bounds();
+                let font_atlas_set = font_atlas_sets.sets.entry(*asset_id).or_default();
+                let atlas_info = font_atlas_set
+                    .get_sdf_glyph_atlas_info(sg.glyph.id)
+                    .map(Ok)
+                    .unwrap_or_else(|| {
+                        font_atlas_set.add_sdf_glyph_to_atlas(
+                            texture_atlases,
+                            textures,
+                            outlined_glyph,
+                            text_settings,
+                        )
+                    })?;
+
+                let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+                let glyph_rect = texture_atlas.textures[atlas_info.glyph_index];
+
+                // The signed distance field is scaled from its font size, including its padding
+                let scale = font_size / SDF_FONT_SIZE;
+                let size = glyph_rect.size().as_vec2() * scale;
+                let min = Vec2::new(sg.glyph.position.x, sg.glyph.position.y)
+                    + (Vec2::new(sdf_bounds.min.x, sdf_bounds.min.y) - SDF_SPREAD as f32) * scale;
+
+                let x = min.x + size.x / 2.0 + h_anchor;
+                let y = match y_axis_orientation {
+                    YAxisOrientation::BottomToTop => text_bounds.max.y - min.y - size.y / 2.0,
+                    YAxisOrientation::TopToBottom => min.y + size.y / 2.0 - text_bounds.min.y,
+                };
+
+                positioned_glyphs.push(PositionedGlyph {
+                    position: Vec2::new(x, y),
+                    size,
+                    atlas_info,
+                    section_index: sg.section_index,
+                    byte_index: sg.byte_index,
+                });
+            }
+            return Ok(positioned_glyphs);
+        }
+
         for sg in glyphs {
             let SectionGlyph {
                 section_index: _,
@@ -95,32 +306,63 @@ impl GlyphBrush {
                 mut glyph,
                 font_id: _,
             } = sg;
+            let adjust = GlyphPlacementAdjuster::new(&mut glyph);
             let placed_glyph = PlacedGlyph {
                 glyph_id: glyph.id,
                 subpixel_offset: glyph.position.into(),
             };
-            let adjust = GlyphPlacementAdjuster::new(&mut glyph);
             let section_data = sections_data[sg.section_index];
-            if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
-                let bounds = outlined_glyph.px_bounds();
-                let font_atlas_set = font_atlas_sets
-                    .sets
-                    .entry(*section_data.0)
-                    .or_insert_with(FontAtlasSet::default);
+            let font = section_data.1;
+            // Color glyphs, like emoji, are drawn from their image instead of their outline
+            let color_bounds = font.color_glyph_bounds(&glyph);
+            let outlined_glyph = match color_bounds {
+                Some(_) => None,
+                None => font.font.outline_glyph(glyph.clone()),
+            };
+            if let Some(bounds) =
+                color_bounds.or_else(|| outlined_glyph.as_ref().map(OutlinedGlyph::px_bounds))
+            {
+                let font_atlas_set = font_atlas_sets.entry(*section_data.0);
 
-                let atlas_info = font_atlas_set
+                let atlas_info = match font_atlas_set
                     .get_glyph_atlas_info(section_data.2, &placed_glyph)
-                    .map(Ok)
-                    .unwrap_or_else(|| {
-                        font_atlas_set.add_glyph_to_atlas(texture_atlases, textures, outlined_glyph)
-                    })?;
+                {
+                    Some(atlas_info) => atlas_info,
+                    None => match outlined_glyph {
+                        Some(outlined_glyph) => font_atlas_set.add_glyph_to_atlas(
+                            texture_atlases,
+                            textures,
+                            outlined_glyph,
+                            text_settings,
+                        )?,
+                        None => {
+                            let Some(glyph_texture) = font.get_color_glyph_texture(&glyph) else {
+                                continue;
+                            };
+                            font_atlas_set.add_color_glyph_to_atlas(
+                                texture_atlases,
+                                textures,
+                                &glyph,
+                                glyph_texture,
+                                text_settings,
+                            )?
+                        }
+                    },
+                };
 
-                if !text_settings.allow_dynamic_font_size
-                    && font_atlas_set.len() > text_settings.soft_max_font_atlases.get()
+                // With `EvictLeastRecentlyUsed`, font sizes are evicted at the end of the frame by
+                // `evict_font_atlases`
+                let soft_max_font_atlases = text_settings.soft_max_font_atlases.get();
+                if font_atlas_set.len() > soft_max_font_atlases
+                    && !matches!(
+                        text_settings.atlas_overflow,
+                        FontAtlasOverflow::EvictLeastRecentlyUsed { .. }
+                    )
+                    && !text_settings.allow_dynamic_font_size
                 {
                     warn_once!(
                         "warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer. See: https://bevyengine.org/learn/errors/#b0005",
-                        text_settings.soft_max_font_atlases.get());
+                        soft_max_font_atlases);
                 }
 
                 let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
@@ -161,24 +403,86 @@ impl GlyphBrush {
         self.latest_font_id = FontId(font_id.0 + 1);
         font_id
     }
+
+    /// Replaces the font added as `font_id`, after its asset was modified.
+    pub(crate) fn replace_font(&mut self, font_id: FontId, font: FontArc) {
+        self.fonts[font_id.0] = font;
+    }
+
+    /// Returns the font added as `font_id`.
+    pub(crate) fn font(&self, font_id: FontId) -> &FontArc {
+        &self.fonts[font_id.0]
+    }
 }
 
 #[derive(Debug, Clone, Reflect)]
 pub struct PositionedGlyph {
     pub position: Vec2,
+    /// The size of the glyph's quad. This is the size of its atlas rect, scaled for [`FontRenderMode::Sdf`] glyphs.
     pub size: Vec2,
     pub atlas_info: GlyphAtlasInfo,
     pub section_index: usize,
     pub byte_index: usize,
 }
 
+/// The layout box of a character, used for hit testing and selections.
+///
+/// Unlike [`PositionedGlyph`]s, boxes are generated for every laid out character, including whitespace.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct GlyphBox {
+    /// The byte range of the character in the text formed by concatenating the values of all the text's sections.
+    pub byte_range: Range<usize>,
+    /// The advance of the character horizontally, and the height of its line from the font's ascent to its descent vertically.
+    /// The characters of vertical text advance by the height of their font, so their boxes don't overlap.
+    ///
+    /// In the same coordinate space as [`PositionedGlyph::position`].
+    pub rect: Rect,
+}
+
+/// The box of a placeholder [`TextSection`](crate::TextSection) in a text layout.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct TextPlaceholder {
+    /// The index of the placeholder's section.
+    pub section_index: usize,
+    /// The box reserved for the placeholder, in the same coordinate space as [`GlyphBox::rect`].
+    pub rect: Rect,
+}
+
+/// The metrics of a laid out line of text.
+///
+/// Lines without any characters, like the empty lines between consecutive line breaks, aren't included.
+///
+/// The lines of vertical text are its columns. Their [`TextLine::baseline`] is the horizontal position of their
+/// center, and their ascent and descent are the distances from the center to their sides.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct TextLine {
+    /// The line box, from the start of its first character to the end of its last character horizontally, and
+    /// from the largest ascent to the largest descent of its fonts vertically.
+    ///
+    /// In the same coordinate space as [`PositionedGlyph::position`].
+    pub rect: Rect,
+    /// The vertical position of the line's baseline, in the same coordinate space as [`TextLine::rect`].
+    pub baseline: f32,
+    /// The largest distance above the baseline of the line's fonts.
+    pub ascent: f32,
+    /// The largest distance below the baseline of the line's fonts.
+    pub descent: f32,
+    /// The byte range of the line's characters in the text formed by concatenating the values of all the text's
+    /// sections.
+    pub byte_range: Range<usize>,
+    /// The range of the line's characters in the text's [`GlyphBox`]es.
+    pub glyph_box_range: Range<usize>,
+}
+
 #[cfg(feature = "subpixel_glyph_atlas")]
 struct GlyphPlacementAdjuster;
 
 #[cfg(feature = "subpixel_glyph_atlas")]
 impl GlyphPlacementAdjuster {
     #[inline(always)]
-    pub fn new(_: &mut Glyph) -> Self {
+    pub fn new(glyph: &mut Glyph) -> Self {
+        glyph.position.x = SubpixelOffset::snap(glyph.position.x);
+        glyph.position.y = SubpixelOffset::snap(glyph.position.y);
         Self
     }
 
@@ -235,3 +539,204 @@ where
 
     text_bounds
 }
+
+/// Computes the [`GlyphBox`] of each laid out character.
+pub(crate) fn compute_glyph_boxes<T>(
+    section_glyphs: &[SectionGlyph],
+    sections: &[SectionText],
+    get_scaled_font: impl Fn(usize) -> PxScaleFont<T>,
+    text_bounds: Rect,
+    y_axis_orientation: &YAxisOrientation,
+    h_anchor: f32,
+) -> Vec<GlyphBox>
+where
+    T: ab_glyph::Font,
+{
+    let section_starts = sections
+        .iter()
+        .scan(0, |start, section| {
+            let section_start = *start;
+            *start += section.text.len();
+            Some(section_start)
+        })
+        .collect::<Vec<_>>();
+
+    section_glyphs
+        .iter()
+        .map(|sg| {
+            let scaled_font = get_scaled_font(sg.section_index);
+            let glyph = &sg.glyph;
+            let text = sections[sg.section_index].text;
+            let len = text[sg.byte_index..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8);
+            let start = section_starts[sg.section_index] + sg.byte_index;
+
+            let x = glyph.position.x + h_anchor;
+            let top = glyph.position.y - scaled_font.ascent();
+            let bottom = glyph.position.y - scaled_font.descent();
+            let (min_y, max_y) = match y_axis_orientation {
+                YAxisOrientation::BottomToTop => {
+                    (text_bounds.max.y - bottom, text_bounds.max.y - top)
+                }
+                YAxisOrientation::TopToBottom => {
+                    (top - text_bounds.min.y, bottom - text_bounds.min.y)
+                }
+            };
+
+            GlyphBox {
+                byte_range: start..start + len,
+                rect: Rect {
+                    min: Vec2::new(x, min_y),
+                    max: Vec2::new(x + scaled_font.h_advance(glyph.id), max_y),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Computes the [`TextLine`]s of the laid out characters, from their [`GlyphBox`]es computed by
+/// [`compute_glyph_boxes`].
+pub(crate) fn compute_text_lines<T>(
+    section_glyphs: &[SectionGlyph],
+    glyph_boxes: &[GlyphBox],
+    get_scaled_font: impl Fn(usize) -> PxScaleFont<T>,
+    text_bounds: Rect,
+    y_axis_orientation: &YAxisOrientation,
+) -> Vec<TextLine>
+where
+    T: ab_glyph::Font,
+{
+    let mut lines: Vec<TextLine> = Vec::new();
+    for (index, (sg, glyph_box)) in section_glyphs.iter().zip(glyph_boxes).enumerate() {
+        let scaled_font = get_scaled_font(sg.section_index);
+        let ascent = scaled_font.ascent();
+        let descent = -scaled_font.descent();
+        let baseline = match y_axis_orientation {
+            YAxisOrientation::BottomToTop => text_bounds.max.y - sg.glyph.position.y,
+            YAxisOrientation::TopToBottom => sg.glyph.position.y - text_bounds.min.y,
+        };
+        match lines.last_mut() {
+            // The glyphs of a line share a baseline
+            Some(line) if line.baseline == baseline => {
+                line.rect = line.rect.union(glyph_box.rect);
+                line.ascent = line.ascent.max(ascent);
+                line.descent = line.descent.max(descent);
+                line.byte_range.end = glyph_box.byte_range.end;
+                line.glyph_box_range.end = index + 1;
+            }
+            _ => lines.push(TextLine {
+                rect: glyph_box.rect,
+                baseline,
+                ascent,
+                descent,
+                byte_range: glyph_box.byte_range.clone(),
+                glyph_box_range: index..index + 1,
+            }),
+        }
+    }
+    for line in &mut lines {
+        let (to_min, to_max) = match y_axis_orientation {
+            YAxisOrientation::BottomToTop => (line.descent, line.ascent),
+            YAxisOrientation::TopToBottom => (line.ascent, line.descent),
+        };
+        line.rect.min.y = line.baseline - to_min;
+        line.rect.max.y = line.baseline + to_max;
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::{Font as _, PxScale, ScaleFont as _};
+    use bevy_asset::AssetId;
+    use bevy_math::Vec2;
+    use glyph_brush_layout::{SectionGlyph, SectionText};
+
+    use super::GlyphBrush;
+    use crate::{BreakLineOn, Font, JustifyText, OverflowBehavior};
+
+    fn brush() -> (GlyphBrush, Font) {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let mut brush = GlyphBrush::default();
+        brush.add_font(AssetId::default(), font.font.clone());
+        (brush, font)
+    }
+
+    fn section(text: &str) -> SectionText<'_> {
+        SectionText {
+            text,
+            scale: PxScale::from(10.),
+            font_id: glyph_brush_layout::FontId(0),
+        }
+    }
+
+    fn line_count(glyphs: &[SectionGlyph]) -> usize {
+        let mut baselines = glyphs
+            .iter()
+            .map(|sg| sg.glyph.position.y as i32)
+            .collect::<Vec<_>>();
+        baselines.dedup();
+        baselines.len()
+    }
+
+    #[test]
+    fn break_word_only_breaks_long_words() {
+        let (brush, _) = brush();
+        let sections = [section("a bbbbbbbbbbbbbbbbbbbb")];
+        // Room for about five characters
+        let bounds = Vec2::new(32., f32::INFINITY);
+        let layout = |linebreak_behavior| {
+            brush
+                .compute_glyphs(&sections, bounds, JustifyText::Left, linebreak_behavior, 0.)
+                .unwrap()
+        };
+
+        let word_boundary = layout(BreakLineOn::WordBoundary);
+        assert_eq!(line_count(&word_boundary), 2);
+
+        let break_word = layout(BreakLineOn::BreakWord);
+        assert!(line_count(&break_word) > 2);
+        // The short word is still on a line by itself
+        let first_line_y = break_word[0].glyph.position.y;
+        assert!(break_word
+            .iter()
+            .filter(|sg| sg.glyph.position.y == first_line_y)
+            .all(|sg| sg.byte_index < 2));
+    }
+
+    #[test]
+    fn overflowing_text_ends_with_ellipsis() {
+        let (brush, font) = brush();
+        let sections = [section("hello world")];
+        let bounds = Vec2::new(32., 20.);
+        let mut glyphs = brush
+            .compute_glyphs(
+                &sections,
+                bounds,
+                JustifyText::Left,
+                BreakLineOn::NoWrap,
+                0.,
+            )
+            .unwrap();
+        brush.truncate_glyphs(
+            &mut glyphs,
+            &sections,
+            bounds,
+            JustifyText::Left,
+            OverflowBehavior::Ellipsis,
+        );
+
+        let scaled_font = font.font.as_scaled(10.);
+        let last = glyphs.last().unwrap();
+        assert!(last.glyph.position.x + scaled_font.h_advance(last.glyph.id) <= bounds.x + 0.5);
+        let ellipsis = font.font.glyph_id('…');
+        if ellipsis.0 != 0 {
+            assert_eq!(last.glyph.id, ellipsis);
+        } else {
+            assert_eq!(last.glyph.id, font.font.glyph_id('.'));
+        }
+        assert!(glyphs.len() < "hello world".len());
+    }
+}
@@ -10,26 +10,36 @@
 mod error;
 mod font;
 mod font_atlas;
+mod font_atlas_diagnostics;
 mod font_atlas_set;
 mod font_loader;
+mod font_variant;
 mod glyph_brush;
+mod line_break;
 mod pipeline;
+mod system_fonts;
 mod text;
 mod text2d;
+mod vertical_layout;
 
 pub use error::*;
 pub use font::*;
 pub use font_atlas::*;
+pub use font_atlas_diagnostics::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
 pub use pipeline::*;
+pub use system_fonts::*;
 pub use text::*;
 pub use text2d::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Font, JustifyText, Text, Text2dBundle, TextError, TextSection, TextStyle};
+    pub use crate::{
+        Font, JustifyText, Text, Text2dBundle, TextDirection, TextError, TextSection, TextStyle,
+        WritingMode,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -37,10 +47,13 @@ use bevy_asset::AssetApp;
 #[cfg(feature = "default_font")]
 use bevy_asset::{load_internal_binary_asset, Handle};
 use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
 use bevy_render::{
-    camera::CameraUpdateSystem, view::VisibilitySystems, ExtractSchedule, RenderApp,
+    camera::CameraUpdateSystem, render_resource::TextureFormat, view::VisibilitySystems,
+    ExtractSchedule, RenderApp,
 };
 use bevy_sprite::SpriteSystem;
+use bevy_utils::warn_once;
 use std::num::NonZeroUsize;
 
 /// Adds text rendering support to an app.
@@ -59,6 +72,22 @@ pub struct TextSettings {
     /// Allows font size to be set dynamically exceeding the amount set in `soft_max_font_atlases`.
     /// Note each font size has to be generated which can have a strong performance impact.
     pub allow_dynamic_font_size: bool,
+    /// What to do when the atlases for a font run out of space.
+    pub atlas_overflow: FontAtlasOverflow,
+    /// The size in pixels of new font atlas textures. Atlases for glyphs that don't fit in this size are made
+    /// large enough for them.
+    pub atlas_size: UVec2,
+    /// The number of atlases added at once for each new font size.
+    pub initial_atlas_count: NonZeroUsize,
+    /// The format of font atlas textures.
+    ///
+    /// With the 8 bit RGBA and BGRA formats, glyphs are stored as white pixels with their coverage in the alpha
+    /// channel. With [`TextureFormat::R8Unorm`] only the coverage is stored, using a quarter of the memory, and the
+    /// sprite and UI shaders draw single channel textures as white with their red channel as alpha.
+    /// Other formats are replaced with [`TextureFormat::Rgba8UnormSrgb`], see [`TextSettings::atlas_texture_format`].
+    ///
+    /// Color glyphs, like emoji, are always stored in [`TextureFormat::Rgba8UnormSrgb`] atlases of their own.
+    pub atlas_format: TextureFormat,
 }
 
 impl Default for TextSettings {
@@ -66,10 +95,56 @@ impl Default for TextSettings {
         Self {
             soft_max_font_atlases: NonZeroUsize::new(16).unwrap(),
             allow_dynamic_font_size: false,
+            atlas_overflow: FontAtlasOverflow::default(),
+            atlas_size: UVec2::splat(512),
+            initial_atlas_count: NonZeroUsize::MIN,
+            atlas_format: TextureFormat::Rgba8UnormSrgb,
         }
     }
 }
 
+impl TextSettings {
+    /// Returns [`TextSettings::atlas_format`] if it is supported, or [`TextureFormat::Rgba8UnormSrgb`] otherwise.
+    pub fn atlas_texture_format(&self) -> TextureFormat {
+        match self.atlas_format {
+            format @ (TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Rgba8Unorm
+            | TextureFormat::Bgra8UnormSrgb
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::R8Unorm) => format,
+            format => {
+                warn_once!("Font atlases can't use the texture format {format:?}, using Rgba8UnormSrgb instead");
+                TextureFormat::Rgba8UnormSrgb
+            }
+        }
+    }
+}
+
+/// Controls how font atlases handle running out of space, set with [`TextSettings::atlas_overflow`].
+///
+/// Overflows and evictions are counted by [`FontAtlasSets`], and reported by the
+/// [`FontAtlasDiagnosticsPlugin`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontAtlasOverflow {
+    /// Add a new atlas when a glyph doesn't fit in the existing atlases for its font size, and warn once
+    /// when the number of font sizes of a font exceeds [`TextSettings::soft_max_font_atlases`].
+    #[default]
+    Warn,
+    /// When a glyph doesn't fit, double the size of the last atlas for its font size, up to `max_size`
+    /// pixels on each side, before adding a new atlas.
+    Grow { max_size: u32 },
+    /// When the number of font sizes of a font exceeds [`TextSettings::soft_max_font_atlases`], or the font atlas
+    /// textures of all fonts use more than `max_bytes`, remove the atlases of the least recently used font sizes at
+    /// the end of the frame with [`evict_font_atlases`].
+    ///
+    /// Font sizes used in the frame or by the layout of any text are never evicted, so no text has to be laid
+    /// out again.
+    EvictLeastRecentlyUsed {
+        /// The maximum number of bytes used by the font atlas textures of all fonts, or `None` for no maximum.
+        max_bytes: Option<usize>,
+    },
+}
+
 /// Text is rendered for two different view projections, a [`Text2dBundle`] is rendered with a
 /// `BottomToTop` y axis, while UI is rendered with a `TopToBottom` y axis. This matters for text because
 /// the glyph positioning is different in either layout.
@@ -87,9 +162,15 @@ impl Plugin for TextPlugin {
         app.init_asset::<Font>()
             .register_type::<Text>()
             .register_type::<Text2dBounds>()
+            .register_type::<Text2dAnchorBox>()
+            .register_type::<Text2dDepthBias>()
+            .register_type::<Text2dLayoutBounds>()
+            .register_type::<TextShadow>()
+            .register_type::<TextOutline>()
             .init_asset_loader::<FontLoader>()
             .init_resource::<TextSettings>()
             .init_resource::<FontAtlasSets>()
+            .init_resource::<FontAtlasPrewarm>()
             .insert_resource(TextPipeline::default())
             .add_systems(
                 PostUpdate,
@@ -97,6 +178,7 @@ impl Plugin for TextPlugin {
                     calculate_bounds_text2d
                         .in_set(VisibilitySystems::CalculateBounds)
                         .after(update_text2d_layout),
+                    update_text2d_layout_bounds.after(update_text2d_layout),
                     update_text2d_layout
                         .after(font_atlas_set::remove_dropped_font_atlas_sets)
                         // Potential conflict: `Assets<Image>`
@@ -105,8 +187,17 @@ impl Plugin for TextPlugin {
                         // will never modify a pre-existing `Image` asset.
                         .ambiguous_with(CameraUpdateSystem),
                     remove_dropped_font_atlas_sets,
+                    reload_modified_fonts
+                        .after(remove_dropped_font_atlas_sets)
+                        .before(update_text2d_layout),
+                    prewarm_font_atlases
+                        .after(remove_dropped_font_atlas_sets)
+                        .before(update_text2d_layout)
+                        .ambiguous_with(CameraUpdateSystem),
                 ),
-            );
+            )
+            // After all the text of the frame is laid out
+            .add_systems(Last, evict_font_atlases);
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.add_systems(
@@ -0,0 +1,239 @@
+use ab_glyph::{Font as _, FontArc, ScaleFont as _};
+use bevy_math::Vec2;
+use glyph_brush_layout::{
+    BuiltInLineBreaker, GlyphPositioner, Layout, LineBreak, LineBreaker, SectionGeometry,
+    SectionGlyph, SectionText, ToSectionText,
+};
+
+use crate::{BreakLineOn, JustifyText};
+
+/// Lays out `sections` within `bounds`, breaking lines according to `linebreak_behavior`, with
+/// `paragraph_spacing` in physical pixels added after every hard line break.
+pub(crate) fn layout_glyphs<S: ToSectionText>(
+    fonts: &[FontArc],
+    sections: &[S],
+    bounds: Vec2,
+    text_alignment: JustifyText,
+    linebreak_behavior: BreakLineOn,
+    paragraph_spacing: f32,
+) -> Vec<SectionGlyph> {
+    let width = if linebreak_behavior == BreakLineOn::NoWrap {
+        f32::INFINITY
+    } else {
+        bounds.x
+    };
+    let geom = SectionGeometry {
+        bounds: (width, bounds.y),
+        ..Default::default()
+    };
+    let layout = Layout::default().h_align(text_alignment.into());
+
+    let mut glyphs = match linebreak_behavior {
+        BreakLineOn::BreakWord | BreakLineOn::Anywhere => {
+            let sections = sections
+                .iter()
+                .map(ToSectionText::to_section_text)
+                .collect::<Vec<_>>();
+            let long_word_breaks = long_word_breaks(fonts, &sections, width);
+            layout
+                .line_breaker(BreakWordLineBreaker {
+                    long_word_breaks: &long_word_breaks,
+                })
+                .calculate_glyphs(fonts, &geom, &sections)
+        }
+        _ => layout
+            .line_breaker(BuiltInLineBreaker::from(linebreak_behavior))
+            .calculate_glyphs(fonts, &geom, sections),
+    };
+    if glyphs.is_empty() || (paragraph_spacing == 0. && !text_alignment.is_justified()) {
+        return glyphs;
+    }
+    let paragraphs = Paragraphs::new(sections);
+    if text_alignment.is_justified() {
+        justify_lines(
+            &mut glyphs,
+            fonts,
+            sections,
+            &paragraphs,
+            width,
+            text_alignment,
+        );
+    }
+    if paragraph_spacing != 0. {
+        for sg in &mut glyphs {
+            sg.glyph.position.y += paragraphs.index(sg) as f32 * paragraph_spacing;
+        }
+    }
+    glyphs
+}
+
+/// The hard line breaks of a text, which separate its paragraphs.
+struct Paragraphs {
+    /// The number of breaks in the sections before each section, and the offsets of the breaks in the section.
+    section_breaks: Vec<(usize, Vec<usize>)>,
+}
+
+impl Paragraphs {
+    fn new<S: ToSectionText>(sections: &[S]) -> Self {
+        let is_paragraph_break = |c: char| matches!(c, '\n' | '\u{2029}');
+        let mut breaks_before = 0;
+        let section_breaks = sections
+            .iter()
+            .map(|section| {
+                let offsets = section
+                    .to_section_text()
+                    .text
+                    .char_indices()
+                    .filter(|(_, c)| is_paragraph_break(*c))
+                    .map(|(offset, _)| offset)
+                    .collect::<Vec<_>>();
+                let before = breaks_before;
+                breaks_before += offsets.len();
+                (before, offsets)
+            })
+            .collect();
+        Self { section_breaks }
+    }
+
+    /// Returns the index of the paragraph of `sg`, the number of hard line breaks before it.
+    fn index(&self, sg: &SectionGlyph) -> usize {
+        let (before, offsets) = &self.section_breaks[sg.section_index];
+        before + offsets.partition_point(|offset| *offset < sg.byte_index)
+    }
+}
+
+/// Stretches the lines followed by another line of their paragraph to `width`, spreading the extra space between
+/// the spaces of each line.
+fn justify_lines<S: ToSectionText>(
+    glyphs: &mut [SectionGlyph],
+    fonts: &[FontArc],
+    sections: &[S],
+    paragraphs: &Paragraphs,
+    width: f32,
+    text_alignment: JustifyText,
+) {
+    if !width.is_finite() {
+        return;
+    }
+    let is_whitespace = |sg: &SectionGlyph| {
+        sections[sg.section_index].to_section_text().text[sg.byte_index..]
+            .chars()
+            .next()
+            .is_some_and(char::is_whitespace)
+    };
+    let glyph_end = |sg: &SectionGlyph| {
+        sg.glyph.position.x
+            + fonts[sg.font_id.0]
+                .as_scaled(sg.glyph.scale)
+                .h_advance(sg.glyph.id)
+    };
+    // Lines are laid out starting at 0 or ending at 0, depending on the alignment
+    let start = -text_alignment.fraction() * width;
+
+    let mut line_start = 0;
+    while line_start < glyphs.len() {
+        // The glyphs of a line share a baseline
+        let baseline = glyphs[line_start].glyph.position.y;
+        let line_end = line_start
+            + glyphs[line_start..]
+                .iter()
+                .take_while(|sg| sg.glyph.position.y == baseline)
+                .count();
+        let (line, rest) = glyphs[line_start..].split_at_mut(line_end - line_start);
+        line_start = line_end;
+        let is_last_line = match rest.first() {
+            Some(next) => paragraphs.index(next) != paragraphs.index(line.last().unwrap()),
+            None => true,
+        };
+        // Trailing spaces hang past the end of the line
+        let Some(content_end) = line.iter().rposition(|sg| !is_whitespace(sg)) else {
+            continue;
+        };
+        let spaces = line[..content_end]
+            .iter()
+            .filter(|sg| is_whitespace(sg))
+            .count();
+        if is_last_line || spaces == 0 {
+            continue;
+        }
+
+        let line_x = line[0].glyph.position.x;
+        let gap = (width - (glyph_end(&line[content_end]) - line_x)).max(0.) / spaces as f32;
+        let mut offset = start - line_x;
+        for sg in line.iter_mut() {
+            let is_space = is_whitespace(sg);
+            sg.glyph.position.x += offset;
+            if is_space {
+                offset += gap;
+            }
+        }
+    }
+}
+
+/// Finds where the words in each section that are wider than `max_width` should be broken, so that each part
+/// fills a line.
+fn long_word_breaks<'a>(
+    fonts: &[FontArc],
+    sections: &[SectionText<'a>],
+    max_width: f32,
+) -> Vec<(&'a str, Vec<usize>)> {
+    sections
+        .iter()
+        .map(|section| {
+            let font = fonts[section.font_id.0].as_scaled(section.scale);
+            let advance = |c: char| font.h_advance(font.glyph_id(c));
+            let mut breaks = Vec::new();
+            let mut start = 0;
+            for end in BuiltInLineBreaker::UnicodeLineBreaker
+                .line_breaks(section.text)
+                .map(|line_break| line_break.offset())
+                .chain([section.text.len()])
+            {
+                if end <= start {
+                    continue;
+                }
+                let word = &section.text[start..end];
+                if max_width < word.trim_end().chars().map(advance).sum::<f32>() {
+                    let mut width = 0.;
+                    for (index, c) in word.char_indices() {
+                        width += advance(c);
+                        if max_width < width && 0 < index {
+                            breaks.push(start + index);
+                            width = advance(c);
+                        }
+                    }
+                }
+                start = end;
+            }
+            (section.text, breaks)
+        })
+        .collect()
+}
+
+/// Breaks lines at word boundaries, and inside the words that are too long to fit on a line.
+///
+/// `glyph_brush_layout` only passes the text of each section to the line breaker, so sections are identified
+/// by the address of their text.
+#[derive(Debug, Clone, Copy, Hash)]
+struct BreakWordLineBreaker<'a> {
+    /// The text of each section, with the offsets of the breaks inside its long words.
+    long_word_breaks: &'a [(&'a str, Vec<usize>)],
+}
+
+impl LineBreaker for BreakWordLineBreaker<'_> {
+    fn line_breaks<'b>(&self, text: &'b str) -> Box<dyn Iterator<Item = LineBreak> + 'b> {
+        let word_breaks = BuiltInLineBreaker::UnicodeLineBreaker.line_breaks(text);
+        let Some((_, long_word_breaks)) = self
+            .long_word_breaks
+            .iter()
+            .find(|(section, breaks)| std::ptr::eq(*section, text) && !breaks.is_empty())
+        else {
+            return word_breaks;
+        };
+
+        let mut breaks = word_breaks.collect::<Vec<_>>();
+        breaks.extend(long_word_breaks.iter().copied().map(LineBreak::Soft));
+        breaks.sort_by_key(LineBreak::offset);
+        Box::new(breaks.into_iter())
+    }
+}
@@ -1,24 +1,35 @@
 use crate::{
-    compute_text_bounds, error::TextError, glyph_brush::GlyphBrush, scale_value, BreakLineOn, Font,
-    FontAtlasSets, JustifyText, PositionedGlyph, Text, TextSection, TextSettings, YAxisOrientation,
+    compute_text_bounds,
+    error::TextError,
+    font_variant::FontVariant,
+    glyph_brush::{compute_glyph_boxes, compute_text_lines, GlyphBrush},
+    line_break::layout_glyphs,
+    scale_value,
+    vertical_layout::{compute_column_lines, layout_vertical_glyphs},
+    BreakLineOn, Font, FontAtlasSets, FontRenderMode, GlyphBox, JustifyText, OverflowBehavior,
+    PositionedGlyph, Text, TextLine, TextPlaceholder, TextSection, TextSettings, WritingMode,
+    YAxisOrientation,
 };
-use ab_glyph::PxScale;
+use ab_glyph::{Font as _, FontArc, GlyphId, PxScale, PxScaleFont};
 use bevy_asset::{AssetId, Assets, Handle};
+use bevy_color::{Alpha, Color};
 use bevy_ecs::component::Component;
 use bevy_ecs::prelude::ReflectComponent;
 use bevy_ecs::system::Resource;
-use bevy_math::Vec2;
+use bevy_math::{Rect, Vec2};
 use bevy_reflect::prelude::ReflectDefault;
 use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
 use bevy_utils::HashMap;
-use glyph_brush_layout::{FontId, GlyphPositioner, SectionGeometry, SectionText, ToSectionText};
+use glyph_brush_layout::{FontId, SectionGlyph, SectionText, ToSectionText};
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Default, Resource)]
 pub struct TextPipeline {
     brush: GlyphBrush,
-    map_font_id: HashMap<AssetId<Font>, FontId>,
+    map_font_id: HashMap<(AssetId<Font>, FontVariant), FontId>,
 }
 
 /// Render information for a corresponding [`Text`] component.
@@ -28,16 +39,262 @@ pub struct TextPipeline {
 #[reflect(Component, Default)]
 pub struct TextLayoutInfo {
     pub glyphs: Vec<PositionedGlyph>,
+    /// The layout boxes of all the laid out characters, in layout order.
+    pub glyph_boxes: Vec<GlyphBox>,
+    /// The metrics of each laid out line, from the first line to the last.
+    pub lines: Vec<TextLine>,
+    /// The boxes of the laid out placeholder sections, in layout order.
+    pub placeholders: Vec<TextPlaceholder>,
     pub logical_size: Vec2,
+    /// The alignment the text was laid out with, with [`JustifyText::Start`] and [`JustifyText::End`] resolved
+    /// for the direction of the text.
+    pub justify: JustifyText,
+    /// The writing mode the text was laid out with.
+    pub writing_mode: WritingMode,
+}
+
+impl TextLayoutInfo {
+    /// Returns the range of the glyphs of the section at `section_index` in [`TextLayoutInfo::glyphs`].
+    ///
+    /// The range is empty if none of the section's characters have a visible glyph.
+    pub fn section_glyph_range(&self, section_index: usize) -> Range<usize> {
+        // Glyphs are in layout order, so the glyphs of each section are contiguous
+        let start = self
+            .glyphs
+            .partition_point(|glyph| glyph.section_index < section_index);
+        let end = self
+            .glyphs
+            .partition_point(|glyph| glyph.section_index <= section_index);
+        start..end
+    }
+
+    /// Returns the line containing the character at `byte_offset`, or `None` if there isn't one.
+    ///
+    /// Byte offsets index into the text formed by concatenating the values of all the text's sections.
+    pub fn line_at(&self, byte_offset: usize) -> Option<&TextLine> {
+        self.lines
+            .iter()
+            .find(|line| line.byte_range.contains(&byte_offset))
+    }
+
+    /// Returns the rects covering the characters in the byte `range`, one per line.
+    ///
+    /// Byte offsets index into the text formed by concatenating the values of all the text's sections.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects: Vec<Rect> = Vec::new();
+        for glyph_box in self.glyph_boxes.iter().filter(|glyph_box| {
+            range.start < glyph_box.byte_range.end && glyph_box.byte_range.start < range.end
+        }) {
+            match rects.last_mut() {
+                // Boxes on the same line overlap vertically
+                Some(rect)
+                    if glyph_box.rect.min.y < rect.max.y && rect.min.y < glyph_box.rect.max.y =>
+                {
+                    *rect = rect.union(glyph_box.rect);
+                }
+                _ => rects.push(glyph_box.rect),
+            }
+        }
+        rects
+    }
+
+    /// Returns the rects covering the characters of each section of `text` with a visible
+    /// [`TextStyle::background_color`], one per line, with the section's background color.
+    ///
+    /// `text` must be the [`Text`] this layout was computed from.
+    ///
+    /// [`TextStyle::background_color`]: crate::TextStyle::background_color
+    pub fn section_background_rects<'a>(
+        &'a self,
+        text: &'a Text,
+    ) -> impl Iterator<Item = (Rect, Color)> + 'a {
+        text.sections
+            .iter()
+            .scan(0, |start, section| {
+                let range = *start..*start + section.value.len();
+                *start = range.end;
+                Some((range, section.style.background_color))
+            })
+            .filter(|(_, color)| !color.is_fully_transparent())
+            .flat_map(move |(range, color)| {
+                self.selection_rects(range)
+                    .into_iter()
+                    .map(move |rect| (rect, color))
+            })
+    }
+
+    /// Returns the byte offset of the caret position nearest to `point`.
+    ///
+    /// The line is chosen by the vertical position of `point`, then the caret is placed before or after the
+    /// character under `point` depending on which half of the character it is in.
+    /// Points before or after the end of a line snap to the start or end of the line.
+    ///
+    /// Byte offsets index into the text formed by concatenating the values of all the text's sections.
+    pub fn byte_offset_at(&self, point: Vec2) -> usize {
+        let vertical_distance = |rect: Rect| {
+            if point.y < rect.min.y {
+                rect.min.y - point.y
+            } else {
+                (point.y - rect.max.y).max(0.)
+            }
+        };
+        let Some(line_rect) = self
+            .glyph_boxes
+            .iter()
+            .map(|glyph_box| glyph_box.rect)
+            .min_by(|a, b| vertical_distance(*a).total_cmp(&vertical_distance(*b)))
+        else {
+            return 0;
+        };
+        let line = self.glyph_boxes.iter().filter(|glyph_box| {
+            glyph_box.rect.min.y < line_rect.max.y && line_rect.min.y < glyph_box.rect.max.y
+        });
+        let mut last_end = 0;
+        for glyph_box in line {
+            if point.x < glyph_box.rect.center().x {
+                return glyph_box.byte_range.start;
+            }
+            last_end = glyph_box.byte_range.end;
+        }
+        last_end
+    }
+
+    /// Returns a zero width rect with the height of the line at the caret position before the character at
+    /// `byte_offset`, or after the last character if `byte_offset` is at the end of the text.
+    ///
+    /// Carets before a line break are placed at the end of the line, and carets at a soft wrap are placed at the
+    /// start of the next line. Returns `None` if nothing has been laid out.
+    ///
+    /// Byte offsets index into the text formed by concatenating the values of all the text's sections.
+    pub fn caret_rect(&self, byte_offset: usize) -> Option<Rect> {
+        self.caret_rect_with_affinity(byte_offset, CaretAffinity::Downstream)
+    }
+
+    /// Returns a zero width rect with the height of the line at the caret position at `byte_offset`, like
+    /// [`TextLayoutInfo::caret_rect`], with `affinity` choosing the line of carets at a soft wrap.
+    ///
+    /// Returns `None` if nothing has been laid out.
+    pub fn caret_rect_with_affinity(
+        &self,
+        byte_offset: usize,
+        affinity: CaretAffinity,
+    ) -> Option<Rect> {
+        let caret = |x: f32, rect: Rect| Rect {
+            min: Vec2::new(x, rect.min.y),
+            max: Vec2::new(x, rect.max.y),
+        };
+        let next_index = self
+            .glyph_boxes
+            .partition_point(|glyph_box| glyph_box.byte_range.end <= byte_offset);
+        let previous = next_index
+            .checked_sub(1)
+            .map(|index| &self.glyph_boxes[index]);
+        let Some(next) = self.glyph_boxes.get(next_index) else {
+            // After the last character
+            return previous.map(|glyph_box| caret(glyph_box.rect.max.x, glyph_box.rect));
+        };
+        let trailing_edge = match previous {
+            // Characters that aren't laid out, like line breaks, are between the previous character and the
+            // caret, so it is at the end of the previous line
+            Some(_) if byte_offset < next.byte_range.start => previous,
+            // A soft wrap, the characters before and after the caret are on different lines
+            Some(previous)
+                if affinity == CaretAffinity::Upstream
+                    && byte_offset == next.byte_range.start
+                    && previous.rect.max.y <= next.rect.min.y =>
+            {
+                Some(previous)
+            }
+            _ => None,
+        };
+        Some(match trailing_edge {
+            Some(previous) => caret(previous.rect.max.x, previous.rect),
+            None => caret(next.rect.min.x, next.rect),
+        })
+    }
+}
+
+/// Which character a caret position between two characters on different lines is attached to, see
+/// [`TextLayoutInfo::caret_rect_with_affinity`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum CaretAffinity {
+    /// The caret is at the start of the line of the following character.
+    #[default]
+    Downstream,
+    /// The caret is at the end of the line of the preceding character, e.g. after moving the caret to the end of a
+    /// wrapped line.
+    Upstream,
+}
+
+/// The options of a text layout, for [`TextPipeline::queue_text`] and [`TextPipeline::layout_text`].
+///
+/// Usually created from a [`Text`] with [`TextLayoutOptions::from_text`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextLayoutOptions {
+    /// The alignment of the lines. [`JustifyText::Start`] and [`JustifyText::End`] are treated as left-to-right,
+    /// [`TextLayoutOptions::from_text`] resolves them for the direction of the text.
+    pub justify: JustifyText,
+    /// How the text should linebreak when running out of its bounds.
+    pub linebreak_behavior: BreakLineOn,
+    /// What happens to text that doesn't fit in its bounds.
+    pub overflow: OverflowBehavior,
+    /// Extra space between paragraphs in logical pixels, see [`Text::paragraph_spacing`].
+    pub paragraph_spacing: f32,
+    /// A character drawn in place of each grapheme of the text, see [`Text::mask`].
+    pub mask: Option<char>,
+    /// Whether the text is laid out in horizontal lines or in vertical columns.
+    pub writing_mode: WritingMode,
+}
+
+impl TextLayoutOptions {
+    /// Returns the layout options of `text`, with its alignment resolved for its direction.
+    ///
+    /// This detects the direction of text with [`TextDirection::Auto`] from its content, so the options should
+    /// be computed once for each layout.
+    ///
+    /// [`TextDirection::Auto`]: crate::TextDirection::Auto
+    pub fn from_text(text: &Text) -> Self {
+        Self {
+            justify: text.resolved_justify(),
+            writing_mode: text.writing_mode,
+            linebreak_behavior: text.linebreak_behavior,
+            overflow: text.overflow,
+            paragraph_spacing: text.paragraph_spacing,
+            mask: text.mask,
+        }
+    }
 }
 
 impl TextPipeline {
     pub fn get_or_insert_font_id(&mut self, handle: &Handle<Font>, font: &Font) -> FontId {
+        self.get_or_insert_variant_id(handle, font, FontVariant::default())
+    }
+
+    /// Returns the id of the font with the letter spacing, word spacing and features of `variant`.
+    fn get_or_insert_variant_id(
+        &mut self,
+        handle: &Handle<Font>,
+        font: &Font,
+        variant: FontVariant,
+    ) -> FontId {
         let brush = &mut self.brush;
         *self
             .map_font_id
-            .entry(handle.id())
-            .or_insert_with(|| brush.add_font(handle.id(), font.font.clone()))
+            .entry((handle.id(), variant))
+            .or_insert_with(|| brush.add_font(handle.id(), font.with_variant(variant)))
+    }
+
+    /// Replaces the font of `id` with its modified asset, if the font has been used.
+    pub fn reload_font(&mut self, id: AssetId<Font>, font: &Font) {
+        for ((_, variant), font_id) in self
+            .map_font_id
+            .iter()
+            .filter(|((font_asset_id, _), _)| *font_asset_id == id)
+        {
+            self.brush
+                .replace_font(*font_id, font.with_variant(*variant));
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -46,62 +303,70 @@ impl TextPipeline {
         fonts: &Assets<Font>,
         sections: &[TextSection],
         scale_factor: f32,
-        text_alignment: JustifyText,
-        linebreak_behavior: BreakLineOn,
+        options: &TextLayoutOptions,
         bounds: Vec2,
         font_atlas_sets: &mut FontAtlasSets,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         textures: &mut Assets<Image>,
         text_settings: &TextSettings,
         y_axis_orientation: YAxisOrientation,
+        render_mode: FontRenderMode,
     ) -> Result<TextLayoutInfo, TextError> {
-        let mut scaled_fonts = Vec::with_capacity(sections.len());
+        let text_sections = sections;
+        let masked = options.mask.map(|mask| MaskedSections::new(sections, mask));
         let sections = sections
             .iter()
-            .map(|section| {
+            .enumerate()
+            .map(|(index, section)| {
                 let font = fonts
                     .get(&section.style.font)
                     .ok_or(TextError::NoSuchFont)?;
-                let font_id = self.get_or_insert_font_id(&section.style.font, font);
-                let font_size = scale_value(section.style.font_size, scale_factor);
-
-                scaled_fonts.push(ab_glyph::Font::as_scaled(&font.font, font_size));
+                let font_id = self.get_or_insert_variant_id(
+                    &section.style.font,
+                    font,
+                    FontVariant::of(section),
+                );
+                let (text, scale) = section_text_and_scale(section, &font.font, scale_factor);
+                let text = masked
+                    .as_ref()
+                    .and_then(|masked| masked.value(index))
+                    .unwrap_or(text);
 
                 let section = SectionText {
                     font_id,
-                    scale: PxScale::from(font_size),
-                    text: &section.value,
+                    scale,
+                    text,
                 };
 
                 Ok(section)
             })
             .collect::<Result<Vec<_>, _>>()?;
+        let scaled_fonts = sections
+            .iter()
+            .map(|section| {
+                ab_glyph::Font::as_scaled(self.brush.font(section.font_id), section.scale)
+            })
+            .collect::<Vec<_>>();
 
-        let section_glyphs =
-            self.brush
-                .compute_glyphs(&sections, bounds, text_alignment, linebreak_behavior)?;
-
-        if section_glyphs.is_empty() {
+        let Some(mut laid_out) = lay_out_glyphs(
+            &self.brush,
+            &sections,
+            text_sections,
+            &scaled_fonts,
+            options,
+            scale_factor,
+            bounds,
+            &y_axis_orientation,
+        )?
+        else {
             return Ok(TextLayoutInfo::default());
-        }
-
-        let size = compute_text_bounds(&section_glyphs, |index| scaled_fonts[index]).size();
-
-        let h_limit = if bounds.x.is_finite() {
-            bounds.x
-        } else {
-            size.x
         };
-
-        let h_anchor = match text_alignment {
-            JustifyText::Left => 0.0,
-            JustifyText::Center => h_limit * 0.5,
-            JustifyText::Right => h_limit * 1.0,
+        if let Some(masked) = &masked {
+            masked.unmask(&mut laid_out, &sections);
         }
-        .floor();
 
         let glyphs = self.brush.process_glyphs(
-            section_glyphs,
+            laid_out.section_glyphs,
             &sections,
             font_atlas_sets,
             fonts,
@@ -109,20 +374,373 @@ impl TextPipeline {
             textures,
             text_settings,
             y_axis_orientation,
-            h_anchor,
+            laid_out.h_anchor,
+            render_mode,
         )?;
 
         Ok(TextLayoutInfo {
             glyphs,
-            logical_size: size,
+            glyph_boxes: laid_out.glyph_boxes,
+            lines: laid_out.lines,
+            placeholders: laid_out.placeholders,
+            logical_size: laid_out.text_bounds.size(),
+            justify: options.justify,
+            writing_mode: options.writing_mode,
+        })
+    }
+
+    /// Lays out `sections` like [`TextPipeline::queue_text`], without adding the glyphs to font atlases.
+    ///
+    /// The font of each section is `fonts[section_index]`, the fonts referenced by the sections' [`TextStyle`]s
+    /// are ignored. This doesn't need a [`TextPipeline`], [`Assets`] or any other resource, so it can be used to
+    /// draw text with a custom renderer, e.g. as meshes in a 3D scene, using the glyph outlines of the fonts.
+    ///
+    /// Returns [`TextError::NoSuchFont`] if there are fewer fonts than sections.
+    ///
+    /// [`TextStyle`]: crate::TextStyle
+    pub fn layout_text(
+        fonts: &[&Font],
+        sections: &[TextSection],
+        scale_factor: f32,
+        options: &TextLayoutOptions,
+        bounds: Vec2,
+        y_axis_orientation: YAxisOrientation,
+    ) -> Result<TextGlyphLayout, TextError> {
+        if fonts.len() < sections.len() {
+            return Err(TextError::NoSuchFont);
+        }
+        let text_sections = sections;
+        let masked = options.mask.map(|mask| MaskedSections::new(sections, mask));
+        let variant_fonts = sections
+            .iter()
+            .zip(fonts)
+            .map(|(section, font)| font.with_variant(FontVariant::of(section)))
+            .collect::<Vec<_>>();
+        let mut brush = GlyphBrush::default();
+        let mut scaled_fonts = Vec::with_capacity(sections.len());
+        let sections = sections
+            .iter()
+            .zip(fonts)
+            .zip(&variant_fonts)
+            .enumerate()
+            .map(|(index, ((section, font), variant_font))| {
+                let (text, scale) = section_text_and_scale(section, &font.font, scale_factor);
+                let text = masked
+                    .as_ref()
+                    .and_then(|masked| masked.value(index))
+                    .unwrap_or(text);
+                scaled_fonts.push(ab_glyph::Font::as_scaled(variant_font, scale));
+                SectionText {
+                    font_id: brush.add_font(AssetId::default(), variant_font.clone()),
+                    scale,
+                    text,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let Some(mut laid_out) = lay_out_glyphs(
+            &brush,
+            &sections,
+            text_sections,
+            &scaled_fonts,
+            options,
+            scale_factor,
+            bounds,
+            &y_axis_orientation,
+        )?
+        else {
+            return Ok(TextGlyphLayout::default());
+        };
+        if let Some(masked) = &masked {
+            masked.unmask(&mut laid_out, &sections);
+        }
+
+        let text_bounds = laid_out.text_bounds;
+        let glyph_quads = laid_out
+            .section_glyphs
+            .into_iter()
+            .filter_map(|sg| {
+                let bounds = fonts[sg.section_index].glyph_bounds(sg.glyph.clone())?;
+                let min_y = match y_axis_orientation {
+                    YAxisOrientation::BottomToTop => text_bounds.max.y - bounds.max.y,
+                    YAxisOrientation::TopToBottom => bounds.min.y - text_bounds.min.y,
+                };
+                let min = Vec2::new(bounds.min.x + laid_out.h_anchor, min_y);
+                Some(GlyphQuad {
+                    rect: Rect::from_corners(min, min + Vec2::new(bounds.width(), bounds.height())),
+                    glyph_id: sg.glyph.id,
+                    font_size: sg.glyph.scale.y,
+                    section_index: sg.section_index,
+                    byte_index: sg.byte_index,
+                })
+            })
+            .collect();
+
+        Ok(TextGlyphLayout {
+            glyph_quads,
+            glyph_boxes: laid_out.glyph_boxes,
+            lines: laid_out.lines,
+            placeholders: laid_out.placeholders,
+            logical_size: text_bounds.size(),
         })
     }
 }
 
+/// A glyph laid out by [`TextPipeline::layout_text`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphQuad {
+    /// The bounds of the glyph's outline, in the same coordinate space as [`GlyphBox::rect`].
+    pub rect: Rect,
+    /// The glyph in the font of its section.
+    pub glyph_id: GlyphId,
+    /// The size of the font in physical pixels, including the scale factor.
+    pub font_size: f32,
+    /// The index of the glyph's section.
+    pub section_index: usize,
+    /// The byte offset of the glyph's character in the value of its section.
+    pub byte_index: usize,
+}
+
+/// Text laid out by [`TextPipeline::layout_text`].
+///
+/// Unlike a [`TextLayoutInfo`], the glyphs aren't added to font atlases, so they can be drawn by any renderer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextGlyphLayout {
+    /// The glyphs with a visible outline, in layout order.
+    pub glyph_quads: Vec<GlyphQuad>,
+    /// The layout boxes of all the laid out characters, in layout order.
+    pub glyph_boxes: Vec<GlyphBox>,
+    /// The metrics of each laid out line, from the first line to the last.
+    pub lines: Vec<TextLine>,
+    /// The boxes of the laid out placeholder sections, in layout order.
+    pub placeholders: Vec<TextPlaceholder>,
+    /// The size of the laid out text.
+    pub logical_size: Vec2,
+}
+
+/// The glyphs of a text laid out and truncated to its bounds, before they are drawn.
+struct LaidOutGlyphs {
+    section_glyphs: Vec<SectionGlyph>,
+    glyph_boxes: Vec<GlyphBox>,
+    lines: Vec<TextLine>,
+    placeholders: Vec<TextPlaceholder>,
+    text_bounds: Rect,
+    /// The horizontal offset of the glyphs for the text alignment.
+    h_anchor: f32,
+}
+
+/// Returns the text and scale of `section` in the layout, in physical pixels.
+///
+/// Placeholders are laid out as a single [`TextSection::PLACEHOLDER_VALUE`] glyph, scaled to the size of their box.
+fn section_text_and_scale<'a>(
+    section: &'a TextSection,
+    font: &FontArc,
+    scale_factor: f32,
+) -> (&'a str, PxScale) {
+    let Some(size) = section.placeholder else {
+        let font_size = scale_value(section.style.font_size, scale_factor);
+        return (&section.value, PxScale::from(font_size));
+    };
+    let size = size * scale_factor;
+    // The horizontal scale is relative to the height of the font, like the vertical scale
+    let advance = font.h_advance_unscaled(font.glyph_id('\u{FFFC}'));
+    let x = if 0. < advance {
+        size.x * font.height_unscaled() / advance
+    } else {
+        0.
+    };
+    (TextSection::PLACEHOLDER_VALUE, PxScale { x, y: size.y })
+}
+
+/// The values of text sections with each grapheme replaced by a mask character, see [`Text::mask`].
+struct MaskedSections {
+    /// The masked value of each section, or `None` for placeholder sections, which aren't masked.
+    values: Vec<Option<String>>,
+    /// The byte offsets of the grapheme boundaries of each masked value, with the byte offsets of the same
+    /// boundaries in the section's value.
+    boundaries: Vec<Vec<(usize, usize)>>,
+}
+
+impl MaskedSections {
+    fn new(sections: &[TextSection], mask: char) -> Self {
+        let mut values = Vec::with_capacity(sections.len());
+        let mut boundaries = Vec::with_capacity(sections.len());
+        for section in sections {
+            if section.placeholder.is_some() {
+                values.push(None);
+                boundaries.push(Vec::new());
+                continue;
+            }
+            let mut value = String::with_capacity(section.value.len());
+            let mut section_boundaries = vec![(0, 0)];
+            for (start, grapheme) in section.value.grapheme_indices(true) {
+                if grapheme == "\n" || grapheme == "\r\n" {
+                    value.push_str(grapheme);
+                } else {
+                    value.push(mask);
+                }
+                section_boundaries.push((value.len(), start + grapheme.len()));
+            }
+            values.push(Some(value));
+            boundaries.push(section_boundaries);
+        }
+        Self { values, boundaries }
+    }
+
+    /// Returns the masked value of the section at `index`, or `None` if it is a placeholder.
+    fn value(&self, index: usize) -> Option<&str> {
+        self.values[index].as_deref()
+    }
+
+    /// Maps a byte offset in the masked value of the section at `index` to its value.
+    fn section_offset(&self, index: usize, offset: usize) -> usize {
+        let boundaries = &self.boundaries[index];
+        match boundaries.binary_search_by_key(&offset, |(masked, _)| *masked) {
+            Ok(boundary) => boundaries[boundary].1,
+            // Placeholders aren't masked
+            Err(_) if boundaries.is_empty() => offset,
+            Err(boundary) => boundaries[boundary - 1].1,
+        }
+    }
+
+    /// Maps the byte offsets of `laid_out`, laid out from the masked `sections`, to the values of the sections.
+    fn unmask(&self, laid_out: &mut LaidOutGlyphs, sections: &[SectionText]) {
+        // The start of each section in the masked text and in the text, and its length in the masked text
+        let mut starts = Vec::with_capacity(sections.len());
+        let (mut masked_start, mut start) = (0, 0);
+        for (index, section) in sections.iter().enumerate() {
+            starts.push((masked_start, start));
+            masked_start += section.text.len();
+            start += self.section_offset(index, section.text.len());
+        }
+        let text_offset = |offset: usize| {
+            let index = starts
+                .partition_point(|(masked_start, _)| *masked_start <= offset)
+                .saturating_sub(1);
+            let (masked_start, start) = starts[index];
+            start + self.section_offset(index, offset - masked_start)
+        };
+
+        for sg in &mut laid_out.section_glyphs {
+            sg.byte_index = self.section_offset(sg.section_index, sg.byte_index);
+        }
+        for glyph_box in &mut laid_out.glyph_boxes {
+            glyph_box.byte_range =
+                text_offset(glyph_box.byte_range.start)..text_offset(glyph_box.byte_range.end);
+        }
+        for line in &mut laid_out.lines {
+            line.byte_range = text_offset(line.byte_range.start)..text_offset(line.byte_range.end);
+        }
+    }
+}
+
+/// Lays out `sections` with the fonts of `brush`, returning `None` if there are no glyphs.
+///
+/// The glyphs of placeholder sections are removed, and their boxes are returned as [`TextPlaceholder`]s.
+#[allow(clippy::too_many_arguments)]
+fn lay_out_glyphs(
+    brush: &GlyphBrush,
+    sections: &[SectionText],
+    text_sections: &[TextSection],
+    scaled_fonts: &[PxScaleFont<&FontArc>],
+    options: &TextLayoutOptions,
+    scale_factor: f32,
+    bounds: Vec2,
+    y_axis_orientation: &YAxisOrientation,
+) -> Result<Option<LaidOutGlyphs>, TextError> {
+    let text_alignment = options.justify;
+    let paragraph_spacing = scale_value(options.paragraph_spacing, scale_factor);
+    let mut section_glyphs = if options.writing_mode.is_vertical() {
+        brush.compute_vertical_glyphs(
+            sections,
+            bounds,
+            options.writing_mode,
+            text_alignment,
+            options.linebreak_behavior,
+            options.overflow,
+            paragraph_spacing,
+        )
+    } else {
+        let mut section_glyphs = brush.compute_glyphs(
+            sections,
+            bounds,
+            text_alignment,
+            options.linebreak_behavior,
+            paragraph_spacing,
+        )?;
+        brush.truncate_glyphs(
+            &mut section_glyphs,
+            sections,
+            bounds,
+            text_alignment,
+            options.overflow,
+        );
+        section_glyphs
+    };
+
+    if section_glyphs.is_empty() {
+        return Ok(None);
+    }
+
+    let text_bounds = compute_text_bounds(&section_glyphs, |index| scaled_fonts[index]);
+    let size = text_bounds.size();
+
+    let h_limit = if bounds.x.is_finite() {
+        bounds.x
+    } else {
+        size.x
+    };
+
+    let h_anchor = (h_limit * options.writing_mode.horizontal_alignment(text_alignment)).floor();
+
+    let glyph_boxes = compute_glyph_boxes(
+        &section_glyphs,
+        sections,
+        |index| scaled_fonts[index],
+        text_bounds,
+        y_axis_orientation,
+        h_anchor,
+    );
+    let lines = if options.writing_mode.is_vertical() {
+        compute_column_lines(&glyph_boxes)
+    } else {
+        compute_text_lines(
+            &section_glyphs,
+            &glyph_boxes,
+            |index| scaled_fonts[index],
+            text_bounds,
+            y_axis_orientation,
+        )
+    };
+
+    let mut placeholders = Vec::new();
+    let mut glyph_boxes_iter = glyph_boxes.iter();
+    section_glyphs.retain(|sg| {
+        let glyph_box = glyph_boxes_iter.next().unwrap();
+        if text_sections[sg.section_index].placeholder.is_none() {
+            return true;
+        }
+        placeholders.push(TextPlaceholder {
+            section_index: sg.section_index,
+            rect: glyph_box.rect,
+        });
+        false
+    });
+
+    Ok(Some(LaidOutGlyphs {
+        section_glyphs,
+        glyph_boxes,
+        lines,
+        placeholders,
+        text_bounds,
+        h_anchor,
+    }))
+}
+
 #[derive(Debug, Clone)]
 pub struct TextMeasureSection {
     pub text: Box<str>,
-    pub scale: f32,
+    pub scale: PxScale,
     pub font_id: FontId,
 }
 
@@ -131,7 +749,10 @@ pub struct TextMeasureInfo {
     pub fonts: Box<[ab_glyph::FontArc]>,
     pub sections: Box<[TextMeasureSection]>,
     pub justification: JustifyText,
-    pub linebreak_behavior: glyph_brush_layout::BuiltInLineBreaker,
+    pub linebreak_behavior: BreakLineOn,
+    /// The [`Text::paragraph_spacing`] in physical pixels.
+    pub paragraph_spacing: f32,
+    pub writing_mode: WritingMode,
     pub min: Vec2,
     pub max: Vec2,
 }
@@ -142,17 +763,24 @@ impl TextMeasureInfo {
         fonts: &Assets<Font>,
         scale_factor: f32,
     ) -> Result<TextMeasureInfo, TextError> {
+        let options = TextLayoutOptions::from_text(text);
         let sections = &text.sections;
+        let masked = options.mask.map(|mask| MaskedSections::new(sections, mask));
         let mut auto_fonts = Vec::with_capacity(sections.len());
         let mut out_sections = Vec::with_capacity(sections.len());
         for (i, section) in sections.iter().enumerate() {
             match fonts.get(&section.style.font) {
                 Some(font) => {
-                    auto_fonts.push(font.font.clone());
+                    auto_fonts.push(font.with_variant(FontVariant::of(section)));
+                    let (text, scale) = section_text_and_scale(section, &font.font, scale_factor);
+                    let text = masked
+                        .as_ref()
+                        .and_then(|masked| masked.value(i))
+                        .unwrap_or(text);
                     out_sections.push(TextMeasureSection {
                         font_id: FontId(i),
-                        scale: scale_value(section.style.font_size, scale_factor),
-                        text: section.value.clone().into_boxed_str(),
+                        scale,
+                        text: text.into(),
                     });
                 }
                 None => return Err(TextError::NoSuchFont),
@@ -162,26 +790,37 @@ impl TextMeasureInfo {
         Ok(Self::new(
             auto_fonts,
             out_sections,
-            text.justify,
-            text.linebreak_behavior.into(),
+            options.justify,
+            options.linebreak_behavior,
+            scale_value(options.paragraph_spacing, scale_factor),
+            options.writing_mode,
         ))
     }
     fn new(
         fonts: Vec<ab_glyph::FontArc>,
         sections: Vec<TextMeasureSection>,
         justification: JustifyText,
-        linebreak_behavior: glyph_brush_layout::BuiltInLineBreaker,
+        linebreak_behavior: BreakLineOn,
+        paragraph_spacing: f32,
+        writing_mode: WritingMode,
     ) -> Self {
         let mut info = Self {
             fonts: fonts.into_boxed_slice(),
             sections: sections.into_boxed_slice(),
             justification,
             linebreak_behavior,
+            paragraph_spacing,
+            writing_mode,
             min: Vec2::ZERO,
             max: Vec2::ZERO,
         };
 
-        let min = info.compute_size(Vec2::new(0.0, f32::INFINITY));
+        // Unlike `Anywhere`, the breaks inside long words don't reduce the minimum width with `BreakWord`
+        let min_linebreak_behavior = match linebreak_behavior {
+            BreakLineOn::BreakWord => BreakLineOn::WordBoundary,
+            linebreak_behavior => linebreak_behavior,
+        };
+        let min = info.compute_size_with(Vec2::new(0.0, f32::INFINITY), min_linebreak_behavior);
         let max = info.compute_size(Vec2::INFINITY);
         info.min = min;
         info.max = max;
@@ -189,20 +828,36 @@ impl TextMeasureInfo {
     }
 
     pub fn compute_size(&self, bounds: Vec2) -> Vec2 {
-        let sections = &self.sections;
-        let geom = SectionGeometry {
-            bounds: (bounds.x, bounds.y),
-            ..Default::default()
+        self.compute_size_with(bounds, self.linebreak_behavior)
+    }
+
+    fn compute_size_with(&self, bounds: Vec2, linebreak_behavior: BreakLineOn) -> Vec2 {
+        let section_glyphs = if self.writing_mode.is_vertical() {
+            layout_vertical_glyphs(
+                &self.fonts,
+                &self.sections,
+                bounds,
+                self.writing_mode,
+                self.justification,
+                linebreak_behavior,
+                OverflowBehavior::Visible,
+                self.paragraph_spacing,
+            )
+        } else {
+            layout_glyphs(
+                &self.fonts,
+                &self.sections,
+                bounds,
+                self.justification,
+                linebreak_behavior,
+                self.paragraph_spacing,
+            )
         };
-        let section_glyphs = glyph_brush_layout::Layout::default()
-            .h_align(self.justification.into())
-            .line_breaker(self.linebreak_behavior)
-            .calculate_glyphs(&self.fonts, &geom, sections);
 
         compute_text_bounds(&section_glyphs, |index| {
             let font = &self.fonts[index];
-            let font_size = self.sections[index].scale;
-            ab_glyph::Font::into_scaled(font, font_size)
+            let scale = self.sections[index].scale;
+            ab_glyph::Font::into_scaled(font, scale)
         })
         .size()
     }
@@ -212,8 +867,644 @@ impl ToSectionText for TextMeasureSection {
     fn to_section_text(&self) -> SectionText<'_> {
         SectionText {
             text: &self.text,
-            scale: PxScale::from(self.scale),
+            scale: self.scale,
             font_id: self.font_id,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Assets;
+    use bevy_color::Color;
+    use bevy_math::{Rect, Vec2};
+    use bevy_render::texture::Image;
+    use bevy_sprite::TextureAtlasLayout;
+
+    use glyph_brush_layout::SectionText;
+
+    use super::{LaidOutGlyphs, MaskedSections, TextGlyphLayout};
+    use crate::{
+        CaretAffinity, Font, FontAtlasSets, FontRenderMode, GlyphAtlasInfo, GlyphBox, JustifyText,
+        LineHeight, PositionedGlyph, Text, TextError, TextLayoutInfo, TextLayoutOptions,
+        TextPipeline, TextSection, TextSettings, TextStyle, WritingMode, YAxisOrientation,
+    };
+
+    /// Lays out `lines` with every character 10 wide and every line 20 high.
+    fn layout(lines: &[&str]) -> TextLayoutInfo {
+        let mut glyph_boxes = Vec::new();
+        let mut start = 0;
+        for (line, text) in lines.iter().enumerate() {
+            let mut x = 0.;
+            for c in text.chars() {
+                let len = c.len_utf8();
+                glyph_boxes.push(GlyphBox {
+                    byte_range: start..start + len,
+                    rect: Rect::new(x, line as f32 * 20., x + 10., (line + 1) as f32 * 20.),
+                });
+                start += len;
+                x += 10.;
+            }
+        }
+        TextLayoutInfo {
+            glyph_boxes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn selection_rects_has_a_rect_per_line() {
+        let info = layout(&["abc ", "de"]);
+        assert_eq!(
+            info.selection_rects(1..5),
+            vec![Rect::new(10., 0., 40., 20.), Rect::new(0., 20., 10., 40.)]
+        );
+        assert_eq!(info.selection_rects(2..2), vec![]);
+        assert_eq!(info.selection_rects(0..100).len(), 2);
+    }
+
+    #[test]
+    fn section_backgrounds_cover_their_sections() {
+        let info = layout(&["ab cd ", "ef"]);
+        let highlighted = TextStyle {
+            background_color: Color::WHITE,
+            ..Default::default()
+        };
+        let text = Text::from_sections([
+            TextSection::new("ab ", TextStyle::default()),
+            TextSection::new("cd ef", highlighted),
+        ]);
+        let backgrounds = info.section_background_rects(&text).collect::<Vec<_>>();
+        assert_eq!(
+            backgrounds,
+            vec![
+                (Rect::new(30., 0., 60., 20.), Color::WHITE),
+                (Rect::new(0., 20., 20., 40.), Color::WHITE)
+            ]
+        );
+    }
+
+    #[test]
+    fn selection_rects_uses_byte_offsets() {
+        let info = layout(&["aé b"]);
+        // `é` is two bytes long
+        assert_eq!(
+            info.selection_rects(1..3),
+            vec![Rect::new(10., 0., 20., 20.)]
+        );
+    }
+
+    #[test]
+    fn byte_offset_at_picks_the_nearest_caret_position() {
+        let info = layout(&["abc ", "de"]);
+        assert_eq!(info.byte_offset_at(Vec2::new(4., 10.)), 0);
+        assert_eq!(info.byte_offset_at(Vec2::new(6., 10.)), 1);
+        assert_eq!(info.byte_offset_at(Vec2::new(-50., 10.)), 0);
+        assert_eq!(info.byte_offset_at(Vec2::new(500., 10.)), 4);
+        assert_eq!(info.byte_offset_at(Vec2::new(16., 30.)), 6);
+        // points below the last line snap to it
+        assert_eq!(info.byte_offset_at(Vec2::new(500., 500.)), 6);
+        assert_eq!(TextLayoutInfo::default().byte_offset_at(Vec2::ZERO), 0);
+    }
+
+    #[test]
+    fn caret_rect_is_placed_before_the_character() {
+        let info = layout(&["ab", "c"]);
+        assert_eq!(info.caret_rect(1), Some(Rect::new(10., 0., 10., 20.)));
+        assert_eq!(info.caret_rect(2), Some(Rect::new(0., 20., 0., 40.)));
+        assert_eq!(info.caret_rect(3), Some(Rect::new(10., 20., 10., 40.)));
+        assert_eq!(TextLayoutInfo::default().caret_rect(0), None);
+    }
+
+    #[test]
+    fn caret_rect_is_on_the_trailing_edge_of_line_ends() {
+        // A soft wrap between "ab" and "c"
+        let info = layout(&["ab", "c"]);
+        assert_eq!(
+            info.caret_rect_with_affinity(2, CaretAffinity::Upstream),
+            Some(Rect::new(20., 0., 20., 20.))
+        );
+        assert_eq!(
+            info.caret_rect_with_affinity(2, CaretAffinity::Downstream),
+            Some(Rect::new(0., 20., 0., 40.))
+        );
+        assert_eq!(
+            info.caret_rect_with_affinity(1, CaretAffinity::Upstream),
+            Some(Rect::new(10., 0., 10., 20.))
+        );
+
+        // A line break after "ab", which has no glyph box
+        let mut info = layout(&["ab", "c"]);
+        info.glyph_boxes[2].byte_range = 3..4;
+        assert_eq!(info.caret_rect(2), Some(Rect::new(20., 0., 20., 20.)));
+        assert_eq!(info.caret_rect(3), Some(Rect::new(0., 20., 0., 40.)));
+        assert_eq!(info.caret_rect(4), Some(Rect::new(10., 20., 10., 40.)));
+    }
+
+    #[test]
+    fn queued_text_hit_tests_its_glyphs() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let mut fonts = Assets::<Font>::default();
+        let style = TextStyle {
+            font: fonts.add(font),
+            font_size: 20.,
+            ..Default::default()
+        };
+        // Wraps after the space
+        let info = TextPipeline::default()
+            .queue_text(
+                &fonts,
+                &[
+                    TextSection::new("AB ", style.clone()),
+                    TextSection::new("CD", style),
+                ],
+                1.,
+                &TextLayoutOptions::default(),
+                Vec2::new(40., 100.),
+                &mut FontAtlasSets::default(),
+                &mut Assets::<TextureAtlasLayout>::default(),
+                &mut Assets::<Image>::default(),
+                &TextSettings::default(),
+                YAxisOrientation::TopToBottom,
+                FontRenderMode::Bitmap,
+            )
+            .unwrap();
+
+        let boxes = info
+            .glyph_boxes
+            .iter()
+            .map(|glyph_box| (glyph_box.byte_range.clone(), glyph_box.rect))
+            .collect::<Vec<_>>();
+        let ranges = boxes
+            .iter()
+            .map(|(range, _)| range.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![0..1, 1..2, 2..3, 3..4, 4..5]);
+        let rects = boxes.iter().map(|(_, rect)| *rect).collect::<Vec<_>>();
+        // The font is monospaced, so the boxes of each line are the same width and adjacent
+        let advance = rects[0].width();
+        assert!(advance > 0.);
+        for pair in [&rects[0..3], &rects[3..5]]
+            .iter()
+            .flat_map(|line| line.windows(2))
+        {
+            assert!((pair[0].max.x - pair[1].min.x).abs() < 0.01);
+            assert!((pair[1].width() - advance).abs() < 0.01);
+        }
+        assert!(rects[2].max.y <= rects[3].min.y + 0.01);
+
+        // Every visible glyph is drawn inside the box of its character
+        assert_eq!(info.glyphs.len(), 4);
+        for glyph in &info.glyphs {
+            let byte_offset = [0, 3][glyph.section_index] + glyph.byte_index;
+            let (_, rect) = &boxes[byte_offset];
+            assert!(
+                rect.contains(glyph.position),
+                "{glyph:?} is outside {rect:?}"
+            );
+        }
+
+        let b = rects[1];
+        let c = rects[3];
+        let left_half = |rect: Rect| Vec2::new(rect.min.x + 0.25 * advance, rect.center().y);
+        let right_half = |rect: Rect| Vec2::new(rect.max.x - 0.25 * advance, rect.center().y);
+        assert_eq!(info.byte_offset_at(left_half(b)), 1);
+        assert_eq!(info.byte_offset_at(right_half(b)), 2);
+        assert_eq!(info.byte_offset_at(left_half(c)), 3);
+        assert_eq!(info.byte_offset_at(right_half(c)), 4);
+        assert_eq!(
+            info.caret_rect(1),
+            Some(Rect::new(b.min.x, b.min.y, b.min.x, b.max.y))
+        );
+        assert_eq!(
+            info.selection_rects(1..4),
+            vec![Rect::from_corners(b.min, rects[2].max), c]
+        );
+    }
+
+    #[test]
+    fn masked_offsets_index_into_the_section_values() {
+        let style = TextStyle::default();
+        let sections = [
+            TextSection::new("pé\n", style.clone()),
+            TextSection::placeholder(Vec2::splat(10.), style.clone()),
+            TextSection::new("x", style),
+        ];
+        let masked = MaskedSections::new(&sections, '•');
+        assert_eq!(masked.value(0), Some("••\n"));
+        assert_eq!(masked.value(1), None);
+        assert_eq!(masked.value(2), Some("•"));
+        // `•` is three bytes long and `é` is two
+        assert_eq!(masked.section_offset(0, 3), 1);
+        assert_eq!(masked.section_offset(0, 7), 4);
+
+        let section_texts = [
+            masked.value(0).unwrap(),
+            TextSection::PLACEHOLDER_VALUE,
+            "•",
+        ]
+        .map(|text| SectionText {
+            text,
+            ..Default::default()
+        });
+        let glyph_box = |byte_range| GlyphBox {
+            byte_range,
+            rect: Rect::default(),
+        };
+        let placeholder_len = TextSection::PLACEHOLDER_VALUE.len();
+        let mut laid_out = LaidOutGlyphs {
+            section_glyphs: Vec::new(),
+            glyph_boxes: vec![
+                glyph_box(0..3),
+                glyph_box(3..6),
+                glyph_box(7..7 + placeholder_len),
+                glyph_box(7 + placeholder_len..10 + placeholder_len),
+            ],
+            lines: Vec::new(),
+            placeholders: Vec::new(),
+            text_bounds: Rect::default(),
+            h_anchor: 0.,
+        };
+        masked.unmask(&mut laid_out, &section_texts);
+        assert_eq!(
+            laid_out
+                .glyph_boxes
+                .into_iter()
+                .map(|glyph_box| glyph_box.byte_range)
+                .collect::<Vec<_>>(),
+            vec![
+                0..1,
+                1..3,
+                4..4 + placeholder_len,
+                4 + placeholder_len..5 + placeholder_len
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_text_without_assets() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let style = TextStyle {
+            font_size: 20.,
+            ..Default::default()
+        };
+        let sections = [
+            TextSection::new("AB ", style.clone()),
+            TextSection::new("CD", style),
+        ];
+        let layout_text = |fonts: &[&Font], bounds| {
+            TextPipeline::layout_text(
+                fonts,
+                &sections,
+                1.,
+                &TextLayoutOptions::default(),
+                bounds,
+                YAxisOrientation::TopToBottom,
+            )
+        };
+
+        let layout = layout_text(&[&font, &font], Vec2::INFINITY).unwrap();
+        // The space has a layout box but no outline
+        assert_eq!(layout.glyph_boxes.len(), 5);
+        let quads = layout
+            .glyph_quads
+            .iter()
+            .map(|quad| (quad.section_index, quad.byte_index))
+            .collect::<Vec<_>>();
+        assert_eq!(quads, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+        let bounds = Rect::from_corners(Vec2::ZERO, layout.logical_size);
+        for quad in &layout.glyph_quads {
+            assert!(bounds.contains(quad.rect.min) && bounds.contains(quad.rect.max));
+        }
+
+        // Wrapping between the sections puts the second one on a new line
+        let wrapped =
+            layout_text(&[&font, &font], Vec2::new(layout.logical_size.x - 1., 100.)).unwrap();
+        assert!(wrapped.glyph_quads[2].rect.min.y > layout.glyph_quads[0].rect.max.y);
+
+        assert!(matches!(
+            layout_text(&[&font], Vec2::INFINITY),
+            Err(TextError::NoSuchFont)
+        ));
+    }
+
+    #[test]
+    fn letter_and_word_spacing() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let lay_out = |letter_spacing: f32, word_spacing: f32| {
+            let style = TextStyle {
+                font_size: 20.,
+                letter_spacing,
+                word_spacing,
+                ..Default::default()
+            };
+            let layout = TextPipeline::layout_text(
+                &[&font],
+                &[TextSection::new("AB CD", style)],
+                2.,
+                &TextLayoutOptions::default(),
+                Vec2::INFINITY,
+                YAxisOrientation::TopToBottom,
+            )
+            .unwrap();
+            let starts = layout
+                .glyph_boxes
+                .iter()
+                .map(|glyph_box| glyph_box.rect.min.x)
+                .collect::<Vec<_>>();
+            (starts, layout.logical_size.x)
+        };
+
+        let (starts, width) = lay_out(0., 0.);
+        // Spacing is in logical pixels, scaled by the scale factor like the font size
+        let (letter_starts, letter_width) = lay_out(3., 0.);
+        for (index, (start, spaced)) in starts.iter().zip(&letter_starts).enumerate() {
+            assert!((spaced - start - 6. * index as f32).abs() < 0.01);
+        }
+        assert!((letter_width - width - 30.).abs() < 0.01);
+
+        // Word spacing only moves the characters after the space
+        let (word_starts, word_width) = lay_out(0., 5.);
+        for (index, (start, spaced)) in starts.iter().zip(&word_starts).enumerate() {
+            let offset = if index > 2 { 10. } else { 0. };
+            assert!((spaced - start - offset).abs() < 0.01);
+        }
+        assert!((word_width - width - 10.).abs() < 0.01);
+    }
+
+    #[test]
+    fn line_height_and_paragraph_spacing() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let lay_out = |line_height: LineHeight, paragraph_spacing: f32| {
+            let style = TextStyle {
+                font_size: 20.,
+                line_height,
+                ..Default::default()
+            };
+            // Wraps after the first word, then breaks the line
+            TextPipeline::layout_text(
+                &[&font],
+                &[TextSection::new("A A\nA", style)],
+                1.,
+                &TextLayoutOptions {
+                    paragraph_spacing,
+                    ..Default::default()
+                },
+                Vec2::new(15., f32::INFINITY),
+                YAxisOrientation::TopToBottom,
+            )
+            .unwrap()
+        };
+        let line_spacings = |layout: &TextGlyphLayout| {
+            assert_eq!(layout.lines.len(), 3);
+            [
+                layout.lines[1].baseline - layout.lines[0].baseline,
+                layout.lines[2].baseline - layout.lines[1].baseline,
+            ]
+        };
+
+        let auto = lay_out(LineHeight::Auto, 0.);
+        let [wrapped, _] = line_spacings(&auto);
+        assert!(wrapped < 30.);
+
+        for line_height in [LineHeight::Px(40.), LineHeight::RelativeToFont(2.)] {
+            let layout = lay_out(line_height, 0.);
+            for spacing in line_spacings(&layout) {
+                assert!((spacing - 40.).abs() < 0.01);
+            }
+            // The extra space is added between the lines
+            assert!((layout.lines[0].rect.height() - 20.).abs() < 0.01);
+            assert!((layout.logical_size.y - 100.).abs() < 0.01);
+        }
+
+        // Paragraph spacing is only added after the hard line break
+        let layout = lay_out(LineHeight::Px(40.), 10.);
+        let [wrapped, broken] = line_spacings(&layout);
+        assert!((wrapped - 40.).abs() < 0.01);
+        assert!((broken - 50.).abs() < 0.01);
+        assert!((layout.logical_size.y - 110.).abs() < 0.01);
+    }
+
+    #[test]
+    fn justified_lines_fill_the_bounds() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let value = "a b c d e f g h i j k l m n o p\nq r";
+        let width = 100.;
+        let lay_out = |justify: JustifyText| {
+            let layout = TextPipeline::layout_text(
+                &[&font],
+                &[TextSection::new(value, TextStyle::default())],
+                1.,
+                &TextLayoutOptions {
+                    justify,
+                    ..Default::default()
+                },
+                Vec2::new(width, f32::INFINITY),
+                YAxisOrientation::TopToBottom,
+            )
+            .unwrap();
+            // The horizontal extent of the visible characters of each line
+            layout
+                .lines
+                .iter()
+                .map(|line| {
+                    let boxes = layout.glyph_boxes[line.glyph_box_range.clone()]
+                        .iter()
+                        .filter(|glyph_box| &value[glyph_box.byte_range.clone()] != " ")
+                        .collect::<Vec<_>>();
+                    (boxes[0].rect.min.x, boxes.last().unwrap().rect.max.x)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let left = lay_out(JustifyText::JustifiedLeft);
+        assert!(left.len() > 3);
+        let (last_line, lines) = left.split_last().unwrap();
+        let (paragraph_end, lines) = lines.split_last().unwrap();
+        for (start, end) in lines {
+            assert!(start.abs() < 0.01 && (end - width).abs() < 0.01);
+        }
+        for (start, end) in [paragraph_end, last_line] {
+            assert!(start.abs() < 0.01 && *end < width - 1.);
+        }
+
+        let right = lay_out(JustifyText::JustifiedRight);
+        assert_eq!(right.len(), left.len());
+        for ((start, end), (left_start, left_end)) in right.iter().zip(&left) {
+            assert!((end - width).abs() < 0.01);
+            assert!((end - start - (left_end - left_start)).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn line_metrics() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let style = TextStyle {
+            font_size: 20.,
+            ..Default::default()
+        };
+        let sections = [
+            TextSection::new("AB ", style.clone()),
+            TextSection::new("CD", style),
+        ];
+        for top_to_bottom in [true, false] {
+            let layout = TextPipeline::layout_text(
+                &[&font, &font],
+                &sections,
+                1.,
+                &TextLayoutOptions::default(),
+                Vec2::new(40., 100.),
+                if top_to_bottom {
+                    YAxisOrientation::TopToBottom
+                } else {
+                    YAxisOrientation::BottomToTop
+                },
+            )
+            .unwrap();
+            let [first, second] = &layout.lines[..] else {
+                panic!("expected two lines, got {:?}", layout.lines);
+            };
+            assert_eq!(
+                (first.byte_range.clone(), second.byte_range.clone()),
+                (0..3, 3..5)
+            );
+            assert_eq!(second.glyph_box_range, 3..5);
+            for line in [first, second] {
+                assert!((line.ascent + line.descent - 20.).abs() < 0.01);
+                assert!((line.rect.height() - 20.).abs() < 0.01);
+                let boxes = &layout.glyph_boxes[line.glyph_box_range.clone()];
+                assert_eq!(line.rect.min.x, boxes[0].rect.min.x);
+                assert_eq!(line.rect.max.x, boxes.last().unwrap().rect.max.x);
+            }
+            let (ascent, second_line_follows) = if top_to_bottom {
+                (
+                    first.baseline - first.rect.min.y,
+                    first.rect.max.y <= second.rect.min.y + 0.01,
+                )
+            } else {
+                (
+                    first.rect.max.y - first.baseline,
+                    second.rect.max.y <= first.rect.min.y + 0.01,
+                )
+            };
+            // The ascent is on the side of the baseline towards the first line
+            assert!((ascent - first.ascent).abs() < 0.01);
+            assert!(second_line_follows);
+        }
+    }
+
+    #[test]
+    fn section_glyph_ranges() {
+        let glyph = |section_index| PositionedGlyph {
+            position: Vec2::ZERO,
+            size: Vec2::ZERO,
+            atlas_info: GlyphAtlasInfo {
+                texture_atlas: Default::default(),
+                texture: Default::default(),
+                glyph_index: 0,
+                is_color: false,
+            },
+            section_index,
+            byte_index: 0,
+        };
+        let info = TextLayoutInfo {
+            glyphs: [0, 0, 2, 2, 2].map(glyph).to_vec(),
+            ..Default::default()
+        };
+        assert_eq!(info.section_glyph_range(0), 0..2);
+        // Sections without glyphs have empty ranges
+        assert_eq!(info.section_glyph_range(1), 2..2);
+        assert_eq!(info.section_glyph_range(2), 2..5);
+        assert_eq!(info.section_glyph_range(3), 5..5);
+    }
+
+    #[test]
+    fn placeholders_reserve_boxes() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let style = TextStyle {
+            font_size: 20.,
+            ..Default::default()
+        };
+        let sections = [
+            TextSection::new("A", style.clone()),
+            TextSection::placeholder(Vec2::new(30., 40.), style.clone()),
+            TextSection::new("B", style),
+        ];
+        let layout = TextPipeline::layout_text(
+            &[&font, &font, &font],
+            &sections,
+            2.,
+            &TextLayoutOptions::default(),
+            Vec2::INFINITY,
+            YAxisOrientation::TopToBottom,
+        )
+        .unwrap();
+
+        // The placeholder isn't drawn
+        let quads = layout
+            .glyph_quads
+            .iter()
+            .map(|quad| quad.section_index)
+            .collect::<Vec<_>>();
+        assert_eq!(quads, vec![0, 2]);
+        let [placeholder] = &layout.placeholders[..] else {
+            panic!("expected one placeholder, got {:?}", layout.placeholders);
+        };
+        assert_eq!(placeholder.section_index, 1);
+        // The box is scaled by the scale factor, and the characters after it are moved past it
+        assert!(
+            (placeholder.rect.size() - Vec2::new(60., 80.))
+                .abs()
+                .max_element()
+                < 0.01
+        );
+        assert_eq!(placeholder.rect.min.x, layout.glyph_boxes[0].rect.max.x);
+        assert_eq!(layout.glyph_boxes[2].rect.min.x, placeholder.rect.max.x);
+        assert_eq!(layout.glyph_boxes[2].byte_range, 4..5);
+        // The line is tall enough for the placeholder
+        assert!(80. <= layout.lines[0].rect.height());
+    }
+    #[test]
+    fn vertical_text_is_laid_out_in_columns() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let style = TextStyle {
+            font_size: 20.,
+            ..Default::default()
+        };
+        let layout = TextPipeline::layout_text(
+            &[&font],
+            &[TextSection::new("AB\nC", style)],
+            1.,
+            &TextLayoutOptions {
+                writing_mode: WritingMode::VerticalRl,
+                ..Default::default()
+            },
+            Vec2::INFINITY,
+            YAxisOrientation::TopToBottom,
+        )
+        .unwrap();
+
+        let rects = layout
+            .glyph_boxes
+            .iter()
+            .map(|glyph_box| glyph_box.rect)
+            .collect::<Vec<_>>();
+        assert_eq!(rects.len(), 3);
+        // "B" is below "A", and "C" starts a column to the left of them
+        assert!((rects[0].max.y - rects[1].min.y).abs() < 0.01);
+        assert_eq!(rects[0].center().x, rects[1].center().x);
+        assert!(rects[2].max.x < rects[0].min.x);
+        assert_eq!(rects[2].min.y, rects[0].min.y);
+        assert_eq!(layout.lines.len(), 2);
+        assert_eq!(layout.lines[0].byte_range, 0..2);
+        assert_eq!(layout.lines[1].byte_range, 3..4);
+        // The text is as tall as its longest column, and ends at its right edge
+        assert!((layout.logical_size.y - rects[1].max.y).abs() < 0.01);
+        assert!((layout.logical_size.x - rects[0].max.x).abs() < 0.01);
+        for quad in &layout.glyph_quads {
+            assert!(
+                Rect::from_corners(Vec2::ZERO, layout.logical_size).contains(quad.rect.center())
+            );
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::{App, Plugin, PreUpdate, Startup};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::{ResMut, Resource};
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{
+    tracing::{debug, warn},
+    HashMap, HashSet,
+};
+use ttf_parser::{name, name_id, PlatformId, RawFace, Tag};
+
+use crate::Font;
+
+/// Adds the [`SystemFonts`] resource, which finds the fonts installed on the system
+/// and loads them as [`Font`] assets on demand.
+///
+/// The font directories are scanned once, in a task on the [`IoTaskPool`] started at startup, so startup isn't
+/// blocked. [`SystemFonts`] is empty until the scan has finished, see [`SystemFonts::is_scanning`].
+/// Only `.ttf` and `.otf` files are found, font collections are skipped.
+/// This plugin isn't part of the [`TextPlugin`](crate::TextPlugin) and has no effect on `wasm32`.
+#[derive(Default)]
+pub struct SystemFontPlugin;
+
+impl Plugin for SystemFontPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SystemFonts>()
+            .add_systems(Startup, start_system_font_scan)
+            .add_systems(PreUpdate, finish_system_font_scan);
+    }
+}
+
+/// A font file installed on the system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemFontFace {
+    /// The path of the font file.
+    pub path: PathBuf,
+    /// The family name of the font, e.g. `"DejaVu Sans"`.
+    pub family: String,
+    /// The style of the font within its family, e.g. `"Bold"`.
+    pub subfamily: String,
+}
+
+/// The fonts installed on the system.
+///
+/// See [`SystemFontPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct SystemFonts {
+    faces: Vec<SystemFontFace>,
+    loaded: HashMap<PathBuf, Handle<Font>>,
+    /// The result of the scan in progress, set by its task once it has finished.
+    scan: Option<Arc<Mutex<Option<Vec<SystemFontFace>>>>>,
+}
+
+impl SystemFonts {
+    /// Finds the fonts in the system's font directories.
+    ///
+    /// This blocks until all the directories have been scanned, [`SystemFontPlugin`] scans them in a task instead.
+    pub fn scan() -> Self {
+        Self {
+            faces: scan_font_directories(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns true while the font directories are being scanned in the background.
+    pub fn is_scanning(&self) -> bool {
+        self.scan.is_some()
+    }
+
+    /// All the font files that were found, in order of family and subfamily.
+    pub fn faces(&self) -> &[SystemFontFace] {
+        &self.faces
+    }
+
+    /// The names of the font families that were found, in alphabetical order.
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(|(i, face)| *i == 0 || self.faces[i - 1].family != face.family)
+            .map(|(_, face)| face.family.as_str())
+    }
+
+    /// Loads the regular style of the font `family`, or its first style if there is no regular style.
+    ///
+    /// Family names are compared case-insensitively.
+    /// Returns `None` if the family wasn't found or its font file couldn't be read.
+    pub fn load(&mut self, family: &str, fonts: &mut Assets<Font>) -> Option<Handle<Font>> {
+        let face = self
+            .family_faces(family)
+            .find(|face| face.subfamily.eq_ignore_ascii_case("regular"))
+            .or_else(|| self.family_faces(family).next())?
+            .clone();
+        self.load_face(&face, fonts)
+    }
+
+    /// Loads the `subfamily` style of the font `family`, for example `"Bold"` or `"Italic"`.
+    ///
+    /// Names are compared case-insensitively.
+    /// Returns `None` if the font wasn't found or its font file couldn't be read.
+    pub fn load_style(
+        &mut self,
+        family: &str,
+        subfamily: &str,
+        fonts: &mut Assets<Font>,
+    ) -> Option<Handle<Font>> {
+        let face = self
+            .family_faces(family)
+            .find(|face| face.subfamily.eq_ignore_ascii_case(subfamily))?
+            .clone();
+        self.load_face(&face, fonts)
+    }
+
+    /// Loads `face`. Each font file is only loaded once, later calls return the same handle.
+    pub fn load_face(
+        &mut self,
+        face: &SystemFontFace,
+        fonts: &mut Assets<Font>,
+    ) -> Option<Handle<Font>> {
+        if let Some(handle) = self.loaded.get(&face.path) {
+            return Some(handle.clone());
+        }
+        let font = std::fs::read(&face.path)
+            .ok()
+            .and_then(|bytes| Font::try_from_bytes(bytes).ok());
+        let Some(font) = font else {
+            warn!("Failed to load the system font {:?}", face.path);
+            return None;
+        };
+        let handle = fonts.add(font);
+        self.loaded.insert(face.path.clone(), handle.clone());
+        Some(handle)
+    }
+
+    fn family_faces<'a>(&'a self, family: &'a str) -> impl Iterator<Item = &'a SystemFontFace> {
+        self.faces
+            .iter()
+            .filter(move |face| face.family.eq_ignore_ascii_case(family))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn font_directories() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn font_directories() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut directories = Vec::new();
+    if cfg!(target_os = "windows") {
+        if let Some(windows) = std::env::var_os("WINDIR") {
+            directories.push(PathBuf::from(windows).join("Fonts"));
+        }
+        if let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") {
+            directories.push(PathBuf::from(local_app_data).join("Microsoft/Windows/Fonts"));
+        }
+    } else if cfg!(target_os = "macos") {
+        directories.push("/System/Library/Fonts".into());
+        directories.push("/Library/Fonts".into());
+        directories.extend(home.map(|home| home.join("Library/Fonts")));
+    } else {
+        directories.push("/usr/share/fonts".into());
+        directories.push("/usr/local/share/fonts".into());
+        if let Some(home) = home {
+            directories.push(home.join(".local/share/fonts"));
+            directories.push(home.join(".fonts"));
+        }
+    }
+    directories
+}
+
+/// Starts scanning the font directories for [`SystemFonts`] in a task.
+pub fn start_system_font_scan(mut system_fonts: ResMut<SystemFonts>) {
+    if system_fonts.is_scanning() {
+        return;
+    }
+    let scan = Arc::new(Mutex::new(None));
+    system_fonts.scan = Some(scan.clone());
+    IoTaskPool::get()
+        .spawn(async move {
+            let faces = scan_font_directories();
+            *scan.lock().unwrap() = Some(faces);
+        })
+        .detach();
+}
+
+/// Moves the fonts found by the scan started in [`start_system_font_scan`] to [`SystemFonts`] once it has finished.
+pub fn finish_system_font_scan(mut system_fonts: ResMut<SystemFonts>) {
+    let Some(scan) = &system_fonts.scan else {
+        return;
+    };
+    let Some(faces) = scan.lock().unwrap().take() else {
+        return;
+    };
+    debug!("Found {} system fonts", faces.len());
+    system_fonts.faces = faces;
+    system_fonts.scan = None;
+}
+
+fn scan_font_directories() -> Vec<SystemFontFace> {
+    let mut faces = Vec::new();
+    let mut visited = HashSet::default();
+    for directory in font_directories() {
+        scan_directory(&directory, &mut visited, &mut faces);
+    }
+    faces.sort_by(|a, b| (&a.family, &a.subfamily).cmp(&(&b.family, &b.subfamily)));
+    faces
+}
+
+/// Finds the font files in `directory` and its subdirectories.
+///
+/// Symbolic links are followed, but each directory is only scanned once so links to a parent directory don't
+/// recurse forever.
+fn scan_directory(
+    directory: &Path,
+    visited: &mut HashSet<PathBuf>,
+    faces: &mut Vec<SystemFontFace>,
+) {
+    let Ok(canonical) = directory.canonicalize() else {
+        return;
+    };
+    if !visited.insert(canonical) {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_directory(&path, visited, faces);
+            continue;
+        }
+        let is_font = path.extension().is_some_and(|extension| {
+            ["ttf", "otf"]
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        });
+        if !is_font {
+            continue;
+        }
+        let Some((family, subfamily)) = File::open(&path)
+            .ok()
+            .and_then(|mut file| font_names(&mut file))
+        else {
+            continue;
+        };
+        faces.push(SystemFontFace {
+            path,
+            family,
+            subfamily,
+        });
+    }
+}
+
+/// Reads the family and subfamily names of an OpenType font.
+///
+/// Only the table directory and the `name` table are read, not the whole font.
+fn font_names(font: &mut (impl Read + Seek)) -> Option<(String, String)> {
+    let mut directory = vec![0; 12];
+    font.read_exact(&mut directory).ok()?;
+    let num_tables = u16::from_be_bytes([directory[4], directory[5]]) as usize;
+    directory.resize(12 + 16 * num_tables, 0);
+    font.read_exact(&mut directory[12..]).ok()?;
+
+    let record = RawFace::parse(&directory, 0)
+        .ok()?
+        .table_records
+        .into_iter()
+        .find(|record| record.tag == Tag::from_bytes(b"name"))?;
+    let mut data = vec![0; record.length as usize];
+    font.seek(SeekFrom::Start(record.offset as u64)).ok()?;
+    font.read_exact(&mut data).ok()?;
+    let table = name::Table::parse(&data)?;
+
+    // Prefer English Windows names, then Unicode names, then Macintosh Roman names.
+    let best_name = |id: u16| {
+        table
+            .names
+            .into_iter()
+            .filter(|name| name.name_id == id)
+            .filter_map(|name| {
+                let english = name.platform_id == PlatformId::Windows && name.language_id == 0x409;
+                match name.to_string() {
+                    Some(value) => Some((if english { 0 } else { 1 }, value)),
+                    None if name.platform_id == PlatformId::Macintosh
+                        && name.encoding_id == 0
+                        && name.name.is_ascii() =>
+                    {
+                        Some((2, String::from_utf8_lossy(name.name).into_owned()))
+                    }
+                    None => None,
+                }
+            })
+            .min_by_key(|(priority, _)| *priority)
+            .map(|(_, value)| value)
+    };
+    Some((
+        best_name(name_id::FAMILY)?,
+        best_name(name_id::SUBFAMILY).unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use bevy_utils::HashSet;
+
+    use super::{font_names, scan_directory};
+
+    #[test]
+    fn font_names_are_read_from_the_name_table() {
+        let data = include_bytes!("FiraMono-subset.ttf");
+        assert_eq!(
+            font_names(&mut Cursor::new(data)),
+            Some(("Fira Mono Medium".to_string(), "Regular".to_string()))
+        );
+        assert_eq!(font_names(&mut Cursor::new(&data[..8])), None);
+        assert_eq!(font_names(&mut Cursor::new([])), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symbolic_link_loops_are_scanned_once() {
+        let directory =
+            std::env::temp_dir().join(format!("bevy_text_fonts_{}", std::process::id()));
+        let fonts = directory.join("fonts");
+        std::fs::create_dir_all(&fonts).unwrap();
+        std::fs::write(
+            fonts.join("FiraMono.ttf"),
+            include_bytes!("FiraMono-subset.ttf"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(&directory, fonts.join("parent")).unwrap();
+
+        let mut faces = Vec::new();
+        scan_directory(&directory, &mut HashSet::default(), &mut faces);
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].family, "Fira Mono Medium");
+    }
+}
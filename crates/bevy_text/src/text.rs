@@ -1,6 +1,7 @@
 use bevy_asset::Handle;
-use bevy_color::Color;
+use bevy_color::{Alpha, Color, LinearRgba};
 use bevy_ecs::{prelude::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
 use bevy_reflect::prelude::*;
 use bevy_utils::default;
 use serde::{Deserialize, Serialize};
@@ -14,8 +15,26 @@ pub struct Text {
     /// The text's internal alignment.
     /// Should not affect its position within a container.
     pub justify: JustifyText,
+    /// The direction of the text's paragraphs, which decides the sides [`JustifyText::Start`] and
+    /// [`JustifyText::End`] align to.
+    pub direction: TextDirection,
+    /// Whether the text is laid out in horizontal lines or in vertical columns.
+    pub writing_mode: WritingMode,
     /// How the text should linebreak when running out of the bounds determined by `max_size`
     pub linebreak_behavior: BreakLineOn,
+    /// What happens to text that doesn't fit in its bounds.
+    pub overflow: OverflowBehavior,
+    /// How the glyphs of the text are rasterized and drawn.
+    pub render_mode: FontRenderMode,
+    /// A character drawn in place of each grapheme of the text, e.g. `'•'` to hide passwords.
+    ///
+    /// The values of the sections aren't changed, and the byte offsets of the text's [`TextLayoutInfo`] index into
+    /// them. Line breaks aren't masked.
+    ///
+    /// [`TextLayoutInfo`]: crate::TextLayoutInfo
+    pub mask: Option<char>,
+    /// Extra space between paragraphs, in logical pixels, added after every hard line break, e.g. `\n`.
+    pub paragraph_spacing: f32,
 }
 
 impl Text {
@@ -36,6 +55,7 @@ impl Text {
     ///         font: font_handle.clone(),
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// );
     ///
@@ -45,6 +65,7 @@ impl Text {
     ///         font: font_handle,
     ///         font_size: 60.0,
     ///         color: Color::WHITE,
+    ///         ..Default::default()
     ///     },
     /// ) // You can still add text justifaction.
     /// .with_justify(JustifyText::Center);
@@ -73,6 +94,7 @@ impl Text {
     ///             font: font_handle.clone(),
     ///             font_size: 60.0,
     ///             color: BLUE.into(),
+    ///             ..Default::default()
     ///         },
     ///     ),
     ///     TextSection::new(
@@ -81,6 +103,7 @@ impl Text {
     ///             font: font_handle,
     ///             font_size: 60.0,
     ///             color: RED.into(),
+    ///             ..Default::default()
     ///         },
     ///     ),
     /// ]);
@@ -98,26 +121,92 @@ impl Text {
         self
     }
 
+    /// Returns this [`Text`] with a new [`TextDirection`].
+    pub const fn with_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Returns this [`Text`] with a new [`WritingMode`].
+    pub const fn with_writing_mode(mut self, writing_mode: WritingMode) -> Self {
+        self.writing_mode = writing_mode;
+        self
+    }
+
+    /// Returns the direction of the text, detected from its first strongly directional character if it is
+    /// [`TextDirection::Auto`].
+    ///
+    /// This scans the text, the direction a text was laid out with is kept in [`TextLayoutInfo::justify`].
+    ///
+    /// [`TextLayoutInfo::justify`]: crate::TextLayoutInfo::justify
+    pub fn resolved_direction(&self) -> TextDirection {
+        self.direction
+            .resolve(self.sections.iter().map(|section| section.value.as_str()))
+    }
+
+    /// Returns the physical alignment of the text, with [`JustifyText::Start`] and [`JustifyText::End`] resolved
+    /// for its direction.
+    ///
+    /// Vertical text always runs from top to bottom, so its direction isn't detected and
+    /// [`JustifyText::Start`] aligns its columns to the top.
+    pub fn resolved_justify(&self) -> JustifyText {
+        if self.writing_mode.is_vertical() {
+            return self.justify.resolve(TextDirection::LeftToRight);
+        }
+        self.justify.resolve(self.resolved_direction())
+    }
+
     /// Returns this [`Text`] with soft wrapping disabled.
     /// Hard wrapping, where text contains an explicit linebreak such as the escape sequence `\n`, will still occur.
     pub const fn with_no_wrap(mut self) -> Self {
         self.linebreak_behavior = BreakLineOn::NoWrap;
         self
     }
+
+    /// Returns this [`Text`] with a new [`OverflowBehavior`].
+    pub const fn with_overflow(mut self, overflow: OverflowBehavior) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns this [`Text`] with a new [`FontRenderMode`].
+    pub const fn with_render_mode(mut self, render_mode: FontRenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    /// Returns this [`Text`] with each grapheme drawn as `mask`, see [`Text::mask`].
+    pub const fn with_mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Returns this [`Text`] with a new [`Text::paragraph_spacing`].
+    pub const fn with_paragraph_spacing(mut self, paragraph_spacing: f32) -> Self {
+        self.paragraph_spacing = paragraph_spacing;
+        self
+    }
 }
 
 #[derive(Debug, Default, Clone, Reflect)]
 pub struct TextSection {
     pub value: String,
     pub style: TextStyle,
+    /// If set, the section is laid out as an inline box of this size in logical pixels instead of as text, to make
+    /// room for an image or widget flowing with the text. See [`TextSection::placeholder`].
+    pub placeholder: Option<Vec2>,
 }
 
 impl TextSection {
+    /// The value of placeholder sections, the object replacement character.
+    pub const PLACEHOLDER_VALUE: &'static str = "\u{FFFC}";
+
     /// Create a new [`TextSection`].
     pub fn new(value: impl Into<String>, style: TextStyle) -> Self {
         Self {
             value: value.into(),
             style,
+            placeholder: None,
         }
     }
 
@@ -126,6 +215,26 @@ impl TextSection {
         Self {
             value: String::new(),
             style,
+            placeholder: None,
+        }
+    }
+
+    /// Create a placeholder [`TextSection`], an inline box of `size` in logical pixels that flows with the text
+    /// like a single character.
+    ///
+    /// The box sits on the baseline of its line, in the proportions of the ascent and descent of the section's
+    /// font, and the lines grow to fit it. Its position is reported by [`TextLayoutInfo::placeholders`].
+    /// The section's font must be loaded for the text to be laid out, but the placeholder itself isn't drawn.
+    ///
+    /// The value of the section is [`TextSection::PLACEHOLDER_VALUE`], so it is counted as a 3 byte character
+    /// in the byte offsets of the laid out text.
+    ///
+    /// [`TextLayoutInfo::placeholders`]: crate::TextLayoutInfo::placeholders
+    pub fn placeholder(size: Vec2, style: TextStyle) -> Self {
+        Self {
+            value: Self::PLACEHOLDER_VALUE.to_string(),
+            style,
+            placeholder: Some(size),
         }
     }
 }
@@ -155,9 +264,27 @@ impl From<String> for TextSection {
 /// does not affect the text entity's position.
 ///
 /// _Has no affect on a single line text entity._
+///
+/// [`JustifyText::Start`] and [`JustifyText::End`] depend on the [`TextDirection`] of the text, and are resolved
+/// to [`JustifyText::Left`] or [`JustifyText::Right`] with [`Text::resolved_justify`] before the text is laid out.
+/// Likewise [`JustifyText::Justified`] is resolved to [`JustifyText::JustifiedLeft`] or
+/// [`JustifyText::JustifiedRight`].
+///
+/// The default is [`JustifyText::Left`], which doesn't change with the direction of the text. Use
+/// [`JustifyText::Start`] for text that should follow the direction of its content, e.g. in interfaces translated
+/// to right-to-left languages.
+///
+/// The columns of vertical text, see [`WritingMode`], are aligned along their length: [`JustifyText::Left`] and
+/// [`JustifyText::Start`] align them to the top, [`JustifyText::Right`] and [`JustifyText::End`] to the bottom.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 #[reflect(Serialize, Deserialize)]
 pub enum JustifyText {
+    /// Lines are aligned to the side their paragraph starts from,
+    /// the left for left-to-right text and the right for right-to-left text.
+    Start,
+    /// Lines are aligned to the side their paragraph ends at,
+    /// the right for left-to-right text and the left for right-to-left text.
+    End,
     /// Leftmost character is immediately to the right of the render position.
     /// Bounds start from the render position and advance rightwards.
     #[default]
@@ -168,18 +295,166 @@ pub enum JustifyText {
     /// Rightmost character is immediately to the left of the render position.
     /// Bounds start from the render position and advance leftwards.
     Right,
+    /// Lines are stretched to the width of the bounds by widening the spaces between their words.
+    ///
+    /// The last line of each paragraph, and lines without spaces, aren't stretched and are aligned to the side their
+    /// paragraph starts from, like [`JustifyText::Start`]. Text without a bounded width is aligned like
+    /// [`JustifyText::Start`].
+    Justified,
+    /// [`JustifyText::Justified`] with the unstretched lines aligned to the left.
+    JustifiedLeft,
+    /// [`JustifyText::Justified`] with the unstretched lines aligned to the right.
+    JustifiedRight,
+}
+
+impl JustifyText {
+    /// Returns the physical alignment for text in the given direction,
+    /// with [`TextDirection::Auto`] treated as left-to-right.
+    pub const fn resolve(self, direction: TextDirection) -> Self {
+        match (self, direction) {
+            (JustifyText::Start, TextDirection::RightToLeft)
+            | (JustifyText::End, TextDirection::Auto | TextDirection::LeftToRight) => {
+                JustifyText::Right
+            }
+            (JustifyText::Start, _) | (JustifyText::End, _) => JustifyText::Left,
+            (JustifyText::Justified, TextDirection::RightToLeft) => JustifyText::JustifiedRight,
+            (JustifyText::Justified, _) => JustifyText::JustifiedLeft,
+            (justify, _) => justify,
+        }
+    }
+
+    /// The position of the lines in the bounds of the text, from `0.` for lines aligned to the left
+    /// to `1.` for lines aligned to the right.
+    ///
+    /// Unresolved [`JustifyText::Start`] and [`JustifyText::End`] are treated as left-to-right.
+    pub(crate) fn fraction(self) -> f32 {
+        match self.resolve(TextDirection::LeftToRight) {
+            JustifyText::Center => 0.5,
+            JustifyText::Right | JustifyText::JustifiedRight => 1.,
+            _ => 0.,
+        }
+    }
+
+    /// Returns true if lines are stretched to the width of the bounds.
+    pub(crate) const fn is_justified(self) -> bool {
+        matches!(
+            self,
+            JustifyText::Justified | JustifyText::JustifiedLeft | JustifyText::JustifiedRight
+        )
+    }
 }
 
 impl From<JustifyText> for glyph_brush_layout::HorizontalAlign {
     fn from(val: JustifyText) -> Self {
-        match val {
-            JustifyText::Left => glyph_brush_layout::HorizontalAlign::Left,
+        match val.resolve(TextDirection::LeftToRight) {
             JustifyText::Center => glyph_brush_layout::HorizontalAlign::Center,
-            JustifyText::Right => glyph_brush_layout::HorizontalAlign::Right,
+            JustifyText::Right | JustifyText::JustifiedRight => {
+                glyph_brush_layout::HorizontalAlign::Right
+            }
+            _ => glyph_brush_layout::HorizontalAlign::Left,
+        }
+    }
+}
+
+/// The direction of the paragraphs of a [`Text`].
+///
+/// The direction decides which side [`JustifyText::Start`] and [`JustifyText::End`] align lines to.
+/// Glyphs are still laid out in the order of their characters from left to right, as there is no support for
+/// reordering bidirectional text. The direction of vertical text is ignored, see [`WritingMode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize)]
+pub enum TextDirection {
+    /// The direction is detected from the first strongly directional character of the text,
+    /// and is left-to-right if there are none.
+    #[default]
+    Auto,
+    /// Paragraphs start on the left, as in English.
+    LeftToRight,
+    /// Paragraphs start on the right, as in Arabic or Hebrew.
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Returns [`TextDirection::LeftToRight`] or [`TextDirection::RightToLeft`], detecting
+    /// [`TextDirection::Auto`] from the first strongly directional character of `text`.
+    pub fn resolve<'a>(self, text: impl IntoIterator<Item = &'a str>) -> Self {
+        match self {
+            TextDirection::Auto => text
+                .into_iter()
+                .flat_map(str::chars)
+                .find_map(strong_direction)
+                .unwrap_or(TextDirection::LeftToRight),
+            direction => direction,
+        }
+    }
+}
+
+/// Whether the lines of a [`Text`] are horizontal or vertical, and the direction they are stacked in.
+///
+/// Vertical text is laid out in columns of upright glyphs running from top to bottom, each glyph advancing by the
+/// height of its font, and each column as wide as the tallest font in it. Columns are broken at hard line breaks,
+/// and when they run out of the height of the text's bounds after their last whitespace, or at any character for
+/// [`BreakLineOn::AnyCharacter`] and for words longer than a column. Columns are aligned to each other along their
+/// length, see [`JustifyText`].
+///
+/// Letter and word spacing only apply to horizontal text, and [`OverflowBehavior::Ellipsis`] clips vertical text
+/// like [`OverflowBehavior::Clip`], removing the columns that don't fit in the width of the bounds and the glyphs
+/// that don't fit in their height. The hit testing and caret methods of a [`TextLayoutInfo`] assume horizontal
+/// lines.
+///
+/// [`TextLayoutInfo`]: crate::TextLayoutInfo
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize)]
+pub enum WritingMode {
+    /// Horizontal lines stacked from top to bottom, as in English or Arabic.
+    #[default]
+    HorizontalTb,
+    /// Vertical columns stacked from right to left, as in traditional Chinese and Japanese.
+    VerticalRl,
+    /// Vertical columns stacked from left to right, as in Mongolian.
+    VerticalLr,
+}
+
+impl WritingMode {
+    /// Returns true for the vertical writing modes.
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, WritingMode::VerticalRl | WritingMode::VerticalLr)
+    }
+
+    /// The horizontal position of the lines or columns in the bounds of the text, from `0.` for text aligned to
+    /// the left to `1.` for text aligned to the right.
+    pub(crate) fn horizontal_alignment(self, justify: JustifyText) -> f32 {
+        match self {
+            WritingMode::HorizontalTb => justify.fraction(),
+            WritingMode::VerticalRl => 1.,
+            WritingMode::VerticalLr => 0.,
         }
     }
 }
 
+/// Returns the direction of strongly directional characters.
+///
+/// Letters in the right-to-left blocks of Unicode, such as Hebrew, Arabic, Syriac and Thaana, are right-to-left,
+/// and all other letters are left-to-right. Digits, punctuation and whitespace have no strong direction.
+fn strong_direction(c: char) -> Option<TextDirection> {
+    if matches!(
+        c,
+        '\u{0590}'..='\u{08FF}'
+            | '\u{200F}'
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}'
+    ) && (c.is_alphabetic() || c == '\u{200F}')
+    {
+        Some(TextDirection::RightToLeft)
+    } else if c.is_alphabetic() || c == '\u{200E}' {
+        Some(TextDirection::LeftToRight)
+    } else {
+        None
+    }
+}
+
 #[derive(Clone, Debug, Reflect)]
 pub struct TextStyle {
     /// If this is not specified, then
@@ -196,6 +471,19 @@ pub struct TextStyle {
     /// which can have a strong performance impact.
     pub font_size: f32,
     pub color: Color,
+    /// Extra space added after every glyph, in logical pixels. Can be negative to tighten the text.
+    pub letter_spacing: f32,
+    /// Extra space added after every space, in logical pixels, on top of the letter spacing.
+    pub word_spacing: f32,
+    /// The OpenType-like features used to lay out the text.
+    pub font_features: FontFeatures,
+    /// The height of the lines containing the text.
+    pub line_height: LineHeight,
+    /// The color of the boxes drawn behind the characters of the text, e.g. to highlight search matches or
+    /// inline code. Transparent by default.
+    ///
+    /// The boxes span the advances of the characters horizontally, and the ascent and descent of the font vertically.
+    pub background_color: Color,
 }
 
 impl Default for TextStyle {
@@ -204,10 +492,47 @@ impl Default for TextStyle {
             font: Default::default(),
             font_size: 24.0,
             color: Color::WHITE,
+            letter_spacing: 0.,
+            word_spacing: 0.,
+            font_features: FontFeatures::default(),
+            line_height: LineHeight::Auto,
+            background_color: Color::NONE,
         }
     }
 }
 
+/// The height of a line of text, see [`TextStyle::line_height`].
+///
+/// The line height is the distance between the baselines of consecutive lines. Like the line gap of a font, the
+/// difference between the line height and the font size is added below each line except the last, so it doesn't
+/// change the height of single line text or the glyph boxes and lines of a [`TextLayoutInfo`]. Lines containing
+/// sections with different line heights are as tall as the tallest.
+///
+/// [`TextLayoutInfo`]: crate::TextLayoutInfo
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum LineHeight {
+    /// The line height from the metrics of the font, its ascent, descent and line gap.
+    #[default]
+    Auto,
+    /// A multiple of the font size, e.g. `1.5` for lines one and a half times as tall as the font size.
+    RelativeToFont(f32),
+    /// A height in logical pixels.
+    Px(f32),
+}
+
+/// Font features of a [`TextStyle`].
+///
+/// Text isn't shaped, so ligatures, contextual alternates and other features that substitute glyphs aren't
+/// supported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct FontFeatures {
+    /// Lays out every digit with the advance of the font's widest digit, so columns of numbers line up and
+    /// changing numbers don't shift the text around them.
+    pub tabular_numbers: bool,
+}
+
 /// Determines how lines will be broken when preventing text from running out of bounds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
 #[reflect(Serialize, Deserialize)]
@@ -224,6 +549,14 @@ pub enum BreakLineOn {
     /// No soft wrapping, where text is automatically broken up into separate lines when it overflows a boundary, will ever occur.
     /// Hard wrapping, where text contains an explicit linebreak such as the escape sequence `\n`, is still enabled.
     NoWrap,
+    /// Lines will be broken at word boundaries like [`BreakLineOn::WordBoundary`], except for words too long to fit on
+    /// a line by themselves, which are broken at any character.
+    ///
+    /// The minimum width of UI text is the width of its longest word, like `overflow-wrap: break-word` in CSS.
+    BreakWord,
+    /// Lines will be broken like [`BreakLineOn::BreakWord`], but the minimum width of UI text is the width of its
+    /// widest character, like `overflow-wrap: anywhere` in CSS.
+    Anywhere,
 }
 
 impl From<BreakLineOn> for glyph_brush_layout::BuiltInLineBreaker {
@@ -231,10 +564,278 @@ impl From<BreakLineOn> for glyph_brush_layout::BuiltInLineBreaker {
         match val {
             // If `NoWrap` is set the choice of `BuiltInLineBreaker` doesn't matter as the text is given unbounded width and soft wrapping will never occur.
             // But `NoWrap` does not disable hard breaks where a [`Text`] contains a newline character.
-            BreakLineOn::WordBoundary | BreakLineOn::NoWrap => {
-                glyph_brush_layout::BuiltInLineBreaker::UnicodeLineBreaker
-            }
+            // `BreakWord` and `Anywhere` add breaks inside long words to the word boundaries.
+            BreakLineOn::WordBoundary
+            | BreakLineOn::NoWrap
+            | BreakLineOn::BreakWord
+            | BreakLineOn::Anywhere => glyph_brush_layout::BuiltInLineBreaker::UnicodeLineBreaker,
             BreakLineOn::AnyCharacter => glyph_brush_layout::BuiltInLineBreaker::AnyCharLineBreaker,
         }
     }
 }
+
+/// Determines what happens to text that doesn't fit in its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize)]
+pub enum OverflowBehavior {
+    /// All of the text is drawn, even outside of its bounds.
+    #[default]
+    Visible,
+    /// Lines that don't fit vertically are removed, and the characters that don't fit horizontally are removed
+    /// from the end of each line. At least one line is always kept.
+    Clip,
+    /// Text is removed like [`OverflowBehavior::Clip`], and every line with removed text ends with an ellipsis "…",
+    /// including the last visible line when the lines after it were removed.
+    Ellipsis,
+}
+
+/// Determines how the glyphs of a [`Text`] are rasterized and drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize)]
+pub enum FontRenderMode {
+    /// Glyphs are rasterized for each font size they are drawn at, and drawn pixel for pixel.
+    #[default]
+    Bitmap,
+    /// Glyphs are rasterized once per font as signed distance fields at [`SDF_FONT_SIZE`](crate::SDF_FONT_SIZE),
+    /// and scaled to each font size they are drawn at, with edges antialiased from the distance to the outline.
+    ///
+    /// This saves atlas space and rasterization time for text drawn at many sizes or animated font sizes, at the
+    /// cost of rounded corners on small details of large glyphs. Color glyphs, like emoji, are drawn from their
+    /// outline if they have one, and not drawn otherwise.
+    ///
+    /// Only UI text is drawn with distance-based antialiasing,
+    /// [`Text2dBundle`](crate::Text2dBundle)s always use [`FontRenderMode::Bitmap`].
+    Sdf,
+}
+
+/// Draws a shadow behind each glyph of a text entity, for both [`Text2dBundle`](crate::Text2dBundle) and UI text.
+///
+/// The shadow is drawn behind the [`TextOutline`], if the entity has one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct TextShadow {
+    /// The offset of the shadow in logical pixels. Positive `y` values move the shadow down.
+    pub offset: Vec2,
+    /// The radius of the blur in logical pixels, no blur is applied if it is zero.
+    ///
+    /// The blur is approximated by drawing several faint copies of each glyph around the offset.
+    pub blur: f32,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::splat(2.),
+            blur: 0.,
+            color: Color::BLACK.with_alpha(0.75),
+        }
+    }
+}
+
+/// Draws an outline around each glyph of a text entity, for both [`Text2dBundle`](crate::Text2dBundle) and UI text.
+///
+/// The outline is made from copies of each glyph drawn in a ring around it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct TextOutline {
+    /// The width of the outline in logical pixels.
+    pub width: f32,
+    /// The color of the outline.
+    pub color: Color,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            width: 1.,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// A copy of every glyph of a text, drawn behind the glyphs to render a [`TextShadow`] or a [`TextOutline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextEffectLayer {
+    /// The offset of the copy in logical pixels, with `y` pointing down.
+    pub offset: Vec2,
+    /// The color of the copy.
+    pub color: LinearRgba,
+}
+
+/// The number of glyph copies used to approximate the blur of a [`TextShadow`], around its center.
+const SHADOW_BLUR_SAMPLES: usize = 8;
+
+/// Offsets evenly spaced on a circle.
+fn ring(count: usize, radius: f32) -> impl Iterator<Item = Vec2> {
+    (0..count).map(move |i| {
+        let angle = std::f32::consts::TAU * i as f32 / count as f32;
+        radius * Vec2::from_angle(angle)
+    })
+}
+
+impl TextShadow {
+    /// Returns the glyph copies to draw for this shadow.
+    pub fn layers(&self) -> Vec<TextEffectLayer> {
+        let color = LinearRgba::from(self.color);
+        if self.blur <= 0. {
+            return vec![TextEffectLayer {
+                offset: self.offset,
+                color,
+            }];
+        }
+        // Where all the copies overlap they add up to the shadow's alpha, fading out towards the edges.
+        let count = SHADOW_BLUR_SAMPLES + 1;
+        let color = color.with_alpha(1. - (1. - color.alpha).powf(1. / count as f32));
+        std::iter::once(Vec2::ZERO)
+            .chain(ring(SHADOW_BLUR_SAMPLES, 0.5 * self.blur))
+            .map(|offset| TextEffectLayer {
+                offset: self.offset + offset,
+                color,
+            })
+            .collect()
+    }
+}
+
+impl TextOutline {
+    /// Returns the glyph copies to draw for this outline.
+    pub fn layers(&self) -> Vec<TextEffectLayer> {
+        if self.width <= 0. {
+            return Vec::new();
+        }
+        let color = LinearRgba::from(self.color);
+        // Use enough copies that they are about a pixel apart along the ring.
+        let count = ((std::f32::consts::TAU * self.width).ceil() as usize).clamp(8, 32);
+        ring(count, self.width)
+            .map(|offset| TextEffectLayer { offset, color })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        JustifyText, Text, TextDirection, TextOutline, TextSection, TextShadow, WritingMode,
+    };
+    use bevy_color::{Alpha, Color, LinearRgba};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn text_effect_layers() {
+        let shadow = TextShadow {
+            offset: Vec2::new(3., 4.),
+            blur: 0.,
+            color: Color::BLACK,
+        };
+        let layers = shadow.layers();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].offset, Vec2::new(3., 4.));
+        assert_eq!(layers[0].color, LinearRgba::BLACK);
+
+        let blurred = TextShadow {
+            blur: 2.,
+            color: Color::BLACK.with_alpha(0.5),
+            ..shadow
+        };
+        let layers = blurred.layers();
+        assert_eq!(layers.len(), 9);
+        // the overlapping copies add up to the shadow's alpha
+        let coverage = 1.
+            - layers
+                .iter()
+                .fold(1., |a, layer| a * (1. - layer.color.alpha));
+        assert!((coverage - 0.5).abs() < 1e-5);
+
+        let outline = TextOutline {
+            width: 1.,
+            color: Color::WHITE,
+        };
+        let layers = outline.layers();
+        assert_eq!(layers.len(), 8);
+        assert!(layers
+            .iter()
+            .all(|layer| (layer.offset.length() - 1.).abs() < 1e-5
+                && layer.color == LinearRgba::WHITE));
+        assert!(TextOutline {
+            width: 0.,
+            ..outline
+        }
+        .layers()
+        .is_empty());
+    }
+
+    #[test]
+    fn paragraph_direction() {
+        let text = |value: &str| {
+            Text::from_section(value, Default::default()).with_justify(JustifyText::Start)
+        };
+        // Digits and punctuation have no strong direction
+        assert_eq!(
+            text("12. שלום").resolved_direction(),
+            TextDirection::RightToLeft
+        );
+        assert_eq!(
+            text("- مرحبا").resolved_direction(),
+            TextDirection::RightToLeft
+        );
+        assert_eq!(
+            text("Hi שלום").resolved_direction(),
+            TextDirection::LeftToRight
+        );
+        assert_eq!(text("123").resolved_direction(), TextDirection::LeftToRight);
+        assert_eq!(
+            Text::from_sections([
+                TextSection::new("", Default::default()),
+                TextSection::new("שלום", Default::default()),
+            ])
+            .resolved_direction(),
+            TextDirection::RightToLeft
+        );
+
+        assert_eq!(text("שלום").resolved_justify(), JustifyText::Right);
+        assert_eq!(
+            text("שלום")
+                .with_justify(JustifyText::End)
+                .resolved_justify(),
+            JustifyText::Left
+        );
+        assert_eq!(text("Hi").resolved_justify(), JustifyText::Left);
+        assert_eq!(
+            text("Hi")
+                .with_direction(TextDirection::RightToLeft)
+                .resolved_justify(),
+            JustifyText::Right
+        );
+        assert_eq!(
+            text("שלום")
+                .with_justify(JustifyText::Justified)
+                .resolved_justify(),
+            JustifyText::JustifiedRight
+        );
+        assert_eq!(
+            text("hello")
+                .with_justify(JustifyText::Justified)
+                .resolved_justify(),
+            JustifyText::JustifiedLeft
+        );
+        // Physical alignments don't depend on the direction
+        assert_eq!(
+            text("שלום")
+                .with_justify(JustifyText::Left)
+                .resolved_justify(),
+            JustifyText::Left
+        );
+        // The default alignment is physical, and vertical text starts at the top
+        assert_eq!(
+            Text::from_section("שלום", Default::default()).resolved_justify(),
+            JustifyText::Left
+        );
+        assert_eq!(
+            text("שלום")
+                .with_writing_mode(WritingMode::VerticalRl)
+                .resolved_justify(),
+            JustifyText::Left
+        );
+    }
+}
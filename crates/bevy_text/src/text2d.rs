@@ -1,22 +1,23 @@
 use crate::{
-    BreakLineOn, Font, FontAtlasSets, PositionedGlyph, Text, TextError, TextLayoutInfo,
-    TextPipeline, TextSettings, YAxisOrientation,
+    BreakLineOn, Font, FontAtlasSets, FontRenderMode, OverflowBehavior, PositionedGlyph, Text,
+    TextError, TextLayoutInfo, TextLayoutOptions, TextOutline, TextPipeline, TextSettings,
+    TextShadow, YAxisOrientation,
 };
-use bevy_asset::Assets;
+use bevy_asset::{AssetId, Assets};
 use bevy_color::LinearRgba;
 use bevy_ecs::{
     bundle::Bundle,
-    change_detection::{DetectChanges, Ref},
+    change_detection::{DetectChanges, DetectChangesMut, Ref},
     component::Component,
     entity::Entity,
     event::EventReader,
     prelude::With,
-    query::{Changed, Without},
+    query::{Changed, Or, Without},
     reflect::ReflectComponent,
     system::{Commands, Local, Query, Res, ResMut},
 };
-use bevy_math::Vec2;
-use bevy_reflect::Reflect;
+use bevy_math::{Rect, Vec2, Vec3};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
 use bevy_render::{
     primitives::Aabb,
     texture::Image,
@@ -27,6 +28,7 @@ use bevy_sprite::{Anchor, ExtractedSprite, ExtractedSprites, SpriteSource, Textu
 use bevy_transform::prelude::{GlobalTransform, Transform};
 use bevy_utils::HashSet;
 use bevy_window::{PrimaryWindow, Window, WindowScaleFactorChanged};
+use std::ops::Range;
 
 /// The maximum width and height of text. The text will wrap according to the specified size.
 /// Characters out of the bounds after wrapping will be truncated. Text is aligned according to the
@@ -56,6 +58,69 @@ impl Text2dBounds {
     };
 }
 
+/// Selects the box that a [`Text2dBundle`]'s [`Anchor`] positions relative to its transform.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum Text2dAnchorBox {
+    /// Anchor the box around the laid out text.
+    #[default]
+    Text,
+    /// Anchor the box given by the [`Text2dBounds`], along the axes where the bounds are finite.
+    ///
+    /// The text is justified within the width of the bounds, and placed at the top of the bounds, so the
+    /// text's position doesn't change as its contents change.
+    Bounds,
+}
+
+/// Offsets the depth of a [`Text2dBundle`]'s glyphs by this amount, without moving the text.
+///
+/// 2D sprites and text are drawn in order of their depth along the z axis, so a positive bias draws the text in
+/// front of sprites at the same z, and a negative bias draws it behind them.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Text2dDepthBias(pub f32);
+
+/// Returns the offset from the transform of a text2d entity to the bottom left corner of its laid out text.
+///
+/// `layout_info` must be the layout of `text`, its alignment is used instead of resolving the alignment of `text`
+/// again.
+pub fn text2d_offset(
+    text: &Text,
+    layout_info: &TextLayoutInfo,
+    anchor: &Anchor,
+    anchor_box: Text2dAnchorBox,
+    bounds: &Text2dBounds,
+) -> Vec2 {
+    let layout_size = layout_info.logical_size;
+    let anchor_box_size = match anchor_box {
+        Text2dAnchorBox::Text => layout_size,
+        Text2dAnchorBox::Bounds => Vec2::new(
+            if bounds.size.x.is_finite() {
+                bounds.size.x
+            } else {
+                layout_size.x
+            },
+            if bounds.size.y.is_finite() {
+                bounds.size.y
+            } else {
+                layout_size.y
+            },
+        ),
+    };
+    let mut offset = anchor_box_size * -(anchor.as_vec() + 0.5);
+    // Text is placed at the top of its box
+    offset.y += anchor_box_size.y - layout_size.y;
+    // Text that was laid out without a width limit isn't justified within the width of the box yet
+    if text.linebreak_behavior == BreakLineOn::NoWrap && text.overflow == OverflowBehavior::Visible
+    {
+        offset.x += (anchor_box_size.x - layout_size.x)
+            * layout_info
+                .writing_mode
+                .horizontal_alignment(layout_info.justify);
+    }
+    offset
+}
+
 /// The bundle of components needed to draw text in a 2D scene via a 2D `Camera2dBundle`.
 /// [Example usage.](https://github.com/bevyengine/bevy/blob/latest/examples/2d/text2d.rs)
 #[derive(Bundle, Clone, Debug, Default)]
@@ -71,6 +136,8 @@ pub struct Text2dBundle {
     /// `text_anchor` does not affect the internal alignment of the block of text, only
     /// its position.
     pub text_anchor: Anchor,
+    /// Whether `text_anchor` positions the laid out text or its bounds.
+    pub text_anchor_box: Text2dAnchorBox,
     /// The maximum width and height of the text.
     pub text_2d_bounds: Text2dBounds,
     /// The transform of the text.
@@ -91,6 +158,10 @@ pub struct Text2dBundle {
     pub sprite_source: SpriteSource,
 }
 
+/// The distance along the z axis between a [`Text2dBundle`]'s glyphs and its [`TextOutline`],
+/// and between the outline and its [`TextShadow`].
+const TEXT_EFFECT_DEPTH: f32 = 0.001;
+
 /// This system extracts the sprites from the 2D text components and adds them to the
 /// "render world".
 pub fn extract_text2d_sprite(
@@ -106,6 +177,11 @@ pub fn extract_text2d_sprite(
             &TextLayoutInfo,
             &Anchor,
             &GlobalTransform,
+            Option<&TextShadow>,
+            Option<&TextOutline>,
+            Option<&Text2dAnchorBox>,
+            Option<&Text2dBounds>,
+            Option<&Text2dDepthBias>,
         )>,
     >,
 ) {
@@ -116,18 +192,96 @@ pub fn extract_text2d_sprite(
         .unwrap_or(1.0);
     let scaling = GlobalTransform::from_scale(Vec2::splat(scale_factor.recip()).extend(1.));
 
-    for (original_entity, view_visibility, text, text_layout_info, anchor, global_transform) in
-        text2d_query.iter()
+    for (
+        original_entity,
+        view_visibility,
+        text,
+        text_layout_info,
+        anchor,
+        global_transform,
+        shadow,
+        outline,
+        anchor_box,
+        bounds,
+        depth_bias,
+    ) in text2d_query.iter()
     {
         if !view_visibility.get() {
             continue;
         }
 
-        let text_anchor = -(anchor.as_vec() + 0.5);
-        let alignment_translation = text_layout_info.logical_size * text_anchor;
-        let transform = *global_transform
+        let alignment_translation = text2d_offset(
+            text,
+            text_layout_info,
+            anchor,
+            anchor_box.copied().unwrap_or_default(),
+            bounds.unwrap_or(&Text2dBounds::UNBOUNDED),
+        );
+        // The bias is applied in world space, so it isn't affected by the scale of the transform
+        let depth_bias = depth_bias.map_or(0., |depth_bias| depth_bias.0);
+        let transform = GlobalTransform::from_translation(Vec3::Z * depth_bias)
+            * *global_transform
             * GlobalTransform::from_translation(alignment_translation.extend(0.))
             * scaling;
+
+        // Section backgrounds are drawn behind the shadows.
+        for (rect, color) in text_layout_info.section_background_rects(text) {
+            extracted_sprites.sprites.insert(
+                commands.spawn_empty().id(),
+                ExtractedSprite {
+                    transform: transform
+                        * GlobalTransform::from_translation(
+                            rect.center().extend(-3. * TEXT_EFFECT_DEPTH),
+                        ),
+                    color: color.into(),
+                    rect: None,
+                    custom_size: Some(rect.size()),
+                    image_handle_id: AssetId::default(),
+                    flip_x: false,
+                    flip_y: false,
+                    anchor: Anchor::Center.as_vec(),
+                    original_entity: Some(original_entity),
+                },
+            );
+        }
+
+        // Shadows are drawn behind outlines, and both just behind the glyphs.
+        let layers = shadow
+            .map(|shadow| (shadow.layers(), 2. * TEXT_EFFECT_DEPTH))
+            .into_iter()
+            .chain(outline.map(|outline| (outline.layers(), TEXT_EFFECT_DEPTH)));
+        for (layers, depth) in layers {
+            for layer in layers {
+                // The y axis of `Text2d` points up, the y axis of the layer offsets points down.
+                let offset = scale_factor * Vec2::new(layer.offset.x, -layer.offset.y);
+                for PositionedGlyph {
+                    position,
+                    atlas_info,
+                    ..
+                } in &text_layout_info.glyphs
+                {
+                    let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+                    extracted_sprites.sprites.insert(
+                        commands.spawn_empty().id(),
+                        ExtractedSprite {
+                            transform: transform
+                                * GlobalTransform::from_translation(
+                                    (*position + offset).extend(-depth),
+                                ),
+                            color: layer.color,
+                            rect: Some(atlas.textures[atlas_info.glyph_index].as_rect()),
+                            custom_size: None,
+                            image_handle_id: atlas_info.texture.id(),
+                            flip_x: false,
+                            flip_y: false,
+                            anchor: Anchor::Center.as_vec(),
+                            original_entity: Some(original_entity),
+                        },
+                    );
+                }
+            }
+        }
+
         let mut color = LinearRgba::WHITE;
         let mut current_section = usize::MAX;
         for PositionedGlyph {
@@ -148,7 +302,7 @@ pub fn extract_text2d_sprite(
                 entity,
                 ExtractedSprite {
                     transform: transform * GlobalTransform::from_translation(position.extend(0.)),
-                    color,
+                    color: atlas_info.tint(color),
                     rect: Some(atlas.textures[atlas_info.glyph_index].as_rect()),
                     custom_size: None,
                     image_handle_id: atlas_info.texture.id(),
@@ -197,7 +351,9 @@ pub fn update_text2d_layout(
     for (entity, text, bounds, mut text_layout_info) in &mut text_query {
         if factor_changed || text.is_changed() || bounds.is_changed() || queue.remove(&entity) {
             let text_bounds = Vec2::new(
-                if text.linebreak_behavior == BreakLineOn::NoWrap {
+                if text.linebreak_behavior == BreakLineOn::NoWrap
+                    && text.overflow == OverflowBehavior::Visible
+                {
                     f32::INFINITY
                 } else {
                     scale_value(bounds.size.x, scale_factor)
@@ -208,14 +364,15 @@ pub fn update_text2d_layout(
                 &fonts,
                 &text.sections,
                 scale_factor,
-                text.justify,
-                text.linebreak_behavior,
+                &TextLayoutOptions::from_text(&text),
                 text_bounds,
                 &mut font_atlas_sets,
                 &mut texture_atlases,
                 &mut textures,
                 text_settings.as_ref(),
                 YAxisOrientation::BottomToTop,
+                // Sprites have no distance-based antialiasing
+                FontRenderMode::Bitmap,
             ) {
                 Err(TextError::NoSuchFont) => {
                     // There was an error processing the text layout, let's add this entity to the
@@ -241,20 +398,41 @@ pub fn scale_value(value: f32, factor: f32) -> f32 {
 }
 
 /// System calculating and inserting an [`Aabb`] component to entities with some
-/// [`TextLayoutInfo`] and [`Anchor`] components, and without a [`NoFrustumCulling`] component.
+/// [`Text`], [`TextLayoutInfo`] and [`Anchor`] components, and without a [`NoFrustumCulling`] component.
 ///
 /// Used in system set [`VisibilitySystems::CalculateBounds`](bevy_render::view::VisibilitySystems::CalculateBounds).
+#[allow(clippy::type_complexity)]
 pub fn calculate_bounds_text2d(
     mut commands: Commands,
     mut text_to_update_aabb: Query<
-        (Entity, &TextLayoutInfo, &Anchor, Option<&mut Aabb>),
-        (Changed<TextLayoutInfo>, Without<NoFrustumCulling>),
+        (
+            Entity,
+            &Text,
+            &TextLayoutInfo,
+            &Anchor,
+            Option<&Text2dAnchorBox>,
+            Option<&Text2dBounds>,
+            Option<&mut Aabb>,
+        ),
+        (
+            Or<(
+                Changed<TextLayoutInfo>,
+                Changed<Anchor>,
+                Changed<Text2dAnchorBox>,
+            )>,
+            Without<NoFrustumCulling>,
+        ),
     >,
 ) {
-    for (entity, layout_info, anchor, aabb) in &mut text_to_update_aabb {
-        // `Anchor::as_vec` gives us an offset relative to the text2d bounds, by negating it and scaling
-        // by the logical size we compensate the transform offset in local space to get the center.
-        let center = (-anchor.as_vec() * layout_info.logical_size)
+    for (entity, text, layout_info, anchor, anchor_box, bounds, aabb) in &mut text_to_update_aabb {
+        // The offset is to the bottom left corner of the text, the center is half its size further.
+        let center = (text2d_offset(
+            text,
+            layout_info,
+            anchor,
+            anchor_box.copied().unwrap_or_default(),
+            bounds.unwrap_or(&Text2dBounds::UNBOUNDED),
+        ) + layout_info.logical_size / 2.0)
             .extend(0.0)
             .into();
         // Distance in local space from the center to the x and y limits of the text2d bounds.
@@ -273,6 +451,165 @@ pub fn calculate_bounds_text2d(
     }
 }
 
+/// The area covered by a text2d entity's laid out text, for hit testing it against points in world space.
+///
+/// Inserted and kept up to date by [`update_text2d_layout_bounds`] for entities with a [`Text`],
+/// [`TextLayoutInfo`] and [`Anchor`].
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Text2dLayoutBounds {
+    /// The bounds of the laid out characters in logical pixels, relative to the entity's transform.
+    ///
+    /// Tighter than the [`Aabb`], which covers the whole [`TextLayoutInfo::logical_size`]. [`Rect::EMPTY`] if no
+    /// characters are laid out.
+    pub rect: Rect,
+    /// The offset from the entity's transform to the bottom left corner of its laid out text, see
+    /// [`text2d_offset`].
+    pub origin: Vec2,
+    /// The scale factor the text was laid out with.
+    pub scale_factor: f32,
+}
+
+impl Default for Text2dLayoutBounds {
+    fn default() -> Self {
+        Self {
+            rect: Rect::EMPTY,
+            origin: Vec2::ZERO,
+            scale_factor: 1.,
+        }
+    }
+}
+
+/// The character of a text2d entity at a point, returned by [`Text2dLayoutBounds::hit_test`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Text2dHit {
+    /// The index of the section containing the character.
+    pub section_index: usize,
+    /// The byte range of the character in the text formed by concatenating the values of all the text's sections.
+    pub byte_range: Range<usize>,
+}
+
+impl Text2dLayoutBounds {
+    /// Computes the bounds of `layout_info`, laid out with `scale_factor` and offset by `origin`.
+    pub fn new(layout_info: &TextLayoutInfo, origin: Vec2, scale_factor: f32) -> Self {
+        let rect = layout_info
+            .glyph_boxes
+            .iter()
+            .map(|glyph_box| glyph_box.rect)
+            .chain(
+                layout_info
+                    .placeholders
+                    .iter()
+                    .map(|placeholder| placeholder.rect),
+            )
+            .fold(Rect::EMPTY, |bounds, rect| bounds.union(rect));
+        let rect = if rect.is_empty() {
+            Rect::EMPTY
+        } else {
+            Rect::from_corners(
+                origin + rect.min / scale_factor,
+                origin + rect.max / scale_factor,
+            )
+        };
+        Self {
+            rect,
+            origin,
+            scale_factor,
+        }
+    }
+
+    /// Maps `world_point` into the local space of a text2d entity with `transform`, in logical pixels.
+    pub fn local_point(transform: &GlobalTransform, world_point: Vec2) -> Vec2 {
+        transform
+            .affine()
+            .inverse()
+            .transform_point3(world_point.extend(transform.translation().z))
+            .truncate()
+    }
+
+    /// Returns true if `world_point` is inside the laid out text of a text2d entity with `transform`.
+    pub fn contains(&self, transform: &GlobalTransform, world_point: Vec2) -> bool {
+        self.rect
+            .contains(Self::local_point(transform, world_point))
+    }
+
+    /// Returns the character of `text` under `world_point`, if any.
+    ///
+    /// `layout_info` must be the layout these bounds were computed from, `transform` the entity's transform.
+    pub fn hit_test(
+        &self,
+        transform: &GlobalTransform,
+        text: &Text,
+        layout_info: &TextLayoutInfo,
+        world_point: Vec2,
+    ) -> Option<Text2dHit> {
+        let local_point = Self::local_point(transform, world_point);
+        if !self.rect.contains(local_point) {
+            return None;
+        }
+        let layout_point = (local_point - self.origin) * self.scale_factor;
+        let glyph_box = layout_info
+            .glyph_boxes
+            .iter()
+            .find(|glyph_box| glyph_box.rect.contains(layout_point))?;
+        let mut section_start = 0;
+        let section_index = text.sections.iter().position(|section| {
+            section_start += section.value.len();
+            glyph_box.byte_range.start < section_start
+        })?;
+        Some(Text2dHit {
+            section_index,
+            byte_range: glyph_box.byte_range.clone(),
+        })
+    }
+}
+
+/// Inserts and updates the [`Text2dLayoutBounds`] of entities with some [`Text`], [`TextLayoutInfo`] and
+/// [`Anchor`] components.
+#[allow(clippy::type_complexity)]
+pub fn update_text2d_layout_bounds(
+    mut commands: Commands,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut text_query: Query<
+        (
+            Entity,
+            &Text,
+            &TextLayoutInfo,
+            &Anchor,
+            Option<&Text2dAnchorBox>,
+            Option<&Text2dBounds>,
+            Option<&mut Text2dLayoutBounds>,
+        ),
+        Or<(
+            Changed<TextLayoutInfo>,
+            Changed<Anchor>,
+            Changed<Text2dAnchorBox>,
+        )>,
+    >,
+) {
+    // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.0);
+
+    for (entity, text, layout_info, anchor, anchor_box, bounds, layout_bounds) in &mut text_query {
+        let origin = text2d_offset(
+            text,
+            layout_info,
+            anchor,
+            anchor_box.copied().unwrap_or_default(),
+            bounds.unwrap_or(&Text2dBounds::UNBOUNDED),
+        );
+        let new_bounds = Text2dLayoutBounds::new(layout_info, origin, scale_factor);
+        if let Some(mut layout_bounds) = layout_bounds {
+            layout_bounds.set_if_neq(new_bounds);
+        } else {
+            commands.entity(entity).try_insert(new_bounds);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -282,6 +619,7 @@ mod tests {
     use bevy_utils::default;
 
     use super::*;
+    use crate::{JustifyText, TextSection};
 
     const FIRST_TEXT: &str = "Sample text.";
     const SECOND_TEXT: &str = "Another, longer sample text.";
@@ -300,6 +638,7 @@ mod tests {
                 (
                     update_text2d_layout,
                     calculate_bounds_text2d.after(update_text2d_layout),
+                    update_text2d_layout_bounds.after(update_text2d_layout),
                 ),
             );
 
@@ -387,4 +726,130 @@ mod tests {
         assert!(FIRST_TEXT.len() < SECOND_TEXT.len());
         assert!(first_aabb.half_extents.x < second_aabb.half_extents.x);
     }
+
+    #[test]
+    fn text2d_offset_anchors_text_or_bounds() {
+        let text = Text::from_section(FIRST_TEXT, default());
+        let mut layout_info = TextLayoutInfo {
+            logical_size: Vec2::new(40., 10.),
+            ..Default::default()
+        };
+        let bounds = Text2dBounds {
+            size: Vec2::new(100., 50.),
+        };
+        let offset = |anchor: Anchor, anchor_box: Text2dAnchorBox, bounds: &Text2dBounds| {
+            text2d_offset(&text, &layout_info, &anchor, anchor_box, bounds)
+        };
+
+        assert_eq!(
+            offset(Anchor::Center, Text2dAnchorBox::Text, &bounds),
+            Vec2::new(-20., -5.)
+        );
+        assert_eq!(
+            offset(Anchor::TopLeft, Text2dAnchorBox::Text, &bounds),
+            Vec2::new(0., -10.)
+        );
+        // The text is at the top of the bounds, and already justified within their width by the layout
+        assert_eq!(
+            offset(Anchor::Center, Text2dAnchorBox::Bounds, &bounds),
+            Vec2::new(-50., 15.)
+        );
+        assert_eq!(
+            offset(Anchor::TopLeft, Text2dAnchorBox::Bounds, &bounds),
+            Vec2::new(0., -10.)
+        );
+        // Unbounded axes use the size of the text
+        assert_eq!(
+            offset(
+                Anchor::Center,
+                Text2dAnchorBox::Bounds,
+                &Text2dBounds::UNBOUNDED
+            ),
+            Vec2::new(-20., -5.)
+        );
+
+        // Text that isn't wrapped is justified within the bounds here
+        let text = text.with_justify(JustifyText::Right).with_no_wrap();
+        layout_info.justify = JustifyText::Right;
+        assert_eq!(
+            text2d_offset(
+                &text,
+                &layout_info,
+                &Anchor::BottomLeft,
+                Text2dAnchorBox::Bounds,
+                &bounds
+            ),
+            Vec2::new(60., 40.)
+        );
+    }
+
+    #[test]
+    fn calculate_bounds_text2d_anchor_box() {
+        let (mut app, entity) = setup();
+        app.world_mut().entity_mut(entity).insert((
+            Text2dBounds {
+                size: Vec2::new(1000., 500.),
+            },
+            Anchor::BottomLeft,
+        ));
+        app.update();
+        let text_aabb = *app.world().get::<Aabb>(entity).unwrap();
+        assert_eq!(text_aabb.center, text_aabb.half_extents);
+
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Text2dAnchorBox::Bounds);
+        app.update();
+        let bounds_aabb = *app.world().get::<Aabb>(entity).unwrap();
+        assert_eq!(bounds_aabb.half_extents, text_aabb.half_extents);
+        assert_eq!(bounds_aabb.center.x, text_aabb.center.x);
+        assert_eq!(bounds_aabb.center.y, 500. - text_aabb.half_extents.y);
+    }
+
+    #[test]
+    fn text2d_hit_test_finds_sections() {
+        let (mut app, entity) = setup();
+        app.world_mut().entity_mut(entity).insert((
+            Text::from_sections([
+                TextSection::new("ab", default()),
+                TextSection::new("cd", default()),
+            ]),
+            Transform::from_xyz(100., 50., 0.),
+            GlobalTransform::from_xyz(100., 50., 0.),
+        ));
+        app.update();
+
+        let world = app.world();
+        let text = world.get::<Text>(entity).unwrap();
+        let layout_info = world.get::<TextLayoutInfo>(entity).unwrap();
+        let layout_bounds = world.get::<Text2dLayoutBounds>(entity).unwrap();
+        let transform = world.get::<GlobalTransform>(entity).unwrap();
+        assert!(!layout_bounds.rect.is_empty());
+
+        let hit_at = |index: usize| {
+            let glyph_center = layout_info.glyph_boxes[index].rect.center();
+            let world_point = Vec2::new(100., 50.) + layout_bounds.origin + glyph_center;
+            layout_bounds.hit_test(transform, text, layout_info, world_point)
+        };
+        assert_eq!(
+            hit_at(1),
+            Some(Text2dHit {
+                section_index: 0,
+                byte_range: 1..2
+            })
+        );
+        assert_eq!(
+            hit_at(2),
+            Some(Text2dHit {
+                section_index: 1,
+                byte_range: 2..3
+            })
+        );
+        assert!(layout_bounds.contains(transform, Vec2::new(100., 50.)));
+        assert!(!layout_bounds.contains(transform, Vec2::ZERO));
+        assert_eq!(
+            layout_bounds.hit_test(transform, text, layout_info, Vec2::ZERO),
+            None
+        );
+    }
 }
@@ -0,0 +1,355 @@
+use ab_glyph::{point, Font as _, FontArc, Glyph, ScaleFont as _};
+use bevy_math::Vec2;
+use glyph_brush_layout::{SectionGlyph, ToSectionText};
+
+use crate::{BreakLineOn, GlyphBox, JustifyText, OverflowBehavior, TextLine, WritingMode};
+
+/// A glyph in a column of vertical text.
+struct ColumnGlyph {
+    sg: SectionGlyph,
+    /// The distance from the top of the column to the top of the glyph.
+    top: f32,
+    /// The height of the glyph's font, which the glyph advances down the column by.
+    height: f32,
+    /// The height of the glyph's font plus its line gap, the distance to the next column.
+    line_advance: f32,
+    /// The distance from the top of the glyph to its baseline.
+    ascent: f32,
+    /// The horizontal advance of the glyph, which is centered in the column.
+    width: f32,
+    is_whitespace: bool,
+}
+
+/// A column of vertical text, the equivalent of a line of horizontal text.
+#[derive(Default)]
+struct Column {
+    glyphs: Vec<ColumnGlyph>,
+    /// The width and advance of an empty column, from the font of the line break ending it.
+    empty_size: (f32, f32),
+    /// True if the column is followed by a hard line break.
+    ends_paragraph: bool,
+}
+
+impl Column {
+    fn length(&self) -> f32 {
+        self.glyphs
+            .last()
+            .map_or(0., |glyph| glyph.top + glyph.height)
+    }
+
+    /// The number of glyphs before the trailing whitespace of the column, which hangs past its end.
+    fn content_len(&self) -> usize {
+        self.glyphs
+            .iter()
+            .rposition(|glyph| !glyph.is_whitespace)
+            .map_or(0, |index| index + 1)
+    }
+
+    fn content_length(&self) -> f32 {
+        self.glyphs[..self.content_len()]
+            .last()
+            .map_or(0., |glyph| glyph.top + glyph.height)
+    }
+
+    /// Moves the glyphs after the last whitespace of the column to a new column, or none of them when breaking at
+    /// any character or if there is no whitespace.
+    fn wrap(&mut self, linebreak_behavior: BreakLineOn) -> Column {
+        let split = match linebreak_behavior {
+            BreakLineOn::AnyCharacter => self.glyphs.len(),
+            _ => self
+                .glyphs
+                .iter()
+                .rposition(|glyph| glyph.is_whitespace)
+                .map_or(self.glyphs.len(), |index| index + 1),
+        };
+        let mut glyphs = self.glyphs.split_off(split);
+        let top = glyphs.first().map_or(0., |glyph| glyph.top);
+        for glyph in &mut glyphs {
+            glyph.top -= top;
+        }
+        Column {
+            glyphs,
+            ..Default::default()
+        }
+    }
+}
+
+/// Lays out `sections` in columns for a vertical `writing_mode`, like
+/// [`layout_glyphs`](crate::line_break::layout_glyphs) lays out horizontal text in lines, with
+/// `paragraph_spacing` in physical pixels added after every hard line break.
+///
+/// Glyph positions are the left of their advance and their baseline, like horizontal glyphs, with the top of the
+/// columns at `y = 0`. Columns are stacked leftwards ending at `x = 0` for [`WritingMode::VerticalRl`], and
+/// rightwards starting at `x = 0` otherwise. Unless `overflow` is [`OverflowBehavior::Visible`], the columns that
+/// don't fit in the width of `bounds` and the glyphs that don't fit in its height are removed, keeping at least
+/// one column and the first glyph of each column.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_vertical_glyphs<S: ToSectionText>(
+    fonts: &[FontArc],
+    sections: &[S],
+    bounds: Vec2,
+    writing_mode: WritingMode,
+    text_alignment: JustifyText,
+    linebreak_behavior: BreakLineOn,
+    overflow: OverflowBehavior,
+    paragraph_spacing: f32,
+) -> Vec<SectionGlyph> {
+    let max_length = if linebreak_behavior == BreakLineOn::NoWrap {
+        f32::INFINITY
+    } else {
+        bounds.y
+    };
+
+    let mut columns = vec![Column::default()];
+    for (section_index, section) in sections.iter().enumerate() {
+        let section = section.to_section_text();
+        let font = fonts[section.font_id.0].as_scaled(section.scale);
+        let height = font.height();
+        let line_advance = height + font.line_gap();
+        for (byte_index, c) in section.text.char_indices() {
+            if matches!(c, '\n' | '\u{2029}') {
+                let column = columns.last_mut().unwrap();
+                column.empty_size = (height, line_advance);
+                column.ends_paragraph = true;
+                columns.push(Column::default());
+                continue;
+            }
+            if c.is_control() {
+                continue;
+            }
+            let is_whitespace = c.is_whitespace();
+            let column = columns.last_mut().unwrap();
+            // Whitespace hangs past the end of a full column
+            if !is_whitespace && !column.glyphs.is_empty() && max_length < column.length() + height
+            {
+                let next = column.wrap(linebreak_behavior);
+                columns.push(next);
+            }
+
+            let column = columns.last_mut().unwrap();
+            let id = font.glyph_id(c);
+            let top = column.length();
+            column.glyphs.push(ColumnGlyph {
+                sg: SectionGlyph {
+                    section_index,
+                    byte_index,
+                    glyph: Glyph {
+                        id,
+                        scale: section.scale,
+                        position: point(0., 0.),
+                    },
+                    font_id: section.font_id,
+                },
+                top,
+                height,
+                line_advance,
+                ascent: font.ascent(),
+                width: font.h_advance(id),
+                is_whitespace,
+            });
+        }
+    }
+
+    // Columns are aligned within the length of the longest column
+    let longest = columns
+        .iter()
+        .map(Column::content_length)
+        .fold(0., f32::max);
+    let alignment = text_alignment.fraction();
+    let clip = overflow != OverflowBehavior::Visible;
+    // Tolerance for rounding errors in the bounds
+    let (max_x, max_y) = (bounds.x + 0.5, bounds.y + 0.5);
+    let last_column = columns.len() - 1;
+
+    let mut glyphs = Vec::new();
+    let (mut min_x, mut max_end) = (f32::MAX, f32::MIN);
+    // The distance from the side the columns are stacked from
+    let mut x = 0.;
+    for (index, column) in columns.into_iter().enumerate() {
+        let (width, advance) = column.glyphs.iter().fold(
+            if column.glyphs.is_empty() {
+                column.empty_size
+            } else {
+                (0., 0.)
+            },
+            |(width, advance), glyph| (width.max(glyph.height), advance.max(glyph.line_advance)),
+        );
+        if clip && 0 < index && max_x < x + width {
+            break;
+        }
+
+        let content_len = column.content_len();
+        let extra = (longest - column.content_length()).max(0.);
+        let is_last_line = column.ends_paragraph || index == last_column;
+        let (offset, gap) = if text_alignment.is_justified() && !is_last_line && 1 < content_len {
+            (0., extra / (content_len - 1) as f32)
+        } else {
+            (alignment * extra, 0.)
+        };
+        let center = match writing_mode {
+            WritingMode::VerticalRl => -(x + 0.5 * width),
+            _ => x + 0.5 * width,
+        };
+        for (glyph_index, glyph) in column.glyphs.into_iter().enumerate() {
+            let top =
+                offset + glyph.top + glyph_index.min(content_len.saturating_sub(1)) as f32 * gap;
+            if clip && 0 < glyph_index && max_y < top + glyph.height {
+                break;
+            }
+            let mut sg = glyph.sg;
+            sg.glyph.position = point(center - 0.5 * glyph.width, top + glyph.ascent);
+            min_x = min_x.min(sg.glyph.position.x);
+            max_end = max_end.max(sg.glyph.position.x + glyph.width);
+            glyphs.push(sg);
+        }
+
+        x += advance;
+        if column.ends_paragraph {
+            x += paragraph_spacing;
+        }
+    }
+
+    // The glyphs are centered in their columns, move the outer edge of the first column to `x = 0`
+    let shift = match writing_mode {
+        WritingMode::VerticalRl => -max_end,
+        _ => -min_x,
+    };
+    for sg in &mut glyphs {
+        sg.glyph.position.x += shift;
+    }
+    glyphs
+}
+
+/// Computes the [`TextLine`]s of vertical text, one for each column, from the [`GlyphBox`]es of its characters.
+///
+/// The baseline of a column is the horizontal position of its center, and its ascent and descent are the
+/// distances from its center to its sides.
+pub(crate) fn compute_column_lines(glyph_boxes: &[GlyphBox]) -> Vec<TextLine> {
+    let mut lines: Vec<TextLine> = Vec::new();
+    for (index, glyph_box) in glyph_boxes.iter().enumerate() {
+        let center = glyph_box.rect.center().x;
+        match lines.last_mut() {
+            // The glyphs of a column share a center
+            Some(line) if (line.baseline - center).abs() < 0.5 => {
+                line.rect = line.rect.union(glyph_box.rect);
+                line.byte_range.end = glyph_box.byte_range.end;
+                line.glyph_box_range.end = index + 1;
+            }
+            _ => lines.push(TextLine {
+                rect: glyph_box.rect,
+                baseline: center,
+                ascent: 0.,
+                descent: 0.,
+                byte_range: glyph_box.byte_range.clone(),
+                glyph_box_range: index..index + 1,
+            }),
+        }
+    }
+    for line in &mut lines {
+        line.ascent = 0.5 * line.rect.width();
+        line.descent = line.ascent;
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::{Font as _, PxScale, ScaleFont as _};
+    use bevy_math::Vec2;
+    use glyph_brush_layout::{FontId, SectionText};
+
+    use super::layout_vertical_glyphs;
+    use crate::{BreakLineOn, Font, JustifyText, OverflowBehavior, WritingMode};
+
+    #[test]
+    fn vertical_text_is_laid_out_in_columns() {
+        let font = Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap();
+        let fonts = [font.font.clone()];
+        let scaled_font = font.font.as_scaled(10.);
+        let height = scaled_font.height();
+        let column_advance = height + scaled_font.line_gap();
+        let sections = [SectionText {
+            text: "ab cd\nef",
+            scale: PxScale::from(10.),
+            font_id: FontId(0),
+        }];
+        let layout = |writing_mode, text_alignment, bounds, overflow| {
+            layout_vertical_glyphs(
+                &fonts,
+                &sections,
+                bounds,
+                writing_mode,
+                text_alignment,
+                BreakLineOn::WordBoundary,
+                overflow,
+                0.,
+            )
+        };
+        // The center of the column and the top of each glyph
+        let cells = |glyphs: Vec<glyph_brush_layout::SectionGlyph>| {
+            glyphs
+                .iter()
+                .map(|sg| {
+                    let width = scaled_font.h_advance(sg.glyph.id);
+                    (
+                        sg.glyph.position.x + 0.5 * width,
+                        sg.glyph.position.y - scaled_font.ascent(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let unbounded = cells(layout(
+            WritingMode::VerticalLr,
+            JustifyText::Left,
+            Vec2::INFINITY,
+            OverflowBehavior::Visible,
+        ));
+        assert_eq!(unbounded.len(), 7);
+        // "ab cd" in the first column, "ef" in the second
+        let first_column = unbounded[0].0;
+        assert!(unbounded[..5].iter().all(|(x, _)| *x == first_column));
+        assert!((unbounded[5].0 - first_column - column_advance).abs() < 0.01);
+        for (index, (_, top)) in unbounded[..5].iter().enumerate() {
+            assert!((top - index as f32 * height).abs() < 0.01);
+        }
+
+        // Right to left columns end at 0, and wrap after the space
+        let wrapped = cells(layout(
+            WritingMode::VerticalRl,
+            JustifyText::Left,
+            Vec2::new(f32::INFINITY, 3.5 * height),
+            OverflowBehavior::Visible,
+        ));
+        let columns = wrapped
+            .iter()
+            .fold(Vec::<f32>::new(), |mut columns, (x, _)| {
+                if columns.last() != Some(x) {
+                    columns.push(*x);
+                }
+                columns
+            });
+        assert_eq!(columns.len(), 3);
+        assert!(columns.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!(columns[0] < 0.);
+        assert!(wrapped[3].1.abs() < 0.01);
+
+        // Columns are aligned within the longest column
+        let bottom = cells(layout(
+            WritingMode::VerticalLr,
+            JustifyText::Right,
+            Vec2::INFINITY,
+            OverflowBehavior::Visible,
+        ));
+        assert!((bottom[5].1 - 3. * height).abs() < 0.01);
+
+        // Clipping keeps the columns and glyphs that fit
+        let clipped = layout(
+            WritingMode::VerticalLr,
+            JustifyText::Left,
+            Vec2::new(column_advance, 2. * height),
+            OverflowBehavior::Clip,
+        );
+        assert_eq!(clipped.len(), 2);
+    }
+}
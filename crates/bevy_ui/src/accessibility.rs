@@ -1,20 +1,22 @@
 use crate::{
     prelude::{Button, Label},
-    Node, UiImage,
+    widget::{TextInput, TextInputFocus},
+    DefaultUiCamera, FocusState, Node, TargetCamera, UiImage, UiScale,
 };
 use bevy_a11y::{
     accesskit::{NodeBuilder, Rect, Role},
-    AccessibilityNode,
+    AccessibilityNode, Focus,
 };
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::{
     prelude::{DetectChanges, Entity},
     query::{Changed, Without},
     schedule::IntoSystemConfigs,
-    system::{Commands, Query},
+    system::{Commands, Query, Res, ResMut},
     world::Ref,
 };
 use bevy_hierarchy::Children;
+use bevy_math::Vec2;
 use bevy_render::{camera::CameraUpdateSystem, prelude::Camera};
 use bevy_text::Text;
 use bevy_transform::prelude::GlobalTransform;
@@ -35,25 +37,40 @@ fn calc_name(texts: &Query<&Text>, children: &Children) -> Option<Box<str>> {
 }
 
 fn calc_bounds(
-    camera: Query<(&Camera, &GlobalTransform)>,
-    mut nodes: Query<(&mut AccessibilityNode, Ref<Node>, Ref<GlobalTransform>)>,
+    cameras: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    mut nodes: Query<(
+        &mut AccessibilityNode,
+        Ref<Node>,
+        Ref<GlobalTransform>,
+        Option<&TargetCamera>,
+    )>,
 ) {
-    if let Ok((camera, camera_transform)) = camera.get_single() {
-        for (mut accessible, node, transform) in &mut nodes {
-            if node.is_changed() || transform.is_changed() {
-                if let Some(translation) =
-                    camera.world_to_viewport(camera_transform, transform.translation())
-                {
-                    let bounds = Rect::new(
-                        translation.x.into(),
-                        translation.y.into(),
-                        (translation.x + node.calculated_size.x).into(),
-                        (translation.y + node.calculated_size.y).into(),
-                    );
-                    accessible.set_bounds(bounds);
-                }
-            }
+    for (mut accessible, node, transform, target_camera) in &mut nodes {
+        if !(node.is_changed() || transform.is_changed() || ui_scale.is_changed()) {
+            continue;
         }
+        let Some(camera) = target_camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|camera_entity| cameras.get(camera_entity).ok())
+        else {
+            continue;
+        };
+        // The bounds are in physical pixels, relative to the top left corner of the window
+        let scale_factor = camera.target_scaling_factor().unwrap_or(1.);
+        let viewport_origin = camera
+            .physical_viewport_rect()
+            .map_or(Vec2::ZERO, |viewport| viewport.min.as_vec2());
+        let rect = node.physical_rect(&transform, scale_factor, ui_scale.0);
+        let (min, max) = (rect.min + viewport_origin, rect.max + viewport_origin);
+        accessible.set_bounds(Rect::new(
+            min.x.into(),
+            min.y.into(),
+            max.x.into(),
+            max.y.into(),
+        ));
     }
 }
 
@@ -142,6 +159,48 @@ fn label_changed(
     }
 }
 
+fn text_input_changed(
+    mut commands: Commands,
+    mut query: Query<(Entity, &TextInput, Option<&mut AccessibilityNode>), Changed<TextInput>>,
+) {
+    for (entity, text_input, accessible) in &mut query {
+        let value = text_input.value().to_string().into_boxed_str();
+        if let Some(mut accessible) = accessible {
+            accessible.set_role(Role::TextInput);
+            accessible.set_value(value);
+        } else {
+            let mut node = NodeBuilder::new(Role::TextInput);
+            node.set_value(value);
+            commands
+                .entity(entity)
+                .try_insert(AccessibilityNode::from(node));
+        }
+    }
+}
+
+/// Moves the accessibility focus to the focused [`TextInput`], or otherwise the node with the [`FocusState`].
+fn focus_changed(
+    focus_state: Res<FocusState>,
+    text_input_focus: Res<TextInputFocus>,
+    accessible: Query<Ref<AccessibilityNode>>,
+    mut focus: ResMut<Focus>,
+) {
+    let target = text_input_focus.0.or(focus_state.focused);
+    let target_node = target.and_then(|entity| accessible.get(entity).ok());
+    // The node of the focused entity may be added after the focus changed
+    if !(focus_state.is_changed()
+        || text_input_focus.is_changed()
+        || target_node.as_ref().is_some_and(DetectChanges::is_added))
+    {
+        return;
+    }
+    // Screen readers can only focus nodes in the accessibility tree
+    let focused = target.filter(|_| target_node.is_some());
+    if focus.0 != focused {
+        focus.0 = focused;
+    }
+}
+
 /// `AccessKit` integration for `bevy_ui`.
 pub(crate) struct AccessibilityPlugin;
 
@@ -159,7 +218,57 @@ impl Plugin for AccessibilityPlugin {
                 button_changed,
                 image_changed,
                 label_changed,
+                text_input_changed,
+                focus_changed
+                    .after(text_input_changed)
+                    .run_if(bevy_ecs::schedule::common_conditions::resource_exists::<Focus>),
             ),
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_a11y::{accesskit::Role, AccessibilityNode, Focus};
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+
+    use super::{focus_changed, text_input_changed};
+    use crate::{
+        widget::{TextInput, TextInputFocus},
+        FocusState,
+    };
+
+    #[test]
+    fn text_inputs_are_accessible_and_focused() {
+        let mut world = World::new();
+        world.init_resource::<Focus>();
+        world.init_resource::<FocusState>();
+        world.init_resource::<TextInputFocus>();
+        let text_input = world
+            .spawn(TextInput::new("hello", Default::default()))
+            .id();
+        world.resource_mut::<TextInputFocus>().0 = Some(text_input);
+
+        // The focus moves once the text input's accessibility node is added
+        world.run_system_once(focus_changed);
+        assert_eq!(world.resource::<Focus>().0, None);
+        world.run_system_once(text_input_changed);
+        let node = world.get::<AccessibilityNode>(text_input).unwrap();
+        assert_eq!(node.role(), Role::TextInput);
+        assert_eq!(node.value(), Some("hello"));
+        world.run_system_once(focus_changed);
+        assert_eq!(world.resource::<Focus>().0, Some(text_input));
+
+        world
+            .get_mut::<TextInput>(text_input)
+            .unwrap()
+            .set_value("world");
+        world.run_system_once(text_input_changed);
+        let node = world.get::<AccessibilityNode>(text_input).unwrap();
+        assert_eq!(node.value(), Some("world"));
+
+        world.resource_mut::<TextInputFocus>().0 = None;
+        world.run_system_once(focus_changed);
+        assert_eq!(world.resource::<Focus>().0, None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Sounds played on UI interactions.
+//!
+//! [`UiAudioPlugin`] plays the sounds of the [`UiSounds`] resource when a node is hovered, pressed or released,
+//! and when the navigation focus moves to another node. Nodes can play different sounds, or none, with a
+//! [`UiSoundOverride`] component.
+//!
+//! ```
+//! # use bevy_app::App;
+//! # use bevy_asset::AssetServer;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ui::audio::{UiAudioPlugin, UiSounds};
+//! fn setup_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.insert_resource(UiSounds {
+//!         hover: Some(asset_server.load("sounds/hover.ogg")),
+//!         press: Some(asset_server.load("sounds/click.ogg")),
+//!         ..Default::default()
+//!     });
+//! }
+//! # let _ = (UiAudioPlugin, setup_sounds);
+//! ```
+//!
+//! The plugin isn't added by [`UiPlugin`](crate::UiPlugin), and is only available with the `bevy_audio` feature.
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::Handle;
+use bevy_audio::{AudioBundle, AudioSource, PlaybackSettings};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+use crate::{FocusState, UiInteractionEvent, UiInteractionKind, UiSystem};
+
+/// Plays the [`UiSounds`] on interactions with UI nodes, see the [module docs](self).
+pub struct UiAudioPlugin;
+
+impl Plugin for UiAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiSounds>()
+            .add_systems(PreUpdate, ui_sound_system.after(UiSystem::Focus));
+    }
+}
+
+/// The sounds played by [`UiAudioPlugin`] for every UI node without a [`UiSoundOverride`].
+///
+/// Interactions without a sound are silent.
+#[derive(Resource, Clone, Debug)]
+pub struct UiSounds {
+    /// Played when the pointer starts hovering over a node.
+    pub hover: Option<Handle<AudioSource>>,
+    /// Played when a node is pressed.
+    pub press: Option<Handle<AudioSource>>,
+    /// Played when a pressed node is released.
+    pub release: Option<Handle<AudioSource>>,
+    /// Played when the navigation focus moves to a node.
+    pub focus: Option<Handle<AudioSource>>,
+    /// The settings the sounds are played with.
+    pub settings: PlaybackSettings,
+}
+
+impl Default for UiSounds {
+    fn default() -> Self {
+        Self {
+            hover: None,
+            press: None,
+            release: None,
+            focus: None,
+            settings: PlaybackSettings::DESPAWN,
+        }
+    }
+}
+
+/// The sound played for one interaction with a node with a [`UiSoundOverride`].
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum UiSound {
+    /// Play the sound of the [`UiSounds`] resource.
+    #[default]
+    Default,
+    /// Play no sound.
+    Silent,
+    /// Play this sound instead.
+    Play(Handle<AudioSource>),
+}
+
+impl UiSound {
+    fn resolve<'a>(
+        &'a self,
+        default: &'a Option<Handle<AudioSource>>,
+    ) -> Option<&'a Handle<AudioSource>> {
+        match self {
+            UiSound::Default => default.as_ref(),
+            UiSound::Silent => None,
+            UiSound::Play(handle) => Some(handle),
+        }
+    }
+}
+
+/// Overrides the [`UiSounds`] played for a node.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiSoundOverride {
+    /// Played when the pointer starts hovering over the node.
+    pub hover: UiSound,
+    /// Played when the node is pressed.
+    pub press: UiSound,
+    /// Played when the node is released.
+    pub release: UiSound,
+    /// Played when the navigation focus moves to the node.
+    pub focus: UiSound,
+}
+
+impl UiSoundOverride {
+    /// A [`UiSoundOverride`] silencing every interaction with the node.
+    pub const SILENT: Self = Self {
+        hover: UiSound::Silent,
+        press: UiSound::Silent,
+        release: UiSound::Silent,
+        focus: UiSound::Silent,
+    };
+}
+
+/// Plays the [`UiSounds`] for this frame's [`UiInteractionEvent`]s and navigation focus changes.
+pub fn ui_sound_system(
+    mut commands: Commands,
+    sounds: Res<UiSounds>,
+    focus: Res<FocusState>,
+    mut last_focused: Local<Option<Entity>>,
+    mut interaction_events: EventReader<UiInteractionEvent>,
+    overrides: Query<&UiSoundOverride>,
+) {
+    let mut play = |entity: Entity, select: fn(&UiSoundOverride) -> &UiSound, default| {
+        let source = match overrides.get(entity) {
+            Ok(sound_override) => select(sound_override).resolve(default),
+            Err(_) => default.as_ref(),
+        };
+        if let Some(source) = source {
+            commands.spawn(AudioBundle {
+                source: source.clone(),
+                settings: sounds.settings,
+            });
+        }
+    };
+
+    for event in interaction_events.read() {
+        match event.kind {
+            UiInteractionKind::HoverStart => {
+                play(event.entity, |o| &o.hover, &sounds.hover);
+            }
+            UiInteractionKind::Pressed => {
+                play(event.entity, |o| &o.press, &sounds.press);
+            }
+            UiInteractionKind::Released => {
+                play(event.entity, |o| &o.release, &sounds.release);
+            }
+            _ => {}
+        }
+    }
+
+    if *last_focused != focus.focused {
+        *last_focused = focus.focused;
+        if let Some(focused) = focus.focused {
+            play(focused, |o| &o.focus, &sounds.focus);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Handle;
+    use bevy_audio::AudioSource;
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    use super::{ui_sound_system, UiSound, UiSoundOverride, UiSounds};
+    use crate::{FocusState, UiInteractionEvent, UiInteractionKind};
+
+    #[test]
+    fn overrides_replace_the_default_sounds() {
+        let default_press = Handle::<AudioSource>::weak_from_u128(1);
+        let override_press = Handle::<AudioSource>::weak_from_u128(2);
+        let mut world = World::new();
+        world.init_resource::<Events<UiInteractionEvent>>();
+        world.init_resource::<FocusState>();
+        world.insert_resource(UiSounds {
+            press: Some(default_press.clone()),
+            ..Default::default()
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_sound_system);
+
+        let plain = world.spawn_empty().id();
+        let silent = world.spawn(UiSoundOverride::SILENT).id();
+        let overridden = world
+            .spawn(UiSoundOverride {
+                press: UiSound::Play(override_press.clone()),
+                ..Default::default()
+            })
+            .id();
+        for entity in [plain, silent, overridden] {
+            world.send_event(UiInteractionEvent {
+                entity,
+                kind: UiInteractionKind::Pressed,
+                position: None,
+                button: None,
+            });
+        }
+        // No hover sound is set
+        world.send_event(UiInteractionEvent {
+            entity: plain,
+            kind: UiInteractionKind::HoverStart,
+            position: None,
+            button: None,
+        });
+        schedule.run(&mut world);
+
+        let mut played = world
+            .query::<&Handle<AudioSource>>()
+            .iter(&world)
+            .cloned()
+            .collect::<Vec<_>>();
+        played.sort();
+        assert_eq!(played, [default_press, override_press]);
+    }
+}
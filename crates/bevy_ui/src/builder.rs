@@ -0,0 +1,241 @@
+//! A fluent API for building UI node hierarchies.
+//!
+//! [`UiBuilder`] describes a node with its components, observers and children, and spawns the whole tree with a
+//! single call. Nodes given a name with [`UiBuilder::named`] can be looked up in the returned [`UiEntities`] to
+//! mutate them later:
+//!
+//! ```
+//! # use bevy_color::Color;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ui::{builder::UiBuilder, prelude::*};
+//! fn setup(mut commands: Commands) {
+//!     let entities = UiBuilder::node()
+//!         .style(Style {
+//!             flex_direction: FlexDirection::Column,
+//!             row_gap: Val::Px(8.),
+//!             ..Default::default()
+//!         })
+//!         .background(Color::BLACK)
+//!         .child(UiBuilder::node().named("header").background(Color::WHITE))
+//!         .children((0..3).map(|_| UiBuilder::bundle(ButtonBundle::default())))
+//!         .spawn(&mut commands);
+//!
+//!     commands.entity(entities["header"]).insert(ZIndex::Local(1));
+//! }
+//! # bevy_ecs::system::assert_is_system(setup);
+//! ```
+
+use std::{borrow::Cow, ops::Index};
+
+use bevy_color::Color;
+use bevy_ecs::{prelude::*, system::EntityCommands, system::IntoObserverSystem};
+use bevy_hierarchy::BuildChildren;
+use bevy_utils::HashMap;
+
+use crate::{node_bundles::NodeBundle, BackgroundColor, BorderColor, Style};
+
+type EntityOp = Box<dyn FnOnce(&mut EntityCommands) + Send + 'static>;
+
+/// A UI node to spawn with its children, see the [module docs](self).
+#[must_use = "the node isn't spawned until `UiBuilder::spawn` is called"]
+pub struct UiBuilder {
+    name: Option<Cow<'static, str>>,
+    ops: Vec<EntityOp>,
+    children: Vec<UiBuilder>,
+}
+
+impl UiBuilder {
+    /// A node spawned with a default [`NodeBundle`].
+    pub fn node() -> Self {
+        Self::bundle(NodeBundle::default())
+    }
+
+    /// A node spawned with `bundle`, usually one of the [`node_bundles`](crate::node_bundles).
+    pub fn bundle(bundle: impl Bundle) -> Self {
+        Self {
+            name: None,
+            ops: Vec::new(),
+            children: Vec::new(),
+        }
+        .insert(bundle)
+    }
+
+    /// A text node displaying `text` with the default [`TextStyle`](bevy_text::TextStyle).
+    #[cfg(feature = "bevy_text")]
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::bundle(crate::node_bundles::TextBundle::from_section(
+            text,
+            Default::default(),
+        ))
+    }
+
+    /// Names the node, so its entity can be looked up in the [`UiEntities`] returned by [`UiBuilder::spawn`].
+    ///
+    /// Names should be unique within a tree, the last node spawned with a name is returned for it.
+    pub fn named(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Inserts `bundle` into the node, replacing the components of the node's bundle it contains.
+    pub fn insert(mut self, bundle: impl Bundle) -> Self {
+        self.ops.push(Box::new(move |entity: &mut EntityCommands| {
+            entity.insert(bundle);
+        }));
+        self
+    }
+
+    /// Sets the [`Style`] of the node.
+    pub fn style(self, style: Style) -> Self {
+        self.insert(style)
+    }
+
+    /// Sets the [`BackgroundColor`] of the node.
+    pub fn background(self, color: impl Into<Color>) -> Self {
+        self.insert(BackgroundColor(color.into()))
+    }
+
+    /// Sets the [`BorderColor`] of all the borders of the node.
+    pub fn border(self, color: impl Into<Color>) -> Self {
+        self.insert(BorderColor::all(color.into()))
+    }
+
+    /// Adds an [`Observer`] listening for events targeting the node.
+    pub fn observe<E: Event, B: Bundle, M>(
+        mut self,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> Self {
+        self.ops.push(Box::new(move |entity: &mut EntityCommands| {
+            entity.observe(observer);
+        }));
+        self
+    }
+
+    /// Adds `child` after the node's other children.
+    pub fn child(mut self, child: UiBuilder) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Adds `children` after the node's other children.
+    pub fn children(mut self, children: impl IntoIterator<Item = UiBuilder>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Spawns the node and its descendants, returning their entities.
+    pub fn spawn(self, commands: &mut Commands) -> UiEntities {
+        let mut named = HashMap::new();
+        let root = self.spawn_recursive(commands, &mut named);
+        UiEntities { root, named }
+    }
+
+    fn spawn_recursive(
+        self,
+        commands: &mut Commands,
+        named: &mut HashMap<Cow<'static, str>, Entity>,
+    ) -> Entity {
+        let mut entity_commands = commands.spawn_empty();
+        for op in self.ops {
+            op(&mut entity_commands);
+        }
+        let entity = entity_commands.id();
+        if let Some(name) = self.name {
+            named.insert(name, entity);
+        }
+        let children = self
+            .children
+            .into_iter()
+            .map(|child| child.spawn_recursive(commands, named))
+            .collect::<Vec<_>>();
+        if !children.is_empty() {
+            commands.entity(entity).push_children(&children);
+        }
+        entity
+    }
+}
+
+/// The entities spawned by [`UiBuilder::spawn`].
+///
+/// Named nodes can be looked up by indexing with their name, which panics if no node has the name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UiEntities {
+    /// The root node.
+    pub root: Entity,
+    named: HashMap<Cow<'static, str>, Entity>,
+}
+
+impl UiEntities {
+    /// Returns the entity of the node named `name`.
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        self.named.get(name).copied()
+    }
+}
+
+impl Index<&str> for UiEntities {
+    type Output = Entity;
+
+    fn index(&self, name: &str) -> &Entity {
+        self.named
+            .get(name)
+            .unwrap_or_else(|| panic!("no UI node named `{name}` was spawned"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::Color;
+    use bevy_ecs::{observer::Trigger, prelude::*};
+    use bevy_hierarchy::{Children, Parent};
+
+    use super::UiBuilder;
+    use crate::{BackgroundColor, Node, Style, Val};
+
+    #[derive(Event)]
+    struct Ping;
+
+    #[derive(Resource, Default)]
+    struct Pinged(Vec<Entity>);
+
+    #[test]
+    fn builder_spawns_named_trees_with_observers() {
+        let mut world = World::new();
+        world.init_resource::<Pinged>();
+        let style = Style {
+            width: Val::Px(10.),
+            ..Default::default()
+        };
+        let entities = UiBuilder::node()
+            .named("root")
+            .child(
+                UiBuilder::node()
+                    .named("panel")
+                    .style(style.clone())
+                    .background(Color::WHITE)
+                    .observe(|trigger: Trigger<Ping>, mut pinged: ResMut<Pinged>| {
+                        pinged.0.push(trigger.entity());
+                    })
+                    .children([UiBuilder::node(), UiBuilder::node().named("leaf")]),
+            )
+            .spawn(&mut world.commands());
+        world.flush();
+
+        assert_eq!(entities["root"], entities.root);
+        assert_eq!(entities.get("missing"), None);
+        let panel = entities["panel"];
+        assert_eq!(world.get::<Parent>(panel).unwrap().get(), entities.root);
+        let children = world.get::<Children>(panel).unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[1], entities["leaf"]);
+        assert!(world.get::<Node>(entities["leaf"]).is_some());
+        assert_eq!(world.get::<Style>(panel), Some(&style));
+        assert_eq!(
+            world.get::<BackgroundColor>(panel),
+            Some(&BackgroundColor(Color::WHITE))
+        );
+
+        world.trigger_targets(Ping, panel);
+        world.flush();
+        assert_eq!(world.resource::<Pinged>().0, [panel]);
+    }
+}
@@ -0,0 +1,272 @@
+//! Copying text to and pasting text from the system clipboard.
+//!
+//! Accessing the system clipboard can block for a long time, up to several seconds on X11 when the clipboard owner
+//! doesn't respond. When control (or command on macOS) and `V` are pressed, [`clipboard_paste_system`] reads the
+//! clipboard with the app's [`ClipboardSource`] in a task on the [`IoTaskPool`], and sends a [`PasteIntoFocused`]
+//! event once the text has been read, so the main thread is never blocked. Text copied with
+//! [`ClipboardSource::write`] is written in a task too.
+//!
+//! The paste is delivered to the focused [`TextInput`](crate::widget::TextInput) if there is one, or to the node
+//! with navigation focus in the [`FocusState`] otherwise.
+
+use std::{
+    fmt,
+    sync::{mpsc, Arc, Mutex},
+};
+
+use bevy_ecs::prelude::*;
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_tasks::IoTaskPool;
+
+use crate::FocusState;
+
+/// Reads and writes the text of the system clipboard.
+///
+/// Bevy doesn't access the system clipboard itself, apps set the source with a clipboard crate like `arboard`:
+///
+/// ```ignore
+/// app.insert_resource(ClipboardSource::new(
+///     || arboard::Clipboard::new().ok()?.get_text().ok(),
+///     |text| {
+///         if let Ok(mut clipboard) = arboard::Clipboard::new() {
+///             clipboard.set_text(text).ok();
+///         }
+///     },
+/// ));
+/// ```
+///
+/// No [`PasteIntoFocused`] events are sent while the source isn't set, and [`TextInput`](crate::widget::TextInput)s
+/// copy to and paste from their app-local clipboard instead.
+#[derive(Resource, Clone, Default)]
+pub struct ClipboardSource(Option<ClipboardAccess>);
+
+#[derive(Clone)]
+struct ClipboardAccess {
+    read: Arc<dyn Fn() -> Option<String> + Send + Sync>,
+    write: Arc<dyn Fn(String) + Send + Sync>,
+    /// The text of the last [`ClipboardSource::write`], until it has been written.
+    ///
+    /// Reads write it first, so a paste requested right after a copy never reads the previous text.
+    pending_write: Arc<Mutex<Option<String>>>,
+}
+
+impl ClipboardAccess {
+    fn flush(&self) {
+        let mut pending_write = self.pending_write.lock().unwrap();
+        if let Some(text) = pending_write.take() {
+            (self.write)(text);
+        }
+    }
+}
+
+impl ClipboardSource {
+    /// Creates a [`ClipboardSource`] reading the clipboard with `read` and writing it with `write`.
+    ///
+    /// Both are called from a task pool thread and may block.
+    pub fn new(
+        read: impl Fn() -> Option<String> + Send + Sync + 'static,
+        write: impl Fn(String) + Send + Sync + 'static,
+    ) -> Self {
+        Self(Some(ClipboardAccess {
+            read: Arc::new(read),
+            write: Arc::new(write),
+            pending_write: Default::default(),
+        }))
+    }
+
+    /// Returns true if the clipboard reader and writer are set.
+    pub fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Writes `text` to the system clipboard in a task on the [`IoTaskPool`].
+    ///
+    /// Does nothing if the source isn't set.
+    pub fn write(&self, text: String) {
+        let Some(access) = self.0.clone() else {
+            return;
+        };
+        *access.pending_write.lock().unwrap() = Some(text);
+        IoTaskPool::get()
+            .spawn(async move { access.flush() })
+            .detach();
+    }
+}
+
+impl fmt::Debug for ClipboardSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ClipboardSource")
+            .field(&if self.is_set() { "set" } else { "unset" })
+            .finish()
+    }
+}
+
+/// Sent with the text read from the system clipboard when a paste was requested, see the [module docs](self).
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct PasteIntoFocused {
+    /// The entity that was focused when the paste was requested.
+    pub entity: Entity,
+    /// The text of the clipboard.
+    pub text: String,
+}
+
+/// The clipboard reads in progress in [`clipboard_paste_system`].
+pub struct PendingPastes {
+    sender: mpsc::Sender<(Entity, String)>,
+    receiver: mpsc::Receiver<(Entity, String)>,
+}
+
+impl Default for PendingPastes {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+}
+
+/// Returns true if the modifier of the clipboard shortcuts is pressed: control, or control or command on macOS.
+pub(crate) fn clipboard_modifier_pressed(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+        || (cfg!(target_os = "macos")
+            && keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]))
+}
+
+/// Reads the system clipboard in a task when control (or command) and `V` are pressed, and sends a
+/// [`PasteIntoFocused`] event for the focused entity once the read has finished.
+pub fn clipboard_paste_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    source: Res<ClipboardSource>,
+    focus: Res<FocusState>,
+    #[cfg(feature = "bevy_text")] text_input_focus: Option<Res<crate::widget::TextInputFocus>>,
+    pending: Local<PendingPastes>,
+    mut paste_events: EventWriter<PasteIntoFocused>,
+) {
+    #[cfg(feature = "bevy_text")]
+    let focused = text_input_focus
+        .and_then(|text_input_focus| text_input_focus.0)
+        .or(focus.focused);
+    #[cfg(not(feature = "bevy_text"))]
+    let focused = focus.focused;
+
+    if let (Some(access), Some(entity), true) = (
+        source.0.clone(),
+        focused,
+        clipboard_modifier_pressed(&keys) && keys.just_pressed(KeyCode::KeyV),
+    ) {
+        let sender = pending.sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                access.flush();
+                if let Some(text) = (access.read)() {
+                    // The receiver is only dropped with the system
+                    sender.send((entity, text)).ok();
+                }
+            })
+            .detach();
+    }
+
+    for (entity, text) in pending.receiver.try_iter() {
+        paste_events.send(PasteIntoFocused { entity, text });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+    use bevy_input::{keyboard::KeyCode, ButtonInput};
+    use bevy_tasks::{IoTaskPool, TaskPool};
+
+    use super::{clipboard_paste_system, ClipboardSource, PasteIntoFocused};
+    use crate::FocusState;
+
+    #[test]
+    fn paste_is_delivered_to_the_focused_entity() {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let mut world = World::new();
+        world.init_resource::<Events<PasteIntoFocused>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.insert_resource(ClipboardSource::new(|| Some("pasted".to_string()), |_| {}));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(clipboard_paste_system);
+
+        let focused = world.spawn_empty().id();
+        world.insert_resource(FocusState {
+            focused: Some(focused),
+        });
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::ControlLeft);
+        keys.press(KeyCode::KeyV);
+
+        // The clipboard is read in a task, poll until the paste is delivered
+        let mut pastes = Vec::new();
+        for _ in 0..100 {
+            schedule.run(&mut world);
+            world.resource_mut::<ButtonInput<KeyCode>>().clear();
+            pastes.extend(world.resource_mut::<Events<PasteIntoFocused>>().drain());
+            if !pastes.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            pastes,
+            [PasteIntoFocused {
+                entity: focused,
+                text: "pasted".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn paste_reads_the_text_copied_before_it() {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let clipboard = Arc::new(Mutex::new(String::new()));
+        let read = clipboard.clone();
+        let write = clipboard.clone();
+        let source = ClipboardSource::new(
+            move || Some(read.lock().unwrap().clone()),
+            move |text| {
+                // A slow clipboard owner
+                std::thread::sleep(Duration::from_millis(50));
+                *write.lock().unwrap() = text;
+            },
+        );
+        let mut world = World::new();
+        world.init_resource::<Events<PasteIntoFocused>>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.insert_resource(source.clone());
+        let mut schedule = Schedule::default();
+        schedule.add_systems(clipboard_paste_system);
+
+        let focused = world.spawn_empty().id();
+        world.insert_resource(FocusState {
+            focused: Some(focused),
+        });
+        source.write("copied".to_string());
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::ControlLeft);
+        keys.press(KeyCode::KeyV);
+
+        let mut pastes = Vec::new();
+        for _ in 0..100 {
+            schedule.run(&mut world);
+            world.resource_mut::<ButtonInput<KeyCode>>().clear();
+            pastes.extend(world.resource_mut::<Events<PasteIntoFocused>>().drain());
+            if !pastes.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            pastes,
+            [PasteIntoFocused {
+                entity: focused,
+                text: "copied".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! An immediate-mode overlay for drawing debug text and rectangles over the UI.
+//!
+//! [`DebugUi`] collects draw commands from any system each frame, without spawning any entities. The
+//! commands are drawn on top of all other UI nodes by the default UI camera, using the same instance
+//! buffers as the rest of the UI, and then discarded at the start of the next frame.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::{Rect, Vec2};
+//! # use bevy_color::{palettes::basic::RED, Alpha};
+//! # use bevy_ui::debug_ui::DebugUi;
+//! fn draw_debug_info(mut debug_ui: ResMut<DebugUi>) {
+//!     debug_ui.rect(Rect::new(10., 10., 210., 40.), RED.with_alpha(0.5));
+//!     debug_ui.text(Vec2::new(15., 15.), "Entities: 42");
+//! }
+//! ```
+//!
+//! The overlay is only available with the `debug_ui` feature, so it can be compiled out of release builds.
+//!
+//! [`UiDebugOverlayPlugin`] uses the overlay to draw the layout of every UI node, toggled with the
+//! [`UiDebugOverlay`] resource.
+
+use bevy_app::{App, First, Plugin, PostUpdate};
+use bevy_color::Color;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{camera::Camera, view::ViewVisibility, Extract, ExtractSchedule, RenderApp};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{
+    extract_ui_painter,
+    geometry::{resolve_border_thickness, ui_logical_viewport_size},
+    widget, DefaultUiCamera, Node, OverflowClipBox, RenderUiSystem, RootFontSize, Style,
+    TargetCamera, UiPainter, UiPainterExtract, UiPainterTextLayout, UiScale, UiStack, Val,
+};
+
+/// Adds the [`DebugUi`] overlay. Added automatically by [`UiPlugin`](crate::UiPlugin) when the `debug_ui`
+/// feature is enabled.
+pub struct DebugUiPlugin;
+
+impl Plugin for DebugUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugUi>()
+            .add_systems(First, clear_debug_ui)
+            .add_systems(
+                PostUpdate,
+                layout_debug_ui_text
+                    .after(widget::text_system)
+                    .ambiguous_with(bevy_text::update_text2d_layout),
+            );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.add_systems(
+            ExtractSchedule,
+            extract_debug_ui
+                .after(RenderUiSystem::ExtractText)
+                .after(extract_ui_painter)
+                .before(RenderUiSystem::CullNodes),
+        );
+    }
+}
+
+/// Immediate-mode debug drawing over the UI.
+///
+/// A [`UiPainter`] drawn over the UI and any other painter, so everything that can be drawn with a painter can
+/// be drawn to the overlay. Everything drawn is cleared at the start of each frame, so systems that draw to the
+/// overlay must draw again every frame.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct DebugUi(pub UiPainter);
+
+/// Clears the [`DebugUi`] at the start of each frame.
+pub fn clear_debug_ui(mut debug_ui: ResMut<DebugUi>) {
+    debug_ui.clear();
+}
+
+/// Lays out the text drawn to the [`DebugUi`] this frame.
+pub fn layout_debug_ui_text(mut debug_ui: ResMut<DebugUi>, mut layout: UiPainterTextLayout) {
+    layout.layout(&mut debug_ui);
+}
+
+/// Extracts the [`DebugUi`] overlay, above all other UI nodes and the [`UiPainter`].
+pub fn extract_debug_ui(mut extract: UiPainterExtract, debug_ui: Extract<Res<DebugUi>>) {
+    extract.extract(&debug_ui);
+}
+
+/// Draws the layout of every UI node with the [`DebugUi`] overlay while [`UiDebugOverlay::enabled`] is set.
+///
+/// Requires the [`DebugUiPlugin`], which is added by [`UiPlugin`](crate::UiPlugin).
+pub struct UiDebugOverlayPlugin;
+
+impl Plugin for UiDebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiDebugOverlay>()
+            .register_type::<UiDebugOverlay>()
+            .add_systems(
+                PostUpdate,
+                draw_ui_debug_overlay
+                    .after(TransformSystem::TransformPropagate)
+                    .before(layout_debug_ui_text),
+            );
+    }
+}
+
+/// Controls what [`UiDebugOverlayPlugin`] draws over each UI node.
+///
+/// Only the nodes rendered by the default UI camera are drawn. Colors are drawn over the UI, so they should be
+/// translucent.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct UiDebugOverlay {
+    /// Whether the overlay is drawn. Disabled by default.
+    pub enabled: bool,
+    /// The width of the outlines, in logical pixels.
+    pub line_width: f32,
+    /// The color of the outline of each node's border box, or `None` to not draw it.
+    pub outline_color: Option<Color>,
+    /// The color filling each node's margins, or `None` to not draw them.
+    pub margin_color: Option<Color>,
+    /// The color filling each node's padding, or `None` to not draw it.
+    pub padding_color: Option<Color>,
+    /// The color of the outline of each node's content box, or `None` to not draw it.
+    pub content_color: Option<Color>,
+    /// Whether each node is labeled with its stack index, in the [`DebugUi::text_style`].
+    pub show_stack_index: bool,
+}
+
+impl Default for UiDebugOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            line_width: 1.,
+            outline_color: Some(Color::srgba(1., 0., 1., 0.8)),
+            margin_color: Some(Color::srgba(1., 0.6, 0.2, 0.3)),
+            padding_color: Some(Color::srgba(0.4, 0.8, 0.3, 0.3)),
+            content_color: Some(Color::srgba(0.3, 0.6, 1., 0.8)),
+            show_stack_index: false,
+        }
+    }
+}
+
+/// Draws the [`UiDebugOverlay`] for every visible UI node, from back to front.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_ui_debug_overlay(
+    overlay: Res<UiDebugOverlay>,
+    mut debug_ui: ResMut<DebugUi>,
+    ui_stack: Res<UiStack>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    root_font_size: Res<RootFontSize>,
+    node_query: Query<(
+        &Node,
+        &GlobalTransform,
+        &Style,
+        &ViewVisibility,
+        Option<&TargetCamera>,
+        Option<&Parent>,
+    )>,
+    parent_query: Query<&Node>,
+) {
+    if !overlay.enabled {
+        return;
+    }
+    let Some(camera_entity) = default_ui_camera.get() else {
+        return;
+    };
+    let viewport_size = ui_logical_viewport_size(camera_query.get(camera_entity).ok(), ui_scale.0);
+
+    for entity in &ui_stack.uinodes {
+        let Ok((node, transform, style, view_visibility, target_camera, parent)) =
+            node_query.get(*entity)
+        else {
+            continue;
+        };
+        if !view_visibility.get()
+            || target_camera.is_some_and(|target_camera| target_camera.entity() != camera_entity)
+        {
+            continue;
+        }
+        let rect = node.logical_rect(transform);
+
+        if let Some(color) = overlay.margin_color {
+            // Margins are resolved like borders, against the width of the parent
+            let parent_width = parent
+                .and_then(|parent| parent_query.get(parent.get()).ok())
+                .map(|parent| parent.size().x)
+                .unwrap_or(viewport_size.x);
+            let resolve = |value: Val| {
+                resolve_border_thickness(
+                    value.scale_lengths(node.root_scale()),
+                    parent_width,
+                    viewport_size,
+                    root_font_size.0,
+                )
+            };
+            let margin_box = Rect {
+                min: rect.min - Vec2::new(resolve(style.margin.left), resolve(style.margin.top)),
+                max: rect.max
+                    + Vec2::new(resolve(style.margin.right), resolve(style.margin.bottom)),
+            };
+            debug_ui.frame(margin_box, rect, color);
+        }
+        let content_box = node.logical_box_rect(transform, OverflowClipBox::ContentBox);
+        if let Some(color) = overlay.padding_color {
+            let padding_box = node.logical_box_rect(transform, OverflowClipBox::PaddingBox);
+            debug_ui.frame(padding_box, content_box, color);
+        }
+        if let Some(color) = overlay.content_color {
+            debug_ui.outline(content_box, overlay.line_width, color);
+        }
+        if let Some(color) = overlay.outline_color {
+            debug_ui.outline(rect, overlay.line_width, color);
+        }
+        if overlay.show_stack_index {
+            debug_ui.text(rect.min, node.stack_index().to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Rect, Vec2};
+
+    use super::DebugUi;
+
+    #[test]
+    fn debug_ui_is_cleared() {
+        let mut debug_ui = DebugUi::default();
+        assert!(debug_ui.is_empty());
+
+        debug_ui.rect(Rect::new(0., 0., 10., 10.), bevy_color::Color::WHITE);
+        debug_ui.text(Vec2::ZERO, "debug");
+        assert!(!debug_ui.is_empty());
+        assert_eq!(debug_ui.text_style.font_size, 16.);
+
+        debug_ui.clear();
+        assert!(debug_ui.is_empty());
+    }
+}
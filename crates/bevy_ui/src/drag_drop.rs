@@ -0,0 +1,472 @@
+//! Dragging UI nodes and dropping them on other nodes.
+//!
+//! Pressing a [`Draggable`] node and moving the pointer starts a drag, sending a [`DragStart`]. While the node is
+//! dragged, a [`DragGhost`] copy of it follows the pointer over all other nodes, and a [`DragOver`] is sent every
+//! frame the pointer is over a [`DropTarget`]. Releasing the pointer over a drop target sends a [`DragDrop`].
+//!
+//! Drags follow the [`UiInteractionKind::DragStart`], [`UiInteractionKind::Drag`] and
+//! [`UiInteractionKind::DragEnd`] events of the mouse and touches, so they start after the pointer moves further
+//! than [`UiDragSettings::threshold`](crate::UiDragSettings::threshold).
+
+use bevy_color::{Alpha, Color};
+use bevy_ecs::{
+    entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::{ReflectComponent, ReflectMapEntities},
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_sprite::TextureAtlas;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::default;
+
+use crate::{
+    node_bundles::NodeBundle, BackgroundColor, BorderRadius, Disabled, Node, PositionType, Style,
+    TargetCamera, UiImage, UiInteractionEvent, UiInteractionKind, UiStacks, Val, ZIndex,
+};
+
+/// Makes a UI node draggable with the cursor.
+///
+/// The node needs an [`Interaction`](crate::Interaction) component, a drag starts when the node is pressed and the
+/// pointer moves further than [`UiDragSettings::threshold`](crate::UiDragSettings::threshold). [`Disabled`] nodes
+/// can't be dragged.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq, MapEntities)]
+pub struct Draggable {
+    /// The entity carried by the drag, sent with its events.
+    ///
+    /// If `None`, the payload is the draggable node itself.
+    pub payload: Option<Entity>,
+    /// The opacity of the [`DragGhost`] that follows the cursor while the node is dragged.
+    ///
+    /// If `None`, no ghost is spawned.
+    pub ghost_opacity: Option<f32>,
+}
+
+impl MapEntities for Draggable {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let Some(entity) = &mut self.payload {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+impl Draggable {
+    /// A draggable node carrying `payload`.
+    pub fn with_payload(payload: Entity) -> Self {
+        Self {
+            payload: Some(payload),
+            ..default()
+        }
+    }
+}
+
+impl Default for Draggable {
+    fn default() -> Self {
+        Self {
+            payload: None,
+            ghost_opacity: Some(0.6),
+        }
+    }
+}
+
+/// Marks a UI node that [`Draggable`] nodes can be dropped on.
+///
+/// The topmost drop target under the cursor, other than the dragged node itself, receives the drag.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct DropTarget;
+
+/// The root node following the cursor while a [`Draggable`] node is dragged, spawned by [`ui_drag_drop_system`].
+///
+/// The ghost has the size, background, border radius and image of the dragged node, and is drawn over all other
+/// nodes. Children can be added to it to show more of the dragged content. It is despawned when the drag ends.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct DragGhost {
+    /// The dragged node.
+    pub dragged: Entity,
+}
+
+/// Sent by [`ui_drag_drop_system`] when a [`Draggable`] node starts being dragged.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct DragStart {
+    /// The dragged node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`Draggable::payload`].
+    pub payload: Entity,
+    /// The position of the cursor, in logical pixels relative to the top left of the camera's viewport.
+    pub position: Vec2,
+}
+
+/// Sent by [`ui_drag_drop_system`] every frame the cursor is over a [`DropTarget`] while dragging.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct DragOver {
+    /// The dragged node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`Draggable::payload`].
+    pub payload: Entity,
+    /// The drop target under the cursor.
+    pub target: Entity,
+    /// The position of the cursor, in logical pixels relative to the top left of the camera's viewport.
+    pub position: Vec2,
+}
+
+/// Sent by [`ui_drag_drop_system`] when a drag is released over a [`DropTarget`].
+///
+/// Drags released anywhere else end without an event.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct DragDrop {
+    /// The dragged node.
+    pub dragged: Entity,
+    /// The entity carried by the drag, see [`Draggable::payload`].
+    pub payload: Entity,
+    /// The drop target the drag was released over.
+    pub target: Entity,
+    /// The position of the cursor, in logical pixels relative to the top left of the camera's viewport.
+    pub position: Vec2,
+}
+
+/// A [`Draggable`] node being dragged.
+struct ActiveDrag {
+    entity: Entity,
+    payload: Entity,
+    camera: Entity,
+    grab_offset: Vec2,
+    ghost: Option<Entity>,
+    position: Vec2,
+}
+
+/// The state of [`ui_drag_drop_system`].
+#[derive(Default)]
+pub struct DragDropState {
+    /// The pressed [`Draggable`] nodes, with the positions of the pointers pressing them relative to their top
+    /// left corners.
+    grab_offsets: Vec<(Entity, Vec2)>,
+    drags: Vec<ActiveDrag>,
+}
+
+/// Starts and ends drags of [`Draggable`] nodes from the [`UiInteractionKind::DragStart`] and
+/// [`UiInteractionKind::DragEnd`] events of their pointers, moves their [`DragGhost`]s and sends [`DragStart`],
+/// [`DragOver`] and [`DragDrop`] events.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_drag_drop_system(
+    mut commands: Commands,
+    mut state: Local<DragDropState>,
+    mut interaction_events: EventReader<UiInteractionEvent>,
+    ui_stacks: UiStacks,
+    draggables: Query<(&Draggable, &Node, &GlobalTransform, Has<Disabled>)>,
+    ghost_sources: Query<(
+        Option<&BackgroundColor>,
+        Option<&BorderRadius>,
+        Option<&UiImage>,
+        Option<&TextureAtlas>,
+    )>,
+    drop_targets: Query<(), With<DropTarget>>,
+    mut ghost_styles: Query<&mut Style, With<DragGhost>>,
+    mut start_events: EventWriter<DragStart>,
+    mut over_events: EventWriter<DragOver>,
+    mut drop_events: EventWriter<DragDrop>,
+) {
+    let state = &mut *state;
+    let target_under = |drag: &ActiveDrag| {
+        ui_stacks
+            .hit_test_camera(drag.camera, drag.position)
+            .into_iter()
+            .find(|&entity| entity != drag.entity && drop_targets.contains(entity))
+    };
+    let end_drag = |commands: &mut Commands, drag: ActiveDrag| {
+        if let Some(ghost) = drag.ghost.and_then(|ghost| commands.get_entity(ghost)) {
+            ghost.despawn_recursive();
+        }
+    };
+
+    for event in interaction_events.read() {
+        let entity = event.entity;
+        let Ok((draggable, node, transform, disabled)) = draggables.get(entity) else {
+            continue;
+        };
+        match event.kind {
+            UiInteractionKind::Pressed => {
+                let Some(position) = event.position else {
+                    continue;
+                };
+                let grab_offset = position - node.logical_rect(transform).min;
+                state.grab_offsets.retain(|(pressed, _)| *pressed != entity);
+                state.grab_offsets.push((entity, grab_offset));
+            }
+            UiInteractionKind::Released => {
+                state.grab_offsets.retain(|(pressed, _)| *pressed != entity);
+            }
+            UiInteractionKind::DragStart => {
+                if disabled || state.drags.iter().any(|drag| drag.entity == entity) {
+                    continue;
+                }
+                let (Some(position), Some(camera)) = (event.position, ui_stacks.camera_of(entity))
+                else {
+                    continue;
+                };
+                let grab_offset = state
+                    .grab_offsets
+                    .iter()
+                    .find(|(pressed, _)| *pressed == entity)
+                    .map_or(
+                        position - node.logical_rect(transform).min,
+                        |(_, offset)| *offset,
+                    );
+                let payload = draggable.payload.unwrap_or(entity);
+                let ghost = draggable.ghost_opacity.map(|opacity| {
+                    let (background_color, border_radius, image, atlas) =
+                        ghost_sources.get(entity).unwrap_or_default();
+                    let min = position - grab_offset;
+                    let mut ghost = commands.spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(min.x),
+                                top: Val::Px(min.y),
+                                width: Val::Px(node.size().x),
+                                height: Val::Px(node.size().y),
+                                ..default()
+                            },
+                            background_color: background_color
+                                .map_or(Color::NONE, |color| {
+                                    color.0.with_alpha(color.0.alpha() * opacity)
+                                })
+                                .into(),
+                            border_radius: border_radius.copied().unwrap_or_default(),
+                            z_index: ZIndex::DRAG_GHOST,
+                            ..default()
+                        },
+                        TargetCamera(camera),
+                        DragGhost { dragged: entity },
+                    ));
+                    if let Some(image) = image {
+                        let color = image.color.with_alpha(image.color.alpha() * opacity);
+                        ghost.insert(image.clone().with_color(color));
+                    }
+                    if let Some(atlas) = atlas {
+                        ghost.insert(atlas.clone());
+                    }
+                    ghost.id()
+                });
+
+                start_events.send(DragStart {
+                    dragged: entity,
+                    payload,
+                    position,
+                });
+                state.drags.push(ActiveDrag {
+                    entity,
+                    payload,
+                    camera,
+                    grab_offset,
+                    ghost,
+                    position,
+                });
+            }
+            UiInteractionKind::Drag { .. } => {
+                if let (Some(drag), Some(position)) = (
+                    state.drags.iter_mut().find(|drag| drag.entity == entity),
+                    event.position,
+                ) {
+                    drag.position = position;
+                }
+            }
+            UiInteractionKind::DragEnd => {
+                let Some(index) = state.drags.iter().position(|drag| drag.entity == entity) else {
+                    continue;
+                };
+                let mut drag = state.drags.swap_remove(index);
+                if let Some(position) = event.position {
+                    drag.position = position;
+                }
+                if let Some(target) = target_under(&drag) {
+                    drop_events.send(DragDrop {
+                        dragged: drag.entity,
+                        payload: drag.payload,
+                        target,
+                        position: drag.position,
+                    });
+                }
+                end_drag(&mut commands, drag);
+            }
+            _ => {}
+        }
+    }
+
+    // Drags of nodes that were despawned or disabled end without dropping them
+    let (drags, ended): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut state.drags)
+            .into_iter()
+            .partition(|drag| {
+                draggables
+                    .get(drag.entity)
+                    .is_ok_and(|(.., disabled)| !disabled)
+            });
+    state.drags = drags;
+    for drag in ended {
+        end_drag(&mut commands, drag);
+    }
+
+    for drag in &state.drags {
+        if let Some(mut style) = drag
+            .ghost
+            .and_then(|ghost| ghost_styles.get_mut(ghost).ok())
+        {
+            let min = drag.position - drag.grab_offset;
+            style.left = Val::Px(min.x);
+            style.top = Val::Px(min.y);
+        }
+        if let Some(target) = target_under(drag) {
+            over_events.send(DragOver {
+                dragged: drag.entity,
+                payload: drag.payload,
+                target,
+                position: drag.position,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, prelude::*};
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::Vec2;
+    use bevy_render::view::ViewVisibility;
+
+    use super::{
+        ui_drag_drop_system, DragDrop, DragGhost, DragOver, DragStart, Draggable, DropTarget,
+    };
+    use crate::{
+        prelude::*,
+        testing::{ui_test_schedule, ui_test_world},
+        UiInteractionEvent, UiInteractionKind,
+    };
+
+    fn send_interaction(
+        world: &mut World,
+        entity: Entity,
+        kind: UiInteractionKind,
+        position: Vec2,
+    ) {
+        world.send_event(UiInteractionEvent {
+            entity,
+            kind,
+            position: Some(position),
+            button: None,
+        });
+    }
+
+    #[test]
+    fn drag_node_onto_drop_target() {
+        let mut world = ui_test_world(400., 300.);
+        world.init_resource::<Events<UiInteractionEvent>>();
+        world.init_resource::<Events<DragStart>>();
+        world.init_resource::<Events<DragOver>>();
+        world.init_resource::<Events<DragDrop>>();
+        let mut layout_schedule = ui_test_schedule();
+        let mut drag_schedule = Schedule::default();
+        drag_schedule.add_systems(ui_drag_drop_system);
+
+        let square = NodeBundle {
+            style: Style {
+                width: Val::Px(100.),
+                height: Val::Px(100.),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let payload = world.spawn_empty().id();
+        let mut draggable = Entity::PLACEHOLDER;
+        let mut target = Entity::PLACEHOLDER;
+        world.spawn(NodeBundle::default()).with_children(|parent| {
+            draggable = parent
+                .spawn((
+                    square.clone(),
+                    Interaction::default(),
+                    Draggable::with_payload(payload),
+                ))
+                .id();
+            target = parent.spawn((square.clone(), DropTarget)).id();
+        });
+        layout_schedule.run(&mut world);
+        // Hidden nodes aren't hit, and visibility isn't checked by the test schedule
+        world.get_mut::<ViewVisibility>(target).unwrap().set();
+
+        // Pressing the node doesn't start a drag until the pointer moves past the drag threshold
+        send_interaction(
+            &mut world,
+            draggable,
+            UiInteractionKind::Pressed,
+            Vec2::new(50., 50.),
+        );
+        drag_schedule.run(&mut world);
+        assert!(world.resource::<Events<DragStart>>().is_empty());
+
+        send_interaction(
+            &mut world,
+            draggable,
+            UiInteractionKind::DragStart,
+            Vec2::new(150., 40.),
+        );
+        drag_schedule.run(&mut world);
+        let start = *world
+            .resource::<Events<DragStart>>()
+            .iter_current_update_events()
+            .next()
+            .unwrap();
+        assert_eq!(start.dragged, draggable);
+        assert_eq!(start.payload, payload);
+        let mut ghosts = world.query::<(&DragGhost, &Style)>();
+        let (ghost, style) = ghosts.single(&world);
+        assert_eq!(ghost.dragged, draggable);
+        // The ghost keeps the node's offset from the pointer when it was pressed
+        assert_eq!((style.left, style.top), (Val::Px(100.), Val::Px(-10.)));
+        let over = world
+            .resource::<Events<DragOver>>()
+            .iter_current_update_events()
+            .next()
+            .unwrap();
+        assert_eq!(over.target, target);
+
+        send_interaction(
+            &mut world,
+            draggable,
+            UiInteractionKind::Drag {
+                delta: Vec2::new(10., 10.),
+            },
+            Vec2::new(160., 50.),
+        );
+        drag_schedule.run(&mut world);
+        let (_, style) = ghosts.single(&world);
+        assert_eq!((style.left, style.top), (Val::Px(110.), Val::Px(0.)));
+
+        // Releasing the node drops it on the target and despawns the ghost
+        send_interaction(
+            &mut world,
+            draggable,
+            UiInteractionKind::DragEnd,
+            Vec2::new(160., 50.),
+        );
+        send_interaction(
+            &mut world,
+            draggable,
+            UiInteractionKind::Released,
+            Vec2::new(160., 50.),
+        );
+        drag_schedule.run(&mut world);
+        let drop = world
+            .resource::<Events<DragDrop>>()
+            .iter_current_update_events()
+            .next()
+            .unwrap();
+        assert_eq!(
+            (drop.dragged, drop.payload, drop.target),
+            (draggable, payload, target)
+        );
+        assert_eq!(ghosts.iter(&world).count(), 0);
+    }
+}
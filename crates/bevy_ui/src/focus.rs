@@ -1,19 +1,29 @@
-use crate::{CalculatedClip, DefaultUiCamera, Node, TargetCamera, UiScale, UiStack};
+use crate::{
+    geometry::resolve_node_radii,
+    rounded_rect_contains,
+    viewports::{is_view_visible, NodeVisibility},
+    BorderRadius, CalculatedClip, Node, RootFontSize, ScrollPosition, Style, TargetCamera, UiScale,
+    UiStack, UiViewports,
+};
+#[cfg(feature = "render")]
+use crate::{GamepadCursor, TouchState};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::Entity,
-    prelude::{Component, With},
-    query::QueryData,
-    reflect::ReflectComponent,
-    system::{Local, Query, Res},
+    event::{Event, EventReader, EventWriter},
+    prelude::Component,
+    query::{Has, QueryData},
+    reflect::{ReflectComponent, ReflectResource},
+    system::{Local, Query, Res, Resource},
+};
+use bevy_input::{
+    mouse::{MouseButton, MouseScrollUnit, MouseWheel},
+    ButtonInput,
 };
-use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::{camera::NormalizedRenderTarget, prelude::Camera, view::ViewVisibility};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
-use bevy_window::{PrimaryWindow, Window};
 
 use smallvec::SmallVec;
 
@@ -67,6 +77,14 @@ impl Default for Interaction {
     }
 }
 
+/// Marks a UI node as disabled.
+///
+/// Disabled nodes block the cursor like nodes with [`FocusPolicy::Block`], but their [`Interaction`] is always
+/// [`Interaction::None`], and they can't receive navigation focus.
+#[derive(Component, Copy, Clone, Eq, PartialEq, Debug, Default, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Disabled;
+
 /// A component storing the position of the mouse relative to the node, (0., 0.) being the top-left corner and (1., 1.) being the bottom-right
 /// If the mouse is not over the node, the value will go beyond the range of (0., 0.) to (1., 1.)
 ///
@@ -122,10 +140,100 @@ impl Default for FocusPolicy {
     }
 }
 
-/// Contains entities whose Interaction should be set to None
+/// How the shape of a UI node is hit tested by [`ui_focus_system`],
+/// [`ui_picking_system`](crate::ui_picking_system) and [`UiStacks`](crate::UiStacks).
+///
+/// Nodes without a [`HitTestMode`] are hit tested with [`HitTestMode::Shape`].
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum HitTestMode {
+    /// Points in the corners cut off by the node's [`BorderRadius`], or by the rounded corners of its
+    /// [`CalculatedClip`], don't hit the node.
+    #[default]
+    Shape,
+    /// Any point in the node's clipped rect hits the node, ignoring rounded corners.
+    ///
+    /// This is cheaper than [`HitTestMode::Shape`] for nodes with rounded corners.
+    Bounds,
+}
+
+/// The kind of a [`UiInteractionEvent`].
+#[derive(Copy, Clone, PartialEq, Debug, Reflect)]
+#[reflect(PartialEq)]
+pub enum UiInteractionKind {
+    /// The pointer was pressed over the node.
+    Pressed,
+    /// The pointer was released after pressing the node, whether or not it is still over the node.
+    Released,
+    /// The pointer was pressed and released over the node, possibly in the same frame.
+    Clicked,
+    /// The pointer started hovering over the node.
+    HoverStart,
+    /// The pointer stopped hovering over the node.
+    HoverEnd,
+    /// The pointer moved further than [`UiDragSettings::threshold`] while pressing the node.
+    DragStart,
+    /// The pointer moved while dragging the node.
+    Drag {
+        /// The distance moved since the last [`UiInteractionKind::DragStart`] or [`UiInteractionKind::Drag`]
+        /// event, in logical pixels.
+        delta: Vec2,
+    },
+    /// The pointer was released while dragging the node. Sent before [`UiInteractionKind::Released`].
+    DragEnd,
+}
+
+/// An interaction with a UI node that has an [`Interaction`] component, sent by [`ui_focus_system`] for the
+/// mouse and by [`ui_touch_system`](crate::ui_touch_system) for each touch.
+///
+/// Unlike polling [`Interaction`], these events capture presses that start and end within a single frame, and
+/// the movement of the pointer while dragging.
+#[derive(Event, Copy, Clone, PartialEq, Debug, Reflect)]
+pub struct UiInteractionEvent {
+    /// The node that was interacted with.
+    pub entity: Entity,
+    /// What happened.
+    pub kind: UiInteractionKind,
+    /// The position of the pointer, in logical pixels relative to the top left of the viewport of the node's camera.
+    ///
+    /// `None` if the position is unknown, such as when the cursor has left the window.
+    pub position: Option<Vec2>,
+    /// The button responsible for the interaction, `None` for hover events and touches.
+    ///
+    /// Presses by the button of an enabled [`GamepadCursor`] are reported as [`MouseButton::Left`].
+    pub button: Option<MouseButton>,
+}
+
+/// Controls when a pressed pointer starts dragging a node, for the mouse, touches and
+/// [`Draggable`](crate::Draggable) nodes.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct UiDragSettings {
+    /// The distance in logical pixels that a pressed pointer has to move before a drag starts.
+    pub threshold: f32,
+}
+
+impl Default for UiDragSettings {
+    fn default() -> Self {
+        Self { threshold: 4. }
+    }
+}
+
+/// A node pressed by the pointer, tracked to send drag and release events.
+struct PressedNode {
+    entity: Entity,
+    camera: Option<Entity>,
+    press_position: Option<Vec2>,
+    last_position: Option<Vec2>,
+    dragging: bool,
+}
+
+/// Contains entities whose Interaction should be set to None, and the state used to send [`UiInteractionEvent`]s.
 #[derive(Default)]
 pub struct State {
     entities_to_reset: SmallVec<[Entity; 1]>,
+    hovered: SmallVec<[(Entity, Option<Entity>); 1]>,
+    pressed: Vec<PressedNode>,
 }
 
 /// Main query for [`ui_focus_system`]
@@ -139,28 +247,64 @@ pub struct NodeQuery {
     relative_cursor_position: Option<&'static mut RelativeCursorPosition>,
     focus_policy: Option<&'static FocusPolicy>,
     calculated_clip: Option<&'static CalculatedClip>,
-    view_visibility: Option<&'static ViewVisibility>,
+    view_visibility: NodeVisibility,
     target_camera: Option<&'static TargetCamera>,
+    border_radius: Option<&'static BorderRadius>,
+    hit_test_mode: Option<&'static HitTestMode>,
+    disabled: Has<Disabled>,
+}
+
+/// The button and position of an enabled [`GamepadCursor`], which presses nodes like the left mouse button and
+/// replaces the cursor of the primary window.
+///
+/// Without the `render` feature, there is no gamepad cursor and this is always the default.
+#[derive(Clone, Copy, Default)]
+struct CursorButton {
+    pressed: bool,
+    just_pressed: bool,
+    just_released: bool,
+    position: Option<Vec2>,
+}
+
+#[cfg(feature = "render")]
+impl CursorButton {
+    fn new(gamepad_cursor: Option<&GamepadCursor>) -> Self {
+        gamepad_cursor.map_or(Self::default(), |cursor| Self {
+            pressed: cursor.pressed(),
+            just_pressed: cursor.just_pressed(),
+            just_released: cursor.just_released(),
+            position: cursor.active_position(),
+        })
+    }
 }
 
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
 ///
-/// Entities with a hidden [`ViewVisibility`] are always treated as released.
+/// The button of an enabled [`GamepadCursor`] presses nodes like the left mouse button. A node pressed by both is
+/// released once neither is held.
+///
+/// Touches are handled by [`ui_touch_system`](crate::ui_touch_system), and nodes pressed by a touch stay pressed
+/// until the last touch pressing them is released.
+///
+/// Entities with a hidden [`ViewVisibility`] are always treated as released, and [`Disabled`] entities
+/// block the cursor without being hovered or pressed.
+///
+/// The cursor is outside of the clipped regions of nodes, and of the corners cut off by their
+/// [`BorderRadius`] unless their [`HitTestMode`] is [`HitTestMode::Bounds`].
 #[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
-    camera_query: Query<(Entity, &Camera)>,
-    default_ui_camera: DefaultUiCamera,
-    primary_window: Query<Entity, With<PrimaryWindow>>,
-    windows: Query<&Window>,
+    viewports: UiViewports,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    touches_input: Res<Touches>,
+    #[cfg(feature = "render")] touch_state: Option<Res<TouchState>>,
+    #[cfg(feature = "render")] gamepad_cursor: Option<Res<GamepadCursor>>,
     ui_scale: Res<UiScale>,
+    root_font_size: Res<RootFontSize>,
+    drag_settings: Res<UiDragSettings>,
     ui_stack: Res<UiStack>,
     mut node_query: Query<NodeQuery>,
+    mut interaction_events: EventWriter<UiInteractionEvent>,
 ) {
-    let primary_window = primary_window.iter().next();
-
     // reset entities that were both clicked and released in the last frame
     for entity in state.entities_to_reset.drain(..) {
         if let Ok(NodeQueryItem {
@@ -172,12 +316,28 @@ pub fn ui_focus_system(
         }
     }
 
-    let mouse_released =
-        mouse_button_input.just_released(MouseButton::Left) || touches_input.any_just_released();
+    // Nodes pressed by a touch are released by `ui_touch_system`
+    #[cfg(feature = "render")]
+    let touch_pressed = |entity: Entity| {
+        touch_state
+            .as_ref()
+            .is_some_and(|touch_state| touch_state.is_pressing(entity))
+    };
+    #[cfg(not(feature = "render"))]
+    let touch_pressed = |_: Entity| false;
+
+    #[cfg(feature = "render")]
+    let cursor_button = CursorButton::new(gamepad_cursor.as_deref());
+    #[cfg(not(feature = "render"))]
+    let cursor_button = CursorButton::default();
+    let mouse_released = (mouse_button_input.just_released(MouseButton::Left)
+        || cursor_button.just_released)
+        && !mouse_button_input.pressed(MouseButton::Left)
+        && !cursor_button.pressed;
     if mouse_released {
         for node in &mut node_query {
             if let Some(mut interaction) = node.interaction {
-                if *interaction == Interaction::Pressed {
+                if *interaction == Interaction::Pressed && !touch_pressed(node.entity) {
                     *interaction = Interaction::None;
                 }
             }
@@ -185,33 +345,37 @@ pub fn ui_focus_system(
     }
 
     let mouse_clicked =
-        mouse_button_input.just_pressed(MouseButton::Left) || touches_input.any_just_pressed();
+        mouse_button_input.just_pressed(MouseButton::Left) || cursor_button.just_pressed;
 
-    let camera_cursor_positions: HashMap<Entity, Vec2> = camera_query
-        .iter()
-        .filter_map(|(entity, camera)| {
-            // Interactions are only supported for cameras rendering to a window.
-            let Some(NormalizedRenderTarget::Window(window_ref)) =
-                camera.target.normalize(primary_window)
-            else {
-                return None;
-            };
+    let camera_cursor_positions = viewports.cursor_positions(cursor_button.position, ui_scale.0);
 
-            let viewport_position = camera
-                .logical_viewport_rect()
-                .map(|rect| rect.min)
-                .unwrap_or_default();
-            windows
-                .get(window_ref.entity())
-                .ok()
-                .and_then(|window| window.cursor_position())
-                .or_else(|| touches_input.first_pressed_position())
-                .map(|cursor_position| (entity, cursor_position - viewport_position))
-        })
-        // The cursor position returned by `Window` only takes into account the window scale factor and not `UiScale`.
-        // To convert the cursor position to logical UI viewport coordinates we have to divide it by `UiScale`.
-        .map(|(entity, cursor_position)| (entity, cursor_position / ui_scale.0))
-        .collect();
+    // Points in the corners cut off by a node's border radius don't hit it, unless its `HitTestMode` is `Bounds`
+    let shape_contains = |node: &NodeQueryItem, camera_entity: Entity, point: Vec2| {
+        if node.hit_test_mode.copied().unwrap_or_default() != HitTestMode::Shape {
+            return true;
+        }
+        // The rounded corners of the clip
+        if node
+            .calculated_clip
+            .is_some_and(|clip| !clip.contains(point))
+        {
+            return false;
+        }
+        let Some(border_radius) = node.border_radius else {
+            return true;
+        };
+        let viewport_size = viewports
+            .get(camera_entity)
+            .map_or(Vec2::ZERO, |viewport| viewport.ui_logical_size(ui_scale.0));
+        let radii = resolve_node_radii(
+            border_radius,
+            node.node,
+            viewport_size,
+            ui_scale.0,
+            root_font_size.0,
+        );
+        rounded_rect_contains(node.node.logical_rect(node.global_transform), radii, point)
+    };
 
     // prepare an iterator that contains all the nodes that have the cursor in their rect,
     // from the top node to the bottom one. this will also reset the interaction to `None`
@@ -226,9 +390,8 @@ pub fn ui_focus_system(
                 return None;
             };
 
-            let view_visibility = node.view_visibility?;
             // Nodes that are not rendered should not be interactable
-            if !view_visibility.get() {
+            if !is_view_visible(&node.view_visibility)? {
                 // Reset their interaction to None to avoid strange stuck state
                 if let Some(mut interaction) = node.interaction {
                     // We cannot simply set the interaction to None, as that will trigger change detection repeatedly
@@ -236,10 +399,7 @@ pub fn ui_focus_system(
                 }
                 return None;
             }
-            let camera_entity = node
-                .target_camera
-                .map(TargetCamera::entity)
-                .or(default_ui_camera.get())?;
+            let camera_entity = viewports.viewport_of(node.target_camera)?;
 
             let node_rect = node.node.logical_rect(node.global_transform);
 
@@ -270,7 +430,10 @@ pub fn ui_focus_system(
                 normalized: relative_cursor_position,
             };
 
-            let contains_cursor = relative_cursor_position_component.mouse_over();
+            let contains_cursor = relative_cursor_position_component.mouse_over()
+                && camera_cursor_positions
+                    .get(&camera_entity)
+                    .is_some_and(|cursor| shape_contains(&node, camera_entity, *cursor));
 
             // Save the relative cursor position to the correct component
             if let Some(mut node_relative_cursor_position_component) = node.relative_cursor_position
@@ -282,7 +445,8 @@ pub fn ui_focus_system(
                 Some(*entity)
             } else {
                 if let Some(mut interaction) = node.interaction {
-                    if *interaction == Interaction::Hovered || (relative_cursor_position.is_none())
+                    if *interaction == Interaction::Hovered
+                        || (relative_cursor_position.is_none() && !touch_pressed(*entity))
                     {
                         interaction.set_if_neq(Interaction::None);
                     }
@@ -295,9 +459,18 @@ pub fn ui_focus_system(
 
     // set Pressed or Hovered on top nodes. as soon as a node with a `Block` focus policy is detected,
     // the iteration will stop on it because it "captures" the interaction.
+    let mut top_nodes: SmallVec<[(Entity, Option<Entity>); 1]> = SmallVec::new();
     let mut iter = node_query.iter_many_mut(hovered_nodes.by_ref());
     while let Some(node) = iter.fetch_next() {
+        if node.disabled {
+            if let Some(mut interaction) = node.interaction {
+                interaction.set_if_neq(Interaction::None);
+            }
+            break;
+        }
         if let Some(mut interaction) = node.interaction {
+            let camera = viewports.viewport_of(node.target_camera);
+            top_nodes.push((node.entity, camera));
             if mouse_clicked {
                 // only consider nodes with Interaction "pressed"
                 if *interaction != Interaction::Pressed {
@@ -331,4 +504,323 @@ pub fn ui_focus_system(
             }
         }
     }
+
+    let mut events = Vec::new();
+    send_interaction_events(
+        &mut state,
+        &mut events,
+        &top_nodes,
+        &camera_cursor_positions,
+        drag_settings.threshold,
+        mouse_clicked,
+        mouse_released,
+    );
+    interaction_events.send_batch(events);
+}
+
+/// Collects the [`UiInteractionEvent`]s for the nodes hovered this frame, given by `top_nodes` with their cameras.
+fn send_interaction_events(
+    state: &mut State,
+    interaction_events: &mut Vec<UiInteractionEvent>,
+    top_nodes: &[(Entity, Option<Entity>)],
+    camera_cursor_positions: &HashMap<Entity, Vec2>,
+    drag_threshold: f32,
+    mouse_clicked: bool,
+    mouse_released: bool,
+) {
+    let cursor_position = |camera: Option<Entity>| {
+        camera.and_then(|camera| camera_cursor_positions.get(&camera).copied())
+    };
+    let event = |entity, kind, position, button| UiInteractionEvent {
+        entity,
+        kind,
+        position,
+        button,
+    };
+    let is_top_node = |entity: Entity| top_nodes.iter().any(|(top, _)| *top == entity);
+
+    for &(entity, camera) in &state.hovered {
+        if !is_top_node(entity) {
+            interaction_events.push(event(
+                entity,
+                UiInteractionKind::HoverEnd,
+                cursor_position(camera),
+                None,
+            ));
+        }
+    }
+    for &(entity, camera) in top_nodes {
+        if !state.hovered.iter().any(|(hovered, _)| *hovered == entity) {
+            interaction_events.push(event(
+                entity,
+                UiInteractionKind::HoverStart,
+                cursor_position(camera),
+                None,
+            ));
+        }
+    }
+    state.hovered = top_nodes.iter().copied().collect();
+
+    let button = Some(MouseButton::Left);
+    if mouse_clicked {
+        for &(entity, camera) in top_nodes {
+            let position = cursor_position(camera);
+            interaction_events.push(event(entity, UiInteractionKind::Pressed, position, button));
+            if !state.pressed.iter().any(|pressed| pressed.entity == entity) {
+                state.pressed.push(PressedNode {
+                    entity,
+                    camera,
+                    press_position: position,
+                    last_position: position,
+                    dragging: false,
+                });
+            }
+        }
+    }
+
+    for pressed in &mut state.pressed {
+        let Some(position) = cursor_position(pressed.camera) else {
+            continue;
+        };
+        let Some(last_position) = pressed.last_position else {
+            pressed.press_position = Some(position);
+            pressed.last_position = Some(position);
+            continue;
+        };
+        if !pressed.dragging
+            && pressed
+                .press_position
+                .is_some_and(|press_position| drag_threshold <= press_position.distance(position))
+        {
+            pressed.dragging = true;
+            interaction_events.push(event(
+                pressed.entity,
+                UiInteractionKind::DragStart,
+                Some(position),
+                button,
+            ));
+        }
+        if pressed.dragging && position != last_position {
+            interaction_events.push(event(
+                pressed.entity,
+                UiInteractionKind::Drag {
+                    delta: position - last_position,
+                },
+                Some(position),
+                button,
+            ));
+        }
+        pressed.last_position = Some(position);
+    }
+
+    if mouse_released {
+        for pressed in state.pressed.drain(..) {
+            let position = cursor_position(pressed.camera).or(pressed.last_position);
+            if pressed.dragging {
+                interaction_events.push(event(
+                    pressed.entity,
+                    UiInteractionKind::DragEnd,
+                    position,
+                    button,
+                ));
+            }
+            interaction_events.push(event(
+                pressed.entity,
+                UiInteractionKind::Released,
+                position,
+                button,
+            ));
+            if is_top_node(pressed.entity) {
+                interaction_events.push(event(
+                    pressed.entity,
+                    UiInteractionKind::Clicked,
+                    position,
+                    button,
+                ));
+            }
+        }
+    }
+}
+
+/// The number of logical pixels scrolled for each line of a [`MouseWheel`] event in [`MouseScrollUnit::Line`] units.
+pub const SCROLL_LINE_HEIGHT: f32 = 20.;
+
+/// The system that scrolls nodes with a [`ScrollPosition`] using the mouse wheel.
+///
+/// Only the topmost visible node under the cursor with a [`ScrollPosition`] and an [`Overflow`](crate::Overflow)
+/// that isn't visible on both axes is scrolled. If the node can only scroll horizontally, vertical mouse wheel
+/// motion scrolls it horizontally.
+///
+/// The resulting [`ScrollPosition`] is clamped to the size of the node's content by [`ui_layout_system`](crate::ui_layout_system).
+#[allow(clippy::too_many_arguments)]
+pub fn ui_scroll_system(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    viewports: UiViewports,
+    #[cfg(feature = "render")] gamepad_cursor: Option<Res<GamepadCursor>>,
+    ui_scale: Res<UiScale>,
+    ui_stack: Res<UiStack>,
+    mut scroll_query: Query<ScrollQuery>,
+) {
+    let delta = mouse_wheel_events
+        .read()
+        .map(|event| {
+            let delta = Vec2::new(event.x, event.y);
+            match event.unit {
+                MouseScrollUnit::Line => delta * SCROLL_LINE_HEIGHT,
+                MouseScrollUnit::Pixel => delta,
+            }
+        })
+        .sum::<Vec2>();
+    if delta == Vec2::ZERO {
+        return;
+    }
+
+    #[cfg(feature = "render")]
+    let cursor_position = CursorButton::new(gamepad_cursor.as_deref()).position;
+    #[cfg(not(feature = "render"))]
+    let cursor_position = None;
+    let camera_cursor_positions = viewports.cursor_positions(cursor_position, ui_scale.0);
+
+    for entity in ui_stack.uinodes.iter().rev() {
+        let Ok(mut node) = scroll_query.get_mut(*entity) else {
+            continue;
+        };
+
+        if is_view_visible(&node.view_visibility) != Some(true) || node.style.overflow.is_visible()
+        {
+            continue;
+        }
+
+        let Some(cursor_position) = viewports
+            .viewport_of(node.target_camera)
+            .and_then(|camera_entity| camera_cursor_positions.get(&camera_entity))
+        else {
+            continue;
+        };
+
+        let node_rect = node.node.logical_rect(node.global_transform);
+        let visible_rect = node
+            .calculated_clip
+            .map(|clip| node_rect.intersect(clip.clip))
+            .unwrap_or(node_rect);
+        if !visible_rect.contains(*cursor_position) {
+            continue;
+        }
+
+        // A positive mouse wheel delta scrolls towards the start of the content.
+        let mut delta = -delta;
+        if node.style.overflow.y.is_visible() {
+            delta = Vec2::new(delta.x + delta.y, 0.);
+        } else if node.style.overflow.x.is_visible() {
+            delta.x = 0.;
+        }
+        node.scroll_position.offset_x += delta.x;
+        node.scroll_position.offset_y += delta.y;
+        return;
+    }
+}
+
+/// Main query for [`ui_scroll_system`]
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct ScrollQuery {
+    node: &'static Node,
+    style: &'static Style,
+    global_transform: &'static GlobalTransform,
+    scroll_position: &'static mut ScrollPosition,
+    calculated_clip: Option<&'static CalculatedClip>,
+    view_visibility: NodeVisibility,
+    target_camera: Option<&'static TargetCamera>,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::entity::Entity;
+    use bevy_input::mouse::MouseButton;
+    use bevy_math::Vec2;
+    use bevy_utils::HashMap;
+
+    use super::{send_interaction_events, State, UiInteractionEvent, UiInteractionKind};
+
+    #[test]
+    fn interaction_events() {
+        let node = Entity::from_raw(1);
+        let camera = Entity::from_raw(2);
+        let mut state = State::default();
+
+        let mut frame = |cursor: Vec2, over_node: bool, clicked: bool, released: bool| {
+            let mut events = Vec::new();
+            let top_nodes = if over_node {
+                vec![(node, Some(camera))]
+            } else {
+                Vec::new()
+            };
+            send_interaction_events(
+                &mut state,
+                &mut events,
+                &top_nodes,
+                &HashMap::from([(camera, cursor)]),
+                4.,
+                clicked,
+                released,
+            );
+            events
+                .into_iter()
+                .map(|event: UiInteractionEvent| {
+                    assert_eq!(event.entity, node);
+                    assert_eq!(event.position, Some(cursor));
+                    let is_hover = matches!(
+                        event.kind,
+                        UiInteractionKind::HoverStart | UiInteractionKind::HoverEnd
+                    );
+                    assert_eq!(event.button, (!is_hover).then_some(MouseButton::Left));
+                    event.kind
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            frame(Vec2::ZERO, true, false, false),
+            [UiInteractionKind::HoverStart]
+        );
+        // A click that starts and ends in a single frame
+        assert_eq!(
+            frame(Vec2::ZERO, true, true, true),
+            [
+                UiInteractionKind::Pressed,
+                UiInteractionKind::Released,
+                UiInteractionKind::Clicked
+            ]
+        );
+
+        // Dragging off of the node
+        assert_eq!(
+            frame(Vec2::ZERO, true, true, false),
+            [UiInteractionKind::Pressed]
+        );
+        assert_eq!(frame(Vec2::new(2., 0.), true, false, false), []);
+        assert_eq!(
+            frame(Vec2::new(5., 0.), true, false, false),
+            [
+                UiInteractionKind::DragStart,
+                UiInteractionKind::Drag {
+                    delta: Vec2::new(3., 0.)
+                }
+            ]
+        );
+        assert_eq!(
+            frame(Vec2::new(20., 0.), false, false, false),
+            [
+                UiInteractionKind::HoverEnd,
+                UiInteractionKind::Drag {
+                    delta: Vec2::new(15., 0.)
+                }
+            ]
+        );
+        // Released away from the node, so it isn't clicked
+        assert_eq!(
+            frame(Vec2::new(20., 0.), false, false, true),
+            [UiInteractionKind::DragEnd, UiInteractionKind::Released]
+        );
+    }
 }
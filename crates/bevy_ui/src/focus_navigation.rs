@@ -0,0 +1,398 @@
+//! Keyboard and gamepad focus navigation between UI nodes.
+//!
+//! Nodes with a [`Focusable`] component can receive navigation focus, which is stored in the [`FocusState`]
+//! resource. Focus is moved:
+//! * in tab order with `Tab` and `Shift + Tab`,
+//! * to the nearest node in a direction with the arrow keys or a gamepad's d-pad,
+//! * to a node pressed with the mouse or a touch.
+//!
+//! Pressing `Enter` or a gamepad's south action button sends a [`FocusActivated`] event for the focused node.
+//!
+//! While a UI node has captured the keyboard with [`UiKeyboardCapture`], like a focused text input or an open
+//! command palette, the keyboard doesn't move the focus or activate the focused node.
+
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonType},
+    keyboard::KeyCode,
+    ButtonInput,
+};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::prelude::GlobalTransform;
+
+#[cfg(feature = "render")]
+use crate::GamepadCursor;
+use crate::{
+    viewports::{is_view_visible, NodeVisibility},
+    Disabled, Interaction, Node, UiStack,
+};
+
+/// Marks a UI node as able to receive navigation focus.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Focusable {
+    /// The position of the node in the tab order.
+    ///
+    /// Nodes are visited in ascending order of their tab index when `Tab` is pressed. Nodes with equal tab
+    /// indices are visited in the order of the [`UiStack`].
+    pub tab_index: i32,
+}
+
+impl Focusable {
+    /// Creates a [`Focusable`] with the given tab index.
+    pub const fn new(tab_index: i32) -> Self {
+        Self { tab_index }
+    }
+}
+
+/// The UI node that has navigation focus, updated by [`ui_focus_navigation_system`].
+///
+/// Setting [`FocusState::focused`] moves the focus directly. The focus is cleared if the focused node is hidden,
+/// despawned or its [`Focusable`] component is removed.
+#[derive(Resource, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default, PartialEq)]
+pub struct FocusState {
+    /// The focused node, if any.
+    pub focused: Option<Entity>,
+}
+
+/// The UI nodes that have captured the keyboard, like an open command palette.
+///
+/// The keyboard input itself is left untouched. Instead, systems that handle keys check this resource: only the
+/// [`UiKeyboardCapture::owner`] should respond to keyboard input, [`ui_focus_navigation_system`] ignores the keyboard
+/// while it is captured, and the systems of an app can be skipped with the [`ui_has_keyboard_capture`] run condition:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::ui_has_keyboard_capture;
+/// # fn move_player() {}
+/// # let mut schedule = Schedule::default();
+/// schedule.add_systems(move_player.run_if(not(ui_has_keyboard_capture)));
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default, PartialEq)]
+pub struct UiKeyboardCapture {
+    /// The capturing nodes, in the order they captured the keyboard.
+    owners: Vec<Entity>,
+}
+
+impl UiKeyboardCapture {
+    /// Captures the keyboard for `entity`, on top of any earlier captures.
+    ///
+    /// Does nothing if `entity` has already captured the keyboard.
+    pub fn capture(&mut self, entity: Entity) {
+        if !self.has_captured(entity) {
+            self.owners.push(entity);
+        }
+    }
+
+    /// Releases the capture of `entity`, if any. The keyboard stays captured by the other owners.
+    pub fn release(&mut self, entity: Entity) {
+        self.owners.retain(|&owner| owner != entity);
+    }
+
+    /// Whether `entity` has captured the keyboard, even if a later capture has taken it over.
+    pub fn has_captured(&self, entity: Entity) -> bool {
+        self.owners.contains(&entity)
+    }
+
+    /// The node that receives keyboard input, the latest of the capturing nodes.
+    pub fn owner(&self) -> Option<Entity> {
+        self.owners.last().copied()
+    }
+
+    /// Whether any node has captured the keyboard.
+    pub fn is_captured(&self) -> bool {
+        !self.owners.is_empty()
+    }
+}
+
+/// A run condition that is true while a UI node has captured the keyboard with [`UiKeyboardCapture`].
+pub fn ui_has_keyboard_capture(capture: Option<Res<UiKeyboardCapture>>) -> bool {
+    capture.is_some_and(|capture| capture.is_captured())
+}
+
+/// Sent when the focused UI node is activated by pressing `Enter` or a gamepad's south action button.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FocusActivated(pub Entity);
+
+/// A direction in which focus can be moved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusDirection {
+    /// The direction as a unit vector in UI coordinates, where the y axis points down.
+    fn as_vec2(self) -> Vec2 {
+        match self {
+            FocusDirection::Up => Vec2::NEG_Y,
+            FocusDirection::Down => Vec2::Y,
+            FocusDirection::Left => Vec2::NEG_X,
+            FocusDirection::Right => Vec2::X,
+        }
+    }
+}
+
+/// A node that can be focused this frame.
+struct FocusCandidate {
+    entity: Entity,
+    tab_index: i32,
+    rect: Rect,
+}
+
+/// Finds the nearest candidate in `direction` from `from`.
+///
+/// Candidates are scored by their distance along `direction`, with their offset across it weighted more heavily,
+/// so nodes in line with `from` are preferred over closer nodes off to the side.
+fn nearest_in_direction(
+    candidates: &[FocusCandidate],
+    from: &FocusCandidate,
+    direction: FocusDirection,
+) -> Option<Entity> {
+    let direction = direction.as_vec2();
+    candidates
+        .iter()
+        .filter(|candidate| candidate.entity != from.entity)
+        .filter_map(|candidate| {
+            let offset = candidate.rect.center() - from.rect.center();
+            let distance = offset.dot(direction);
+            if distance <= 0. {
+                return None;
+            }
+            let cross_distance = (offset - distance * direction).length();
+            Some((candidate.entity, distance + 2. * cross_distance))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+/// Moves the focus in response to keyboard and gamepad input, and sends [`FocusActivated`] events.
+///
+/// The keyboard is ignored while it is captured with [`UiKeyboardCapture`].
+#[allow(clippy::too_many_arguments)]
+pub fn ui_focus_navigation_system(
+    mut focus_state: ResMut<FocusState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    keyboard_capture: Option<Res<UiKeyboardCapture>>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    #[cfg(feature = "render")] gamepad_cursor: Option<Res<GamepadCursor>>,
+    ui_stack: Res<UiStack>,
+    focusable_query: Query<
+        (&Focusable, &Node, &GlobalTransform, NodeVisibility),
+        Without<Disabled>,
+    >,
+    interaction_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Focusable>)>,
+    mut activated_events: EventWriter<FocusActivated>,
+) {
+    let keyboard_captured = keyboard_capture.is_some_and(|capture| capture.is_captured());
+    let key_just_pressed = |key: KeyCode| !keyboard_captured && keyboard_input.just_pressed(key);
+    let button_just_pressed = |button_type: GamepadButtonType| {
+        gamepad_input
+            .get_just_pressed()
+            .any(|button| button.button_type == button_type)
+    };
+
+    // Focusable nodes that are visible and enabled, in stack order
+    let mut candidates = ui_stack
+        .uinodes
+        .iter()
+        .filter_map(|&entity| {
+            let (focusable, node, global_transform, view_visibility) =
+                focusable_query.get(entity).ok()?;
+            if is_view_visible(&view_visibility) == Some(false)
+                || node.size().cmple(Vec2::ZERO).any()
+            {
+                return None;
+            }
+            Some(FocusCandidate {
+                entity,
+                tab_index: focusable.tab_index,
+                rect: node.logical_rect(global_transform),
+            })
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|candidate| candidate.tab_index);
+
+    let mut focused = focus_state
+        .focused
+        .and_then(|entity| candidates.iter().position(|c| c.entity == entity));
+
+    // Nodes pressed with the mouse take the focus
+    if let Some(pressed) = interaction_query
+        .iter()
+        .filter(|(_, interaction)| **interaction == Interaction::Pressed)
+        .find_map(|(entity, _)| candidates.iter().position(|c| c.entity == entity))
+    {
+        focused = Some(pressed);
+    }
+
+    let direction =
+        if key_just_pressed(KeyCode::ArrowUp) || button_just_pressed(GamepadButtonType::DPadUp) {
+            Some(FocusDirection::Up)
+        } else if key_just_pressed(KeyCode::ArrowDown)
+            || button_just_pressed(GamepadButtonType::DPadDown)
+        {
+            Some(FocusDirection::Down)
+        } else if key_just_pressed(KeyCode::ArrowLeft)
+            || button_just_pressed(GamepadButtonType::DPadLeft)
+        {
+            Some(FocusDirection::Left)
+        } else if key_just_pressed(KeyCode::ArrowRight)
+            || button_just_pressed(GamepadButtonType::DPadRight)
+        {
+            Some(FocusDirection::Right)
+        } else {
+            None
+        };
+
+    if !candidates.is_empty() {
+        if key_just_pressed(KeyCode::Tab) {
+            let backwards = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+            let count = candidates.len();
+            focused = Some(match (focused, backwards) {
+                (Some(index), false) => (index + 1) % count,
+                (Some(index), true) => (index + count - 1) % count,
+                (None, false) => 0,
+                (None, true) => count - 1,
+            });
+        } else if let Some(direction) = direction {
+            focused = match focused {
+                Some(index) => nearest_in_direction(&candidates, &candidates[index], direction)
+                    .and_then(|entity| candidates.iter().position(|c| c.entity == entity))
+                    .or(focused),
+                // Without a focused node, navigation starts at the first node in tab order
+                None => Some(0),
+            };
+        }
+    }
+
+    let focused = focused.map(|index| candidates[index].entity);
+    if focus_state.focused != focused {
+        focus_state.focused = focused;
+    }
+
+    // An enabled gamepad cursor clicks with its button instead
+    #[cfg(feature = "render")]
+    let cursor_button = gamepad_cursor
+        .filter(|cursor| cursor.enabled)
+        .map(|cursor| cursor.button);
+    #[cfg(not(feature = "render"))]
+    let cursor_button = None;
+    if let Some(entity) = focused {
+        if key_just_pressed(KeyCode::Enter)
+            || key_just_pressed(KeyCode::NumpadEnter)
+            || (cursor_button != Some(GamepadButtonType::South)
+                && button_just_pressed(GamepadButtonType::South))
+        {
+            activated_events.send(FocusActivated(entity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, prelude::*};
+    use bevy_input::{gamepad::GamepadButton, keyboard::KeyCode, ButtonInput};
+    use bevy_math::Vec2;
+    use bevy_transform::prelude::GlobalTransform;
+
+    use super::{
+        ui_focus_navigation_system, FocusActivated, FocusState, Focusable, UiKeyboardCapture,
+    };
+    use crate::{Node, UiStack};
+
+    fn press(world: &mut World, schedule: &mut Schedule, key: KeyCode) {
+        world.resource_mut::<ButtonInput<KeyCode>>().press(key);
+        schedule.run(world);
+        let mut keyboard_input = world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard_input.release(key);
+        keyboard_input.clear();
+    }
+
+    #[test]
+    fn focus_moves_in_tab_order_and_by_direction() {
+        let mut world = World::new();
+        world.init_resource::<FocusState>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ButtonInput<GamepadButton>>();
+        world.init_resource::<Events<FocusActivated>>();
+
+        // A 2x2 grid of focusable nodes, with the tab order running down the columns
+        let mut spawn = |center: Vec2, tab_index: i32| {
+            world
+                .spawn((
+                    Node {
+                        calculated_size: Vec2::splat(50.),
+                        ..Default::default()
+                    },
+                    GlobalTransform::from_translation(center.extend(0.)),
+                    Focusable::new(tab_index),
+                ))
+                .id()
+        };
+        let top_left = spawn(Vec2::new(50., 50.), 0);
+        let top_right = spawn(Vec2::new(150., 50.), 2);
+        let bottom_left = spawn(Vec2::new(50., 150.), 1);
+        let bottom_right = spawn(Vec2::new(150., 150.), 3);
+        world.insert_resource(UiStack {
+            uinodes: vec![top_left, top_right, bottom_left, bottom_right],
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_focus_navigation_system);
+        let focused = |world: &World| world.resource::<FocusState>().focused;
+
+        schedule.run(&mut world);
+        assert_eq!(focused(&world), None);
+
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(focused(&world), Some(top_left));
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(focused(&world), Some(bottom_left));
+        press(&mut world, &mut schedule, KeyCode::ArrowRight);
+        assert_eq!(focused(&world), Some(bottom_right));
+        press(&mut world, &mut schedule, KeyCode::ArrowUp);
+        assert_eq!(focused(&world), Some(top_right));
+        // There is nothing further up, so the focus doesn't move
+        press(&mut world, &mut schedule, KeyCode::ArrowUp);
+        assert_eq!(focused(&world), Some(top_right));
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(focused(&world), Some(bottom_right));
+        // Tab wraps around to the start of the tab order
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(focused(&world), Some(top_left));
+
+        press(&mut world, &mut schedule, KeyCode::Enter);
+        let events = world.resource::<Events<FocusActivated>>();
+        assert_eq!(
+            events
+                .get_reader()
+                .read(events)
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![FocusActivated(top_left)]
+        );
+
+        // The keyboard doesn't move the focus while it is captured
+        let mut capture = UiKeyboardCapture::default();
+        capture.capture(bottom_right);
+        world.insert_resource(capture);
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(focused(&world), Some(top_left));
+        world
+            .resource_mut::<UiKeyboardCapture>()
+            .release(bottom_right);
+        press(&mut world, &mut schedule, KeyCode::Tab);
+        assert_eq!(focused(&world), Some(bottom_left));
+
+        // Focus is cleared when the focused node can no longer be focused
+        world.entity_mut(bottom_left).remove::<Focusable>();
+        schedule.run(&mut world);
+        assert_eq!(focused(&world), None);
+    }
+}
@@ -0,0 +1,346 @@
+//! A software cursor moved with a gamepad's left stick.
+//!
+//! While [`GamepadCursor::enabled`] is set, the cursor replaces the mouse cursor of the primary window for UI hit
+//! testing, so nodes are hovered and their [`Interaction`](crate::Interaction) updated exactly as with a mouse.
+//! Pressing [`GamepadCursor::button`] presses the nodes under the cursor like the left mouse button, without
+//! changing the [`ButtonInput<MouseButton>`](bevy_input::ButtonInput) resource, so the systems of the app don't see
+//! fake mouse clicks. The state of the button is read with [`GamepadCursor::pressed`].
+//!
+//! The cursor accelerates while the stick is held, and snaps to the center of the nearest [`Focusable`] node when
+//! the stick is released close to one. It is drawn with [`GamepadCursor::image`] over all other nodes, with
+//! [`ZIndex::CURSOR`].
+
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_input::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    Axis, ButtonInput,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{camera::Camera, view::ViewVisibility};
+use bevy_time::Time;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::default;
+use bevy_window::{PrimaryWindow, Window};
+
+use crate::{
+    node_bundles::ImageBundle, DefaultUiCamera, Disabled, FocusPolicy, Focusable, Node,
+    PositionType, Style, UiImage, UiScale, UiStacks, Val, ZIndex,
+};
+
+/// The gamepad-driven software cursor, see the [module docs](self).
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GamepadCursor {
+    /// Whether the cursor is shown and replaces the mouse cursor.
+    pub enabled: bool,
+    /// The position of the cursor in logical pixels, relative to the top left of the primary window.
+    ///
+    /// Set to the center of the window when the cursor is enabled without a position.
+    pub position: Option<Vec2>,
+    /// The speed of the cursor when the stick starts being pushed all the way, in logical pixels per second.
+    pub speed: f32,
+    /// The maximum speed of the cursor, in logical pixels per second.
+    pub max_speed: f32,
+    /// How fast the speed of the cursor increases while the stick is held, in logical pixels per second squared.
+    pub acceleration: f32,
+    /// The cursor snaps to the center of the nearest [`Focusable`] node within this distance when the stick is
+    /// released, in logical pixels. Zero disables snapping.
+    pub snap_distance: f32,
+    /// The gamepad button pressing the nodes under the cursor, like the left mouse button.
+    pub button: GamepadButtonType,
+    /// The image the cursor is drawn with.
+    pub image: UiImage,
+    /// The size of the cursor image, in logical pixels.
+    pub size: Vec2,
+    /// The point of the cursor image that is at the cursor position, relative to its top left corner.
+    pub hotspot: Vec2,
+    /// The current speed, reset when the stick is released.
+    #[reflect(ignore)]
+    current_speed: f32,
+    #[reflect(ignore)]
+    pressed: bool,
+    #[reflect(ignore)]
+    just_pressed: bool,
+    #[reflect(ignore)]
+    just_released: bool,
+}
+
+impl Default for GamepadCursor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: None,
+            speed: 400.,
+            max_speed: 1600.,
+            acceleration: 2000.,
+            snap_distance: 32.,
+            button: GamepadButtonType::South,
+            image: UiImage::default(),
+            size: Vec2::splat(12.),
+            hotspot: Vec2::ZERO,
+            current_speed: 0.,
+            pressed: false,
+            just_pressed: false,
+            just_released: false,
+        }
+    }
+}
+
+impl GamepadCursor {
+    /// Returns the cursor position if the cursor is enabled.
+    pub fn active_position(&self) -> Option<Vec2> {
+        self.position.filter(|_| self.enabled)
+    }
+
+    /// Whether [`GamepadCursor::button`] is held while the cursor is enabled.
+    pub fn pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Whether the cursor started pressing this frame.
+    pub fn just_pressed(&self) -> bool {
+        self.just_pressed
+    }
+
+    /// Whether the cursor stopped pressing this frame, because its button was released or the cursor was disabled.
+    pub fn just_released(&self) -> bool {
+        self.just_released
+    }
+
+    /// Updates the state of the cursor's button.
+    fn set_pressed(&mut self, pressed: bool) {
+        self.just_pressed = pressed && !self.pressed;
+        self.just_released = !pressed && self.pressed;
+        self.pressed = pressed;
+    }
+
+    /// Moves the cursor with the `stick` direction over `delta_seconds`, keeping it inside `bounds`.
+    ///
+    /// Returns false if the stick is at rest, resetting the cursor's speed.
+    fn advance(&mut self, stick: Vec2, delta_seconds: f32, bounds: Vec2) -> bool {
+        if stick == Vec2::ZERO {
+            self.current_speed = 0.;
+            return false;
+        }
+        self.current_speed = if self.current_speed == 0. {
+            self.speed
+        } else {
+            (self.current_speed + self.acceleration * delta_seconds).min(self.max_speed)
+        };
+        let position = self.position.unwrap_or(0.5 * bounds);
+        let position = position + stick.clamp_length_max(1.) * self.current_speed * delta_seconds;
+        self.position = Some(position.clamp(Vec2::ZERO, bounds));
+        true
+    }
+}
+
+/// Returns the target nearest to `position` within `snap_distance`.
+fn snap_target(
+    position: Vec2,
+    targets: impl Iterator<Item = Vec2>,
+    snap_distance: f32,
+) -> Option<Vec2> {
+    targets
+        .map(|target| (target, target.distance(position)))
+        .filter(|(_, distance)| *distance <= snap_distance)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(target, _)| target)
+}
+
+/// Marker for the node drawing the [`GamepadCursor`].
+#[derive(Component)]
+pub struct GamepadCursorNode;
+
+/// The state of [`gamepad_cursor_system`].
+#[derive(Default)]
+pub struct GamepadCursorState {
+    /// Whether the stick moved the cursor last frame.
+    moving: bool,
+    node: Option<Entity>,
+}
+
+/// Moves the [`GamepadCursor`] with the left stick of the connected gamepads, updates the state of its button,
+/// and updates the node drawing the cursor.
+#[allow(clippy::too_many_arguments)]
+pub fn gamepad_cursor_system(
+    mut commands: Commands,
+    mut state: Local<GamepadCursorState>,
+    mut cursor: ResMut<GamepadCursor>,
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    ui_stacks: UiStacks,
+    focusables: Query<
+        (Entity, &Node, &GlobalTransform, Option<&ViewVisibility>),
+        (With<Focusable>, Without<Disabled>),
+    >,
+    mut cursor_nodes: Query<&mut Style, With<GamepadCursorNode>>,
+) {
+    let window = primary_window.get_single().ok().filter(|_| cursor.enabled);
+    let Some(window) = window else {
+        if cursor.pressed || cursor.just_released {
+            cursor.set_pressed(false);
+        }
+        if let Some(node) = state.node.take().and_then(|node| commands.get_entity(node)) {
+            node.despawn_recursive();
+        }
+        state.moving = false;
+        return;
+    };
+    let bounds = Vec2::new(window.width(), window.height());
+
+    let stick_of = |gamepad: Gamepad| {
+        let axis = |axis_type| axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.);
+        // The stick's y axis points up, the UI's points down
+        Vec2::new(
+            axis(GamepadAxisType::LeftStickX),
+            -axis(GamepadAxisType::LeftStickY),
+        )
+    };
+    let stick = gamepads
+        .iter()
+        .map(stick_of)
+        .find(|stick| *stick != Vec2::ZERO)
+        .unwrap_or(Vec2::ZERO);
+    if cursor.position.is_none() {
+        cursor.position = Some(0.5 * bounds);
+    }
+    let moving = cursor.advance(stick, time.delta_seconds(), bounds);
+
+    if state.moving && !moving && cursor.snap_distance > 0. {
+        let centers = focusables
+            .iter()
+            .filter(|(.., view_visibility)| !view_visibility.is_some_and(|v| !v.get()))
+            .filter_map(|(entity, node, transform, _)| {
+                let viewport_position = ui_stacks
+                    .camera_of(entity)
+                    .and_then(|camera| camera_query.get(camera).ok())
+                    .and_then(Camera::logical_viewport_rect)
+                    .map(|rect| rect.min)
+                    .unwrap_or_default();
+                (node.size() != Vec2::ZERO)
+                    .then(|| node.logical_rect(transform).center() * ui_scale.0 + viewport_position)
+            });
+        if let Some(target) = snap_target(
+            cursor.position.unwrap_or_default(),
+            centers,
+            cursor.snap_distance,
+        ) {
+            cursor.position = Some(target);
+        }
+    }
+    state.moving = moving;
+
+    let button_pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad_buttons.pressed(GamepadButton::new(gamepad, cursor.button)));
+    cursor.set_pressed(button_pressed);
+
+    // The cursor node is placed in the viewport of the default UI camera
+    let viewport_position = default_ui_camera
+        .get()
+        .and_then(|camera| camera_query.get(camera).ok())
+        .and_then(Camera::logical_viewport_rect)
+        .map(|rect| rect.min)
+        .unwrap_or_default();
+    let top_left =
+        (cursor.position.unwrap_or_default() - viewport_position) / ui_scale.0 - cursor.hotspot;
+    let style = Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(top_left.x),
+        top: Val::Px(top_left.y),
+        width: Val::Px(cursor.size.x),
+        height: Val::Px(cursor.size.y),
+        ..default()
+    };
+    match state.node.map(|node| cursor_nodes.get_mut(node)) {
+        Some(Ok(mut node_style)) => {
+            if *node_style != style {
+                *node_style = style;
+            }
+        }
+        _ => {
+            state.node = Some(
+                commands
+                    .spawn((
+                        ImageBundle {
+                            style,
+                            image: cursor.image.clone(),
+                            focus_policy: FocusPolicy::Pass,
+                            z_index: ZIndex::CURSOR,
+                            ..default()
+                        },
+                        GamepadCursorNode,
+                    ))
+                    .id(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+
+    use super::{snap_target, GamepadCursor};
+
+    #[test]
+    fn cursor_accelerates_and_snaps_to_nearby_targets() {
+        let bounds = Vec2::new(800., 600.);
+        let mut cursor = GamepadCursor {
+            enabled: true,
+            position: Some(Vec2::new(100., 100.)),
+            speed: 100.,
+            max_speed: 300.,
+            acceleration: 1000.,
+            ..Default::default()
+        };
+
+        assert!(cursor.advance(Vec2::X, 0.1, bounds));
+        assert_eq!(cursor.active_position(), Some(Vec2::new(110., 100.)));
+        // Accelerated from 100 to 200 pixels per second
+        assert!(cursor.advance(Vec2::X, 0.1, bounds));
+        assert_eq!(cursor.active_position(), Some(Vec2::new(130., 100.)));
+        // Capped at the maximum speed, and kept inside the bounds
+        assert!(cursor.advance(Vec2::NEG_Y, 1., bounds));
+        assert_eq!(cursor.active_position(), Some(Vec2::new(130., 0.)));
+
+        // Releasing the stick resets the speed
+        assert!(!cursor.advance(Vec2::ZERO, 0.1, bounds));
+        assert!(cursor.advance(Vec2::Y, 0.1, bounds));
+        assert_eq!(cursor.active_position(), Some(Vec2::new(130., 10.)));
+
+        let targets = [Vec2::new(150., 10.), Vec2::new(140., 20.)];
+        assert_eq!(
+            snap_target(Vec2::new(130., 10.), targets.into_iter(), 32.),
+            Some(Vec2::new(140., 20.))
+        );
+        assert_eq!(
+            snap_target(Vec2::new(130., 100.), targets.into_iter(), 32.),
+            None
+        );
+
+        cursor.enabled = false;
+        assert_eq!(cursor.active_position(), None);
+    }
+
+    #[test]
+    fn cursor_button_reports_presses_and_releases() {
+        let mut cursor = GamepadCursor::default();
+        cursor.set_pressed(true);
+        assert!(cursor.pressed() && cursor.just_pressed() && !cursor.just_released());
+        cursor.set_pressed(true);
+        assert!(cursor.pressed() && !cursor.just_pressed());
+        cursor.set_pressed(false);
+        assert!(!cursor.pressed() && cursor.just_released());
+        cursor.set_pressed(false);
+        assert!(!cursor.just_released());
+    }
+}
@@ -1,8 +1,11 @@
-use bevy_math::Vec2;
+use crate::{BorderRadius, Node};
+use bevy_math::{Rect, Vec2};
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::Reflect;
+#[cfg(feature = "render")]
+use bevy_render::camera::Camera;
 use std::ops::Neg;
-use std::ops::{Div, DivAssign, Mul, MulAssign};
+use std::ops::{Add, Div, DivAssign, Mul, MulAssign, Sub};
 use thiserror::Error;
 
 #[cfg(feature = "serialize")]
@@ -46,46 +49,34 @@ pub enum Val {
     VMin(f32),
     /// Set this value in percent of the viewport's larger dimension.
     VMax(f32),
+    /// Set this value as a multiple of the [`RootFontSize`](crate::RootFontSize).
+    ///
+    /// Like [`Val::Px`] values, `Rem` values are scaled by the [`UiScale`](crate::UiScale).
+    Rem(f32),
+    /// Set this value as a sum of values in different units, like the CSS `calc()` function.
+    ///
+    /// Created by adding or subtracting [`Val`]s, see [`ValExpr`].
+    ///
+    /// Percentages mixed with other units are resolved against the size of the parent node, by computing the
+    /// layout again when the parent's size changes. Mixed percentages aren't supported for `flex_basis`, where
+    /// only the percentage is used.
+    Calc(ValExpr),
 }
 
 impl PartialEq for Val {
     fn eq(&self, other: &Self) -> bool {
-        let same_unit = matches!(
-            (self, other),
-            (Self::Auto, Self::Auto)
-                | (Self::Px(_), Self::Px(_))
-                | (Self::Percent(_), Self::Percent(_))
-                | (Self::Vw(_), Self::Vw(_))
-                | (Self::Vh(_), Self::Vh(_))
-                | (Self::VMin(_), Self::VMin(_))
-                | (Self::VMax(_), Self::VMax(_))
-        );
-
-        let left = match self {
-            Self::Auto => None,
-            Self::Px(v)
-            | Self::Percent(v)
-            | Self::Vw(v)
-            | Self::Vh(v)
-            | Self::VMin(v)
-            | Self::VMax(v) => Some(v),
-        };
-
-        let right = match other {
-            Self::Auto => None,
-            Self::Px(v)
-            | Self::Percent(v)
-            | Self::Vw(v)
-            | Self::Vh(v)
-            | Self::VMin(v)
-            | Self::VMax(v) => Some(v),
-        };
-
-        match (same_unit, left, right) {
-            (true, a, b) => a == b,
-            // All zero-value variants are considered equal.
-            (false, Some(&a), Some(&b)) => a == 0. && b == 0.,
-            _ => false,
+        match (self, other) {
+            (Self::Auto, Self::Auto) => true,
+            (Self::Auto, _) | (_, Self::Auto) => false,
+            (Self::VMin(a), Self::VMin(b))
+            | (Self::VMax(a), Self::VMax(b))
+            | (Self::Rem(a), Self::Rem(b)) => a == b,
+            // Values with the same unit are equal if their values are equal, and all zero-value variants are
+            // considered equal.
+            _ => match (ValExpr::try_from_val(*self), ValExpr::try_from_val(*other)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
         }
     }
 }
@@ -113,6 +104,8 @@ impl Mul<f32> for Val {
             Val::Vh(value) => Val::Vh(value * rhs),
             Val::VMin(value) => Val::VMin(value * rhs),
             Val::VMax(value) => Val::VMax(value * rhs),
+            Val::Rem(value) => Val::Rem(value * rhs),
+            Val::Calc(expr) => Val::Calc(expr * rhs),
         }
     }
 }
@@ -126,7 +119,9 @@ impl MulAssign<f32> for Val {
             | Val::Vw(value)
             | Val::Vh(value)
             | Val::VMin(value)
-            | Val::VMax(value) => *value *= rhs,
+            | Val::VMax(value)
+            | Val::Rem(value) => *value *= rhs,
+            Val::Calc(expr) => *expr = *expr * rhs,
         }
     }
 }
@@ -143,6 +138,8 @@ impl Div<f32> for Val {
             Val::Vh(value) => Val::Vh(value / rhs),
             Val::VMin(value) => Val::VMin(value / rhs),
             Val::VMax(value) => Val::VMax(value / rhs),
+            Val::Rem(value) => Val::Rem(value / rhs),
+            Val::Calc(expr) => Val::Calc(expr / rhs),
         }
     }
 }
@@ -156,7 +153,9 @@ impl DivAssign<f32> for Val {
             | Val::Vw(value)
             | Val::Vh(value)
             | Val::VMin(value)
-            | Val::VMax(value) => *value /= rhs,
+            | Val::VMax(value)
+            | Val::Rem(value) => *value /= rhs,
+            Val::Calc(expr) => *expr = *expr / rhs,
         }
     }
 }
@@ -172,6 +171,8 @@ impl Neg for Val {
             Val::Vh(value) => Val::Vh(-value),
             Val::VMin(value) => Val::VMin(-value),
             Val::VMax(value) => Val::VMax(-value),
+            Val::Rem(value) => Val::Rem(-value),
+            Val::Calc(expr) => Val::Calc(-expr),
             _ => self,
         }
     }
@@ -189,8 +190,15 @@ impl Val {
     /// Resolves a [`Val`] to its value in logical pixels and returns this as an [`f32`].
     /// Returns a [`ValArithmeticError::NonEvaluateable`] if the [`Val`] is impossible to resolve into a concrete value.
     ///
+    /// [`Val::Rem`] values are resolved against `root_font_size`.
+    ///
     /// **Note:** If a [`Val::Px`] is resolved, its inner value is returned unchanged.
-    pub fn resolve(self, parent_size: f32, viewport_size: Vec2) -> Result<f32, ValArithmeticError> {
+    pub fn resolve(
+        self,
+        parent_size: f32,
+        viewport_size: Vec2,
+        root_font_size: f32,
+    ) -> Result<f32, ValArithmeticError> {
         match self {
             Val::Percent(value) => Ok(parent_size * value / 100.0),
             Val::Px(value) => Ok(value),
@@ -198,9 +206,203 @@ impl Val {
             Val::Vh(value) => Ok(viewport_size.y * value / 100.0),
             Val::VMin(value) => Ok(viewport_size.min_element() * value / 100.0),
             Val::VMax(value) => Ok(viewport_size.max_element() * value / 100.0),
+            Val::Rem(value) => Ok(root_font_size * value),
+            Val::Calc(expr) => Ok(expr.resolve(parent_size, viewport_size)),
             Val::Auto => Err(ValArithmeticError::NonEvaluateable),
         }
     }
+
+    /// Returns the value with its pixel and rem lengths multiplied by `scale`, leaving percentages and viewport
+    /// units unchanged, as a [`UiRootScale`](crate::UiRootScale) scales a node.
+    #[cfg_attr(not(feature = "render"), allow(dead_code))]
+    pub(crate) fn scale_lengths(self, scale: f32) -> Val {
+        match self {
+            Val::Px(value) => Val::Px(value * scale),
+            Val::Rem(value) => Val::Rem(value * scale),
+            Val::Calc(expr) => Val::Calc(ValExpr {
+                px: expr.px * scale,
+                ..expr
+            }),
+            value => value,
+        }
+    }
+}
+
+/// A sum of pixels, percentages and viewport width and height units, like the CSS `calc()` function.
+///
+/// Adding or subtracting [`Val`]s with different units creates a [`ValExpr`], which can be converted into a
+/// [`Val::Calc`] to use it in a [`Style`](crate::Style):
+///
+/// ```
+/// # use bevy_ui::{Style, Val};
+/// // A sidebar filling the width of its parent, except for a 40 pixel gap
+/// let style = Style {
+///     width: (Val::Percent(100.) - Val::Px(40.)).into(),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// [`Val::Auto`] has no numeric value, so it is treated as zero in expressions.
+///
+/// To keep [`Val`] small, [`Val::VMin`], [`Val::VMax`] and [`Val::Rem`] values can't be used in expressions, and
+/// converting them into a [`ValExpr`] panics unless they are zero. See [`ValExpr::try_from_val`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ValExpr {
+    /// The value in logical pixels, see [`Val::Px`].
+    pub px: f32,
+    /// The value in percent of the parent node's length, see [`Val::Percent`].
+    pub percent: f32,
+    /// The value in percent of the viewport width, see [`Val::Vw`].
+    pub vw: f32,
+    /// The value in percent of the viewport height, see [`Val::Vh`].
+    pub vh: f32,
+}
+
+impl ValExpr {
+    pub const ZERO: Self = Self {
+        px: 0.,
+        percent: 0.,
+        vw: 0.,
+        vh: 0.,
+    };
+
+    /// Converts `value` into an expression, or returns `None` if it is a non-zero [`Val::VMin`], [`Val::VMax`] or
+    /// [`Val::Rem`] value, which can't be used in expressions.
+    pub fn try_from_val(value: Val) -> Option<Self> {
+        let zero = ValExpr::ZERO;
+        match value {
+            Val::Auto => Some(zero),
+            Val::Px(px) => Some(ValExpr { px, ..zero }),
+            Val::Percent(percent) => Some(ValExpr { percent, ..zero }),
+            Val::Vw(vw) => Some(ValExpr { vw, ..zero }),
+            Val::Vh(vh) => Some(ValExpr { vh, ..zero }),
+            Val::VMin(value) | Val::VMax(value) | Val::Rem(value) => (value == 0.).then_some(zero),
+            Val::Calc(expr) => Some(expr),
+        }
+    }
+
+    /// Converts `value` into an expression, replacing [`Val::VMin`] and [`Val::VMax`] values with the viewport
+    /// unit of the matching axis of `viewport_size`, and [`Val::Rem`] values with pixels.
+    #[cfg_attr(not(feature = "render"), allow(dead_code))]
+    pub(crate) fn from_val_in_viewport(
+        value: Val,
+        viewport_size: Vec2,
+        root_font_size: f32,
+    ) -> Self {
+        let (min, max): (fn(f32) -> Val, fn(f32) -> Val) = if viewport_size.x <= viewport_size.y {
+            (Val::Vw, Val::Vh)
+        } else {
+            (Val::Vh, Val::Vw)
+        };
+        ValExpr::from(match value {
+            Val::VMin(value) => min(value),
+            Val::VMax(value) => max(value),
+            Val::Rem(rem) => Val::Px(root_font_size * rem),
+            value => value,
+        })
+    }
+
+    /// Resolves the expression to its value in logical pixels, see [`Val::resolve`].
+    pub fn resolve(self, parent_size: f32, viewport_size: Vec2) -> f32 {
+        self.px + parent_size * self.percent / 100. + self.resolve_viewport_units(viewport_size)
+    }
+
+    /// Resolves the viewport units of the expression to their value in the units of `viewport_size`.
+    pub(crate) fn resolve_viewport_units(self, viewport_size: Vec2) -> f32 {
+        (viewport_size.x * self.vw + viewport_size.y * self.vh) / 100.
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self {
+            px: f(self.px, other.px),
+            percent: f(self.percent, other.percent),
+            vw: f(self.vw, other.vw),
+            vh: f(self.vh, other.vh),
+        }
+    }
+
+    fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        self.zip_with(Self::ZERO, |value, _| f(value))
+    }
+}
+
+impl From<Val> for ValExpr {
+    /// # Panics
+    ///
+    /// Panics if `value` is a non-zero [`Val::VMin`], [`Val::VMax`] or [`Val::Rem`] value.
+    fn from(value: Val) -> Self {
+        ValExpr::try_from_val(value).unwrap_or_else(|| {
+            panic!("{value:?} can't be used in a `ValExpr`, only `Px`, `Percent`, `Vw` and `Vh` values can")
+        })
+    }
+}
+
+impl From<ValExpr> for Val {
+    fn from(expr: ValExpr) -> Self {
+        Val::Calc(expr)
+    }
+}
+
+impl<T: Into<ValExpr>> Add<T> for ValExpr {
+    type Output = ValExpr;
+
+    fn add(self, rhs: T) -> Self::Output {
+        self.zip_with(rhs.into(), |a, b| a + b)
+    }
+}
+
+impl<T: Into<ValExpr>> Sub<T> for ValExpr {
+    type Output = ValExpr;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self.zip_with(rhs.into(), |a, b| a - b)
+    }
+}
+
+impl Mul<f32> for ValExpr {
+    type Output = ValExpr;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        self.map(|value| value * rhs)
+    }
+}
+
+impl Div<f32> for ValExpr {
+    type Output = ValExpr;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        self.map(|value| value / rhs)
+    }
+}
+
+impl Neg for ValExpr {
+    type Output = ValExpr;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
+}
+
+impl<T: Into<ValExpr>> Add<T> for Val {
+    type Output = ValExpr;
+
+    fn add(self, rhs: T) -> Self::Output {
+        ValExpr::from(self) + rhs
+    }
+}
+
+impl<T: Into<ValExpr>> Sub<T> for Val {
+    type Output = ValExpr;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        ValExpr::from(self) - rhs
+    }
 }
 
 /// A type which is commonly used to define margins, paddings and borders.
@@ -620,6 +822,196 @@ impl Default for UiRect {
     }
 }
 
+/// Returns the size of the viewport of `camera` in logical UI pixels, which viewport units like [`Val::Vw`] are
+/// resolved against, or zero if there is no camera.
+///
+/// Each UI root is laid out in the viewport of its own camera, so this must be the camera of the node being
+/// resolved, not the primary window.
+#[cfg(feature = "render")]
+pub(crate) fn ui_logical_viewport_size(camera: Option<&Camera>, ui_scale: f32) -> Vec2 {
+    camera
+        .and_then(Camera::logical_viewport_size)
+        .unwrap_or(Vec2::ZERO)
+        // The logical viewport size returned by `Camera` only takes into account the window scale factor and not
+        // `UiScale`, so we have to divide by `UiScale` to get the size of the UI viewport.
+        / ui_scale
+}
+
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
+pub(crate) fn resolve_border_thickness(
+    value: Val,
+    parent_width: f32,
+    viewport_size: Vec2,
+    root_font_size: f32,
+) -> f32 {
+    match value {
+        Val::Auto => 0.,
+        Val::Px(px) => px.max(0.),
+        Val::Percent(percent) => (parent_width * percent / 100.).max(0.),
+        Val::Vw(percent) => (viewport_size.x * percent / 100.).max(0.),
+        Val::Vh(percent) => (viewport_size.y * percent / 100.).max(0.),
+        Val::VMin(percent) => (viewport_size.min_element() * percent / 100.).max(0.),
+        Val::VMax(percent) => (viewport_size.max_element() * percent / 100.).max(0.),
+        Val::Rem(rem) => (root_font_size * rem).max(0.),
+        Val::Calc(expr) => expr.resolve(parent_width, viewport_size).max(0.),
+    }
+}
+
+/// Resolves the radii of each corner, ordered top left, top right, bottom right, bottom left.
+///
+/// The x and y components of each radius are its horizontal and vertical radii, which are equal for circular
+/// corners.
+pub(crate) fn resolve_border_radius(
+    values: &BorderRadius,
+    node_size: Vec2,
+    viewport_size: Vec2,
+    ui_scale: f32,
+    root_font_size: f32,
+) -> [Vec2; 4] {
+    // `percent_basis` is the length that percentages are resolved against
+    let resolve = |value: Val, percent_basis: f32| {
+        match value {
+            Val::Auto => 0.,
+            Val::Px(px) => ui_scale * px,
+            Val::Percent(percent) => percent_basis * percent / 100.,
+            Val::Vw(percent) => viewport_size.x * percent / 100.,
+            Val::Vh(percent) => viewport_size.y * percent / 100.,
+            Val::VMin(percent) => viewport_size.min_element() * percent / 100.,
+            Val::VMax(percent) => viewport_size.max_element() * percent / 100.,
+            Val::Rem(rem) => ui_scale * root_font_size * rem,
+            Val::Calc(expr) => {
+                ui_scale * expr.px
+                    + percent_basis * expr.percent / 100.
+                    + expr.resolve_viewport_units(viewport_size)
+            }
+        }
+        .max(0.)
+    };
+
+    let corners = values.corners();
+    let max_radius = 0.5 * node_size * ui_scale;
+    match values.vertical {
+        None => corners.map(|value| {
+            // As in CSS, a single radius is both the horizontal and vertical radius of the corner, so
+            // percentages resolve against the width and height separately and make elliptical corners.
+            let radius = Vec2::new(resolve(value, node_size.x), resolve(value, node_size.y));
+            if radius.x == radius.y {
+                // Circular corners stay circular
+                Vec2::splat(radius.x.min(max_radius.min_element()))
+            } else {
+                radius.min(max_radius)
+            }
+        }),
+        Some(vertical) => std::array::from_fn(|i| {
+            Vec2::new(
+                resolve(corners[i], node_size.x),
+                resolve(vertical[i], node_size.y),
+            )
+            .min(max_radius)
+        }),
+    }
+}
+
+/// Resolves the radii of the corners of `node`, in logical pixels, for [`rounded_rect_contains`].
+///
+/// `viewport_size` is the size of the node's viewport in logical UI pixels.
+pub(crate) fn resolve_node_radii(
+    border_radius: &BorderRadius,
+    node: &Node,
+    viewport_size: Vec2,
+    ui_scale: f32,
+    root_font_size: f32,
+) -> [Vec2; 4] {
+    resolve_border_radius(
+        border_radius,
+        node.size(),
+        viewport_size,
+        ui_scale * node.root_scale(),
+        root_font_size,
+    )
+}
+
+/// Returns `true` if `point` is inside `rect` with rounded corners.
+///
+/// `radii` are the radii of the corners ordered top left, top right, bottom right, bottom left, with the horizontal
+/// and vertical radius of each corner as its x and y components. The y axis points down, as in UI coordinates.
+pub fn rounded_rect_contains(rect: Rect, radii: [Vec2; 4], point: Vec2) -> bool {
+    if !rect.contains(point) {
+        return false;
+    }
+    let half_size = rect.half_size();
+    let offset = point - rect.center();
+    let radius = match (offset.x < 0., offset.y < 0.) {
+        (true, true) => radii[0],
+        (false, true) => radii[1],
+        (false, false) => radii[2],
+        (true, false) => radii[3],
+    };
+    let radius = if radius.x == radius.y {
+        // Circular corners stay circular
+        Vec2::splat(radius.x.min(half_size.min_element()))
+    } else {
+        radius.min(half_size)
+    };
+    if radius.cmple(Vec2::ZERO).any() {
+        return true;
+    }
+    // The distance of the point past the center of the corner's ellipse on each axis
+    let distance = offset.abs() - (half_size - radius);
+    distance.cmple(Vec2::ZERO).any() || (distance / radius).length_squared() <= 1.
+}
+
+/// The thickness of each edge of a rect in logical pixels, like the resolved border and padding of a [`Node`](crate::Node).
+///
+/// This is [`bevy_sprite::BorderRect`] if the `render` feature is enabled.
+#[cfg(feature = "render")]
+pub use bevy_sprite::BorderRect;
+
+/// The thickness of each edge of a rect in logical pixels, like the resolved border and padding of a [`Node`](crate::Node).
+///
+/// This is `bevy_sprite::BorderRect` if the `render` feature is enabled.
+#[cfg(not(feature = "render"))]
+#[derive(Default, Copy, Clone, PartialEq, Debug, Reflect)]
+pub struct BorderRect {
+    /// Pixel padding to the left
+    pub left: f32,
+    /// Pixel padding to the right
+    pub right: f32,
+    /// Pixel padding to the top
+    pub top: f32,
+    /// Pixel padding to the bottom
+    pub bottom: f32,
+}
+
+#[cfg(not(feature = "render"))]
+impl BorderRect {
+    /// Creates a new border as a square, with identical pixel padding values on every direction
+    #[must_use]
+    #[inline]
+    pub const fn square(value: f32) -> Self {
+        Self {
+            left: value,
+            right: value,
+            top: value,
+            bottom: value,
+        }
+    }
+
+    /// Creates a new border as a rectangle, with:
+    /// - `horizontal` for left and right pixel padding
+    /// - `vertical` for top and bottom pixel padding
+    #[must_use]
+    #[inline]
+    pub const fn rectangle(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::geometry::*;
@@ -629,7 +1021,7 @@ mod tests {
     fn val_evaluate() {
         let size = 250.;
         let viewport_size = vec2(1000., 500.);
-        let result = Val::Percent(80.).resolve(size, viewport_size).unwrap();
+        let result = Val::Percent(80.).resolve(size, viewport_size, 16.).unwrap();
 
         assert_eq!(result, size * 0.8);
     }
@@ -638,7 +1030,7 @@ mod tests {
     fn val_resolve_px() {
         let size = 250.;
         let viewport_size = vec2(1000., 500.);
-        let result = Val::Px(10.).resolve(size, viewport_size).unwrap();
+        let result = Val::Px(10.).resolve(size, viewport_size, 16.).unwrap();
 
         assert_eq!(result, 10.);
     }
@@ -651,33 +1043,93 @@ mod tests {
         for value in (-10..10).map(|value| value as f32) {
             // for a square viewport there should be no difference between `Vw` and `Vh` and between `Vmin` and `Vmax`.
             assert_eq!(
-                Val::Vw(value).resolve(size, viewport_size),
-                Val::Vh(value).resolve(size, viewport_size)
+                Val::Vw(value).resolve(size, viewport_size, 16.),
+                Val::Vh(value).resolve(size, viewport_size, 16.)
             );
             assert_eq!(
-                Val::VMin(value).resolve(size, viewport_size),
-                Val::VMax(value).resolve(size, viewport_size)
+                Val::VMin(value).resolve(size, viewport_size, 16.),
+                Val::VMax(value).resolve(size, viewport_size, 16.)
             );
             assert_eq!(
-                Val::VMin(value).resolve(size, viewport_size),
-                Val::Vw(value).resolve(size, viewport_size)
+                Val::VMin(value).resolve(size, viewport_size, 16.),
+                Val::Vw(value).resolve(size, viewport_size, 16.)
             );
         }
 
         let viewport_size = vec2(1000., 500.);
-        assert_eq!(Val::Vw(100.).resolve(size, viewport_size).unwrap(), 1000.);
-        assert_eq!(Val::Vh(100.).resolve(size, viewport_size).unwrap(), 500.);
-        assert_eq!(Val::Vw(60.).resolve(size, viewport_size).unwrap(), 600.);
-        assert_eq!(Val::Vh(40.).resolve(size, viewport_size).unwrap(), 200.);
-        assert_eq!(Val::VMin(50.).resolve(size, viewport_size).unwrap(), 250.);
-        assert_eq!(Val::VMax(75.).resolve(size, viewport_size).unwrap(), 750.);
+        assert_eq!(
+            Val::Vw(100.).resolve(size, viewport_size, 16.).unwrap(),
+            1000.
+        );
+        assert_eq!(
+            Val::Vh(100.).resolve(size, viewport_size, 16.).unwrap(),
+            500.
+        );
+        assert_eq!(
+            Val::Vw(60.).resolve(size, viewport_size, 16.).unwrap(),
+            600.
+        );
+        assert_eq!(
+            Val::Vh(40.).resolve(size, viewport_size, 16.).unwrap(),
+            200.
+        );
+        assert_eq!(
+            Val::VMin(50.).resolve(size, viewport_size, 16.).unwrap(),
+            250.
+        );
+        assert_eq!(
+            Val::VMax(75.).resolve(size, viewport_size, 16.).unwrap(),
+            750.
+        );
+    }
+
+    #[test]
+    fn val_resolve_rem() {
+        let size = 250.;
+        let viewport_size = vec2(1000., 500.);
+
+        assert_eq!(Val::Rem(2.).resolve(size, viewport_size, 16.).unwrap(), 32.);
+        assert_eq!(
+            Val::Rem(0.5).resolve(size, viewport_size, 20.).unwrap(),
+            10.
+        );
+    }
+
+    #[test]
+    fn val_calc() {
+        let size = 250.;
+        let viewport_size = vec2(1000., 500.);
+
+        let sidebar = Val::Percent(100.) - Val::Px(40.);
+        assert_eq!(
+            Val::from(sidebar).resolve(size, viewport_size, 16.),
+            Ok(210.)
+        );
+
+        let expr = (Val::Vw(10.) + Val::Vh(2.) - Val::Calc(sidebar)) * 0.5;
+        assert_eq!(expr.resolve(size, viewport_size), 0.5 * (100. + 10. - 210.));
+
+        // Expressions equal values in a single unit with the same value
+        assert_eq!(Val::Calc(Val::Px(10.) + Val::Px(5.)), Val::Px(15.));
+        assert_ne!(Val::Calc(Val::Px(10.) + Val::Percent(5.)), Val::Px(10.));
+        assert_eq!(Val::Calc(ValExpr::ZERO), Val::Percent(0.));
+        assert_ne!(Val::Calc(ValExpr::ZERO), Val::Auto);
+        assert_eq!(Val::Calc(ValExpr::ZERO), Val::Rem(0.));
+        assert_ne!(Val::Rem(1.), Val::VMin(1.));
+
+        // Units that can't be used in expressions are only accepted when they are zero
+        assert_eq!(ValExpr::try_from_val(Val::Rem(0.)), Some(ValExpr::ZERO));
+        assert_eq!(ValExpr::try_from_val(Val::VMax(5.)), None);
+
+        // Expressions are stored inline without making `Val` much larger
+        assert_eq!(std::mem::size_of::<Val>(), 20);
     }
 
     #[test]
     fn val_auto_is_non_resolveable() {
         let size = 250.;
         let viewport_size = vec2(1000., 500.);
-        let resolve_auto = Val::Auto.resolve(size, viewport_size);
+        let resolve_auto = Val::Auto.resolve(size, viewport_size, 16.);
 
         assert_eq!(resolve_auto, Err(ValArithmeticError::NonEvaluateable));
     }
@@ -0,0 +1,329 @@
+//! Packs small [`UiImage`] textures into shared atlases, so that UI nodes displaying different images can be
+//! drawn in the same batch.
+
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{URect, UVec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::{Image, ImageSampler},
+};
+use bevy_sprite::{DynamicTextureAtlasBuilder, ImageScaleMode, TextureAtlas, TextureAtlasLayout};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::UiImage;
+
+/// The format of the UI image atlases. Only images with this format are packed.
+const ATLAS_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+/// The gap left between the images packed into a UI image atlas.
+const ATLAS_PADDING: u32 = 2;
+
+/// Controls the packing of small [`UiImage`] textures into shared atlases.
+///
+/// The batches of UI nodes are broken each time the texture changes, so UIs displaying many different icons can
+/// require a draw call per node. When enabled, the textures of [`UiImage`]s that are small enough are copied into
+/// atlases, and the nodes displaying them are drawn using the atlases instead.
+///
+/// Only images in the [`TextureFormat::Rgba8UnormSrgb`] format with the default sampler and data in the main world
+/// are packed. Nodes with a [`TextureAtlas`] or an [`ImageScaleMode`] always use their own texture.
+///
+/// Because neighboring images are separated by transparent padding, the edges of packed images may be slightly
+/// more transparent when sampled with linear filtering.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct UiImageAtlasSettings {
+    /// Whether images are packed. Disabled by default.
+    ///
+    /// Disabling packing releases all the atlases.
+    pub enabled: bool,
+    /// Images larger than this size on either axis are never packed.
+    pub max_image_size: UVec2,
+    /// The size of each atlas texture.
+    pub atlas_size: UVec2,
+}
+
+impl Default for UiImageAtlasSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_image_size: UVec2::splat(128),
+            atlas_size: UVec2::splat(1024),
+        }
+    }
+}
+
+/// The location of an image that has been packed into a UI image atlas.
+#[derive(Debug, Clone)]
+pub struct PackedUiImage {
+    /// The atlas texture containing the image.
+    pub atlas_texture: Handle<Image>,
+    /// The region of the atlas texture covered by the image, in pixels.
+    pub rect: URect,
+    /// The size of the atlas texture, in pixels.
+    pub atlas_size: UVec2,
+}
+
+struct UiImageAtlas {
+    builder: DynamicTextureAtlasBuilder,
+    layout: TextureAtlasLayout,
+    texture: Handle<Image>,
+}
+
+/// The atlases that small [`UiImage`] textures have been packed into, updated by [`pack_ui_images`].
+#[derive(Resource, Default)]
+pub struct UiImageAtlases {
+    atlases: Vec<UiImageAtlas>,
+    packed: HashMap<AssetId<Image>, PackedUiImage>,
+    /// Images that can't be packed, so aren't checked again until they are modified.
+    rejected: HashSet<AssetId<Image>>,
+}
+
+impl UiImageAtlases {
+    /// Returns where the image with `id` was packed, or `None` if it hasn't been packed.
+    pub fn get(&self, id: AssetId<Image>) -> Option<&PackedUiImage> {
+        self.packed.get(&id)
+    }
+
+    /// The number of atlas textures.
+    pub fn atlas_count(&self) -> usize {
+        self.atlases.len()
+    }
+
+    /// The number of images packed into the atlases.
+    pub fn packed_count(&self) -> usize {
+        self.packed.len()
+    }
+
+    fn clear(&mut self) {
+        self.atlases.clear();
+        self.packed.clear();
+        self.rejected.clear();
+    }
+
+    /// Copies `image` into the first atlas with room for it, adding a new atlas if none have room.
+    fn pack(
+        &mut self,
+        image: &Image,
+        images: &mut Assets<Image>,
+        settings: &UiImageAtlasSettings,
+    ) -> Option<PackedUiImage> {
+        for atlas in &mut self.atlases {
+            let atlas_texture = images.get_mut(&atlas.texture)?;
+            if let Some(index) = atlas
+                .builder
+                .add_texture(&mut atlas.layout, image, atlas_texture)
+            {
+                return Some(PackedUiImage {
+                    atlas_texture: atlas.texture.clone(),
+                    rect: atlas.layout.textures[index],
+                    atlas_size: atlas.layout.size,
+                });
+            }
+        }
+
+        let mut atlas = UiImageAtlas {
+            builder: DynamicTextureAtlasBuilder::new(settings.atlas_size, ATLAS_PADDING),
+            layout: TextureAtlasLayout::new_empty(settings.atlas_size),
+            texture: images.add(Image::new_fill(
+                Extent3d {
+                    width: settings.atlas_size.x,
+                    height: settings.atlas_size.y,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[0; 4],
+                ATLAS_FORMAT,
+                RenderAssetUsages::default(),
+            )),
+        };
+        let index =
+            atlas
+                .builder
+                .add_texture(&mut atlas.layout, image, images.get_mut(&atlas.texture)?)?;
+        let packed = PackedUiImage {
+            atlas_texture: atlas.texture.clone(),
+            rect: atlas.layout.textures[index],
+            atlas_size: atlas.layout.size,
+        };
+        self.atlases.push(atlas);
+        Some(packed)
+    }
+}
+
+/// Returns `true` if `image` can be copied into a UI image atlas.
+fn is_packable(image: &Image, settings: &UiImageAtlasSettings) -> bool {
+    let size = image.size();
+    let descriptor = &image.texture_descriptor;
+    descriptor.format == ATLAS_FORMAT
+        && descriptor.dimension == TextureDimension::D2
+        && descriptor.size.depth_or_array_layers == 1
+        && descriptor.mip_level_count == 1
+        && matches!(image.sampler, ImageSampler::Default)
+        && !image.data.is_empty()
+        && size.cmpgt(UVec2::ZERO).all()
+        && size.cmple(settings.max_image_size).all()
+        && (size + ATLAS_PADDING).cmple(settings.atlas_size).all()
+}
+
+/// Packs the textures of [`UiImage`]s into the [`UiImageAtlases`], if enabled by the [`UiImageAtlasSettings`].
+///
+/// Images that are modified after being packed are unpacked and drawn using their own texture from then on, since
+/// space in the atlases is not reclaimed until packing is disabled.
+pub fn pack_ui_images(
+    settings: Res<UiImageAtlasSettings>,
+    mut atlases: ResMut<UiImageAtlases>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    image_query: Query<&UiImage, (Without<TextureAtlas>, Without<ImageScaleMode>)>,
+) {
+    if !settings.enabled {
+        if !atlases.atlases.is_empty() || !atlases.rejected.is_empty() {
+            atlases.clear();
+        }
+        image_events.clear();
+        return;
+    }
+
+    if settings.is_changed() && !settings.is_added() {
+        // Packed images may no longer fit the new settings
+        atlases.clear();
+    }
+
+    for event in image_events.read() {
+        match event {
+            AssetEvent::Modified { id } => {
+                // Space in the atlases isn't reclaimed, so images that change after being packed are left unpacked.
+                if atlases.packed.remove(id).is_some() {
+                    atlases.rejected.insert(*id);
+                } else {
+                    atlases.rejected.remove(id);
+                }
+            }
+            AssetEvent::Removed { id } => {
+                atlases.packed.remove(id);
+                atlases.rejected.remove(id);
+            }
+            _ => {}
+        }
+    }
+
+    for ui_image in &image_query {
+        let id = ui_image.texture.id();
+        if atlases.packed.contains_key(&id) || atlases.rejected.contains(&id) {
+            continue;
+        }
+
+        let Some(image) = images.get(id) else {
+            // Not loaded yet
+            continue;
+        };
+
+        if !is_packable(image, &settings) {
+            atlases.rejected.insert(id);
+            continue;
+        }
+
+        // The image is cloned to release the borrow of `images`, the atlas textures are also image assets.
+        let image = image.clone();
+        match atlases.pack(&image, &mut images, &settings) {
+            Some(packed) => {
+                atlases.packed.insert(id, packed);
+            }
+            None => {
+                atlases.rejected.insert(id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AssetEvent, Assets};
+    use bevy_ecs::{
+        event::Events,
+        schedule::Schedule,
+        world::{Mut, World},
+    };
+    use bevy_math::{URect, UVec2};
+    use bevy_render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::{Image, TextureFormatPixelInfo},
+    };
+
+    use super::{pack_ui_images, UiImageAtlasSettings, UiImageAtlases};
+    use crate::UiImage;
+
+    fn image(size: u32, format: TextureFormat) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255; 4][..format.pixel_size()],
+            format,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn small_images_are_packed_into_a_shared_atlas() {
+        let mut world = World::new();
+        world.insert_resource(UiImageAtlasSettings {
+            enabled: true,
+            max_image_size: UVec2::splat(32),
+            atlas_size: UVec2::splat(64),
+        });
+        world.init_resource::<UiImageAtlases>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        world.init_resource::<Assets<Image>>();
+
+        let [small_a, small_b, large, wrong_format] =
+            world.resource_scope(|_, mut images: Mut<Assets<Image>>| {
+                [
+                    image(16, TextureFormat::Rgba8UnormSrgb),
+                    image(16, TextureFormat::Rgba8UnormSrgb),
+                    image(48, TextureFormat::Rgba8UnormSrgb),
+                    image(16, TextureFormat::R8Unorm),
+                ]
+                .map(|image| images.add(image))
+            });
+        for texture in [&small_a, &small_b, &large, &wrong_format] {
+            world.spawn(UiImage::new(texture.clone()));
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(pack_ui_images);
+        schedule.run(&mut world);
+
+        let atlases = world.resource::<UiImageAtlases>();
+        assert_eq!(atlases.atlas_count(), 1);
+        assert_eq!(atlases.packed_count(), 2);
+
+        let a = atlases.get(small_a.id()).unwrap();
+        let b = atlases.get(small_b.id()).unwrap();
+        assert_eq!(a.atlas_texture, b.atlas_texture);
+        assert_eq!(a.atlas_size, UVec2::splat(64));
+        assert_eq!(a.rect.size(), UVec2::splat(16));
+        assert_eq!(b.rect.size(), UVec2::splat(16));
+        assert!(a.rect.intersect(b.rect).is_empty());
+        assert_ne!(a.rect, URect::default());
+
+        assert!(atlases.get(large.id()).is_none());
+        assert!(atlases.get(wrong_format.id()).is_none());
+
+        // Disabling packing releases the atlases
+        world.resource_mut::<UiImageAtlasSettings>().enabled = false;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<UiImageAtlases>().atlas_count(), 0);
+        assert!(world
+            .resource::<UiImageAtlases>()
+            .get(small_a.id())
+            .is_none());
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, GridAutoFlow,
     GridPlacement, GridTrack, GridTrackRepetition, JustifyContent, JustifyItems, JustifySelf,
     MaxTrackSizingFunction, MinTrackSizingFunction, OverflowAxis, PositionType, RepeatedGridTrack,
-    Style, UiRect, Val,
+    Style, UiRect, Val, ValExpr,
 };
 
 use super::LayoutContext;
@@ -32,6 +32,24 @@ impl Val {
             Val::Vh(value) => {
                 taffy::style::LengthPercentageAuto::Length(context.physical_size.y * value / 100.)
             }
+            Val::Rem(value) => {
+                taffy::style::LengthPercentageAuto::Length(context.root_font_size * value)
+            }
+            Val::Calc(expr) => {
+                let length = context.scale_factor * expr.px
+                    + expr.resolve_viewport_units(context.physical_size);
+                match context.percent_basis {
+                    _ if expr.percent == 0. => taffy::style::LengthPercentageAuto::Length(length),
+                    _ if length == 0. => {
+                        taffy::style::LengthPercentageAuto::Percent(expr.percent / 100.)
+                    }
+                    Some(basis) => taffy::style::LengthPercentageAuto::Length(
+                        length + basis * expr.percent / 100.,
+                    ),
+                    // Until the parent has been laid out, only the percentage can be used
+                    None => taffy::style::LengthPercentageAuto::Percent(expr.percent / 100.),
+                }
+            }
         }
     }
 
@@ -63,11 +81,94 @@ impl UiRect {
     }
 }
 
+/// Returns `margin` with the space taken up by an outline with [`OutlineMode::Push`](crate::OutlineMode::Push)
+/// added to it. Auto margins absorb free space, so they aren't changed.
+fn pushed_margin(margin: Val, outline_push: Option<ValExpr>) -> Val {
+    match outline_push {
+        Some(push) if margin != Val::Auto => Val::Calc(push + margin),
+        _ => margin,
+    }
+}
+
+/// Returns `true` if the style, with the margins pushed out by `outline_push`, has [`Val::Calc`] values that mix
+/// percentages with other units, which are resolved against [`LayoutContext::parent_size`].
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
+pub fn uses_parent_size(style: &Style, outline_push: Option<ValExpr>) -> bool {
+    let is_mixed = |val: &Val| matches!(val, Val::Calc(expr) if expr.percent != 0. && ValExpr { percent: 0., ..*expr } != ValExpr::ZERO);
+    let rect = |rect: &UiRect| [rect.left, rect.right, rect.top, rect.bottom];
+    let margin = rect(&style.margin).map(|margin| pushed_margin(margin, outline_push));
+    [
+        style.left,
+        style.right,
+        style.top,
+        style.bottom,
+        style.width,
+        style.height,
+        style.min_width,
+        style.min_height,
+        style.max_width,
+        style.max_height,
+        style.row_gap,
+        style.column_gap,
+    ]
+    .iter()
+    .chain(&margin)
+    .chain(&rect(&style.padding))
+    .chain(&rect(&style.border))
+    .any(is_mixed)
+}
+
+/// Finds the area named `name` in a grid's `grid_template_areas`, returning the row and column placements that
+/// cover it.
+///
+/// Returns `None` if there is no area with that name, or if its cells don't form a filled rectangle.
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
+pub(crate) fn grid_area_placement(
+    template_areas: &[String],
+    name: &str,
+) -> Option<(GridPlacement, GridPlacement)> {
+    if name == "." {
+        return None;
+    }
+    let mut cells = 0;
+    let mut rows = (usize::MAX, 0);
+    let mut columns = (usize::MAX, 0);
+    for (row, line) in template_areas.iter().enumerate() {
+        for (column, cell) in line.split_whitespace().enumerate() {
+            if cell == name {
+                cells += 1;
+                rows = (rows.0.min(row), rows.1.max(row));
+                columns = (columns.0.min(column), columns.1.max(column));
+            }
+        }
+    }
+    if cells == 0 || cells != (rows.1 - rows.0 + 1) * (columns.1 - columns.0 + 1) {
+        return None;
+    }
+    let lines = |(first, last): (usize, usize)| {
+        Some(GridPlacement::start_end(
+            i16::try_from(first + 1).ok()?,
+            i16::try_from(last + 2).ok()?,
+        ))
+    };
+    Some((lines(rows)?, lines(columns)?))
+}
+
 pub fn from_style(
     context: &LayoutContext,
     style: &Style,
     ignore_padding_and_border: bool,
 ) -> taffy::style::Style {
+    // Percentages of `Val::Calc` values are resolved against the parent's width for horizontal properties, and
+    // margins, padding and borders, like in CSS. The parent's height is used for vertical properties.
+    let horizontal = &LayoutContext {
+        percent_basis: context.parent_size.map(|size| size.x),
+        ..*context
+    };
+    let vertical = &LayoutContext {
+        percent_basis: context.parent_size.map(|size| size.y),
+        ..*context
+    };
     taffy::style::Style {
         display: style.display.into(),
         overflow: taffy::Point {
@@ -85,14 +186,14 @@ pub fn from_style(
         align_content: style.align_content.into(),
         justify_content: style.justify_content.into(),
         inset: taffy::Rect {
-            left: style.left.into_length_percentage_auto(context),
-            right: style.right.into_length_percentage_auto(context),
-            top: style.top.into_length_percentage_auto(context),
-            bottom: style.bottom.into_length_percentage_auto(context),
+            left: style.left.into_length_percentage_auto(horizontal),
+            right: style.right.into_length_percentage_auto(horizontal),
+            top: style.top.into_length_percentage_auto(vertical),
+            bottom: style.bottom.into_length_percentage_auto(vertical),
         },
-        margin: style
-            .margin
-            .map_to_taffy_rect(|m| m.into_length_percentage_auto(context)),
+        margin: style.margin.map_to_taffy_rect(|m| {
+            pushed_margin(m, context.outline_push).into_length_percentage_auto(horizontal)
+        }),
         // Ignore padding for leaf nodes as it isn't implemented in the rendering engine.
         // TODO: Implement rendering of padding for leaf nodes
         padding: if ignore_padding_and_border {
@@ -100,7 +201,7 @@ pub fn from_style(
         } else {
             style
                 .padding
-                .map_to_taffy_rect(|m| m.into_length_percentage(context))
+                .map_to_taffy_rect(|m| m.into_length_percentage(horizontal))
         },
         // Ignore border for leaf nodes as it isn't implemented in the rendering engine.
         // TODO: Implement rendering of border for leaf nodes
@@ -109,27 +210,27 @@ pub fn from_style(
         } else {
             style
                 .border
-                .map_to_taffy_rect(|m| m.into_length_percentage(context))
+                .map_to_taffy_rect(|m| m.into_length_percentage(horizontal))
         },
         flex_grow: style.flex_grow,
         flex_shrink: style.flex_shrink,
         flex_basis: style.flex_basis.into_dimension(context),
         size: taffy::Size {
-            width: style.width.into_dimension(context),
-            height: style.height.into_dimension(context),
+            width: style.width.into_dimension(horizontal),
+            height: style.height.into_dimension(vertical),
         },
         min_size: taffy::Size {
-            width: style.min_width.into_dimension(context),
-            height: style.min_height.into_dimension(context),
+            width: style.min_width.into_dimension(horizontal),
+            height: style.min_height.into_dimension(vertical),
         },
         max_size: taffy::Size {
-            width: style.max_width.into_dimension(context),
-            height: style.max_height.into_dimension(context),
+            width: style.max_width.into_dimension(horizontal),
+            height: style.max_height.into_dimension(vertical),
         },
         aspect_ratio: style.aspect_ratio,
         gap: taffy::Size {
-            width: style.column_gap.into_length_percentage(context),
-            height: style.row_gap.into_length_percentage(context),
+            width: style.column_gap.into_length_percentage(horizontal),
+            height: style.row_gap.into_length_percentage(vertical),
         },
         grid_auto_flow: style.grid_auto_flow.into(),
         grid_template_rows: style
@@ -152,8 +253,14 @@ pub fn from_style(
             .iter()
             .map(|track| track.into_taffy_track(context))
             .collect::<Vec<_>>(),
-        grid_row: style.grid_row.into(),
-        grid_column: style.grid_column.into(),
+        grid_row: context
+            .grid_area
+            .map_or(style.grid_row, |(row, _)| row)
+            .into(),
+        grid_column: context
+            .grid_area
+            .map_or(style.grid_column, |(_, column)| column)
+            .into(),
     }
 }
 
@@ -519,8 +626,10 @@ mod tests {
             ],
             grid_column: GridPlacement::start(4),
             grid_row: GridPlacement::span(3),
+            grid_template_areas: Vec::new(),
+            grid_area: None,
         };
-        let viewport_values = LayoutContext::new(1.0, bevy_math::Vec2::new(800., 600.));
+        let viewport_values = LayoutContext::new(1.0, bevy_math::Vec2::new(800., 600.), 16.);
         let taffy_style = from_style(&viewport_values, &bevy_style, false);
         assert_eq!(taffy_style.display, taffy::style::Display::Flex);
         assert_eq!(taffy_style.position, taffy::style::Position::Absolute);
@@ -657,7 +766,7 @@ mod tests {
     #[test]
     fn test_into_length_percentage() {
         use taffy::style::LengthPercentage;
-        let context = LayoutContext::new(2.0, bevy_math::Vec2::new(800., 600.));
+        let context = LayoutContext::new(2.0, bevy_math::Vec2::new(800., 600.), 16.);
         let cases = [
             (Val::Auto, LengthPercentage::Length(0.)),
             (Val::Percent(1.), LengthPercentage::Percent(0.01)),
@@ -666,6 +775,7 @@ mod tests {
             (Val::Vh(1.), LengthPercentage::Length(6.)),
             (Val::VMin(2.), LengthPercentage::Length(12.)),
             (Val::VMax(2.), LengthPercentage::Length(16.)),
+            (Val::Rem(1.5), LengthPercentage::Length(48.)),
         ];
         for (val, length) in cases {
             assert!(match (val.into_length_percentage(&context), length) {
@@ -676,4 +786,55 @@ mod tests {
             });
         }
     }
+
+    #[test]
+    fn test_grid_area_placement() {
+        let areas = vec![
+            "header header header".to_string(),
+            "sidebar main main".to_string(),
+            "sidebar main main".to_string(),
+            ". footer broken".to_string(),
+            "broken . .".to_string(),
+        ];
+        assert_eq!(
+            grid_area_placement(&areas, "header"),
+            Some((
+                GridPlacement::start_end(1, 2),
+                GridPlacement::start_end(1, 4)
+            ))
+        );
+        assert_eq!(
+            grid_area_placement(&areas, "sidebar"),
+            Some((
+                GridPlacement::start_end(2, 4),
+                GridPlacement::start_end(1, 2)
+            ))
+        );
+        assert_eq!(
+            grid_area_placement(&areas, "main"),
+            Some((
+                GridPlacement::start_end(2, 4),
+                GridPlacement::start_end(2, 4)
+            ))
+        );
+        assert_eq!(grid_area_placement(&areas, "broken"), None);
+        assert_eq!(grid_area_placement(&areas, "missing"), None);
+        assert_eq!(grid_area_placement(&areas, "."), None);
+
+        let context = LayoutContext {
+            grid_area: grid_area_placement(&areas, "footer"),
+            ..LayoutContext::DEFAULT
+        };
+        let style = Style {
+            grid_area: Some("footer".to_string()),
+            grid_row: GridPlacement::start(1),
+            ..Default::default()
+        };
+        let taffy_style = from_style(&context, &style, false);
+        assert_eq!(taffy_style.grid_row, GridPlacement::start_end(4, 5).into());
+        assert_eq!(
+            taffy_style.grid_column,
+            GridPlacement::start_end(2, 3).into()
+        );
+    }
 }
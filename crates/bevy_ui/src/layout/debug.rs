@@ -9,13 +9,21 @@ use crate::layout::ui_surface::UiSurface;
 
 /// Prints a debug representation of the computed layout of the UI layout tree for each window.
 pub fn print_ui_layout_tree(ui_surface: &UiSurface) {
+    bevy_utils::tracing::info!("{}", layout_tree_string(ui_surface));
+}
+
+/// Returns a debug representation of the computed layout of the UI layout tree for each camera.
+///
+/// Each node is listed on its own line with its display mode, location and size in physical pixels, and entity.
+pub fn layout_tree_string(ui_surface: &UiSurface) -> String {
     let taffy_to_entity: HashMap<NodeId, Entity> = ui_surface
         .entity_to_taffy
         .iter()
         .map(|(entity, node)| (*node, *entity))
         .collect();
+    let mut out = String::new();
     for (&entity, roots) in &ui_surface.camera_roots {
-        let mut out = String::new();
+        writeln!(out, "Layout tree for camera entity: {entity:?}").ok();
         for root in roots {
             print_node(
                 ui_surface,
@@ -27,8 +35,8 @@ pub fn print_ui_layout_tree(ui_surface: &UiSurface) {
                 &mut out,
             );
         }
-        bevy_utils::tracing::info!("Layout tree for camera entity: {entity:?}\n{out}");
     }
+    out
 }
 
 /// Recursively navigates the layout tree printing each node's information.
@@ -0,0 +1,57 @@
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+use bevy_utils::Duration;
+
+use crate::layout::ui_surface::UiSurface;
+use crate::UiSystem;
+
+/// Statistics about the work done by [`ui_layout_system`](crate::ui_layout_system) in its most recent run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UiLayoutStats {
+    /// The number of times the layout of a camera's UI was computed.
+    pub relayouts: usize,
+    /// The number of nodes whose styles or measure functions were updated.
+    pub dirty_nodes: usize,
+    /// The number of calls to the measure functions of nodes with a [`ContentSize`](crate::ContentSize).
+    pub measure_calls: usize,
+    /// The total time spent computing layouts.
+    pub layout_time: Duration,
+}
+
+/// Adds UI layout diagnostics to an App: the number of relayouts, dirty nodes and measure function calls, and the
+/// time spent computing the layout, each frame.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin) to output diagnostics to the console, and
+/// [`layout_tree_string`](crate::debug::layout_tree_string) for the computed layout tree.
+#[derive(Default)]
+pub struct UiLayoutDiagnosticsPlugin;
+
+impl Plugin for UiLayoutDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::RELAYOUTS))
+            .register_diagnostic(Diagnostic::new(Self::DIRTY_NODES))
+            .register_diagnostic(Diagnostic::new(Self::MEASURE_CALLS))
+            .register_diagnostic(Diagnostic::new(Self::LAYOUT_TIME).with_suffix("ms"))
+            .add_systems(PostUpdate, Self::diagnostic_system.after(UiSystem::Layout));
+    }
+}
+
+impl UiLayoutDiagnosticsPlugin {
+    pub const RELAYOUTS: DiagnosticPath = DiagnosticPath::const_new("ui/layout/relayouts");
+    pub const DIRTY_NODES: DiagnosticPath = DiagnosticPath::const_new("ui/layout/dirty_nodes");
+    pub const MEASURE_CALLS: DiagnosticPath = DiagnosticPath::const_new("ui/layout/measure_calls");
+    pub const LAYOUT_TIME: DiagnosticPath = DiagnosticPath::const_new("ui/layout/time");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, ui_surface: Res<UiSurface>) {
+        let stats = ui_surface.stats();
+        diagnostics.add_measurement(&Self::RELAYOUTS, || stats.relayouts as f64);
+        diagnostics.add_measurement(&Self::DIRTY_NODES, || stats.dirty_nodes as f64);
+        diagnostics.add_measurement(&Self::MEASURE_CALLS, || stats.measure_calls as f64);
+        diagnostics.add_measurement(&Self::LAYOUT_TIME, || {
+            stats.layout_time.as_secs_f64() * 1000.0
+        });
+    }
+}
@@ -1,33 +1,58 @@
 use thiserror::Error;
 
-use crate::{ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiScale};
+use crate::{viewports::ViewportComponent, BorderRect};
+use crate::{
+    ContentSize, Display, FlexDirection, GridPlacement, LayoutRounding, Node, Outline,
+    PercentRounding, PositionType, RootFontSize, ScrollPosition, Style, TargetCamera,
+    UiLayoutSettings, UiRootScale, UiScale, UiViewport, UiViewports, Val, ValExpr,
+};
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
     entity::Entity,
     event::EventReader,
     query::{With, Without},
     removal_detection::RemovedComponents,
-    system::{Query, Res, ResMut, SystemParam},
+    system::{Local, Query, Res, ResMut, SystemParam},
     world::Ref,
 };
 use bevy_hierarchy::{Children, Parent};
 use bevy_math::{UVec2, Vec2};
-use bevy_render::camera::{Camera, NormalizedRenderTarget};
 use bevy_transform::components::Transform;
-use bevy_utils::tracing::warn;
-use bevy_utils::{HashMap, HashSet};
-use bevy_window::{PrimaryWindow, Window, WindowScaleFactorChanged};
-use ui_surface::UiSurface;
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+use bevy_utils::{tracing::warn, warn_once, HashMap, HashSet, Instant};
+use bevy_window::WindowScaleFactorChanged;
+pub use ui_surface::UiSurface;
+
+pub use diagnostics::*;
 
 mod convert;
 pub mod debug;
+mod diagnostics;
 pub(crate) mod ui_surface;
 
+#[derive(Clone, Copy)]
 pub struct LayoutContext {
     pub scale_factor: f32,
     pub physical_size: Vec2,
     pub min_size: f32,
     pub max_size: f32,
+    /// The [`RootFontSize`] in physical pixels.
+    pub root_font_size: f32,
+    /// The physical size of the content box of the node's parent in the previous layout, if known.
+    ///
+    /// Taffy can't lay out [`Val::Calc`] values that mix percentages with other units, so they are resolved
+    /// against this size instead.
+    pub parent_size: Option<Vec2>,
+    /// The length that percentages in [`Val::Calc`] values are resolved against, selected from
+    /// [`LayoutContext::parent_size`] for the axis of the property being converted.
+    percent_basis: Option<f32>,
+    /// The row and column placements of the area in the parent's [`Style::grid_template_areas`] named by the
+    /// node's [`Style::grid_area`], if any. These override the node's `grid_row` and `grid_column`.
+    pub grid_area: Option<(GridPlacement, GridPlacement)>,
+    /// The space added to each of the node's margins by an [`Outline`] with
+    /// [`OutlineMode::Push`](crate::OutlineMode::Push), if any.
+    pub outline_push: Option<ValExpr>,
 }
 
 impl LayoutContext {
@@ -36,14 +61,28 @@ impl LayoutContext {
         physical_size: Vec2::ZERO,
         min_size: 0.0,
         max_size: 0.0,
+        root_font_size: 16.0,
+        parent_size: None,
+        percent_basis: None,
+        grid_area: None,
+        outline_push: None,
     };
-    /// create new a [`LayoutContext`] from the window's physical size and scale factor
-    fn new(scale_factor: f32, physical_size: Vec2) -> Self {
+    /// Creates a [`LayoutContext`] for the nodes of a viewport of the given physical size and scale factor, with the
+    /// root font size in logical pixels.
+    ///
+    /// The viewport doesn't have to belong to a window or camera, so with [`UiSurface`] a UI can be laid out in a
+    /// fixed-size viewport, e.g. for offscreen UI or server-side layout computation.
+    pub fn new(scale_factor: f32, physical_size: Vec2, root_font_size: f32) -> Self {
         Self {
             scale_factor,
             physical_size,
             min_size: physical_size.x.min(physical_size.y),
             max_size: physical_size.x.max(physical_size.y),
+            root_font_size: scale_factor * root_font_size,
+            parent_size: None,
+            percent_basis: None,
+            grid_area: None,
+            outline_push: None,
         }
     }
 }
@@ -64,19 +103,42 @@ pub enum LayoutError {
 
 #[derive(SystemParam)]
 pub struct UiLayoutSystemRemovedComponentParam<'w, 's> {
-    removed_cameras: RemovedComponents<'w, 's, Camera>,
+    removed_cameras: RemovedComponents<'w, 's, ViewportComponent>,
     removed_children: RemovedComponents<'w, 's, Children>,
     removed_content_sizes: RemovedComponents<'w, 's, ContentSize>,
     removed_nodes: RemovedComponents<'w, 's, Node>,
 }
 
+/// The resources that control how [`ui_layout_system`] rounds the geometry of nodes.
+#[derive(SystemParam)]
+pub struct UiLayoutRoundingParam<'w> {
+    percent_rounding: Res<'w, PercentRounding>,
+    layout_settings: Res<'w, UiLayoutSettings>,
+}
+
+/// The maximum number of times [`ui_layout_system`] computes a camera's layout again to resolve [`Val::Calc`]
+/// values against the new sizes of their parents.
+const MAX_CALC_RELAYOUTS: usize = 8;
+
+/// State kept between runs of [`ui_layout_system`].
+#[derive(Default)]
+pub struct UiLayoutSystemState {
+    last_camera_sizes: HashMap<Entity, UVec2>,
+    /// The parent sizes and cameras that the `Val::Calc` values of each node were last resolved against.
+    calc_parent_sizes: HashMap<Entity, (Option<Vec2>, Entity)>,
+    /// The margins last added to the nodes with outlines that take up space in the layout.
+    outline_pushes: HashMap<Entity, ValExpr>,
+    /// The [`UiRootScale`]s that the nodes under scaled roots were last laid out with.
+    root_scales: HashMap<Entity, f32>,
+}
+
 /// Updates the UI's layout tree, computes the new layout geometry and then updates the sizes and transforms of all the UI nodes.
 #[allow(clippy::too_many_arguments)]
 pub fn ui_layout_system(
-    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
-    cameras: Query<(Entity, &Camera)>,
-    default_ui_camera: DefaultUiCamera,
+    viewports: UiViewports,
     ui_scale: Res<UiScale>,
+    root_font_size: Res<RootFontSize>,
+    rounding: UiLayoutRoundingParam,
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut resize_events: EventReader<bevy_window::WindowResized>,
     mut ui_surface: ResMut<UiSurface>,
@@ -87,14 +149,37 @@ pub fn ui_layout_system(
             Ref<Style>,
             Option<&mut ContentSize>,
             Option<&TargetCamera>,
+            Option<Ref<Parent>>,
+            Option<&Outline>,
+            Option<&UiRootScale>,
         ),
         With<Node>,
     >,
     children_query: Query<(Entity, Ref<Children>), With<Node>>,
     just_children_query: Query<&Children>,
     mut removed_components: UiLayoutSystemRemovedComponentParam,
-    mut node_transform_query: Query<(&mut Node, &mut Transform)>,
+    mut node_transform_query: Query<(
+        &mut Node,
+        &mut Transform,
+        Option<&Style>,
+        Option<&mut ScrollPosition>,
+        Option<&LayoutRounding>,
+    )>,
+    mut state: Local<UiLayoutSystemState>,
 ) {
+    let UiLayoutSystemState {
+        last_camera_sizes,
+        calc_parent_sizes,
+        outline_pushes,
+        root_scales,
+    } = &mut *state;
+
+    #[cfg(feature = "trace")]
+    let _span = info_span!("ui_layout", nodes = ui_surface.entity_to_taffy.len()).entered();
+
+    let start = Instant::now();
+    ui_surface.stats = UiLayoutStats::default();
+
     struct CameraLayoutInfo {
         size: UVec2,
         resized: bool,
@@ -102,26 +187,23 @@ pub fn ui_layout_system(
         root_nodes: Vec<Entity>,
     }
 
-    let camera_with_default = |target_camera: Option<&TargetCamera>| {
-        target_camera
-            .map(TargetCamera::entity)
-            .or(default_ui_camera.get())
-    };
+    let camera_with_default =
+        |target_camera: Option<&TargetCamera>| viewports.viewport_of(target_camera);
 
     let resized_windows: HashSet<Entity> = resize_events.read().map(|event| event.window).collect();
-    let calculate_camera_layout_info = |camera: &Camera| {
-        let size = camera.physical_viewport_size().unwrap_or(UVec2::ZERO);
-        let scale_factor = camera.target_scaling_factor().unwrap_or(1.0);
-        let camera_target = camera
-            .target
-            .normalize(primary_window.get_single().map(|(e, _)| e).ok());
-        let resized = matches!(camera_target,
-          Some(NormalizedRenderTarget::Window(window_ref)) if resized_windows.contains(&window_ref.entity())
-        );
+    let calculate_camera_layout_info = |camera_entity: Entity, camera: UiViewport| {
+        let size = camera.physical_size;
+        // Viewport units resolve against the camera's viewport, which can be resized independently of its window.
+        let resized = camera
+            .window
+            .is_some_and(|window| resized_windows.contains(&window))
+            || last_camera_sizes
+                .get(&camera_entity)
+                .is_some_and(|last_size| *last_size != size);
         CameraLayoutInfo {
             size,
             resized,
-            scale_factor: scale_factor * ui_scale.0,
+            scale_factor: camera.scale_factor * ui_scale.0,
             root_nodes: Vec::new(),
         }
     };
@@ -131,7 +213,7 @@ pub fn ui_layout_system(
     for (entity, target_camera) in &root_node_query {
         match camera_with_default(target_camera) {
             Some(camera_entity) => {
-                let Ok((_, camera)) = cameras.get(camera_entity) else {
+                let Some(camera) = viewports.get(camera_entity) else {
                     warn!(
                         "TargetCamera (of root UI node {entity:?}) is pointing to a camera {:?} which doesn't exist",
                         camera_entity
@@ -140,11 +222,17 @@ pub fn ui_layout_system(
                 };
                 let layout_info = camera_layout_info
                     .entry(camera_entity)
-                    .or_insert_with(|| calculate_camera_layout_info(camera));
+                    .or_insert_with(|| calculate_camera_layout_info(camera_entity, camera));
                 layout_info.root_nodes.push(entity);
             }
+            #[cfg(not(feature = "render"))]
+            None => {
+                warn!("No primary window found to lay out UI in. To fix this, spawn a `Window` with a `PrimaryWindow`.");
+                continue;
+            }
+            #[cfg(feature = "render")]
             None => {
-                if cameras.is_empty() {
+                if viewports.iter().next().is_none() {
                     warn!("No camera found to render UI to. To fix this, add at least one camera to the scene.");
                 } else {
                     warn!(
@@ -158,29 +246,105 @@ pub fn ui_layout_system(
         }
     }
 
+    *last_camera_sizes = camera_layout_info
+        .iter()
+        .map(|(camera_entity, info)| (*camera_entity, info.size))
+        .collect();
+
     // When a `ContentSize` component is removed from an entity, we need to remove the measure from the corresponding taffy node.
     for entity in removed_components.removed_content_sizes.read() {
         ui_surface.try_remove_node_context(entity);
     }
 
+    // The physical size that percentages in `Val::Calc` values mixed with other units are resolved against
+    let calc_parent_size =
+        |ui_surface: &UiSurface, parent: Option<&Parent>, camera_size: UVec2| match parent {
+            Some(parent) => ui_surface.content_box_size(parent.get()),
+            None => Some(camera_size.as_vec2()),
+        };
+
+    // Resolve the named grid areas of nodes against their parents' `grid_template_areas`, noting whether the
+    // placement may have changed since the last update
+    let mut grid_areas: HashMap<Entity, (Option<(GridPlacement, GridPlacement)>, bool)> =
+        HashMap::new();
+    for (entity, style, _, _, parent, ..) in style_query.iter() {
+        let Some(area) = style.grid_area.as_deref() else {
+            continue;
+        };
+        let parent_style = parent
+            .as_ref()
+            .and_then(|parent| style_query.get(parent.get()).ok())
+            .map(|(_, parent_style, ..)| parent_style);
+        let placement = parent_style.as_ref().and_then(|parent_style| {
+            convert::grid_area_placement(&parent_style.grid_template_areas, area)
+        });
+        let changed = parent.as_ref().is_some_and(DetectChanges::is_changed)
+            || parent_style.as_ref().is_some_and(DetectChanges::is_changed);
+        if placement.is_none() && (changed || style.is_changed()) {
+            warn!(
+                "Grid area \"{area}\" of UI node {entity:?} is not a rectangular area in its parent's `grid_template_areas`"
+            );
+        }
+        grid_areas.insert(entity, (placement, changed));
+    }
+
     // Sync Style and ContentSize to Taffy for all nodes
-    for (entity, style, content_size, target_camera) in style_query.iter_mut() {
-        if let Some(camera) =
-            camera_with_default(target_camera).and_then(|c| camera_layout_info.get(&c))
+    let previous_calc_parent_sizes = std::mem::take(calc_parent_sizes);
+    let previous_outline_pushes = std::mem::take(outline_pushes);
+    let previous_root_scales = std::mem::take(root_scales);
+    for (entity, style, content_size, target_camera, parent, outline, root_scale) in
+        style_query.iter_mut()
+    {
+        let camera_entity = camera_with_default(target_camera);
+        if let Some((camera_entity, camera)) =
+            camera_entity.and_then(|c| camera_layout_info.get(&c).map(|camera| (c, camera)))
         {
+            let outline_push = outline
+                .and_then(|outline| outline.push_margin(camera.size.as_vec2(), root_font_size.0));
+            if let Some(push) = outline_push {
+                outline_pushes.insert(entity, push);
+            }
+            // Only the nodes under scaled roots are tracked, so that changing the scale of a root only updates
+            // the nodes under it
+            let root_scale = root_scale
+                .map(|root_scale| root_scale.0)
+                .filter(|&s| s != 1.);
+            if let Some(root_scale) = root_scale {
+                root_scales.insert(entity, root_scale);
+            }
+            let uses_parent_size = convert::uses_parent_size(&style, outline_push);
+            let parent_size = uses_parent_size
+                .then(|| calc_parent_size(&ui_surface, parent.as_deref(), camera.size))
+                .flatten();
+            if uses_parent_size {
+                calc_parent_sizes.insert(entity, (parent_size, camera_entity));
+            }
+            let grid_area = grid_areas.get(&entity);
+
             if camera.resized
                 || !scale_factor_events.is_empty()
                 || ui_scale.is_changed()
+                || root_font_size.is_changed()
                 || style.is_changed()
+                || (uses_parent_size
+                    && previous_calc_parent_sizes.get(&entity)
+                        != Some(&(parent_size, camera_entity)))
+                || grid_area.is_some_and(|(_, changed)| *changed)
+                || previous_outline_pushes.get(&entity) != outline_push.as_ref()
+                || previous_root_scales.get(&entity) != root_scale.as_ref()
                 || content_size
                     .as_ref()
                     .map(|c| c.measure.is_some())
                     .unwrap_or(false)
             {
-                let layout_context = LayoutContext::new(
-                    camera.scale_factor,
+                let mut layout_context = LayoutContext::new(
+                    camera.scale_factor * root_scale.unwrap_or(1.),
                     [camera.size.x as f32, camera.size.y as f32].into(),
+                    root_font_size.0,
                 );
+                layout_context.parent_size = parent_size;
+                layout_context.grid_area = grid_area.and_then(|(placement, _)| *placement);
+                layout_context.outline_push = outline_push;
                 let measure = content_size.and_then(|mut c| c.measure.take());
                 ui_surface.upsert_node(&layout_context, entity, &style, measure);
             }
@@ -197,7 +361,7 @@ pub fn ui_layout_system(
     ui_surface.remove_camera_entities(removed_components.removed_cameras.read());
 
     // update camera children
-    for (camera_id, _) in cameras.iter() {
+    for camera_id in viewports.iter() {
         let root_nodes =
             if let Some(CameraLayoutInfo { root_nodes, .. }) = camera_layout_info.get(&camera_id) {
                 root_nodes.iter().cloned()
@@ -221,63 +385,202 @@ pub fn ui_layout_system(
         let inverse_target_scale_factor = camera.scale_factor.recip();
 
         ui_surface.compute_camera_layout(*camera_id, camera.size);
+
+        // `Val::Calc` values are resolved against the parent sizes from the previous layout, so while any of those
+        // sizes change the layout is computed again. Each pass resolves another level of nested values. A parent
+        // sized by its children can keep changing size, so the number of passes is limited.
+        for _ in 0..MAX_CALC_RELAYOUTS {
+            let mut relayout = false;
+            for (entity, (parent_size, calc_camera)) in calc_parent_sizes.iter_mut() {
+                if calc_camera != camera_id {
+                    continue;
+                }
+                let Ok((_, style, _, _, parent, ..)) = style_query.get(*entity) else {
+                    continue;
+                };
+                let Some(new_parent_size) =
+                    calc_parent_size(&ui_surface, parent.as_deref(), camera.size)
+                else {
+                    continue;
+                };
+                if Some(new_parent_size) != *parent_size {
+                    *parent_size = Some(new_parent_size);
+                    let mut layout_context = LayoutContext::new(
+                        camera.scale_factor * root_scales.get(entity).copied().unwrap_or(1.),
+                        camera.size.as_vec2(),
+                        root_font_size.0,
+                    );
+                    layout_context.parent_size = Some(new_parent_size);
+                    layout_context.grid_area =
+                        grid_areas.get(entity).and_then(|(placement, _)| *placement);
+                    layout_context.outline_push = outline_pushes.get(entity).copied();
+                    ui_surface.upsert_node(&layout_context, *entity, &style, None);
+                    relayout = true;
+                }
+            }
+            if !relayout {
+                break;
+            }
+            ui_surface.compute_camera_layout(*camera_id, camera.size);
+        }
+
         for root in &camera.root_nodes {
+            let root_scale = style_query
+                .get(*root)
+                .ok()
+                .and_then(|(.., root_scale)| root_scale)
+                .map_or(1., |root_scale| root_scale.0);
             update_uinode_geometry_recursive(
                 *root,
                 &ui_surface,
                 &mut node_transform_query,
                 &just_children_query,
+                *rounding.percent_rounding,
+                rounding.layout_settings.rounding,
                 inverse_target_scale_factor,
+                root_scale,
+                Vec2::ZERO,
                 Vec2::ZERO,
                 Vec2::ZERO,
+                SiblingAdjustment::default(),
             );
         }
     }
 
+    if let Some(budget) = rounding.layout_settings.layout_budget {
+        let elapsed = start.elapsed();
+        if budget < elapsed {
+            warn!(
+                "UI layout took {elapsed:?}, over the budget of {budget:?}: {:?}",
+                ui_surface.stats()
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn update_uinode_geometry_recursive(
         entity: Entity,
         ui_surface: &UiSurface,
-        node_transform_query: &mut Query<(&mut Node, &mut Transform)>,
+        node_transform_query: &mut Query<(
+            &mut Node,
+            &mut Transform,
+            Option<&Style>,
+            Option<&mut ScrollPosition>,
+            Option<&LayoutRounding>,
+        )>,
         children_query: &Query<&Children>,
+        percent_rounding: PercentRounding,
+        layout_rounding: LayoutRounding,
         inverse_target_scale_factor: f32,
+        root_scale: f32,
         parent_size: Vec2,
+        parent_scroll_position: Vec2,
         mut absolute_location: Vec2,
+        adjustment: SiblingAdjustment,
     ) {
-        if let Ok((mut node, mut transform)) = node_transform_query.get_mut(entity) {
+        if let Ok((mut node, mut transform, style, scroll_position, node_rounding)) =
+            node_transform_query.get_mut(entity)
+        {
+            // A node's `LayoutRounding` applies to it and its descendants
+            let layout_rounding = node_rounding.copied().unwrap_or(layout_rounding);
+
             let Ok(layout) = ui_surface.get_layout(entity) else {
                 return;
             };
             let layout_size =
                 inverse_target_scale_factor * Vec2::new(layout.size.width, layout.size.height);
-            let layout_location =
-                inverse_target_scale_factor * Vec2::new(layout.location.x, layout.location.y);
+            let layout_location = adjustment.offset
+                + inverse_target_scale_factor * Vec2::new(layout.location.x, layout.location.y);
+
+            absolute_location += layout_location - parent_scroll_position;
 
-            absolute_location += layout_location;
+            let adjusted_size = adjustment.size.unwrap_or(layout_size);
+            let rounded_size = layout_rounding.round(absolute_location + adjusted_size)
+                - layout_rounding.round(absolute_location);
 
-            let rounded_size = round_layout_coords(absolute_location + layout_size)
-                - round_layout_coords(absolute_location);
+            let rounded_location = layout_rounding.round(layout_location - parent_scroll_position)
+                + 0.5 * (rounded_size - parent_size);
 
-            let rounded_location =
-                round_layout_coords(layout_location) + 0.5 * (rounded_size - parent_size);
+            let to_border_rect = |rect: taffy::Rect<f32>| BorderRect {
+                left: inverse_target_scale_factor * rect.left,
+                right: inverse_target_scale_factor * rect.right,
+                top: inverse_target_scale_factor * rect.top,
+                bottom: inverse_target_scale_factor * rect.bottom,
+            };
+            let border = to_border_rect(layout.border);
+            let padding = to_border_rect(layout.padding);
+            let content_size = layout_rounding.round(
+                inverse_target_scale_factor
+                    * Vec2::new(layout.content_size.width, layout.content_size.height),
+            );
 
             // only trigger change detection when the new values are different
-            if node.calculated_size != rounded_size || node.unrounded_size != layout_size {
+            if node.calculated_size != rounded_size
+                || node.unrounded_size != layout_size
+                || node.border != border
+                || node.padding != padding
+                || node.content_size != content_size
+            {
                 node.calculated_size = rounded_size;
                 node.unrounded_size = layout_size;
+                node.border = border;
+                node.padding = padding;
+                node.content_size = content_size;
+            }
+            if node.root_scale != root_scale {
+                node.root_scale = root_scale;
             }
             if transform.translation.truncate() != rounded_location {
                 transform.translation = rounded_location.extend(0.);
             }
+
+            // Children are only scrolled along the axes where the node's overflow isn't visible.
+            let scroll_position = match (style, scroll_position) {
+                (Some(style), Some(mut scroll_position)) => {
+                    let max_offset = (content_size - rounded_size).max(Vec2::ZERO);
+                    let clamped = scroll_position.offset().clamp(Vec2::ZERO, max_offset);
+                    if scroll_position.offset() != clamped {
+                        *scroll_position = clamped.into();
+                    }
+                    Vec2::new(
+                        if style.overflow.x.is_visible() {
+                            0.
+                        } else {
+                            clamped.x
+                        },
+                        if style.overflow.y.is_visible() {
+                            0.
+                        } else {
+                            clamped.y
+                        },
+                    )
+                }
+                _ => Vec2::ZERO,
+            };
+
             if let Ok(children) = children_query.get(entity) {
-                for &child_uinode in children {
+                let adjustments = sibling_adjustments(
+                    percent_rounding,
+                    style.map(|style| (style.display, style.flex_direction)),
+                    children,
+                    ui_surface,
+                    node_transform_query,
+                    inverse_target_scale_factor,
+                );
+                for (&child_uinode, adjustment) in children.iter().zip(adjustments) {
                     update_uinode_geometry_recursive(
                         child_uinode,
                         ui_surface,
                         node_transform_query,
                         children_query,
+                        percent_rounding,
+                        layout_rounding,
                         inverse_target_scale_factor,
+                        root_scale,
                         rounded_size,
+                        scroll_position,
                         absolute_location,
+                        adjustment,
                     );
                 }
             }
@@ -285,29 +588,169 @@ pub fn ui_layout_system(
     }
 }
 
+/// Changes to the layout of a node made by the [`PercentRounding`] policy of its parent.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct SiblingAdjustment {
+    /// Added to the node's location.
+    offset: Vec2,
+    /// Replaces the node's size.
+    size: Option<Vec2>,
+}
+
+/// Computes the adjustments needed to apply the [`PercentRounding`] policy to the in-flow children of a flex
+/// container, returned in the same order as `children`.
+///
+/// The sizes of percentage sized children along the main axis are replaced by their rounded sizes, and every child
+/// is moved by the sum of the size changes of the children laid out before it. The adjustments are made to the
+/// computed layout, which isn't computed again, see [`PercentRounding`].
+///
+/// The children of grid and block containers aren't adjusted, and a warning is logged if they are percentage sized.
+fn sibling_adjustments(
+    percent_rounding: PercentRounding,
+    container: Option<(Display, FlexDirection)>,
+    children: &[Entity],
+    ui_surface: &UiSurface,
+    node_query: &Query<(
+        &mut Node,
+        &mut Transform,
+        Option<&Style>,
+        Option<&mut ScrollPosition>,
+        Option<&LayoutRounding>,
+    )>,
+    inverse_target_scale_factor: f32,
+) -> Vec<SiblingAdjustment> {
+    let mut adjustments = vec![SiblingAdjustment::default(); children.len()];
+    let axis = match (percent_rounding, container) {
+        (PercentRounding::None, _) | (_, None) | (_, Some((Display::None, _))) => {
+            return adjustments
+        }
+        (_, Some((Display::Flex, FlexDirection::Row | FlexDirection::RowReverse))) => 0,
+        (_, Some((Display::Flex, FlexDirection::Column | FlexDirection::ColumnReverse))) => 1,
+        (_, Some((container_display, _))) => {
+            let is_percent_sized = |child: &Entity| {
+                node_query
+                    .get(*child)
+                    .ok()
+                    .and_then(|(_, _, style, ..)| style)
+                    .is_some_and(|style| {
+                        [style.width, style.height, style.flex_basis]
+                            .iter()
+                            .any(|val| matches!(val, Val::Percent(_)))
+                    })
+            };
+            if children.iter().any(is_percent_sized) {
+                warn_once!(
+                    "{percent_rounding:?} percent rounding is only applied to the children of flex containers, \
+                    the percentage sized children of {container_display:?} containers are not rounded."
+                );
+            }
+            return adjustments;
+        }
+    };
+
+    struct InFlowChild {
+        index: usize,
+        location: f32,
+        size: Vec2,
+        percent: bool,
+    }
+    let mut in_flow = Vec::new();
+    for (index, &child) in children.iter().enumerate() {
+        let (Ok(layout), Ok((_, _, Some(style), _, _))) =
+            (ui_surface.get_layout(child), node_query.get(child))
+        else {
+            continue;
+        };
+        if style.display == Display::None || style.position_type == PositionType::Absolute {
+            continue;
+        }
+        let main_size = if axis == 0 { style.width } else { style.height };
+        in_flow.push(InFlowChild {
+            index,
+            location: inverse_target_scale_factor * [layout.location.x, layout.location.y][axis],
+            size: inverse_target_scale_factor * Vec2::new(layout.size.width, layout.size.height),
+            percent: matches!(main_size, Val::Percent(_))
+                || matches!(style.flex_basis, Val::Percent(_)),
+        });
+    }
+
+    let mut targets: Vec<Option<f32>> = in_flow
+        .iter()
+        .map(|child| {
+            child.percent.then(|| match percent_rounding {
+                PercentRounding::Round => child.size[axis].round(),
+                _ => child.size[axis].floor(),
+            })
+        })
+        .collect();
+    if percent_rounding == PercentRounding::FloorDistribute {
+        let total: f32 = in_flow
+            .iter()
+            .filter(|child| child.percent)
+            .map(|child| child.size[axis])
+            .sum();
+        let floored: f32 = targets.iter().flatten().sum();
+        let mut remaining = (total.round() - floored).max(0.) as usize;
+        let mut by_fraction: Vec<usize> =
+            (0..in_flow.len()).filter(|&i| in_flow[i].percent).collect();
+        // `sort_by` is stable, so earlier siblings come first on ties
+        by_fraction.sort_by(|&a, &b| {
+            in_flow[b].size[axis]
+                .fract()
+                .total_cmp(&in_flow[a].size[axis].fract())
+        });
+        for i in by_fraction {
+            if remaining == 0 {
+                break;
+            }
+            if let Some(target) = targets[i].as_mut() {
+                *target += 1.;
+                remaining -= 1;
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..in_flow.len()).collect();
+    order.sort_by(|&a, &b| in_flow[a].location.total_cmp(&in_flow[b].location));
+    let mut offset = 0.;
+    for i in order {
+        let child = &in_flow[i];
+        let adjustment = &mut adjustments[child.index];
+        adjustment.offset[axis] = offset;
+        if let Some(target) = targets[i] {
+            let mut size = child.size;
+            size[axis] = target;
+            adjustment.size = Some(size);
+            offset += target - child.size[axis];
+        }
+    }
+    adjustments
+}
+
 /// Resolve and update the widths of Node outlines
+///
+/// Viewport units are resolved against the viewport of each node's target camera.
 pub fn resolve_outlines_system(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
+    viewports: UiViewports,
     ui_scale: Res<UiScale>,
-    mut outlines_query: Query<(&Outline, &mut Node)>,
+    root_font_size: Res<RootFontSize>,
+    mut outlines_query: Query<(&Outline, &mut Node, Option<&TargetCamera>)>,
 ) {
-    let viewport_size = primary_window
-        .get_single()
-        .map(|window| window.size())
-        .unwrap_or(Vec2::ZERO)
-        / ui_scale.0;
+    for (outline, mut node, target_camera) in outlines_query.iter_mut() {
+        let viewport_size = viewports.ui_logical_size_of(target_camera, ui_scale.0);
 
-    for (outline, mut node) in outlines_query.iter_mut() {
         let node = node.bypass_change_detection();
         node.outline_width = outline
             .width
-            .resolve(node.size().x, viewport_size)
+            .scale_lengths(node.root_scale)
+            .resolve(node.size().x, viewport_size, root_font_size.0)
             .unwrap_or(0.)
             .max(0.);
 
         node.outline_offset = outline
             .offset
-            .resolve(node.size().x, viewport_size)
+            .scale_lengths(node.root_scale)
+            .resolve(node.size().x, viewport_size, root_font_size.0)
             .unwrap_or(0.)
             .max(0.);
     }
@@ -341,12 +784,24 @@ fn round_layout_coords(value: Vec2) -> Vec2 {
     }
 }
 
-#[cfg(test)]
+impl LayoutRounding {
+    /// Rounds layout coordinates according to the rounding mode.
+    fn round(self, value: Vec2) -> Vec2 {
+        match self {
+            LayoutRounding::Round => round_layout_coords(value),
+            LayoutRounding::Floor => value.floor(),
+            LayoutRounding::None => value,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "render"))]
 mod tests {
     use taffy::TraversePartialTree;
 
     use bevy_asset::AssetEvent;
     use bevy_asset::Assets;
+    use bevy_color::Color;
     use bevy_core_pipeline::core_2d::Camera2dBundle;
     use bevy_ecs::entity::Entity;
     use bevy_ecs::event::Events;
@@ -361,10 +816,13 @@ mod tests {
     use bevy_math::{vec2, Rect, UVec2, Vec2};
     use bevy_render::camera::ManualTextureViews;
     use bevy_render::camera::OrthographicProjection;
+    use bevy_render::camera::RenderTarget;
+    use bevy_render::camera::Viewport;
     use bevy_render::prelude::Camera;
+    use bevy_render::render_asset::RenderAssetUsages;
+    use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
     use bevy_render::texture::Image;
-    use bevy_transform::prelude::GlobalTransform;
-    use bevy_transform::systems::{propagate_transforms, sync_simple_transforms};
+    use bevy_transform::prelude::{GlobalTransform, Transform};
     use bevy_utils::prelude::default;
     use bevy_utils::HashMap;
     use bevy_window::PrimaryWindow;
@@ -376,10 +834,16 @@ mod tests {
 
     use crate::layout::round_layout_coords;
     use crate::layout::ui_surface::UiSurface;
+    use crate::measurement::AvailableSpace;
     use crate::prelude::*;
+    use crate::testing::{ui_test_schedule, ui_test_world};
     use crate::ui_layout_system;
     use crate::update::update_target_camera_system;
     use crate::ContentSize;
+    use crate::LayoutRounding;
+    use crate::PercentRounding;
+    use crate::UiLayoutSettings;
+    use crate::UiRootScale;
 
     #[test]
     fn round_layout_coords_must_round_ties_up() {
@@ -391,42 +855,10 @@ mod tests {
     const WINDOW_HEIGHT: f32 = 100.;
 
     fn setup_ui_test_world() -> (World, Schedule) {
-        let mut world = World::new();
-        world.init_resource::<UiScale>();
-        world.init_resource::<UiSurface>();
-        world.init_resource::<Events<WindowScaleFactorChanged>>();
-        world.init_resource::<Events<WindowResized>>();
-        // Required for the camera system
-        world.init_resource::<Events<WindowCreated>>();
-        world.init_resource::<Events<AssetEvent<Image>>>();
-        world.init_resource::<Assets<Image>>();
-        world.init_resource::<ManualTextureViews>();
-
-        // spawn a dummy primary window and camera
-        world.spawn((
-            Window {
-                resolution: WindowResolution::new(WINDOW_WIDTH, WINDOW_HEIGHT),
-                ..default()
-            },
-            PrimaryWindow,
-        ));
-        world.spawn(Camera2dBundle::default());
-
-        let mut ui_schedule = Schedule::default();
-        ui_schedule.add_systems(
-            (
-                // UI is driven by calculated camera target info, so we need to run the camera system first
-                bevy_render::camera::camera_system::<OrthographicProjection>,
-                update_target_camera_system,
-                apply_deferred,
-                ui_layout_system,
-                sync_simple_transforms,
-                propagate_transforms,
-            )
-                .chain(),
-        );
-
-        (world, ui_schedule)
+        (
+            ui_test_world(WINDOW_WIDTH, WINDOW_HEIGHT),
+            ui_test_schedule(),
+        )
     }
 
     #[test]
@@ -468,6 +900,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn calc_values_mixing_percentages_resolve_against_the_parent_size() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let ui_root = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+
+        let ui_child = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: (Val::Percent(100.) - Val::Px(40.)).into(),
+                    height: (Val::Percent(50.) + Val::Px(5.)).into(),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+
+        let ui_grandchild = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: (Val::Percent(50.) + Val::Px(10.)).into(),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+
+        world.entity_mut(ui_root).add_child(ui_child);
+        world.entity_mut(ui_child).add_child(ui_grandchild);
+
+        // The parents' sizes are resolved in the same frame the nodes are added, including nested values
+        ui_schedule.run(&mut world);
+        let ui_surface = world.resource::<UiSurface>();
+        let layout = ui_surface.get_layout(ui_child).unwrap();
+        assert_eq!(layout.size.width, WINDOW_WIDTH - 20. - 40.);
+        assert_eq!(layout.size.height, 0.5 * (WINDOW_HEIGHT - 20.) + 5.);
+        let layout = ui_surface.get_layout(ui_grandchild).unwrap();
+        assert_eq!(layout.size.width, 0.5 * (WINDOW_WIDTH - 20. - 40.) + 10.);
+
+        // Changes to the parents' sizes are followed in the same frame
+        world.get_mut::<Style>(ui_root).unwrap().padding = UiRect::all(Val::Px(20.));
+        ui_schedule.run(&mut world);
+        let ui_surface = world.resource::<UiSurface>();
+        let layout = ui_surface.get_layout(ui_child).unwrap();
+        assert_eq!(layout.size.width, WINDOW_WIDTH - 40. - 40.);
+        assert_eq!(layout.size.height, 0.5 * (WINDOW_HEIGHT - 40.) + 5.);
+        let layout = ui_surface.get_layout(ui_grandchild).unwrap();
+        assert_eq!(layout.size.width, 0.5 * (WINDOW_WIDTH - 40. - 40.) + 10.);
+    }
+
     #[test]
     fn ui_surface_tracks_ui_entities() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
@@ -545,6 +1037,249 @@ mod tests {
         assert!(ui_surface.camera_entity_to_taffy.is_empty());
     }
 
+    #[test]
+    fn viewport_units_resolve_against_the_target_camera_viewport() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let viewport = |x: u32, width: u32| Viewport {
+            physical_position: UVec2::new(x, 0),
+            physical_size: UVec2::new(width, WINDOW_HEIGHT as u32),
+            ..default()
+        };
+        let cameras = [(0, 1), (500, 2)].map(|(x, order)| {
+            world
+                .spawn(Camera2dBundle {
+                    camera: Camera {
+                        viewport: Some(viewport(x, 500)),
+                        order,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .id()
+        });
+        let roots = cameras.map(|camera| {
+            world
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Vw(50.),
+                            height: Val::Vh(50.),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    TargetCamera(camera),
+                ))
+                .id()
+        });
+
+        ui_schedule.run(&mut world);
+
+        for root in roots {
+            assert_eq!(
+                world.get::<Node>(root).unwrap().size(),
+                Vec2::new(250., 50.)
+            );
+        }
+
+        // resizing a viewport without resizing the window
+        world.get_mut::<Camera>(cameras[0]).unwrap().viewport = Some(viewport(0, 200));
+
+        ui_schedule.run(&mut world);
+
+        assert_eq!(
+            world.get::<Node>(roots[0]).unwrap().size(),
+            Vec2::new(100., 50.)
+        );
+        assert_eq!(
+            world.get::<Node>(roots[1]).unwrap().size(),
+            Vec2::new(250., 50.)
+        );
+    }
+
+    #[test]
+    fn ui_root_nodes_fill_the_target_image_of_their_camera() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let image_size = |width, height| Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let image = world.resource_mut::<Assets<Image>>().add(Image::new_fill(
+            image_size(300, 200),
+            TextureDimension::D2,
+            &[0; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        ));
+        let camera = world
+            .spawn(Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(image.clone()),
+                    order: 1,
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        let root = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                TargetCamera(camera),
+            ))
+            .id();
+
+        ui_schedule.run(&mut world);
+
+        assert_eq!(
+            world.get::<Node>(root).unwrap().size(),
+            Vec2::new(300., 200.)
+        );
+
+        // resizing the target image
+        world
+            .resource_mut::<Assets<Image>>()
+            .get_mut(&image)
+            .unwrap()
+            .resize(image_size(120, 80));
+        world.send_event(AssetEvent::Modified { id: image.id() });
+
+        ui_schedule.run(&mut world);
+
+        assert_eq!(
+            world.get::<Node>(root).unwrap().size(),
+            Vec2::new(120., 80.)
+        );
+    }
+
+    #[test]
+    fn outline_viewport_units_resolve_against_the_target_camera_viewport() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+        ui_schedule.add_systems(crate::resolve_outlines_system.after(ui_layout_system));
+
+        let nodes = [(0, 500, 1), (500, 200, 2)].map(|(x, width, order)| {
+            let camera = world
+                .spawn(Camera2dBundle {
+                    camera: Camera {
+                        viewport: Some(Viewport {
+                            physical_position: UVec2::new(x, 0),
+                            physical_size: UVec2::new(width, WINDOW_HEIGHT as u32),
+                            ..default()
+                        }),
+                        order,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .id();
+            world
+                .spawn((
+                    NodeBundle::default(),
+                    Outline::new(Val::Vw(10.), Val::Vh(10.), Color::WHITE),
+                    TargetCamera(camera),
+                ))
+                .id()
+        });
+
+        ui_schedule.run(&mut world);
+
+        let outlines = nodes.map(|node| {
+            let node = world.get::<Node>(node).unwrap();
+            (node.outline_width, node.outline_offset)
+        });
+        assert_eq!(outlines, [(50., 10.), (20., 10.)]);
+    }
+
+    #[test]
+    fn push_outlines_take_up_space_in_the_layout() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let square = || NodeBundle {
+            style: Style {
+                width: Val::Px(100.),
+                height: Val::Px(100.),
+                ..default()
+            },
+            ..default()
+        };
+        let outlined = world
+            .spawn((
+                square(),
+                Outline::new(Val::Px(4.), Val::Px(6.), Color::WHITE).with_mode(OutlineMode::Push),
+            ))
+            .id();
+        let neighbor = world.spawn(square()).id();
+        world
+            .spawn(NodeBundle::default())
+            .push_children(&[outlined, neighbor]);
+
+        let location = |world: &World, entity| {
+            let layout = world.resource::<UiSurface>().get_layout(entity).unwrap();
+            Vec2::new(layout.location.x, layout.location.y)
+        };
+
+        ui_schedule.run(&mut world);
+        assert_eq!(location(&world, outlined), Vec2::new(10., 10.));
+        assert_eq!(location(&world, neighbor), Vec2::new(120., 0.));
+
+        // Changing the color doesn't affect the layout
+        world.get_mut::<Outline>(outlined).unwrap().color = Color::BLACK;
+        ui_schedule.run(&mut world);
+        assert_eq!(location(&world, neighbor), Vec2::new(120., 0.));
+
+        world.get_mut::<Outline>(outlined).unwrap().mode = OutlineMode::Overlay;
+        ui_schedule.run(&mut world);
+        assert_eq!(location(&world, outlined), Vec2::ZERO);
+        assert_eq!(location(&world, neighbor), Vec2::new(100., 0.));
+    }
+
+    #[test]
+    fn ui_root_scale_scales_only_its_own_root() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let square = || NodeBundle {
+            style: Style {
+                width: Val::Px(100.),
+                height: Val::Px(100.),
+                ..default()
+            },
+            ..default()
+        };
+        let scaled_child = world.spawn(square()).id();
+        let scaled_root = world
+            .spawn((NodeBundle::default(), UiRootScale(2.)))
+            .add_child(scaled_child)
+            .id();
+        let child = world.spawn(square()).id();
+        world.spawn(NodeBundle::default()).add_child(child);
+
+        ui_schedule.run(&mut world);
+        assert_eq!(
+            world.get::<Node>(scaled_child).unwrap().size(),
+            Vec2::splat(200.)
+        );
+        assert_eq!(world.get::<Node>(scaled_child).unwrap().root_scale(), 2.);
+        assert_eq!(world.get::<Node>(child).unwrap().size(), Vec2::splat(100.));
+
+        // Animating the scale updates the layout of the root
+        world.get_mut::<UiRootScale>(scaled_root).unwrap().0 = 0.5;
+        ui_schedule.run(&mut world);
+        assert_eq!(
+            world.get::<Node>(scaled_child).unwrap().size(),
+            Vec2::splat(50.)
+        );
+        assert_eq!(world.get::<Node>(child).unwrap().size(), Vec2::splat(100.));
+    }
+
     #[test]
     #[should_panic]
     fn despawning_a_ui_entity_should_remove_its_corresponding_ui_node() {
@@ -924,6 +1659,293 @@ mod tests {
         assert_eq!(layout.size.height, content_size.y);
     }
 
+    #[test]
+    fn scroll_position_should_offset_children_and_be_clamped() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let scroll_container = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Px(100.),
+                        height: Val::Px(50.),
+                        overflow: Overflow::clip_y(),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ScrollPosition::new(0., 30.),
+            ))
+            .id();
+
+        let content = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(200.),
+                    flex_shrink: 0.,
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+
+        world.entity_mut(scroll_container).add_child(content);
+
+        ui_schedule.run(&mut world);
+
+        let content_translation = |world: &World| {
+            world
+                .get::<GlobalTransform>(content)
+                .unwrap()
+                .translation()
+                .truncate()
+        };
+
+        // the content is moved up by the scroll offset
+        assert_eq!(content_translation(&world), Vec2::new(50., 70.));
+        assert_eq!(
+            world.get::<Node>(scroll_container).unwrap().content_size(),
+            Vec2::new(100., 200.)
+        );
+
+        // scroll past the end of the content
+        world
+            .get_mut::<ScrollPosition>(scroll_container)
+            .unwrap()
+            .offset_y = 1000.;
+
+        ui_schedule.run(&mut world);
+
+        // the offset is clamped so that the bottom of the content is aligned with the bottom of the container
+        assert_eq!(
+            *world.get::<ScrollPosition>(scroll_container).unwrap(),
+            ScrollPosition::new(0., 150.)
+        );
+        assert_eq!(content_translation(&world), Vec2::new(50., -50.));
+    }
+
+    #[test]
+    fn percent_rounding_policies() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let parent = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        let columns: Vec<Entity> = (0..3)
+            .map(|_| {
+                world
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100. / 3.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .id()
+            })
+            .collect();
+        world.entity_mut(parent).push_children(&columns);
+
+        let mut column_widths = |world: &mut World, percent_rounding: PercentRounding| {
+            *world.resource_mut::<PercentRounding>() = percent_rounding;
+            ui_schedule.run(world);
+            let rects: Vec<Rect> = columns
+                .iter()
+                .map(|&column| {
+                    let node = world.get::<Node>(column).unwrap();
+                    let transform = world.get::<GlobalTransform>(column).unwrap();
+                    node.logical_rect(transform)
+                })
+                .collect();
+            // the columns are adjacent
+            for pair in rects.windows(2) {
+                assert_eq!(pair[0].max.x, pair[1].min.x);
+            }
+            rects.iter().map(Rect::width).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            column_widths(&mut world, PercentRounding::None),
+            [33., 34., 33.]
+        );
+        assert_eq!(
+            column_widths(&mut world, PercentRounding::Round),
+            [33., 33., 33.]
+        );
+        assert_eq!(
+            column_widths(&mut world, PercentRounding::FloorDistribute),
+            [34., 33., 33.]
+        );
+    }
+
+    #[test]
+    fn layout_rounding_modes() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let parent = world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(100.),
+                    height: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        let columns: Vec<Entity> = (0..3)
+            .map(|_| {
+                world
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100. / 3.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .id()
+            })
+            .collect();
+        world.entity_mut(parent).push_children(&columns);
+
+        let mut column_widths = |world: &mut World| {
+            ui_schedule.run(world);
+            columns
+                .iter()
+                .map(|&column| world.get::<Node>(column).unwrap().size().x)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(column_widths(&mut world), [33., 34., 33.]);
+
+        world.resource_mut::<UiLayoutSettings>().rounding = LayoutRounding::Floor;
+        assert_eq!(column_widths(&mut world), [33., 33., 34.]);
+
+        world.resource_mut::<UiLayoutSettings>().rounding = LayoutRounding::None;
+        for width in column_widths(&mut world) {
+            assert!((width - 100. / 3.).abs() < 0.001);
+        }
+
+        // The component on the parent overrides the settings for its descendants
+        world.entity_mut(parent).insert(LayoutRounding::Floor);
+        assert_eq!(column_widths(&mut world), [33., 33., 34.]);
+    }
+
+    #[test]
+    fn grid_template_areas() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let grid = world
+            .spawn(NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    width: Val::Px(200.),
+                    height: Val::Px(100.),
+                    grid_template_columns: RepeatedGridTrack::flex(2, 1.),
+                    grid_template_rows: RepeatedGridTrack::flex(2, 1.),
+                    grid_template_areas: vec!["header header".into(), "sidebar main".into()],
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        let area_node = |area: &str| NodeBundle {
+            style: Style {
+                grid_area: Some(area.to_string()),
+                ..default()
+            },
+            ..default()
+        };
+        let main = world.spawn(area_node("main")).id();
+        let header = world.spawn(area_node("header")).id();
+        world.entity_mut(grid).push_children(&[main, header]);
+
+        let mut geometry = |world: &mut World, entity: Entity| {
+            ui_schedule.run(world);
+            let size = world.get::<Node>(entity).unwrap().size();
+            let translation = world
+                .get::<Transform>(entity)
+                .unwrap()
+                .translation
+                .truncate();
+            (size, translation)
+        };
+
+        assert_eq!(
+            geometry(&mut world, header),
+            (Vec2::new(200., 50.), Vec2::new(0., -25.))
+        );
+        assert_eq!(
+            geometry(&mut world, main),
+            (Vec2::new(100., 50.), Vec2::new(50., 25.))
+        );
+
+        // Changing the parent's template moves the children
+        world.get_mut::<Style>(grid).unwrap().grid_template_areas =
+            vec!["main header".into(), "main header".into()];
+        assert_eq!(
+            geometry(&mut world, main),
+            (Vec2::new(100., 100.), Vec2::new(-50., 0.))
+        );
+    }
+
+    #[test]
+    fn layout_stats_and_tree_string() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let parent = world.spawn(NodeBundle::default()).id();
+        let child = world.spawn(NodeBundle::default()).id();
+        world.entity_mut(parent).add_child(child);
+
+        ui_schedule.run(&mut world);
+        let ui_surface = world.resource::<UiSurface>();
+        let stats = ui_surface.stats();
+        assert_eq!(stats.relayouts, 1);
+        assert_eq!(stats.dirty_nodes, 2);
+        assert_eq!(stats.measure_calls, 0);
+
+        let tree = crate::debug::layout_tree_string(ui_surface);
+        assert!(tree.contains(&format!("{parent:?}")));
+        assert!(tree.contains(&format!("{child:?}")));
+
+        // Unchanged nodes aren't updated again
+        ui_schedule.run(&mut world);
+        let stats = world.resource::<UiSurface>().stats();
+        assert_eq!(stats.relayouts, 1);
+        assert_eq!(stats.dirty_nodes, 0);
+    }
+
+    #[test]
+    fn ui_surface_lays_out_a_fixed_size_viewport_without_a_window() {
+        let mut ui_surface = UiSurface::default();
+        let viewport = Entity::from_raw(0);
+        let root = Entity::from_raw(1);
+        let context = super::LayoutContext::new(2., Vec2::new(400., 200.), 16.);
+        let style = Style {
+            width: Val::Percent(50.),
+            height: Val::Px(20.),
+            ..Default::default()
+        };
+
+        ui_surface.upsert_node(&context, root, &style, None);
+        ui_surface.set_camera_children(viewport, [root].into_iter());
+        ui_surface.compute_camera_layout(viewport, UVec2::new(400, 200));
+
+        assert_eq!(ui_surface.cameras().collect::<Vec<_>>(), vec![viewport]);
+        assert_eq!(ui_surface.camera_root_nodes(viewport), vec![root]);
+        assert_eq!(ui_surface.root_node_camera(root), Some(viewport));
+        let layout = ui_surface.get_layout(root).unwrap();
+        assert_eq!(layout.size.width, 200.);
+        assert_eq!(layout.size.height, 40.);
+    }
+
     #[test]
     fn measure_funcs_should_be_removed_on_content_size_removal() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
@@ -967,6 +1989,47 @@ mod tests {
         assert_eq!(layout.size.height, 0.);
     }
 
+    #[test]
+    fn measure_fn_sizes_its_node() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        // Takes up a quarter of the available width, as a square
+        let quarter_square = |width: Option<f32>, height: Option<f32>, available_width, _| {
+            let side = match available_width {
+                AvailableSpace::Definite(available) => available / 4.,
+                _ => 0.,
+            };
+            Vec2::new(width.unwrap_or(side), height.unwrap_or(side))
+        };
+        let ui_entity = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_self: AlignSelf::Start,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ContentSize::from_measure_fn(quarter_square),
+            ))
+            .id();
+
+        ui_schedule.run(&mut world);
+        let layout = world.resource::<UiSurface>().get_layout(ui_entity).unwrap();
+        assert_eq!(layout.size.width, WINDOW_WIDTH / 4.);
+        assert_eq!(layout.size.height, WINDOW_WIDTH / 4.);
+
+        // Setting a new measure relayouts the node
+        world
+            .get_mut::<ContentSize>(ui_entity)
+            .unwrap()
+            .set_measure_fn(|_, _, _, _| Vec2::new(10., 20.));
+        ui_schedule.run(&mut world);
+        let layout = world.resource::<UiSurface>().get_layout(ui_entity).unwrap();
+        assert_eq!(layout.size.width, 10.);
+        assert_eq!(layout.size.height, 20.);
+    }
+
     #[test]
     fn ui_rounding_test() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
@@ -1025,6 +2088,9 @@ mod tests {
     fn no_camera_ui() {
         let mut world = World::new();
         world.init_resource::<UiScale>();
+        world.init_resource::<RootFontSize>();
+        world.init_resource::<PercentRounding>();
+        world.init_resource::<UiLayoutSettings>();
         world.init_resource::<UiSurface>();
         world.init_resource::<Events<WindowScaleFactorChanged>>();
         world.init_resource::<Events<WindowResized>>();
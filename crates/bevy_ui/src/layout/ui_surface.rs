@@ -5,12 +5,15 @@ use taffy::TaffyTree;
 use bevy_ecs::entity::{Entity, EntityHashMap};
 use bevy_ecs::prelude::Resource;
 use bevy_hierarchy::Children;
-use bevy_math::UVec2;
+use bevy_math::{UVec2, Vec2};
 use bevy_utils::default;
 use bevy_utils::tracing::warn;
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::{field, info_span};
+use bevy_utils::Instant;
 
 use crate::layout::convert;
-use crate::{LayoutContext, LayoutError, Measure, NodeMeasure, Style};
+use crate::{LayoutContext, LayoutError, Measure, NodeMeasure, Style, UiLayoutStats};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RootNodePair {
@@ -20,12 +23,23 @@ pub struct RootNodePair {
     pub(super) user_root_node: taffy::NodeId,
 }
 
+/// The layout tree of the UI, maintained by [`ui_layout_system`](crate::ui_layout_system).
+///
+/// Each UI node entity has a node in the tree, and the root nodes of each camera are laid out in a viewport with
+/// the size of the camera's render target. The computed layout of a node can be read with
+/// [`UiSurface::get_layout`].
+///
+/// The viewports are keyed by an entity that doesn't need to be a camera, so a `UiSurface` can also be used
+/// directly to lay out nodes in a viewport with a fixed size: insert the nodes with [`UiSurface::upsert_node`] and
+/// a [`LayoutContext::new`], add the roots to the viewport with [`UiSurface::set_camera_children`], and compute
+/// their layout with [`UiSurface::compute_camera_layout`].
 #[derive(Resource)]
 pub struct UiSurface {
     pub(super) entity_to_taffy: EntityHashMap<taffy::NodeId>,
     pub(super) camera_entity_to_taffy: EntityHashMap<EntityHashMap<taffy::NodeId>>,
     pub(super) camera_roots: EntityHashMap<Vec<RootNodePair>>,
     pub(super) taffy: TaffyTree<NodeMeasure>,
+    pub(super) stats: UiLayoutStats,
 }
 
 fn _assert_send_sync_ui_surface_impl_safe() {
@@ -40,6 +54,7 @@ impl fmt::Debug for UiSurface {
         f.debug_struct("UiSurface")
             .field("entity_to_taffy", &self.entity_to_taffy)
             .field("camera_roots", &self.camera_roots)
+            .field("stats", &self.stats)
             .finish()
     }
 }
@@ -53,6 +68,7 @@ impl Default for UiSurface {
             camera_entity_to_taffy: Default::default(),
             camera_roots: Default::default(),
             taffy,
+            stats: default(),
         }
     }
 }
@@ -67,6 +83,7 @@ impl UiSurface {
         style: &Style,
         mut new_node_context: Option<NodeMeasure>,
     ) {
+        self.stats.dirty_nodes += 1;
         let taffy = &mut self.taffy;
 
         let mut added = false;
@@ -108,6 +125,7 @@ impl UiSurface {
     /// Update the `MeasureFunc` of the taffy node corresponding to the given [`Entity`] if the node exists.
     pub fn update_node_context(&mut self, entity: Entity, context: NodeMeasure) -> Option<()> {
         let taffy_node = self.entity_to_taffy.get(&entity)?;
+        self.stats.dirty_nodes += 1;
         self.taffy.set_node_context(*taffy_node, Some(context)).ok()
     }
 
@@ -146,6 +164,8 @@ without UI components as a child of an entity with UI components, results may be
     }
 
     /// Set the ui node entities without a [`bevy_hierarchy::Parent`] as children to the root node in the taffy layout.
+    ///
+    /// `camera_id` identifies the viewport the nodes are laid out in, it doesn't need to be a camera entity.
     pub fn set_camera_children(
         &mut self,
         camera_id: Entity,
@@ -195,12 +215,23 @@ without UI components as a child of an entity with UI components, results may be
         self.camera_roots.insert(camera_id, new_roots);
     }
 
-    /// Compute the layout for each window entity's corresponding root node in the layout.
+    /// Compute the layout of the root nodes of the viewport of `camera`, with the given physical size.
     pub fn compute_camera_layout(&mut self, camera: Entity, render_target_resolution: UVec2) {
         let Some(camera_root_nodes) = self.camera_roots.get(&camera) else {
             return;
         };
 
+        #[cfg(feature = "trace")]
+        let span = info_span!(
+            "compute_camera_layout",
+            ?camera,
+            nodes = self.taffy.total_node_count(),
+            measure_calls = field::Empty,
+        )
+        .entered();
+
+        let start = Instant::now();
+        let mut measure_calls = 0;
         let available_space = taffy::geometry::Size {
             width: taffy::style::AvailableSpace::Definite(render_target_resolution.x as f32),
             height: taffy::style::AvailableSpace::Definite(render_target_resolution.y as f32),
@@ -218,6 +249,9 @@ without UI components as a child of an entity with UI components, results may be
                      -> taffy::Size<f32> {
                         context
                             .map(|ctx| {
+                                #[cfg(feature = "trace")]
+                                let _span = info_span!("measure", node = ?_node_id).entered();
+                                measure_calls += 1;
                                 let size = ctx.measure(
                                     known_dimensions.width,
                                     known_dimensions.height,
@@ -235,6 +269,18 @@ without UI components as a child of an entity with UI components, results may be
                 )
                 .unwrap();
         }
+        self.stats.relayouts += 1;
+        self.stats.measure_calls += measure_calls;
+        self.stats.layout_time += start.elapsed();
+
+        #[cfg(feature = "trace")]
+        span.record("measure_calls", measure_calls);
+    }
+
+    /// Statistics about the work done by the layout since the start of the most recent run of
+    /// [`ui_layout_system`](crate::ui_layout_system).
+    pub fn stats(&self) -> UiLayoutStats {
+        self.stats
     }
 
     /// Removes each camera entity from the internal map and then removes their associated node from taffy
@@ -259,6 +305,52 @@ without UI components as a child of an entity with UI components, results may be
 
     /// Get the layout geometry for the taffy node corresponding to the ui node [`Entity`].
     /// Does not compute the layout geometry, `compute_window_layouts` should be run before using this function.
+    /// Returns the size of the content box of the given [`Entity`]'s node in its last computed layout, in
+    /// physical pixels.
+    #[cfg_attr(not(feature = "render"), allow(dead_code))]
+    pub(crate) fn content_box_size(&self, entity: Entity) -> Option<Vec2> {
+        let taffy_node = self.entity_to_taffy.get(&entity)?;
+        let layout = self.taffy.layout(*taffy_node).ok()?;
+        let (padding, border) = (layout.padding, layout.border);
+        let size = Vec2::new(
+            layout.size.width - padding.left - padding.right - border.left - border.right,
+            layout.size.height - padding.top - padding.bottom - border.top - border.bottom,
+        );
+        Some(size.max(Vec2::ZERO))
+    }
+
+    /// Returns the entities of the viewports that root nodes were set for with [`UiSurface::set_camera_children`].
+    pub fn cameras(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.camera_roots.keys().copied()
+    }
+
+    /// Returns the root node entities laid out in the viewport of `camera`, in the order they were set.
+    pub fn camera_root_nodes(&self, camera: Entity) -> Vec<Entity> {
+        let Some(roots) = self.camera_roots.get(&camera) else {
+            return Vec::new();
+        };
+        roots
+            .iter()
+            .filter_map(|root| {
+                self.entity_to_taffy
+                    .iter()
+                    .find(|(_, node)| **node == root.user_root_node)
+                    .map(|(entity, _)| *entity)
+            })
+            .collect()
+    }
+
+    /// Returns the viewport that the root node `entity` is laid out in, if it is a root node.
+    pub fn root_node_camera(&self, entity: Entity) -> Option<Entity> {
+        let node = self.entity_to_taffy.get(&entity)?;
+        self.camera_roots
+            .iter()
+            .find(|(_, roots)| roots.iter().any(|root| root.user_root_node == *node))
+            .map(|(camera, _)| *camera)
+    }
+
+    /// Returns the computed layout of the node of `entity`, with its location relative to its parent, in physical
+    /// pixels.
     pub fn get_layout(&self, entity: Entity) -> Result<&taffy::Layout, LayoutError> {
         if let Some(taffy_node) = self.entity_to_taffy.get(&entity) {
             self.taffy
@@ -10,58 +10,140 @@
 //! # Basic usage
 //! Spawn UI elements with [`node_bundles::ButtonBundle`], [`node_bundles::ImageBundle`], [`node_bundles::TextBundle`] and [`node_bundles::NodeBundle`]
 //! This UI is laid out with the Flexbox and CSS Grid layout models (see <https://cssreference.io/flexbox/>)
+//!
+//! # Headless layout
+//! UI nodes are laid out in the viewports of cameras, interacted with and drawn by the `render` feature, which is
+//! enabled by default. Without it, `bevy_ui` doesn't depend on `bevy_render`: [`UiPlugin`] still lays out, stacks
+//! and clips UI nodes, and updates their focus and keyboard navigation, in the primary window (see [`UiViewports`]),
+//! but doesn't draw them. Servers and automated tests can use it, or the helpers in [`testing`], to compute UI
+//! layouts without the renderer.
 
+#[cfg(feature = "bevy_audio")]
+pub mod audio;
+#[cfg(feature = "render")]
+pub mod builder;
+#[cfg(feature = "debug_ui")]
+pub mod debug_ui;
 pub mod measurement;
+#[cfg(feature = "render")]
 pub mod node_bundles;
+#[cfg(feature = "pseudo_locale")]
+pub mod pseudo_locale;
+#[cfg(feature = "reflect_inspector")]
+pub mod reflect_inspector;
+pub mod testing;
+#[cfg(feature = "render")]
 pub mod ui_material;
 pub mod update;
+#[cfg(feature = "render")]
 pub mod widget;
 
 use bevy_derive::{Deref, DerefMut};
-use bevy_reflect::Reflect;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 #[cfg(feature = "bevy_text")]
 mod accessibility;
+#[cfg(feature = "render")]
+mod clipboard;
+#[cfg(feature = "render")]
+mod drag_drop;
 mod focus;
+mod focus_navigation;
+#[cfg(feature = "render")]
+mod gamepad_cursor;
 mod geometry;
+#[cfg(feature = "render")]
+mod image_atlas;
 mod layout;
+#[cfg(feature = "render")]
+mod painter;
+#[cfg(feature = "render")]
+mod picking;
+#[cfg(feature = "render")]
 mod render;
 mod stack;
+mod style_sheet;
+#[cfg(feature = "render")]
 mod texture_slice;
+#[cfg(feature = "render")]
+mod touch;
+mod transition;
 mod ui_node;
+#[cfg(feature = "render")]
+mod viewport_ui;
+mod viewports;
 
+#[cfg(feature = "render")]
+pub use clipboard::*;
+#[cfg(feature = "render")]
+pub use drag_drop::*;
 pub use focus::*;
+pub use focus_navigation::*;
+#[cfg(feature = "render")]
+pub use gamepad_cursor::*;
 pub use geometry::*;
+#[cfg(feature = "render")]
+pub use image_atlas::*;
 pub use layout::*;
 pub use measurement::*;
+#[cfg(feature = "render")]
+pub use painter::*;
+#[cfg(feature = "render")]
+pub use picking::*;
+#[cfg(feature = "render")]
 pub use render::*;
+pub use style_sheet::*;
+#[cfg(feature = "render")]
+pub use touch::*;
+pub use transition::*;
+#[cfg(feature = "render")]
 pub use ui_material::*;
 pub use ui_node::*;
+#[cfg(feature = "render")]
+pub use viewport_ui::*;
+pub use viewports::{UiViewport, UiViewports};
+#[cfg(feature = "render")]
 use widget::UiImageSize;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        geometry::*, node_bundles::*, ui_material::*, ui_node::*, widget::Button, widget::Label,
-        Interaction, UiMaterialPlugin, UiScale,
+        geometry::*, ui_node::*, Disabled, FocusActivated, FocusState, Focusable, Interaction,
+        RootFontSize, UiKeyboardCapture, UiScale,
+    };
+    #[doc(hidden)]
+    #[cfg(feature = "render")]
+    pub use crate::{
+        node_bundles::*, ui_material::*, widget::Button, widget::ButtonPressed,
+        widget::ButtonStyle, widget::ButtonVisuals, widget::Label, Draggable, DropTarget,
+        UiMaterialPlugin,
     };
     // `bevy_sprite` re-exports for texture slicing
     #[doc(hidden)]
-    pub use bevy_sprite::{BorderRect, ImageScaleMode, SliceScaleMode, TextureSlicer};
+    #[cfg(feature = "render")]
+    pub use bevy_sprite::{ImageScaleMode, SliceScaleMode, TextureSlicer};
 }
 
 use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
 use bevy_ecs::prelude::*;
 use bevy_input::InputSystem;
+#[cfg(feature = "render")]
 use bevy_render::{
+    camera::CameraUpdateSystem,
     view::{check_visibility, VisibilitySystems},
     RenderApp,
 };
 use bevy_transform::TransformSystem;
-use layout::ui_surface::UiSurface;
+use bevy_utils::Duration;
 use stack::ui_stack_system;
-pub use stack::UiStack;
-use update::{update_clipping_system, update_target_camera_system};
+pub use stack::{UiStack, UiStacks};
+#[cfg(feature = "render")]
+use update::hide_ui_root_layers_system;
+use update::{
+    update_clipping_system, update_target_camera_system, update_ui_depth_system,
+    update_ui_layer_system, update_ui_root_layer_system, update_ui_root_scale_system,
+};
 
 /// The basic plugin for Bevy UI
 #[derive(Default)]
@@ -70,10 +152,18 @@ pub struct UiPlugin;
 /// The label enum labeling the types of systems in the Bevy UI
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum UiSystem {
+    /// The [`ContentSize`] measures of nodes are updated in this set, which runs before [`UiSystem::Layout`].
+    ///
+    /// Systems that set the measures of their own widgets with [`ContentSize::set`] or
+    /// [`ContentSize::set_measure_fn`] should be added to this set, so the layout is updated in the same frame.
+    Measure,
     /// After this label, the ui layout state has been updated
     Layout,
     /// After this label, input interactions with UI entities have been updated for this frame
     Focus,
+    /// Clipboard reads are started and finished reads are sent as [`PasteIntoFocused`] events in this set, which
+    /// runs after [`UiSystem::Focus`]
+    Paste,
     /// After this label, the [`UiStack`] resource has been updated
     Stack,
     /// After this label, node outline widths have been updated
@@ -93,94 +183,136 @@ impl Default for UiScale {
     }
 }
 
+/// The font size that [`Val::Rem`] values are multiples of, in logical pixels.
+///
+/// Sizing a UI with `Rem` values allows all of it to be scaled consistently by changing this one value, like the
+/// font size of the root element in CSS.
+#[derive(Debug, Reflect, Resource, Deref, DerefMut)]
+pub struct RootFontSize(pub f32);
+
+impl Default for RootFontSize {
+    fn default() -> Self {
+        Self(16.0)
+    }
+}
+
+/// How the sizes of nodes with [`Val::Percent`] widths or heights along the main axis of their flex container
+/// are rounded.
+///
+/// Percentages of a parent size often resolve to fractional values, like three `33.33%` columns in a `100px` wide
+/// parent. By default the edges of each node are rounded independently, so the widths of such nodes depend on their
+/// position and may differ from one another by a pixel.
+///
+/// Only the children of [`Display::Flex`] containers are rounded. The children of [`Display::Grid`] and
+/// [`Display::Block`] containers keep their unrounded sizes, and a warning is logged if they are percentage sized.
+///
+/// The rounding is applied to the computed layout, without laying it out again. The in-flow siblings after a
+/// rounded node are moved along the main axis by the change in its size, but flex lines don't wrap differently,
+/// the sizes and alignment of the nodes on the cross axis don't change, and the descendants of a rounded node keep
+/// the layout computed for its unrounded size.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Resource)]
+pub enum PercentRounding {
+    /// Only the edges of nodes are rounded.
+    #[default]
+    None,
+    /// The size of each percentage sized node is rounded to the nearest integer.
+    ///
+    /// Nodes with equal percentages have equal sizes, but they may not exactly fill their parent.
+    Round,
+    /// The size of each percentage sized node is rounded down, then the pixels lost to rounding are given back one
+    /// at a time to the siblings with the largest fractional parts, earlier siblings first on ties.
+    ///
+    /// Percentage sized siblings that together span a whole number of pixels exactly fill that span.
+    FloorDistribute,
+}
+
+/// How the edges of UI nodes computed by the layout are snapped to pixels.
+///
+/// Used by [`UiLayoutSettings`] for the whole UI, and as a component to override the rounding of a node and its
+/// descendants.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum LayoutRounding {
+    /// Edges are rounded to the nearest whole pixel, with ties rounded up.
+    #[default]
+    Round,
+    /// Edges are rounded down to whole pixels.
+    ///
+    /// Unlike rounding to the nearest pixel, the result never depends on how ties are broken, so pixel-art UIs
+    /// that must stay aligned to texels can use this for deterministic placement.
+    Floor,
+    /// Edges are not rounded, so nodes can have fractional positions and sizes.
+    ///
+    /// Nodes move and resize smoothly, for example during animations or on displays with fractional scale factors,
+    /// but their edges may be blurred by antialiasing.
+    None,
+}
+
+/// Settings for the layout of the UI.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Resource)]
+pub struct UiLayoutSettings {
+    /// How the edges of nodes are rounded, unless overridden by a [`LayoutRounding`] component on the node or
+    /// one of its ancestors.
+    pub rounding: LayoutRounding,
+    /// If set, [`ui_layout_system`] logs a warning with the [`UiLayoutStats`] of each frame in which it takes
+    /// longer than this to update the layout, to help find nodes that are expensive to lay out, like text that
+    /// is measured many times.
+    ///
+    /// With the `trace` feature, the layout, the calls to measure functions and the text systems are also
+    /// instrumented with tracing spans.
+    pub layout_budget: Option<Duration>,
+}
+
 // Marks systems that can be ambiguous with [`widget::text_system`] if the `bevy_text` feature is enabled.
 // See https://github.com/bevyengine/bevy/pull/11391 for more details.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 struct AmbiguousWithTextSystem;
 
+#[cfg(feature = "render")]
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 struct AmbiguousWithUpdateText2DLayout;
 
 /// A convenient alias for `With<Node>`, for use with
 /// [`bevy_render::view::VisibleEntities`].
+#[cfg(feature = "render")]
 pub type WithNode = With<Node>;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
+        register_types(app);
+
         app.init_resource::<UiSurface>()
             .init_resource::<UiScale>()
-            .init_resource::<UiStack>()
-            .register_type::<BackgroundColor>()
-            .register_type::<CalculatedClip>()
-            .register_type::<ContentSize>()
-            .register_type::<FocusPolicy>()
-            .register_type::<Interaction>()
-            .register_type::<Node>()
-            .register_type::<RelativeCursorPosition>()
-            .register_type::<Style>()
-            .register_type::<TargetCamera>()
-            .register_type::<UiImage>()
-            .register_type::<UiImageSize>()
-            .register_type::<UiRect>()
-            .register_type::<UiScale>()
-            .register_type::<BorderColor>()
-            .register_type::<BorderRadius>()
-            .register_type::<widget::Button>()
-            .register_type::<widget::Label>()
-            .register_type::<ZIndex>()
-            .register_type::<Outline>()
-            .add_systems(
-                PreUpdate,
-                ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
-            );
+            .init_resource::<RootFontSize>()
+            .init_resource::<PercentRounding>()
+            .init_resource::<UiLayoutSettings>()
+            .init_asset::<UiStyleSheet>()
+            .init_asset_loader::<UiStyleSheetLoader>();
+
+        app.configure_sets(PostUpdate, UiSystem::Measure.before(UiSystem::Layout));
 
         app.add_systems(
             PostUpdate,
             (
-                check_visibility::<WithNode>.in_set(VisibilitySystems::CheckVisibility),
-                update_target_camera_system.before(UiSystem::Layout),
-                apply_deferred
-                    .after(update_target_camera_system)
-                    .before(UiSystem::Layout),
-                ui_layout_system
-                    .in_set(UiSystem::Layout)
-                    .before(TransformSystem::TransformPropagate),
-                resolve_outlines_system
-                    .in_set(UiSystem::Outlines)
-                    .after(UiSystem::Layout)
-                    // clipping doesn't care about outlines
-                    .ambiguous_with(update_clipping_system)
-                    .in_set(AmbiguousWithTextSystem),
-                ui_stack_system
-                    .in_set(UiSystem::Stack)
-                    // the systems don't care about stack index
-                    .ambiguous_with(update_clipping_system)
-                    .ambiguous_with(resolve_outlines_system)
-                    .ambiguous_with(ui_layout_system)
-                    .in_set(AmbiguousWithTextSystem),
-                update_clipping_system.after(TransformSystem::TransformPropagate),
-                // Potential conflicts: `Assets<Image>`
-                // They run independently since `widget::image_node_system` will only ever observe
-                // its own UiImage, and `widget::text_system` & `bevy_text::update_text2d_layout`
-                // will never modify a pre-existing `Image` asset.
-                widget::update_image_content_size_system
-                    .before(UiSystem::Layout)
-                    .in_set(AmbiguousWithTextSystem)
-                    .in_set(AmbiguousWithUpdateText2DLayout),
-                (
-                    texture_slice::compute_slices_on_asset_event,
-                    texture_slice::compute_slices_on_image_change,
-                )
-                    .after(UiSystem::Layout),
+                // Style sheets are applied before transitions, so class changes can be animated
+                apply_style_sheets.before(ui_transition_system),
+                ui_transition_system.before(UiSystem::Layout),
             ),
         );
 
+        build_layout_systems(app);
+
+        #[cfg(feature = "render")]
+        build_camera_systems(app);
+
         #[cfg(feature = "bevy_text")]
         build_text_interop(app);
 
+        #[cfg(feature = "render")]
         build_ui_render(app);
     }
 
+    #[cfg(feature = "render")]
     fn finish(&self, app: &mut App) {
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -190,6 +322,279 @@ impl Plugin for UiPlugin {
     }
 }
 
+/// Registers the reflected UI types, so UIs can be saved to and loaded from scenes.
+fn register_types(app: &mut App) {
+    app.register_type::<BackgroundColor>()
+        .register_type::<CalculatedClip>()
+        .register_type::<ContentSize>()
+        .register_type::<Node>()
+        .register_type::<ScrollPosition>()
+        .register_type::<Style>()
+        .register_type::<TargetCamera>()
+        .register_type::<Class>()
+        .register_type::<StyleSheetRef>()
+        .register_type::<ImageRepeat>()
+        .register_type::<ObjectFit>()
+        .register_type::<UiTransition>()
+        .register_type::<EaseFunction>()
+        .register_type::<UiLayer>()
+        .register_type::<UiRootLayer>()
+        .register_type::<UiRootScale>()
+        .register_type::<UiDepth>()
+        .register_type::<UiCameraConfig>()
+        .register_type::<UiRect>()
+        .register_type::<UiScale>()
+        .register_type::<RootFontSize>()
+        .register_type::<PercentRounding>()
+        .register_type::<LayoutRounding>()
+        .register_type::<UiLayoutSettings>()
+        .register_type::<BorderColor>()
+        .register_type::<BorderRadius>()
+        .register_type::<ClipShape>()
+        .register_type::<ZIndex>()
+        .register_type::<Outline>()
+        .register_type::<OutlineMode>()
+        .register_type::<FocusPolicy>()
+        .register_type::<Disabled>()
+        .register_type::<UiInteractionEvent>()
+        .register_type::<UiInteractionKind>()
+        .register_type::<HitTestMode>()
+        .register_type::<UiDragSettings>()
+        .register_type::<Focusable>()
+        .register_type::<FocusState>()
+        .register_type::<UiKeyboardCapture>()
+        .register_type::<Interaction>()
+        .register_type::<RelativeCursorPosition>();
+
+    #[cfg(feature = "render")]
+    app.register_type::<UiGestureEvent>()
+        .register_type::<UiGesture>()
+        .register_type::<UiNodeHit>()
+        .register_type::<UiPointer>()
+        .register_type::<Pickable>()
+        .register_type::<Draggable>()
+        .register_type::<DropTarget>()
+        .register_type::<DragGhost>()
+        .register_type::<DragStart>()
+        .register_type::<DragOver>()
+        .register_type::<DragDrop>()
+        .register_type::<UiTouchSettings>()
+        .register_type::<GamepadCursor>()
+        .register_type::<UiDataSource>()
+        .register_type::<ViewportUiRoot>()
+        .register_type::<UiImage>()
+        .register_type::<BorderImage>()
+        .register_type::<UiImageSize>()
+        .register_type::<UiImageAtlasSettings>()
+        .register_type::<widget::Button>()
+        .register_type::<widget::ButtonStyle>()
+        .register_type::<widget::ButtonVisuals>()
+        .register_type::<widget::Slider>()
+        .register_type::<widget::Checkbox>()
+        .register_type::<widget::CheckboxKind>()
+        .register_type::<widget::ProgressBar>()
+        .register_type::<widget::RadioGroup>()
+        .register_type::<widget::RadioButton>()
+        .register_type::<widget::TabView>()
+        .register_type::<widget::Tab>()
+        .register_type::<widget::Scrollbar>()
+        .register_type::<widget::ScrollbarAxis>()
+        .register_type::<widget::Label>()
+        .register_type::<widget::UiAtlasAnimation>()
+        .register_type::<widget::Tooltip>()
+        .register_type::<widget::TooltipContent>()
+        .register_type::<widget::TooltipPlacement>()
+        .register_type::<widget::TooltipNode>();
+
+    #[cfg(feature = "bevy_audio")]
+    app.register_type::<audio::UiSound>()
+        .register_type::<audio::UiSoundOverride>();
+}
+
+/// A function that should be called from [`UiPlugin::build`] to lay out and stack UI nodes in their viewports, and
+/// to update the [`Interaction`]s and navigation focus of the nodes, whether or not the `render` feature is enabled.
+fn build_layout_systems(app: &mut App) {
+    app.init_resource::<UiStack>()
+        .init_resource::<FocusState>()
+        .init_resource::<UiKeyboardCapture>()
+        .init_resource::<UiDragSettings>()
+        .add_event::<FocusActivated>()
+        .add_event::<UiInteractionEvent>()
+        .add_systems(
+            PreUpdate,
+            (
+                ui_focus_system,
+                ui_scroll_system,
+                ui_focus_navigation_system
+                    .after(ui_focus_system)
+                    .after(ui_scroll_system),
+            )
+                .in_set(UiSystem::Focus)
+                .after(InputSystem),
+        );
+
+    app.add_systems(
+        PostUpdate,
+        (
+            update_target_camera_system.before(UiSystem::Layout),
+            update_ui_layer_system.before(UiSystem::Layout),
+            update_ui_root_layer_system.before(UiSystem::Layout),
+            update_ui_depth_system.before(UiSystem::Layout),
+            update_ui_root_scale_system.before(UiSystem::Layout),
+            apply_deferred
+                .after(update_target_camera_system)
+                .before(UiSystem::Layout),
+            ui_layout_system
+                .in_set(UiSystem::Layout)
+                .before(TransformSystem::TransformPropagate),
+            resolve_outlines_system
+                .in_set(UiSystem::Outlines)
+                .after(UiSystem::Layout)
+                // clipping doesn't care about outlines
+                .ambiguous_with(update_clipping_system)
+                .in_set(AmbiguousWithTextSystem),
+            ui_stack_system
+                .in_set(UiSystem::Stack)
+                // the systems don't care about stack index
+                .ambiguous_with(update_clipping_system)
+                .ambiguous_with(resolve_outlines_system)
+                .ambiguous_with(ui_layout_system)
+                .in_set(AmbiguousWithTextSystem),
+            update_clipping_system.after(TransformSystem::TransformPropagate),
+        ),
+    );
+}
+
+/// A function that should be called from [`UiPlugin::build`] when the `render` feature is enabled, to interact
+/// with and update the UI nodes in the viewports of cameras.
+#[cfg(feature = "render")]
+fn build_camera_systems(app: &mut App) {
+    app.init_resource::<UiImageAtlasSettings>()
+        .init_resource::<UiImageAtlases>()
+        .init_resource::<GamepadCursor>()
+        .init_resource::<UiTouchSettings>()
+        .init_resource::<TouchState>()
+        .init_resource::<ClipboardSource>()
+        .add_event::<PasteIntoFocused>()
+        .add_event::<widget::ButtonPressed>()
+        .add_event::<widget::SliderChanged>()
+        .add_event::<widget::CheckboxChanged>()
+        .add_event::<widget::ProgressBarChanged>()
+        .add_event::<widget::SelectionChanged>()
+        .add_event::<UiGestureEvent>()
+        .add_event::<UiNodeHit>()
+        .add_event::<DragStart>()
+        .add_event::<DragOver>()
+        .add_event::<DragDrop>()
+        .add_systems(
+            PreUpdate,
+            (
+                ui_picking_system,
+                ui_touch_system
+                    .after(ui_focus_system)
+                    .after(ui_scroll_system)
+                    .before(ui_focus_navigation_system),
+                ui_drag_drop_system.after(ui_touch_system),
+                (
+                    widget::button_pressed_system,
+                    widget::update_button_style_system,
+                )
+                    .after(ui_focus_navigation_system),
+            )
+                .in_set(UiSystem::Focus)
+                .after(InputSystem),
+        )
+        .add_systems(
+            PreUpdate,
+            gamepad_cursor_system
+                .after(InputSystem)
+                .before(UiSystem::Focus),
+        );
+
+    app.add_systems(
+        PreUpdate,
+        (
+            (
+                widget::spawn_slider_children,
+                widget::slider_drag_system,
+                widget::update_slider_children,
+            )
+                .chain(),
+            (
+                widget::spawn_checkbox_children,
+                widget::checkbox_toggle_system,
+                widget::update_checkbox_children,
+            )
+                .chain(),
+            (
+                widget::spawn_radio_button_children,
+                widget::radio_button_select_system,
+                widget::update_radio_buttons,
+                widget::update_radio_button_children,
+            )
+                .chain(),
+            (widget::tab_select_system, widget::update_tab_views).chain(),
+            (
+                widget::spawn_progress_bar_children,
+                widget::update_progress_bar_children,
+            )
+                .chain(),
+            (
+                widget::spawn_scrollbar_children,
+                widget::scrollbar_drag_system,
+            )
+                .chain(),
+            widget::tooltip_system,
+        )
+            .after(UiSystem::Focus),
+    );
+
+    app.configure_sets(PreUpdate, UiSystem::Paste.after(UiSystem::Focus))
+        // Outlines resolve viewport units against the viewports of cameras
+        .configure_sets(PostUpdate, UiSystem::Outlines.after(CameraUpdateSystem))
+        .add_systems(PreUpdate, clipboard_paste_system.in_set(UiSystem::Paste));
+
+    app.add_systems(
+        PostUpdate,
+        (
+            check_visibility::<WithNode>.in_set(VisibilitySystems::CheckVisibility),
+            update_viewport_ui_system.before(update_target_camera_system),
+            hide_ui_root_layers_system
+                .after(VisibilitySystems::CheckVisibility)
+                .after(update_target_camera_system)
+                .after(update_ui_root_layer_system),
+            // Thumbs are placed for the layout of the previous frame, as scrolling doesn't change it
+            widget::update_scrollbar_thumbs.before(UiSystem::Layout),
+            // The atlas index is animated before the image is measured, as frames may differ in size
+            widget::ui_atlas_animation_system.before(widget::update_image_content_size_system),
+            // Potential conflicts: `Assets<Image>`
+            // They run independently since `widget::image_node_system` will only ever observe
+            // its own UiImage, and `widget::text_system` & `bevy_text::update_text2d_layout`
+            // will never modify a pre-existing `Image` asset.
+            widget::update_image_content_size_system
+                .in_set(UiSystem::Measure)
+                .after(CameraUpdateSystem)
+                // Images are measured at the propagated `UiRootScale`
+                .after(update_ui_root_scale_system)
+                .in_set(AmbiguousWithTextSystem)
+                .in_set(AmbiguousWithUpdateText2DLayout),
+            (
+                texture_slice::compute_slices_on_asset_event,
+                texture_slice::compute_slices_on_image_change,
+            )
+                .after(UiSystem::Layout),
+            // Potential conflicts: `Assets<Image>`
+            // Only the atlas images it creates are modified, which no other system observes.
+            pack_ui_images
+                .after(UiSystem::Layout)
+                .in_set(AmbiguousWithTextSystem)
+                .in_set(AmbiguousWithUpdateText2DLayout),
+        ),
+    );
+
+    app.add_plugins(UiPainterPlugin);
+}
+
 /// A function that should be called from [`UiPlugin::build`] when [`bevy_text`] is enabled.
 #[cfg(feature = "bevy_text")]
 fn build_text_interop(app: &mut App) {
@@ -197,13 +602,55 @@ fn build_text_interop(app: &mut App) {
     use bevy_text::TextLayoutInfo;
 
     app.register_type::<TextLayoutInfo>()
-        .register_type::<TextFlags>();
+        .register_type::<TextFlags>()
+        .register_type::<widget::TextPlaceholderNode>()
+        .init_resource::<widget::CommandRegistry>()
+        .init_resource::<widget::TextMeasureCacheStats>()
+        .add_event::<widget::CommandPaletteSelection>()
+        .init_resource::<widget::TextInputFocus>()
+        .init_resource::<widget::TextInputClipboard>()
+        .add_event::<widget::TextInputSubmitted>();
+
+    #[cfg(feature = "pseudo_locale")]
+    app.register_type::<pseudo_locale::PseudoLocale>()
+        .init_resource::<pseudo_locale::PseudoLocale>();
+
+    app.add_systems(
+        PreUpdate,
+        (
+            widget::spawn_command_palette_children,
+            widget::command_palette_input,
+            widget::update_command_palettes,
+        )
+            .chain()
+            // Open palettes capture the keyboard before focus navigation and text inputs read it
+            .after(InputSystem)
+            .before(UiSystem::Focus),
+    );
+
+    app.add_systems(
+        PreUpdate,
+        (
+            widget::spawn_text_input_children,
+            widget::text_input_mouse_system,
+            widget::text_input_keyboard_system,
+            widget::text_input_paste_system,
+            widget::text_input_ime_system,
+            widget::update_text_input_text,
+            // Focus navigation sees the capture from the next frame
+            widget::text_input_keyboard_capture_system,
+        )
+            .chain()
+            .after(UiSystem::Paste),
+    );
 
     app.add_systems(
         PostUpdate,
         (
             widget::measure_text_system
-                .before(UiSystem::Layout)
+                .in_set(UiSystem::Measure)
+                .after(update_ui_root_scale_system)
+                .after(bevy_text::reload_modified_fonts)
                 // Potential conflict: `Assets<Image>`
                 // In practice, they run independently since `bevy_render::camera_update_system`
                 // will only ever observe its own render target, and `widget::measure_text_system`
@@ -221,11 +668,21 @@ fn build_text_interop(app: &mut App) {
                 .after(bevy_text::remove_dropped_font_atlas_sets)
                 // Text2d and bevy_ui text are entirely on separate entities
                 .ambiguous_with(bevy_text::update_text2d_layout),
+            (
+                widget::update_text_input_caret,
+                widget::update_text_input_ime_window,
+            )
+                .chain()
+                .after(widget::text_system),
+            widget::update_text_placeholder_nodes.after(widget::text_system),
         ),
     );
 
     app.add_plugins(accessibility::AccessibilityPlugin);
 
+    #[cfg(feature = "debug_ui")]
+    app.add_plugins(debug_ui::DebugUiPlugin);
+
     app.configure_sets(
         PostUpdate,
         AmbiguousWithTextSystem.ambiguous_with(widget::text_system),
@@ -236,3 +693,92 @@ fn build_text_interop(app: &mut App) {
         AmbiguousWithUpdateText2DLayout.ambiguous_with(bevy_text::update_text2d_layout),
     );
 }
+
+#[cfg(all(test, feature = "render"))]
+mod tests {
+    use bevy_app::App;
+    use bevy_color::Color;
+    use bevy_ecs::{entity::EntityHashMap, prelude::*, reflect::AppTypeRegistry};
+    use bevy_scene::{serde::SceneDeserializer, DynamicSceneBuilder};
+    use serde::de::DeserializeSeed;
+
+    use super::register_types;
+    use crate::{
+        widget::{RadioGroup, Tab},
+        BackgroundColor, BorderColor, BorderRadius, FocusPolicy, Outline, Style, TargetCamera, Val,
+        ZIndex,
+    };
+
+    #[test]
+    fn ui_components_round_trip_through_scenes() {
+        let mut app = App::new();
+        register_types(&mut app);
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+
+        let world = app.world_mut();
+        let camera = world.spawn_empty().id();
+        let page = world.spawn(Style::default()).id();
+        let style = Style {
+            width: Val::Px(120.),
+            height: Val::Percent(50.),
+            row_gap: Val::Vw(2.),
+            ..Default::default()
+        };
+        let node = world
+            .spawn((
+                style.clone(),
+                BackgroundColor(Color::srgb(0.2, 0.4, 0.6)),
+                BorderColor::all(Color::WHITE),
+                BorderRadius::all(Val::Px(4.)),
+                Outline::new(Val::Px(2.), Val::ZERO, Color::BLACK),
+                ZIndex::Global(3),
+                FocusPolicy::Pass,
+                TargetCamera(camera),
+                Tab::new(page),
+                RadioGroup::new(page),
+            ))
+            .id();
+
+        let scene = DynamicSceneBuilder::from_world(world)
+            .extract_entities([camera, page, node].into_iter())
+            .build();
+        let serialized = scene.serialize(&registry.read()).unwrap();
+        let mut deserializer = bevy_scene::ron::de::Deserializer::from_str(&serialized).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        // Entities already in the world shift the ids of the loaded ones
+        let mut world = World::new();
+        world.insert_resource(registry);
+        world.spawn_empty();
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut world, &mut entity_map).unwrap();
+        let [camera, page, node] = [camera, page, node].map(|entity| entity_map[&entity]);
+
+        assert_eq!(world.get::<Style>(node), Some(&style));
+        assert_eq!(
+            world.get::<BackgroundColor>(node),
+            Some(&BackgroundColor(Color::srgb(0.2, 0.4, 0.6)))
+        );
+        assert_eq!(
+            world.get::<BorderColor>(node),
+            Some(&BorderColor::all(Color::WHITE))
+        );
+        assert_eq!(
+            world.get::<BorderRadius>(node),
+            Some(&BorderRadius::all(Val::Px(4.)))
+        );
+        assert_eq!(
+            world.get::<Outline>(node),
+            Some(&Outline::new(Val::Px(2.), Val::ZERO, Color::BLACK))
+        );
+        assert_eq!(world.get::<ZIndex>(node), Some(&ZIndex::Global(3)));
+        assert_eq!(world.get::<FocusPolicy>(node), Some(&FocusPolicy::Pass));
+        assert_eq!(world.get::<TargetCamera>(node), Some(&TargetCamera(camera)));
+        assert_eq!(world.get::<Tab>(node), Some(&Tab::new(page)));
+        assert_eq!(world.get::<RadioGroup>(node), Some(&RadioGroup::new(page)));
+    }
+}
@@ -5,6 +5,7 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use std::fmt::Formatter;
 pub use taffy::style::AvailableSpace;
 
+#[cfg(feature = "render")]
 use crate::widget::ImageMeasure;
 
 #[cfg(feature = "bevy_text")]
@@ -18,8 +19,55 @@ impl std::fmt::Debug for ContentSize {
 
 /// A `Measure` is used to compute the size of a ui node
 /// when the size of that node is based on its content.
+///
+/// Measures are called by the layout algorithm, possibly several times per layout, and all sizes are in physical
+/// pixels. A measure must follow this contract:
+/// * If `width` or `height` is `Some`, that dimension of the node is already known and must be returned unchanged.
+/// * Otherwise the dimension is computed from the content and the available space along that axis:
+///     * [`AvailableSpace::MinContent`] asks for the smallest size the content can take without overflowing,
+///       e.g. the width of the longest word of a text.
+///     * [`AvailableSpace::MaxContent`] asks for the size the content takes without any constraint,
+///       e.g. the width of a text without any line breaks.
+///     * [`AvailableSpace::Definite`] gives the space available to the node. The returned size may be larger
+///       than the available space when the content can't shrink to fit it.
+/// * The result must only depend on the arguments, so the same constraints always produce the same size.
+///
+/// Custom measures are set on a node with [`ContentSize::set`] and [`NodeMeasure::Custom`], or from a closure with
+/// [`ContentSize::set_measure_fn`]:
+///
+/// ```
+/// # use bevy_math::Vec2;
+/// # use bevy_ui::{measurement::AvailableSpace, ContentSize, Measure, NodeMeasure};
+/// /// Keeps a square shape, as large as the available width allows, up to `max_side`
+/// struct SquareMeasure {
+///     max_side: f32,
+/// }
+///
+/// impl Measure for SquareMeasure {
+///     fn measure(
+///         &self,
+///         width: Option<f32>,
+///         height: Option<f32>,
+///         available_width: AvailableSpace,
+///         _available_height: AvailableSpace,
+///         _style: &taffy::Style,
+///     ) -> Vec2 {
+///         let side = width.or(height).unwrap_or(match available_width {
+///             AvailableSpace::Definite(available) => available.min(self.max_side),
+///             AvailableSpace::MinContent => 0.,
+///             AvailableSpace::MaxContent => self.max_side,
+///         });
+///         Vec2::new(width.unwrap_or(side), height.unwrap_or(side))
+///     }
+/// }
+///
+/// let mut content_size = ContentSize::default();
+/// content_size.set(NodeMeasure::Custom(Box::new(SquareMeasure { max_side: 100. })));
+/// ```
 pub trait Measure: Send + Sync + 'static {
     /// Calculate the size of the node given the constraints.
+    ///
+    /// See the [`Measure`] documentation for the meaning of the arguments.
     fn measure(
         &self,
         width: Option<f32>,
@@ -35,10 +83,15 @@ pub trait Measure: Send + Sync + 'static {
 /// It has specific variants for common built-in types to avoid making them opaque and needing to box them
 /// by wrapping them in a closure and a Custom variant that allows arbitrary measurement closures if required.
 pub enum NodeMeasure {
+    /// A node with a fixed size.
     Fixed(FixedMeasure),
+    /// A text node.
     #[cfg(feature = "bevy_text")]
     Text(TextMeasure),
+    /// An image node.
+    #[cfg(feature = "render")]
     Image(ImageMeasure),
+    /// A node measured by a [`Measure`] implemented outside of `bevy_ui`.
     Custom(Box<dyn Measure>),
 }
 
@@ -59,6 +112,7 @@ impl Measure for NodeMeasure {
             NodeMeasure::Text(text) => {
                 text.measure(width, height, available_width, available_height, style)
             }
+            #[cfg(feature = "render")]
             NodeMeasure::Image(image) => {
                 image.measure(width, height, available_width, available_height, style)
             }
@@ -76,6 +130,26 @@ pub struct FixedMeasure {
     pub size: Vec2,
 }
 
+/// A `Measure` that calls a closure, created by [`ContentSize::set_measure_fn`] and
+/// [`ContentSize::from_measure_fn`].
+struct FnMeasure<F>(F);
+
+impl<F> Measure for FnMeasure<F>
+where
+    F: Fn(Option<f32>, Option<f32>, AvailableSpace, AvailableSpace) -> Vec2 + Send + Sync + 'static,
+{
+    fn measure(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        available_width: AvailableSpace,
+        available_height: AvailableSpace,
+        _: &taffy::Style,
+    ) -> Vec2 {
+        (self.0)(width, height, available_width, available_height)
+    }
+}
+
 impl Measure for FixedMeasure {
     fn measure(
         &self,
@@ -91,6 +165,37 @@ impl Measure for FixedMeasure {
 
 /// A node with a `ContentSize` component is a node where its size
 /// is based on its content.
+///
+/// The measures of text and image nodes are set automatically. Other widgets, like a canvas or a chart whose size
+/// depends on its data, can set their own measure in a system in [`UiSystem::Measure`](crate::UiSystem::Measure):
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_ui::{measurement::AvailableSpace, ContentSize, UiSystem};
+/// # use bevy_app::{App, PostUpdate};
+/// #[derive(Component)]
+/// struct Chart {
+///     /// The width of each bar, in physical pixels
+///     bar_width: f32,
+///     values: Vec<f32>,
+/// }
+///
+/// fn measure_charts(mut charts: Query<(&Chart, &mut ContentSize), Changed<Chart>>) {
+///     for (chart, mut content_size) in &mut charts {
+///         let size = Vec2::new(
+///             chart.bar_width * chart.values.len() as f32,
+///             chart.values.iter().copied().fold(0., f32::max),
+///         );
+///         content_size.set_measure_fn(move |width, height, _, _| {
+///             Vec2::new(width.unwrap_or(size.x), height.unwrap_or(size.y))
+///         });
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_systems(PostUpdate, measure_charts.in_set(UiSystem::Measure));
+/// ```
 #[derive(Component, Reflect, Default)]
 #[reflect(Component, Default)]
 pub struct ContentSize {
@@ -101,10 +206,38 @@ pub struct ContentSize {
 
 impl ContentSize {
     /// Set a `Measure` for the UI node entity with this component
+    ///
+    /// The node is measured again by the next layout update.
     pub fn set(&mut self, measure: NodeMeasure) {
         self.measure = Some(measure);
     }
 
+    /// Set a closure as the `Measure` for the UI node entity with this component.
+    ///
+    /// The closure is called with the known width and height of the node and the space available along each axis,
+    /// and must follow the contract of [`Measure`]. The node is measured again by the next layout update.
+    pub fn set_measure_fn(
+        &mut self,
+        measure: impl Fn(Option<f32>, Option<f32>, AvailableSpace, AvailableSpace) -> Vec2
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.set(NodeMeasure::Custom(Box::new(FnMeasure(measure))));
+    }
+
+    /// Creates a `ContentSize` with a closure as its `Measure`, see [`ContentSize::set_measure_fn`].
+    pub fn from_measure_fn(
+        measure: impl Fn(Option<f32>, Option<f32>, AvailableSpace, AvailableSpace) -> Vec2
+            + Send
+            + Sync
+            + 'static,
+    ) -> ContentSize {
+        let mut content_size = Self::default();
+        content_size.set_measure_fn(measure);
+        content_size
+    }
+
     /// Creates a `ContentSize` with a `Measure` that always returns given `size` argument, regardless of the UI layout's constraints.
     pub fn fixed_size(size: Vec2) -> ContentSize {
         let mut content_size = Self::default();
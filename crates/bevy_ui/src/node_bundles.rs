@@ -2,12 +2,18 @@
 
 //! This module contains basic node bundles used to build UIs
 
+use crate::{
+    widget::{
+        Button, Checkbox, CheckboxKind, ProgressBar, RadioButton, Scrollbar, ScrollbarAxis, Slider,
+        UiImageSize,
+    },
+    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Interaction, Node,
+    RelativeCursorPosition, Style, UiImage, UiMaterial, Val, ZIndex,
+};
 #[cfg(feature = "bevy_text")]
-use crate::widget::TextFlags;
 use crate::{
-    widget::{Button, UiImageSize},
-    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Interaction, Node, Style,
-    UiImage, UiMaterial, ZIndex,
+    widget::{CommandPalette, TextFlags, TextInput},
+    AlignItems, Display, FlexDirection, PositionType, UiRect,
 };
 use bevy_asset::Handle;
 use bevy_color::Color;
@@ -295,6 +301,152 @@ where
     }
 }
 
+/// A UI node that is a [`CommandPalette`]
+///
+/// By default the palette covers its parent, or the whole viewport if it is a root node, with a translucent
+/// backdrop that blocks interaction with the nodes below it, and is rendered on top of all other nodes.
+#[cfg(feature = "bevy_text")]
+#[derive(Bundle, Clone, Debug)]
+pub struct CommandPaletteBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The command palette's state
+    pub palette: CommandPalette,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The color of the backdrop
+    pub background_color: BackgroundColor,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `CommandPaletteBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+#[cfg(feature = "bevy_text")]
+impl Default for CommandPaletteBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            palette: Default::default(),
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::top(Val::Percent(10.)),
+                ..Default::default()
+            },
+            focus_policy: FocusPolicy::Block,
+            background_color: BackgroundColor(Color::srgba(0., 0., 0., 0.5)),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: ZIndex::Global(i32::MAX - 128),
+        }
+    }
+}
+
+/// A UI node that is a single line text field
+///
+/// The text, caret and selection highlight are displayed by child nodes spawned when the bundle is added.
+/// Use the [`TextInput`] component to read and set the text and the
+/// [`TextInputSubmitted`](crate::widget::TextInputSubmitted) event to respond to enter being pressed.
+#[cfg(feature = "bevy_text")]
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The text input's state
+    pub text_input: TextInput,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Describes whether and how the text input has been interacted with by the input
+    pub interaction: Interaction,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The background color of the text input
+    pub background_color: BackgroundColor,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `TextInputBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+#[cfg(feature = "bevy_text")]
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            text_input: Default::default(),
+            style: Style {
+                align_items: AlignItems::Center,
+                overflow: crate::Overflow::clip(),
+                ..Default::default()
+            },
+            interaction: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            background_color: Default::default(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+#[cfg(feature = "bevy_text")]
+impl TextInputBundle {
+    /// Creates a new [`TextInputBundle`] containing `value`.
+    pub fn new(value: impl Into<String>, text_style: TextStyle) -> Self {
+        Self {
+            text_input: TextInput::new(value, text_style),
+            ..Default::default()
+        }
+    }
+}
+
 /// A UI node that is a button
 ///
 /// # Extra behaviours
@@ -348,7 +500,7 @@ impl Default for ButtonBundle {
             style: Default::default(),
             interaction: Default::default(),
             focus_policy: FocusPolicy::Block,
-            border_color: BorderColor(Color::NONE),
+            border_color: BorderColor::all(Color::NONE),
             border_radius: BorderRadius::default(),
             image: Default::default(),
             transform: Default::default(),
@@ -361,6 +513,404 @@ impl Default for ButtonBundle {
     }
 }
 
+/// A UI node that is a horizontal slider
+///
+/// The filled part of the track and the handle are displayed by child nodes spawned when the bundle is added.
+/// Use the [`Slider`] component to read and set the value and the
+/// [`SliderChanged`](crate::widget::SliderChanged) event to respond to it being dragged.
+#[derive(Bundle, Clone, Debug)]
+pub struct SliderBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The slider's state
+    pub slider: Slider,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Describes whether and how the slider has been interacted with by the input
+    pub interaction: Interaction,
+    /// The position of the cursor relative to the slider, used to drag it
+    pub relative_cursor_position: RelativeCursorPosition,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The color of the track
+    pub background_color: BackgroundColor,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `SliderBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for SliderBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            slider: Default::default(),
+            style: Style {
+                width: Val::Px(200.),
+                height: Val::Px(8.),
+                ..Default::default()
+            },
+            interaction: Default::default(),
+            relative_cursor_position: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            background_color: Color::srgb(0.2, 0.2, 0.2).into(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+impl SliderBundle {
+    /// Creates a new [`SliderBundle`] for the given [`Slider`].
+    pub fn new(slider: Slider) -> Self {
+        Self {
+            slider,
+            ..Default::default()
+        }
+    }
+}
+
+/// A UI node that is a checkbox or toggle switch
+///
+/// The mark or knob is displayed by a child node spawned when the bundle is added.
+/// Use the [`Checkbox`] component to read and set whether it is checked and the
+/// [`CheckboxChanged`](crate::widget::CheckboxChanged) event to respond to it being toggled.
+#[derive(Bundle, Clone, Debug)]
+pub struct CheckboxBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The checkbox's state
+    pub checkbox: Checkbox,
+    /// Marker component that makes the checkbox send [`ButtonPressed`](crate::widget::ButtonPressed) events
+    pub button: Button,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Describes whether and how the checkbox has been interacted with by the input
+    pub interaction: Interaction,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The background color of the checkbox
+    pub background_color: BackgroundColor,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `CheckboxBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for CheckboxBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            checkbox: Default::default(),
+            button: Default::default(),
+            style: Style {
+                width: Val::Px(20.),
+                height: Val::Px(20.),
+                ..Default::default()
+            },
+            interaction: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            background_color: Color::srgb(0.2, 0.2, 0.2).into(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+impl CheckboxBundle {
+    /// Creates a new [`CheckboxBundle`] for the given [`Checkbox`].
+    ///
+    /// Toggle switches are twice as wide as they are high and fully rounded.
+    pub fn new(checkbox: Checkbox) -> Self {
+        let mut bundle = Self {
+            checkbox,
+            ..Default::default()
+        };
+        if checkbox.kind == CheckboxKind::Toggle {
+            bundle.style.width = Val::Px(40.);
+            bundle.border_radius = BorderRadius::MAX;
+        }
+        bundle
+    }
+}
+
+/// A UI node that is a radio button
+///
+/// The mark is displayed by a child node spawned when the bundle is added.
+/// Spawn radio buttons as descendants of a node with a [`RadioGroup`](crate::widget::RadioGroup) and use the
+/// [`SelectionChanged`](crate::widget::SelectionChanged) event to respond to the selection changing.
+#[derive(Bundle, Clone, Debug)]
+pub struct RadioButtonBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The radio button's state
+    pub radio_button: RadioButton,
+    /// Marker component that makes the radio button send [`ButtonPressed`](crate::widget::ButtonPressed) events
+    pub button: Button,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Describes whether and how the radio button has been interacted with by the input
+    pub interaction: Interaction,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The background color of the radio button
+    pub background_color: BackgroundColor,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `RadioButtonBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for RadioButtonBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            radio_button: Default::default(),
+            button: Default::default(),
+            style: Style {
+                width: Val::Px(20.),
+                height: Val::Px(20.),
+                ..Default::default()
+            },
+            interaction: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            background_color: Color::srgb(0.2, 0.2, 0.2).into(),
+            border_color: Default::default(),
+            border_radius: BorderRadius::MAX,
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+/// A UI node that is a horizontal progress bar
+///
+/// The filled part of the bar is displayed by a child node spawned when the bundle is added.
+/// Use the [`ProgressBar`] component to set the progress.
+#[derive(Bundle, Clone, Debug)]
+pub struct ProgressBarBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The progress bar's state
+    pub progress_bar: ProgressBar,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// The color of the empty part of the bar
+    pub background_color: BackgroundColor,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `ProgressBarBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for ProgressBarBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            progress_bar: Default::default(),
+            style: Style {
+                width: Val::Px(200.),
+                height: Val::Px(12.),
+                overflow: crate::Overflow::clip(),
+                ..Default::default()
+            },
+            background_color: Color::srgb(0.2, 0.2, 0.2).into(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+impl ProgressBarBundle {
+    /// Creates a new [`ProgressBarBundle`] with the given progress, from `0.` to `1.`.
+    pub fn new(progress: f32) -> Self {
+        Self {
+            progress_bar: ProgressBar::new(progress),
+            ..Default::default()
+        }
+    }
+}
+
+/// A UI node that is a scrollbar for a scroll container
+///
+/// The thumb is displayed by a child node spawned when the bundle is added.
+#[derive(Bundle, Clone, Debug)]
+pub struct ScrollbarBundle {
+    /// Describes the logical size of the node
+    pub node: Node,
+    /// The scrollbar's state
+    pub scrollbar: Scrollbar,
+    /// Styles which control the layout (size and position) of the node and its children
+    /// In some cases these styles also affect how the node drawn/painted.
+    pub style: Style,
+    /// Describes whether and how the scrollbar has been interacted with by the input
+    pub interaction: Interaction,
+    /// The position of the cursor relative to the scrollbar, used to drag its thumb
+    pub relative_cursor_position: RelativeCursorPosition,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The color of the track
+    pub background_color: BackgroundColor,
+    /// The color of the Node's border
+    pub border_color: BorderColor,
+    /// The border radius of the node
+    pub border_radius: BorderRadius,
+    /// The transform of the node
+    ///
+    /// This component is automatically managed by the UI layout system.
+    /// To alter the position of the `ScrollbarBundle`, use the properties of the [`Style`] component.
+    pub transform: Transform,
+    /// The global transform of the node
+    ///
+    /// This component is automatically updated by the [`TransformPropagate`](`bevy_transform::TransformSystem::TransformPropagate`) systems.
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    ///
+    /// This component is managed by the scrollbar to hide it when there is nothing to scroll.
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Indicates the depth at which the node should appear in the UI
+    pub z_index: ZIndex,
+}
+
+impl Default for ScrollbarBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            scrollbar: Default::default(),
+            style: Style {
+                width: Val::Px(8.),
+                height: Val::Percent(100.),
+                ..Default::default()
+            },
+            interaction: Default::default(),
+            relative_cursor_position: Default::default(),
+            focus_policy: FocusPolicy::Block,
+            background_color: Color::srgba(0., 0., 0., 0.3).into(),
+            border_color: Default::default(),
+            border_radius: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            inherited_visibility: Default::default(),
+            view_visibility: Default::default(),
+            z_index: Default::default(),
+        }
+    }
+}
+
+impl ScrollbarBundle {
+    /// Creates a new [`ScrollbarBundle`] for the given [`Scrollbar`], as long as its target along its axis.
+    pub fn new(scrollbar: Scrollbar) -> Self {
+        let mut bundle = Self {
+            scrollbar,
+            ..Default::default()
+        };
+        if scrollbar.axis == ScrollbarAxis::Horizontal {
+            bundle.style.width = Val::Percent(100.);
+            bundle.style.height = Val::Px(8.);
+        }
+        bundle
+    }
+}
+
 /// A UI node that is rendered using a [`UiMaterial`]
 ///
 /// Adding a `BackgroundColor` component to an entity with this bundle will ignore the custom
@@ -0,0 +1,491 @@
+//! Immediate-mode drawing over the UI.
+//!
+//! [`UiPainter`] collects shapes and text drawn by any system each frame, without spawning any entities. They are
+//! drawn on top of all UI nodes by the default UI camera, using the same instance buffers as the rest of the UI,
+//! and then discarded at the start of the next frame. This makes it cheap to draw short-lived visuals like HUDs
+//! and plots that change every frame.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::{Rect, Vec2};
+//! # use bevy_color::{palettes::basic::{GREEN, RED}, Alpha};
+//! # use bevy_ui::UiPainter;
+//! fn draw_plot(mut painter: ResMut<UiPainter>) {
+//!     painter.rounded_rect(Rect::new(10., 10., 210., 110.), 8., RED.with_alpha(0.5));
+//!     painter.line(Vec2::new(20., 100.), Vec2::new(200., 20.), 2., GREEN);
+//! }
+//! ```
+
+use bevy_app::{App, First, Plugin};
+use bevy_color::{Color, LinearRgba};
+use bevy_ecs::prelude::*;
+use bevy_math::{Mat4, Quat, Rect, Vec2};
+
+#[cfg(any(feature = "render", feature = "bevy_text"))]
+use crate::DefaultUiCamera;
+#[cfg(feature = "render")]
+use crate::{ExtractedUiNode, ExtractedUiNodes, NodeType, RenderUiSystem, UiStack};
+#[cfg(feature = "render")]
+use bevy_asset::AssetId;
+#[cfg(any(feature = "render", feature = "bevy_text"))]
+use bevy_ecs::system::SystemParam;
+#[cfg(feature = "render")]
+use bevy_render::{Extract, ExtractSchedule, RenderApp};
+
+#[cfg(feature = "bevy_text")]
+use crate::UiScale;
+#[cfg(feature = "bevy_text")]
+use bevy_app::PostUpdate;
+#[cfg(feature = "bevy_text")]
+use bevy_asset::Assets;
+#[cfg(feature = "bevy_text")]
+use bevy_render::camera::Camera;
+#[cfg(feature = "bevy_text")]
+use bevy_render::texture::Image;
+#[cfg(feature = "bevy_text")]
+use bevy_sprite::TextureAtlasLayout;
+#[cfg(all(feature = "render", feature = "bevy_text"))]
+use bevy_text::PositionedGlyph;
+#[cfg(feature = "bevy_text")]
+use bevy_text::{
+    BreakLineOn, Font, FontAtlasSets, FontRenderMode, JustifyText, TextError, TextLayoutInfo,
+    TextLayoutOptions, TextPipeline, TextSection, TextSettings, TextStyle, YAxisOrientation,
+};
+
+/// Adds the [`UiPainter`]. Added automatically by [`UiPlugin`](crate::UiPlugin).
+pub struct UiPainterPlugin;
+
+impl Plugin for UiPainterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiPainter>()
+            .add_systems(First, clear_ui_painter);
+
+        #[cfg(feature = "bevy_text")]
+        app.add_systems(
+            PostUpdate,
+            layout_ui_painter_text
+                .after(crate::widget::text_system)
+                .ambiguous_with(bevy_text::update_text2d_layout),
+        );
+
+        #[cfg(feature = "render")]
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                ExtractSchedule,
+                extract_ui_painter
+                    .after(RenderUiSystem::ExtractText)
+                    .before(RenderUiSystem::CullNodes),
+            );
+        }
+    }
+}
+
+/// A rectangle drawn by a [`UiPainter`].
+#[derive(Clone, Debug, PartialEq)]
+struct PaintedRect {
+    /// The translation to the center of the rectangle and its rotation.
+    transform: Mat4,
+    size: Vec2,
+    color: LinearRgba,
+    border_radius: [Vec2; 4],
+}
+
+/// A piece of text drawn by a [`UiPainter`], laid out in `PostUpdate`.
+#[cfg(feature = "bevy_text")]
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
+struct PaintedText {
+    position: Vec2,
+    section: TextSection,
+    layout: Option<TextLayoutInfo>,
+}
+
+/// Immediate-mode drawing over the UI.
+///
+/// Everything drawn is cleared at the start of each frame, so systems that draw with the painter must draw
+/// again every frame. Positions are in logical pixels, relative to the top left corner of the viewport of the
+/// default UI camera. Shapes are drawn in order, and behind all text.
+#[derive(Resource)]
+pub struct UiPainter {
+    /// The style used for text drawn with [`UiPainter::text`].
+    ///
+    /// The default font is used unless another font is set, which requires `bevy_text`'s
+    /// `default_font` feature.
+    #[cfg(feature = "bevy_text")]
+    pub text_style: TextStyle,
+    rects: Vec<PaintedRect>,
+    #[cfg(feature = "bevy_text")]
+    texts: Vec<PaintedText>,
+}
+
+#[cfg_attr(not(feature = "bevy_text"), allow(clippy::derivable_impls))]
+impl Default for UiPainter {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "bevy_text")]
+            text_style: TextStyle {
+                font_size: 16.,
+                ..Default::default()
+            },
+            rects: Vec::new(),
+            #[cfg(feature = "bevy_text")]
+            texts: Vec::new(),
+        }
+    }
+}
+
+impl UiPainter {
+    /// Fills `rect` with `color`.
+    pub fn rect(&mut self, rect: Rect, color: impl Into<Color>) {
+        self.rounded_rect(rect, 0., color);
+    }
+
+    /// Fills `rect` with `color`, with its corners rounded by `radius`.
+    ///
+    /// The radius is clamped to half the smallest side of the rect.
+    pub fn rounded_rect(&mut self, rect: Rect, radius: f32, color: impl Into<Color>) {
+        if rect.is_empty() {
+            return;
+        }
+        self.rects.push(PaintedRect {
+            transform: Mat4::from_translation(rect.center().extend(0.)),
+            size: rect.size(),
+            color: color.into().into(),
+            border_radius: [Vec2::splat(radius.clamp(0., 0.5 * rect.size().min_element())); 4],
+        });
+    }
+
+    /// Draws a line from `start` to `end`, `width` logical pixels thick.
+    pub fn line(&mut self, start: Vec2, end: Vec2, width: f32, color: impl Into<Color>) {
+        let delta = end - start;
+        let length = delta.length();
+        if length == 0. || width <= 0. {
+            return;
+        }
+        let angle = delta.y.atan2(delta.x);
+        self.rects.push(PaintedRect {
+            transform: Mat4::from_rotation_translation(
+                Quat::from_rotation_z(angle),
+                (0.5 * (start + end)).extend(0.),
+            ),
+            size: Vec2::new(length, width),
+            color: color.into().into(),
+            border_radius: [Vec2::ZERO; 4],
+        });
+    }
+
+    /// Fills the area inside `outer` and outside of `inner` with `color`, as up to four rectangles.
+    ///
+    /// `inner` is clamped to `outer`.
+    pub fn frame(&mut self, outer: Rect, inner: Rect, color: impl Into<Color>) {
+        let color = color.into();
+        let inner = inner.intersect(outer);
+        if inner.is_empty() {
+            self.rect(outer, color);
+            return;
+        }
+        for edge in [
+            Rect::new(outer.min.x, outer.min.y, outer.max.x, inner.min.y),
+            Rect::new(outer.min.x, inner.max.y, outer.max.x, outer.max.y),
+            Rect::new(outer.min.x, inner.min.y, inner.min.x, inner.max.y),
+            Rect::new(inner.max.x, inner.min.y, outer.max.x, inner.max.y),
+        ] {
+            self.rect(edge, color);
+        }
+    }
+
+    /// Draws the outline of `rect`, `width` logical pixels thick and inside of the rect.
+    pub fn outline(&mut self, rect: Rect, width: f32, color: impl Into<Color>) {
+        self.frame(rect, rect.inflate(-width), color);
+    }
+
+    /// Draws `value` with its top left corner at `position`, using the current [`UiPainter::text_style`].
+    ///
+    /// The text is not wrapped.
+    #[cfg(feature = "bevy_text")]
+    pub fn text(&mut self, position: Vec2, value: impl Into<String>) {
+        self.texts.push(PaintedText {
+            position,
+            section: TextSection::new(value, self.text_style.clone()),
+            layout: None,
+        });
+    }
+
+    /// Removes everything drawn this frame.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+        #[cfg(feature = "bevy_text")]
+        self.texts.clear();
+    }
+
+    /// Returns `true` if nothing has been drawn this frame.
+    pub fn is_empty(&self) -> bool {
+        #[cfg(feature = "bevy_text")]
+        if !self.texts.is_empty() {
+            return false;
+        }
+        self.rects.is_empty()
+    }
+}
+
+/// Clears the [`UiPainter`] at the start of each frame.
+pub fn clear_ui_painter(mut painter: ResMut<UiPainter>) {
+    painter.clear();
+}
+
+/// The resources used to lay out the text drawn by a [`UiPainter`].
+#[cfg(feature = "bevy_text")]
+#[derive(SystemParam)]
+pub struct UiPainterTextLayout<'w, 's> {
+    fonts: Res<'w, Assets<Font>>,
+    camera_query: Query<'w, 's, &'static Camera>,
+    default_ui_camera: DefaultUiCamera<'w, 's>,
+    ui_scale: Res<'w, UiScale>,
+    text_settings: Res<'w, TextSettings>,
+    text_pipeline: ResMut<'w, TextPipeline>,
+    font_atlas_sets: ResMut<'w, FontAtlasSets>,
+    texture_atlases: ResMut<'w, Assets<TextureAtlasLayout>>,
+    textures: ResMut<'w, Assets<Image>>,
+}
+
+#[cfg(feature = "bevy_text")]
+impl UiPainterTextLayout<'_, '_> {
+    /// Lays out the text drawn by `painter` this frame.
+    pub fn layout(&mut self, painter: &mut UiPainter) {
+        if painter.texts.is_empty() {
+            return;
+        }
+
+        let scale_factor = self
+            .default_ui_camera
+            .get()
+            .and_then(|camera_entity| self.camera_query.get(camera_entity).ok())
+            .and_then(Camera::target_scaling_factor)
+            .unwrap_or(1.0)
+            * self.ui_scale.0;
+
+        for text in &mut painter.texts {
+            match self.text_pipeline.queue_text(
+                &self.fonts,
+                std::slice::from_ref(&text.section),
+                scale_factor,
+                &TextLayoutOptions {
+                    justify: JustifyText::Left,
+                    linebreak_behavior: BreakLineOn::NoWrap,
+                    ..Default::default()
+                },
+                Vec2::splat(f32::INFINITY),
+                &mut self.font_atlas_sets,
+                &mut self.texture_atlases,
+                &mut self.textures,
+                &self.text_settings,
+                YAxisOrientation::TopToBottom,
+                FontRenderMode::Bitmap,
+            ) {
+                Ok(layout) => text.layout = Some(layout),
+                // The font hasn't loaded yet, the text will be drawn once it has.
+                Err(TextError::NoSuchFont) => {}
+                Err(e @ TextError::FailedToAddGlyph(_)) => {
+                    panic!("Fatal error when processing painted UI text: {e}.");
+                }
+            }
+        }
+    }
+}
+
+/// Lays out the text drawn with the [`UiPainter`] this frame.
+#[cfg(feature = "bevy_text")]
+pub fn layout_ui_painter_text(mut painter: ResMut<UiPainter>, mut layout: UiPainterTextLayout) {
+    layout.layout(&mut painter);
+}
+
+/// The resources used to extract what a [`UiPainter`] drew to the render world.
+#[cfg(feature = "render")]
+#[derive(SystemParam)]
+pub struct UiPainterExtract<'w, 's> {
+    commands: Commands<'w, 's>,
+    extracted_uinodes: ResMut<'w, ExtractedUiNodes>,
+    #[cfg(feature = "bevy_text")]
+    camera_query: Extract<'w, 's, Query<'static, 'static, &'static Camera>>,
+    default_ui_camera: Extract<'w, 's, DefaultUiCamera<'static, 'static>>,
+    #[cfg(feature = "bevy_text")]
+    texture_atlases: Extract<'w, 's, Res<'static, Assets<TextureAtlasLayout>>>,
+    #[cfg(feature = "bevy_text")]
+    ui_scale: Extract<'w, 's, Res<'static, UiScale>>,
+    ui_stack: Extract<'w, 's, Res<'static, UiStack>>,
+}
+
+#[cfg(feature = "render")]
+impl UiPainterExtract<'_, '_> {
+    /// Extracts what `painter` drew this frame, above every UI node.
+    ///
+    /// Shapes and text are given stack indices from [`ExtractedUiNodes::reserve_stack_range`], so painters
+    /// extracted later are drawn over painters extracted earlier.
+    pub fn extract(&mut self, painter: &UiPainter) {
+        if painter.is_empty() {
+            return;
+        }
+
+        let Some(camera_entity) = self.default_ui_camera.get() else {
+            return;
+        };
+
+        let stack_range = self
+            .extracted_uinodes
+            .reserve_stack_range(&self.ui_stack, 2);
+        let rect_stack_index = stack_range.start;
+        for rect in &painter.rects {
+            self.extracted_uinodes.push(
+                self.commands.spawn_empty().id(),
+                ExtractedUiNode {
+                    stack_index: rect_stack_index,
+                    transform: rect.transform,
+                    color: rect.color,
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: rect.size,
+                    },
+                    image: AssetId::default(),
+                    atlas_size: None,
+                    clip: None,
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border: [0.; 4],
+                    border_radius: rect.border_radius,
+                    repeat: None,
+                    node_type: NodeType::Rect,
+                },
+            );
+        }
+
+        #[cfg(feature = "bevy_text")]
+        {
+            let scale_factor = self
+                .camera_query
+                .get(camera_entity)
+                .ok()
+                .and_then(Camera::target_scaling_factor)
+                .unwrap_or(1.0)
+                * self.ui_scale.0;
+            let inverse_scale_factor = scale_factor.recip();
+            let text_stack_index = stack_range.start + 1;
+
+            for text in &painter.texts {
+                let Some(layout) = &text.layout else {
+                    continue;
+                };
+
+                // Align the text to the nearest physical pixel
+                let top_left = (text.position * scale_factor).round() * inverse_scale_factor;
+                let color = LinearRgba::from(text.section.style.color);
+
+                for PositionedGlyph {
+                    position,
+                    atlas_info,
+                    ..
+                } in &layout.glyphs
+                {
+                    let Some(atlas) = self.texture_atlases.get(&atlas_info.texture_atlas) else {
+                        continue;
+                    };
+
+                    let mut rect = atlas.textures[atlas_info.glyph_index].as_rect();
+                    rect.min *= inverse_scale_factor;
+                    rect.max *= inverse_scale_factor;
+                    self.extracted_uinodes.push(
+                        self.commands.spawn_empty().id(),
+                        ExtractedUiNode {
+                            stack_index: text_stack_index,
+                            transform: Mat4::from_translation(
+                                (top_left + *position * inverse_scale_factor).extend(0.),
+                            ),
+                            color: atlas_info.tint(color),
+                            rect,
+                            image: atlas_info.texture.id(),
+                            atlas_size: Some(atlas.size.as_vec2() * inverse_scale_factor),
+                            clip: None,
+                            flip_x: false,
+                            flip_y: false,
+                            camera_entity,
+                            border: [0.; 4],
+                            border_radius: [Vec2::ZERO; 4],
+                            repeat: None,
+                            node_type: NodeType::Rect,
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Extracts the [`UiPainter`], above all other UI nodes.
+#[cfg(feature = "render")]
+pub fn extract_ui_painter(mut extract: UiPainterExtract, painter: Extract<Res<UiPainter>>) {
+    extract.extract(&painter);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::Color;
+    use bevy_math::{Rect, Vec2};
+
+    use super::UiPainter;
+
+    #[test]
+    fn painter_shapes() {
+        let mut painter = UiPainter::default();
+        assert!(painter.is_empty());
+
+        painter.rounded_rect(Rect::new(0., 0., 10., 20.), 8., Color::WHITE);
+        // The radius is clamped to half the width
+        assert_eq!(painter.rects[0].border_radius, [Vec2::splat(5.); 4]);
+        assert_eq!(painter.rects[0].size, Vec2::new(10., 20.));
+
+        // Vertical lines are rotated a quarter turn
+        painter.line(Vec2::new(5., 0.), Vec2::new(5., 10.), 2., Color::WHITE);
+        let line = &painter.rects[1];
+        assert_eq!(line.size, Vec2::new(10., 2.));
+        let end = line
+            .transform
+            .transform_point3(Vec2::new(5., 0.).extend(0.));
+        assert!(end.truncate().distance(Vec2::new(5., 10.)) < 1e-5);
+
+        // Zero length lines and empty rects aren't drawn
+        painter.line(Vec2::ONE, Vec2::ONE, 2., Color::WHITE);
+        painter.rect(Rect::new(0., 0., 0., 10.), Color::WHITE);
+        assert_eq!(painter.rects.len(), 2);
+
+        painter.clear();
+        assert!(painter.is_empty());
+    }
+
+    #[test]
+    fn painter_frames() {
+        let mut painter = UiPainter::default();
+        let outer = Rect::new(0., 0., 100., 50.);
+        painter.frame(outer, Rect::new(10., 0., 90., 40.), Color::WHITE);
+        let rects = painter
+            .rects
+            .iter()
+            .map(|rect| {
+                Rect::from_center_size(rect.transform.w_axis.truncate().truncate(), rect.size)
+            })
+            .collect::<Vec<_>>();
+        // The frame has no top edge, as the inner rect touches the top of the outer rect
+        assert_eq!(
+            rects,
+            [
+                Rect::new(0., 40., 100., 50.),
+                Rect::new(0., 0., 10., 40.),
+                Rect::new(90., 0., 100., 40.),
+            ]
+        );
+
+        // An outline wider than the rect fills it
+        painter.clear();
+        painter.outline(Rect::new(0., 0., 4., 4.), 3., Color::WHITE);
+        assert_eq!(painter.rects.len(), 1);
+        assert_eq!(painter.rects[0].size, Vec2::splat(4.));
+    }
+}
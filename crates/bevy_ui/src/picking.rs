@@ -0,0 +1,181 @@
+//! Hit testing of UI nodes under each pointer, for use by picking backends.
+//!
+//! [`ui_picking_system`] sends a [`UiNodeHit`] every frame for each pointer over a node with a [`Pickable`]
+//! component. Unlike [`Interaction`](crate::Interaction), hits take the rounded corners of nodes into account and
+//! don't change the state of the nodes, so other picking and input systems can be built on top of them.
+
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_input::touch::Touches;
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::Camera;
+
+use crate::{touch::ui_position, GamepadCursor, UiScale, UiStacks, UiViewports};
+
+/// Makes a UI node a target of [`ui_picking_system`].
+///
+/// Nodes without a [`Pickable`] are ignored by the hit test, and pointers pass through them to the nodes below.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Pickable {
+    /// Whether the node stops pointers from hitting the pickable nodes below it.
+    pub should_block_lower: bool,
+}
+
+impl Pickable {
+    /// A node that is hit and blocks the nodes below it.
+    pub const BLOCK: Self = Self {
+        should_block_lower: true,
+    };
+
+    /// A node that is hit and lets the nodes below it be hit too.
+    pub const PASS: Self = Self {
+        should_block_lower: false,
+    };
+}
+
+impl Default for Pickable {
+    fn default() -> Self {
+        Self::BLOCK
+    }
+}
+
+/// A pointer hit tested by [`ui_picking_system`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(PartialEq)]
+pub enum UiPointer {
+    /// The mouse cursor.
+    Mouse,
+    /// An enabled [`GamepadCursor`], which replaces the mouse cursor.
+    GamepadCursor,
+    /// A pressed touch, with its id.
+    Touch(u64),
+}
+
+/// A [`Pickable`] node under a pointer, sent by [`ui_picking_system`] every frame the pointer is over the node.
+///
+/// Each pointer hits the topmost pickable node under it, and the nodes below it until a node that
+/// [blocks lower nodes](Pickable::should_block_lower). Hits for a pointer are sent from front to back.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct UiNodeHit {
+    /// The pointer over the node.
+    pub pointer: UiPointer,
+    /// The node that was hit.
+    pub entity: Entity,
+    /// The camera rendering the node.
+    pub camera: Entity,
+    /// The position of the pointer, in logical pixels relative to the top left of the camera's viewport.
+    pub position: Vec2,
+    /// The depth of the node in the [`UiStack`](crate::UiStack), as returned by [`UiStacks::depth_of`].
+    ///
+    /// Nodes with greater depths are drawn over nodes with lesser depths.
+    pub depth: usize,
+}
+
+/// Sends [`UiNodeHit`] events for the [`Pickable`] nodes under the mouse cursor and each pressed touch.
+///
+/// While a [`GamepadCursor`] is enabled, it replaces the mouse cursor and its hits are sent for
+/// [`UiPointer::GamepadCursor`].
+///
+/// Each pointer hits the nodes of the frontmost camera with pickable nodes under it. Hidden nodes, the clipped
+/// regions of nodes and, unless their [`HitTestMode`] is [`HitTestMode::Bounds`], the corners cut off by their
+/// [`BorderRadius`] are never hit.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_picking_system(
+    camera_query: Query<(Entity, &Camera)>,
+    viewports: UiViewports,
+    touches_input: Res<Touches>,
+    gamepad_cursor: Option<Res<GamepadCursor>>,
+    ui_scale: Res<UiScale>,
+    ui_stacks: UiStacks,
+    pickables: Query<&Pickable>,
+    mut hit_events: EventWriter<UiNodeHit>,
+) {
+    // Cameras ordered from front to back
+    let mut cameras = camera_query
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .collect::<Vec<_>>();
+    cameras.sort_by_key(|(_, camera)| std::cmp::Reverse(camera.order));
+
+    let mut pick = |pointer, position_of: &dyn Fn(Entity, &Camera) -> Option<Vec2>| {
+        for &(camera, camera_component) in &cameras {
+            let Some(position) = position_of(camera, camera_component) else {
+                continue;
+            };
+            let mut hits = ui_stacks
+                .hit_test_camera(camera, position)
+                .into_iter()
+                .filter_map(|entity| Some((entity, pickables.get(entity).ok()?)))
+                .peekable();
+            if hits.peek().is_none() {
+                continue;
+            }
+            for (entity, pickable) in hits {
+                hit_events.send(UiNodeHit {
+                    pointer,
+                    entity,
+                    camera,
+                    position,
+                    depth: ui_stacks.depth_of(entity).unwrap_or_default(),
+                });
+                if pickable.should_block_lower {
+                    break;
+                }
+            }
+            return;
+        }
+    };
+
+    let gamepad_cursor_position = gamepad_cursor
+        .as_deref()
+        .and_then(GamepadCursor::active_position);
+    let cursor_positions = viewports.cursor_positions(gamepad_cursor_position, ui_scale.0);
+    let pointer = if gamepad_cursor_position.is_some() {
+        UiPointer::GamepadCursor
+    } else {
+        UiPointer::Mouse
+    };
+    pick(pointer, &|camera, _| cursor_positions.get(&camera).copied());
+
+    for touch in touches_input.iter() {
+        pick(UiPointer::Touch(touch.id()), &|_, camera| {
+            Some(ui_position(camera, touch.position(), ui_scale.0))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rounded_rect_contains;
+    use bevy_math::{Rect, Vec2};
+
+    #[test]
+    fn rounded_corners_are_not_hit() {
+        let rect = Rect::new(0., 0., 100., 50.);
+        let square = [Vec2::ZERO; 4];
+        assert!(rounded_rect_contains(rect, square, Vec2::new(1., 1.)));
+        assert!(!rounded_rect_contains(rect, square, Vec2::new(101., 1.)));
+
+        // A circular top left corner, and an elliptical bottom right corner
+        let radii = [
+            Vec2::splat(20.),
+            Vec2::ZERO,
+            Vec2::new(40., 10.),
+            Vec2::ZERO,
+        ];
+        assert!(!rounded_rect_contains(rect, radii, Vec2::new(2., 2.)));
+        assert!(rounded_rect_contains(rect, radii, Vec2::new(8., 8.)));
+        assert!(rounded_rect_contains(rect, radii, Vec2::new(20., 1.)));
+        assert!(rounded_rect_contains(rect, radii, Vec2::new(99., 1.)));
+        assert!(!rounded_rect_contains(rect, radii, Vec2::new(95., 48.)));
+        assert!(rounded_rect_contains(rect, radii, Vec2::new(70., 48.)));
+        assert!(rounded_rect_contains(rect, radii, Vec2::new(99., 40.)));
+
+        // Radii are clamped to half the size of the rect, making a capsule
+        let capsule = [Vec2::splat(f32::MAX); 4];
+        assert!(!rounded_rect_contains(rect, capsule, Vec2::new(2., 2.)));
+        assert!(rounded_rect_contains(rect, capsule, Vec2::new(2., 25.)));
+        assert!(rounded_rect_contains(rect, capsule, Vec2::new(50., 1.)));
+    }
+}
@@ -0,0 +1,157 @@
+//! A pseudo-locale stress mode for UI text.
+//!
+//! When [`PseudoLocale::enabled`] is set, the text of every UI text node is transformed before it is measured
+//! and laid out, without modifying the [`Text`] components themselves.
+//! This makes it possible to find layouts that don't leave enough room for translations, which are often
+//! considerably longer than the original text, fonts missing accented glyphs and UI that isn't ready for
+//! right-to-left languages, without having to translate anything.
+//!
+//! Because the transformed text is the one laid out, the byte ranges in
+//! [`TextLayoutInfo`](bevy_text::TextLayoutInfo) refer to the transformed text while the mode is enabled.
+
+use std::borrow::Cow;
+
+use bevy_ecs::{prelude::Resource, reflect::ReflectResource};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_text::{JustifyText, Text, TextDirection};
+
+/// Accented replacements for `a` to `z`.
+const ACCENTED_LOWERCASE: [char; 26] = [
+    'à', 'ƀ', 'ç', 'đ', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ĺ', 'ɱ', 'ñ', 'ö', 'þ', 'ǫ', 'ŕ', 'š',
+    'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+
+/// Accented replacements for `A` to `Z`.
+const ACCENTED_UPPERCASE: [char; 26] = [
+    'À', 'Ɓ', 'Ç', 'Đ', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ĺ', 'Ṁ', 'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š',
+    'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+];
+
+/// The character used to pad expanded text.
+const PADDING: char = '~';
+
+/// Controls the pseudo-locale transform applied to UI text at layout time.
+///
+/// Changing this resource causes all UI text to be measured and laid out again.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct PseudoLocale {
+    /// Whether the transform is applied. Disabled by default.
+    pub enabled: bool,
+    /// The fraction by which the length of each text section is increased with padding.
+    ///
+    /// The expanded text is also enclosed in square brackets, so clipped text is easy to spot.
+    pub expansion: f32,
+    /// Replace ASCII letters with accented lookalikes.
+    pub accents: bool,
+    /// Mirror the characters of each line and justify the text to the right, to approximate how
+    /// right-to-left text will be displayed.
+    pub force_rtl: bool,
+}
+
+impl Default for PseudoLocale {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            expansion: 0.4,
+            accents: true,
+            force_rtl: false,
+        }
+    }
+}
+
+impl PseudoLocale {
+    /// Transforms a single string.
+    ///
+    /// Unlike [`PseudoLocale::localize_text`] this doesn't add the enclosing brackets, and the transform is
+    /// applied even if the pseudo-locale isn't enabled.
+    pub fn localize_str(&self, value: &str) -> String {
+        let mut lines = Vec::new();
+        for line in value.split('\n') {
+            let mut localized: String = line
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' if self.accents => ACCENTED_LOWERCASE[(c as u8 - b'a') as usize],
+                    'A'..='Z' if self.accents => ACCENTED_UPPERCASE[(c as u8 - b'A') as usize],
+                    c => c,
+                })
+                .collect();
+            let padding = (line.chars().count() as f32 * self.expansion.max(0.)).ceil() as usize;
+            for _ in 0..padding {
+                localized.push(PADDING);
+            }
+            if self.force_rtl {
+                localized = localized.chars().rev().collect();
+            }
+            lines.push(localized);
+        }
+        lines.join("\n")
+    }
+
+    /// Returns the text to lay out in place of `text`.
+    ///
+    /// If the pseudo-locale isn't enabled, `text` is returned unchanged.
+    pub fn localize_text<'a>(&self, text: &'a Text) -> Cow<'a, Text> {
+        if !self.enabled || text.sections.is_empty() {
+            return Cow::Borrowed(text);
+        }
+        let mut localized = text.clone();
+        for section in &mut localized.sections {
+            section.value = self.localize_str(&section.value);
+        }
+        let (open, close) = if self.force_rtl {
+            (']', '[')
+        } else {
+            ('[', ']')
+        };
+        localized.sections[0].value.insert(0, open);
+        if let Some(last) = localized.sections.last_mut() {
+            last.value.push(close);
+        }
+        if self.force_rtl {
+            localized.direction = TextDirection::RightToLeft;
+            localized.justify = match text.justify {
+                JustifyText::Left => JustifyText::Right,
+                JustifyText::Right => JustifyText::Left,
+                JustifyText::JustifiedLeft => JustifyText::JustifiedRight,
+                JustifyText::JustifiedRight => JustifyText::JustifiedLeft,
+                justify => justify,
+            };
+        }
+        Cow::Owned(localized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PseudoLocale;
+    use bevy_text::{JustifyText, Text, TextSection};
+
+    #[test]
+    fn pseudo_locale_transforms_text() {
+        let mut pseudo_locale = PseudoLocale {
+            enabled: true,
+            expansion: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(pseudo_locale.localize_str("Save"), "Šàṽé~~");
+        assert_eq!(pseudo_locale.localize_str("Ok\nNo!"), "Öķ~\nÑö!~~");
+
+        let text = Text::from_sections([
+            TextSection::new("Hi", Default::default()),
+            TextSection::new("z", Default::default()),
+        ]);
+        let localized = pseudo_locale.localize_text(&text);
+        assert_eq!(localized.sections[0].value, "[Ĥî~");
+        assert_eq!(localized.sections[1].value, "ž~]");
+
+        pseudo_locale.accents = false;
+        pseudo_locale.force_rtl = true;
+        let localized = pseudo_locale.localize_text(&text);
+        assert_eq!(localized.sections[0].value, "]~iH");
+        assert_eq!(localized.resolved_justify(), JustifyText::Right);
+
+        pseudo_locale.enabled = false;
+        assert_eq!(pseudo_locale.localize_text(&text).sections[0].value, "Hi");
+    }
+}
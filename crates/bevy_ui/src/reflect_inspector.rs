@@ -0,0 +1,719 @@
+//! A minimal inspector that generates editing widgets for any [`Reflect`] component.
+//!
+//! Add the [`ReflectInspectorPlugin`] and spawn a UI node with a [`ReflectInspector`] component
+//! pointing at the entity and the component type to edit. The inspector's children are generated
+//! from the component's [`TypeRegistration`](bevy_reflect::TypeRegistration):
+//!
+//! - structs, tuples, tuple structs, lists and arrays become [`Foldout`]s containing one row per field,
+//! - `bool` fields become an [`InspectorCheckbox`],
+//! - numeric fields become a [`DragValue`], edited by pressing and dragging it horizontally,
+//! - [`Color`] fields become a [`ColorPicker`] swatch followed by draggable hue, saturation, lightness and alpha channels,
+//! - every other value is shown as read-only text.
+//!
+//! The component type must be registered and reflect [`Component`] (`#[reflect(Component)]`).
+//! Edits are written back to the component through [`ReflectComponent::reflect_mut`], which triggers its change detection.
+use std::any::TypeId;
+
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, Display, FlexDirection, Interaction, Style, UiRect, Val,
+};
+use bevy_app::{App, Plugin, Update};
+use bevy_color::{Color, Hsla};
+use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+use bevy_hierarchy::{
+    BuildWorldChildren, Children, DespawnRecursiveExt, Parent, WorldChildBuilder,
+};
+use bevy_input::mouse::MouseMotion;
+use bevy_reflect::{GetPath, Reflect, ReflectRef};
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_utils::default;
+
+/// The font size used by the inspector's text.
+const FONT_SIZE: f32 = 14.;
+
+/// The maximum depth of nested values that get their own widgets, deeper values are shown as read-only text.
+const MAX_DEPTH: usize = 8;
+
+/// Background color of the draggable widgets.
+const DRAG_BACKGROUND_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+
+/// Adds the systems that build, update and apply the edits of [`ReflectInspector`] widgets.
+pub struct ReflectInspectorPlugin;
+
+impl Plugin for ReflectInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReflectInspectorEdits>().add_systems(
+            Update,
+            (
+                build_reflect_inspectors,
+                reflect_inspector_input,
+                apply_reflect_inspector_edits,
+                sync_reflect_inspectors,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Generates editing widgets as children of this node for the component with the type id `component` on `entity`.
+///
+/// The widgets are rebuilt whenever this component is changed.
+#[derive(Component, Clone, Debug)]
+pub struct ReflectInspector {
+    /// The entity with the inspected component.
+    pub entity: Entity,
+    /// The [`TypeId`] of the inspected component.
+    pub component: TypeId,
+}
+
+impl ReflectInspector {
+    /// Creates an inspector for the component `C` on `entity`.
+    pub fn new<C: Component + Reflect>(entity: Entity) -> Self {
+        Self {
+            entity,
+            component: TypeId::of::<C>(),
+        }
+    }
+}
+
+/// The value edited or displayed by an inspector widget.
+#[derive(Component, Clone, Debug)]
+pub struct InspectorField {
+    /// The [`ReflectInspector`] entity this widget belongs to.
+    pub inspector: Entity,
+    /// The reflection path of the value relative to the inspected component.
+    /// An empty path refers to the component itself.
+    pub path: String,
+}
+
+/// A header that shows or hides its sibling nodes when pressed.
+#[derive(Component, Clone, Debug)]
+pub struct Foldout {
+    /// Whether the siblings of the header are displayed.
+    pub open: bool,
+}
+
+impl Foldout {
+    fn marker(&self) -> &'static str {
+        if self.open {
+            "[-] "
+        } else {
+            "[+] "
+        }
+    }
+}
+
+/// Toggles a `bool` value when pressed.
+///
+/// Unlike [`widget::Checkbox`](crate::widget::Checkbox), it doesn't store whether it's checked, it edits the
+/// inspected value directly.
+#[derive(Component, Clone, Debug, Default)]
+pub struct InspectorCheckbox;
+
+/// Changes a numeric value by dragging horizontally while pressed.
+#[derive(Component, Clone, Debug)]
+pub struct DragValue {
+    /// How much the value changes per pixel of mouse motion.
+    pub speed: f64,
+    integer: bool,
+    remainder: f64,
+}
+
+/// A swatch that displays a [`Color`] value.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ColorPicker;
+
+/// Changes one channel of a [`Color`] value by dragging horizontally while pressed.
+///
+/// The edited color is converted to [`Hsla`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorPickerChannel {
+    /// The hue, in degrees. Wraps around at 360.
+    Hue,
+    /// The saturation, from 0 to 1.
+    Saturation,
+    /// The lightness, from 0 to 1.
+    Lightness,
+    /// The alpha, from 0 to 1.
+    Alpha,
+}
+
+impl ColorPickerChannel {
+    const ALL: [Self; 4] = [Self::Hue, Self::Saturation, Self::Lightness, Self::Alpha];
+
+    /// How much the channel changes per pixel of mouse motion.
+    fn speed(self) -> f32 {
+        match self {
+            Self::Hue => 1.,
+            _ => 0.005,
+        }
+    }
+
+    fn apply(self, hsla: &mut Hsla, delta: f32) {
+        match self {
+            Self::Hue => hsla.hue = (hsla.hue + delta).rem_euclid(360.),
+            Self::Saturation => hsla.saturation = (hsla.saturation + delta).clamp(0., 1.),
+            Self::Lightness => hsla.lightness = (hsla.lightness + delta).clamp(0., 1.),
+            Self::Alpha => hsla.alpha = (hsla.alpha + delta).clamp(0., 1.),
+        }
+    }
+
+    fn label(self, hsla: Hsla) -> String {
+        match self {
+            Self::Hue => format!("H {:.0}", hsla.hue),
+            Self::Saturation => format!("S {:.2}", hsla.saturation),
+            Self::Lightness => format!("L {:.2}", hsla.lightness),
+            Self::Alpha => format!("A {:.2}", hsla.alpha),
+        }
+    }
+}
+
+/// An edit to an inspected value, queued by [`reflect_inspector_input`].
+#[derive(Clone, Copy, Debug)]
+enum InspectorEdit {
+    Toggle,
+    AddNumber(f64),
+    AddColorChannel(ColorPickerChannel, f32),
+}
+
+/// Edits waiting to be written back to the inspected components, keyed by the widget entity.
+#[derive(Resource, Default)]
+struct ReflectInspectorEdits(Vec<(Entity, InspectorEdit)>);
+
+macro_rules! impl_number_access {
+    (floats: $($float:ty),*; integers: $($integer:ty),*) => {
+        /// Returns `Some(true)` if the value is an integer, `Some(false)` if it is a float and `None` otherwise.
+        fn number_kind(value: &dyn Reflect) -> Option<bool> {
+            $(if value.is::<$float>() { return Some(false); })*
+            $(if value.is::<$integer>() { return Some(true); })*
+            None
+        }
+
+        fn number_label(value: &dyn Reflect) -> Option<String> {
+            $(if let Some(value) = value.downcast_ref::<$float>() { return Some(format!("{value:.3}")); })*
+            $(if let Some(value) = value.downcast_ref::<$integer>() { return Some(value.to_string()); })*
+            None
+        }
+
+        /// Adds `delta` to the value, saturating at the bounds of integer types.
+        fn add_to_number(value: &mut dyn Reflect, delta: f64) {
+            $(if let Some(value) = value.downcast_mut::<$float>() {
+                *value += delta as $float;
+                return;
+            })*
+            $(if let Some(value) = value.downcast_mut::<$integer>() {
+                *value = (*value as f64 + delta) as $integer;
+                return;
+            })*
+        }
+    };
+}
+
+impl_number_access!(
+    floats: f32, f64;
+    integers: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize
+);
+
+/// The widget generated for a value.
+enum FieldSpec {
+    Compound(Vec<(String, String, FieldSpec)>),
+    Checkbox,
+    DragValue { integer: bool },
+    Color,
+    ReadOnly,
+}
+
+impl FieldSpec {
+    fn new(value: &dyn Reflect, path: &str, depth: usize) -> Self {
+        if value.is::<Color>() {
+            return Self::Color;
+        }
+        if value.is::<bool>() {
+            return Self::Checkbox;
+        }
+        if let Some(integer) = number_kind(value) {
+            return Self::DragValue { integer };
+        }
+        if depth >= MAX_DEPTH {
+            return Self::ReadOnly;
+        }
+        let mut fields = Vec::new();
+        let mut push_field = |label: String, path: String, value: &dyn Reflect| {
+            let spec = Self::new(value, &path, depth + 1);
+            fields.push((label, path, spec));
+        };
+        match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    let name = value.name_at(index).unwrap_or_default();
+                    push_field(name.to_string(), format!("{path}.{name}"), field);
+                }
+            }
+            ReflectRef::TupleStruct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    push_field(index.to_string(), format!("{path}.{index}"), field);
+                }
+            }
+            ReflectRef::Tuple(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    push_field(index.to_string(), format!("{path}.{index}"), field);
+                }
+            }
+            ReflectRef::List(value) => {
+                for (index, item) in value.iter().enumerate() {
+                    push_field(format!("[{index}]"), format!("{path}[{index}]"), item);
+                }
+            }
+            ReflectRef::Array(value) => {
+                for (index, item) in value.iter().enumerate() {
+                    push_field(format!("[{index}]"), format!("{path}[{index}]"), item);
+                }
+            }
+            _ => return Self::ReadOnly,
+        }
+        Self::Compound(fields)
+    }
+}
+
+fn text_style() -> TextStyle {
+    TextStyle {
+        font_size: FONT_SIZE,
+        color: Color::WHITE,
+        ..default()
+    }
+}
+
+fn field_text(inspector: Entity, path: &str) -> (TextBundle, InspectorField) {
+    (
+        TextBundle::from_section("", text_style()),
+        InspectorField {
+            inspector,
+            path: path.to_string(),
+        },
+    )
+}
+
+fn drag_text(inspector: Entity, path: &str) -> (TextBundle, InspectorField, Interaction) {
+    let (mut text, field) = field_text(inspector, path);
+    text.background_color = DRAG_BACKGROUND_COLOR.into();
+    text.style.padding = UiRect::horizontal(Val::Px(4.));
+    (text, field, Interaction::default())
+}
+
+fn spawn_field(
+    builder: &mut WorldChildBuilder,
+    inspector: Entity,
+    label: &str,
+    path: &str,
+    spec: &FieldSpec,
+) {
+    if let FieldSpec::Compound(fields) = spec {
+        builder
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|builder| {
+                let foldout = Foldout { open: true };
+                builder.spawn((
+                    TextBundle::from_sections([
+                        TextSection::new(foldout.marker(), text_style()),
+                        TextSection::new(label, text_style()),
+                    ]),
+                    foldout,
+                    Interaction::default(),
+                ));
+                builder
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::left(Val::Px(12.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|builder| {
+                        for (label, path, spec) in fields {
+                            spawn_field(builder, inspector, label, path, spec);
+                        }
+                    });
+            });
+        return;
+    }
+
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                column_gap: Val::Px(6.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(format!("{label}:"), text_style()));
+            match spec {
+                FieldSpec::Checkbox => {
+                    builder.spawn((
+                        field_text(inspector, path),
+                        InspectorCheckbox,
+                        Interaction::default(),
+                    ));
+                }
+                FieldSpec::DragValue { integer } => {
+                    builder.spawn((
+                        drag_text(inspector, path),
+                        DragValue {
+                            speed: if *integer { 0.2 } else { 0.01 },
+                            integer: *integer,
+                            remainder: 0.,
+                        },
+                    ));
+                }
+                FieldSpec::Color => {
+                    builder.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(FONT_SIZE),
+                                height: Val::Px(FONT_SIZE),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        InspectorField {
+                            inspector,
+                            path: path.to_string(),
+                        },
+                        ColorPicker,
+                    ));
+                    for channel in ColorPickerChannel::ALL {
+                        builder.spawn((drag_text(inspector, path), channel));
+                    }
+                }
+                FieldSpec::ReadOnly | FieldSpec::Compound(_) => {
+                    builder.spawn(field_text(inspector, path));
+                }
+            }
+        });
+}
+
+/// Rebuilds the widgets of changed [`ReflectInspector`]s.
+fn build_reflect_inspectors(
+    world: &mut World,
+    inspectors: &mut QueryState<(Entity, &ReflectInspector), Changed<ReflectInspector>>,
+) {
+    let changed = inspectors
+        .iter(world)
+        .map(|(entity, inspector)| (entity, inspector.clone()))
+        .collect::<Vec<_>>();
+
+    for (inspector_entity, inspector) in changed {
+        let (label, spec) = {
+            let registry = world.resource::<AppTypeRegistry>().read();
+            let registration = registry.get(inspector.component);
+            let label = registration
+                .map(|registration| registration.type_info().type_path_table().short_path())
+                .unwrap_or("unregistered component")
+                .to_string();
+            let spec = registration
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .zip(world.get_entity(inspector.entity))
+                .and_then(|(reflect_component, entity)| reflect_component.reflect(entity))
+                .map(|value| FieldSpec::new(value, "", 0));
+            (label, spec)
+        };
+
+        let Some(mut entity) = world.get_entity_mut(inspector_entity) else {
+            continue;
+        };
+        entity.despawn_descendants();
+        entity.with_children(|builder| match spec {
+            Some(spec) => spawn_field(builder, inspector_entity, &label, "", &spec),
+            None => {
+                builder.spawn(TextBundle::from_section(
+                    format!("{label} not found on {:?}", inspector.entity),
+                    text_style(),
+                ));
+            }
+        });
+    }
+}
+
+/// Toggles [`Foldout`]s and queues the edits made with [`InspectorCheckbox`], [`DragValue`] and [`ColorPickerChannel`] widgets.
+#[allow(clippy::too_many_arguments)]
+fn reflect_inspector_input(
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut edits: ResMut<ReflectInspectorEdits>,
+    checkboxes: Query<(Entity, &Interaction), (Changed<Interaction>, With<InspectorCheckbox>)>,
+    mut drag_values: Query<(Entity, &Interaction, &mut DragValue)>,
+    color_channels: Query<(Entity, &Interaction, &ColorPickerChannel)>,
+    mut foldouts: Query<
+        (Entity, &Interaction, &mut Foldout, &mut Text, &Parent),
+        Changed<Interaction>,
+    >,
+    children_query: Query<&Children>,
+    mut style_query: Query<&mut Style>,
+) {
+    let dx = mouse_motion
+        .read()
+        .map(|motion| motion.delta.x)
+        .sum::<f32>();
+
+    for (entity, interaction) in &checkboxes {
+        if *interaction == Interaction::Pressed {
+            edits.0.push((entity, InspectorEdit::Toggle));
+        }
+    }
+
+    if dx != 0. {
+        for (entity, interaction, mut drag_value) in &mut drag_values {
+            if *interaction != Interaction::Pressed {
+                continue;
+            }
+            let drag_value = drag_value.as_mut();
+            let mut delta = drag_value.remainder + dx as f64 * drag_value.speed;
+            // Integers are only changed in whole steps, the rest is carried over to the next drag.
+            if drag_value.integer {
+                drag_value.remainder = delta.fract();
+                delta = delta.trunc();
+            }
+            if delta != 0. {
+                edits.0.push((entity, InspectorEdit::AddNumber(delta)));
+            }
+        }
+
+        for (entity, interaction, channel) in &color_channels {
+            if *interaction == Interaction::Pressed {
+                edits.0.push((
+                    entity,
+                    InspectorEdit::AddColorChannel(*channel, dx * channel.speed()),
+                ));
+            }
+        }
+    }
+
+    for (entity, interaction, mut foldout, mut text, parent) in &mut foldouts {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        foldout.open = !foldout.open;
+        text.sections[0].value = foldout.marker().to_string();
+        let display = if foldout.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        let Ok(siblings) = children_query.get(parent.get()) else {
+            continue;
+        };
+        for &sibling in siblings.iter().filter(|&&sibling| sibling != entity) {
+            if let Ok(mut style) = style_query.get_mut(sibling) {
+                style.display = display;
+            }
+        }
+    }
+}
+
+/// Calls `f` with the inspected value of `field`.
+fn read_field<R>(
+    world: &World,
+    field: &InspectorField,
+    f: impl FnOnce(&dyn Reflect) -> R,
+) -> Option<R> {
+    let inspector = world.get::<ReflectInspector>(field.inspector)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let reflect_component = registry.get_type_data::<ReflectComponent>(inspector.component)?;
+    let component = reflect_component.reflect(world.get_entity(inspector.entity)?)?;
+    if field.path.is_empty() {
+        Some(f(component))
+    } else {
+        component.reflect_path(field.path.as_str()).ok().map(f)
+    }
+}
+
+/// Calls `f` with the inspected value of `field` mutably.
+fn write_field(world: &mut World, field: &InspectorField, f: impl FnOnce(&mut dyn Reflect)) {
+    let Some(inspector) = world.get::<ReflectInspector>(field.inspector) else {
+        return;
+    };
+    let target = inspector.entity;
+    let Some(reflect_component) = world
+        .resource::<AppTypeRegistry>()
+        .read()
+        .get_type_data::<ReflectComponent>(inspector.component)
+        .cloned()
+    else {
+        return;
+    };
+    let Some(mut entity) = world.get_entity_mut(target) else {
+        return;
+    };
+    let Some(mut component) = reflect_component.reflect_mut(&mut entity) else {
+        return;
+    };
+    if field.path.is_empty() {
+        f(&mut *component);
+    } else if let Ok(value) = component.reflect_path_mut(field.path.as_str()) {
+        f(value);
+    }
+}
+
+/// Writes the queued edits back to the inspected components.
+fn apply_reflect_inspector_edits(world: &mut World) {
+    let edits = std::mem::take(&mut world.resource_mut::<ReflectInspectorEdits>().0);
+    for (entity, edit) in edits {
+        let Some(field) = world.get::<InspectorField>(entity).cloned() else {
+            continue;
+        };
+        write_field(world, &field, |value| match edit {
+            InspectorEdit::Toggle => {
+                if let Some(value) = value.downcast_mut::<bool>() {
+                    *value = !*value;
+                }
+            }
+            InspectorEdit::AddNumber(delta) => add_to_number(value, delta),
+            InspectorEdit::AddColorChannel(channel, delta) => {
+                if let Some(color) = value.downcast_mut::<Color>() {
+                    let mut hsla = Hsla::from(*color);
+                    channel.apply(&mut hsla, delta);
+                    *color = hsla.into();
+                }
+            }
+        });
+    }
+}
+
+/// What an inspector widget displays.
+enum FieldDisplay {
+    Text(String),
+    Swatch(Color),
+}
+
+/// Updates the text and colors of the inspector widgets from the inspected values.
+fn sync_reflect_inspectors(
+    world: &mut World,
+    fields: &mut QueryState<(
+        Entity,
+        &InspectorField,
+        Has<InspectorCheckbox>,
+        Has<ColorPicker>,
+        Option<&ColorPickerChannel>,
+    )>,
+) {
+    let displays = fields
+        .iter(world)
+        .filter_map(|(entity, field, is_checkbox, is_color_picker, channel)| {
+            read_field(world, field, |value| {
+                if let Some(color) = value.downcast_ref::<Color>() {
+                    if is_color_picker {
+                        return FieldDisplay::Swatch(*color);
+                    }
+                    if let Some(channel) = channel {
+                        return FieldDisplay::Text(channel.label(Hsla::from(*color)));
+                    }
+                }
+                if is_checkbox {
+                    if let Some(value) = value.downcast_ref::<bool>() {
+                        return FieldDisplay::Text(if *value { "[x]" } else { "[ ]" }.to_string());
+                    }
+                }
+                FieldDisplay::Text(number_label(value).unwrap_or_else(|| format!("{value:?}")))
+            })
+            .map(|display| (entity, display))
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, display) in displays {
+        match display {
+            FieldDisplay::Text(value) => {
+                if let Some(mut text) = world.get_mut::<Text>(entity) {
+                    if text.sections[0].value != value {
+                        text.sections[0].value = value;
+                    }
+                }
+            }
+            FieldDisplay::Swatch(color) => {
+                if let Some(mut background_color) = world.get_mut::<BackgroundColor>(entity) {
+                    background_color.set_if_neq(BackgroundColor(color));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Inspected {
+        enabled: bool,
+        speed: f32,
+        count: u8,
+        color: Color,
+        name: String,
+    }
+
+    fn find_field<C: Component>(world: &mut World, path: &str) -> Entity {
+        world
+            .query_filtered::<(Entity, &InspectorField), With<C>>()
+            .iter(world)
+            .find(|(_, field)| field.path == path)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    #[test]
+    fn inspector_edits_are_written_back_to_the_component() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<ReflectInspectorEdits>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Inspected>();
+
+        let target = world.spawn(Inspected::default()).id();
+        world.spawn((
+            NodeBundle::default(),
+            ReflectInspector::new::<Inspected>(target),
+        ));
+        world.run_system_once(build_reflect_inspectors);
+
+        let checkbox = find_field::<InspectorCheckbox>(&mut world, ".enabled");
+        let speed = find_field::<DragValue>(&mut world, ".speed");
+        let count = find_field::<DragValue>(&mut world, ".count");
+        let swatch = find_field::<ColorPicker>(&mut world, ".color");
+        let name = find_field::<crate::Node>(&mut world, ".name");
+
+        world.resource_mut::<ReflectInspectorEdits>().0.extend([
+            (checkbox, InspectorEdit::Toggle),
+            (speed, InspectorEdit::AddNumber(0.5)),
+            (count, InspectorEdit::AddNumber(-3.)),
+        ]);
+        world.run_system_once(apply_reflect_inspector_edits);
+        world.run_system_once(sync_reflect_inspectors);
+
+        let inspected = world.get::<Inspected>(target).unwrap();
+        assert!(inspected.enabled);
+        assert_eq!(inspected.speed, 0.5);
+        // integers saturate at their bounds
+        assert_eq!(inspected.count, 0);
+
+        assert_eq!(
+            world.get::<Text>(checkbox).unwrap().sections[0].value,
+            "[x]"
+        );
+        assert_eq!(world.get::<Text>(speed).unwrap().sections[0].value, "0.500");
+        assert_eq!(
+            world.get::<BackgroundColor>(swatch).unwrap().0,
+            Color::default()
+        );
+        assert_eq!(world.get::<Text>(name).unwrap().sections[0].value, "\"\"");
+    }
+}
@@ -0,0 +1,156 @@
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    render_resource::{Buffer, BufferAddress, BufferDescriptor, BufferUsages},
+    renderer::{RenderDevice, RenderQueue},
+};
+use bytemuck::{must_cast_slice, NoUninit};
+
+/// The smallest number of elements allocated for a [`UiBufferVec`].
+const MIN_CAPACITY: usize = 64;
+
+/// The number of consecutive frames a [`UiBufferVec`] must use at most a quarter of its capacity before it
+/// is shrunk.
+const SHRINK_AFTER_FRAMES: u32 = 300;
+
+/// The number of bytes uploaded to, and allocated for, the UI vertex and index buffers in the most recent frame.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiBufferStats {
+    /// The number of bytes written to the GPU.
+    pub bytes_uploaded: usize,
+    /// The total size of the GPU buffers.
+    pub bytes_allocated: usize,
+    /// The number of GPU buffers that were created or resized.
+    pub reallocations: usize,
+}
+
+/// Resets the [`UiBufferStats`] before the UI buffers are prepared.
+pub fn reset_ui_buffer_stats(mut stats: ResMut<UiBufferStats>) {
+    *stats = UiBufferStats::default();
+}
+
+/// A vertex or index buffer that is filled again every frame.
+///
+/// Unlike [`RawBufferVec`](bevy_render::render_resource::RawBufferVec), the GPU buffer grows by powers of two
+/// so a growing UI doesn't reallocate it every frame, and is only shrunk after it has been mostly unused for
+/// [`SHRINK_AFTER_FRAMES`] frames in a row. The allocation of the values in system memory follows the buffer.
+pub(crate) struct UiBufferVec<T: NoUninit> {
+    values: Vec<T>,
+    buffer: Option<Buffer>,
+    /// The number of elements that fit in `buffer`.
+    capacity: usize,
+    /// The number of consecutive frames in which at most a quarter of the capacity was used.
+    underused_frames: u32,
+    buffer_usage: BufferUsages,
+    label: &'static str,
+}
+
+impl<T: NoUninit> UiBufferVec<T> {
+    pub(crate) fn new(buffer_usage: BufferUsages, label: &'static str) -> Self {
+        Self {
+            values: Vec::new(),
+            buffer: None,
+            capacity: 0,
+            underused_frames: 0,
+            buffer_usage,
+            label,
+        }
+    }
+
+    /// Returns the GPU buffer, if any values have been uploaded.
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.buffer.as_ref()
+    }
+
+    pub(crate) fn push(&mut self, value: T) {
+        self.values.push(value);
+    }
+
+    /// Removes all the values, keeping the allocations.
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Returns the capacity the GPU buffer should have to hold `len` values.
+    fn next_capacity(&mut self, len: usize) -> usize {
+        if self.capacity < len {
+            self.underused_frames = 0;
+            return len.next_power_of_two().max(MIN_CAPACITY);
+        }
+        if MIN_CAPACITY < self.capacity && len <= self.capacity / 4 {
+            self.underused_frames += 1;
+        } else {
+            self.underused_frames = 0;
+        }
+        if SHRINK_AFTER_FRAMES <= self.underused_frames {
+            self.underused_frames = 0;
+            return len.next_power_of_two().max(MIN_CAPACITY);
+        }
+        self.capacity
+    }
+
+    /// Uploads the values to the GPU buffer, growing or shrinking it first if needed.
+    pub(crate) fn write_buffer(
+        &mut self,
+        device: &RenderDevice,
+        queue: &RenderQueue,
+        stats: &mut UiBufferStats,
+    ) {
+        let item_size = std::mem::size_of::<T>();
+        let capacity = self.next_capacity(self.values.len());
+        if capacity != self.capacity || (self.buffer.is_none() && !self.values.is_empty()) {
+            self.capacity = capacity;
+            self.values.shrink_to(capacity);
+            self.buffer = Some(device.create_buffer(&BufferDescriptor {
+                label: Some(self.label),
+                size: (item_size * capacity) as BufferAddress,
+                usage: BufferUsages::COPY_DST | self.buffer_usage,
+                mapped_at_creation: false,
+            }));
+            stats.reallocations += 1;
+        }
+        if let Some(buffer) = &self.buffer {
+            stats.bytes_allocated += item_size * self.capacity;
+            if !self.values.is_empty() {
+                let bytes: &[u8] = must_cast_slice(&self.values);
+                queue.write_buffer(buffer, 0, bytes);
+                stats.bytes_uploaded += bytes.len();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UiBufferVec, MIN_CAPACITY, SHRINK_AFTER_FRAMES};
+    use bevy_render::render_resource::BufferUsages;
+
+    #[test]
+    fn ui_buffer_capacity() {
+        fn write(buffer: &mut UiBufferVec<u32>, len: usize) -> usize {
+            buffer.capacity = buffer.next_capacity(len);
+            buffer.capacity
+        }
+
+        let mut buffer = UiBufferVec::<u32>::new(BufferUsages::VERTEX, "test");
+
+        assert_eq!(write(&mut buffer, 10), MIN_CAPACITY);
+        assert_eq!(write(&mut buffer, 1000), 1024);
+        // Growing within the capacity doesn't reallocate
+        assert_eq!(write(&mut buffer, 1024), 1024);
+        assert_eq!(write(&mut buffer, 1025), 2048);
+
+        // Small drops never shrink the buffer
+        for _ in 0..2 * SHRINK_AFTER_FRAMES {
+            assert_eq!(write(&mut buffer, 600), 2048);
+        }
+        // Large drops only shrink it once they are sustained
+        for _ in 1..SHRINK_AFTER_FRAMES {
+            assert_eq!(write(&mut buffer, 100), 2048);
+        }
+        write(&mut buffer, 600);
+        for _ in 1..SHRINK_AFTER_FRAMES {
+            assert_eq!(write(&mut buffer, 100), 2048);
+        }
+        assert_eq!(write(&mut buffer, 100), 128);
+    }
+}
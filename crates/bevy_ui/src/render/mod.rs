@@ -1,3 +1,4 @@
+mod buffer;
 mod pipeline;
 mod render_pass;
 mod ui_material_pipeline;
@@ -15,21 +16,26 @@ use bevy_render::{
     ExtractSchedule, Render,
 };
 use bevy_sprite::{SpriteAssetEvents, TextureAtlas};
+pub use buffer::{reset_ui_buffer_stats, UiBufferStats};
 pub use pipeline::*;
 pub use render_pass::*;
 pub use ui_material_pipeline::*;
 
-use crate::graph::{NodeUi, SubGraphUi};
+use crate::graph::{NodeUi, SubGraphUi, SubGraphUiBeforePostProcessing, SubGraphUiMainPass};
 use crate::{
-    texture_slice::ComputedTextureSlices, BackgroundColor, BorderColor, BorderRadius,
-    CalculatedClip, ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiImage,
-    UiScale, Val,
+    geometry::{resolve_border_radius, resolve_border_thickness, ui_logical_viewport_size},
+    texture_slice::{compute_border_image_slices, slice_geometry, ComputedTextureSlices},
+    widget::UiImageSize,
+    BackgroundColor, BorderColor, BorderImage, BorderRadius, CalculatedClip, ClipShape,
+    ContentSize, DefaultUiCamera, ImageRepeat, Node, ObjectFit, Outline, RootFontSize, Style,
+    TargetCamera, UiDepth, UiImage, UiImageAtlases, UiLayer, UiScale, UiStack,
 };
 
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
 use bevy_math::{FloatOrd, Mat4, Rect, URect, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_render::{
     camera::Camera,
@@ -39,15 +45,16 @@ use bevy_render::{
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
     texture::Image,
-    view::{ExtractedView, ViewUniforms},
+    view::{ExtractedView, Msaa, ViewUniforms},
     Extract, RenderApp, RenderSet,
 };
 use bevy_sprite::TextureAtlasLayout;
 #[cfg(feature = "bevy_text")]
-use bevy_text::{PositionedGlyph, Text, TextLayoutInfo};
+use bevy_text::{FontRenderMode, PositionedGlyph, Text, TextLayoutInfo, TextOutline, TextShadow};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
+use smallvec::{smallvec, SmallVec};
 use std::ops::Range;
 
 pub mod graph {
@@ -56,9 +63,19 @@ pub mod graph {
     #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
     pub struct SubGraphUi;
 
+    /// The sub graph drawing the UI with [`UiLayer::BeforePostProcessing`](crate::UiLayer::BeforePostProcessing).
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
+    pub struct SubGraphUiBeforePostProcessing;
+
+    /// The sub graph drawing the UI with [`UiLayer::MainPass`](crate::UiLayer::MainPass).
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
+    pub struct SubGraphUiMainPass;
+
     #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
     pub enum NodeUi {
         UiPass,
+        UiPassBeforePostProcessing,
+        UiPassMainPass,
     }
 }
 
@@ -66,10 +83,13 @@ pub const UI_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(130128470471
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum RenderUiSystem {
+    /// Extracts the backgrounds, borders and outlines of UI nodes.
     ExtractBackgrounds,
     ExtractImages,
     ExtractBorders,
     ExtractText,
+    /// Removes extracted UI nodes that are outside of their camera's viewport or clipping rect.
+    CullNodes,
 }
 
 pub fn build_ui_render(app: &mut App) {
@@ -85,6 +105,10 @@ pub fn build_ui_render(app: &mut App) {
         .init_resource::<UiMeta>()
         .init_resource::<ExtractedUiNodes>()
         .allow_ambiguous_resource::<ExtractedUiNodes>()
+        .init_resource::<ExtractedUiLayers>()
+        .init_resource::<UiCullingStats>()
+        .init_resource::<UiBufferStats>()
+        .init_resource::<UiBatchStats>()
         .init_resource::<DrawFunctions<TransparentUi>>()
         .init_resource::<ViewSortedRenderPhases<TransparentUi>>()
         .add_render_command::<TransparentUi, DrawUi>()
@@ -95,6 +119,7 @@ pub fn build_ui_render(app: &mut App) {
                 RenderUiSystem::ExtractImages,
                 RenderUiSystem::ExtractBorders,
                 RenderUiSystem::ExtractText,
+                RenderUiSystem::CullNodes,
             )
                 .chain(),
         )
@@ -102,12 +127,13 @@ pub fn build_ui_render(app: &mut App) {
             ExtractSchedule,
             (
                 extract_default_ui_camera_view,
-                extract_uinode_background_colors.in_set(RenderUiSystem::ExtractBackgrounds),
+                extract_ui_layers,
+                extract_uinode_boxes.in_set(RenderUiSystem::ExtractBackgrounds),
                 extract_uinode_images.in_set(RenderUiSystem::ExtractImages),
-                extract_uinode_borders.in_set(RenderUiSystem::ExtractBorders),
-                extract_uinode_outlines.in_set(RenderUiSystem::ExtractBorders),
+                extract_uinode_border_images.in_set(RenderUiSystem::ExtractBorders),
                 #[cfg(feature = "bevy_text")]
                 extract_uinode_text.in_set(RenderUiSystem::ExtractText),
+                cull_extracted_uinodes.in_set(RenderUiSystem::CullNodes),
             ),
         )
         .add_systems(
@@ -115,13 +141,20 @@ pub fn build_ui_render(app: &mut App) {
             (
                 queue_uinodes.in_set(RenderSet::Queue),
                 sort_phase_system::<TransparentUi>.in_set(RenderSet::PhaseSort),
+                merge_ui_batches
+                    .in_set(RenderSet::PhaseSort)
+                    .after(sort_phase_system::<TransparentUi>),
+                reset_ui_buffer_stats.in_set(RenderSet::PrepareResources),
                 prepare_uinodes.in_set(RenderSet::PrepareBindGroups),
             ),
         );
 
     // Render graph
-    let ui_graph_2d = get_ui_graph(render_app);
-    let ui_graph_3d = get_ui_graph(render_app);
+    let ui_graph_2d = get_ui_graph(render_app, UiLayer::Overlay);
+    let ui_graph_3d = get_ui_graph(render_app, UiLayer::Overlay);
+    let before_post_processing_graph_2d = get_ui_graph(render_app, UiLayer::BeforePostProcessing);
+    let before_post_processing_graph_3d = get_ui_graph(render_app, UiLayer::BeforePostProcessing);
+    let main_pass_graph_3d = get_ui_graph(render_app, UiLayer::MainPass);
     let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
 
     if let Some(graph_2d) = graph.get_sub_graph_mut(Core2d) {
@@ -130,6 +163,20 @@ pub fn build_ui_render(app: &mut App) {
         graph_2d.add_node_edge(Node2d::EndMainPass, NodeUi::UiPass);
         graph_2d.add_node_edge(Node2d::EndMainPassPostProcessing, NodeUi::UiPass);
         graph_2d.add_node_edge(NodeUi::UiPass, Node2d::Upscaling);
+
+        graph_2d.add_sub_graph(
+            SubGraphUiBeforePostProcessing,
+            before_post_processing_graph_2d,
+        );
+        graph_2d.add_node(
+            NodeUi::UiPassBeforePostProcessing,
+            RunGraphOnViewNode::new(SubGraphUiBeforePostProcessing),
+        );
+        graph_2d.add_node_edge(
+            Node2d::MainTransparentPass,
+            NodeUi::UiPassBeforePostProcessing,
+        );
+        graph_2d.add_node_edge(NodeUi::UiPassBeforePostProcessing, Node2d::EndMainPass);
     }
 
     if let Some(graph_3d) = graph.get_sub_graph_mut(Core3d) {
@@ -138,11 +185,34 @@ pub fn build_ui_render(app: &mut App) {
         graph_3d.add_node_edge(Node3d::EndMainPass, NodeUi::UiPass);
         graph_3d.add_node_edge(Node3d::EndMainPassPostProcessing, NodeUi::UiPass);
         graph_3d.add_node_edge(NodeUi::UiPass, Node3d::Upscaling);
+
+        graph_3d.add_sub_graph(
+            SubGraphUiBeforePostProcessing,
+            before_post_processing_graph_3d,
+        );
+        graph_3d.add_node(
+            NodeUi::UiPassBeforePostProcessing,
+            RunGraphOnViewNode::new(SubGraphUiBeforePostProcessing),
+        );
+        graph_3d.add_node_edge(
+            Node3d::MainTransparentPass,
+            NodeUi::UiPassBeforePostProcessing,
+        );
+        graph_3d.add_node_edge(NodeUi::UiPassBeforePostProcessing, Node3d::EndMainPass);
+
+        // Only 3D cameras have a depth buffer to test the UI against
+        graph_3d.add_sub_graph(SubGraphUiMainPass, main_pass_graph_3d);
+        graph_3d.add_node(
+            NodeUi::UiPassMainPass,
+            RunGraphOnViewNode::new(SubGraphUiMainPass),
+        );
+        graph_3d.add_node_edge(Node3d::MainTransparentPass, NodeUi::UiPassMainPass);
+        graph_3d.add_node_edge(NodeUi::UiPassMainPass, NodeUi::UiPassBeforePostProcessing);
     }
 }
 
-fn get_ui_graph(render_app: &mut SubApp) -> RenderGraph {
-    let ui_pass_node = UiPassNode::new(render_app.world_mut());
+fn get_ui_graph(render_app: &mut SubApp, layer: UiLayer) -> RenderGraph {
+    let ui_pass_node = UiPassNode::new(render_app.world_mut()).with_layer(layer);
     let mut ui_graph = RenderGraph::default();
     ui_graph.add_node(NodeUi::UiPass, ui_pass_node);
     ui_graph
@@ -154,67 +224,304 @@ fn get_ui_graph(render_app: &mut SubApp) -> RenderGraph {
 pub enum NodeType {
     Rect,
     Border,
+    /// One edge of a border with a different color on each edge.
+    /// The index of the edge in the order left, top, right, bottom.
+    BorderEdge(usize),
+    /// A glyph of text with `FontRenderMode::Sdf`, whose texture's alpha channel is a signed distance field.
+    SdfGlyph,
 }
 
+/// An instance of the UI pipeline extracted to the render world, drawn as a textured quad.
+#[derive(Clone)]
 pub struct ExtractedUiNode {
+    /// The position of the instance in the UI's draw order. Instances with greater stack indices are drawn on top.
+    ///
+    /// This is the [`Node::stack_index`] of the node the instance belongs to, or an index returned by
+    /// [`ExtractedUiNodes::reserve_stack_range`].
     pub stack_index: u32,
     pub transform: Mat4,
     pub color: LinearRgba,
     pub rect: Rect,
     pub image: AssetId<Image>,
     pub atlas_size: Option<Vec2>,
-    pub clip: Option<Rect>,
+    pub clip: Option<CalculatedClip>,
     pub flip_x: bool,
     pub flip_y: bool,
     // Camera to render this UI node to. By the time it is extracted,
     // it is defaulted to a single camera if only one exists.
     // Nodes with ambiguous camera will be ignored.
     pub camera_entity: Entity,
-    /// Border radius of the UI node.
+    /// Border radius of the UI node, with the horizontal and vertical radius of each corner.
     /// Ordering: top left, top right, bottom right, bottom left.
-    pub border_radius: [f32; 4],
+    pub border_radius: [Vec2; 4],
     /// Border thickness of the UI node.
     /// Ordering: left, top, right, bottom.
     pub border: [f32; 4],
+    /// The number of times the texture repeats along each axis, or `None` if the texture is stretched to
+    /// fill the node.
+    pub repeat: Option<Vec2>,
     pub node_type: NodeType,
 }
 
+/// The UI instances extracted for the current frame.
+///
+/// This is the extension point for drawing custom instances with the UI pipeline. Systems in [`ExtractSchedule`]
+/// ordered after [`RenderUiSystem::ExtractText`] and before [`RenderUiSystem::CullNodes`] can add instances with
+/// [`ExtractedUiNodes::push`], keyed by an entity spawned in the render world. To draw them at the right depth:
+/// - [`ExtractedUiNodes::push_after`] draws an instance on top of the node that owns it, below the nodes stacked
+///   above that node.
+/// - [`ExtractedUiNodes::reserve_stack_range`] allocates stack indices above every UI node, for overlays.
+///
+/// The instances are removed after they are prepared each frame.
 #[derive(Resource, Default)]
 pub struct ExtractedUiNodes {
     pub uinodes: EntityHashMap<ExtractedUiNode>,
+    /// The order in which instances pushed with [`ExtractedUiNodes::push_after`] were pushed.
+    pushed_after: EntityHashMap<u32>,
+    /// The next stack index returned by [`ExtractedUiNodes::reserve_stack_range`], if any were reserved this frame.
+    next_reserved_index: Option<u32>,
 }
 
-pub fn extract_uinode_background_colors(
+impl ExtractedUiNodes {
+    /// Adds an instance, drawn in the order of its [`ExtractedUiNode::stack_index`].
+    ///
+    /// Instances with the same stack index as a node may be drawn above or below the node's own instances, use
+    /// [`ExtractedUiNodes::push_after`] to draw on top of them.
+    pub fn push(&mut self, entity: Entity, extracted_uinode: ExtractedUiNode) {
+        self.uinodes.insert(entity, extracted_uinode);
+    }
+
+    /// Adds an instance drawn on top of the background, border, image and text of `owner`, and below the nodes
+    /// stacked above it. Instances pushed after the same node are drawn in the order they were pushed.
+    ///
+    /// The [`ExtractedUiNode::stack_index`] of the instance is set to the stack index of `owner`.
+    pub fn push_after(
+        &mut self,
+        owner: &Node,
+        entity: Entity,
+        mut extracted_uinode: ExtractedUiNode,
+    ) {
+        extracted_uinode.stack_index = owner.stack_index();
+        let order = self.pushed_after.len() as u32;
+        self.pushed_after.insert(entity, order);
+        self.uinodes.insert(entity, extracted_uinode);
+    }
+
+    /// Reserves `len` consecutive stack indices above every node in `ui_stack` and every range reserved
+    /// earlier this frame.
+    ///
+    /// Instances given the reserved stack indices are drawn over all UI nodes, with the default [`UiLayer`].
+    pub fn reserve_stack_range(&mut self, ui_stack: &UiStack, len: u32) -> Range<u32> {
+        let start = self
+            .next_reserved_index
+            .unwrap_or(0)
+            .max(ui_stack.uinodes.len() as u32);
+        self.next_reserved_index = Some(start + len);
+        start..start + len
+    }
+
+    /// Returns the key that orders the instance `entity` among the instances with its stack index.
+    ///
+    /// Instances pushed with [`ExtractedUiNodes::push_after`] are sorted halfway between their owner and the
+    /// next stack index.
+    fn stack_sort_key(&self, entity: Entity, stack_index: u32) -> (FloatOrd, u32) {
+        match self.pushed_after.get(&entity) {
+            Some(&order) => (FloatOrd(stack_index as f32 + 0.5), order),
+            None => (FloatOrd(stack_index as f32), entity.index()),
+        }
+    }
+
+    /// Removes all the instances, and releases the reserved stack indices.
+    pub fn clear(&mut self) {
+        self.uinodes.clear();
+        self.pushed_after.clear();
+        self.next_reserved_index = None;
+    }
+}
+
+impl ExtractedUiNode {
+    /// Returns the axis-aligned bounding rectangle of the node's quad, in logical UI coordinates.
+    pub fn bounding_rect(&self) -> Rect {
+        let size = self.rect.size().extend(1.);
+        QUAD_VERTEX_POSITIONS
+            .iter()
+            .map(|&corner| (self.transform * (corner * size).extend(1.)).xy())
+            .fold(
+                Rect {
+                    min: Vec2::INFINITY,
+                    max: Vec2::NEG_INFINITY,
+                },
+                |bounds, point| Rect {
+                    min: bounds.min.min(point),
+                    max: bounds.max.max(point),
+                },
+            )
+    }
+}
+
+/// The number of extracted UI nodes that were kept or culled by [`cull_extracted_uinodes`] in the
+/// most recent frame.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiCullingStats {
+    /// The number of nodes that intersect their camera's viewport and clipping rect.
+    pub visible: usize,
+    /// The number of nodes that were removed before being prepared.
+    pub culled: usize,
+}
+
+/// Removes extracted UI nodes whose bounds don't intersect their camera's viewport, or their
+/// [`CalculatedClip`] if they are clipped, so that nodes outside the view aren't prepared and uploaded.
+///
+/// Nodes with cameras that have no viewport size aren't culled.
+pub fn cull_extracted_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
-    camera_query: Extract<Query<(Entity, &Camera)>>,
+    mut stats: ResMut<UiCullingStats>,
+    camera_query: Extract<Query<&Camera>>,
+    ui_scale: Extract<Res<UiScale>>,
+    mut viewports: Local<EntityHashMap<Option<Rect>>>,
+) {
+    viewports.clear();
+    let before = extracted_uinodes.uinodes.len();
+    extracted_uinodes.uinodes.retain(|_, extracted_uinode| {
+        let viewport = *viewports
+            .entry(extracted_uinode.camera_entity)
+            .or_insert_with(|| {
+                let size = camera_query
+                    .get(extracted_uinode.camera_entity)
+                    .ok()?
+                    .logical_viewport_size()?
+                    / ui_scale.0;
+                Some(Rect::from_corners(Vec2::ZERO, size))
+            });
+        let Some(mut visible_rect) = viewport else {
+            return true;
+        };
+        if let Some(clip) = extracted_uinode.clip {
+            visible_rect = visible_rect.intersect(clip.clip);
+        }
+        let bounds = extracted_uinode.bounding_rect();
+        // Comparisons with NaN are false, so nodes with invalid transforms are kept
+        !(bounds.max.x < visible_rect.min.x
+            || visible_rect.max.x < bounds.min.x
+            || bounds.max.y < visible_rect.min.y
+            || visible_rect.max.y < bounds.min.y)
+    });
+    let visible = extracted_uinodes.uinodes.len();
+    *stats = UiCullingStats {
+        visible,
+        culled: before - visible,
+    };
+}
+
+/// The [`UiLayer`] of each extracted UI node, and the [`UiDepth`] of those drawn with [`UiLayer::MainPass`],
+/// indexed by its stack index.
+#[derive(Resource, Default)]
+pub struct ExtractedUiLayers {
+    layers: Vec<UiLayer>,
+    depths: Vec<f32>,
+}
+
+impl ExtractedUiLayers {
+    /// Returns the layer of the UI node at `stack_index`.
+    pub fn get(&self, stack_index: usize) -> UiLayer {
+        self.layers.get(stack_index).copied().unwrap_or_default()
+    }
+
+    /// Returns the [`UiDepth`] of the UI node at `stack_index`, if it is drawn with [`UiLayer::MainPass`].
+    pub fn depth(&self, stack_index: usize) -> Option<f32> {
+        (self.get(stack_index) == UiLayer::MainPass)
+            .then(|| self.depths.get(stack_index).copied())
+            .flatten()
+    }
+}
+
+pub fn extract_ui_layers(
+    mut extracted_ui_layers: ResMut<ExtractedUiLayers>,
+    ui_stack: Extract<Res<UiStack>>,
+    uinode_query: Extract<Query<(&Node, &UiLayer, Option<&UiDepth>)>>,
+) {
+    let ExtractedUiLayers { layers, depths } = &mut *extracted_ui_layers;
+    layers.clear();
+    layers.resize(ui_stack.uinodes.len(), UiLayer::default());
+    depths.clear();
+    depths.resize(ui_stack.uinodes.len(), UiDepth::DEFAULT.0);
+    for (uinode, layer, depth) in &uinode_query {
+        let index = uinode.stack_index as usize;
+        if let Some(entry) = layers.get_mut(index) {
+            *entry = *layer;
+            depths[index] = depth.copied().unwrap_or_default().0;
+        }
+    }
+}
+
+/// The instances extracted for the background, border and outline of a node by [`extract_uinode_boxes`], kept in
+/// its extraction cache.
+pub struct ExtractedUiNodeBox {
+    background: Option<ExtractedUiNode>,
+    /// The whole border, or each visible edge of a border with a different color on each edge.
+    borders: SmallVec<[ExtractedUiNode; 1]>,
+    /// The visible edges of the outline.
+    outline: SmallVec<[ExtractedUiNode; 4]>,
+    camera_entity: Entity,
+    /// Which of the optional inputs the node had when it was extracted, since removing a component isn't detected.
+    components: [bool; 7],
+}
+
+/// Extracts the background, border and outline of each UI node in a single pass.
+///
+/// The three are extracted together so the inputs they share, like the camera's viewport and the resolved border
+/// thickness, are only computed once per node. The extracted instances are retained between frames in
+/// `extraction_cache`, and only computed again for nodes whose [`Node`], [`GlobalTransform`], [`BackgroundColor`],
+/// [`BorderColor`], [`Outline`] or other inputs changed, so static UIs are cheap to extract.
+///
+/// Only the extraction is cached. The cached instances are still added to [`ExtractedUiNodes`] every frame, and
+/// queued, batched and written to the instance buffers like those of every other UI node.
+///
+/// Borders aren't drawn for nodes with a [`ContentSize`].
+#[allow(clippy::too_many_arguments)]
+pub fn extract_uinode_boxes(
+    mut commands: Commands,
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    mut extraction_cache: Local<EntityHashMap<ExtractedUiNodeBox>>,
+    camera_query: Extract<Query<(Entity, Ref<Camera>)>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     ui_scale: Extract<Res<UiScale>>,
+    root_font_size: Extract<Res<RootFontSize>>,
     uinode_query: Extract<
         Query<(
             Entity,
-            &Node,
-            &GlobalTransform,
-            &ViewVisibility,
-            Option<&CalculatedClip>,
+            (Ref<Node>, Ref<GlobalTransform>, &ViewVisibility, Ref<Style>),
+            Option<Ref<CalculatedClip>>,
             Option<&TargetCamera>,
-            &BackgroundColor,
-            Option<&BorderRadius>,
-            &Style,
-            Option<&Parent>,
+            Option<Ref<BackgroundColor>>,
+            Option<Ref<BorderColor>>,
+            Option<Ref<BorderRadius>>,
+            Option<Ref<Outline>>,
+            Option<Ref<ClipShape>>,
+            Has<ContentSize>,
+            Option<Ref<Parent>>,
         )>,
     >,
-    node_query: Extract<Query<&Node>>,
+    node_query: Extract<Query<Ref<Node>>>,
 ) {
+    let image = AssetId::<Image>::default();
+    // Viewport units and the scale factor affect every node
+    let invalidate_all = ui_scale.is_changed()
+        || root_font_size.is_changed()
+        || camera_query.iter().any(|(_, camera)| camera.is_changed());
+    let mut previous = std::mem::take(&mut *extraction_cache);
+
     for (
         entity,
-        uinode,
-        transform,
-        view_visibility,
+        (uinode, transform, view_visibility, style),
         clip,
         camera,
         background_color,
+        border_color,
         border_radius,
-        style,
+        outline,
+        clip_shape,
+        has_content_size,
         parent,
     ) in &uinode_query
     {
@@ -223,69 +530,256 @@ pub fn extract_uinode_background_colors(
             continue;
         };
 
-        // Skip invisible backgrounds
-        if !view_visibility.get() || background_color.0.is_fully_transparent() {
+        // Skip invisible nodes, and nodes without a background, border or outline
+        if !view_visibility.get()
+            || (background_color.is_none() && border_color.is_none() && outline.is_none())
+        {
             continue;
         }
 
-        let ui_logical_viewport_size = camera_query
-            .get(camera_entity)
-            .ok()
-            .and_then(|(_, c)| c.logical_viewport_size())
-            .unwrap_or(Vec2::ZERO)
-            // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
-            // so we have to divide by `UiScale` to get the size of the UI viewport.
-            / ui_scale.0;
+        let parent_node = parent
+            .as_ref()
+            .and_then(|parent| node_query.get(parent.get()).ok());
+
+        let components = [
+            clip.is_some(),
+            background_color.is_some(),
+            border_color.is_some(),
+            border_radius.is_some(),
+            outline.is_some(),
+            clip_shape.is_some(),
+            has_content_size,
+        ];
+        let changed = invalidate_all
+            || uinode.is_changed()
+            || transform.is_changed()
+            || style.is_changed()
+            || clip.as_ref().is_some_and(|clip| clip.is_changed())
+            || background_color
+                .as_ref()
+                .is_some_and(|background_color| background_color.is_changed())
+            || border_color
+                .as_ref()
+                .is_some_and(|border_color| border_color.is_changed())
+            || border_radius
+                .as_ref()
+                .is_some_and(|border_radius| border_radius.is_changed())
+            || outline.as_ref().is_some_and(|outline| outline.is_changed())
+            || clip_shape
+                .as_ref()
+                .is_some_and(|clip_shape| clip_shape.is_changed())
+            || parent.as_ref().is_some_and(|parent| parent.is_changed())
+            || parent_node
+                .as_ref()
+                .is_some_and(|parent_node| parent_node.is_changed());
+
+        let mut node_box = match previous.remove(&entity) {
+            // The camera and stack index can change without change detection, so those are checked separately.
+            Some(node_box)
+                if !changed
+                    && node_box.camera_entity == camera_entity
+                    && node_box.components == components =>
+            {
+                node_box
+            }
+            _ => {
+                let ui_logical_viewport_size = ui_logical_viewport_size(
+                    camera_query
+                        .get(camera_entity)
+                        .ok()
+                        .map(|(_, camera)| camera.into_inner()),
+                    ui_scale.0,
+                );
+                let clip = clip.as_deref().copied();
+                let world_from_local = transform.compute_matrix();
+                let instance = |color: LinearRgba, node_type| ExtractedUiNode {
+                    stack_index: uinode.stack_index,
+                    transform: world_from_local,
+                    color,
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: uinode.size(),
+                    },
+                    image,
+                    atlas_size: None,
+                    clip,
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border_radius: [Vec2::ZERO; 4],
+                    border: [0.; 4],
+                    repeat: None,
+                    node_type,
+                };
 
-        // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
-        // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
-        let parent_width = parent
-            .and_then(|parent| node_query.get(parent.get()).ok())
-            .map(|parent_node| parent_node.size().x)
-            .unwrap_or(ui_logical_viewport_size.x);
-        let left =
-            resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size);
-        let right =
-            resolve_border_thickness(style.border.right, parent_width, ui_logical_viewport_size);
-        let top =
-            resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size);
-        let bottom =
-            resolve_border_thickness(style.border.bottom, parent_width, ui_logical_viewport_size);
+                // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
+                // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
+                let parent_width = parent_node
+                    .as_ref()
+                    .map(|parent_node| parent_node.size().x)
+                    .unwrap_or(ui_logical_viewport_size.x);
+                let border = [
+                    style.border.left,
+                    style.border.top,
+                    style.border.right,
+                    style.border.bottom,
+                ]
+                .map(|value| {
+                    resolve_border_thickness(
+                        value.scale_lengths(uinode.root_scale()),
+                        parent_width,
+                        ui_logical_viewport_size,
+                        root_font_size.0,
+                    )
+                });
+
+                let background = background_color
+                    .as_ref()
+                    .filter(|background_color| !background_color.0.is_fully_transparent())
+                    .map(|background_color| ExtractedUiNode {
+                        rect: Rect {
+                            min: Vec2::ZERO,
+                            max: uinode.calculated_size,
+                        },
+                        border,
+                        border_radius: resolve_fill_radius(
+                            clip_shape.as_deref(),
+                            border_radius.as_deref(),
+                            uinode.size(),
+                            ui_logical_viewport_size,
+                            ui_scale.0 * uinode.root_scale(),
+                            root_font_size.0,
+                        ),
+                        ..instance(background_color.0.into(), NodeType::Rect)
+                    });
 
-        let border = [left, top, right, bottom];
+                let mut borders = SmallVec::new();
+                if let (Some(border_color), Some(border_radius), false) =
+                    (&border_color, &border_radius, has_content_size)
+                {
+                    if !border_color.is_fully_transparent()
+                        && uinode.size().x > 0.
+                        && uinode.size().y > 0.
+                        && border != [0.; 4]
+                    {
+                        let border_radius = clamp_radius(
+                            resolve_border_radius(
+                                border_radius,
+                                uinode.size(),
+                                ui_logical_viewport_size,
+                                ui_scale.0 * uinode.root_scale(),
+                                root_font_size.0,
+                            ),
+                            uinode.size(),
+                            border.into(),
+                        );
+                        // A uniform border is drawn in one piece, otherwise each visible edge is drawn separately
+                        let edges: SmallVec<[_; 4]> = if border_color.is_uniform() {
+                            smallvec![(NodeType::Border, border_color.top)]
+                        } else {
+                            border_color
+                                .edges()
+                                .into_iter()
+                                .enumerate()
+                                .filter(|&(edge, color)| {
+                                    0. < border[edge] && !color.is_fully_transparent()
+                                })
+                                .map(|(edge, color)| (NodeType::BorderEdge(edge), color))
+                                .collect()
+                        };
+                        borders.extend(edges.into_iter().map(|(node_type, color)| {
+                            ExtractedUiNode {
+                                border,
+                                border_radius,
+                                ..instance(color.into(), node_type)
+                            }
+                        }));
+                    }
+                }
 
-        let border_radius = if let Some(border_radius) = border_radius {
-            resolve_border_radius(
-                border_radius,
-                uinode.size(),
-                ui_logical_viewport_size,
-                ui_scale.0,
-            )
-        } else {
-            [0.; 4]
+                let mut outline_edges = SmallVec::new();
+                if let Some(outline) = outline.as_ref().filter(|outline| {
+                    !outline.color.is_fully_transparent() && uinode.outline_width != 0.
+                }) {
+                    let inner_rect = Rect::from_center_size(
+                        Vec2::ZERO,
+                        uinode.size() + 2. * uinode.outline_offset,
+                    );
+                    let outer_rect = inner_rect.inflate(uinode.outline_width());
+                    let edges = [
+                        // Left edge
+                        Rect::new(
+                            outer_rect.min.x,
+                            outer_rect.min.y,
+                            inner_rect.min.x,
+                            outer_rect.max.y,
+                        ),
+                        // Right edge
+                        Rect::new(
+                            inner_rect.max.x,
+                            outer_rect.min.y,
+                            outer_rect.max.x,
+                            outer_rect.max.y,
+                        ),
+                        // Top edge
+                        Rect::new(
+                            inner_rect.min.x,
+                            outer_rect.min.y,
+                            inner_rect.max.x,
+                            inner_rect.min.y,
+                        ),
+                        // Bottom edge
+                        Rect::new(
+                            inner_rect.min.x,
+                            inner_rect.max.y,
+                            inner_rect.max.x,
+                            outer_rect.max.y,
+                        ),
+                    ];
+                    outline_edges.extend(
+                        edges
+                            .into_iter()
+                            .filter(|edge| edge.min.x < edge.max.x && edge.min.y < edge.max.y)
+                            .map(|edge| ExtractedUiNode {
+                                // This translates the uinode's transform to the center of the current outline rectangle
+                                transform: world_from_local
+                                    * Mat4::from_translation(edge.center().extend(0.)),
+                                rect: Rect {
+                                    max: edge.size(),
+                                    ..Default::default()
+                                },
+                                ..instance(outline.color.into(), NodeType::Rect)
+                            }),
+                    );
+                }
+
+                ExtractedUiNodeBox {
+                    background,
+                    borders,
+                    outline: outline_edges,
+                    camera_entity,
+                    components,
+                }
+            }
         };
 
-        extracted_uinodes.uinodes.insert(
-            entity,
-            ExtractedUiNode {
-                stack_index: uinode.stack_index,
-                transform: transform.compute_matrix(),
-                color: background_color.0.into(),
-                rect: Rect {
-                    min: Vec2::ZERO,
-                    max: uinode.calculated_size,
-                },
-                clip: clip.map(|clip| clip.clip),
-                image: AssetId::default(),
-                atlas_size: None,
-                flip_x: false,
-                flip_y: false,
-                camera_entity,
-                border,
-                border_radius,
-                node_type: NodeType::Rect,
-            },
-        );
+        for instance in node_box
+            .background
+            .iter_mut()
+            .chain(&mut node_box.borders)
+            .chain(&mut node_box.outline)
+        {
+            instance.stack_index = uinode.stack_index;
+        }
+        if let Some(background) = &node_box.background {
+            extracted_uinodes.uinodes.insert(entity, background.clone());
+        }
+        for instance in node_box.borders.iter().chain(&node_box.outline) {
+            extracted_uinodes
+                .uinodes
+                .insert(commands.spawn_empty().id(), instance.clone());
+        }
+        extraction_cache.insert(entity, node_box);
     }
 }
 
@@ -296,6 +790,7 @@ pub fn extract_uinode_images(
     camera_query: Extract<Query<(Entity, &Camera)>>,
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
     ui_scale: Extract<Res<UiScale>>,
+    root_font_size: Extract<Res<RootFontSize>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     uinode_query: Extract<
         Query<(
@@ -308,11 +803,16 @@ pub fn extract_uinode_images(
             Option<&TextureAtlas>,
             Option<&ComputedTextureSlices>,
             Option<&BorderRadius>,
+            Option<&ClipShape>,
             Option<&Parent>,
             &Style,
+            Option<&ImageRepeat>,
+            Option<&UiImageSize>,
+            Option<&ObjectFit>,
         )>,
     >,
     node_query: Extract<Query<&Node>>,
+    ui_image_atlases: Extract<Res<UiImageAtlases>>,
 ) {
     for (
         uinode,
@@ -324,8 +824,12 @@ pub fn extract_uinode_images(
         atlas,
         slices,
         border_radius,
+        clip_shape,
         parent,
         style,
+        image_repeat,
+        image_size,
+        object_fit,
     ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
@@ -347,37 +851,58 @@ pub fn extract_uinode_images(
             continue;
         }
 
-        let (rect, atlas_size) = match atlas {
+        let mut image_id = image.texture.id();
+        let atlas_rect_and_size = match atlas {
             Some(atlas) => {
                 let Some(layout) = texture_atlases.get(&atlas.layout) else {
                     // Atlas not present in assets resource (should this warn the user?)
                     continue;
                 };
-                let mut atlas_rect = layout.textures[atlas.index].as_rect();
-                let mut atlas_size = layout.size.as_vec2();
-                let scale = uinode.size() / atlas_rect.size();
-                atlas_rect.min *= scale;
-                atlas_rect.max *= scale;
+                Some((layout.textures[atlas.index], layout.size))
+            }
+            // Images packed into a UI image atlas are drawn from the atlas, so they can be batched together
+            None => ui_image_atlases.get(image_id).map(|packed| {
+                image_id = packed.atlas_texture.id();
+                (packed.rect, packed.atlas_size)
+            }),
+        };
+        let texture_size = match atlas_rect_and_size {
+            Some((atlas_rect, _)) => atlas_rect.size().as_vec2(),
+            None => image_size.map_or(uinode.size(), |image_size| image_size.size().as_vec2()),
+        };
+        // The size of the texture after it is fitted to the node, and how much of it is cropped from each side
+        let fit_size = object_fit
+            .filter(|_| image_repeat.is_none())
+            .map_or(uinode.size(), |object_fit| {
+                object_fit.fit(uinode.size(), texture_size)
+            });
+        let crop = ((fit_size - uinode.size()) / 2.).max(Vec2::ZERO);
+        let (rect, atlas_size) = match atlas_rect_and_size {
+            Some((atlas_rect, atlas_size)) => {
+                let mut atlas_rect = atlas_rect.as_rect();
+                let mut atlas_size = atlas_size.as_vec2();
+                let scale = fit_size / atlas_rect.size();
+                atlas_rect.min = atlas_rect.min * scale + crop;
+                atlas_rect.max = atlas_rect.max * scale - crop;
                 atlas_size *= scale;
                 (atlas_rect, Some(atlas_size))
             }
             None => (
                 Rect {
-                    min: Vec2::ZERO,
-                    max: uinode.calculated_size,
+                    min: crop,
+                    max: fit_size - crop,
                 },
-                None,
+                Some(fit_size),
             ),
         };
 
-        let ui_logical_viewport_size = camera_query
-            .get(camera_entity)
-            .ok()
-            .and_then(|(_, c)| c.logical_viewport_size())
-            .unwrap_or(Vec2::ZERO)
-            // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
-            // so we have to divide by `UiScale` to get the size of the UI viewport.
-            / ui_scale.0;
+        let ui_logical_viewport_size = ui_logical_viewport_size(
+            camera_query
+                .get(camera_entity)
+                .ok()
+                .map(|(_, camera)| camera),
+            ui_scale.0,
+        );
 
         // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
         // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
@@ -385,27 +910,52 @@ pub fn extract_uinode_images(
             .and_then(|parent| node_query.get(parent.get()).ok())
             .map(|parent_node| parent_node.size().x)
             .unwrap_or(ui_logical_viewport_size.x);
-        let left =
-            resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size);
-        let right =
-            resolve_border_thickness(style.border.right, parent_width, ui_logical_viewport_size);
-        let top =
-            resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size);
-        let bottom =
-            resolve_border_thickness(style.border.bottom, parent_width, ui_logical_viewport_size);
+        let left = resolve_border_thickness(
+            style.border.left.scale_lengths(uinode.root_scale()),
+            parent_width,
+            ui_logical_viewport_size,
+            root_font_size.0,
+        );
+        let right = resolve_border_thickness(
+            style.border.right.scale_lengths(uinode.root_scale()),
+            parent_width,
+            ui_logical_viewport_size,
+            root_font_size.0,
+        );
+        let top = resolve_border_thickness(
+            style.border.top.scale_lengths(uinode.root_scale()),
+            parent_width,
+            ui_logical_viewport_size,
+            root_font_size.0,
+        );
+        let bottom = resolve_border_thickness(
+            style.border.bottom.scale_lengths(uinode.root_scale()),
+            parent_width,
+            ui_logical_viewport_size,
+            root_font_size.0,
+        );
 
         let border = [left, top, right, bottom];
 
-        let border_radius = if let Some(border_radius) = border_radius {
-            resolve_border_radius(
-                border_radius,
-                uinode.size(),
-                ui_logical_viewport_size,
-                ui_scale.0,
+        let repeat = image_repeat.map(|image_repeat| {
+            // Tiles are resolved against the node's size before it was scaled by its `UiRootScale`, so that pixel
+            // lengths and the texture's size are scaled with the node
+            image_repeat.tile_count(
+                uinode.size() / uinode.root_scale(),
+                texture_size,
+                ui_logical_viewport_size / uinode.root_scale(),
+                root_font_size.0,
             )
-        } else {
-            [0.; 4]
-        };
+        });
+
+        let border_radius = resolve_fill_radius(
+            clip_shape,
+            border_radius,
+            uinode.size(),
+            ui_logical_viewport_size,
+            ui_scale.0 * uinode.root_scale(),
+            root_font_size.0,
+        );
 
         extracted_uinodes.uinodes.insert(
             commands.spawn_empty().id(),
@@ -414,72 +964,66 @@ pub fn extract_uinode_images(
                 transform: transform.compute_matrix(),
                 color: image.color.into(),
                 rect,
-                clip: clip.map(|clip| clip.clip),
-                image: image.texture.id(),
+                clip: clip.copied(),
+                image: image_id,
                 atlas_size,
                 flip_x: image.flip_x,
                 flip_y: image.flip_y,
                 camera_entity,
                 border,
                 border_radius,
+                repeat,
                 node_type: NodeType::Rect,
             },
         );
     }
 }
 
-pub(crate) fn resolve_border_thickness(value: Val, parent_width: f32, viewport_size: Vec2) -> f32 {
-    match value {
-        Val::Auto => 0.,
-        Val::Px(px) => px.max(0.),
-        Val::Percent(percent) => (parent_width * percent / 100.).max(0.),
-        Val::Vw(percent) => (viewport_size.x * percent / 100.).max(0.),
-        Val::Vh(percent) => (viewport_size.y * percent / 100.).max(0.),
-        Val::VMin(percent) => (viewport_size.min_element() * percent / 100.).max(0.),
-        Val::VMax(percent) => (viewport_size.max_element() * percent / 100.).max(0.),
-    }
-}
-
-pub(crate) fn resolve_border_radius(
-    &values: &BorderRadius,
+/// Resolves the radii of the corners of a node's background and image, which are set by its [`ClipShape`] if it
+/// has one, and by its [`BorderRadius`] otherwise.
+pub(crate) fn resolve_fill_radius(
+    clip_shape: Option<&ClipShape>,
+    border_radius: Option<&BorderRadius>,
     node_size: Vec2,
     viewport_size: Vec2,
     ui_scale: f32,
-) -> [f32; 4] {
-    let max_radius = 0.5 * node_size.min_element() * ui_scale;
-    [
-        values.top_left,
-        values.top_right,
-        values.bottom_right,
-        values.bottom_left,
-    ]
-    .map(|value| {
-        match value {
-            Val::Auto => 0.,
-            Val::Px(px) => ui_scale * px,
-            Val::Percent(percent) => node_size.min_element() * percent / 100.,
-            Val::Vw(percent) => viewport_size.x * percent / 100.,
-            Val::Vh(percent) => viewport_size.y * percent / 100.,
-            Val::VMin(percent) => viewport_size.min_element() * percent / 100.,
-            Val::VMax(percent) => viewport_size.max_element() * percent / 100.,
-        }
-        .clamp(0., max_radius)
+    root_font_size: f32,
+) -> [Vec2; 4] {
+    let border_radius = match clip_shape {
+        Some(ClipShape::Rect) => None,
+        // An ellipse is a rectangle whose corners are as round as they can be
+        Some(ClipShape::Ellipse) => return [0.5 * node_size; 4],
+        Some(ClipShape::RoundedRect(border_radius)) => Some(border_radius),
+        None => border_radius,
+    };
+    border_radius.map_or([Vec2::ZERO; 4], |border_radius| {
+        resolve_border_radius(
+            border_radius,
+            node_size,
+            viewport_size,
+            ui_scale,
+            root_font_size,
+        )
     })
 }
 
 #[inline]
-fn clamp_corner(r: f32, size: Vec2, offset: Vec2) -> f32 {
+fn clamp_corner(r: Vec2, size: Vec2, offset: Vec2) -> Vec2 {
     let s = 0.5 * size + offset;
-    let sm = s.x.min(s.y);
-    r.min(sm)
+    if r.x == r.y {
+        // Circular corners stay circular
+        Vec2::splat(r.x.min(s.min_element()))
+    } else {
+        r.min(s)
+    }
 }
 
 #[inline]
 fn clamp_radius(
-    [top_left, top_right, bottom_right, bottom_left]: [f32; 4],
+    [top_left, top_right, bottom_right, bottom_left]: [Vec2; 4],
     size: Vec2,
     border: Vec4,
-) -> [f32; 4] {
+) -> [Vec2; 4] {
     let s = size - border.xy() - border.zw();
     [
         clamp_corner(top_left, s, border.xy()),
@@ -489,66 +1033,53 @@ fn clamp_radius(
     ]
 }
 
-pub fn extract_uinode_borders(
+/// Extracts the slices of the [`BorderImage`] of each node, each drawn as an image instance.
+#[allow(clippy::too_many_arguments)]
+pub fn extract_uinode_border_images(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     camera_query: Extract<Query<(Entity, &Camera)>>,
     default_ui_camera: Extract<DefaultUiCamera>,
     ui_scale: Extract<Res<UiScale>>,
+    root_font_size: Extract<Res<RootFontSize>>,
+    images: Extract<Res<Assets<Image>>>,
     uinode_query: Extract<
-        Query<
-            (
-                &Node,
-                &GlobalTransform,
-                &ViewVisibility,
-                Option<&CalculatedClip>,
-                Option<&TargetCamera>,
-                Option<&Parent>,
-                &Style,
-                &BorderColor,
-                &BorderRadius,
-            ),
-            Without<ContentSize>,
-        >,
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&CalculatedClip>,
+            Option<&TargetCamera>,
+            Option<&Parent>,
+            &Style,
+            &BorderImage,
+        )>,
     >,
     node_query: Extract<Query<&Node>>,
 ) {
-    let image = AssetId::<Image>::default();
-
-    for (
-        node,
-        global_transform,
-        view_visibility,
-        clip,
-        camera,
-        parent,
-        style,
-        border_color,
-        border_radius,
-    ) in &uinode_query
+    for (node, global_transform, view_visibility, clip, camera, parent, style, border_image) in
+        &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
             continue;
         };
 
-        // Skip invisible borders
-        if !view_visibility.get()
-            || border_color.0.is_fully_transparent()
-            || node.size().x <= 0.
-            || node.size().y <= 0.
-        {
+        // Skip invisible border images, and images that haven't loaded yet
+        if !view_visibility.get() || border_image.color.is_fully_transparent() {
             continue;
         }
+        let Some(image) = images.get(&border_image.texture) else {
+            continue;
+        };
 
-        let ui_logical_viewport_size = camera_query
-            .get(camera_entity)
-            .ok()
-            .and_then(|(_, c)| c.logical_viewport_size())
-            .unwrap_or(Vec2::ZERO)
-            // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
-            // so we have to divide by `UiScale` to get the size of the UI viewport.
-            / ui_scale.0;
+        let ui_logical_viewport_size = ui_logical_viewport_size(
+            camera_query
+                .get(camera_entity)
+                .ok()
+                .map(|(_, camera)| camera),
+            ui_scale.0,
+        );
 
         // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
         // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
@@ -556,148 +1087,53 @@ pub fn extract_uinode_borders(
             .and_then(|parent| node_query.get(parent.get()).ok())
             .map(|parent_node| parent_node.size().x)
             .unwrap_or(ui_logical_viewport_size.x);
-        let left =
-            resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size);
-        let right =
-            resolve_border_thickness(style.border.right, parent_width, ui_logical_viewport_size);
-        let top =
-            resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size);
-        let bottom =
-            resolve_border_thickness(style.border.bottom, parent_width, ui_logical_viewport_size);
-
-        let border = [left, top, right, bottom];
-
-        // don't extract border if no border
-        if left == 0.0 && top == 0.0 && right == 0.0 && bottom == 0.0 {
-            continue;
-        }
+        let border = [
+            style.border.left,
+            style.border.top,
+            style.border.right,
+            style.border.bottom,
+        ]
+        .map(|value| {
+            resolve_border_thickness(
+                value.scale_lengths(node.root_scale()),
+                parent_width,
+                ui_logical_viewport_size,
+                root_font_size.0,
+            )
+        });
 
-        let border_radius = resolve_border_radius(
-            border_radius,
+        let image_size = image.size_f32();
+        let slices = compute_border_image_slices(
+            border_image,
+            Rect {
+                min: Vec2::ZERO,
+                max: image_size,
+            },
             node.size(),
-            ui_logical_viewport_size,
-            ui_scale.0,
+            border,
         );
-
-        let border_radius = clamp_radius(border_radius, node.size(), border.into());
-        let transform = global_transform.compute_matrix();
-
-        extracted_uinodes.uinodes.insert(
-            commands.spawn_empty().id(),
-            ExtractedUiNode {
-                stack_index: node.stack_index,
-                // This translates the uinode's transform to the center of the current border rectangle
-                transform,
-                color: border_color.0.into(),
-                rect: Rect {
-                    max: node.size(),
-                    ..Default::default()
+        for slice in &slices {
+            let (transform, rect, atlas_size) =
+                slice_geometry(slice, Vec2::new(1., -1.), image_size, global_transform);
+            extracted_uinodes.uinodes.insert(
+                commands.spawn_empty().id(),
+                ExtractedUiNode {
+                    stack_index: node.stack_index,
+                    transform,
+                    color: border_image.color.into(),
+                    rect,
+                    image: border_image.texture.id(),
+                    atlas_size: Some(atlas_size),
+                    clip: clip.copied(),
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border_radius: [Vec2::ZERO; 4],
+                    border: [0.; 4],
+                    repeat: None,
+                    node_type: NodeType::Rect,
                 },
-                image,
-                atlas_size: None,
-                clip: clip.map(|clip| clip.clip),
-                flip_x: false,
-                flip_y: false,
-                camera_entity,
-                border_radius,
-                border,
-                node_type: NodeType::Border,
-            },
-        );
-    }
-}
-
-pub fn extract_uinode_outlines(
-    mut commands: Commands,
-    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
-    default_ui_camera: Extract<DefaultUiCamera>,
-    uinode_query: Extract<
-        Query<(
-            &Node,
-            &GlobalTransform,
-            &ViewVisibility,
-            Option<&CalculatedClip>,
-            Option<&TargetCamera>,
-            &Outline,
-        )>,
-    >,
-) {
-    let image = AssetId::<Image>::default();
-    for (node, global_transform, view_visibility, maybe_clip, camera, outline) in &uinode_query {
-        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
-        else {
-            continue;
-        };
-
-        // Skip invisible outlines
-        if !view_visibility.get()
-            || outline.color.is_fully_transparent()
-            || node.outline_width == 0.
-        {
-            continue;
-        }
-
-        // Calculate the outline rects.
-        let inner_rect = Rect::from_center_size(Vec2::ZERO, node.size() + 2. * node.outline_offset);
-        let outer_rect = inner_rect.inflate(node.outline_width());
-        let outline_edges = [
-            // Left edge
-            Rect::new(
-                outer_rect.min.x,
-                outer_rect.min.y,
-                inner_rect.min.x,
-                outer_rect.max.y,
-            ),
-            // Right edge
-            Rect::new(
-                inner_rect.max.x,
-                outer_rect.min.y,
-                outer_rect.max.x,
-                outer_rect.max.y,
-            ),
-            // Top edge
-            Rect::new(
-                inner_rect.min.x,
-                outer_rect.min.y,
-                inner_rect.max.x,
-                inner_rect.min.y,
-            ),
-            // Bottom edge
-            Rect::new(
-                inner_rect.min.x,
-                inner_rect.max.y,
-                inner_rect.max.x,
-                outer_rect.max.y,
-            ),
-        ];
-
-        let world_from_local = global_transform.compute_matrix();
-        for edge in outline_edges {
-            if edge.min.x < edge.max.x && edge.min.y < edge.max.y {
-                extracted_uinodes.uinodes.insert(
-                    commands.spawn_empty().id(),
-                    ExtractedUiNode {
-                        stack_index: node.stack_index,
-                        // This translates the uinode's transform to the center of the current border rectangle
-                        transform: world_from_local
-                            * Mat4::from_translation(edge.center().extend(0.)),
-                        color: outline.color.into(),
-                        rect: Rect {
-                            max: edge.size(),
-                            ..Default::default()
-                        },
-                        image,
-                        atlas_size: None,
-                        clip: maybe_clip.map(|clip| clip.clip),
-                        flip_x: false,
-                        flip_y: false,
-                        camera_entity,
-                        border: [0.; 4],
-                        border_radius: [0.; 4],
-                        node_type: NodeType::Rect,
-                    },
-                );
-            }
+            );
         }
     }
 }
@@ -802,11 +1238,22 @@ pub fn extract_uinode_text(
             Option<&TargetCamera>,
             &Text,
             &TextLayoutInfo,
+            Option<&TextShadow>,
+            Option<&TextOutline>,
         )>,
     >,
 ) {
-    for (uinode, global_transform, view_visibility, clip, camera, text, text_layout_info) in
-        &uinode_query
+    for (
+        uinode,
+        global_transform,
+        view_visibility,
+        clip,
+        camera,
+        text,
+        text_layout_info,
+        shadow,
+        outline,
+    ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
@@ -842,10 +1289,93 @@ pub fn extract_uinode_text(
         transform.translation = transform.translation.round();
         transform.translation *= inverse_scale_factor;
 
+        // The glyph quads of `FontRenderMode::Sdf` text are scaled from the size of their atlas rects,
+        // and their edges are antialiased from the signed distance fields.
+        let glyph_node_type = match text.render_mode {
+            FontRenderMode::Bitmap => NodeType::Rect,
+            FontRenderMode::Sdf => NodeType::SdfGlyph,
+        };
+
+        // Everything is pushed after the node, in the order it is drawn: the section backgrounds, then the shadows
+        // and outlines, then the text's glyphs. Pushing them with the node's stack index would leave their order
+        // undefined.
+        for (rect, color) in text_layout_info.section_background_rects(text) {
+            extracted_uinodes.push_after(
+                uinode,
+                commands.spawn_empty().id(),
+                ExtractedUiNode {
+                    stack_index: uinode.stack_index,
+                    transform: transform
+                        * Mat4::from_translation((rect.center() * inverse_scale_factor).extend(0.)),
+                    color: color.into(),
+                    rect: Rect {
+                        min: Vec2::ZERO,
+                        max: rect.size() * inverse_scale_factor,
+                    },
+                    image: AssetId::default(),
+                    atlas_size: None,
+                    clip: clip.copied(),
+                    flip_x: false,
+                    flip_y: false,
+                    camera_entity,
+                    border: [0.; 4],
+                    border_radius: [Vec2::ZERO; 4],
+                    repeat: None,
+                    node_type: NodeType::Rect,
+                },
+            );
+        }
+
+        let layers = shadow
+            .map(TextShadow::layers)
+            .into_iter()
+            .chain(outline.map(TextOutline::layers))
+            .flatten();
+        for layer in layers {
+            for PositionedGlyph {
+                position,
+                size,
+                atlas_info,
+                ..
+            } in &text_layout_info.glyphs
+            {
+                let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+
+                let mut rect = atlas.textures[atlas_info.glyph_index].as_rect();
+                let glyph_scale = *size / rect.size() * inverse_scale_factor;
+                rect.min *= glyph_scale;
+                rect.max *= glyph_scale;
+                extracted_uinodes.push_after(
+                    uinode,
+                    commands.spawn_empty().id(),
+                    ExtractedUiNode {
+                        stack_index: uinode.stack_index,
+                        transform: transform
+                            * Mat4::from_translation(
+                                (*position * inverse_scale_factor + layer.offset).extend(0.),
+                            ),
+                        color: layer.color,
+                        rect,
+                        image: atlas_info.texture.id(),
+                        atlas_size: Some(atlas.size.as_vec2() * glyph_scale),
+                        clip: clip.copied(),
+                        flip_x: false,
+                        flip_y: false,
+                        camera_entity,
+                        border: [0.; 4],
+                        border_radius: [Vec2::ZERO; 4],
+                        repeat: None,
+                        node_type: glyph_node_type,
+                    },
+                );
+            }
+        }
+
         let mut color = LinearRgba::WHITE;
         let mut current_section = usize::MAX;
         for PositionedGlyph {
             position,
+            size,
             atlas_info,
             section_index,
             ..
@@ -858,25 +1388,28 @@ pub fn extract_uinode_text(
             let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
 
             let mut rect = atlas.textures[atlas_info.glyph_index].as_rect();
-            rect.min *= inverse_scale_factor;
-            rect.max *= inverse_scale_factor;
-            extracted_uinodes.uinodes.insert(
+            let glyph_scale = *size / rect.size() * inverse_scale_factor;
+            rect.min *= glyph_scale;
+            rect.max *= glyph_scale;
+            extracted_uinodes.push_after(
+                uinode,
                 commands.spawn_empty().id(),
                 ExtractedUiNode {
                     stack_index: uinode.stack_index,
                     transform: transform
                         * Mat4::from_translation(position.extend(0.) * inverse_scale_factor),
-                    color,
+                    color: atlas_info.tint(color),
                     rect,
                     image: atlas_info.texture.id(),
-                    atlas_size: Some(atlas.size.as_vec2() * inverse_scale_factor),
-                    clip: clip.map(|clip| clip.clip),
+                    atlas_size: Some(atlas.size.as_vec2() * glyph_scale),
+                    clip: clip.copied(),
                     flip_x: false,
                     flip_y: false,
                     camera_entity,
                     border: [0.; 4],
-                    border_radius: [0.; 4],
-                    node_type: NodeType::Rect,
+                    border_radius: [Vec2::ZERO; 4],
+                    repeat: None,
+                    node_type: glyph_node_type,
                 },
             );
         }
@@ -892,28 +1425,42 @@ struct UiVertex {
     /// Shader flags to determine how to render the UI node.
     /// See [`shader_flags`] for possible values.
     pub flags: u32,
-    /// Border radius of the UI node.
+    /// Horizontal border radius of the UI node.
     /// Ordering: top left, top right, bottom right, bottom left.
-    pub radius: [f32; 4],
+    pub radius_x: [f32; 4],
+    /// Vertical border radius of the UI node.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub radius_y: [f32; 4],
     /// Border thickness of the UI node.
     /// Ordering: left, top, right, bottom.
     pub border: [f32; 4],
     /// Size of the UI node.
     pub size: [f32; 2],
+    /// The rect of the texture drawn in each tile, in normalized texture coordinates, if the texture
+    /// repeats. Ordering: min x, min y, max x, max y.
+    pub uv_rect: [f32; 4],
+    /// The rect of the node's clip, ordering: min x, min y, max x, max y.
+    pub clip_rect: [f32; 4],
+    /// Horizontal radius of the rounded corners of the node's clip, zero if the node isn't clipped.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub clip_radius_x: [f32; 4],
+    /// Vertical radius of the rounded corners of the node's clip, zero if the node isn't clipped.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub clip_radius_y: [f32; 4],
 }
 
 #[derive(Resource)]
 pub struct UiMeta {
-    vertices: RawBufferVec<UiVertex>,
-    indices: RawBufferVec<u32>,
+    vertices: buffer::UiBufferVec<UiVertex>,
+    indices: buffer::UiBufferVec<u32>,
     view_bind_group: Option<BindGroup>,
 }
 
 impl Default for UiMeta {
     fn default() -> Self {
         Self {
-            vertices: RawBufferVec::new(BufferUsages::VERTEX),
-            indices: RawBufferVec::new(BufferUsages::INDEX),
+            vertices: buffer::UiBufferVec::new(BufferUsages::VERTEX, "ui_vertex_buffer"),
+            indices: buffer::UiBufferVec::new(BufferUsages::INDEX, "ui_index_buffer"),
             view_bind_group: None,
         }
     }
@@ -942,17 +1489,28 @@ pub mod shader_flags {
     /// Ordering: top left, top right, bottom right, bottom left.
     pub const CORNERS: [u32; 4] = [0, 2, 2 | 4, 4];
     pub const BORDER: u32 = 8;
+    /// The texture repeats across the node, once per unit of the vertex uvs.
+    pub const TILED: u32 = 16;
+    /// Draw only one edge of the border.
+    /// Ordering: left, top, right, bottom.
+    pub const BORDER_EDGES: [u32; 4] = [32, 64, 128, 256];
+    /// The alpha channel of the texture is a signed distance field, antialiased at `0.5`.
+    pub const SDF_GLYPH: u32 = 512;
+    /// The texture has a single channel, sampled as white with the channel as alpha.
+    pub const SINGLE_CHANNEL: u32 = 1024;
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn queue_uinodes(
     extracted_uinodes: Res<ExtractedUiNodes>,
+    extracted_ui_layers: Res<ExtractedUiLayers>,
     ui_pipeline: Res<UiPipeline>,
     mut pipelines: ResMut<SpecializedRenderPipelines<UiPipeline>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
     mut views: Query<(Entity, &ExtractedView)>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
+    msaa: Res<Msaa>,
 ) {
     let draw_function = draw_functions.read().id::<DrawUi>();
     for (entity, extracted_uinode) in extracted_uinodes.uinodes.iter() {
@@ -964,19 +1522,24 @@ pub fn queue_uinodes(
             continue;
         };
 
+        let layer = extracted_ui_layers.get(extracted_uinode.stack_index as usize);
+        let (stack_key, order) =
+            extracted_uinodes.stack_sort_key(*entity, extracted_uinode.stack_index);
+        let depth_test = layer == UiLayer::MainPass;
         let pipeline = pipelines.specialize(
             &pipeline_cache,
             &ui_pipeline,
-            UiPipelineKey { hdr: view.hdr },
+            UiPipelineKey {
+                hdr: view.hdr,
+                depth_test,
+                samples: if depth_test { msaa.samples() } else { 1 },
+            },
         );
         transparent_phase.add(TransparentUi {
             draw_function,
             pipeline,
             entity: *entity,
-            sort_key: (
-                FloatOrd(extracted_uinode.stack_index as f32),
-                entity.index(),
-            ),
+            sort_key: (layer, stack_key, order),
             // batch_range will be calculated in prepare_uinodes
             batch_range: 0..0,
             extra_index: PhaseItemExtraIndex::NONE,
@@ -984,6 +1547,145 @@ pub fn queue_uinodes(
     }
 }
 
+/// Places the vertices of UI nodes drawn with [`UiLayer::MainPass`] at the depth of their [`UiDepth`].
+#[derive(SystemParam)]
+pub struct UiMainPassDepth<'w, 's> {
+    extracted_ui_layers: Res<'w, ExtractedUiLayers>,
+    views: Query<'w, 's, &'static ExtractedView>,
+    default_camera_views: Query<'w, 's, &'static DefaultCameraView>,
+}
+
+impl<'w, 's> UiMainPassDepth<'w, 's> {
+    /// Returns the z coordinate at which the UI view of `camera` maps the vertices of the node at `stack_index`
+    /// to the depth of its [`UiDepth`] in the camera's view, or `None` if the node isn't drawn with
+    /// [`UiLayer::MainPass`].
+    pub fn vertex_z(&self, camera: Entity, stack_index: usize) -> Option<f32> {
+        let distance = self.extracted_ui_layers.depth(stack_index)?;
+        let camera_view = self.views.get(camera).ok()?;
+        let ui_view = self
+            .views
+            .get(self.default_camera_views.get(camera).ok()?.0)
+            .ok()?;
+        let clip = camera_view.clip_from_view * Vec4::new(0., 0., -distance, 1.);
+        let depth = clip.z / clip.w;
+        // The UI view's projection is orthographic, so the depth of a vertex only depends on its z coordinate
+        let ui_clip_from_world =
+            ui_view.clip_from_view * ui_view.world_from_view.compute_matrix().inverse();
+        let row = ui_clip_from_world.row(2);
+        Some((depth - row.w) / row.z)
+    }
+}
+
+/// The number of UI batches in the most recent frame.
+///
+/// Each batch is drawn with one draw call. A new batch is started whenever a node uses a different texture than
+/// the previous one, so interleaving nodes with different textures, like images and text, increases the number
+/// of batches.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiBatchStats {
+    /// The number of UI nodes drawn.
+    pub nodes: usize,
+    /// The number of batches the nodes were drawn in.
+    pub batches: usize,
+    /// The number of nodes [`merge_ui_batches`] moved ahead of the nodes before them to join an earlier batch.
+    pub merged: usize,
+}
+
+/// The maximum number of nodes [`merge_ui_batches`] moves a node ahead of.
+pub const UI_BATCH_MERGE_WINDOW: usize = 32;
+
+/// The properties of a phase item that decide whether it can join a batch.
+#[derive(Clone, Copy, Debug)]
+struct UiBatchKey {
+    image: AssetId<Image>,
+    layer: UiLayer,
+    /// The area the item draws to, items can only be reordered when they don't overlap.
+    bounds: Rect,
+}
+
+/// Reorders sorted phase items so that nodes using the same texture are drawn in the same batch.
+///
+/// A node is moved ahead of the nodes between it and the end of an earlier batch it can join, if it doesn't
+/// overlap any of them, so the output looks the same. `keys` are `None` for items that aren't UI nodes, which
+/// are never reordered. Returns the number of moved items.
+fn merge_sorted_batches<T>(keys: &mut [Option<UiBatchKey>], items: &mut [T]) -> usize {
+    let mut merged = 0;
+    let mut start = 0;
+    while start < keys.len() {
+        let Some(first) = keys[start] else {
+            start += 1;
+            continue;
+        };
+        let mut image = first.image;
+        // The batch is the items in `start..end`, the items in `end..scan` were skipped
+        let mut end = start + 1;
+        let mut skipped: SmallVec<[Rect; UI_BATCH_MERGE_WINDOW]> = SmallVec::new();
+        let mut scan = end;
+        while scan < keys.len() && skipped.len() < UI_BATCH_MERGE_WINDOW {
+            let Some(key) = keys[scan].filter(|key| key.layer == first.layer) else {
+                break;
+            };
+            let compatible = image == AssetId::default()
+                || key.image == AssetId::default()
+                || key.image == image;
+            if compatible
+                && skipped
+                    .iter()
+                    .all(|bounds| bounds.intersect(key.bounds).is_empty())
+            {
+                if image == AssetId::default() {
+                    image = key.image;
+                }
+                if scan > end {
+                    keys[end..=scan].rotate_right(1);
+                    items[end..=scan].rotate_right(1);
+                    merged += 1;
+                }
+                end += 1;
+            } else {
+                skipped.push(key.bounds);
+            }
+            scan += 1;
+        }
+        start = end;
+    }
+    merged
+}
+
+/// Moves UI nodes in the sorted [`TransparentUi`] phases ahead of the nodes they don't overlap, so that nodes
+/// using the same texture are drawn in fewer batches.
+pub fn merge_ui_batches(
+    extracted_uinodes: Res<ExtractedUiNodes>,
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    mut phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
+    mut stats: ResMut<UiBatchStats>,
+) {
+    let draw_function = draw_functions.read().id::<DrawUi>();
+    stats.merged = 0;
+    for ui_phase in phases.values_mut() {
+        let mut keys = ui_phase
+            .items
+            .iter()
+            .map(|item| {
+                if item.draw_function != draw_function {
+                    return None;
+                }
+                let extracted_uinode = extracted_uinodes.uinodes.get(&item.entity)?;
+                let mut bounds = extracted_uinode.bounding_rect();
+                if let Some(clip) = extracted_uinode.clip {
+                    bounds = bounds.intersect(clip.clip);
+                }
+                Some(UiBatchKey {
+                    image: extracted_uinode.image,
+                    layer: item.sort_key.0,
+                    bounds,
+                })
+            })
+            .collect::<Vec<_>>();
+        stats.merged += merge_sorted_batches(&mut keys, &mut ui_phase.items);
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct UiImageBindGroups {
     pub values: HashMap<AssetId<Image>, BindGroup>,
@@ -1002,6 +1704,9 @@ pub fn prepare_uinodes(
     gpu_images: Res<RenderAssets<GpuImage>>,
     mut phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
     events: Res<SpriteAssetEvents>,
+    mut buffer_stats: ResMut<UiBufferStats>,
+    mut batch_stats: ResMut<UiBatchStats>,
+    main_pass_depth: UiMainPassDepth,
     mut previous_len: Local<usize>,
 ) {
     // If an image has changed, the GpuImage has (probably) changed
@@ -1017,6 +1722,8 @@ pub fn prepare_uinodes(
         };
     }
 
+    batch_stats.nodes = 0;
+    batch_stats.batches = 0;
     if let Some(view_binding) = view_uniforms.uniforms.binding() {
         let mut batches: Vec<(Entity, UiBatch)> = Vec::with_capacity(*previous_len);
 
@@ -1035,6 +1742,7 @@ pub fn prepare_uinodes(
         for ui_phase in phases.values_mut() {
             let mut batch_item_index = 0;
             let mut batch_image_handle = AssetId::invalid();
+            let mut batch_layer = UiLayer::default();
 
             for item_index in 0..ui_phase.items.len() {
                 let item = &mut ui_phase.items[item_index];
@@ -1048,10 +1756,13 @@ pub fn prepare_uinodes(
                             && batch_image_handle != extracted_uinode.image)
                         || existing_batch.as_ref().map(|(_, b)| b.camera)
                             != Some(extracted_uinode.camera_entity)
+                        // Layers are drawn by different passes
+                        || batch_layer != item.sort_key.0
                     {
                         if let Some(gpu_image) = gpu_images.get(extracted_uinode.image) {
                             batch_item_index = item_index;
                             batch_image_handle = extracted_uinode.image;
+                            batch_layer = item.sort_key.0;
 
                             let new_batch = UiBatch {
                                 range: vertices_index..vertices_index,
@@ -1109,6 +1820,14 @@ pub fn prepare_uinodes(
                     } else {
                         shader_flags::UNTEXTURED
                     };
+                    // Single channel textures, like font atlases storing the coverage of glyphs, are drawn as
+                    // white with the channel as alpha
+                    if gpu_images
+                        .get(extracted_uinode.image)
+                        .is_some_and(|gpu_image| gpu_image.texture_format.components() == 1)
+                    {
+                        flags |= shader_flags::SINGLE_CHANNEL;
+                    }
 
                     let mut uinode_rect = extracted_uinode.rect;
 
@@ -1121,35 +1840,44 @@ pub fn prepare_uinodes(
 
                     // Calculate the effect of clipping
                     // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
-                    let mut positions_diff = if let Some(clip) = extracted_uinode.clip {
-                        [
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[0].x, 0.),
-                                f32::max(clip.min.y - positions[0].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[1].x, 0.),
-                                f32::max(clip.min.y - positions[1].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::min(clip.max.x - positions[2].x, 0.),
-                                f32::min(clip.max.y - positions[2].y, 0.),
-                            ),
-                            Vec2::new(
-                                f32::max(clip.min.x - positions[3].x, 0.),
-                                f32::min(clip.max.y - positions[3].y, 0.),
-                            ),
-                        ]
-                    } else {
-                        [Vec2::ZERO; 4]
-                    };
+                    let mut positions_diff =
+                        if let Some(clip) = extracted_uinode.clip.map(|clip| clip.clip) {
+                            [
+                                Vec2::new(
+                                    f32::max(clip.min.x - positions[0].x, 0.),
+                                    f32::max(clip.min.y - positions[0].y, 0.),
+                                ),
+                                Vec2::new(
+                                    f32::min(clip.max.x - positions[1].x, 0.),
+                                    f32::max(clip.min.y - positions[1].y, 0.),
+                                ),
+                                Vec2::new(
+                                    f32::min(clip.max.x - positions[2].x, 0.),
+                                    f32::min(clip.max.y - positions[2].y, 0.),
+                                ),
+                                Vec2::new(
+                                    f32::max(clip.min.x - positions[3].x, 0.),
+                                    f32::min(clip.max.y - positions[3].y, 0.),
+                                ),
+                            ]
+                        } else {
+                            [Vec2::ZERO; 4]
+                        };
 
-                    let positions_clipped = [
+                    let mut positions_clipped = [
                         positions[0] + positions_diff[0].extend(0.),
                         positions[1] + positions_diff[1].extend(0.),
                         positions[2] + positions_diff[2].extend(0.),
                         positions[3] + positions_diff[3].extend(0.),
                     ];
+                    if let Some(z) = main_pass_depth.vertex_z(
+                        extracted_uinode.camera_entity,
+                        extracted_uinode.stack_index as usize,
+                    ) {
+                        for position in &mut positions_clipped {
+                            position.z = z;
+                        }
+                    }
 
                     let transformed_rect_size =
                         extracted_uinode.transform.transform_vector3(rect_size);
@@ -1168,10 +1896,13 @@ pub fn prepare_uinodes(
                             continue;
                         }
                     }
+                    let mut uv_rect = [0.; 4];
                     let uvs = if flags == shader_flags::UNTEXTURED {
                         [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]
                     } else {
                         let atlas_extent = extracted_uinode.atlas_size.unwrap_or(uinode_rect.max);
+                        let uv_min = uinode_rect.min / atlas_extent;
+                        let uv_max = uinode_rect.max / atlas_extent;
                         if extracted_uinode.flip_x {
                             std::mem::swap(&mut uinode_rect.max.x, &mut uinode_rect.min.x);
                             positions_diff[0].x *= -1.;
@@ -1186,7 +1917,7 @@ pub fn prepare_uinodes(
                             positions_diff[2].y *= -1.;
                             positions_diff[3].y *= -1.;
                         }
-                        [
+                        let uvs = [
                             Vec2::new(
                                 uinode_rect.min.x + positions_diff[0].x,
                                 uinode_rect.min.y + positions_diff[0].y,
@@ -1204,23 +1935,48 @@ pub fn prepare_uinodes(
                                 uinode_rect.max.y + positions_diff[3].y,
                             ),
                         ]
-                        .map(|pos| pos / atlas_extent)
+                        .map(|pos| pos / atlas_extent);
+                        match extracted_uinode.repeat {
+                            Some(repeat) if uv_min.cmplt(uv_max).all() => {
+                                // Map the uvs into tile space, where each unit is one repetition of the texture
+                                flags |= shader_flags::TILED;
+                                uv_rect = [uv_min.x, uv_min.y, uv_max.x, uv_max.y];
+                                uvs.map(|uv| (uv - uv_min) / (uv_max - uv_min) * repeat)
+                            }
+                            _ => uvs,
+                        }
                     };
 
                     let color = extracted_uinode.color.to_f32_array();
-                    if extracted_uinode.node_type == NodeType::Border {
-                        flags |= shader_flags::BORDER;
+                    match extracted_uinode.node_type {
+                        NodeType::Rect => {}
+                        NodeType::Border => flags |= shader_flags::BORDER,
+                        NodeType::BorderEdge(edge) => {
+                            flags |= shader_flags::BORDER | shader_flags::BORDER_EDGES[edge];
+                        }
+                        NodeType::SdfGlyph => flags |= shader_flags::SDF_GLYPH,
                     }
 
+                    let clip = extracted_uinode.clip.unwrap_or_default();
                     for i in 0..4 {
                         ui_meta.vertices.push(UiVertex {
                             position: positions_clipped[i].into(),
                             uv: uvs[i].into(),
                             color,
                             flags: flags | shader_flags::CORNERS[i],
-                            radius: extracted_uinode.border_radius,
+                            radius_x: extracted_uinode.border_radius.map(|radius| radius.x),
+                            radius_y: extracted_uinode.border_radius.map(|radius| radius.y),
                             border: extracted_uinode.border,
                             size: rect_size.xy().into(),
+                            uv_rect,
+                            clip_rect: [
+                                clip.clip.min.x,
+                                clip.clip.min.y,
+                                clip.clip.max.x,
+                                clip.clip.max.y,
+                            ],
+                            clip_radius_x: clip.radii.map(|radius| radius.x),
+                            clip_radius_y: clip.radii.map(|radius| radius.y),
                         });
                     }
 
@@ -1233,15 +1989,295 @@ pub fn prepare_uinodes(
 
                     existing_batch.unwrap().1.range.end = vertices_index;
                     ui_phase.items[batch_item_index].batch_range_mut().end += 1;
+                    batch_stats.nodes += 1;
                 } else {
                     batch_image_handle = AssetId::invalid();
                 }
             }
         }
-        ui_meta.vertices.write_buffer(&render_device, &render_queue);
-        ui_meta.indices.write_buffer(&render_device, &render_queue);
+        ui_meta
+            .vertices
+            .write_buffer(&render_device, &render_queue, &mut buffer_stats);
+        ui_meta
+            .indices
+            .write_buffer(&render_device, &render_queue, &mut buffer_stats);
         *previous_len = batches.len();
+        batch_stats.batches = batches.len();
         commands.insert_or_spawn_batch(batches);
     }
-    extracted_uinodes.uinodes.clear();
+    extracted_uinodes.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::{AssetId, Handle};
+    use bevy_color::LinearRgba;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::{Mat4, Rect, Vec2};
+    use bevy_render::texture::Image;
+
+    use super::{
+        merge_sorted_batches, resolve_border_radius, resolve_fill_radius, ExtractedUiNode,
+        ExtractedUiNodes, NodeType, UiBatchKey,
+    };
+    use crate::{BorderRadius, ClipShape, Node, UiLayer, UiStack, Val};
+
+    fn instance(stack_index: u32) -> ExtractedUiNode {
+        ExtractedUiNode {
+            stack_index,
+            transform: Mat4::IDENTITY,
+            color: LinearRgba::WHITE,
+            rect: Rect::new(0., 0., 10., 10.),
+            image: AssetId::default(),
+            atlas_size: None,
+            clip: None,
+            flip_x: false,
+            flip_y: false,
+            camera_entity: Entity::PLACEHOLDER,
+            border_radius: [Vec2::ZERO; 4],
+            border: [0.; 4],
+            repeat: None,
+            node_type: NodeType::Rect,
+        }
+    }
+
+    #[test]
+    fn extracted_uinodes_stack_order() {
+        let ui_stack = UiStack {
+            uinodes: vec![
+                Entity::from_raw(0),
+                Entity::from_raw(1),
+                Entity::from_raw(2),
+            ],
+        };
+        let mut extracted_uinodes = ExtractedUiNodes::default();
+        assert_eq!(extracted_uinodes.reserve_stack_range(&ui_stack, 2), 3..5);
+        assert_eq!(extracted_uinodes.reserve_stack_range(&ui_stack, 1), 5..6);
+
+        let owner = Node {
+            stack_index: 1,
+            ..Default::default()
+        };
+        let [background, first, second, above] = [10, 11, 12, 13].map(Entity::from_raw);
+        extracted_uinodes.push(background, instance(1));
+        extracted_uinodes.push_after(&owner, second, instance(0));
+        extracted_uinodes.push_after(&owner, first, instance(0));
+        extracted_uinodes.push(above, instance(2));
+        assert_eq!(extracted_uinodes.uinodes[&first].stack_index, 1);
+
+        let mut entities = vec![above, second, background, first];
+        entities.sort_by_key(|entity| {
+            extracted_uinodes.stack_sort_key(*entity, extracted_uinodes.uinodes[entity].stack_index)
+        });
+        assert_eq!(entities, vec![background, second, first, above]);
+
+        extracted_uinodes.clear();
+        assert!(extracted_uinodes.uinodes.is_empty());
+        assert_eq!(extracted_uinodes.reserve_stack_range(&ui_stack, 1), 3..4);
+    }
+
+    #[cfg(feature = "bevy_text")]
+    #[test]
+    fn text_shadows_and_outlines_are_drawn_below_the_glyphs() {
+        use bevy_asset::Assets;
+        use bevy_color::palettes::basic::{BLUE, RED};
+        use bevy_color::Color;
+        use bevy_ecs::{system::RunSystemOnce, world::World};
+        use bevy_math::{URect, UVec2};
+        use bevy_render::{view::ViewVisibility, MainWorld};
+        use bevy_sprite::TextureAtlasLayout;
+        use bevy_text::{
+            GlyphAtlasInfo, PositionedGlyph, Text, TextLayoutInfo, TextOutline, TextShadow,
+            TextStyle,
+        };
+        use bevy_transform::components::GlobalTransform;
+
+        use super::extract_uinode_text;
+        use crate::{TargetCamera, UiScale};
+
+        let mut render_world = World::new();
+        render_world.init_resource::<ExtractedUiNodes>();
+        render_world.init_resource::<MainWorld>();
+        let main_world = &mut **render_world.resource_mut::<MainWorld>();
+        main_world.init_resource::<UiScale>();
+        let mut layout = TextureAtlasLayout::new_empty(UVec2::splat(16));
+        let glyph_index = layout.add_texture(URect::new(0, 0, 8, 8));
+        let texture_atlas = main_world
+            .get_resource_or_insert_with(Assets::<TextureAtlasLayout>::default)
+            .add(layout);
+        let mut view_visibility = ViewVisibility::default();
+        view_visibility.set();
+        let node = Node {
+            stack_index: 1,
+            calculated_size: Vec2::splat(20.),
+            ..Default::default()
+        };
+        main_world.spawn((
+            node,
+            GlobalTransform::default(),
+            view_visibility,
+            TargetCamera(Entity::PLACEHOLDER),
+            Text::from_section(
+                "a",
+                TextStyle {
+                    color: Color::WHITE,
+                    ..Default::default()
+                },
+            ),
+            TextLayoutInfo {
+                glyphs: vec![PositionedGlyph {
+                    position: Vec2::splat(4.),
+                    size: Vec2::splat(8.),
+                    atlas_info: GlyphAtlasInfo {
+                        texture_atlas,
+                        texture: Handle::default(),
+                        glyph_index,
+                        is_color: false,
+                    },
+                    section_index: 0,
+                    byte_index: 0,
+                }],
+                ..Default::default()
+            },
+            TextShadow {
+                blur: 0.,
+                color: BLUE.into(),
+                ..Default::default()
+            },
+            TextOutline {
+                width: 1.,
+                color: RED.into(),
+            },
+        ));
+
+        // Freed entities are reused in reverse order, so the instances can't be drawn in the order of their entities
+        let freed = (0..64)
+            .map(|_| render_world.spawn_empty().id())
+            .collect::<Vec<_>>();
+        for entity in freed {
+            render_world.despawn(entity);
+        }
+
+        // The background of the text node is pushed with its stack index
+        let background = Entity::from_raw(1000);
+        render_world
+            .resource_mut::<ExtractedUiNodes>()
+            .push(background, instance(1));
+        render_world.run_system_once(extract_uinode_text);
+
+        let extracted_uinodes = render_world.resource::<ExtractedUiNodes>();
+        let mut entities = extracted_uinodes
+            .uinodes
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|entity| {
+            extracted_uinodes.stack_sort_key(*entity, extracted_uinodes.uinodes[entity].stack_index)
+        });
+        assert_eq!(entities[0], background);
+        let layers = entities[1..]
+            .iter()
+            .map(|entity| {
+                let color = extracted_uinodes.uinodes[entity].color;
+                if color == LinearRgba::from(BLUE) {
+                    "shadow"
+                } else if color == LinearRgba::from(RED) {
+                    "outline"
+                } else {
+                    "fill"
+                }
+            })
+            .collect::<Vec<_>>();
+        let first = |layer| layers.iter().position(|l| *l == layer).unwrap();
+        let last = |layer| layers.iter().rposition(|l| *l == layer).unwrap();
+        assert!(last("shadow") < first("outline"));
+        assert!(last("outline") < first("fill"));
+        assert_eq!(layers.last(), Some(&"fill"));
+    }
+
+    #[test]
+    fn percent_border_radius_resolves_per_axis() {
+        let resolve = |border_radius: BorderRadius| {
+            resolve_border_radius(&border_radius, Vec2::new(200., 100.), Vec2::ZERO, 1., 16.)[0]
+        };
+
+        // Percentages resolve against the width and height, making elliptical corners
+        assert_eq!(
+            resolve(BorderRadius::all(Val::Percent(10.))),
+            Vec2::new(20., 10.)
+        );
+        assert_eq!(
+            resolve(BorderRadius::percent(50., 50., 50., 50.)),
+            Vec2::new(100., 50.)
+        );
+        assert_eq!(
+            resolve(BorderRadius::all(Val::Percent(100.))),
+            Vec2::new(100., 50.)
+        );
+
+        // Other corners stay circular, clamped to half the smallest dimension
+        assert_eq!(resolve(BorderRadius::all(Val::Px(20.))), Vec2::splat(20.));
+        assert_eq!(resolve(BorderRadius::MAX), Vec2::splat(50.));
+
+        assert_eq!(
+            resolve(BorderRadius::elliptical(Val::Px(1000.), Val::Percent(20.))),
+            Vec2::new(100., 20.)
+        );
+    }
+
+    #[test]
+    fn clip_shape_overrides_border_radius() {
+        let border_radius = BorderRadius::all(Val::Px(10.));
+        let resolve = |clip_shape: Option<ClipShape>| {
+            resolve_fill_radius(
+                clip_shape.as_ref(),
+                Some(&border_radius),
+                Vec2::new(200., 100.),
+                Vec2::ZERO,
+                1.,
+                16.,
+            )
+        };
+
+        assert_eq!(resolve(None), [Vec2::splat(10.); 4]);
+        assert_eq!(resolve(Some(ClipShape::Rect)), [Vec2::ZERO; 4]);
+        assert_eq!(
+            resolve(Some(ClipShape::RoundedRect(BorderRadius::all(Val::Px(
+                20.
+            ))))),
+            [Vec2::splat(20.); 4]
+        );
+        assert_eq!(resolve(Some(ClipShape::Ellipse)), [Vec2::new(100., 50.); 4]);
+    }
+
+    #[test]
+    fn batches_merge_past_nodes_they_dont_overlap() {
+        let image = Handle::<Image>::weak_from_u128(1).id();
+        let glyphs = Handle::<Image>::weak_from_u128(2).id();
+        let key = |image, x: f32| {
+            Some(UiBatchKey {
+                image,
+                layer: UiLayer::Overlay,
+                bounds: Rect::new(x, 0., x + 10., 10.),
+            })
+        };
+        // Images and text of three buttons side by side, and a glyph overlapping the last image
+        let mut keys = [
+            key(image, 0.),
+            key(glyphs, 0.),
+            key(image, 20.),
+            key(glyphs, 20.),
+            key(glyphs, 45.),
+            key(image, 40.),
+        ];
+        let mut items = [0, 1, 2, 3, 4, 5];
+        assert_eq!(merge_sorted_batches(&mut keys, &mut items), 1);
+        assert_eq!(items, [0, 2, 1, 3, 4, 5]);
+
+        // Items that aren't UI nodes are never reordered
+        let mut keys = [key(image, 0.), None, key(glyphs, 0.), key(image, 20.)];
+        let mut items = [0, 1, 2, 3];
+        assert_eq!(merge_sorted_batches(&mut keys, &mut items), 0);
+        assert_eq!(items, [0, 1, 2, 3]);
+    }
 }
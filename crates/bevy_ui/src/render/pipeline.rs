@@ -1,3 +1,4 @@
+use bevy_core_pipeline::core_3d::CORE_3D_DEPTH_FORMAT;
 use bevy_ecs::prelude::*;
 use bevy_render::{
     render_resource::{
@@ -48,6 +49,24 @@ impl FromWorld for UiPipeline {
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct UiPipelineKey {
     pub hdr: bool,
+    /// Whether the pipeline draws [`UiLayer::MainPass`](crate::UiLayer::MainPass) nodes, depth tested against
+    /// the main pass.
+    pub depth_test: bool,
+    /// The number of samples of the render target, which is multisampled in the main pass.
+    pub samples: u32,
+}
+
+/// The depth state of UI pipelines, depth tested against the main pass but without writing to it
+/// if `depth_test` is true.
+pub(crate) fn ui_depth_stencil_state(depth_test: bool) -> Option<DepthStencilState> {
+    depth_test.then(|| DepthStencilState {
+        format: CORE_3D_DEPTH_FORMAT,
+        depth_write_enabled: false,
+        // Bevy uses reverse z, nearer fragments have greater depths
+        depth_compare: CompareFunction::GreaterEqual,
+        stencil: StencilState::default(),
+        bias: DepthBiasState::default(),
+    })
 }
 
 impl SpecializedRenderPipeline for UiPipeline {
@@ -65,12 +84,22 @@ impl SpecializedRenderPipeline for UiPipeline {
                 VertexFormat::Float32x4,
                 // mode
                 VertexFormat::Uint32,
-                // border radius
+                // horizontal border radius
+                VertexFormat::Float32x4,
+                // vertical border radius
                 VertexFormat::Float32x4,
                 // border thickness
                 VertexFormat::Float32x4,
                 // border size
                 VertexFormat::Float32x2,
+                // tile texture rect
+                VertexFormat::Float32x4,
+                // clip rect
+                VertexFormat::Float32x4,
+                // horizontal clip radius
+                VertexFormat::Float32x4,
+                // vertical clip radius
+                VertexFormat::Float32x4,
             ],
         );
         let shader_defs = Vec::new();
@@ -107,9 +136,9 @@ impl SpecializedRenderPipeline for UiPipeline {
                 topology: PrimitiveTopology::TriangleList,
                 strip_index_format: None,
             },
-            depth_stencil: None,
+            depth_stencil: ui_depth_stencil_state(key.depth_test),
             multisample: MultisampleState {
-                count: 1,
+                count: key.samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
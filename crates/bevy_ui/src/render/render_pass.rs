@@ -1,7 +1,7 @@
 use std::ops::Range;
 
 use super::{UiBatch, UiImageBindGroups, UiMeta};
-use crate::DefaultCameraView;
+use crate::{DefaultCameraView, UiLayer};
 use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem},
@@ -11,14 +11,22 @@ use bevy_render::{
     camera::ExtractedCamera,
     render_graph::*,
     render_phase::*,
-    render_resource::{CachedRenderPipelineId, RenderPassDescriptor},
+    render_resource::{CachedRenderPipelineId, RenderPassDescriptor, StoreOp},
     renderer::*,
     view::*,
 };
 
 pub struct UiPassNode {
-    ui_view_query: QueryState<(&'static ViewTarget, &'static ExtractedCamera), With<ExtractedView>>,
+    ui_view_query: QueryState<
+        (
+            &'static ViewTarget,
+            &'static ExtractedCamera,
+            Option<&'static ViewDepthTexture>,
+        ),
+        With<ExtractedView>,
+    >,
     default_camera_view_query: QueryState<&'static DefaultCameraView>,
+    layer: UiLayer,
 }
 
 impl UiPassNode {
@@ -26,8 +34,15 @@ impl UiPassNode {
         Self {
             ui_view_query: world.query_filtered(),
             default_camera_view_query: world.query(),
+            layer: UiLayer::Overlay,
         }
     }
+
+    /// Draw only the UI nodes in `layer`. Defaults to [`UiLayer::Overlay`].
+    pub fn with_layer(mut self, layer: UiLayer) -> Self {
+        self.layer = layer;
+        self
+    }
 }
 
 impl Node for UiPassNode {
@@ -54,10 +69,15 @@ impl Node for UiPassNode {
             return Ok(());
         };
 
-        let Ok((target, camera)) = self.ui_view_query.get_manual(world, input_view_entity) else {
+        let Ok((target, camera, depth)) = self.ui_view_query.get_manual(world, input_view_entity)
+        else {
             return Ok(());
         };
-        if transparent_phase.items.is_empty() {
+        // Items are sorted by layer first
+        let items = &transparent_phase.items;
+        let range = items.partition_point(|item| item.sort_key.0 < self.layer)
+            ..items.partition_point(|item| item.sort_key.0 <= self.layer);
+        if range.is_empty() {
             return Ok(());
         }
 
@@ -70,24 +90,41 @@ impl Node for UiPassNode {
         } else {
             input_view_entity
         };
+        // The main pass layer is drawn into the multisampled main texture, depth tested against the scene
+        let (color_attachment, depth_stencil_attachment) = if self.layer == UiLayer::MainPass {
+            let Some(depth) = depth else {
+                return Ok(());
+            };
+            (
+                target.get_color_attachment(),
+                Some(depth.get_attachment(StoreOp::Store)),
+            )
+        } else {
+            (target.get_unsampled_color_attachment(), None)
+        };
+        let label = match self.layer {
+            UiLayer::MainPass => "ui_pass_main_pass",
+            UiLayer::BeforePostProcessing => "ui_pass_before_post_processing",
+            UiLayer::Overlay => "ui_pass",
+        };
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
-            label: Some("ui_pass"),
-            color_attachments: &[Some(target.get_unsampled_color_attachment())],
-            depth_stencil_attachment: None,
+            label: Some(label),
+            color_attachments: &[Some(color_attachment)],
+            depth_stencil_attachment,
             timestamp_writes: None,
             occlusion_query_set: None,
         });
         if let Some(viewport) = camera.viewport.as_ref() {
             render_pass.set_camera_viewport(viewport);
         }
-        transparent_phase.render(&mut render_pass, world, view_entity);
+        transparent_phase.render_range(&mut render_pass, world, view_entity, range);
 
         Ok(())
     }
 }
 
 pub struct TransparentUi {
-    pub sort_key: (FloatOrd, u32),
+    pub sort_key: (UiLayer, FloatOrd, u32),
     pub entity: Entity,
     pub pipeline: CachedRenderPipelineId,
     pub draw_function: DrawFunctionId,
@@ -128,7 +165,7 @@ impl PhaseItem for TransparentUi {
 }
 
 impl SortedPhaseItem for TransparentUi {
-    type SortKey = (FloatOrd, u32);
+    type SortKey = (UiLayer, FloatOrd, u32);
 
     #[inline]
     fn sort_key(&self) -> Self::SortKey {
@@ -4,6 +4,14 @@ const TEXTURED = 1u;
 const RIGHT_VERTEX = 2u;
 const BOTTOM_VERTEX = 4u;
 const BORDER: u32 = 8u;
+const TILED: u32 = 16u;
+const BORDER_LEFT: u32 = 32u;
+const BORDER_TOP: u32 = 64u;
+const BORDER_RIGHT: u32 = 128u;
+const BORDER_BOTTOM: u32 = 256u;
+const BORDER_EDGES: u32 = 480u;
+const SDF_GLYPH: u32 = 512u;
+const SINGLE_CHANNEL: u32 = 1024u;
 
 fn enabled(flags: u32, mask: u32) -> bool {
     return (flags & mask) != 0u;
@@ -17,11 +25,19 @@ struct VertexOutput {
 
     @location(2) @interpolate(flat) size: vec2<f32>,
     @location(3) @interpolate(flat) flags: u32,
-    @location(4) @interpolate(flat) radius: vec4<f32>,    
-    @location(5) @interpolate(flat) border: vec4<f32>,    
+    @location(4) @interpolate(flat) radius_x: vec4<f32>,    
+    @location(5) @interpolate(flat) radius_y: vec4<f32>,    
+    @location(6) @interpolate(flat) border: vec4<f32>,    
 
     // Position relative to the center of the rectangle.
-    @location(6) point: vec2<f32>,
+    @location(7) point: vec2<f32>,
+    @location(8) @interpolate(flat) uv_rect: vec4<f32>,
+
+    // Position in the UI's coordinates, which the clip rect is in.
+    @location(9) world_point: vec2<f32>,
+    @location(10) @interpolate(flat) clip_rect: vec4<f32>,
+    @location(11) @interpolate(flat) clip_radius_x: vec4<f32>,
+    @location(12) @interpolate(flat) clip_radius_y: vec4<f32>,
     @builtin(position) position: vec4<f32>,
 };
 
@@ -32,21 +48,38 @@ fn vertex(
     @location(2) vertex_color: vec4<f32>,
     @location(3) flags: u32,
 
+    // Horizontal and vertical radius of each corner.
     // x: top left, y: top right, z: bottom right, w: bottom left.
-    @location(4) radius: vec4<f32>,
+    @location(4) radius_x: vec4<f32>,
+    @location(5) radius_y: vec4<f32>,
 
     // x: left, y: top, z: right, w: bottom.
-    @location(5) border: vec4<f32>,
-    @location(6) size: vec2<f32>,
+    @location(6) border: vec4<f32>,
+    @location(7) size: vec2<f32>,
+
+    // The texture rect repeated by tiled textures, x: min x, y: min y, z: max x, w: max y.
+    @location(8) uv_rect: vec4<f32>,
+
+    // The rect of the node's clip, x: min x, y: min y, z: max x, w: max y, and the radii of its rounded
+    // corners in the same order as the node's radii. The corners are square if the node isn't clipped.
+    @location(9) clip_rect: vec4<f32>,
+    @location(10) clip_radius_x: vec4<f32>,
+    @location(11) clip_radius_y: vec4<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.uv = vertex_uv;
     out.position = view.clip_from_world * vec4(vertex_position, 1.0);
     out.color = vertex_color;
     out.flags = flags;
-    out.radius = radius;
+    out.radius_x = radius_x;
+    out.radius_y = radius_y;
     out.size = size;
     out.border = border;
+    out.uv_rect = uv_rect;
+    out.world_point = vertex_position.xy;
+    out.clip_rect = clip_rect;
+    out.clip_radius_x = clip_radius_x;
+    out.clip_radius_y = clip_radius_y;
     var point = 0.49999 * size;
     if (flags & RIGHT_VERTEX) == 0u {
         point.x *= -1.;
@@ -72,52 +105,85 @@ fn vertex(
 //  - `point`        -> The function will return the distance from this point to the closest point on 
 //                    the boundary.
 //  - `size`         -> The maximum width and height of the box.
-//  - `corner_radii` -> The radius of each rounded corner. Ordered counter clockwise starting 
-//                    top left:
+//  - `radii_x`      -> The horizontal radius of each rounded corner. Ordered counter clockwise 
+//                    starting top left:
 //                      x: top left, y: top right, z: bottom right, w: bottom left.
-fn sd_rounded_box(point: vec2<f32>, size: vec2<f32>, corner_radii: vec4<f32>) -> f32 {
+//  - `radii_y`      -> The vertical radius of each rounded corner, in the same order as `radii_x`.
+fn sd_rounded_box(point: vec2<f32>, size: vec2<f32>, radii_x: vec4<f32>, radii_y: vec4<f32>) -> f32 {
     // If 0.0 < y then select bottom left (w) and bottom right corner radius (z).
     // Else select top left (x) and top right corner radius (y).
-    let rs = select(corner_radii.xy, corner_radii.wz, 0.0 < point.y);
+    let rxs = select(radii_x.xy, radii_x.wz, 0.0 < point.y);
+    let rys = select(radii_y.xy, radii_y.wz, 0.0 < point.y);
     // w and z are swapped above so that both pairs are in left to right order, otherwise this second 
     // select statement would return the incorrect value for the bottom pair.
-    let radius = select(rs.x, rs.y, 0.0 < point.x);
+    let radius = vec2(select(rxs.x, rxs.y, 0.0 < point.x), select(rys.x, rys.y, 0.0 < point.x));
     // Vector from the corner closest to the point, to the point.
     let corner_to_point = abs(point) - 0.5 * size;
-    // Vector from the center of the radius circle to the point.
+    // Vector from the center of the radius ellipse to the point.
     let q = corner_to_point + radius;
-    // Length from center of the radius circle to the point, zeros a component if the point is not 
-    // within the quadrant of the radius circle that is part of the curved corner.
-    let l = length(max(q, vec2(0.0)));
-    let m = min(max(q.x, q.y), 0.0);
-    return l + m - radius;
+    if 0.0 < q.x && 0.0 < q.y {
+        // The point is within the quadrant of the radius ellipse that is part of the curved corner.
+        if radius.x <= 0.0 || radius.y <= 0.0 {
+            return length(max(corner_to_point, vec2(0.0)));
+        }
+        if radius.x == radius.y {
+            return length(q) - radius.x;
+        }
+        // Approximate the distance to the ellipse.
+        let k0 = length(q / radius);
+        let k1 = length(q / (radius * radius));
+        return k0 * (k0 - 1.0) / k1;
+    }
+    return max(corner_to_point.x, corner_to_point.y);
 }
 
-fn sd_inset_rounded_box(point: vec2<f32>, size: vec2<f32>, radius: vec4<f32>, inset: vec4<f32>) -> f32 {
+fn sd_inset_rounded_box(
+    point: vec2<f32>,
+    size: vec2<f32>,
+    radius_x: vec4<f32>,
+    radius_y: vec4<f32>,
+    inset: vec4<f32>,
+) -> f32 {
     let inner_size = size - inset.xy - inset.zw;
     let inner_center = inset.xy + 0.5 * inner_size - 0.5 * size;
     let inner_point = point - inner_center;
 
-    var r = radius;
-
-    // Top left corner.
-    r.x = r.x - max(inset.x, inset.y);
-
-    // Top right corner.
-    r.y = r.y - max(inset.z, inset.y);
-
-    // Bottom right corner.
-    r.z = r.z - max(inset.z, inset.w); 
-
-    // Bottom left corner.
-    r.w = r.w - max(inset.x, inset.w);
+    // The horizontal radii are reduced by the left and right insets, 
+    // the vertical radii by the top and bottom insets.
+    var rx = radius_x - vec4(inset.x, inset.z, inset.z, inset.x);
+    var ry = radius_y - vec4(inset.y, inset.y, inset.w, inset.w);
 
     let half_size = inner_size * 0.5;
-    let min_size = min(half_size.x, half_size.y);
 
-    r = min(max(r, vec4(0.0)), vec4<f32>(min_size));
+    rx = min(max(rx, vec4(0.0)), vec4<f32>(half_size.x));
+    ry = min(max(ry, vec4(0.0)), vec4<f32>(half_size.y));
 
-    return sd_rounded_box(inner_point, inner_size, r);
+    return sd_rounded_box(inner_point, inner_size, rx, ry);
+}
+
+// Returns the flag of the border edge that `point` belongs to.
+//
+// The distance to each outer edge is measured relative to the edge's thickness, so that adjacent edges meet
+// along the line joining the outer and inner corners of the border, like CSS. Edges with zero thickness are
+// never selected.
+fn border_edge(point: vec2<f32>, size: vec2<f32>, border: vec4<f32>) -> u32 {
+    let half_size = 0.5 * size;
+    let distance = vec4(point.x + half_size.x, point.y + half_size.y, half_size.x - point.x, half_size.y - point.y);
+    let relative = select(distance / border, vec4(3.4e38), border <= vec4(0.0));
+    var edge = BORDER_LEFT;
+    var nearest = relative.x;
+    if relative.y < nearest {
+        edge = BORDER_TOP;
+        nearest = relative.y;
+    }
+    if relative.z < nearest {
+        edge = BORDER_RIGHT;
+        nearest = relative.z;
+    }
+    if relative.w < nearest {
+        edge = BORDER_BOTTOM;
+    }
+    return edge;
 }
 
 // get alpha for antialiasing for sdf
@@ -138,12 +204,12 @@ fn draw(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     // * Positive values indicate the point is outside the shape.
 
     // Signed distance from the exterior boundary.
-    let external_distance = sd_rounded_box(in.point, in.size, in.radius);
+    let external_distance = sd_rounded_box(in.point, in.size, in.radius_x, in.radius_y);
 
     // Signed distance from the border's internal edge (the signed distance is negative if the point 
     // is inside the rect but not on the border).
     // If the border size is set to zero, this is the same as the external distance.
-    let internal_distance = sd_inset_rounded_box(in.point, in.size, in.radius, in.border);
+    let internal_distance = sd_inset_rounded_box(in.point, in.size, in.radius_x, in.radius_y, in.border);
 
     // Signed distance from the border (the intersection of the rect with its border).
     // Points inside the border have negative signed distance. Any point outside the border, whether 
@@ -154,7 +220,13 @@ fn draw(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     // This select statement ensures we only perform anti-aliasing where a non-zero width border 
     // is present, otherwise an outline about the external boundary would be drawn even without 
     // a border.
-    let t = select(1.0 - step(0.0, border_distance), antialias(border_distance), external_distance < internal_distance);
+    var t = select(1.0 - step(0.0, border_distance), antialias(border_distance), external_distance < internal_distance);
+
+    // When each edge of the border is drawn separately, only draw the points belonging to this edge.
+    let edge = in.flags & BORDER_EDGES;
+    if edge != 0u && border_edge(in.point, in.size, in.border) != edge {
+        t = 0.0;
+    }
 
     // Blend mode ALPHA_BLENDING is used for UI elements, so we don't premultiply alpha here.
     return vec4(color.rgb, saturate(color.a * t));
@@ -164,18 +236,60 @@ fn draw_background(in: VertexOutput, texture_color: vec4<f32>) -> vec4<f32> {
     let color = select(in.color, in.color * texture_color, enabled(in.flags, TEXTURED));
 
     // When drawing the background only draw the internal area and not the border.
-    let internal_distance = sd_inset_rounded_box(in.point, in.size, in.radius, in.border);
+    let internal_distance = sd_inset_rounded_box(in.point, in.size, in.radius_x, in.radius_y, in.border);
     let t = antialias(internal_distance);
     return vec4(color.rgb, saturate(color.a * t));
 }
 
+// The alpha channel of signed distance field glyphs is 0.5 on the outline and increases inwards.
+// The edge is antialiased over the width of one pixel.
+fn draw_sdf_glyph(in: VertexOutput, distance: f32, pixel_width: f32) -> vec4<f32> {
+    let t = saturate((distance - 0.5) / max(pixel_width, 0.0001) + 0.5);
+    return vec4(in.color.rgb, saturate(in.color.a * t));
+}
+
+// The coverage of the point by the rounded corners of the node's clip.
+// The clip rect itself is applied to the vertices, so points outside of the corners are always covered.
+fn clip_corners(in: VertexOutput) -> f32 {
+    if all(in.clip_radius_x <= vec4(0.0)) || all(in.clip_radius_y <= vec4(0.0)) {
+        return 1.0;
+    }
+    let size = in.clip_rect.zw - in.clip_rect.xy;
+    let point = in.world_point - 0.5 * (in.clip_rect.xy + in.clip_rect.zw);
+    let half_size = 0.5 * size;
+    let rx = min(max(in.clip_radius_x, vec4(0.0)), vec4(half_size.x));
+    let ry = min(max(in.clip_radius_y, vec4(0.0)), vec4(half_size.y));
+    return saturate(antialias(sd_rounded_box(point, size, rx, ry)));
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    let texture_color = textureSample(sprite_texture, sprite_sampler, in.uv);
-
-    if enabled(in.flags, BORDER) {
-        return draw(in, texture_color);    
+    // Tiled textures are sampled from the texture rect once per unit of `uv`.
+    // The gradients are taken from the continuous uvs, so there are no seams between tiles.
+    let tiled = enabled(in.flags, TILED);
+    let uv_rect_size = select(vec2(1.0), in.uv_rect.zw - in.uv_rect.xy, tiled);
+    let uv = select(in.uv, in.uv_rect.xy + fract(in.uv) * uv_rect_size, tiled);
+    let sampled_color = textureSampleGrad(
+        sprite_texture,
+        sprite_sampler,
+        uv,
+        dpdx(in.uv) * uv_rect_size,
+        dpdy(in.uv) * uv_rect_size,
+    );
+    // Single channel textures, like font atlases storing the coverage of glyphs, are white with the channel as alpha.
+    let texture_color = select(sampled_color, vec4(1.0, 1.0, 1.0, sampled_color.r), enabled(in.flags, SINGLE_CHANNEL));
+
+    // The width of a pixel in the distance field, taken here where control flow is uniform.
+    let sdf_pixel_width = fwidth(texture_color.a);
+
+    var color: vec4<f32>;
+    if enabled(in.flags, SDF_GLYPH) {
+        color = draw_sdf_glyph(in, texture_color.a, sdf_pixel_width);
+    } else if enabled(in.flags, BORDER) {
+        color = draw(in, texture_color);
     } else {
-        return draw_background(in, texture_color);
+        color = draw_background(in, texture_color);
     }
+    color.a *= clip_corners(in);
+    return color;
 }
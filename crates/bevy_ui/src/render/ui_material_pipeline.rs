@@ -10,6 +10,7 @@ use bevy_ecs::{
 };
 use bevy_math::{FloatOrd, Mat4, Rect, Vec2, Vec4Swizzles};
 use bevy_render::{
+    camera::Camera,
     extract_component::ExtractComponentPlugin,
     globals::{GlobalsBuffer, GlobalsUniform},
     render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
@@ -21,7 +22,6 @@ use bevy_render::{
     Extract, ExtractSchedule, Render, RenderSet,
 };
 use bevy_transform::prelude::GlobalTransform;
-use bevy_window::{PrimaryWindow, Window};
 use bytemuck::{Pod, Zeroable};
 
 use crate::*;
@@ -91,7 +91,7 @@ where
 
 #[derive(Resource)]
 pub struct UiMaterialMeta<M: UiMaterial> {
-    vertices: RawBufferVec<UiMaterialVertex>,
+    vertices: super::buffer::UiBufferVec<UiMaterialVertex>,
     view_bind_group: Option<BindGroup>,
     marker: PhantomData<M>,
 }
@@ -99,7 +99,10 @@ pub struct UiMaterialMeta<M: UiMaterial> {
 impl<M: UiMaterial> Default for UiMaterialMeta<M> {
     fn default() -> Self {
         Self {
-            vertices: RawBufferVec::new(BufferUsages::VERTEX),
+            vertices: super::buffer::UiBufferVec::new(
+                BufferUsages::VERTEX,
+                "ui_material_vertex_buffer",
+            ),
             view_bind_group: Default::default(),
             marker: PhantomData,
         }
@@ -188,9 +191,9 @@ where
                 topology: PrimitiveTopology::TriangleList,
                 strip_index_format: None,
             },
-            depth_stencil: None,
+            depth_stencil: super::ui_depth_stencil_state(key.depth_test),
             multisample: MultisampleState {
-                count: 1,
+                count: key.samples,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -352,6 +355,7 @@ impl<M: UiMaterial> Default for ExtractedUiMaterialNodes<M> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn extract_ui_material_nodes<M: UiMaterial>(
     mut extracted_uinodes: ResMut<ExtractedUiMaterialNodes<M>>,
     materials: Extract<Res<Assets<M>>>,
@@ -372,17 +376,10 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
             Without<BackgroundColor>,
         >,
     >,
-    windows: Extract<Query<&Window, With<PrimaryWindow>>>,
+    camera_query: Extract<Query<&Camera>>,
     ui_scale: Extract<Res<UiScale>>,
+    root_font_size: Extract<Res<RootFontSize>>,
 ) {
-    let ui_logical_viewport_size = windows
-        .get_single()
-        .map(|window| window.size())
-        .unwrap_or(Vec2::ZERO)
-        // The logical window resolution returned by `Window` only takes into account the window scale factor and not `UiScale`,
-        // so we have to divide by `UiScale` to get the size of the UI viewport.
-        / ui_scale.0;
-
     // If there is only one camera, we use it as default
     let default_single_camera = default_ui_camera.get();
 
@@ -405,24 +402,35 @@ pub fn extract_ui_material_nodes<M: UiMaterial>(
                 continue;
             }
 
+            let ui_logical_viewport_size =
+                ui_logical_viewport_size(camera_query.get(camera_entity).ok(), ui_scale.0);
+
             // Both vertical and horizontal percentage border values are calculated based on the width of the parent node
             // <https://developer.mozilla.org/en-US/docs/Web/CSS/border-width>
             let parent_width = uinode.size().x;
-            let left =
-                resolve_border_thickness(style.border.left, parent_width, ui_logical_viewport_size)
-                    / uinode.size().x;
+            let left = resolve_border_thickness(
+                style.border.left.scale_lengths(uinode.root_scale()),
+                parent_width,
+                ui_logical_viewport_size,
+                root_font_size.0,
+            ) / uinode.size().x;
             let right = resolve_border_thickness(
-                style.border.right,
+                style.border.right.scale_lengths(uinode.root_scale()),
                 parent_width,
                 ui_logical_viewport_size,
+                root_font_size.0,
             ) / uinode.size().x;
-            let top =
-                resolve_border_thickness(style.border.top, parent_width, ui_logical_viewport_size)
-                    / uinode.size().y;
+            let top = resolve_border_thickness(
+                style.border.top.scale_lengths(uinode.root_scale()),
+                parent_width,
+                ui_logical_viewport_size,
+                root_font_size.0,
+            ) / uinode.size().y;
             let bottom = resolve_border_thickness(
-                style.border.bottom,
+                style.border.bottom.scale_lengths(uinode.root_scale()),
                 parent_width,
                 ui_logical_viewport_size,
+                root_font_size.0,
             ) / uinode.size().y;
 
             extracted_uinodes.uinodes.insert(
@@ -455,6 +463,8 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
     globals_buffer: Res<GlobalsBuffer>,
     ui_material_pipeline: Res<UiMaterialPipeline<M>>,
     mut phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
+    mut buffer_stats: ResMut<UiBufferStats>,
+    main_pass_depth: UiMainPassDepth,
     mut previous_len: Local<usize>,
 ) {
     if let (Some(view_binding), Some(globals_binding)) = (
@@ -474,17 +484,20 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
         for ui_phase in phases.values_mut() {
             let mut batch_item_index = 0;
             let mut batch_shader_handle = AssetId::invalid();
+            let mut batch_layer = UiLayer::default();
 
             for item_index in 0..ui_phase.items.len() {
                 let item = &mut ui_phase.items[item_index];
                 if let Some(extracted_uinode) = extracted_uinodes.uinodes.get(item.entity) {
-                    let mut existing_batch = batches
-                        .last_mut()
-                        .filter(|_| batch_shader_handle == extracted_uinode.material);
+                    let mut existing_batch = batches.last_mut().filter(|_| {
+                        batch_shader_handle == extracted_uinode.material
+                            && batch_layer == item.sort_key.0
+                    });
 
                     if existing_batch.is_none() {
                         batch_item_index = item_index;
                         batch_shader_handle = extracted_uinode.material;
+                        batch_layer = item.sort_key.0;
 
                         let new_batch = UiMaterialBatch {
                             range: index..index,
@@ -527,12 +540,19 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                         [Vec2::ZERO; 4]
                     };
 
-                    let positions_clipped = [
+                    let mut positions_clipped = [
                         positions[0] + positions_diff[0].extend(0.),
                         positions[1] + positions_diff[1].extend(0.),
                         positions[2] + positions_diff[2].extend(0.),
                         positions[3] + positions_diff[3].extend(0.),
                     ];
+                    if let Some(z) = main_pass_depth
+                        .vertex_z(extracted_uinode.camera_entity, extracted_uinode.stack_index)
+                    {
+                        for position in &mut positions_clipped {
+                            position.z = z;
+                        }
+                    }
 
                     let transformed_rect_size =
                         extracted_uinode.transform.transform_vector3(rect_size);
@@ -588,7 +608,9 @@ pub fn prepare_uimaterial_nodes<M: UiMaterial>(
                 }
             }
         }
-        ui_meta.vertices.write_buffer(&render_device, &render_queue);
+        ui_meta
+            .vertices
+            .write_buffer(&render_device, &render_queue, &mut buffer_stats);
         *previous_len = batches.len();
         commands.insert_or_spawn_batch(batches);
     }
@@ -638,6 +660,8 @@ pub fn queue_ui_material_nodes<M: UiMaterial>(
     render_materials: Res<RenderAssets<PreparedUiMaterial<M>>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
     mut views: Query<&ExtractedView>,
+    extracted_ui_layers: Res<ExtractedUiLayers>,
+    msaa: Res<Msaa>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
@@ -656,11 +680,15 @@ pub fn queue_ui_material_nodes<M: UiMaterial>(
             continue;
         };
 
+        let layer = extracted_ui_layers.get(extracted_uinode.stack_index);
+        let depth_test = layer == UiLayer::MainPass;
         let pipeline = pipelines.specialize(
             &pipeline_cache,
             &ui_material_pipeline,
             UiMaterialKey {
                 hdr: view.hdr,
+                depth_test,
+                samples: if depth_test { msaa.samples() } else { 1 },
                 bind_group_data: material.key.clone(),
             },
         );
@@ -672,6 +700,7 @@ pub fn queue_ui_material_nodes<M: UiMaterial>(
             pipeline,
             entity: *entity,
             sort_key: (
+                layer,
                 FloatOrd(extracted_uinode.stack_index as f32),
                 entity.index(),
             ),
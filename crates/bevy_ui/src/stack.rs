@@ -1,9 +1,21 @@
 //! This module contains the systems that update the stored UI nodes stack
 
-use bevy_ecs::prelude::*;
+use bevy_ecs::{
+    entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+    system::SystemParam,
+};
 use bevy_hierarchy::prelude::*;
+use bevy_math::Vec2;
+use bevy_transform::prelude::GlobalTransform;
 
-use crate::{Node, ZIndex};
+use crate::{
+    geometry::resolve_node_radii,
+    rounded_rect_contains,
+    viewports::{is_view_visible, NodeVisibility},
+    BorderRadius, CalculatedClip, HitTestMode, Node, RootFontSize, TargetCamera, UiCameraConfig,
+    UiRootLayer, UiScale, UiViewports, ZIndex,
+};
 
 /// The current UI stack, which contains all UI nodes ordered by their depth (back-to-front).
 ///
@@ -15,6 +27,143 @@ pub struct UiStack {
     pub uinodes: Vec<Entity>,
 }
 
+/// Queries the [`UiStack`] and the geometry of the UI nodes in it, for custom picking and tooltips.
+///
+/// The results reflect the most recent run of [`UiSystem::Stack`](crate::UiSystem::Stack) and
+/// [`UiSystem::Layout`](crate::UiSystem::Layout), so systems using this param should usually run after them.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_ui::UiStacks;
+/// fn print_topmost_node(ui_stacks: UiStacks) {
+///     if let Some(entity) = ui_stacks.hit_test(Vec2::new(100., 50.)).first() {
+///         println!("{entity:?} is at depth {:?}", ui_stacks.depth_of(*entity));
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct UiStacks<'w, 's> {
+    ui_stack: Res<'w, UiStack>,
+    viewports: UiViewports<'w, 's>,
+    ui_scale: Res<'w, UiScale>,
+    root_font_size: Res<'w, RootFontSize>,
+    node_query: Query<
+        'w,
+        's,
+        (
+            &'static Node,
+            &'static GlobalTransform,
+            Option<&'static CalculatedClip>,
+            NodeVisibility,
+            Option<&'static TargetCamera>,
+            Option<&'static BorderRadius>,
+            Option<&'static HitTestMode>,
+        ),
+    >,
+}
+
+impl<'w, 's> UiStacks<'w, 's> {
+    /// Returns the visible UI nodes containing `point`, ordered from front-to-back.
+    ///
+    /// `point` is in logical pixels, relative to the top left corner of the viewport of each node's camera.
+    /// The clipped regions of nodes and the corners cut off by their [`BorderRadius`] are excluded, following their
+    /// [`HitTestMode`].
+    pub fn hit_test(&self, point: Vec2) -> Vec<Entity> {
+        self.hit_test_filtered(point, |_| true)
+    }
+
+    /// Returns the visible UI nodes rendered by `camera` containing `point`, ordered from front-to-back.
+    ///
+    /// `point` is in logical pixels, relative to the top left corner of the camera's viewport.
+    pub fn hit_test_camera(&self, camera: Entity, point: Vec2) -> Vec<Entity> {
+        self.hit_test_filtered(point, |node_camera| node_camera == Some(camera))
+    }
+
+    fn hit_test_filtered(
+        &self,
+        point: Vec2,
+        camera_filter: impl Fn(Option<Entity>) -> bool,
+    ) -> Vec<Entity> {
+        self.ui_stack
+            .uinodes
+            .iter()
+            .rev()
+            .filter(|entity| {
+                let Ok((
+                    node,
+                    global_transform,
+                    clip,
+                    view_visibility,
+                    target_camera,
+                    border_radius,
+                    hit_test_mode,
+                )) = self.node_query.get(**entity)
+                else {
+                    return false;
+                };
+                let camera = self.viewports.viewport_of(target_camera);
+                if is_view_visible(&view_visibility) == Some(false) || !camera_filter(camera) {
+                    return false;
+                }
+                let node_rect = node.logical_rect(global_transform);
+                if !clip
+                    .map(|clip| node_rect.intersect(clip.clip))
+                    .unwrap_or(node_rect)
+                    .contains(point)
+                {
+                    return false;
+                }
+                if hit_test_mode.copied().unwrap_or_default() != HitTestMode::Shape {
+                    return true;
+                }
+                // The rounded corners of the clip
+                if clip.is_some_and(|clip| !clip.contains(point)) {
+                    return false;
+                }
+                let Some(border_radius) = border_radius else {
+                    return true;
+                };
+                let radii = resolve_node_radii(
+                    border_radius,
+                    node,
+                    camera
+                        .and_then(|camera| self.viewports.get(camera))
+                        .map_or(Vec2::ZERO, |viewport| {
+                            viewport.ui_logical_size(self.ui_scale.0)
+                        }),
+                    self.ui_scale.0,
+                    self.root_font_size.0,
+                );
+                rounded_rect_contains(node_rect, radii, point)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Returns the depth of `entity` in the [`UiStack`], or `None` if it isn't a UI node.
+    ///
+    /// The node furthest from the camera has a depth of `0`, nodes with greater depths are drawn over it.
+    pub fn depth_of(&self, entity: Entity) -> Option<usize> {
+        self.node_query
+            .get(entity)
+            .ok()
+            .map(|(node, ..)| node.stack_index as usize)
+    }
+
+    /// Returns the camera rendering `entity`, its [`TargetCamera`] or the default UI camera, or `None` if it isn't a
+    /// UI node.
+    pub fn camera_of(&self, entity: Entity) -> Option<Entity> {
+        let (.., target_camera, _, _) = self.node_query.get(entity).ok()?;
+        self.viewports.viewport_of(target_camera)
+    }
+
+    /// Returns the [`UiStack`], containing all UI nodes ordered from back-to-front.
+    pub fn stack(&self) -> &UiStack {
+        &self.ui_stack
+    }
+}
+
 /// Caches stacking context buffers for use in [`ui_stack_system`].
 #[derive(Default)]
 pub(crate) struct StackingContextCache {
@@ -40,33 +189,204 @@ struct StackingContext {
 }
 
 struct StackingContextEntry {
+    /// The rank of the [`UiRootLayer`] of the entry, see [`UiCameraConfig::layer_rank`].
+    layer_rank: u64,
     z_index: i32,
     entity: Entity,
     stack: StackingContext,
 }
 
+/// The removed components that can change the [`UiStack`].
+#[derive(SystemParam)]
+pub(crate) struct UiStackRemovedComponentParam<'w, 's> {
+    removed_nodes: RemovedComponents<'w, 's, Node>,
+    removed_parents: RemovedComponents<'w, 's, Parent>,
+    removed_children: RemovedComponents<'w, 's, Children>,
+    removed_z_indices: RemovedComponents<'w, 's, ZIndex>,
+    removed_root_layers: RemovedComponents<'w, 's, UiRootLayer>,
+    removed_camera_configs: RemovedComponents<'w, 's, UiCameraConfig>,
+}
+
+/// Ranks the [`UiRootLayer`]s of root nodes by the [`UiCameraConfig`] of their camera.
+#[derive(SystemParam)]
+pub(crate) struct UiRootLayerRanks<'w, 's> {
+    root_query: Query<
+        'w,
+        's,
+        (
+            Option<Ref<'static, UiRootLayer>>,
+            Option<Ref<'static, TargetCamera>>,
+        ),
+        (With<Node>, Without<Parent>),
+    >,
+    camera_query: Query<'w, 's, Ref<'static, UiCameraConfig>>,
+    viewports: UiViewports<'w, 's>,
+}
+
+impl<'w, 's> UiRootLayerRanks<'w, 's> {
+    /// Returns true if the layer or camera of a root node, or the layer order of a camera, changed.
+    fn is_changed(&self) -> bool {
+        self.root_query.iter().any(|(layer, target_camera)| {
+            layer.is_some_and(|layer| layer.is_changed())
+                || target_camera.is_some_and(|target_camera| target_camera.is_changed())
+        }) || self.camera_query.iter().any(|config| config.is_changed())
+    }
+
+    /// The rank of the layer of the root node `entity`.
+    fn rank(&self, entity: Entity) -> u64 {
+        let Ok((layer, target_camera)) = self.root_query.get(entity) else {
+            return 0;
+        };
+        let layer = layer.map(|layer| *layer).unwrap_or_default();
+        self.viewports
+            .viewport_of(target_camera.as_deref())
+            .and_then(|camera| self.camera_query.get(camera).ok())
+            .map_or(layer.0 as u64, |config| config.layer_rank(layer))
+    }
+}
+
+/// State kept between runs of [`ui_stack_system`].
+#[derive(Default)]
+pub(crate) struct UiStackState {
+    cache: StackingContextCache,
+    /// The number of descendants of each node in the [`UiStack`], or `None` if any of its descendants has a
+    /// [`ZIndex::Global`], so that its descendants aren't contiguous in the stack.
+    subtree_lens: EntityHashMap<Option<usize>>,
+    /// The root nodes when the stack was last rebuilt from scratch.
+    roots: EntityHashSet,
+}
+
+/// Above this number of changed subtrees the stack is rebuilt from scratch instead.
+const MAX_INCREMENTAL_UPDATES: usize = 16;
+
 /// Generates the render stack for UI nodes.
 ///
 /// First generate a UI node tree (`StackingContext`) based on z-index.
 /// Then flatten that tree into back-to-front ordered `UiStack`.
+///
+/// Root nodes are ordered by the rank of their [`UiRootLayer`] first, so every node in a layer is above the nodes
+/// of the layers drawn before it.
+///
+/// The stack is only rebuilt when the UI hierarchy, a [`ZIndex`] or the order of the [`UiRootLayer`]s changes. If the changes are localized to
+/// subtrees that contain no [`ZIndex::Global`] nodes, only those subtrees are rebuilt and spliced into the stack.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn ui_stack_system(
-    mut cache: Local<StackingContextCache>,
+    mut state: Local<UiStackState>,
     mut ui_stack: ResMut<UiStack>,
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
     zindex_query: Query<&ZIndex, With<Node>>,
     children_query: Query<&Children>,
-    mut update_query: Query<&mut Node>,
+    parent_query: Query<&Parent>,
+    changed_children_query: Query<Entity, Changed<Children>>,
+    mut removed_components: UiStackRemovedComponentParam,
+    mut update_query: StackNodeQuery,
+    layer_ranks: UiRootLayerRanks,
 ) {
+    let UiStackState {
+        cache,
+        subtree_lens,
+        roots,
+    } = &mut *state;
+
+    // Find the nodes whose descendants need to be reordered
+    // Changes to the layers reorder the roots of the stack
+    let removed_layers = removed_components.removed_root_layers.read().count()
+        + removed_components.removed_camera_configs.read().count();
+    let mut rebuild_all = subtree_lens.is_empty() || removed_layers > 0 || layer_ranks.is_changed();
+    let mut dirty: Vec<Entity> = changed_children_query
+        .iter()
+        .chain(removed_components.removed_children.read())
+        .collect();
+    // Despawned child nodes are also removed from their parent's `Children`, but a child that only lost its
+    // `Node` is still a child, so its parent is rebuilt. Removed roots and nodes that became roots change the
+    // roots of the stack.
+    for entity in removed_components.removed_nodes.read() {
+        if roots.contains(&entity) {
+            rebuild_all = true;
+        } else if let Ok(parent) = parent_query.get(entity) {
+            dirty.push(parent.get());
+        }
+    }
+    for entity in removed_components.removed_parents.read() {
+        rebuild_all |= update_query.contains(entity);
+    }
+    for entity in removed_components.removed_z_indices.read() {
+        match update_query.get(entity) {
+            Ok((_, _, Some(parent), _)) => dirty.push(parent.get()),
+            Ok(_) => rebuild_all = true,
+            Err(_) => {}
+        }
+    }
+    for (_, node, parent, z_index) in &mut update_query {
+        if !node.is_added()
+            && !parent.as_ref().is_some_and(DetectChanges::is_changed)
+            && !z_index.as_ref().is_some_and(DetectChanges::is_changed)
+        {
+            continue;
+        }
+        let Some(parent) = parent else {
+            // The roots of the stack changed
+            rebuild_all = true;
+            break;
+        };
+        // A `Parent` added to an existing node means that it was a root before
+        let was_root = parent.is_added() && !node.is_added();
+        let global = z_index
+            .is_some_and(|z_index| z_index.is_changed() && matches!(*z_index, ZIndex::Global(_)));
+        if was_root || global {
+            rebuild_all = true;
+            break;
+        }
+        dirty.push(parent.get());
+    }
+
+    if !rebuild_all {
+        if dirty.is_empty() {
+            return;
+        }
+        dirty.sort_unstable();
+        dirty.dedup();
+        // Subtrees of other dirty nodes are rebuilt along with them
+        let dirty_set: EntityHashSet = dirty.iter().copied().collect();
+        dirty.retain(|&entity| {
+            !parent_query
+                .iter_ancestors(entity)
+                .any(|ancestor| dirty_set.contains(&ancestor))
+        });
+        rebuild_all = MAX_INCREMENTAL_UPDATES < dirty.len()
+            || !dirty.into_iter().all(|entity| {
+                rebuild_subtree(
+                    cache,
+                    subtree_lens,
+                    &mut ui_stack.uinodes,
+                    &zindex_query,
+                    &children_query,
+                    &parent_query,
+                    &mut update_query,
+                    entity,
+                )
+            });
+    }
+
+    if !rebuild_all {
+        return;
+    }
+
     // Generate `StackingContext` tree
+    subtree_lens.clear();
+    roots.clear();
+    roots.extend(&root_node_query);
     let mut global_context = cache.pop();
     let mut total_entry_count: usize = 0;
 
     for entity in &root_node_query {
         insert_context_hierarchy(
-            &mut cache,
+            cache,
+            subtree_lens,
             &zindex_query,
             &children_query,
             entity,
+            layer_ranks.rank(entity),
             &mut global_context,
             None,
             &mut total_entry_count,
@@ -76,27 +396,133 @@ pub(crate) fn ui_stack_system(
     // Flatten `StackingContext` into `UiStack`
     ui_stack.uinodes.clear();
     ui_stack.uinodes.reserve(total_entry_count);
-    fill_stack_recursively(&mut cache, &mut ui_stack.uinodes, &mut global_context);
+    fill_stack_recursively(cache, &mut ui_stack.uinodes, &mut global_context);
     cache.push(global_context);
 
-    for (i, entity) in ui_stack.uinodes.iter().enumerate() {
-        if let Ok(mut node) = update_query.get_mut(*entity) {
+    update_stack_indices(&ui_stack.uinodes, 0, &mut update_query);
+}
+
+/// The nodes whose stack indices are updated by [`ui_stack_system`], with the components whose changes
+/// invalidate the stack.
+type StackNodeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Node,
+        Option<Ref<'static, Parent>>,
+        Option<Ref<'static, ZIndex>>,
+    ),
+>;
+
+fn update_stack_indices(uinodes: &[Entity], start: usize, update_query: &mut StackNodeQuery) {
+    for (i, entity) in uinodes.iter().enumerate().skip(start) {
+        if let Ok((_, mut node, ..)) = update_query.get_mut(*entity) {
             node.bypass_change_detection().stack_index = i as u32;
         }
     }
 }
 
+/// Rebuilds the stack entries of the descendants of `entity`, replacing its previous descendants in `uinodes`.
+///
+/// Returns false without changing the stack if the descendants of `entity` aren't contiguous in the stack,
+/// before or after the update, in which case the whole stack must be rebuilt. Entities that aren't in the stack
+/// are ignored.
+#[allow(clippy::too_many_arguments)]
+fn rebuild_subtree(
+    cache: &mut StackingContextCache,
+    subtree_lens: &mut EntityHashMap<Option<usize>>,
+    uinodes: &mut Vec<Entity>,
+    zindex_query: &Query<&ZIndex, With<Node>>,
+    children_query: &Query<&Children>,
+    parent_query: &Query<&Parent>,
+    update_query: &mut StackNodeQuery,
+    entity: Entity,
+) -> bool {
+    let old_len = match subtree_lens.get(&entity) {
+        Some(&Some(old_len)) => old_len,
+        Some(None) => return false,
+        // Not in the stack, so there's nothing to update
+        None => return true,
+    };
+    let Ok(index) = update_query
+        .get(entity)
+        .map(|(_, node, ..)| node.stack_index as usize)
+    else {
+        // Despawned nodes are removed from the stack by the update of their parent
+        return true;
+    };
+    if uinodes.get(index) != Some(&entity) {
+        return false;
+    }
+
+    let mut global_context = cache.pop();
+    let mut context = cache.pop();
+    let mut new_len = 0;
+    if let Ok(children) = children_query.get(entity) {
+        for child in children {
+            // Nodes in the global context cause a full rebuild, so the layer rank is irrelevant here
+            insert_context_hierarchy(
+                cache,
+                subtree_lens,
+                zindex_query,
+                children_query,
+                *child,
+                0,
+                &mut global_context,
+                Some(&mut context),
+                &mut new_len,
+            );
+        }
+    }
+    let contiguous = global_context.entries.is_empty();
+    let mut entries = Vec::with_capacity(new_len);
+    if contiguous {
+        fill_stack_recursively(cache, &mut entries, &mut context);
+    }
+    cache.push(context);
+    cache.push(global_context);
+    if !contiguous {
+        return false;
+    }
+
+    let range = index + 1..index + 1 + old_len;
+    let kept: EntityHashSet = entries.iter().copied().collect();
+    for removed in &uinodes[range.clone()] {
+        if !kept.contains(removed) {
+            subtree_lens.remove(removed);
+        }
+    }
+    uinodes.splice(range, entries);
+
+    // The ancestors' subtrees grow or shrink by the change in the number of descendants
+    subtree_lens.insert(entity, Some(new_len));
+    for ancestor in parent_query.iter_ancestors(entity) {
+        if let Some(Some(len)) = subtree_lens.get_mut(&ancestor) {
+            *len = *len + new_len - old_len;
+        }
+    }
+
+    update_stack_indices(uinodes, index + 1, update_query);
+    true
+}
+
 /// Generate z-index based UI node tree
+#[allow(clippy::too_many_arguments)]
 fn insert_context_hierarchy(
     cache: &mut StackingContextCache,
+    subtree_lens: &mut EntityHashMap<Option<usize>>,
     zindex_query: &Query<&ZIndex, With<Node>>,
     children_query: &Query<&Children>,
     entity: Entity,
+    layer_rank: u64,
     global_context: &mut StackingContext,
     parent_context: Option<&mut StackingContext>,
     total_entry_count: &mut usize,
 ) {
     let mut new_context = cache.pop();
+    let first_descendant_count = *total_entry_count;
+    let mut contiguous = true;
 
     if let Ok(children) = children_query.get(entity) {
         // Reserve space for all children. In practice, some may not get pushed since
@@ -106,16 +532,26 @@ fn insert_context_hierarchy(
         for entity in children {
             insert_context_hierarchy(
                 cache,
+                subtree_lens,
                 zindex_query,
                 children_query,
                 *entity,
+                layer_rank,
                 global_context,
                 Some(&mut new_context),
                 total_entry_count,
             );
+            // The descendants are contiguous in the stack if none of them are in the global context
+            contiguous &= !matches!(zindex_query.get(*entity), Ok(ZIndex::Global(_)))
+                && matches!(subtree_lens.get(entity), Some(Some(_)));
         }
     }
 
+    subtree_lens.insert(
+        entity,
+        contiguous.then_some(*total_entry_count - first_descendant_count),
+    );
+
     // The node will be added either to global/parent based on its z-index type: global/local.
     let z_index = zindex_query.get(entity).unwrap_or(&ZIndex::Local(0));
     let (entity_context, z_index) = match z_index {
@@ -125,6 +561,7 @@ fn insert_context_hierarchy(
 
     *total_entry_count += 1;
     entity_context.entries.push(StackingContextEntry {
+        layer_rank,
         z_index,
         entity,
         stack: new_context,
@@ -137,10 +574,11 @@ fn fill_stack_recursively(
     result: &mut Vec<Entity>,
     stack: &mut StackingContext,
 ) {
-    // Sort entries by ascending z_index, while ensuring that siblings
+    // Sort entries by ascending layer rank and z_index, while ensuring that siblings
     // with the same local z_index will keep their ordering. This results
     // in `back-to-front` ordering, low z_index = back; high z_index = front.
-    stack.entries.sort_by_key(|e| e.z_index);
+    // Siblings are always in the same layer, so only the global context is sorted by layer.
+    stack.entries.sort_by_key(|e| (e.layer_rank, e.z_index));
 
     for mut entry in stack.entries.drain(..) {
         // Parent node renders before/behind child nodes
@@ -155,12 +593,21 @@ mod tests {
     use bevy_ecs::{
         component::Component,
         schedule::Schedule,
-        system::Commands,
+        system::{Commands, RunSystemOnce},
         world::{CommandQueue, World},
     };
-    use bevy_hierarchy::BuildChildren;
+    use bevy_hierarchy::{BuildChildren, BuildWorldChildren, DespawnRecursiveExt};
+
+    use bevy_math::Vec2;
+    #[cfg(feature = "render")]
+    use bevy_render::view::ViewVisibility;
+    use bevy_transform::prelude::GlobalTransform;
 
-    use crate::{Node, UiStack, ZIndex};
+    use crate::{
+        BorderRadius, HitTestMode, Node, RootFontSize, UiScale, UiStack, UiStacks, Val, ZIndex,
+    };
+    #[cfg(feature = "render")]
+    use crate::{TargetCamera, UiCameraConfig, UiRootLayer};
 
     use super::ui_stack_system;
 
@@ -264,4 +711,196 @@ mod tests {
         ];
         assert_eq!(actual_result, expected_result);
     }
+
+    // Without the `render` feature, target cameras and their `UiCameraConfig` are ignored
+    #[test]
+    #[cfg(feature = "render")]
+    fn ui_root_layers_are_stacked_in_order() {
+        let mut world = World::default();
+        world.init_resource::<UiStack>();
+
+        let camera = world.spawn(UiCameraConfig::default()).id();
+        let hud = world
+            .spawn((
+                node_with_zindex("hud", ZIndex::Global(10)),
+                UiRootLayer(1),
+                TargetCamera(camera),
+            ))
+            .id();
+        let hud_child = world
+            .spawn(node_with_zindex("hud child", ZIndex::Global(20)))
+            .id();
+        world.entity_mut(hud).add_child(hud_child);
+        let overlay = world
+            .spawn((
+                node_with_zindex("overlay", ZIndex::Global(-10)),
+                UiRootLayer(2),
+                TargetCamera(camera),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_stack_system);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<UiStack>().uinodes,
+            vec![hud, hud_child, overlay]
+        );
+
+        world.get_mut::<UiCameraConfig>(camera).unwrap().layer_order =
+            vec![UiRootLayer(2), UiRootLayer(1)];
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<UiStack>().uinodes,
+            vec![overlay, hud, hud_child]
+        );
+
+        world.entity_mut(camera).remove::<UiCameraConfig>();
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<UiStack>().uinodes,
+            vec![hud, hud_child, overlay]
+        );
+    }
+
+    /// Runs `schedule`, then checks that the stack matches a stack built from scratch.
+    fn run_and_compare_with_rebuild(world: &mut World, schedule: &mut Schedule) {
+        schedule.run(world);
+        let incremental = world.resource::<UiStack>().uinodes.clone();
+        for (i, entity) in incremental.iter().enumerate() {
+            if let Some(node) = world.get::<Node>(*entity) {
+                assert_eq!(node.stack_index, i as u32);
+            }
+        }
+
+        let mut rebuild_schedule = Schedule::default();
+        rebuild_schedule.add_systems(ui_stack_system);
+        rebuild_schedule.run(world);
+        assert_eq!(incremental, world.resource::<UiStack>().uinodes);
+    }
+
+    #[test]
+    fn incremental_ui_stack_updates() {
+        let mut world = World::default();
+        world.init_resource::<UiStack>();
+
+        let root = world.spawn(node_without_zindex("root")).id();
+        let a = world.spawn(node_without_zindex("a")).id();
+        let b = world.spawn(node_without_zindex("b")).id();
+        let a0 = world.spawn(node_without_zindex("a0")).id();
+        let a1 = world.spawn(node_without_zindex("a1")).id();
+        let b0 = world.spawn(node_with_zindex("b0", ZIndex::Local(1))).id();
+        let b1 = world.spawn(node_without_zindex("b1")).id();
+        world.entity_mut(root).push_children(&[a, b]);
+        world.entity_mut(a).push_children(&[a0, a1]);
+        world.entity_mut(b).push_children(&[b0, b1]);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_stack_system);
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+
+        // Reorder siblings
+        world.entity_mut(a0).insert(ZIndex::Local(2));
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+
+        // Add a node
+        let a2 = world.spawn(node_without_zindex("a2")).id();
+        world.entity_mut(a).add_child(a2);
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+
+        // Move a subtree
+        world.entity_mut(b).add_child(a);
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+
+        // Remove a subtree
+        world.entity_mut(a).despawn_recursive();
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+
+        // Global z-indices move nodes out of their parent's subtree
+        world.entity_mut(b1).insert(ZIndex::Global(-1));
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+        world.entity_mut(b1).insert(ZIndex::Local(0));
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+
+        // A child that is no longer a node is still in its parent's `Children`, but its `ZIndex` is ignored
+        world.entity_mut(b0).remove::<Node>();
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+        assert_eq!(world.resource::<UiStack>().uinodes, [root, b, b0, b1]);
+
+        // Nothing changed
+        run_and_compare_with_rebuild(&mut world, &mut schedule);
+        assert_eq!(world.resource::<UiStack>().uinodes, [root, b, b0, b1]);
+    }
+
+    fn node_with_rect(center: Vec2, size: Vec2) -> (Node, GlobalTransform) {
+        (
+            Node {
+                calculated_size: size,
+                ..Default::default()
+            },
+            GlobalTransform::from_translation(center.extend(0.)),
+        )
+    }
+
+    #[test]
+    fn test_ui_stacks_hit_test() {
+        let mut world = World::default();
+        world.init_resource::<UiStack>();
+        world.init_resource::<UiScale>();
+        world.init_resource::<RootFontSize>();
+
+        let root = world
+            .spawn(node_with_rect(Vec2::splat(50.), Vec2::splat(100.)))
+            .id();
+        let back = world
+            .spawn(node_with_rect(Vec2::splat(25.), Vec2::splat(50.)))
+            .id();
+        let front = world
+            .spawn(node_with_rect(Vec2::splat(40.), Vec2::splat(40.)))
+            .id();
+        world.entity_mut(root).push_children(&[back, front]);
+        // Hidden nodes are never hit
+        #[cfg(feature = "render")]
+        {
+            let hidden = world
+                .spawn((
+                    node_with_rect(Vec2::splat(50.), Vec2::splat(100.)),
+                    ViewVisibility::HIDDEN,
+                ))
+                .id();
+            world.entity_mut(root).add_child(hidden);
+        }
+        let not_a_node = world.spawn_empty().id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_stack_system);
+        schedule.run(&mut world);
+
+        world.run_system_once(move |ui_stacks: UiStacks| {
+            assert_eq!(
+                ui_stacks.hit_test(Vec2::splat(30.)),
+                vec![front, back, root]
+            );
+            assert_eq!(ui_stacks.hit_test(Vec2::splat(10.)), vec![back, root]);
+            assert_eq!(ui_stacks.hit_test(Vec2::splat(90.)), vec![root]);
+            assert!(ui_stacks.hit_test(Vec2::splat(150.)).is_empty());
+
+            assert_eq!(ui_stacks.depth_of(root), Some(0));
+            assert_eq!(ui_stacks.depth_of(front), Some(2));
+            assert_eq!(ui_stacks.depth_of(not_a_node), None);
+        });
+
+        // The rounded corners of nodes are only hit with `HitTestMode::Bounds`
+        world
+            .entity_mut(back)
+            .insert(BorderRadius::all(Val::Px(20.)));
+        world.run_system_once(move |ui_stacks: UiStacks| {
+            assert_eq!(ui_stacks.hit_test(Vec2::splat(2.)), vec![root]);
+            assert_eq!(ui_stacks.hit_test(Vec2::splat(10.)), vec![back, root]);
+        });
+        world.entity_mut(back).insert(HitTestMode::Bounds);
+        world.run_system_once(move |ui_stacks: UiStacks| {
+            assert_eq!(ui_stacks.hit_test(Vec2::splat(2.)), vec![back, root]);
+        });
+    }
 }
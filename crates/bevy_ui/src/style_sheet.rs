@@ -0,0 +1,998 @@
+//! Style sheets that set the styles of UI nodes by class, loaded from a subset of CSS.
+//!
+//! A [`UiStyleSheet`] is a list of rules, each with one or more class selectors and a block of declarations:
+//!
+//! ```css
+//! /* Comments are ignored */
+//! .panel {
+//!     display: flex;
+//!     flex-direction: column;
+//!     padding: 8px 16px;
+//!     background-color: #202020;
+//! }
+//!
+//! .button, .toggle {
+//!     width: 10rem;
+//!     border-width: 2px;
+//!     border-color: rgb(255, 255, 255);
+//! }
+//! ```
+//!
+//! Nodes opt into rules with the [`Class`] component, and use the style sheet of the nearest [`StyleSheetRef`]
+//! on themselves or their ancestors. When a rule stops applying to a node, because a class was removed, the sheet
+//! was edited or the node moved under a different sheet, the properties it set are restored to their values from
+//! before the style sheet was applied.
+
+use crate::{
+    AlignContent, AlignItems, AlignSelf, BackgroundColor, BorderColor, Display, FlexDirection,
+    FlexWrap, JustifyContent, PositionType, Style, UiRect, Val,
+};
+use bevy_asset::{
+    io::Reader, Asset, AssetEvent, AssetId, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext,
+};
+use bevy_color::{Color, Srgba};
+use bevy_derive::Deref;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{HierarchyQueryExt, Parent};
+use bevy_reflect::{prelude::*, TypePath};
+use bevy_utils::HashSet;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A list of style rules, parsed from a subset of CSS.
+///
+/// Only class selectors (`.name`) are supported, optionally separated by commas. Rules are applied in the order
+/// they appear in the sheet, so later rules override earlier ones.
+///
+/// The supported properties are:
+/// - Lengths: `width`, `height`, `min-width`, `min-height`, `max-width`, `max-height`, `left`, `right`, `top`,
+///   `bottom`, `flex-basis`, `row-gap` and `column-gap`, in `px`, `%`, `vw`, `vh`, `vmin`, `vmax` or `rem`
+///   units, or `auto`.
+/// - Rects: `margin`, `padding` and `border-width`, with one to four lengths in CSS order, and `gap` with one or
+///   two lengths.
+/// - Keywords: `display`, `position`, `flex-direction`, `flex-wrap`, `justify-content`, `align-items`,
+///   `align-self` and `align-content`.
+/// - Numbers: `flex-grow` and `flex-shrink`.
+/// - Colors: `background-color` and `border-color`, as `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`,
+///   `rgb(r, g, b)`, `rgba(r, g, b, a)`, `transparent`, `black` or `white`.
+#[derive(Asset, TypePath, Clone, Debug, Default, PartialEq)]
+pub struct UiStyleSheet {
+    rules: Vec<StyleRule>,
+}
+
+impl UiStyleSheet {
+    /// The rules of the style sheet, in the order they are applied.
+    pub fn rules(&self) -> &[StyleRule] {
+        &self.rules
+    }
+
+    /// Applies every rule matching `class` to the given components, in order.
+    ///
+    /// Returns the background and border colors set by the matching rules, if any.
+    pub fn apply(&self, class: &Class, style: &mut Style) -> (Option<Color>, Option<Color>) {
+        let mut colors = (None, None);
+        for declaration in self.declarations(class) {
+            match *declaration {
+                StyleDeclaration::BackgroundColor(color) => colors.0 = Some(color),
+                StyleDeclaration::BorderColor(color) => colors.1 = Some(color),
+                _ => declaration.apply(style),
+            }
+        }
+        colors
+    }
+
+    /// The declarations of every rule matching `class`, in the order they are applied.
+    fn declarations<'a>(&'a self, class: &'a Class) -> impl Iterator<Item = &'a StyleDeclaration> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(class))
+            .flat_map(|rule| &rule.declarations)
+    }
+}
+
+impl FromStr for UiStyleSheet {
+    type Err = StyleSheetParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        parse_style_sheet(source)
+    }
+}
+
+/// A rule of a [`UiStyleSheet`], applying its declarations to nodes with any of its classes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleRule {
+    /// The class names selected by the rule, without the leading `.`
+    pub classes: Vec<String>,
+    /// The declarations applied to the selected nodes, in order.
+    pub declarations: Vec<StyleDeclaration>,
+}
+
+impl StyleRule {
+    /// Returns true if the rule selects a node with `class`.
+    pub fn matches(&self, class: &Class) -> bool {
+        self.classes.iter().any(|name| class.contains(name))
+    }
+}
+
+/// A single property set by a [`StyleRule`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StyleDeclaration {
+    Width(Val),
+    Height(Val),
+    MinWidth(Val),
+    MinHeight(Val),
+    MaxWidth(Val),
+    MaxHeight(Val),
+    Left(Val),
+    Right(Val),
+    Top(Val),
+    Bottom(Val),
+    FlexBasis(Val),
+    RowGap(Val),
+    ColumnGap(Val),
+    Margin(UiRect),
+    Padding(UiRect),
+    BorderWidth(UiRect),
+    Display(Display),
+    PositionType(PositionType),
+    FlexDirection(FlexDirection),
+    FlexWrap(FlexWrap),
+    JustifyContent(JustifyContent),
+    AlignItems(AlignItems),
+    AlignSelf(AlignSelf),
+    AlignContent(AlignContent),
+    FlexGrow(f32),
+    FlexShrink(f32),
+    BackgroundColor(Color),
+    BorderColor(Color),
+}
+
+impl StyleDeclaration {
+    /// Sets the property in `style`. Color declarations aren't part of [`Style`] and are ignored.
+    pub fn apply(&self, style: &mut Style) {
+        match *self {
+            Self::Width(val) => style.width = val,
+            Self::Height(val) => style.height = val,
+            Self::MinWidth(val) => style.min_width = val,
+            Self::MinHeight(val) => style.min_height = val,
+            Self::MaxWidth(val) => style.max_width = val,
+            Self::MaxHeight(val) => style.max_height = val,
+            Self::Left(val) => style.left = val,
+            Self::Right(val) => style.right = val,
+            Self::Top(val) => style.top = val,
+            Self::Bottom(val) => style.bottom = val,
+            Self::FlexBasis(val) => style.flex_basis = val,
+            Self::RowGap(val) => style.row_gap = val,
+            Self::ColumnGap(val) => style.column_gap = val,
+            Self::Margin(rect) => style.margin = rect,
+            Self::Padding(rect) => style.padding = rect,
+            Self::BorderWidth(rect) => style.border = rect,
+            Self::Display(display) => style.display = display,
+            Self::PositionType(position_type) => style.position_type = position_type,
+            Self::FlexDirection(flex_direction) => style.flex_direction = flex_direction,
+            Self::FlexWrap(flex_wrap) => style.flex_wrap = flex_wrap,
+            Self::JustifyContent(justify_content) => style.justify_content = justify_content,
+            Self::AlignItems(align_items) => style.align_items = align_items,
+            Self::AlignSelf(align_self) => style.align_self = align_self,
+            Self::AlignContent(align_content) => style.align_content = align_content,
+            Self::FlexGrow(flex_grow) => style.flex_grow = flex_grow,
+            Self::FlexShrink(flex_shrink) => style.flex_shrink = flex_shrink,
+            Self::BackgroundColor(_) | Self::BorderColor(_) => {}
+        }
+    }
+
+    /// Returns a declaration of the same property with its current value in `style`.
+    ///
+    /// Color declarations aren't part of [`Style`] and are returned unchanged.
+    pub fn current(&self, style: &Style) -> Self {
+        match *self {
+            Self::Width(_) => Self::Width(style.width),
+            Self::Height(_) => Self::Height(style.height),
+            Self::MinWidth(_) => Self::MinWidth(style.min_width),
+            Self::MinHeight(_) => Self::MinHeight(style.min_height),
+            Self::MaxWidth(_) => Self::MaxWidth(style.max_width),
+            Self::MaxHeight(_) => Self::MaxHeight(style.max_height),
+            Self::Left(_) => Self::Left(style.left),
+            Self::Right(_) => Self::Right(style.right),
+            Self::Top(_) => Self::Top(style.top),
+            Self::Bottom(_) => Self::Bottom(style.bottom),
+            Self::FlexBasis(_) => Self::FlexBasis(style.flex_basis),
+            Self::RowGap(_) => Self::RowGap(style.row_gap),
+            Self::ColumnGap(_) => Self::ColumnGap(style.column_gap),
+            Self::Margin(_) => Self::Margin(style.margin),
+            Self::Padding(_) => Self::Padding(style.padding),
+            Self::BorderWidth(_) => Self::BorderWidth(style.border),
+            Self::Display(_) => Self::Display(style.display),
+            Self::PositionType(_) => Self::PositionType(style.position_type),
+            Self::FlexDirection(_) => Self::FlexDirection(style.flex_direction),
+            Self::FlexWrap(_) => Self::FlexWrap(style.flex_wrap),
+            Self::JustifyContent(_) => Self::JustifyContent(style.justify_content),
+            Self::AlignItems(_) => Self::AlignItems(style.align_items),
+            Self::AlignSelf(_) => Self::AlignSelf(style.align_self),
+            Self::AlignContent(_) => Self::AlignContent(style.align_content),
+            Self::FlexGrow(_) => Self::FlexGrow(style.flex_grow),
+            Self::FlexShrink(_) => Self::FlexShrink(style.flex_shrink),
+            Self::BackgroundColor(_) | Self::BorderColor(_) => *self,
+        }
+    }
+
+    /// Returns true if both declarations set the same property.
+    fn is_same_property(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// The classes of a UI node, used to select the rules of a [`UiStyleSheet`] applied to it.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Class(Vec<String>);
+
+impl Class {
+    /// Creates a `Class` from a whitespace separated list of class names.
+    pub fn new(classes: impl AsRef<str>) -> Self {
+        let mut class = Self::default();
+        for name in classes.as_ref().split_whitespace() {
+            class.add(name);
+        }
+        class
+    }
+
+    /// Returns true if the node has the class `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|class| class == name)
+    }
+
+    /// Adds the class `name`, if the node doesn't have it already.
+    pub fn add(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        if !self.contains(&name) {
+            self.0.push(name);
+        }
+    }
+
+    /// Removes the class `name`.
+    ///
+    /// The properties set by the class's rules are restored by [`apply_style_sheets`], unless other rules set
+    /// them.
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|class| class != name);
+    }
+
+    /// Iterates over the class names.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// The [`UiStyleSheet`] used by this node and its descendants, unless a descendant has its own `StyleSheetRef`.
+#[derive(Component, Clone, Debug, Default, Deref, Reflect)]
+#[reflect(Component, Default)]
+pub struct StyleSheetRef(pub Handle<UiStyleSheet>);
+
+impl From<Handle<UiStyleSheet>> for StyleSheetRef {
+    fn from(handle: Handle<UiStyleSheet>) -> Self {
+        Self(handle)
+    }
+}
+
+/// The values of the properties of a node replaced by the rules of its style sheet, so they can be restored when
+/// the rules stop applying.
+///
+/// Added to nodes with a [`Class`] by [`apply_style_sheets`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct AppliedStyleRules {
+    /// The style sheet the rules were applied from.
+    sheet: Option<AssetId<UiStyleSheet>>,
+    /// A declaration of the previous value of each [`Style`] property set by the rules.
+    replaced: Vec<StyleDeclaration>,
+    /// The previous background color, if the rules set it, or `None` if the node didn't have one.
+    background_color: Option<Option<BackgroundColor>>,
+    /// The previous border color, if the rules set it, or `None` if the node didn't have one.
+    border_color: Option<Option<BorderColor>>,
+}
+
+impl AppliedStyleRules {
+    /// Restores the properties replaced by the rules.
+    ///
+    /// Returns whether the background and border colors added by the rules are removed.
+    fn restore(
+        &mut self,
+        entity: Entity,
+        commands: &mut Commands,
+        style: &mut Mut<Style>,
+        background_color: Option<&mut Mut<BackgroundColor>>,
+        border_color: Option<&mut Mut<BorderColor>>,
+    ) -> (bool, bool) {
+        for declaration in self.replaced.drain(..) {
+            declaration.apply(style);
+        }
+        let removed_background = match (self.background_color.take(), background_color) {
+            (Some(Some(previous)), Some(background_color)) => {
+                **background_color = previous;
+                false
+            }
+            (Some(None), _) => {
+                commands.entity(entity).remove::<BackgroundColor>();
+                true
+            }
+            _ => false,
+        };
+        let removed_border = match (self.border_color.take(), border_color) {
+            (Some(Some(previous)), Some(border_color)) => {
+                **border_color = previous;
+                false
+            }
+            (Some(None), _) => {
+                commands.entity(entity).remove::<BorderColor>();
+                true
+            }
+            _ => false,
+        };
+        self.sheet = None;
+        (removed_background, removed_border)
+    }
+}
+
+/// Applies the rules of style sheets to the nodes with matching [`Class`]es.
+///
+/// The rules are applied again to a node when its `Class` changes, its style sheet is loaded or modified, which
+/// hot reloads style sheets when asset watching is enabled, or it uses a different style sheet because a
+/// [`StyleSheetRef`] was changed or removed, or the node or one of its ancestors was reparented.
+///
+/// Before the rules are applied again, the properties set by the rules last applied are restored, so properties
+/// that are no longer set by any rule return to their values from before the style sheet was applied. Changes made
+/// to those properties by other systems while the rules applied are lost.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_style_sheets(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<UiStyleSheet>>,
+    style_sheets: Res<Assets<UiStyleSheet>>,
+    sheet_ref_query: Query<Ref<StyleSheetRef>>,
+    mut removed_sheet_refs: RemovedComponents<StyleSheetRef>,
+    parent_query: Query<&Parent>,
+    reparented_query: Query<(), Changed<Parent>>,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut node_query: Query<(
+        Entity,
+        Ref<Class>,
+        Option<&mut AppliedStyleRules>,
+        &mut Style,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+    )>,
+    mut unclassed_query: Query<
+        (
+            Entity,
+            &mut AppliedStyleRules,
+            &mut Style,
+            Option<&mut BackgroundColor>,
+            Option<&mut BorderColor>,
+        ),
+        Without<Class>,
+    >,
+) {
+    // Nodes whose `Class` was removed
+    for (entity, mut applied, mut style, mut background_color, mut border_color) in
+        &mut unclassed_query
+    {
+        applied.restore(
+            entity,
+            &mut commands,
+            &mut style,
+            background_color.as_mut(),
+            border_color.as_mut(),
+        );
+        commands.entity(entity).remove::<AppliedStyleRules>();
+    }
+
+    let mut changed_sheets = HashSet::<AssetId<UiStyleSheet>>::default();
+    for event in asset_events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            changed_sheets.insert(*id);
+        }
+    }
+    // Any of these can change which style sheet a node uses
+    let sheets_moved = removed_sheet_refs.read().count() > 0
+        || removed_parents.read().count() > 0
+        || !reparented_query.is_empty()
+        || sheet_ref_query
+            .iter()
+            .any(|sheet_ref| sheet_ref.is_changed());
+
+    for (entity, class, applied, mut style, mut background_color, mut border_color) in
+        &mut node_query
+    {
+        if !class.is_changed() && !sheets_moved && changed_sheets.is_empty() {
+            continue;
+        }
+
+        // Use the style sheet of the nearest node with a `StyleSheetRef`
+        let sheet_ref = std::iter::once(entity)
+            .chain(parent_query.iter_ancestors(entity))
+            .find_map(|entity| sheet_ref_query.get(entity).ok());
+        let sheet = sheet_ref.as_ref().map(|sheet_ref| sheet_ref.id());
+        let applied_sheet = applied.as_ref().and_then(|applied| applied.sheet);
+
+        if !class.is_changed()
+            && sheet == applied_sheet
+            && !sheet_ref.is_some_and(|sheet_ref| sheet_ref.is_changed())
+            && !sheet.is_some_and(|sheet| changed_sheets.contains(&sheet))
+        {
+            continue;
+        }
+
+        // Wait for the style sheet to load before replacing the rules applied from the previous sheet
+        let style_sheet = sheet.and_then(|sheet| style_sheets.get(sheet));
+        if sheet.is_some() && style_sheet.is_none() {
+            continue;
+        }
+
+        let mut applied = applied.map(|mut applied| std::mem::take(&mut *applied));
+        if let Some(applied) = applied.as_mut() {
+            let (removed_background, removed_border) = applied.restore(
+                entity,
+                &mut commands,
+                &mut style,
+                background_color.as_mut(),
+                border_color.as_mut(),
+            );
+            // The colors are inserted again if the rules still set them
+            if removed_background {
+                background_color = None;
+            }
+            if removed_border {
+                border_color = None;
+            }
+        }
+
+        let Some(style_sheet) = style_sheet else {
+            if applied.is_some() {
+                commands.entity(entity).remove::<AppliedStyleRules>();
+            }
+            continue;
+        };
+
+        let mut applied = applied.unwrap_or_default();
+        applied.sheet = sheet;
+        for declaration in style_sheet.declarations(&class) {
+            match *declaration {
+                StyleDeclaration::BackgroundColor(color) => match background_color.as_mut() {
+                    Some(background_color) => {
+                        applied
+                            .background_color
+                            .get_or_insert(Some(**background_color));
+                        background_color.0 = color;
+                    }
+                    None => {
+                        applied.background_color.get_or_insert(None);
+                        commands.entity(entity).insert(BackgroundColor(color));
+                    }
+                },
+                StyleDeclaration::BorderColor(color) => match border_color.as_mut() {
+                    Some(border_color) => {
+                        applied.border_color.get_or_insert(Some(**border_color));
+                        **border_color = BorderColor::all(color);
+                    }
+                    None => {
+                        applied.border_color.get_or_insert(None);
+                        commands.entity(entity).insert(BorderColor::all(color));
+                    }
+                },
+                _ => {
+                    if !applied
+                        .replaced
+                        .iter()
+                        .any(|replaced| replaced.is_same_property(declaration))
+                    {
+                        applied.replaced.push(declaration.current(&style));
+                    }
+                    declaration.apply(&mut style);
+                }
+            }
+        }
+        commands.entity(entity).insert(applied);
+    }
+}
+
+/// Loads [`UiStyleSheet`]s from `.css` files.
+#[derive(Default)]
+pub struct UiStyleSheetLoader;
+
+/// Possible errors that can be produced by [`UiStyleSheetLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum UiStyleSheetLoaderError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The style sheet isn't valid UTF-8
+    #[error(transparent)]
+    Utf8(#[from] std::str::Utf8Error),
+    /// The style sheet couldn't be parsed
+    #[error(transparent)]
+    Parse(#[from] StyleSheetParseError),
+}
+
+impl AssetLoader for UiStyleSheetLoader {
+    type Asset = UiStyleSheet;
+    type Settings = ();
+    type Error = UiStyleSheetLoaderError;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<UiStyleSheet, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(std::str::from_utf8(&bytes)?.parse()?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["css"]
+    }
+}
+
+/// An error in the source of a [`UiStyleSheet`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}: {message}")]
+pub struct StyleSheetParseError {
+    /// The line of the error, starting from 1
+    pub line: usize,
+    /// A description of the error
+    pub message: String,
+}
+
+fn parse_style_sheet(source: &str) -> Result<UiStyleSheet, StyleSheetParseError> {
+    let source = strip_comments(source);
+    let line_at = |offset: usize| source[..offset].matches('\n').count() + 1;
+    let error_at = |offset: usize, message: String| StyleSheetParseError {
+        line: line_at(offset),
+        message,
+    };
+
+    let mut rules = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = source[offset..].find(|c: char| !c.is_whitespace()) {
+        let start = offset + start;
+        let open = start
+            + source[start..]
+                .find('{')
+                .ok_or_else(|| error_at(start, "expected `{` after the selectors".to_string()))?;
+        let close = open
+            + source[open..]
+                .find('}')
+                .ok_or_else(|| error_at(open, "unclosed `{`".to_string()))?;
+
+        let mut classes = Vec::new();
+        for selector in source[start..open].split(',') {
+            let selector = selector.trim();
+            match selector.strip_prefix('.') {
+                Some(name) if is_class_name(name) => classes.push(name.to_string()),
+                _ => {
+                    return Err(error_at(
+                        start,
+                        format!("expected a class selector, found `{selector}`"),
+                    ))
+                }
+            }
+        }
+
+        let mut declarations = Vec::new();
+        let mut declaration_offset = open + 1;
+        for declaration in source[open + 1..close].split(';') {
+            let declaration_start =
+                declaration_offset + declaration.find(|c: char| !c.is_whitespace()).unwrap_or(0);
+            declaration_offset += declaration.len() + 1;
+            let declaration = declaration.trim();
+            if declaration.is_empty() {
+                continue;
+            }
+            let Some((property, value)) = declaration.split_once(':') else {
+                return Err(error_at(
+                    declaration_start,
+                    format!("expected `property: value`, found `{declaration}`"),
+                ));
+            };
+            parse_declaration(property.trim(), value.trim(), &mut declarations)
+                .map_err(|message| error_at(declaration_start, message))?;
+        }
+
+        rules.push(StyleRule {
+            classes,
+            declarations,
+        });
+        offset = close + 1;
+    }
+
+    Ok(UiStyleSheet { rules })
+}
+
+/// Replaces comments with spaces, keeping their newlines so line numbers are unchanged.
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find("*/")
+            .map_or(rest.len(), |end| start + end + 2);
+        stripped.extend(
+            rest[start..end]
+                .chars()
+                .map(|c| if c == '\n' { '\n' } else { ' ' }),
+        );
+        rest = &rest[end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn is_class_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn parse_declaration(
+    property: &str,
+    value: &str,
+    declarations: &mut Vec<StyleDeclaration>,
+) -> Result<(), String> {
+    use StyleDeclaration as D;
+    let declaration = match property {
+        "width" => D::Width(parse_val(value)?),
+        "height" => D::Height(parse_val(value)?),
+        "min-width" => D::MinWidth(parse_val(value)?),
+        "min-height" => D::MinHeight(parse_val(value)?),
+        "max-width" => D::MaxWidth(parse_val(value)?),
+        "max-height" => D::MaxHeight(parse_val(value)?),
+        "left" => D::Left(parse_val(value)?),
+        "right" => D::Right(parse_val(value)?),
+        "top" => D::Top(parse_val(value)?),
+        "bottom" => D::Bottom(parse_val(value)?),
+        "flex-basis" => D::FlexBasis(parse_val(value)?),
+        "row-gap" => D::RowGap(parse_val(value)?),
+        "column-gap" => D::ColumnGap(parse_val(value)?),
+        "gap" => {
+            let vals = parse_vals(value)?;
+            let [row, column] = match vals[..] {
+                [gap] => [gap; 2],
+                [row, column] => [row, column],
+                _ => return Err(format!("expected one or two lengths, found `{value}`")),
+            };
+            declarations.push(D::RowGap(row));
+            D::ColumnGap(column)
+        }
+        "margin" => D::Margin(parse_rect(value)?),
+        "padding" => D::Padding(parse_rect(value)?),
+        "border-width" => D::BorderWidth(parse_rect(value)?),
+        "display" => D::Display(parse_keyword(
+            value,
+            &[
+                ("flex", Display::Flex),
+                ("grid", Display::Grid),
+                ("block", Display::Block),
+                ("none", Display::None),
+            ],
+        )?),
+        "position" => D::PositionType(parse_keyword(
+            value,
+            &[
+                ("relative", PositionType::Relative),
+                ("absolute", PositionType::Absolute),
+            ],
+        )?),
+        "flex-direction" => D::FlexDirection(parse_keyword(
+            value,
+            &[
+                ("row", FlexDirection::Row),
+                ("column", FlexDirection::Column),
+                ("row-reverse", FlexDirection::RowReverse),
+                ("column-reverse", FlexDirection::ColumnReverse),
+            ],
+        )?),
+        "flex-wrap" => D::FlexWrap(parse_keyword(
+            value,
+            &[
+                ("nowrap", FlexWrap::NoWrap),
+                ("wrap", FlexWrap::Wrap),
+                ("wrap-reverse", FlexWrap::WrapReverse),
+            ],
+        )?),
+        "justify-content" => D::JustifyContent(parse_keyword(
+            value,
+            &[
+                ("normal", JustifyContent::Default),
+                ("start", JustifyContent::Start),
+                ("end", JustifyContent::End),
+                ("flex-start", JustifyContent::FlexStart),
+                ("flex-end", JustifyContent::FlexEnd),
+                ("center", JustifyContent::Center),
+                ("stretch", JustifyContent::Stretch),
+                ("space-between", JustifyContent::SpaceBetween),
+                ("space-evenly", JustifyContent::SpaceEvenly),
+                ("space-around", JustifyContent::SpaceAround),
+            ],
+        )?),
+        "align-items" => D::AlignItems(parse_keyword(
+            value,
+            &[
+                ("normal", AlignItems::Default),
+                ("start", AlignItems::Start),
+                ("end", AlignItems::End),
+                ("flex-start", AlignItems::FlexStart),
+                ("flex-end", AlignItems::FlexEnd),
+                ("center", AlignItems::Center),
+                ("baseline", AlignItems::Baseline),
+                ("stretch", AlignItems::Stretch),
+            ],
+        )?),
+        "align-self" => D::AlignSelf(parse_keyword(
+            value,
+            &[
+                ("auto", AlignSelf::Auto),
+                ("start", AlignSelf::Start),
+                ("end", AlignSelf::End),
+                ("flex-start", AlignSelf::FlexStart),
+                ("flex-end", AlignSelf::FlexEnd),
+                ("center", AlignSelf::Center),
+                ("baseline", AlignSelf::Baseline),
+                ("stretch", AlignSelf::Stretch),
+            ],
+        )?),
+        "align-content" => D::AlignContent(parse_keyword(
+            value,
+            &[
+                ("normal", AlignContent::Default),
+                ("start", AlignContent::Start),
+                ("end", AlignContent::End),
+                ("flex-start", AlignContent::FlexStart),
+                ("flex-end", AlignContent::FlexEnd),
+                ("center", AlignContent::Center),
+                ("stretch", AlignContent::Stretch),
+                ("space-between", AlignContent::SpaceBetween),
+                ("space-evenly", AlignContent::SpaceEvenly),
+                ("space-around", AlignContent::SpaceAround),
+            ],
+        )?),
+        "flex-grow" => D::FlexGrow(parse_number(value)?),
+        "flex-shrink" => D::FlexShrink(parse_number(value)?),
+        "background-color" => D::BackgroundColor(parse_color(value)?),
+        "border-color" => D::BorderColor(parse_color(value)?),
+        _ => return Err(format!("unsupported property `{property}`")),
+    };
+    declarations.push(declaration);
+    Ok(())
+}
+
+fn parse_keyword<T: Copy>(value: &str, keywords: &[(&str, T)]) -> Result<T, String> {
+    keywords
+        .iter()
+        .find(|(keyword, _)| *keyword == value)
+        .map(|(_, value)| *value)
+        .ok_or_else(|| format!("unexpected value `{value}`"))
+}
+
+fn parse_number(value: &str) -> Result<f32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a number, found `{value}`"))
+}
+
+fn parse_val(value: &str) -> Result<Val, String> {
+    if value == "auto" {
+        return Ok(Val::Auto);
+    }
+    let units: [(&str, fn(f32) -> Val); 7] = [
+        ("px", Val::Px),
+        ("%", Val::Percent),
+        ("vmin", Val::VMin),
+        ("vmax", Val::VMax),
+        ("vw", Val::Vw),
+        ("vh", Val::Vh),
+        ("rem", Val::Rem),
+    ];
+    for (unit, val) in units {
+        if let Some(number) = value.strip_suffix(unit) {
+            return number
+                .parse()
+                .map(val)
+                .map_err(|_| format!("expected a length, found `{value}`"));
+        }
+    }
+    // Unitless zero is a valid length in CSS
+    if value.parse::<f32>() == Ok(0.) {
+        Ok(Val::Px(0.))
+    } else {
+        Err(format!("expected a length, found `{value}`"))
+    }
+}
+
+fn parse_vals(value: &str) -> Result<Vec<Val>, String> {
+    value.split_whitespace().map(parse_val).collect()
+}
+
+/// Parses one to four lengths in the CSS order: top, right, bottom, left.
+fn parse_rect(value: &str) -> Result<UiRect, String> {
+    let [top, right, bottom, left] = match parse_vals(value)?[..] {
+        [all] => [all; 4],
+        [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+        [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+        [top, right, bottom, left] => [top, right, bottom, left],
+        _ => return Err(format!("expected one to four lengths, found `{value}`")),
+    };
+    Ok(UiRect::new(left, right, top, bottom))
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    let invalid = || format!("expected a color, found `{value}`");
+    match value {
+        "transparent" => return Ok(Color::NONE),
+        "black" => return Ok(Color::BLACK),
+        "white" => return Ok(Color::WHITE),
+        _ => {}
+    }
+    if value.starts_with('#') {
+        return Srgba::hex(value).map(Color::from).map_err(|_| invalid());
+    }
+    let Some(arguments) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+        .and_then(|arguments| arguments.strip_suffix(')'))
+    else {
+        return Err(invalid());
+    };
+    let components = arguments
+        .split(',')
+        .map(|component| component.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid())?;
+    match components[..] {
+        [r, g, b] => Ok(Color::srgb(r / 255., g / 255., b / 255.)),
+        [r, g, b, a] => Ok(Color::srgba(r / 255., g / 255., b / 255., a)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rules() {
+        let sheet: UiStyleSheet = "
+            /* a comment */
+            .panel {
+                display: flex;
+                padding: 8px 16px;
+                width: 50%;
+                background-color: #ff0000;
+            }
+
+            .a, .b { gap: 2rem 0; border-color: rgb(0, 255, 0) }
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            sheet.rules(),
+            &[
+                StyleRule {
+                    classes: vec!["panel".to_string()],
+                    declarations: vec![
+                        StyleDeclaration::Display(Display::Flex),
+                        StyleDeclaration::Padding(UiRect::new(
+                            Val::Px(16.),
+                            Val::Px(16.),
+                            Val::Px(8.),
+                            Val::Px(8.)
+                        )),
+                        StyleDeclaration::Width(Val::Percent(50.)),
+                        StyleDeclaration::BackgroundColor(Color::srgb(1., 0., 0.)),
+                    ],
+                },
+                StyleRule {
+                    classes: vec!["a".to_string(), "b".to_string()],
+                    declarations: vec![
+                        StyleDeclaration::RowGap(Val::Rem(2.)),
+                        StyleDeclaration::ColumnGap(Val::Px(0.)),
+                        StyleDeclaration::BorderColor(Color::srgb(0., 1., 0.)),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_report_the_line() {
+        let error = ".panel {\n    width: 10px;\n    colour: red;\n}"
+            .parse::<UiStyleSheet>()
+            .unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let error = "/*\n*/\n#panel { width: 10px }"
+            .parse::<UiStyleSheet>()
+            .unwrap_err();
+        assert_eq!(error.line, 3);
+    }
+
+    #[test]
+    fn later_rules_override_earlier_rules() {
+        let sheet: UiStyleSheet = ".a { width: 10px; height: 5px } .b { width: 20px }"
+            .parse()
+            .unwrap();
+
+        let mut style = Style::default();
+        sheet.apply(&Class::new("b a"), &mut style);
+        assert_eq!(style.width, Val::Px(20.));
+        assert_eq!(style.height, Val::Px(5.));
+
+        let mut style = Style::default();
+        sheet.apply(&Class::new("c"), &mut style);
+        assert_eq!(style, Style::default());
+    }
+
+    #[test]
+    fn rules_that_stop_applying_are_restored() {
+        use bevy_hierarchy::BuildWorldChildren;
+
+        let mut world = World::new();
+        world.init_resource::<Events<AssetEvent<UiStyleSheet>>>();
+        let mut style_sheets = Assets::<UiStyleSheet>::default();
+        let sheet_a = style_sheets.add(
+            ".a { width: 10px; background-color: #ff0000 } .b { height: 5px }"
+                .parse::<UiStyleSheet>()
+                .unwrap(),
+        );
+        let sheet_b = style_sheets.add(".b { height: 7px }".parse::<UiStyleSheet>().unwrap());
+        world.insert_resource(style_sheets);
+
+        let root_a = world.spawn(StyleSheetRef(sheet_a)).id();
+        let root_b = world.spawn(StyleSheetRef(sheet_b)).id();
+        let root_without_sheet = world.spawn_empty().id();
+        let base = Style {
+            width: Val::Px(1.),
+            ..Default::default()
+        };
+        let node = world.spawn((Class::new("a b"), base.clone())).id();
+        world.entity_mut(root_a).add_child(node);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_style_sheets);
+        let mut run = |world: &mut World| {
+            schedule.run(world);
+            let style = world.get::<Style>(node).unwrap();
+            (
+                style.width,
+                style.height,
+                world.get::<BackgroundColor>(node).map(|color| color.0),
+            )
+        };
+
+        let red = Some(Color::srgb(1., 0., 0.));
+        assert_eq!(run(&mut world), (Val::Px(10.), Val::Px(5.), red));
+
+        world.get_mut::<Class>(node).unwrap().remove("a");
+        assert_eq!(run(&mut world), (Val::Px(1.), Val::Px(5.), None));
+
+        world.get_mut::<Class>(node).unwrap().add("a");
+        assert_eq!(run(&mut world), (Val::Px(10.), Val::Px(5.), red));
+
+        // Reparenting uses the style sheet of the new ancestors
+        world.entity_mut(root_b).add_child(node);
+        assert_eq!(run(&mut world), (Val::Px(1.), Val::Px(7.), None));
+
+        world.entity_mut(root_without_sheet).add_child(node);
+        assert_eq!(run(&mut world), (Val::Px(1.), Val::Auto, None));
+
+        world.entity_mut(root_a).add_child(node);
+        assert_eq!(run(&mut world), (Val::Px(10.), Val::Px(5.), red));
+
+        world.entity_mut(node).remove::<Class>();
+        assert_eq!(run(&mut world), (Val::Px(1.), Val::Auto, None));
+        assert_eq!(world.get::<Style>(node), Some(&base));
+        assert!(world.get::<AppliedStyleRules>(node).is_none());
+    }
+}
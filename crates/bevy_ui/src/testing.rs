@@ -0,0 +1,263 @@
+//! Utilities for testing UI layouts headlessly, without a renderer or an event loop.
+//!
+//! [`ui_test_world`] and [`ui_test_schedule`] set up a world with a primary window and, with the `render` feature,
+//! a camera, and the systems that lay out the UI. [`ui_snapshot`] then serializes the laid out UI tree deterministically, for comparisons
+//! against golden files in CI:
+//!
+//! ```
+//! # use bevy_ui::{prelude::*, testing::{ui_snapshot, ui_test_schedule, ui_test_world}};
+//! # use bevy_hierarchy::BuildWorldChildren;
+//! # use bevy_transform::prelude::{GlobalTransform, Transform};
+//! let mut world = ui_test_world(800., 600.);
+//! let mut schedule = ui_test_schedule();
+//!
+//! // The components of a `NodeBundle` that the layout needs
+//! let node = |style| (Node::default(), style, Transform::default(), GlobalTransform::default());
+//! let child = world
+//!     .spawn(node(Style {
+//!         width: Val::Px(100.),
+//!         height: Val::Px(50.),
+//!         ..Default::default()
+//!     }))
+//!     .id();
+//! world
+//!     .spawn(node(Style {
+//!         width: Val::Percent(100.),
+//!         ..Default::default()
+//!     }))
+//!     .add_child(child);
+//!
+//! schedule.run(&mut world);
+//! let snapshot = ui_snapshot(&mut world);
+//! assert_eq!(
+//!     snapshot.lines().nth(1),
+//!     Some(format!("  {}v1 rect: [0, 0, 100, 50] clip: none stack: 1", child.index()).as_str())
+//! );
+//! ```
+
+use std::fmt::Write;
+
+#[cfg(feature = "render")]
+use bevy_asset::{AssetEvent, Assets};
+#[cfg(feature = "render")]
+use bevy_core_pipeline::core_2d::Camera2dBundle;
+use bevy_ecs::{
+    entity::Entity,
+    event::Events,
+    query::{QueryState, Without},
+    schedule::{apply_deferred, IntoSystemConfigs, Schedule},
+    world::World,
+};
+use bevy_hierarchy::{Children, Parent};
+#[cfg(feature = "render")]
+use bevy_render::{
+    camera::{camera_system, ManualTextureViews, OrthographicProjection},
+    texture::Image,
+};
+use bevy_transform::{
+    components::GlobalTransform,
+    systems::{propagate_transforms, sync_simple_transforms},
+};
+use bevy_utils::default;
+#[cfg(feature = "render")]
+use bevy_window::WindowCreated;
+use bevy_window::{
+    PrimaryWindow, Window, WindowResized, WindowResolution, WindowScaleFactorChanged,
+};
+
+use crate::{
+    layout::ui_surface::UiSurface,
+    stack::ui_stack_system,
+    ui_layout_system,
+    update::{update_clipping_system, update_target_camera_system, update_ui_root_scale_system},
+    CalculatedClip, Node, PercentRounding, RootFontSize, UiLayoutSettings, UiScale, UiStack,
+};
+
+type NodeQueryState = QueryState<(
+    &'static Node,
+    &'static GlobalTransform,
+    Option<&'static CalculatedClip>,
+)>;
+
+/// Creates a world for testing UI layouts, with a primary window of the given logical size and, with the `render`
+/// feature, a 2D camera rendering to it.
+///
+/// The window has a scale factor of 1, it can be changed through its [`Window`] component.
+pub fn ui_test_world(window_width: f32, window_height: f32) -> World {
+    let mut world = World::new();
+    world.init_resource::<UiScale>();
+    world.init_resource::<RootFontSize>();
+    world.init_resource::<PercentRounding>();
+    world.init_resource::<UiLayoutSettings>();
+    world.init_resource::<UiSurface>();
+    world.init_resource::<UiStack>();
+    world.init_resource::<Events<WindowScaleFactorChanged>>();
+    world.init_resource::<Events<WindowResized>>();
+    // Required for the camera system
+    #[cfg(feature = "render")]
+    {
+        world.init_resource::<Events<WindowCreated>>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<ManualTextureViews>();
+    }
+
+    // spawn a dummy primary window and camera
+    world.spawn((
+        Window {
+            resolution: WindowResolution::new(window_width, window_height),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    #[cfg(feature = "render")]
+    world.spawn(Camera2dBundle::default());
+    world
+}
+
+/// Creates a schedule that lays out the UI of a world created by [`ui_test_world`], and updates the transforms,
+/// stack indices and clipping rects of its nodes.
+pub fn ui_test_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    // UI is driven by calculated camera target info, so we need to run the camera system first
+    #[cfg(feature = "render")]
+    schedule
+        .add_systems(camera_system::<OrthographicProjection>.before(update_target_camera_system));
+    schedule.add_systems(
+        (
+            update_target_camera_system,
+            update_ui_root_scale_system,
+            apply_deferred,
+            ui_layout_system,
+            sync_simple_transforms,
+            propagate_transforms,
+            ui_stack_system,
+            update_clipping_system,
+            apply_deferred,
+        )
+            .chain(),
+    );
+    schedule
+}
+
+/// Serializes the UI tree of `world` into a deterministic string.
+///
+/// Each node is written on its own line, indented by its depth in the tree, with its entity as
+/// `{index}v{generation}`, its rect in logical pixels as `[x, y, width, height]`, its clipping rect in the same
+/// format, or `none` if it isn't clipped, and its stack index. Root nodes are ordered by their stack index and
+/// children by their order in [`Children`].
+pub fn ui_snapshot(world: &mut World) -> String {
+    let mut roots: Vec<(u32, Entity)> = world
+        .query_filtered::<(Entity, &Node), Without<Parent>>()
+        .iter(world)
+        .map(|(entity, node)| (node.stack_index(), entity))
+        .collect();
+    roots.sort();
+
+    let node_query = world.query::<(&Node, &GlobalTransform, Option<&CalculatedClip>)>();
+    let mut out = String::new();
+    for (_, root) in roots {
+        write_node(world, &node_query, root, 0, &mut out);
+    }
+    out
+}
+
+fn write_node(
+    world: &World,
+    node_query: &NodeQueryState,
+    entity: Entity,
+    depth: usize,
+    out: &mut String,
+) {
+    let Ok((node, transform, clip)) = node_query.get_manual(world, entity) else {
+        return;
+    };
+    let rect = node.logical_rect(transform);
+    let clip = clip.map_or("none".to_string(), |clip| {
+        format!(
+            "[{}, {}, {}, {}]",
+            clip.clip.min.x,
+            clip.clip.min.y,
+            clip.clip.width(),
+            clip.clip.height()
+        )
+    });
+    writeln!(
+        out,
+        "{:indent$}{}v{} rect: [{}, {}, {}, {}] clip: {clip} stack: {}",
+        "",
+        entity.index(),
+        entity.generation(),
+        rect.min.x,
+        rect.min.y,
+        rect.width(),
+        rect.height(),
+        node.stack_index(),
+        indent = 2 * depth,
+    )
+    .ok();
+
+    if let Some(children) = world.get::<Children>(entity) {
+        for &child in children {
+            write_node(world, node_query, child, depth + 1, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_transform::prelude::{GlobalTransform, Transform};
+
+    use super::{ui_snapshot, ui_test_schedule, ui_test_world};
+    use crate::prelude::*;
+
+    // Only the components that the layout needs, so the test also runs without the `render` feature
+    fn node(style: Style) -> (Node, Style, Transform, GlobalTransform) {
+        (
+            Node::default(),
+            style,
+            Transform::default(),
+            GlobalTransform::default(),
+        )
+    }
+
+    #[test]
+    fn snapshot_lists_clipped_nodes_in_tree_order() {
+        let mut world = ui_test_world(400., 300.);
+        let mut schedule = ui_test_schedule();
+
+        let square = |side| {
+            node(Style {
+                width: Val::Px(side),
+                height: Val::Px(side),
+                flex_shrink: 0.,
+                ..Default::default()
+            })
+        };
+        let big = world.spawn(square(150.)).id();
+        let small = world.spawn(square(20.)).id();
+        let root = world
+            .spawn(node(Style {
+                width: Val::Px(100.),
+                height: Val::Px(100.),
+                overflow: Overflow::clip(),
+                ..Default::default()
+            }))
+            .push_children(&[big, small])
+            .id();
+
+        schedule.run(&mut world);
+        assert_eq!(
+            ui_snapshot(&mut world),
+            format!(
+                "{}v1 rect: [0, 0, 100, 100] clip: none stack: 0\n\
+                \x20 {}v1 rect: [0, 0, 150, 150] clip: [0, 0, 100, 100] stack: 1\n\
+                \x20 {}v1 rect: [150, 0, 20, 20] clip: [0, 0, 100, 100] stack: 2\n",
+                root.index(),
+                big.index(),
+                small.index()
+            )
+        );
+    }
+}
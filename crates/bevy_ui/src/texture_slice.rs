@@ -2,15 +2,20 @@
 //
 // A more centralized solution should be investigated in the future
 
+// The slices are only drawn by the render module
+#![cfg_attr(not(feature = "render"), allow(dead_code))]
+
 use bevy_asset::{AssetEvent, Assets};
 use bevy_ecs::prelude::*;
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Mat4, Rect, Vec2};
 use bevy_render::texture::Image;
-use bevy_sprite::{ImageScaleMode, TextureAtlas, TextureAtlasLayout, TextureSlice};
+use bevy_sprite::{ImageScaleMode, SliceScaleMode, TextureAtlas, TextureAtlasLayout, TextureSlice};
 use bevy_transform::prelude::*;
 use bevy_utils::HashSet;
 
-use crate::{CalculatedClip, ExtractedUiNode, Node, NodeType, UiImage};
+use crate::{BorderImage, Node, UiImage};
+#[cfg(feature = "render")]
+use crate::{CalculatedClip, ExtractedUiNode, NodeType};
 
 /// Component storing texture slices for image nodes entities with a tiled or sliced  [`ImageScaleMode`]
 ///
@@ -31,6 +36,7 @@ impl ComputedTextureSlices {
     /// * `sprite` - The sprite component
     /// * `handle` - The sprite texture handle
     #[must_use]
+    #[cfg(feature = "render")]
     pub(crate) fn extract_ui_nodes<'a>(
         &'a self,
         transform: &'a GlobalTransform,
@@ -50,32 +56,121 @@ impl ComputedTextureSlices {
             flip_y = true;
         }
         self.slices.iter().map(move |slice| {
-            let offset = (slice.offset * flip).extend(0.0);
-            let transform = transform.mul_transform(Transform::from_translation(offset));
-            let scale = slice.draw_size / slice.texture_rect.size();
-            let mut rect = slice.texture_rect;
-            rect.min *= scale;
-            rect.max *= scale;
-            let atlas_size = Some(self.image_size * scale);
+            let (transform, rect, atlas_size) =
+                slice_geometry(slice, flip, self.image_size, transform);
             ExtractedUiNode {
                 stack_index: node.stack_index,
                 color: image.color.into(),
-                transform: transform.compute_matrix(),
+                transform,
                 rect,
                 flip_x,
                 flip_y,
                 image: image.texture.id(),
-                atlas_size,
-                clip: clip.map(|clip| clip.clip),
+                atlas_size: Some(atlas_size),
+                clip: clip.copied(),
                 camera_entity,
                 border: [0.; 4],
-                border_radius: [0.; 4],
+                border_radius: [Vec2::ZERO; 4],
+                repeat: None,
                 node_type: NodeType::Rect,
             }
         })
     }
 }
 
+/// Returns the transform, rect and atlas size of an [`ExtractedUiNode`] that draws `slice` of a texture of
+/// `image_size`, in a node with the given `transform`.
+///
+/// `flip` multiplies the offset of the slice, whose y-axis points up.
+pub(crate) fn slice_geometry(
+    slice: &TextureSlice,
+    flip: Vec2,
+    image_size: Vec2,
+    transform: &GlobalTransform,
+) -> (Mat4, Rect, Vec2) {
+    let offset = (slice.offset * flip).extend(0.0);
+    let transform = transform.mul_transform(Transform::from_translation(offset));
+    let scale = slice.draw_size / slice.texture_rect.size();
+    let mut rect = slice.texture_rect;
+    rect.min *= scale;
+    rect.max *= scale;
+    (transform.compute_matrix(), rect, image_size * scale)
+}
+
+/// Slices the texture of a [`BorderImage`] into the regions drawn in the border of a node, and inside it if the
+/// center region is drawn.
+///
+/// # Arguments
+///
+/// * `border_image` - The border image
+/// * `texture_rect` - The section of the texture to slice
+/// * `size` - The size of the node
+/// * `border` - The thickness of the node's border, ordered left, top, right, bottom
+#[must_use]
+pub(crate) fn compute_border_image_slices(
+    border_image: &BorderImage,
+    texture_rect: Rect,
+    size: Vec2,
+    [left, top, right, bottom]: [f32; 4],
+) -> Vec<TextureSlice> {
+    let slice = border_image.slice;
+    // The lines that divide the texture and the node into 3 columns and 3 rows
+    let texture_xs = [
+        texture_rect.min.x,
+        texture_rect.min.x + slice.left,
+        texture_rect.max.x - slice.right,
+        texture_rect.max.x,
+    ];
+    let texture_ys = [
+        texture_rect.min.y,
+        texture_rect.min.y + slice.top,
+        texture_rect.max.y - slice.bottom,
+        texture_rect.max.y,
+    ];
+    let node_xs = [0., left, size.x - right, size.x];
+    let node_ys = [0., top, size.y - bottom, size.y];
+
+    let mut slices = Vec::with_capacity(9);
+    for row in 0..3 {
+        for column in 0..3 {
+            let scale_mode = match (column, row) {
+                (1, 1) => match border_image.fill {
+                    Some(scale_mode) => scale_mode,
+                    None => continue,
+                },
+                (1, _) | (_, 1) => border_image.sides_scale_mode,
+                _ => SliceScaleMode::Stretch,
+            };
+            let texture_rect = Rect {
+                min: Vec2::new(texture_xs[column], texture_ys[row]),
+                max: Vec2::new(texture_xs[column + 1], texture_ys[row + 1]),
+            };
+            let draw_rect = Rect {
+                min: Vec2::new(node_xs[column], node_ys[row]),
+                max: Vec2::new(node_xs[column + 1], node_ys[row + 1]),
+            };
+            // Regions without area, like the corners of a border that is only on one side, aren't drawn
+            if texture_rect.is_empty() || draw_rect.is_empty() {
+                continue;
+            }
+            let slice = TextureSlice {
+                texture_rect,
+                draw_size: draw_rect.size(),
+                // The offset is from the center of the node, with the y-axis pointing up
+                offset: (draw_rect.center() - 0.5 * size) * Vec2::new(1., -1.),
+            };
+            match scale_mode {
+                SliceScaleMode::Stretch => slices.push(slice),
+                // The sides are only tiled along their edges
+                SliceScaleMode::Tile { stretch_value } => {
+                    slices.extend(slice.tiled(stretch_value, (column == 1, row == 1)));
+                }
+            }
+        }
+    }
+    slices
+}
+
 /// Generates sprite slices for a `sprite` given a `scale_mode`. The slices
 /// will be computed according to the `image_handle` dimensions.
 ///
@@ -217,3 +312,55 @@ pub(crate) fn compute_slices_on_image_change(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Rect, Vec2};
+    use bevy_sprite::{BorderRect, SliceScaleMode};
+
+    use super::compute_border_image_slices;
+    use crate::BorderImage;
+
+    #[test]
+    fn border_image_slices_fill_the_border() {
+        let mut border_image = BorderImage {
+            slice: BorderRect::square(10.),
+            ..Default::default()
+        };
+        let texture_rect = Rect::new(0., 0., 30., 30.);
+        let size = Vec2::new(100., 50.);
+
+        let slices = compute_border_image_slices(&border_image, texture_rect, size, [5.; 4]);
+        assert_eq!(slices.len(), 8);
+        let top_left = &slices[0];
+        assert_eq!(top_left.texture_rect, Rect::new(0., 0., 10., 10.));
+        assert_eq!(top_left.draw_size, Vec2::splat(5.));
+        assert_eq!(top_left.offset, Vec2::new(-47.5, 22.5));
+        let top = &slices[1];
+        assert_eq!(top.texture_rect, Rect::new(10., 0., 20., 10.));
+        assert_eq!(top.draw_size, Vec2::new(90., 5.));
+        assert_eq!(top.offset, Vec2::new(0., 22.5));
+
+        // Only the corners and sides of the border are drawn
+        let slices =
+            compute_border_image_slices(&border_image, texture_rect, size, [0., 5., 0., 0.]);
+        assert_eq!(slices.len(), 1);
+        assert_eq!(slices[0].draw_size, Vec2::new(100., 5.));
+
+        border_image.fill = Some(SliceScaleMode::Stretch);
+        let slices = compute_border_image_slices(&border_image, texture_rect, size, [5.; 4]);
+        assert_eq!(slices.len(), 9);
+        assert_eq!(slices[4].draw_size, Vec2::new(90., 40.));
+
+        // Tiled sides repeat along their edges only
+        border_image.fill = None;
+        border_image.sides_scale_mode = SliceScaleMode::Tile { stretch_value: 1. };
+        let slices = compute_border_image_slices(&border_image, texture_rect, size, [5.; 4]);
+        let top_tiles: Vec<_> = slices
+            .iter()
+            .filter(|slice| slice.offset.y == 22.5 && slice.draw_size.y == 5.)
+            .collect();
+        // 2 corners and 9 tiles of the top side, 10 pixels wide
+        assert_eq!(top_tiles.len(), 11);
+    }
+}
@@ -0,0 +1,699 @@
+//! Multitouch interaction and gesture recognition for UI nodes.
+//!
+//! Each touch is hit tested separately, so several nodes can be pressed at once. The interactions of each touch
+//! are sent as [`UiInteractionEvent`]s, and the gestures made by the touches as [`UiGestureEvent`]s. Dragging a touch over a node with [`ScrollPosition`] and
+//! non-visible overflow scrolls it.
+//!
+//! Pinches are only sent as [`UiGestureEvent`]s, they don't scale or scroll any nodes. A pinch on a scrollable node
+//! stops its touches from scrolling it, so apps can zoom its content in response to the events instead.
+
+use bevy_ecs::prelude::*;
+use bevy_input::touch::Touches;
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::Camera;
+use bevy_time::Time;
+use bevy_utils::Duration;
+
+use crate::{
+    FocusPolicy, Interaction, ScrollPosition, Style, UiDragSettings, UiInteractionEvent,
+    UiInteractionKind, UiScale, UiStacks,
+};
+
+/// Controls touch gesture recognition for UI nodes.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct UiTouchSettings {
+    /// How long a touch has to be held without moving to be recognized as a long press.
+    pub long_press_duration: Duration,
+    /// Whether dragging a single touch over a scrollable node scrolls it.
+    pub scroll_with_touch: bool,
+}
+
+impl Default for UiTouchSettings {
+    fn default() -> Self {
+        Self {
+            long_press_duration: Duration::from_millis(500),
+            scroll_with_touch: true,
+        }
+    }
+}
+
+/// A touch gesture recognized by [`ui_touch_system`].
+#[derive(Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(PartialEq)]
+pub enum UiGesture {
+    /// The touch was released before it moved or was held long enough to be a long press.
+    Tap,
+    /// The touch was held in place for [`UiTouchSettings::long_press_duration`].
+    LongPress,
+    /// The touch moved further than [`UiDragSettings::threshold`], so it is a drag instead of a tap.
+    DragStart,
+    /// The touch moved while dragging.
+    Drag {
+        /// The distance moved since the last drag event, in logical pixels.
+        delta: Vec2,
+    },
+    /// The touch was released or canceled while dragging.
+    DragEnd,
+    /// Two touches on the same node moved towards or away from each other.
+    ///
+    /// Pinches aren't applied to the node, read them from [`UiGestureEvent`]s to zoom its content.
+    Pinch {
+        /// The ratio of the distance between the touches to their distance in the previous frame.
+        scale: f32,
+    },
+}
+
+/// A touch gesture on a UI node, sent by [`ui_touch_system`].
+#[derive(Event, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct UiGestureEvent {
+    /// The node the gesture was made on.
+    ///
+    /// This is the topmost node with an [`Interaction`] component under the touch when it started, respecting
+    /// [`FocusPolicy`], or otherwise the scrollable node under the touch.
+    pub entity: Entity,
+    /// The id of the touch. For pinches, this is the id of the first of the two touches.
+    pub touch_id: u64,
+    /// The recognized gesture.
+    pub gesture: UiGesture,
+    /// The position of the touch, in logical pixels relative to the top left of the camera's viewport.
+    ///
+    /// For pinches, this is the midpoint between the two touches.
+    pub position: Vec2,
+}
+
+/// A touch being tracked by [`ui_touch_system`].
+struct TrackedTouch {
+    id: u64,
+    camera: Entity,
+    /// The node pressed by the touch.
+    pressed: Option<Entity>,
+    /// The scrollable node under the touch.
+    scroll_container: Option<Entity>,
+    /// The [`Time::elapsed`] when the touch started.
+    start_time: Duration,
+    start_position: Vec2,
+    position: Vec2,
+    dragging: bool,
+    long_pressed: bool,
+}
+
+/// The touches tracked by [`ui_touch_system`].
+#[derive(Resource, Default)]
+pub struct TouchState {
+    touches: Vec<TrackedTouch>,
+}
+
+impl TouchState {
+    /// Returns `true` if any touch is pressing the node `entity`.
+    pub fn is_pressing(&self, entity: Entity) -> bool {
+        self.touches
+            .iter()
+            .any(|tracked| tracked.pressed == Some(entity))
+    }
+}
+
+impl TrackedTouch {
+    fn target(&self) -> Option<Entity> {
+        self.pressed.or(self.scroll_container)
+    }
+}
+
+/// Converts a logical window position to the logical UI coordinates of `camera`.
+pub(crate) fn ui_position(camera: &Camera, window_position: Vec2, ui_scale: f32) -> Vec2 {
+    let viewport_position = camera
+        .logical_viewport_rect()
+        .map(|rect| rect.min)
+        .unwrap_or_default();
+    (window_position - viewport_position) / ui_scale
+}
+
+/// Hit tests each touch separately, updating the [`Interaction`] of the nodes they press, scrolling nodes and
+/// sending [`UiInteractionEvent`]s and [`UiGestureEvent`]s.
+///
+/// A node pressed by several touches is released when the last of them is released.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_touch_system(
+    mut state: ResMut<TouchState>,
+    settings: Res<UiTouchSettings>,
+    time: Res<Time>,
+    drag_settings: Res<UiDragSettings>,
+    touches_input: Res<Touches>,
+    ui_scale: Res<UiScale>,
+    camera_query: Query<(Entity, &Camera)>,
+    ui_stacks: UiStacks,
+    mut node_query: Query<(
+        Option<&mut Interaction>,
+        Option<&FocusPolicy>,
+        Option<(&Style, &mut ScrollPosition)>,
+    )>,
+    mut interaction_events: EventWriter<UiInteractionEvent>,
+    mut gesture_events: EventWriter<UiGestureEvent>,
+) {
+    let tracked_touches = &mut state.touches;
+    let now = time.elapsed();
+    let mut events = Vec::new();
+    let mut touch_interactions = Vec::new();
+    let interaction = |entity, kind, position| UiInteractionEvent {
+        entity,
+        kind,
+        position: Some(position),
+        button: None,
+    };
+
+    // Cameras ordered from front to back
+    let mut cameras = camera_query
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .collect::<Vec<_>>();
+    cameras.sort_by_key(|(_, camera)| std::cmp::Reverse(camera.order));
+
+    for touch in touches_input.iter_just_pressed() {
+        // The touch hits the nodes of the frontmost camera with any nodes under it
+        let Some((camera, position, hits)) = cameras.iter().find_map(|(entity, camera)| {
+            let position = ui_position(camera, touch.position(), ui_scale.0);
+            let hits = ui_stacks.hit_test_camera(*entity, position);
+            (!hits.is_empty()).then_some((*entity, position, hits))
+        }) else {
+            continue;
+        };
+
+        // `Some(None)` once a node without an `Interaction` blocks the nodes below it from being pressed
+        let mut pressed: Option<Option<Entity>> = None;
+        let mut scroll_container = None;
+        for &entity in &hits {
+            let Ok((interaction, focus_policy, scroll)) = node_query.get_mut(entity) else {
+                continue;
+            };
+            if scroll_container.is_none()
+                && scroll.is_some_and(|(style, _)| !style.overflow.is_visible())
+            {
+                scroll_container = Some(entity);
+            }
+            if pressed.is_none() {
+                if let Some(mut interaction) = interaction {
+                    interaction.set_if_neq(Interaction::Pressed);
+                    pressed = Some(Some(entity));
+                } else if *focus_policy.unwrap_or(&FocusPolicy::Block) == FocusPolicy::Block {
+                    // Nodes below a blocking node can't be pressed, but can still be scrolled
+                    pressed = Some(None);
+                }
+            }
+        }
+
+        let pressed = pressed.flatten();
+        if let Some(pressed) = pressed {
+            touch_interactions.push(interaction(pressed, UiInteractionKind::Pressed, position));
+        }
+        tracked_touches.push(TrackedTouch {
+            id: touch.id(),
+            camera,
+            pressed,
+            scroll_container,
+            start_time: now,
+            start_position: position,
+            position,
+            dragging: false,
+            long_pressed: false,
+        });
+    }
+
+    // Touches on the same node, used to recognize pinches
+    let mut pinch = None;
+
+    for tracked in tracked_touches.iter_mut() {
+        let Some(touch) = touches_input.get_pressed(tracked.id) else {
+            continue;
+        };
+        let Some((_, camera)) = cameras.iter().find(|(entity, _)| *entity == tracked.camera) else {
+            continue;
+        };
+        let previous_position = tracked.position;
+        tracked.position = ui_position(camera, touch.position(), ui_scale.0);
+        let Some(target) = tracked.target() else {
+            continue;
+        };
+
+        if !tracked.dragging
+            && drag_settings.threshold <= tracked.start_position.distance(tracked.position)
+        {
+            tracked.dragging = true;
+            events.push(UiGestureEvent {
+                entity: target,
+                touch_id: tracked.id,
+                gesture: UiGesture::DragStart,
+                position: tracked.position,
+            });
+            if let Some(pressed) = tracked.pressed {
+                touch_interactions.push(interaction(
+                    pressed,
+                    UiInteractionKind::DragStart,
+                    tracked.position,
+                ));
+            }
+        }
+
+        if tracked.dragging && tracked.position != previous_position {
+            if let Some(pressed) = tracked.pressed {
+                touch_interactions.push(interaction(
+                    pressed,
+                    UiInteractionKind::Drag {
+                        delta: tracked.position - previous_position,
+                    },
+                    tracked.position,
+                ));
+            }
+            events.push(UiGestureEvent {
+                entity: target,
+                touch_id: tracked.id,
+                gesture: UiGesture::Drag {
+                    delta: tracked.position - previous_position,
+                },
+                position: tracked.position,
+            });
+        } else if !tracked.dragging
+            && !tracked.long_pressed
+            && settings.long_press_duration <= now.saturating_sub(tracked.start_time)
+        {
+            tracked.long_pressed = true;
+            events.push(UiGestureEvent {
+                entity: target,
+                touch_id: tracked.id,
+                gesture: UiGesture::LongPress,
+                position: tracked.position,
+            });
+        }
+
+        pinch = match pinch {
+            None => Some(Some((
+                target,
+                tracked.id,
+                previous_position,
+                tracked.position,
+                None,
+            ))),
+            Some(Some((entity, id, first_previous, first_position, None))) if entity == target => {
+                Some(Some((
+                    entity,
+                    id,
+                    first_previous,
+                    first_position,
+                    Some((previous_position, tracked.position)),
+                )))
+            }
+            // More than two touches, or touches on different nodes, aren't pinches
+            _ => Some(None),
+        };
+    }
+
+    let pinched = match pinch.flatten() {
+        Some((entity, touch_id, first_previous, first_position, Some((previous, position)))) => {
+            let previous_distance = first_previous.distance(previous);
+            let distance = first_position.distance(position);
+            if 0. < previous_distance && distance != previous_distance {
+                events.push(UiGestureEvent {
+                    entity,
+                    touch_id,
+                    gesture: UiGesture::Pinch {
+                        scale: distance / previous_distance,
+                    },
+                    position: first_position.midpoint(position),
+                });
+            }
+            Some(entity)
+        }
+        _ => None,
+    };
+
+    // Single touches dragged over scrollable nodes scroll them
+    if settings.scroll_with_touch {
+        for event in &events {
+            let UiGesture::Drag { delta } = event.gesture else {
+                continue;
+            };
+            let Some(scroll_container) = tracked_touches
+                .iter()
+                .find(|tracked| tracked.id == event.touch_id)
+                .and_then(|tracked| tracked.scroll_container)
+            else {
+                continue;
+            };
+            if pinched == Some(event.entity) {
+                continue;
+            }
+            if let Ok((_, _, Some((style, mut scroll_position)))) =
+                node_query.get_mut(scroll_container)
+            {
+                // Content follows the touch, so it scrolls in the opposite direction to the drag
+                if !style.overflow.x.is_visible() {
+                    scroll_position.offset_x -= delta.x;
+                }
+                if !style.overflow.y.is_visible() {
+                    scroll_position.offset_y -= delta.y;
+                }
+            }
+        }
+    }
+
+    // Released and canceled touches
+    let mut index = 0;
+    while index < tracked_touches.len() {
+        let tracked = &tracked_touches[index];
+        let released = touches_input.just_released(tracked.id);
+        if !released && touches_input.get_pressed(tracked.id).is_some() {
+            index += 1;
+            continue;
+        }
+
+        let tracked = tracked_touches.swap_remove(index);
+        if let Some(target) = tracked.target() {
+            if tracked.dragging {
+                events.push(UiGestureEvent {
+                    entity: target,
+                    touch_id: tracked.id,
+                    gesture: UiGesture::DragEnd,
+                    position: tracked.position,
+                });
+            } else if released && !tracked.long_pressed {
+                events.push(UiGestureEvent {
+                    entity: target,
+                    touch_id: tracked.id,
+                    gesture: UiGesture::Tap,
+                    position: tracked.position,
+                });
+            }
+        }
+
+        // Release the pressed node, unless another touch is still pressing it
+        if let Some(pressed) = tracked.pressed {
+            if tracked.dragging {
+                touch_interactions.push(interaction(
+                    pressed,
+                    UiInteractionKind::DragEnd,
+                    tracked.position,
+                ));
+            }
+            touch_interactions.push(interaction(
+                pressed,
+                UiInteractionKind::Released,
+                tracked.position,
+            ));
+            if released
+                && ui_stacks
+                    .hit_test_camera(tracked.camera, tracked.position)
+                    .contains(&pressed)
+            {
+                touch_interactions.push(interaction(
+                    pressed,
+                    UiInteractionKind::Clicked,
+                    tracked.position,
+                ));
+            }
+            if !tracked_touches
+                .iter()
+                .any(|other| other.pressed == Some(pressed))
+            {
+                if let Ok((Some(mut interaction), ..)) = node_query.get_mut(pressed) {
+                    if *interaction == Interaction::Pressed {
+                        *interaction = Interaction::None;
+                    }
+                }
+            }
+        }
+    }
+
+    interaction_events.send_batch(touch_interactions);
+    gesture_events.send_batch(events);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, prelude::*};
+    use bevy_input::{
+        mouse::MouseButton,
+        touch::{touch_screen_input_system, TouchInput, TouchPhase, Touches},
+        ButtonInput,
+    };
+    use bevy_math::Vec2;
+    use bevy_render::{camera::Camera, view::ViewVisibility};
+    use bevy_time::Time;
+    use bevy_transform::prelude::GlobalTransform;
+
+    use super::{ui_touch_system, TouchState, UiGesture, UiGestureEvent, UiTouchSettings};
+    use crate::{
+        ui_focus_system, FocusPolicy, Interaction, Node, Overflow, RootFontSize, ScrollPosition,
+        Style, UiDragSettings, UiInteractionEvent, UiInteractionKind, UiScale, UiStack,
+    };
+
+    fn touch_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<UiTouchSettings>();
+        world.init_resource::<Time>();
+        world.init_resource::<UiDragSettings>();
+        world.init_resource::<TouchState>();
+        world.init_resource::<UiScale>();
+        world.init_resource::<RootFontSize>();
+        world.init_resource::<Touches>();
+        world.init_resource::<Events<TouchInput>>();
+        world.init_resource::<Events<UiInteractionEvent>>();
+        world.init_resource::<Events<UiGestureEvent>>();
+        world.spawn(Camera::default());
+        world
+    }
+
+    fn touch(
+        world: &mut World,
+        schedule: &mut Schedule,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2,
+    ) {
+        world.send_event(TouchInput {
+            phase,
+            position,
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id,
+        });
+        schedule.run(world);
+    }
+
+    fn gestures(world: &mut World) -> Vec<UiGesture> {
+        world
+            .resource_mut::<Events<UiGestureEvent>>()
+            .drain()
+            .map(|event| event.gesture)
+            .collect()
+    }
+
+    #[test]
+    fn touch_gestures() {
+        let mut world = touch_world();
+
+        let scroll_container = world
+            .spawn((
+                Node {
+                    calculated_size: Vec2::splat(100.),
+                    ..Default::default()
+                },
+                GlobalTransform::from_xyz(50., 50., 0.),
+                Style {
+                    overflow: Overflow::clip_y(),
+                    ..Default::default()
+                },
+                ScrollPosition::default(),
+            ))
+            .id();
+        let button = world
+            .spawn((
+                Node {
+                    calculated_size: Vec2::splat(20.),
+                    ..Default::default()
+                },
+                GlobalTransform::from_xyz(10., 10., 0.),
+                Interaction::default(),
+                FocusPolicy::Block,
+            ))
+            .id();
+        world.insert_resource(UiStack {
+            uinodes: vec![scroll_container, button],
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((touch_screen_input_system, ui_touch_system).chain());
+
+        // Tapping the button presses it
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Started,
+            Vec2::splat(10.),
+        );
+        assert_eq!(
+            world.get::<Interaction>(button),
+            Some(&Interaction::Pressed)
+        );
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Ended,
+            Vec2::splat(10.),
+        );
+        assert_eq!(world.get::<Interaction>(button), Some(&Interaction::None));
+        assert_eq!(gestures(&mut world), [UiGesture::Tap]);
+
+        // Dragging over the scroll container scrolls it vertically
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Started,
+            Vec2::splat(50.),
+        );
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Moved,
+            Vec2::new(50., 30.),
+        );
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Ended,
+            Vec2::new(50., 30.),
+        );
+        assert_eq!(
+            gestures(&mut world),
+            [
+                UiGesture::DragStart,
+                UiGesture::Drag {
+                    delta: Vec2::new(0., -20.)
+                },
+                UiGesture::DragEnd
+            ]
+        );
+        assert_eq!(
+            world
+                .get::<ScrollPosition>(scroll_container)
+                .unwrap()
+                .offset_y,
+            20.
+        );
+
+        // Holding a touch in place is a long press, which isn't also a tap
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Started,
+            Vec2::splat(10.),
+        );
+        schedule.run(&mut world);
+        assert_eq!(gestures(&mut world), []);
+        let long_press_duration = world.resource::<UiTouchSettings>().long_press_duration;
+        world.resource_mut::<Time>().advance_by(long_press_duration);
+        schedule.run(&mut world);
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Ended,
+            Vec2::splat(10.),
+        );
+        assert_eq!(gestures(&mut world), [UiGesture::LongPress]);
+    }
+
+    #[test]
+    fn each_touch_presses_and_releases_its_own_node() {
+        let mut world = touch_world();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        let mut view_visibility = ViewVisibility::default();
+        view_visibility.set();
+        let [left, right] = [10., 40.].map(|x| {
+            world
+                .spawn((
+                    Node {
+                        calculated_size: Vec2::splat(20.),
+                        ..Default::default()
+                    },
+                    GlobalTransform::from_xyz(x, 10., 0.),
+                    Interaction::default(),
+                    view_visibility,
+                ))
+                .id()
+        });
+        world.insert_resource(UiStack {
+            uinodes: vec![left, right],
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((touch_screen_input_system, ui_focus_system, ui_touch_system).chain());
+        let interactions =
+            |world: &World| [left, right].map(|entity| *world.get::<Interaction>(entity).unwrap());
+
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Started,
+            Vec2::new(10., 10.),
+        );
+        touch(
+            &mut world,
+            &mut schedule,
+            1,
+            TouchPhase::Started,
+            Vec2::new(40., 10.),
+        );
+        assert_eq!(interactions(&world), [Interaction::Pressed; 2]);
+
+        // Lifting the first finger only releases the node it pressed
+        touch(
+            &mut world,
+            &mut schedule,
+            0,
+            TouchPhase::Ended,
+            Vec2::new(10., 10.),
+        );
+        assert_eq!(
+            interactions(&world),
+            [Interaction::None, Interaction::Pressed]
+        );
+        schedule.run(&mut world);
+        assert_eq!(
+            interactions(&world),
+            [Interaction::None, Interaction::Pressed]
+        );
+
+        touch(
+            &mut world,
+            &mut schedule,
+            1,
+            TouchPhase::Ended,
+            Vec2::new(40., 10.),
+        );
+        assert_eq!(interactions(&world), [Interaction::None; 2]);
+
+        let events = world
+            .resource_mut::<Events<UiInteractionEvent>>()
+            .drain()
+            .map(|event| (event.entity, event.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                (left, UiInteractionKind::Pressed),
+                (right, UiInteractionKind::Pressed),
+                (left, UiInteractionKind::Released),
+                (left, UiInteractionKind::Clicked),
+                (right, UiInteractionKind::Released),
+                (right, UiInteractionKind::Clicked),
+            ]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Animated transitions between values of UI style properties.
+//!
+//! When a property of a node with a [`UiTransition`] is changed, the node animates from its displayed value to
+//! the new value over the transition's duration, instead of jumping to it immediately. The transitions are
+//! evaluated before layout, so the animated values are laid out and drawn in the same frame.
+
+use bevy_color::{Color, Mix};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_utils::Duration;
+
+use crate::{BackgroundColor, BorderRadius, Style, UiRect, Val, ValExpr};
+
+/// An easing curve, controlling how a [`UiTransition`] progresses over its duration.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum EaseFunction {
+    /// Progresses at a constant rate.
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates.
+    QuadraticIn,
+    /// Starts quickly and decelerates.
+    QuadraticOut,
+    /// Starts slowly, accelerates, and then decelerates.
+    QuadraticInOut,
+    /// Starts slowly and accelerates, more sharply than [`EaseFunction::QuadraticIn`].
+    CubicIn,
+    /// Starts quickly and decelerates, more sharply than [`EaseFunction::QuadraticOut`].
+    CubicOut,
+    /// Starts slowly, accelerates, and then decelerates, more sharply than [`EaseFunction::QuadraticInOut`].
+    CubicInOut,
+}
+
+impl EaseFunction {
+    /// Maps the linear progress `t` of a transition, between `0.` and `1.`, to the eased progress.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            EaseFunction::Linear => t,
+            EaseFunction::QuadraticIn => t * t,
+            EaseFunction::QuadraticOut => 1. - (1. - t) * (1. - t),
+            EaseFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2. * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(2) / 2.
+                }
+            }
+            EaseFunction::CubicIn => t * t * t,
+            EaseFunction::CubicOut => 1. - (1. - t).powi(3),
+            EaseFunction::CubicInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+        }
+    }
+}
+
+/// Animates changes to the width, height, margin, border radius and background color of a UI node.
+///
+/// Changing one of these properties starts a transition from the currently displayed value to the new value,
+/// which [`ui_transition_system`] advances each frame by writing the intermediate values back to the node's
+/// components. Changing the property again during a transition restarts it from the displayed value.
+///
+/// [`Val`]s with different units are interpolated using [`Val::Calc`]. Changes to or from [`Val::Auto`] take
+/// effect immediately.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::{prelude::*, EaseFunction, UiTransition};
+/// # use bevy_utils::Duration;
+/// fn spawn_panel(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle::default(),
+///         UiTransition::new(Duration::from_millis(250), EaseFunction::QuadraticOut),
+///     ));
+/// }
+///
+/// // Setting the width animates the panel to its new size
+/// fn expand_panels(mut panels: Query<&mut Style, With<UiTransition>>) {
+///     for mut style in &mut panels {
+///         style.width = Val::Px(400.);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct UiTransition {
+    /// How long each transition takes.
+    pub duration: Duration,
+    /// The easing curve of each transition.
+    pub easing: EaseFunction,
+    #[reflect(ignore)]
+    state: TransitionState,
+}
+
+impl UiTransition {
+    /// Creates a [`UiTransition`] with the given duration and easing curve.
+    pub fn new(duration: Duration, easing: EaseFunction) -> Self {
+        Self {
+            duration,
+            easing,
+            state: TransitionState::default(),
+        }
+    }
+
+    /// Returns `true` if any of the node's properties are transitioning.
+    pub fn is_active(&self) -> bool {
+        let state = &self.state;
+        state.width.is_active()
+            || state.height.is_active()
+            || state.margin.is_active()
+            || state.border_radius.is_active()
+            || state.background_color.is_active()
+    }
+}
+
+/// The transitions of each animated property of a node.
+#[derive(Clone, Debug, Default)]
+struct TransitionState {
+    width: PropertyTransition<Val>,
+    height: PropertyTransition<Val>,
+    margin: PropertyTransition<UiRect>,
+    border_radius: PropertyTransition<BorderRadius>,
+    background_color: PropertyTransition<Color>,
+}
+
+/// A value that can be interpolated by a [`UiTransition`].
+trait Interpolate: Copy + PartialEq {
+    /// Interpolates from `self` to `target`, where `t` is between `0.` and `1.`.
+    fn interpolate(&self, target: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for Val {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        match (*self, *target) {
+            (Val::Px(a), Val::Px(b)) => Val::Px(lerp(a, b)),
+            (Val::Percent(a), Val::Percent(b)) => Val::Percent(lerp(a, b)),
+            (Val::Vw(a), Val::Vw(b)) => Val::Vw(lerp(a, b)),
+            (Val::Vh(a), Val::Vh(b)) => Val::Vh(lerp(a, b)),
+            (Val::VMin(a), Val::VMin(b)) => Val::VMin(lerp(a, b)),
+            (Val::VMax(a), Val::VMax(b)) => Val::VMax(lerp(a, b)),
+            (Val::Rem(a), Val::Rem(b)) => Val::Rem(lerp(a, b)),
+            // `Auto` has no numeric value to interpolate
+            (Val::Auto, _) | (_, Val::Auto) => *target,
+            // Values with different units are interpolated as an expression in both units, if both units can
+            // be used in expressions
+            (a, b) => match (ValExpr::try_from_val(a), ValExpr::try_from_val(b)) {
+                (Some(a), Some(b)) => Val::Calc(a * (1. - t) + b * t),
+                _ => b,
+            },
+        }
+    }
+}
+
+impl Interpolate for UiRect {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        UiRect {
+            left: self.left.interpolate(&target.left, t),
+            right: self.right.interpolate(&target.right, t),
+            top: self.top.interpolate(&target.top, t),
+            bottom: self.bottom.interpolate(&target.bottom, t),
+        }
+    }
+}
+
+impl Interpolate for BorderRadius {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        BorderRadius {
+            top_left: self.top_left.interpolate(&target.top_left, t),
+            top_right: self.top_right.interpolate(&target.top_right, t),
+            bottom_left: self.bottom_left.interpolate(&target.bottom_left, t),
+            bottom_right: self.bottom_right.interpolate(&target.bottom_right, t),
+            vertical: match (self.vertical, target.vertical) {
+                (None, None) => None,
+                // Circular corners have the same vertical radii as horizontal
+                (start, end) => {
+                    let start = start.unwrap_or(self.corners());
+                    let end = end.unwrap_or(target.corners());
+                    Some(std::array::from_fn(|i| start[i].interpolate(&end[i], t)))
+                }
+            },
+        }
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        self.mix(target, t)
+    }
+}
+
+/// The transition of a single property.
+#[derive(Clone, Debug)]
+struct PropertyTransition<T> {
+    /// The value displayed after the last update, used to tell changes made by the user apart from the
+    /// intermediate values written by the transition.
+    displayed: Option<T>,
+    start: Option<T>,
+    target: Option<T>,
+    elapsed: Duration,
+}
+
+impl<T> Default for PropertyTransition<T> {
+    fn default() -> Self {
+        Self {
+            displayed: None,
+            start: None,
+            target: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl<T: Interpolate> PropertyTransition<T> {
+    fn is_active(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Advances the transition by `delta`, returning the value to display if it differs from `value`.
+    fn update(
+        &mut self,
+        value: T,
+        delta: Duration,
+        duration: Duration,
+        easing: EaseFunction,
+    ) -> Option<T> {
+        let Some(displayed) = self.displayed else {
+            // The first value is displayed without a transition
+            self.displayed = Some(value);
+            return None;
+        };
+
+        if value != displayed {
+            // The value was changed since the last update, so start a new transition from the displayed value
+            self.start = Some(displayed);
+            self.target = Some(value);
+            self.elapsed = Duration::ZERO;
+        } else {
+            self.elapsed += delta;
+        }
+
+        let (Some(start), Some(target)) = (self.start, self.target) else {
+            return None;
+        };
+
+        let t = if duration.is_zero() {
+            1.
+        } else {
+            self.elapsed.as_secs_f32() / duration.as_secs_f32()
+        };
+        let next = if 1. <= t {
+            self.start = None;
+            self.target = None;
+            target
+        } else {
+            start.interpolate(&target, easing.ease(t))
+        };
+        self.displayed = Some(next);
+        (next != value).then_some(next)
+    }
+}
+
+/// Advances the [`UiTransition`]s of UI nodes, writing the animated values to their [`Style`],
+/// [`BorderRadius`] and [`BackgroundColor`].
+pub fn ui_transition_system(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut UiTransition,
+        &mut Style,
+        Option<&mut BorderRadius>,
+        Option<&mut BackgroundColor>,
+    )>,
+) {
+    let delta = time.delta();
+    for (mut transition, mut style, border_radius, background_color) in &mut query {
+        let UiTransition {
+            duration,
+            easing,
+            ref mut state,
+        } = *transition.bypass_change_detection();
+
+        // Only the properties that change are written, to avoid triggering change detection every frame.
+        if let Some(width) = state.width.update(style.width, delta, duration, easing) {
+            style.width = width;
+        }
+        if let Some(height) = state.height.update(style.height, delta, duration, easing) {
+            style.height = height;
+        }
+        if let Some(margin) = state.margin.update(style.margin, delta, duration, easing) {
+            style.margin = margin;
+        }
+        if let Some(mut border_radius) = border_radius {
+            if let Some(value) = state
+                .border_radius
+                .update(*border_radius, delta, duration, easing)
+            {
+                *border_radius = value;
+            }
+        }
+        if let Some(mut background_color) = background_color {
+            if let Some(value) =
+                state
+                    .background_color
+                    .update(background_color.0, delta, duration, easing)
+            {
+                background_color.0 = value;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::Color;
+    use bevy_ecs::prelude::*;
+    use bevy_time::Time;
+    use bevy_utils::Duration;
+
+    use super::{ui_transition_system, EaseFunction, UiTransition};
+    use crate::{BackgroundColor, Style, Val};
+
+    #[test]
+    fn changed_properties_transition_to_their_new_values() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn((
+                Style {
+                    width: Val::Px(100.),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::BLACK),
+                UiTransition::new(Duration::from_secs(1), EaseFunction::Linear),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_transition_system);
+        let mut advance = |world: &mut World, millis: u64| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            schedule.run(world);
+        };
+
+        // The initial values are displayed immediately
+        advance(&mut world, 0);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Px(100.));
+
+        world.get_mut::<Style>(entity).unwrap().width = Val::Px(200.);
+        world.get_mut::<BackgroundColor>(entity).unwrap().0 = Color::WHITE;
+        advance(&mut world, 0);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Px(100.));
+        assert!(world.get::<UiTransition>(entity).unwrap().is_active());
+
+        advance(&mut world, 500);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Px(150.));
+        assert_eq!(
+            world.get::<BackgroundColor>(entity).unwrap().0,
+            Color::linear_rgb(0.5, 0.5, 0.5)
+        );
+
+        // Retargeting starts a new transition from the displayed value
+        world.get_mut::<Style>(entity).unwrap().width = Val::Px(50.);
+        advance(&mut world, 0);
+        advance(&mut world, 500);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Px(100.));
+
+        advance(&mut world, 600);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Px(50.));
+        assert_eq!(
+            world.get::<BackgroundColor>(entity).unwrap().0,
+            Color::WHITE
+        );
+        assert!(!world.get::<UiTransition>(entity).unwrap().is_active());
+
+        // Values with different units are interpolated as expressions
+        world.get_mut::<Style>(entity).unwrap().width = Val::Percent(50.);
+        advance(&mut world, 0);
+        advance(&mut world, 500);
+        assert_eq!(
+            world.get::<Style>(entity).unwrap().width,
+            Val::Calc(Val::Px(25.) + Val::Percent(25.))
+        );
+        advance(&mut world, 500);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Percent(50.));
+
+        // Changes to `Auto` take effect immediately
+        world.get_mut::<Style>(entity).unwrap().width = Val::Auto;
+        advance(&mut world, 0);
+        assert_eq!(world.get::<Style>(entity).unwrap().width, Val::Auto);
+    }
+}
@@ -110,6 +110,11 @@ pub trait UiMaterial: AsBindGroup + Asset + Clone + Sized {
 
 pub struct UiMaterialKey<M: UiMaterial> {
     pub hdr: bool,
+    /// Whether the pipeline draws [`UiLayer::MainPass`](crate::UiLayer::MainPass) nodes, depth tested against
+    /// the main pass.
+    pub depth_test: bool,
+    /// The number of samples of the render target, which is multisampled in the main pass.
+    pub samples: u32,
     pub bind_group_data: M::Data,
 }
 
@@ -120,7 +125,10 @@ where
     M::Data: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.hdr == other.hdr && self.bind_group_data == other.bind_group_data
+        self.hdr == other.hdr
+            && self.depth_test == other.depth_test
+            && self.samples == other.samples
+            && self.bind_group_data == other.bind_group_data
     }
 }
 
@@ -131,6 +139,8 @@ where
     fn clone(&self) -> Self {
         Self {
             hdr: self.hdr,
+            depth_test: self.depth_test,
+            samples: self.samples,
             bind_group_data: self.bind_group_data.clone(),
         }
     }
@@ -142,6 +152,8 @@ where
 {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.hdr.hash(state);
+        self.depth_test.hash(state);
+        self.samples.hash(state);
         self.bind_group_data.hash(state);
     }
 }
@@ -1,15 +1,27 @@
-use crate::{UiRect, Val};
+use crate::{BorderRect, UiRect, Val, ValExpr};
+#[cfg(feature = "render")]
 use bevy_asset::Handle;
-use bevy_color::Color;
-use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_color::{Alpha, Color};
+#[cfg(feature = "render")]
+use bevy_ecs::system::SystemParam;
+use bevy_ecs::{
+    entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::ReflectMapEntities,
+};
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::prelude::*;
+#[cfg(feature = "render")]
 use bevy_render::{
     camera::{Camera, RenderTarget},
     texture::Image,
 };
+#[cfg(feature = "render")]
+use bevy_sprite::SliceScaleMode;
 use bevy_transform::prelude::GlobalTransform;
+#[cfg(feature = "render")]
 use bevy_utils::warn_once;
+#[cfg(feature = "render")]
 use bevy_window::{PrimaryWindow, WindowRef};
 use smallvec::SmallVec;
 use std::num::{NonZeroI16, NonZeroU16};
@@ -44,6 +56,22 @@ pub struct Node {
     ///
     /// Automatically calculated by [`super::layout::ui_layout_system`].
     pub(crate) unrounded_size: Vec2,
+    /// The thickness of the node's border in logical pixels.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) border: BorderRect,
+    /// The size of the node's padding in logical pixels.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) padding: BorderRect,
+    /// The size of the node's content in logical pixels, including any content overflowing the node.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) content_size: Vec2,
+    /// The [`UiRootScale`] of the node's root.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub(crate) root_scale: f32,
 }
 
 impl Node {
@@ -67,6 +95,29 @@ impl Node {
         self.unrounded_size
     }
 
+    /// The thickness of the node's border in logical pixels.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn border(&self) -> BorderRect {
+        self.border
+    }
+
+    /// The size of the node's padding in logical pixels.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn padding(&self) -> BorderRect {
+        self.padding
+    }
+
+    /// The size of the node's content in logical pixels, including any content overflowing the node.
+    ///
+    /// This is larger than the node's [`size`](Node::size) along the axes its content can be scrolled.
+    ///
+    /// Automatically calculated by [`super::layout::ui_layout_system`].
+    pub const fn content_size(&self) -> Vec2 {
+        self.content_size
+    }
+
     /// Returns the size of the node in physical pixels based on the given scale factor and `UiScale`.
     #[inline]
     pub fn physical_size(&self, scale_factor: f32, ui_scale: f32) -> Vec2 {
@@ -82,6 +133,28 @@ impl Node {
         Rect::from_center_size(transform.translation().truncate(), self.size())
     }
 
+    /// Returns the logical pixel coordinates of the given box of the UI node, based on its [`GlobalTransform`].
+    pub fn logical_box_rect(&self, transform: &GlobalTransform, clip_box: OverflowClipBox) -> Rect {
+        let mut rect = self.logical_rect(transform);
+        let mut inset = |edges: BorderRect| {
+            rect.min += Vec2::new(edges.left, edges.top);
+            rect.max -= Vec2::new(edges.right, edges.bottom);
+        };
+        match clip_box {
+            OverflowClipBox::BorderBox => {}
+            OverflowClipBox::PaddingBox => inset(self.border),
+            OverflowClipBox::ContentBox => {
+                inset(self.border);
+                inset(self.padding);
+            }
+        }
+        // Keep the rect centered if the border and padding are larger than the node
+        let center = rect.center();
+        rect.min = rect.min.min(center);
+        rect.max = rect.max.max(center);
+        rect
+    }
+
     /// Returns the physical pixel coordinates of the UI node, based on its [`GlobalTransform`] and the scale factor.
     #[inline]
     pub fn physical_rect(
@@ -109,6 +182,13 @@ impl Node {
     pub fn outline_width(&self) -> f32 {
         self.outline_width
     }
+
+    #[inline]
+    /// Returns the [`UiRootScale`] of the node's root, which its pixel lengths are multiplied by on top of
+    /// [`UiScale`](crate::UiScale).
+    pub fn root_scale(&self) -> f32 {
+        self.root_scale
+    }
 }
 
 impl Node {
@@ -118,6 +198,10 @@ impl Node {
         outline_width: 0.,
         outline_offset: 0.,
         unrounded_size: Vec2::ZERO,
+        border: BorderRect::square(0.),
+        padding: BorderRect::square(0.),
+        content_size: Vec2::ZERO,
+        root_scale: 1.,
     };
 }
 
@@ -425,6 +509,33 @@ pub struct Style {
     ///
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-column>
     pub grid_column: GridPlacement,
+
+    /// Names areas of the grid that children can be placed into with `grid_area`.
+    ///
+    /// Each string is a row of whitespace-separated area names, one name per column. A `.` marks an unnamed cell.
+    /// Each named area must form a filled rectangle.
+    ///
+    /// ```
+    /// # use bevy_ui::Style;
+    /// let style = Style {
+    ///     grid_template_areas: vec![
+    ///         "header header".into(),
+    ///         "sidebar main".into(),
+    ///         ". footer".into(),
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// ```
+    ///
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-areas>
+    pub grid_template_areas: Vec<String>,
+
+    /// Places a grid item into the area with this name in its parent's `grid_template_areas`.
+    ///
+    /// If the area is found this overrides `grid_row` and `grid_column`, otherwise they are used instead.
+    ///
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-area>
+    pub grid_area: Option<String>,
 }
 
 impl Style {
@@ -467,6 +578,8 @@ impl Style {
         grid_auto_columns: Vec::new(),
         grid_column: GridPlacement::DEFAULT,
         grid_row: GridPlacement::DEFAULT,
+        grid_template_areas: Vec::new(),
+        grid_area: None,
     };
 }
 
@@ -835,12 +948,15 @@ pub struct Overflow {
     pub x: OverflowAxis,
     /// Whether to show or clip overflowing items on the y axis
     pub y: OverflowAxis,
+    /// The edge of the node that overflowing items are clipped at, on the axes that don't show overflowing items
+    pub clip_box: OverflowClipBox,
 }
 
 impl Overflow {
     pub const DEFAULT: Self = Self {
         x: OverflowAxis::DEFAULT,
         y: OverflowAxis::DEFAULT,
+        clip_box: OverflowClipBox::DEFAULT,
     };
 
     /// Show overflowing items on both axes
@@ -848,6 +964,7 @@ impl Overflow {
         Self {
             x: OverflowAxis::Visible,
             y: OverflowAxis::Visible,
+            clip_box: OverflowClipBox::DEFAULT,
         }
     }
 
@@ -856,6 +973,7 @@ impl Overflow {
         Self {
             x: OverflowAxis::Clip,
             y: OverflowAxis::Clip,
+            clip_box: OverflowClipBox::DEFAULT,
         }
     }
 
@@ -864,6 +982,7 @@ impl Overflow {
         Self {
             x: OverflowAxis::Clip,
             y: OverflowAxis::Visible,
+            clip_box: OverflowClipBox::DEFAULT,
         }
     }
 
@@ -872,9 +991,23 @@ impl Overflow {
         Self {
             x: OverflowAxis::Visible,
             y: OverflowAxis::Clip,
+            clip_box: OverflowClipBox::DEFAULT,
         }
     }
 
+    /// Clip overflowing items on both axes at the inner edge of the node's border, so that the node's own
+    /// border, outline and shadow are never covered by its children
+    pub const fn clip_content_only() -> Self {
+        Self::clip().with_clip_box(OverflowClipBox::PaddingBox)
+    }
+
+    /// Set the edge of the node that overflowing items are clipped at
+    #[must_use]
+    pub const fn with_clip_box(mut self, clip_box: OverflowClipBox) -> Self {
+        self.clip_box = clip_box;
+        self
+    }
+
     /// Overflow is visible on both axes
     pub const fn is_visible(&self) -> bool {
         self.x.is_visible() && self.y.is_visible()
@@ -887,6 +1020,37 @@ impl Default for Overflow {
     }
 }
 
+/// The edge of a node that its overflowing children are clipped at.
+///
+/// The node's own border, outline and shadow are not clipped by its own [`Overflow`], only by its ancestors'.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/overflow-clip-margin>
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect)]
+#[reflect(Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum OverflowClipBox {
+    /// Clip at the outer edge of the node's border, so children can draw over the border.
+    BorderBox,
+    /// Clip at the inner edge of the node's border.
+    PaddingBox,
+    /// Clip at the inner edge of the node's padding.
+    ContentBox,
+}
+
+impl OverflowClipBox {
+    pub const DEFAULT: Self = Self::BorderBox;
+}
+
+impl Default for OverflowClipBox {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// Whether to show or hide overflowing items
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect)]
 #[reflect(Default, PartialEq)]
@@ -1709,6 +1873,21 @@ impl<T: Into<Color>> From<T> for BackgroundColor {
 }
 
 /// The border color of the UI node.
+///
+/// Each edge of the border can have its own color, like the `border-top-color`, `border-right-color`, etc.
+/// properties in CSS. Adjacent edges of different colors meet along the lines joining the outer and inner corners
+/// of the border.
+///
+/// ```
+/// # use bevy_color::Color;
+/// # use bevy_ui::BorderColor;
+/// // A beveled look, lit from the top left.
+/// let bevel = BorderColor {
+///     top: Color::WHITE,
+///     left: Color::WHITE,
+///     ..BorderColor::all(Color::BLACK)
+/// };
+/// ```
 #[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
 #[reflect(Component, Default)]
 #[cfg_attr(
@@ -1716,16 +1895,54 @@ impl<T: Into<Color>> From<T> for BackgroundColor {
     derive(serde::Serialize, serde::Deserialize),
     reflect(Serialize, Deserialize)
 )]
-pub struct BorderColor(pub Color);
+pub struct BorderColor {
+    pub top: Color,
+    pub right: Color,
+    pub bottom: Color,
+    pub left: Color,
+}
 
 impl<T: Into<Color>> From<T> for BorderColor {
     fn from(color: T) -> Self {
-        Self(color.into())
+        Self::all(color.into())
     }
 }
 
 impl BorderColor {
-    pub const DEFAULT: Self = BorderColor(Color::WHITE);
+    pub const DEFAULT: Self = BorderColor::all(Color::WHITE);
+
+    /// Creates a [`BorderColor`] with the same `color` on every edge.
+    pub const fn all(color: Color) -> Self {
+        Self {
+            top: color,
+            right: color,
+            bottom: color,
+            left: color,
+        }
+    }
+
+    /// Sets the color of every edge to `color`.
+    pub fn set_all(&mut self, color: impl Into<Color>) -> &mut Self {
+        *self = Self::all(color.into());
+        self
+    }
+
+    /// Returns true if every edge has the same color.
+    pub fn is_uniform(&self) -> bool {
+        self.top == self.right && self.top == self.bottom && self.top == self.left
+    }
+
+    /// Returns true if every edge is fully transparent.
+    pub fn is_fully_transparent(&self) -> bool {
+        self.edges()
+            .iter()
+            .all(|color| color.is_fully_transparent())
+    }
+
+    /// The colors of the edges, ordered left, top, right, bottom like the border widths of the UI renderer.
+    pub(crate) fn edges(&self) -> [Color; 4] {
+        [self.left, self.top, self.right, self.bottom]
+    }
 }
 
 impl Default for BorderColor {
@@ -1734,15 +1951,15 @@ impl Default for BorderColor {
     }
 }
 
-#[derive(Component, Copy, Clone, Default, Debug, Reflect)]
-#[reflect(Component, Default)]
+#[derive(Component, Copy, Clone, Default, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
     reflect(Serialize, Deserialize)
 )]
 /// The [`Outline`] component adds an outline outside the edge of a UI node.
-/// Outlines do not take up space in the layout.
+/// Outlines do not take up space in the layout, unless their [`OutlineMode`] is [`OutlineMode::Push`].
 ///
 /// To add an [`Outline`] to a ui node you can spawn a `(NodeBundle, Outline)` tuple bundle:
 /// ```
@@ -1805,6 +2022,8 @@ pub struct Outline {
     /// If you are frequently toggling outlines for a UI node on and off it is recommended to set [`Color::NONE`] to hide the outline.
     /// This avoids the table moves that would occur from the repeated insertion and removal of the `Outline` component.
     pub color: Color,
+    /// Whether the outline takes up space in the layout.
+    pub mode: OutlineMode,
 }
 
 impl Outline {
@@ -1814,13 +2033,61 @@ impl Outline {
             width,
             offset,
             color,
+            mode: OutlineMode::Overlay,
         }
     }
+
+    /// Returns the outline with the given [`OutlineMode`].
+    #[must_use]
+    pub const fn with_mode(mut self, mode: OutlineMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the space added to each of the node's margins by an outline with [`OutlineMode::Push`], or `None`
+    /// if the outline doesn't take up space in the layout.
+    ///
+    /// `viewport_size` and `root_font_size` are used to convert widths and offsets in units that can't be used
+    /// in a [`ValExpr`].
+    #[cfg_attr(not(feature = "render"), allow(dead_code))]
+    pub(crate) fn push_margin(&self, viewport_size: Vec2, root_font_size: f32) -> Option<ValExpr> {
+        if self.mode != OutlineMode::Push {
+            return None;
+        }
+        // The node's width isn't known until it is laid out, so percentages of it are ignored
+        let push = ValExpr {
+            percent: 0.,
+            ..ValExpr::from_val_in_viewport(self.width, viewport_size, root_font_size)
+                + ValExpr::from_val_in_viewport(self.offset, viewport_size, root_font_size)
+        };
+        (push != ValExpr::ZERO).then_some(push)
+    }
+}
+
+/// Whether an [`Outline`] takes up space in the layout.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum OutlineMode {
+    /// The outline is drawn over whatever surrounds the node, without taking up space in the layout.
+    #[default]
+    Overlay,
+    /// The outline's width and offset are added to each of the node's margins, so the outline doesn't overlap
+    /// neighboring nodes. Useful for focus rings.
+    ///
+    /// [`Val::Auto`] margins aren't changed. Percentage widths and offsets don't take up space, as they are
+    /// resolved against the width of the node, which isn't known until it is laid out.
+    Push,
 }
 
 /// The 2D texture displayed for this UI node
 #[derive(Component, Clone, Debug, Reflect, Default)]
 #[reflect(Component, Default)]
+#[cfg(feature = "render")]
 pub struct UiImage {
     /// The tint color used to draw the image
     pub color: Color,
@@ -1832,6 +2099,7 @@ pub struct UiImage {
     pub flip_y: bool,
 }
 
+#[cfg(feature = "render")]
 impl UiImage {
     pub fn new(texture: Handle<Image>) -> Self {
         Self {
@@ -1862,18 +2130,318 @@ impl UiImage {
     }
 }
 
+#[cfg(feature = "render")]
 impl From<Handle<Image>> for UiImage {
     fn from(texture: Handle<Image>) -> Self {
         Self::new(texture)
     }
 }
 
-/// The calculated clip of the node
-#[derive(Component, Default, Copy, Clone, Debug, Reflect)]
+/// A texture drawn in the border of a node, like the CSS `border-image` property.
+///
+/// The texture is sliced into 9 regions by [`BorderImage::slice`]. The corner regions are stretched to fill the
+/// corners of the node's border, whose thickness is set by [`Style::border`], and the side regions are stretched
+/// or tiled along its edges. The center region is only drawn if [`BorderImage::fill`] is set.
+///
+/// The border image is drawn separately from the node's [`UiImage`] and [`BorderColor`], and ignores its
+/// [`BorderRadius`].
+#[derive(Component, Clone, Debug, Reflect)]
 #[reflect(Component, Default)]
+#[cfg(feature = "render")]
+pub struct BorderImage {
+    /// The tint color used to draw the border image
+    pub color: Color,
+    /// Handle to the texture
+    pub texture: Handle<Image>,
+    /// The distance in pixels from each edge of the texture to the line that slices off its side region.
+    pub slice: BorderRect,
+    /// How the top, right, bottom and left regions of the texture are scaled along the edges of the border.
+    pub sides_scale_mode: SliceScaleMode,
+    /// How the center region of the texture is scaled to fill the node inside its border, or `None` if it isn't
+    /// drawn.
+    pub fill: Option<SliceScaleMode>,
+}
+
+#[cfg(feature = "render")]
+impl Default for BorderImage {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            texture: Handle::default(),
+            slice: BorderRect::square(0.),
+            sides_scale_mode: SliceScaleMode::Stretch,
+            fill: None,
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+impl BorderImage {
+    /// Creates a border image from `texture`, sliced `slice` pixels from each edge.
+    pub fn new(texture: Handle<Image>, slice: BorderRect) -> Self {
+        Self {
+            texture,
+            slice,
+            ..Default::default()
+        }
+    }
+
+    /// Set the color tint
+    #[must_use]
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set how the side regions are scaled along the edges of the border
+    #[must_use]
+    pub const fn with_sides_scale_mode(mut self, scale_mode: SliceScaleMode) -> Self {
+        self.sides_scale_mode = scale_mode;
+        self
+    }
+
+    /// Fill the node inside its border with the center region, scaled by `scale_mode`
+    #[must_use]
+    pub const fn with_fill(mut self, scale_mode: SliceScaleMode) -> Self {
+        self.fill = Some(scale_mode);
+        self
+    }
+}
+
+/// Tiles the texture of a [`UiImage`] across its node, instead of stretching the texture to fill the node.
+///
+/// Tiles are laid out from the top left corner of the node and cut off at its right and bottom edges.
+/// Along an axis that doesn't repeat, the texture is stretched to fill the node.
+///
+/// Has no effect on nodes with [`ImageScaleMode`](bevy_sprite::ImageScaleMode) slicing.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ImageRepeat {
+    /// Whether the texture repeats along the x-axis
+    pub repeat_x: bool,
+    /// Whether the texture repeats along the y-axis
+    pub repeat_y: bool,
+    /// The width of each tile.
+    ///
+    /// [`Val::Auto`] uses the width of the texture, and percentages are of the width of the node.
+    pub tile_width: Val,
+    /// The height of each tile.
+    ///
+    /// [`Val::Auto`] uses the height of the texture, and percentages are of the height of the node.
+    pub tile_height: Val,
+}
+
+impl ImageRepeat {
+    /// Repeat the texture along both axes, with tiles the size of the texture.
+    pub const DEFAULT: Self = Self {
+        repeat_x: true,
+        repeat_y: true,
+        tile_width: Val::Auto,
+        tile_height: Val::Auto,
+    };
+
+    /// Repeat the texture along both axes.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Repeat the texture along the x-axis only, stretching it to fill the height of the node.
+    pub const fn x() -> Self {
+        Self {
+            repeat_y: false,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Repeat the texture along the y-axis only, stretching it to fill the width of the node.
+    pub const fn y() -> Self {
+        Self {
+            repeat_x: false,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Set the size of each tile
+    #[must_use]
+    pub const fn with_tile_size(mut self, tile_width: Val, tile_height: Val) -> Self {
+        self.tile_width = tile_width;
+        self.tile_height = tile_height;
+        self
+    }
+
+    /// Returns the number of times the texture is repeated along each axis of a node of `node_size`.
+    ///
+    /// `texture_size` is the size of the texture in logical pixels. Along an axis that doesn't repeat, or
+    /// where the tile size resolves to zero, the count is `1.`.
+    pub fn tile_count(
+        &self,
+        node_size: Vec2,
+        texture_size: Vec2,
+        viewport_size: Vec2,
+        root_font_size: f32,
+    ) -> Vec2 {
+        let count = |repeat: bool, tile: Val, node: f32, texture: f32| {
+            if !repeat {
+                return 1.;
+            }
+            let tile = tile
+                .resolve(node, viewport_size, root_font_size)
+                .unwrap_or(texture);
+            if 0. < tile {
+                node / tile
+            } else {
+                1.
+            }
+        };
+        Vec2::new(
+            count(self.repeat_x, self.tile_width, node_size.x, texture_size.x),
+            count(self.repeat_y, self.tile_height, node_size.y, texture_size.y),
+        )
+    }
+}
+
+impl Default for ImageRepeat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Controls how the texture of a [`UiImage`] is resized to fit its node, like the CSS `object-fit` property.
+///
+/// The node's size is still determined by the layout, the texture is scaled within it and centered. Parts of the
+/// texture that overflow the node are cropped.
+///
+/// Has no effect on nodes with an [`ImageRepeat`] or [`ImageScaleMode`](bevy_sprite::ImageScaleMode) slicing.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/object-fit>
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum ObjectFit {
+    /// The texture is stretched to fill the node, ignoring its aspect ratio.
+    #[default]
+    Fill,
+    /// The texture is scaled to fit within the node, preserving its aspect ratio.
+    Contain,
+    /// The texture is scaled to cover the node, preserving its aspect ratio. The texture is cropped along the
+    /// axis where it overflows the node.
+    Cover,
+    /// Like [`ObjectFit::Contain`], except the texture is never scaled up beyond its own size.
+    ScaleDown,
+    /// The texture isn't resized and is drawn at its own size, cropped if it is larger than the node.
+    None,
+}
+
+impl ObjectFit {
+    /// Returns the size of a texture of `texture_size` after it is fitted to a node of `node_size`, before cropping.
+    ///
+    /// Both sizes are in logical pixels.
+    pub fn fit(self, node_size: Vec2, texture_size: Vec2) -> Vec2 {
+        if texture_size.cmple(Vec2::ZERO).any() {
+            return node_size;
+        }
+        let scale = node_size / texture_size;
+        match self {
+            ObjectFit::Fill => node_size,
+            ObjectFit::Contain => texture_size * scale.min_element(),
+            ObjectFit::Cover => texture_size * scale.max_element(),
+            ObjectFit::ScaleDown => texture_size * scale.min_element().min(1.),
+            ObjectFit::None => texture_size,
+        }
+    }
+}
+
+/// The scroll offset of a node's content.
+///
+/// Children of a node with a [`ScrollPosition`] are moved up and to the left by the offset, along
+/// each axis where the node's [`Style::overflow`] is not [`OverflowAxis::Visible`].
+/// The offset is in logical pixels and is clamped by the layout system so that the content can't be
+/// scrolled past its end.
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ScrollPosition {
+    /// How far the content is scrolled to the right, in logical pixels.
+    pub offset_x: f32,
+    /// How far the content is scrolled down, in logical pixels.
+    pub offset_y: f32,
+}
+
+impl ScrollPosition {
+    pub const DEFAULT: Self = Self {
+        offset_x: 0.,
+        offset_y: 0.,
+    };
+
+    /// Creates a new [`ScrollPosition`] with the given offsets.
+    pub const fn new(offset_x: f32, offset_y: f32) -> Self {
+        Self { offset_x, offset_y }
+    }
+
+    /// Returns the offsets as a [`Vec2`].
+    pub const fn offset(&self) -> Vec2 {
+        Vec2::new(self.offset_x, self.offset_y)
+    }
+}
+
+impl From<Vec2> for ScrollPosition {
+    fn from(offset: Vec2) -> Self {
+        Self::new(offset.x, offset.y)
+    }
+}
+
+/// The calculated clip of the node
+#[derive(Component, Default, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
 pub struct CalculatedClip {
     /// The rect of the clip
     pub clip: Rect,
+    /// The radii of the rounded corners of the clip, with the horizontal and vertical radius of each corner.
+    /// Ordering: top left, top right, bottom right, bottom left.
+    pub radii: [Vec2; 4],
+}
+
+impl CalculatedClip {
+    /// Creates a clip with square corners.
+    pub const fn from_rect(clip: Rect) -> Self {
+        Self {
+            clip,
+            radii: [Vec2::ZERO; 4],
+        }
+    }
+
+    /// Returns the intersection of the two clips.
+    ///
+    /// The intersection of two rounded rects isn't a rounded rect, so it is approximated: the corners of the
+    /// intersection that are corners of either clip keep that clip's radius, the larger one if they are corners of
+    /// both, and the other corners are square.
+    pub fn intersect(self, other: Self) -> Self {
+        let corners = |rect: Rect| {
+            [
+                rect.min,
+                Vec2::new(rect.max.x, rect.min.y),
+                rect.max,
+                Vec2::new(rect.min.x, rect.max.y),
+            ]
+        };
+        let clip = self.clip.intersect(other.clip);
+        let clip_corners = corners(clip);
+        let radii = std::array::from_fn(|i| {
+            [self, other]
+                .iter()
+                .filter(|source| corners(source.clip)[i] == clip_corners[i])
+                .fold(Vec2::ZERO, |radius, source| radius.max(source.radii[i]))
+        });
+        Self { clip, radii }
+    }
+
+    /// Returns `true` if `point` is inside the clip, excluding its rounded corners.
+    pub fn contains(&self, point: Vec2) -> bool {
+        crate::rounded_rect_contains(self.clip, self.radii, point)
+    }
 }
 
 /// Indicates that this [`Node`] entity's front-to-back ordering is not controlled solely
@@ -1899,6 +2467,20 @@ pub enum ZIndex {
     Global(i32),
 }
 
+impl ZIndex {
+    /// The global z-indices reserved for overlays drawn over all other UI nodes, like tooltips and drag ghosts.
+    pub const OVERLAY_RANGE: std::ops::RangeInclusive<i32> = i32::MAX - 1023..=i32::MAX;
+
+    /// The z-index of tooltips, the bottom of [`ZIndex::OVERLAY_RANGE`].
+    pub const TOOLTIP: Self = Self::Global(*Self::OVERLAY_RANGE.start());
+
+    /// The z-index of [`DragGhost`](crate::DragGhost)s, just below [`ZIndex::CURSOR`].
+    pub const DRAG_GHOST: Self = Self::Global(*Self::OVERLAY_RANGE.end() - 1);
+
+    /// The z-index of the [`GamepadCursor`](crate::GamepadCursor), the top of [`ZIndex::OVERLAY_RANGE`].
+    pub const CURSOR: Self = Self::Global(*Self::OVERLAY_RANGE.end());
+}
+
 impl Default for ZIndex {
     fn default() -> Self {
         Self::Local(0)
@@ -1910,8 +2492,15 @@ impl Default for ZIndex {
 /// the length of the smallest dimension between the node's height or width, the radius will
 /// calculated as half the smallest dimension.
 ///
-/// Elliptical nodes are not supported yet. Percentage values are based on the node's smallest
-/// dimension, either width or height.
+/// Percentage values resolve against the node's width for the horizontal radius of a corner and its height for
+/// the vertical radius, so percentage radii on a node that isn't square make elliptical corners, as in CSS.
+/// These horizontal and vertical radii are clamped to half the node's width and height respectively.
+///
+/// Other corners are circular unless [`BorderRadius::vertical`] radii are set, like the values after the
+/// slash in the CSS `border-radius` property. Elliptical corners have the radii of the corner fields
+/// along the horizontal axis and the vertical radii along the vertical axis. Their horizontal radii are
+/// clamped to half the node's width and resolve percentages against its width, and their vertical radii
+/// are clamped to half the node's height and resolve percentages against its height.
 ///
 /// # Example
 /// ```
@@ -1946,7 +2535,7 @@ impl Default for ZIndex {
 ///
 /// <https://developer.mozilla.org/en-US/docs/Web/CSS/border-radius>
 #[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
-#[reflect(PartialEq, Default)]
+#[reflect(Component, PartialEq, Default)]
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
@@ -1957,6 +2546,10 @@ pub struct BorderRadius {
     pub top_right: Val,
     pub bottom_left: Val,
     pub bottom_right: Val,
+    /// The vertical radii of the corners, ordered top left, top right, bottom right, bottom left.
+    ///
+    /// If `None` the corners are circular.
+    pub vertical: Option<[Val; 4]>,
 }
 
 impl Default for BorderRadius {
@@ -1982,9 +2575,16 @@ impl BorderRadius {
             top_right: radius,
             bottom_left: radius,
             bottom_right: radius,
+            vertical: None,
         }
     }
 
+    #[inline]
+    /// Set all four corners to the same elliptical curvature, with the given horizontal and vertical radii.
+    pub const fn elliptical(horizontal: Val, vertical: Val) -> Self {
+        Self::all(horizontal).with_vertical(vertical, vertical, vertical, vertical)
+    }
+
     #[inline]
     pub const fn new(top_left: Val, top_right: Val, bottom_right: Val, bottom_left: Val) -> Self {
         Self {
@@ -1992,6 +2592,7 @@ impl BorderRadius {
             top_right,
             bottom_right,
             bottom_left,
+            vertical: None,
         }
     }
 
@@ -2003,6 +2604,7 @@ impl BorderRadius {
             top_right: Val::Px(top_right),
             bottom_right: Val::Px(bottom_right),
             bottom_left: Val::Px(bottom_left),
+            vertical: None,
         }
     }
 
@@ -2015,10 +2617,11 @@ impl BorderRadius {
         bottom_left: f32,
     ) -> Self {
         Self {
-            top_left: Val::Px(top_left),
-            top_right: Val::Px(top_right),
-            bottom_right: Val::Px(bottom_right),
-            bottom_left: Val::Px(bottom_left),
+            top_left: Val::Percent(top_left),
+            top_right: Val::Percent(top_right),
+            bottom_right: Val::Percent(bottom_right),
+            bottom_left: Val::Percent(bottom_left),
+            vertical: None,
         }
     }
 
@@ -2165,11 +2768,80 @@ impl BorderRadius {
         self.bottom_right = radius;
         self
     }
+
+    /// Returns the radii of the corner fields, ordered top left, top right, bottom right, bottom left.
+    #[inline]
+    pub const fn corners(&self) -> [Val; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+
+    /// Returns the [`BorderRadius`] with its corners made elliptical, with the given vertical radii.
+    ///
+    /// The radii of the corner fields are used as the horizontal radii.
+    #[inline]
+    pub const fn with_vertical(
+        mut self,
+        top_left: Val,
+        top_right: Val,
+        bottom_right: Val,
+        bottom_left: Val,
+    ) -> Self {
+        self.vertical = Some([top_left, top_right, bottom_right, bottom_left]);
+        self
+    }
+}
+
+/// The shape that the background color and image of a node are clipped to, overriding the node's
+/// [`BorderRadius`].
+///
+/// Only the node's own fill and image are clipped, its border, outline and children are drawn as normal. This is
+/// useful for avatar-style crops, where a square image is drawn as a circle:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// fn spawn_avatar(mut commands: Commands, image: UiImage) {
+///     commands.spawn((
+///         ImageBundle {
+///             style: Style {
+///                 width: Val::Px(64.),
+///                 height: Val::Px(64.),
+///                 ..Default::default()
+///             },
+///             image,
+///             ..Default::default()
+///         },
+///         ClipShape::Ellipse,
+///     ));
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+// `ClipShape` is a component that is rarely added, so it isn't worth boxing the border radius
+#[allow(clippy::large_enum_variant)]
+pub enum ClipShape {
+    /// The node's rectangle, with square corners even if the node has a [`BorderRadius`].
+    Rect,
+    /// The node's rectangle, with its corners rounded by the given radii.
+    RoundedRect(BorderRadius),
+    /// The ellipse inscribed in the node's rectangle, which is a circle if the node is square.
+    Ellipse,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::GridPlacement;
+    use crate::{GridPlacement, ImageRepeat, ObjectFit, Val};
+    use bevy_math::Vec2;
 
     #[test]
     fn invalid_grid_placement_values() {
@@ -2196,6 +2868,45 @@ mod tests {
         assert_eq!(GridPlacement::start_span(3, 5).get_end(), None);
         assert_eq!(GridPlacement::end_span(-4, 12).get_start(), None);
     }
+
+    #[test]
+    fn image_repeat_tile_count() {
+        let node_size = Vec2::new(100., 50.);
+        let texture_size = Vec2::new(20., 25.);
+        let tile_count =
+            |repeat: ImageRepeat| repeat.tile_count(node_size, texture_size, Vec2::ZERO, 16.);
+
+        assert_eq!(tile_count(ImageRepeat::new()), Vec2::new(5., 2.));
+        assert_eq!(tile_count(ImageRepeat::x()), Vec2::new(5., 1.));
+        assert_eq!(tile_count(ImageRepeat::y()), Vec2::new(1., 2.));
+        assert_eq!(
+            tile_count(ImageRepeat::new().with_tile_size(Val::Percent(10.), Val::Px(20.))),
+            Vec2::new(10., 2.5)
+        );
+        assert_eq!(
+            tile_count(ImageRepeat::new().with_tile_size(Val::Px(0.), Val::Auto)),
+            Vec2::new(1., 2.)
+        );
+    }
+
+    #[test]
+    fn object_fit_sizes() {
+        let node_size = Vec2::new(100., 50.);
+        let fit =
+            |object_fit: ObjectFit, texture_size: Vec2| object_fit.fit(node_size, texture_size);
+
+        let small = Vec2::new(20., 20.);
+        let large = Vec2::new(400., 100.);
+        assert_eq!(fit(ObjectFit::Fill, large), node_size);
+        assert_eq!(fit(ObjectFit::Contain, large), Vec2::new(100., 25.));
+        assert_eq!(fit(ObjectFit::Contain, small), Vec2::new(50., 50.));
+        assert_eq!(fit(ObjectFit::Cover, large), Vec2::new(200., 50.));
+        assert_eq!(fit(ObjectFit::Cover, small), Vec2::new(100., 100.));
+        assert_eq!(fit(ObjectFit::ScaleDown, large), Vec2::new(100., 25.));
+        assert_eq!(fit(ObjectFit::ScaleDown, small), small);
+        assert_eq!(fit(ObjectFit::None, large), large);
+        assert_eq!(fit(ObjectFit::Cover, Vec2::ZERO), node_size);
+    }
 }
 
 /// Indicates that this root [`Node`] entity should be rendered to a specific camera.
@@ -2205,16 +2916,171 @@ mod tests {
 /// Setting this component on a non-root node will have no effect. It will be overridden
 /// by the root node's component.
 ///
+/// UI can be drawn onto a texture by targeting a camera with a
+/// [`RenderTarget::Image`](bevy_render::camera::RenderTarget::Image). The root node is then sized
+/// relative to the image, and is laid out again whenever the image is resized.
+///
 /// Optional if there is only one camera in the world. Required otherwise.
 #[derive(Component, Clone, Debug, Reflect, Eq, PartialEq)]
+#[reflect(Component, PartialEq, MapEntities)]
 pub struct TargetCamera(pub Entity);
 
+impl MapEntities for TargetCamera {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
 impl TargetCamera {
     pub fn entity(&self) -> Entity {
         self.0
     }
 }
 
+/// Selects where in the camera's render graph this root [`Node`] entity and its descendants are drawn.
+///
+/// Setting this component on a non-root node will have no effect. It will be overridden
+/// by the root node's component.
+#[derive(Component, Copy, Clone, Debug, Default, Reflect, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[reflect(Component, Default, PartialEq)]
+pub enum UiLayer {
+    /// Draw the UI into the main pass of a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d), depth tested
+    /// against the 3D scene at the distance from the camera given by the root node's [`UiDepth`].
+    ///
+    /// This intermixes the UI with the scene, for diegetic UI such as the displays of a cockpit panel.
+    /// The UI doesn't write to the depth buffer, and isn't drawn by cameras without a depth buffer like
+    /// [`Camera2d`](bevy_core_pipeline::core_2d::Camera2d).
+    MainPass,
+    /// Draw the UI into the main pass, before post-processing.
+    ///
+    /// The UI is affected by the camera's post-processing effects like bloom and tonemapping,
+    /// which suits diegetic UI such as HUD elements that should look like a part of the scene.
+    BeforePostProcessing,
+    /// Draw the UI on top of the post-processed image, unaffected by post-processing.
+    #[default]
+    Overlay,
+}
+
+/// The distance from the camera, in world units, at which the UI of this root [`Node`] entity and its
+/// descendants is depth tested against the 3D scene when drawn with [`UiLayer::MainPass`].
+///
+/// Setting this component on a non-root node will have no effect. It will be overridden
+/// by the root node's component.
+#[derive(Component, Copy, Clone, Debug, Reflect, PartialEq)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiDepth(pub f32);
+
+impl UiDepth {
+    pub const DEFAULT: Self = Self(1.);
+}
+
+impl Default for UiDepth {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Assigns this root [`Node`] entity and its descendants to a layer of its camera's UI, so that overlays like a
+/// game HUD and a debug overlay can be sorted and toggled independently with a [`UiCameraConfig`].
+///
+/// Layers are drawn one after another, so every node in a layer is drawn over, and receives interactions before,
+/// the nodes of the layers drawn before it, regardless of their [`ZIndex`]. Nodes without a [`UiRootLayer`] are in
+/// layer `0`.
+///
+/// Setting this component on a non-root node will have no effect. It will be overridden
+/// by the root node's component.
+#[derive(Component, Copy, Clone, Debug, Default, Reflect, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiRootLayer(pub u32);
+
+/// Scales this root [`Node`] entity and its descendants on top of the global [`UiScale`](crate::UiScale), so that
+/// e.g. a game HUD can be scaled independently of its menus.
+///
+/// Pixel and rem lengths, text and images are scaled, while percentages and viewport units aren't. Changing the
+/// scale only lays out the nodes under the root again, so it can be animated without affecting the rest of the UI.
+///
+/// Setting this component on a non-root node will have no effect. It will be overridden
+/// by the root node's component.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiRootScale(pub f32);
+
+impl UiRootScale {
+    pub const DEFAULT: Self = Self(1.);
+}
+
+impl Default for UiRootScale {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Configures the order and visibility of the [`UiRootLayer`]s drawn by a camera.
+///
+/// ```
+/// # use bevy_ui::prelude::*;
+/// # use bevy_ui::{UiCameraConfig, UiRootLayer};
+/// # use bevy_ecs::prelude::Commands;
+/// # use bevy_core_pipeline::prelude::Camera2dBundle;
+/// const HUD: UiRootLayer = UiRootLayer(1);
+/// const DEBUG_OVERLAY: UiRootLayer = UiRootLayer(2);
+///
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         Camera2dBundle::default(),
+///         // Draw the HUD over the debug overlay, and hide the overlay until it is toggled on.
+///         UiCameraConfig::default()
+///             .with_layer_order([DEBUG_OVERLAY, HUD])
+///             .with_hidden_layer(DEBUG_OVERLAY),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect, PartialEq)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiCameraConfig {
+    /// The layers drawn in this order, back to front, after the layers that aren't listed.
+    ///
+    /// Layers that aren't listed are drawn first, in ascending order.
+    pub layer_order: Vec<UiRootLayer>,
+    /// The layers that aren't drawn and don't receive interactions.
+    pub hidden_layers: Vec<UiRootLayer>,
+}
+
+impl UiCameraConfig {
+    /// Draws the layers in this order, back to front, after the layers that aren't listed.
+    pub fn with_layer_order(mut self, layer_order: impl IntoIterator<Item = UiRootLayer>) -> Self {
+        self.layer_order = layer_order.into_iter().collect();
+        self
+    }
+
+    /// Hides `layer`.
+    pub fn with_hidden_layer(mut self, layer: UiRootLayer) -> Self {
+        self.set_layer_visibility(layer, false);
+        self
+    }
+
+    /// Shows or hides `layer`.
+    pub fn set_layer_visibility(&mut self, layer: UiRootLayer, visible: bool) {
+        self.hidden_layers.retain(|hidden| *hidden != layer);
+        if !visible {
+            self.hidden_layers.push(layer);
+        }
+    }
+
+    /// Returns true if `layer` is drawn.
+    pub fn is_layer_visible(&self, layer: UiRootLayer) -> bool {
+        !self.hidden_layers.contains(&layer)
+    }
+
+    /// The position of `layer` in the drawing order, layers with a greater rank are drawn later.
+    pub fn layer_rank(&self, layer: UiRootLayer) -> u64 {
+        match self.layer_order.iter().position(|listed| *listed == layer) {
+            Some(index) => (1 << 32) + index as u64,
+            None => layer.0 as u64,
+        }
+    }
+}
+
 #[derive(Component)]
 /// Marker used to identify default cameras, they will have priority over the [`PrimaryWindow`] camera.
 ///
@@ -2251,8 +3117,10 @@ impl TargetCamera {
 ///     ));
 /// }
 /// ```
+#[cfg(feature = "render")]
 pub struct IsDefaultUiCamera;
 
+#[cfg(feature = "render")]
 #[derive(SystemParam)]
 pub struct DefaultUiCamera<'w, 's> {
     cameras: Query<'w, 's, (Entity, &'static Camera)>,
@@ -2260,6 +3128,7 @@ pub struct DefaultUiCamera<'w, 's> {
     primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
 }
 
+#[cfg(feature = "render")]
 impl<'w, 's> DefaultUiCamera<'w, 's> {
     pub fn get(&self) -> Option<Entity> {
         self.default_cameras.get_single().ok().or_else(|| {
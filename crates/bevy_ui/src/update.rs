@@ -1,30 +1,70 @@
 //! This module contains systems that update the UI when something changes
 
-use crate::{CalculatedClip, Display, OverflowAxis, Style, TargetCamera};
+use crate::{
+    geometry::resolve_node_radii, BorderRadius, CalculatedClip, Display, OverflowAxis,
+    RootFontSize, Style, TargetCamera, UiDepth, UiLayer, UiRootLayer, UiRootScale, UiScale,
+    UiViewports,
+};
+#[cfg(feature = "render")]
+use crate::{DefaultUiCamera, UiCameraConfig};
 
 use super::Node;
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
     query::{Changed, With, Without},
-    system::{Commands, Query},
+    system::{Commands, Query, Res},
 };
 use bevy_hierarchy::{Children, Parent};
-use bevy_math::Rect;
+use bevy_math::Vec2;
+#[cfg(feature = "render")]
+use bevy_render::view::ViewVisibility;
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashSet;
 
+type ClipNodeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Node,
+        &'static GlobalTransform,
+        &'static Style,
+        Option<&'static BorderRadius>,
+        Option<&'static TargetCamera>,
+        Option<&'static mut CalculatedClip>,
+    ),
+>;
+
 /// Updates clipping for all nodes
+///
+/// The clip of a node is the intersection of the clip boxes of its ancestors with [`Overflow::clip`](crate::Overflow::clip).
+/// The rounded corners of an ancestor's [`BorderRadius`] are kept where they are corners of the intersection.
+#[allow(clippy::too_many_arguments)]
 pub fn update_clipping_system(
     mut commands: Commands,
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
-    mut node_query: Query<(&Node, &GlobalTransform, &Style, Option<&mut CalculatedClip>)>,
+    mut node_query: ClipNodeQuery,
     children_query: Query<&Children>,
+    viewports: UiViewports,
+    ui_scale: Res<UiScale>,
+    root_font_size: Res<RootFontSize>,
 ) {
+    let resolve_radii =
+        |border_radius: &BorderRadius, node: &Node, target_camera: Option<&TargetCamera>| {
+            resolve_node_radii(
+                border_radius,
+                node,
+                viewports.ui_logical_size_of(target_camera, ui_scale.0),
+                ui_scale.0,
+                root_font_size.0,
+            )
+        };
     for root_node in &root_node_query {
         update_clipping(
             &mut commands,
             &children_query,
             &mut node_query,
+            &resolve_radii,
             root_node,
             None,
         );
@@ -34,28 +74,28 @@ pub fn update_clipping_system(
 fn update_clipping(
     commands: &mut Commands,
     children_query: &Query<&Children>,
-    node_query: &mut Query<(&Node, &GlobalTransform, &Style, Option<&mut CalculatedClip>)>,
+    node_query: &mut ClipNodeQuery,
+    resolve_radii: &impl Fn(&BorderRadius, &Node, Option<&TargetCamera>) -> [Vec2; 4],
     entity: Entity,
-    mut maybe_inherited_clip: Option<Rect>,
+    mut maybe_inherited_clip: Option<CalculatedClip>,
 ) {
-    let Ok((node, global_transform, style, maybe_calculated_clip)) = node_query.get_mut(entity)
+    let Ok((node, global_transform, style, border_radius, target_camera, maybe_calculated_clip)) =
+        node_query.get_mut(entity)
     else {
         return;
     };
 
     // If `display` is None, clip the entire node and all its descendants by replacing the inherited clip with a default rect (which is empty)
     if style.display == Display::None {
-        maybe_inherited_clip = Some(Rect::default());
+        maybe_inherited_clip = Some(CalculatedClip::default());
     }
 
     // Update this node's CalculatedClip component
     if let Some(mut calculated_clip) = maybe_calculated_clip {
         if let Some(inherited_clip) = maybe_inherited_clip {
             // Replace the previous calculated clip with the inherited clipping rect
-            if calculated_clip.clip != inherited_clip {
-                *calculated_clip = CalculatedClip {
-                    clip: inherited_clip,
-                };
+            if *calculated_clip != inherited_clip {
+                *calculated_clip = inherited_clip;
             }
         } else {
             // No inherited clipping rect, remove the component
@@ -63,9 +103,7 @@ fn update_clipping(
         }
     } else if let Some(inherited_clip) = maybe_inherited_clip {
         // No previous calculated clip, add a new CalculatedClip component with the inherited clipping rect
-        commands.entity(entity).try_insert(CalculatedClip {
-            clip: inherited_clip,
-        });
+        commands.entity(entity).try_insert(inherited_clip);
     }
 
     // Calculate new clip rectangle for children nodes
@@ -80,27 +118,54 @@ fn update_clipping(
         // current node's clip and the inherited clip. This handles the case
         // of nested `Overflow::Hidden` nodes. If parent `clip` is not
         // defined, use the current node's clip.
-        let mut node_rect = node.logical_rect(global_transform);
+        let node_rect = node.logical_rect(global_transform);
+        let mut clip = CalculatedClip {
+            clip: node.logical_box_rect(global_transform, style.overflow.clip_box),
+            radii: [Vec2::ZERO; 4],
+        };
+        if let Some(border_radius) = border_radius {
+            // The corners of the padding and content boxes are rounded by the radii of the border box minus the
+            // thickness of the border and padding, like CSS
+            let inset_min = clip.clip.min - node_rect.min;
+            let inset_max = node_rect.max - clip.clip.max;
+            let insets = [
+                inset_min,
+                Vec2::new(inset_max.x, inset_min.y),
+                inset_max,
+                Vec2::new(inset_min.x, inset_max.y),
+            ];
+            let radii = resolve_radii(border_radius, node, target_camera);
+            clip.radii = std::array::from_fn(|i| (radii[i] - insets[i]).max(Vec2::ZERO));
+        }
         if style.overflow.x == OverflowAxis::Visible {
-            node_rect.min.x = -f32::INFINITY;
-            node_rect.max.x = f32::INFINITY;
+            clip.clip.min.x = -f32::INFINITY;
+            clip.clip.max.x = f32::INFINITY;
+            clip.radii = [Vec2::ZERO; 4];
         }
         if style.overflow.y == OverflowAxis::Visible {
-            node_rect.min.y = -f32::INFINITY;
-            node_rect.max.y = f32::INFINITY;
+            clip.clip.min.y = -f32::INFINITY;
+            clip.clip.max.y = f32::INFINITY;
+            clip.radii = [Vec2::ZERO; 4];
         }
-        Some(maybe_inherited_clip.map_or(node_rect, |c| c.intersect(node_rect)))
+        Some(maybe_inherited_clip.map_or(clip, |inherited_clip| inherited_clip.intersect(clip)))
     };
 
     if let Ok(children) = children_query.get(entity) {
         for &child in children {
-            update_clipping(commands, children_query, node_query, child, children_clip);
+            update_clipping(
+                commands,
+                children_query,
+                node_query,
+                resolve_radii,
+                child,
+                children_clip,
+            );
         }
     }
 }
 
 pub fn update_target_camera_system(
-    mut commands: Commands,
+    commands: Commands,
     changed_root_nodes_query: Query<
         (Entity, Option<&TargetCamera>),
         (With<Node>, Without<Parent>, Changed<TargetCamera>),
@@ -108,43 +173,179 @@ pub fn update_target_camera_system(
     changed_children_query: Query<(Entity, Option<&TargetCamera>), (With<Node>, Changed<Children>)>,
     children_query: Query<&Children, With<Node>>,
     node_query: Query<Option<&TargetCamera>, With<Node>>,
+) {
+    propagate_root_component(
+        commands,
+        &changed_root_nodes_query,
+        &changed_children_query,
+        &children_query,
+        &node_query,
+    );
+}
+
+/// Propagates the [`UiLayer`] of root nodes to their descendants.
+pub fn update_ui_layer_system(
+    commands: Commands,
+    changed_root_nodes_query: Query<
+        (Entity, Option<&UiLayer>),
+        (With<Node>, Without<Parent>, Changed<UiLayer>),
+    >,
+    changed_children_query: Query<(Entity, Option<&UiLayer>), (With<Node>, Changed<Children>)>,
+    children_query: Query<&Children, With<Node>>,
+    node_query: Query<Option<&UiLayer>, With<Node>>,
+) {
+    propagate_root_component(
+        commands,
+        &changed_root_nodes_query,
+        &changed_children_query,
+        &children_query,
+        &node_query,
+    );
+}
+
+/// Propagates the [`UiDepth`] of root nodes to their descendants.
+pub fn update_ui_depth_system(
+    commands: Commands,
+    changed_root_nodes_query: Query<
+        (Entity, Option<&UiDepth>),
+        (With<Node>, Without<Parent>, Changed<UiDepth>),
+    >,
+    changed_children_query: Query<(Entity, Option<&UiDepth>), (With<Node>, Changed<Children>)>,
+    children_query: Query<&Children, With<Node>>,
+    node_query: Query<Option<&UiDepth>, With<Node>>,
+) {
+    propagate_root_component(
+        commands,
+        &changed_root_nodes_query,
+        &changed_children_query,
+        &children_query,
+        &node_query,
+    );
+}
+
+/// Propagates the [`UiRootLayer`] of root nodes to their descendants.
+pub fn update_ui_root_layer_system(
+    commands: Commands,
+    changed_root_nodes_query: Query<
+        (Entity, Option<&UiRootLayer>),
+        (With<Node>, Without<Parent>, Changed<UiRootLayer>),
+    >,
+    changed_children_query: Query<(Entity, Option<&UiRootLayer>), (With<Node>, Changed<Children>)>,
+    children_query: Query<&Children, With<Node>>,
+    node_query: Query<Option<&UiRootLayer>, With<Node>>,
+) {
+    propagate_root_component(
+        commands,
+        &changed_root_nodes_query,
+        &changed_children_query,
+        &children_query,
+        &node_query,
+    );
+}
+
+/// Propagates the [`UiRootScale`] of root nodes to their descendants.
+pub fn update_ui_root_scale_system(
+    commands: Commands,
+    changed_root_nodes_query: Query<
+        (Entity, Option<&UiRootScale>),
+        (With<Node>, Without<Parent>, Changed<UiRootScale>),
+    >,
+    changed_children_query: Query<(Entity, Option<&UiRootScale>), (With<Node>, Changed<Children>)>,
+    children_query: Query<&Children, With<Node>>,
+    node_query: Query<Option<&UiRootScale>, With<Node>>,
+) {
+    propagate_root_component(
+        commands,
+        &changed_root_nodes_query,
+        &changed_children_query,
+        &children_query,
+        &node_query,
+    );
+}
+
+/// Hides the nodes in the [`UiRootLayer`]s hidden by the [`UiCameraConfig`] of their camera.
+#[cfg(feature = "render")]
+pub fn hide_ui_root_layers_system(
+    camera_query: Query<&UiCameraConfig>,
+    default_ui_camera: DefaultUiCamera,
+    mut node_query: Query<
+        (
+            Option<&UiRootLayer>,
+            Option<&TargetCamera>,
+            &mut ViewVisibility,
+        ),
+        With<Node>,
+    >,
+) {
+    if camera_query
+        .iter()
+        .all(|config| config.hidden_layers.is_empty())
+    {
+        return;
+    }
+    let default_camera = default_ui_camera.get();
+    for (layer, target_camera, mut view_visibility) in &mut node_query {
+        if !view_visibility.get() {
+            continue;
+        }
+        let hidden = target_camera
+            .map(TargetCamera::entity)
+            .or(default_camera)
+            .and_then(|camera| camera_query.get(camera).ok())
+            .is_some_and(|config| !config.is_layer_visible(layer.copied().unwrap_or_default()));
+        if hidden {
+            *view_visibility = ViewVisibility::HIDDEN;
+        }
+    }
+}
+
+/// Copies a component that is set on root nodes, like [`TargetCamera`], to all of their descendants.
+fn propagate_root_component<C: Component + Clone + PartialEq>(
+    mut commands: Commands,
+    changed_root_nodes_query: &Query<
+        (Entity, Option<&C>),
+        (With<Node>, Without<Parent>, Changed<C>),
+    >,
+    changed_children_query: &Query<(Entity, Option<&C>), (With<Node>, Changed<Children>)>,
+    children_query: &Query<&Children, With<Node>>,
+    node_query: &Query<Option<&C>, With<Node>>,
 ) {
     // Track updated entities to prevent redundant updates, as `Commands` changes are deferred,
     // and updates done for changed_children_query can overlap with itself or with root_node_query
     let mut updated_entities = HashSet::new();
 
-    // Assuming that TargetCamera is manually set on the root node only,
+    // Assuming that the component is manually set on the root node only,
     // update root nodes first, since it implies the biggest change
-    for (root_node, target_camera) in &changed_root_nodes_query {
-        update_children_target_camera(
+    for (root_node, component) in changed_root_nodes_query {
+        update_children_component(
             root_node,
-            target_camera,
-            &node_query,
-            &children_query,
+            component,
+            node_query,
+            children_query,
             &mut commands,
             &mut updated_entities,
         );
     }
 
-    // If the root node TargetCamera was changed, then every child is updated
+    // If the root node component was changed, then every child is updated
     // by this point, and iteration will be skipped.
     // Otherwise, update changed children
-    for (parent, target_camera) in &changed_children_query {
-        update_children_target_camera(
+    for (parent, component) in changed_children_query {
+        update_children_component(
             parent,
-            target_camera,
-            &node_query,
-            &children_query,
+            component,
+            node_query,
+            children_query,
             &mut commands,
             &mut updated_entities,
         );
     }
 }
 
-fn update_children_target_camera(
+fn update_children_component<C: Component + Clone + PartialEq>(
     entity: Entity,
-    camera_to_set: Option<&TargetCamera>,
-    node_query: &Query<Option<&TargetCamera>, With<Node>>,
+    component_to_set: Option<&C>,
+    node_query: &Query<Option<&C>, With<Node>>,
     children_query: &Query<&Children, With<Node>>,
     commands: &mut Commands,
     updated_entities: &mut HashSet<Entity>,
@@ -156,24 +357,24 @@ fn update_children_target_camera(
     for &child in children {
         // Skip if the child has already been updated or update is not needed
         if updated_entities.contains(&child)
-            || camera_to_set == node_query.get(child).ok().flatten()
+            || component_to_set == node_query.get(child).ok().flatten()
         {
             continue;
         }
 
-        match camera_to_set {
-            Some(camera) => {
-                commands.entity(child).try_insert(camera.clone());
+        match component_to_set {
+            Some(component) => {
+                commands.entity(child).try_insert(component.clone());
             }
             None => {
-                commands.entity(child).remove::<TargetCamera>();
+                commands.entity(child).remove::<C>();
             }
         }
         updated_entities.insert(child);
 
-        update_children_target_camera(
+        update_children_component(
             child,
-            camera_to_set,
+            component_to_set,
             node_query,
             children_query,
             commands,
@@ -181,3 +382,147 @@ fn update_children_target_camera(
         );
     }
 }
+
+#[cfg(all(test, feature = "render"))]
+mod tests {
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_hierarchy::BuildWorldChildren;
+
+    use bevy_math::{Rect, Vec2};
+    use bevy_sprite::BorderRect;
+    use bevy_transform::prelude::GlobalTransform;
+
+    use super::{update_clipping_system, update_ui_layer_system};
+    use crate::{
+        node_bundles::NodeBundle, BorderRadius, CalculatedClip, Node, Overflow, OverflowClipBox,
+        RootFontSize, Style, UiLayer, UiScale, Val,
+    };
+
+    #[test]
+    fn ui_layer_is_propagated_from_root_nodes() {
+        let mut world = World::new();
+        let root = world
+            .spawn((NodeBundle::default(), UiLayer::BeforePostProcessing))
+            .id();
+        let child = world.spawn(NodeBundle::default()).id();
+        let grandchild = world.spawn(NodeBundle::default()).id();
+        world.entity_mut(root).add_child(child);
+        world.entity_mut(child).add_child(grandchild);
+
+        world.run_system_once(update_ui_layer_system);
+        assert_eq!(
+            world.get::<UiLayer>(grandchild),
+            Some(&UiLayer::BeforePostProcessing)
+        );
+
+        world.entity_mut(root).insert(UiLayer::Overlay);
+        world.run_system_once(update_ui_layer_system);
+        assert_eq!(world.get::<UiLayer>(child), Some(&UiLayer::Overlay));
+        assert_eq!(world.get::<UiLayer>(grandchild), Some(&UiLayer::Overlay));
+    }
+
+    #[test]
+    fn children_are_clipped_to_the_clip_box() {
+        let mut world = World::new();
+        world.init_resource::<UiScale>();
+        world.init_resource::<RootFontSize>();
+        let container = world
+            .spawn((NodeBundle {
+                node: Node {
+                    calculated_size: Vec2::splat(100.),
+                    border: BorderRect::square(5.),
+                    padding: BorderRect::rectangle(10., 0.),
+                    ..Default::default()
+                },
+                style: Style {
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                global_transform: GlobalTransform::from_xyz(50., 50., 0.),
+                ..Default::default()
+            },))
+            .id();
+        let child = world.spawn(NodeBundle::default()).id();
+        world.entity_mut(container).add_child(child);
+
+        let child_clip = |world: &mut World, clip_box: OverflowClipBox| {
+            world.get_mut::<Style>(container).unwrap().overflow.clip_box = clip_box;
+            world.run_system_once(update_clipping_system);
+            world.get::<CalculatedClip>(child).unwrap().clip
+        };
+
+        assert_eq!(
+            child_clip(&mut world, OverflowClipBox::BorderBox),
+            Rect::new(0., 0., 100., 100.)
+        );
+        assert_eq!(
+            child_clip(&mut world, OverflowClipBox::PaddingBox),
+            Rect::new(5., 5., 95., 95.)
+        );
+        assert_eq!(
+            child_clip(&mut world, OverflowClipBox::ContentBox),
+            Rect::new(15., 5., 85., 95.)
+        );
+
+        // The container itself isn't clipped by its own overflow
+        assert!(world.get::<CalculatedClip>(container).is_none());
+        assert_eq!(
+            Overflow::clip_content_only().clip_box,
+            OverflowClipBox::PaddingBox
+        );
+    }
+
+    #[test]
+    fn rounded_clips_are_intersected() {
+        let mut world = World::new();
+        world.init_resource::<UiScale>();
+        world.init_resource::<RootFontSize>();
+        let clip_node = |world: &mut World, center: f32, radius: f32| {
+            world
+                .spawn(NodeBundle {
+                    node: Node {
+                        calculated_size: Vec2::splat(100.),
+                        border: BorderRect::square(5.),
+                        ..Default::default()
+                    },
+                    style: Style {
+                        overflow: Overflow::clip_content_only(),
+                        ..Default::default()
+                    },
+                    border_radius: BorderRadius::all(Val::Px(radius)),
+                    global_transform: GlobalTransform::from_xyz(center, center, 0.),
+                    ..Default::default()
+                })
+                .id()
+        };
+        // A rounded container with a rounded child overlapping its bottom right corner
+        let container = clip_node(&mut world, 50., 20.);
+        let inner = clip_node(&mut world, 100., 30.);
+        let leaf = world.spawn(NodeBundle::default()).id();
+        world.entity_mut(container).add_child(inner);
+        world.entity_mut(inner).add_child(leaf);
+        world.run_system_once(update_clipping_system);
+
+        // The radii of the padding box are reduced by the border thickness
+        assert_eq!(
+            world.get::<CalculatedClip>(inner),
+            Some(&CalculatedClip {
+                clip: Rect::new(5., 5., 95., 95.),
+                radii: [Vec2::splat(15.); 4],
+            })
+        );
+        // Only the top left corner of the intersection is a corner of the inner node's clip, the bottom right
+        // corner is a corner of the container's clip
+        assert_eq!(
+            world.get::<CalculatedClip>(leaf),
+            Some(&CalculatedClip {
+                clip: Rect::new(55., 55., 95., 95.),
+                radii: [Vec2::splat(25.), Vec2::ZERO, Vec2::splat(15.), Vec2::ZERO],
+            })
+        );
+        assert!(!world
+            .get::<CalculatedClip>(leaf)
+            .unwrap()
+            .contains(Vec2::new(94., 94.)));
+    }
+}
@@ -0,0 +1,224 @@
+//! UI roots spawned per camera, for split-screen HUDs and other per-viewport UI.
+
+use std::{fmt, sync::Arc};
+
+use bevy_ecs::{
+    entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::ReflectMapEntities,
+    system::SystemParam,
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt, HierarchyQueryExt, Parent};
+use bevy_reflect::Reflect;
+use bevy_utils::default;
+
+use crate::{node_bundles::NodeBundle, Style, TargetCamera, Val};
+
+/// Spawns a UI root filling the viewport of the camera this component is added to, with children built
+/// by a template.
+///
+/// Adding a `ViewportUi` with the same template to each camera of a split-screen game gives every player
+/// their own copy of the HUD. Viewport units like [`Val::Vw`] resolve against the camera's viewport, and the
+/// template's systems can use [`UiDataSources`] to find which player's data each copy should display.
+///
+/// The root is despawned with its children when this component is removed or the camera is despawned.
+#[derive(Component, Clone)]
+pub struct ViewportUi {
+    template: Arc<dyn Fn(&mut ChildBuilder) + Send + Sync>,
+    /// The entity whose data the UI displays, set as the [`UiDataSource`] of the root.
+    pub data_source: Option<Entity>,
+    root: Option<Entity>,
+}
+
+impl ViewportUi {
+    /// Creates a [`ViewportUi`] whose root's children are spawned by `template`.
+    pub fn new(template: impl Fn(&mut ChildBuilder) + Send + Sync + 'static) -> Self {
+        Self {
+            template: Arc::new(template),
+            data_source: None,
+            root: None,
+        }
+    }
+
+    /// Sets the entity whose data the UI displays.
+    pub fn with_data_source(mut self, data_source: Entity) -> Self {
+        self.data_source = Some(data_source);
+        self
+    }
+
+    /// The UI root spawned for the camera, if it has been spawned yet.
+    pub fn root(&self) -> Option<Entity> {
+        self.root
+    }
+}
+
+impl fmt::Debug for ViewportUi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewportUi")
+            .field("data_source", &self.data_source)
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Marks a UI root spawned for a [`ViewportUi`].
+#[derive(Component, Clone, Copy, Debug, Reflect, PartialEq, Eq)]
+#[reflect(Component, PartialEq, MapEntities)]
+pub struct ViewportUiRoot {
+    /// The camera the root was spawned for.
+    pub camera: Entity,
+}
+
+impl MapEntities for ViewportUiRoot {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.camera = entity_mapper.map_entity(self.camera);
+    }
+}
+
+/// The entity whose data a UI node and its descendants display.
+///
+/// See [`UiDataSources`].
+#[derive(Component, Clone, Copy, Debug, Reflect, PartialEq, Eq)]
+#[reflect(Component, PartialEq, MapEntities)]
+pub struct UiDataSource(pub Entity);
+
+impl MapEntities for UiDataSource {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+/// Finds the entity whose data a UI node displays, from the [`UiDataSource`] of the node or its closest ancestor.
+///
+/// This lets systems bind a UI template spawned several times, for example by [`ViewportUi`], to different data:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::UiDataSources;
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// #[derive(Component)]
+/// struct HealthBar;
+///
+/// fn update_health_bars(
+///     data_sources: UiDataSources,
+///     players: Query<&Health>,
+///     health_bars: Query<Entity, With<HealthBar>>,
+/// ) {
+///     for health_bar in &health_bars {
+///         let Some(Ok(health)) = data_sources.get(health_bar).map(|player| players.get(player)) else {
+///             continue;
+///         };
+///         // display `health.0` ...
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct UiDataSources<'w, 's> {
+    parents: Query<'w, 's, &'static Parent>,
+    data_sources: Query<'w, 's, &'static UiDataSource>,
+}
+
+impl<'w, 's> UiDataSources<'w, 's> {
+    /// Returns the data source of `entity`, or `None` if neither it nor any of its ancestors has a [`UiDataSource`].
+    pub fn get(&self, entity: Entity) -> Option<Entity> {
+        std::iter::once(entity)
+            .chain(self.parents.iter_ancestors(entity))
+            .find_map(|entity| self.data_sources.get(entity).ok())
+            .map(|data_source| data_source.0)
+    }
+}
+
+/// Spawns and updates the UI roots of [`ViewportUi`]s, and despawns the roots of removed ones.
+pub fn update_viewport_ui_system(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut ViewportUi), Changed<ViewportUi>>,
+    mut removed: RemovedComponents<ViewportUi>,
+    roots: Query<(Entity, &ViewportUiRoot)>,
+) {
+    for camera in removed.read() {
+        for (root, _) in roots.iter().filter(|(_, root)| root.camera == camera) {
+            commands.entity(root).despawn_recursive();
+        }
+    }
+
+    for (camera, mut viewport_ui) in &mut cameras {
+        if let Some(root) = viewport_ui.root {
+            let mut root = commands.entity(root);
+            match viewport_ui.data_source {
+                Some(data_source) => root.insert(UiDataSource(data_source)),
+                None => root.remove::<UiDataSource>(),
+            };
+            continue;
+        }
+
+        let mut root = commands.spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                ..default()
+            },
+            TargetCamera(camera),
+            ViewportUiRoot { camera },
+        ));
+        if let Some(data_source) = viewport_ui.data_source {
+            root.insert(UiDataSource(data_source));
+        }
+        let template = viewport_ui.template.clone();
+        root.with_children(|builder| template(builder));
+        // Don't trigger change detection, or the root would be updated again next frame.
+        viewport_ui.bypass_change_detection().root = Some(root.id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_hierarchy::Children;
+
+    use super::{update_viewport_ui_system, UiDataSources, ViewportUi, ViewportUiRoot};
+    use crate::{node_bundles::NodeBundle, TargetCamera};
+
+    #[test]
+    fn viewport_ui_roots_are_bound_to_their_camera_and_data_source() {
+        let mut world = World::new();
+        let players = [world.spawn_empty().id(), world.spawn_empty().id()];
+        let cameras = players.map(|player| {
+            world
+                .spawn(
+                    ViewportUi::new(|builder| {
+                        builder.spawn(NodeBundle::default());
+                    })
+                    .with_data_source(player),
+                )
+                .id()
+        });
+
+        world.run_system_once(update_viewport_ui_system);
+
+        for (camera, player) in cameras.into_iter().zip(players) {
+            let root = world.get::<ViewportUi>(camera).unwrap().root().unwrap();
+            assert_eq!(world.get::<TargetCamera>(root), Some(&TargetCamera(camera)));
+            assert_eq!(
+                world.get::<ViewportUiRoot>(root),
+                Some(&ViewportUiRoot { camera })
+            );
+            let child = world.get::<Children>(root).unwrap()[0];
+            let data_source =
+                world.run_system_once(move |data_sources: UiDataSources| data_sources.get(child));
+            assert_eq!(data_source, Some(player));
+        }
+
+        // removing the component despawns the root and its children
+        let root = world.get::<ViewportUi>(cameras[0]).unwrap().root().unwrap();
+        let child = world.get::<Children>(root).unwrap()[0];
+        world.entity_mut(cameras[0]).remove::<ViewportUi>();
+        world.run_system_once(update_viewport_ui_system);
+        assert!(world.get_entity(root).is_none());
+        assert!(world.get_entity(child).is_none());
+    }
+}
@@ -0,0 +1,188 @@
+//! The viewports that UI roots are laid out in and interacted with.
+//!
+//! With the `render` feature, each UI root is laid out in the viewport of its camera, its [`TargetCamera`] or the
+//! default UI camera. Without it, there are no cameras, and every UI root is laid out in the [`PrimaryWindow`],
+//! ignoring [`TargetCamera`]s. The window doesn't have to be opened by a windowing backend: spawning a [`Window`]
+//! with a [`PrimaryWindow`] sets the size of the headless viewport, and setting its cursor position moves the
+//! pointer used by [`ui_focus_system`](crate::ui_focus_system).
+
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{UVec2, Vec2};
+use bevy_utils::HashMap;
+use bevy_window::{PrimaryWindow, Window};
+
+#[cfg(feature = "render")]
+use crate::DefaultUiCamera;
+use crate::TargetCamera;
+#[cfg(feature = "render")]
+use bevy_render::{
+    camera::{Camera, NormalizedRenderTarget},
+    view::ViewVisibility,
+};
+
+/// The component of the entities that UI roots are laid out in, [`Camera`] with the `render` feature and
+/// [`Window`] without it.
+#[cfg(feature = "render")]
+pub(crate) type ViewportComponent = Camera;
+#[cfg(not(feature = "render"))]
+pub(crate) type ViewportComponent = Window;
+
+/// The query data for the [`ViewVisibility`] of a node, which is computed by `bevy_render`.
+///
+/// Without the `render` feature, nodes have no visibility and are always visible, see [`is_view_visible`].
+#[cfg(feature = "render")]
+pub(crate) type NodeVisibility = Option<&'static ViewVisibility>;
+#[cfg(not(feature = "render"))]
+pub(crate) type NodeVisibility = ();
+
+/// Returns whether a node is visible, or `None` if it has no [`ViewVisibility`].
+#[cfg(feature = "render")]
+pub(crate) fn is_view_visible(view_visibility: &Option<&ViewVisibility>) -> Option<bool> {
+    view_visibility.map(|view_visibility| view_visibility.get())
+}
+
+/// Returns whether a node is visible, which it always is without the `render` feature.
+#[cfg(not(feature = "render"))]
+pub(crate) fn is_view_visible(_: &()) -> Option<bool> {
+    Some(true)
+}
+
+/// The size, scale factor and window of a viewport that UI roots are laid out in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiViewport {
+    /// The size of the viewport in physical pixels.
+    pub physical_size: UVec2,
+    /// The scale factor of the target of the viewport, without [`UiScale`](crate::UiScale).
+    pub scale_factor: f32,
+    /// The position of the top left corner of the viewport in its window, in logical pixels.
+    pub logical_position: Vec2,
+    /// The window the viewport is in, or `None` if it renders to an image.
+    pub window: Option<Entity>,
+}
+
+impl UiViewport {
+    /// Returns the size of the viewport in logical UI pixels, which viewport units like [`Val::Vw`](crate::Val::Vw)
+    /// are resolved against.
+    pub fn ui_logical_size(&self, ui_scale: f32) -> Vec2 {
+        self.physical_size.as_vec2() / self.scale_factor / ui_scale
+    }
+}
+
+/// The viewports of UI roots, see the [module docs](self).
+#[cfg(feature = "render")]
+#[derive(SystemParam)]
+pub struct UiViewports<'w, 's> {
+    cameras: Query<'w, 's, (Entity, &'static Camera)>,
+    default_ui_camera: DefaultUiCamera<'w, 's>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    windows: Query<'w, 's, &'static Window>,
+}
+
+/// The viewports of UI roots, see the [module docs](self).
+#[cfg(not(feature = "render"))]
+#[derive(SystemParam)]
+pub struct UiViewports<'w, 's> {
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    windows: Query<'w, 's, &'static Window>,
+}
+
+#[cfg(feature = "render")]
+impl<'w, 's> UiViewports<'w, 's> {
+    /// Returns the viewport of the UI roots with the given [`TargetCamera`], or of the roots without one.
+    pub fn viewport_of(&self, target_camera: Option<&TargetCamera>) -> Option<Entity> {
+        target_camera
+            .map(TargetCamera::entity)
+            .or(self.default_ui_camera.get())
+    }
+
+    /// Returns the size, scale factor and window of `viewport`, or `None` if it isn't a camera.
+    pub fn get(&self, viewport: Entity) -> Option<UiViewport> {
+        let (_, camera) = self.cameras.get(viewport).ok()?;
+        let window = match camera.target.normalize(self.primary_window.iter().next()) {
+            Some(NormalizedRenderTarget::Window(window_ref)) => Some(window_ref.entity()),
+            _ => None,
+        };
+        Some(UiViewport {
+            physical_size: camera.physical_viewport_size().unwrap_or(UVec2::ZERO),
+            scale_factor: camera.target_scaling_factor().unwrap_or(1.0),
+            logical_position: camera
+                .logical_viewport_rect()
+                .map(|rect| rect.min)
+                .unwrap_or_default(),
+            window,
+        })
+    }
+
+    /// Iterates over all viewports, whether or not any UI roots are laid out in them.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.cameras.iter().map(|(entity, _)| entity)
+    }
+}
+
+#[cfg(not(feature = "render"))]
+impl<'w, 's> UiViewports<'w, 's> {
+    /// Returns the viewport of the UI roots with the given [`TargetCamera`], or of the roots without one.
+    ///
+    /// Without the `render` feature, this is always the primary window.
+    pub fn viewport_of(&self, _target_camera: Option<&TargetCamera>) -> Option<Entity> {
+        self.primary_window.iter().next()
+    }
+
+    /// Returns the size, scale factor and window of `viewport`, or `None` if it isn't a window.
+    pub fn get(&self, viewport: Entity) -> Option<UiViewport> {
+        let window = self.windows.get(viewport).ok()?;
+        Some(UiViewport {
+            physical_size: window.physical_size(),
+            scale_factor: window.scale_factor(),
+            logical_position: Vec2::ZERO,
+            window: Some(viewport),
+        })
+    }
+
+    /// Iterates over all viewports, whether or not any UI roots are laid out in them.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.primary_window.iter()
+    }
+}
+
+impl<'w, 's> UiViewports<'w, 's> {
+    /// Returns the size of the viewport of the UI roots with the given [`TargetCamera`] in logical UI pixels, or
+    /// zero if it doesn't exist.
+    pub fn ui_logical_size_of(&self, target_camera: Option<&TargetCamera>, ui_scale: f32) -> Vec2 {
+        self.viewport_of(target_camera)
+            .and_then(|viewport| self.get(viewport))
+            .map_or(Vec2::ZERO, |viewport| viewport.ui_logical_size(ui_scale))
+    }
+
+    /// Returns the cursor position of each viewport in a window, in logical UI viewport coordinates.
+    ///
+    /// `primary_cursor` replaces the cursor of the primary window, like an enabled
+    /// [`GamepadCursor`](crate::GamepadCursor).
+    pub fn cursor_positions(
+        &self,
+        primary_cursor: Option<Vec2>,
+        ui_scale: f32,
+    ) -> HashMap<Entity, Vec2> {
+        let primary_window = self.primary_window.iter().next();
+        self.iter()
+            .filter_map(|entity| {
+                let viewport = self.get(entity)?;
+                // Interactions are only supported for viewports in a window.
+                let window = viewport.window?;
+                primary_cursor
+                    .filter(|_| Some(window) == primary_window)
+                    .or_else(|| {
+                        self.windows
+                            .get(window)
+                            .ok()
+                            .and_then(Window::cursor_position)
+                    })
+                    .map(|cursor_position| (entity, cursor_position - viewport.logical_position))
+            })
+            // The cursor position returned by `Window` only takes into account the window scale factor and not
+            // `UiScale`. To convert the cursor position to logical UI viewport coordinates we have to divide it by
+            // `UiScale`.
+            .map(|(entity, cursor_position)| (entity, cursor_position / ui_scale))
+            .collect()
+    }
+}
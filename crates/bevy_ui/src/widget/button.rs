@@ -1,9 +1,292 @@
-use bevy_ecs::prelude::Component;
+use crate::{BorderColor, Disabled, FocusActivated, Interaction, UiImage};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
 use bevy_ecs::reflect::ReflectComponent;
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::Reflect;
 
+#[cfg(feature = "bevy_text")]
+use bevy_hierarchy::Children;
+#[cfg(feature = "bevy_text")]
+use bevy_text::Text;
+
 /// Marker struct for buttons
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component, Default)]
 pub struct Button;
+
+/// The colors of a [`Button`] in one of its states.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct ButtonVisuals {
+    /// The color of the button's [`UiImage`].
+    pub background: Color,
+    /// The color of all the edges of the button's [`BorderColor`].
+    pub border: Color,
+    /// The color of the sections of the [`Text`](bevy_text::Text) on the button's children.
+    pub text: Color,
+}
+
+impl ButtonVisuals {
+    pub const DEFAULT: Self = Self {
+        background: Color::srgb(0.15, 0.15, 0.15),
+        border: Color::BLACK,
+        text: Color::srgb(0.9, 0.9, 0.9),
+    };
+
+    /// Creates [`ButtonVisuals`] with the given background color, a black border and light text.
+    pub const fn background(background: Color) -> Self {
+        Self {
+            background,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Returns these visuals with the border color replaced.
+    pub const fn with_border(mut self, border: Color) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Returns these visuals with the text color replaced.
+    pub const fn with_text(mut self, text: Color) -> Self {
+        self.text = text;
+        self
+    }
+}
+
+impl Default for ButtonVisuals {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The visuals of a [`Button`] for each of its states, applied by [`update_button_style_system`].
+///
+/// Buttons without a [`ButtonStyle`] are left untouched, so their colors can be set by other systems.
+///
+/// ```
+/// # use bevy_color::Color;
+/// # use bevy_ui::widget::{ButtonStyle, ButtonVisuals};
+/// let style = ButtonStyle {
+///     hovered: ButtonVisuals::background(Color::srgb(0.25, 0.25, 0.25)).with_border(Color::WHITE),
+///     ..Default::default()
+/// };
+/// assert_eq!(style.normal, ButtonVisuals::DEFAULT);
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ButtonStyle {
+    /// Used when the button's [`Interaction`] is [`Interaction::None`].
+    pub normal: ButtonVisuals,
+    /// Used when the button's [`Interaction`] is [`Interaction::Hovered`].
+    pub hovered: ButtonVisuals,
+    /// Used when the button's [`Interaction`] is [`Interaction::Pressed`].
+    pub pressed: ButtonVisuals,
+    /// Used when the button is [`Disabled`].
+    pub disabled: ButtonVisuals,
+}
+
+impl ButtonStyle {
+    pub const DEFAULT: Self = Self {
+        normal: ButtonVisuals::DEFAULT,
+        hovered: ButtonVisuals::background(Color::srgb(0.25, 0.25, 0.25)),
+        pressed: ButtonVisuals::background(Color::srgb(0.35, 0.75, 0.35)).with_border(Color::WHITE),
+        disabled: ButtonVisuals::background(Color::srgb(0.1, 0.1, 0.1))
+            .with_text(Color::srgb(0.4, 0.4, 0.4)),
+    };
+
+    /// Returns the visuals for a button in the given state.
+    pub fn visuals(&self, interaction: Interaction, disabled: bool) -> ButtonVisuals {
+        if disabled {
+            return self.disabled;
+        }
+        match interaction {
+            Interaction::Pressed => self.pressed,
+            Interaction::Hovered => self.hovered,
+            Interaction::None => self.normal,
+        }
+    }
+}
+
+impl Default for ButtonStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Sent by [`button_pressed_system`] when a [`Button`] is pressed with the cursor or a touch, or activated with
+/// navigation focus. [`Disabled`] buttons are never pressed.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonPressed(pub Entity);
+
+/// Sends [`ButtonPressed`] events for pressed and activated buttons.
+pub fn button_pressed_system(
+    interaction_query: Query<
+        (Entity, &Interaction),
+        (Changed<Interaction>, With<Button>, Without<Disabled>),
+    >,
+    button_query: Query<(), (With<Button>, Without<Disabled>)>,
+    mut activated_events: EventReader<FocusActivated>,
+    mut pressed_events: EventWriter<ButtonPressed>,
+) {
+    for (entity, interaction) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            pressed_events.send(ButtonPressed(entity));
+        }
+    }
+    for FocusActivated(entity) in activated_events.read() {
+        if button_query.contains(*entity) {
+            pressed_events.send(ButtonPressed(*entity));
+        }
+    }
+}
+
+/// Applies the [`ButtonVisuals`] of each [`ButtonStyle`] for the button's current state.
+///
+/// The background is set on the button's [`UiImage`], the border on all edges of its [`BorderColor`] and the
+/// text color on every section of the [`Text`](bevy_text::Text) on its direct children.
+#[allow(clippy::type_complexity)]
+pub fn update_button_style_system(
+    mut button_query: Query<
+        (
+            Entity,
+            Ref<ButtonStyle>,
+            Ref<Interaction>,
+            Has<Disabled>,
+            Option<&mut UiImage>,
+            Option<&mut BorderColor>,
+        ),
+        With<Button>,
+    >,
+    added_disabled: Query<(), Added<Disabled>>,
+    mut removed_disabled: RemovedComponents<Disabled>,
+    #[cfg(feature = "bevy_text")] children_query: Query<&Children>,
+    #[cfg(feature = "bevy_text")] mut text_query: Query<&mut Text>,
+) {
+    let removed_disabled = removed_disabled.read().collect::<Vec<_>>();
+    for (entity, style, interaction, disabled, image, border_color) in &mut button_query {
+        if !(style.is_changed()
+            || interaction.is_changed()
+            || added_disabled.contains(entity)
+            || removed_disabled.contains(&entity))
+        {
+            continue;
+        }
+        let visuals = style.visuals(*interaction, disabled);
+        if let Some(mut image) = image {
+            if image.color != visuals.background {
+                image.color = visuals.background;
+            }
+        }
+        if let Some(mut border_color) = border_color {
+            if *border_color != BorderColor::all(visuals.border) {
+                border_color.set_all(visuals.border);
+            }
+        }
+        #[cfg(feature = "bevy_text")]
+        if let Ok(children) = children_query.get(entity) {
+            let mut iter = text_query.iter_many_mut(children);
+            while let Some(mut text) = iter.fetch_next() {
+                if text
+                    .sections
+                    .iter()
+                    .any(|section| section.style.color != visuals.text)
+                {
+                    for section in &mut text.sections {
+                        section.style.color = visuals.text;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        button_pressed_system, update_button_style_system, Button, ButtonPressed, ButtonStyle,
+    };
+    use crate::{BorderColor, Disabled, FocusActivated, Interaction, UiImage};
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    #[test]
+    fn button_states() {
+        let mut world = World::new();
+        world.init_resource::<Events<FocusActivated>>();
+        world.init_resource::<Events<ButtonPressed>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((button_pressed_system, update_button_style_system));
+
+        let style = ButtonStyle::default();
+        let mut spawn_button = |interaction| {
+            world
+                .spawn((
+                    Button,
+                    style,
+                    interaction,
+                    UiImage::default(),
+                    BorderColor::default(),
+                ))
+                .id()
+        };
+        let pressed = spawn_button(Interaction::Pressed);
+        let hovered = spawn_button(Interaction::Hovered);
+        let disabled = spawn_button(Interaction::Pressed);
+        world.entity_mut(disabled).insert(Disabled);
+
+        world.send_event(FocusActivated(hovered));
+        world.send_event(FocusActivated(disabled));
+        schedule.run(&mut world);
+
+        let visuals = |world: &World, entity| {
+            let entity = world.entity(entity);
+            (
+                entity.get::<UiImage>().unwrap().color,
+                *entity.get::<BorderColor>().unwrap(),
+            )
+        };
+        assert_eq!(
+            visuals(&world, pressed),
+            (
+                style.pressed.background,
+                BorderColor::all(style.pressed.border)
+            )
+        );
+        assert_eq!(
+            visuals(&world, hovered),
+            (
+                style.hovered.background,
+                BorderColor::all(style.hovered.border)
+            )
+        );
+        assert_eq!(
+            visuals(&world, disabled),
+            (
+                style.disabled.background,
+                BorderColor::all(style.disabled.border)
+            )
+        );
+
+        let events = world.resource::<Events<ButtonPressed>>();
+        assert_eq!(
+            events
+                .get_reader()
+                .read(events)
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![ButtonPressed(pressed), ButtonPressed(hovered)]
+        );
+
+        // Enabling the button again restores its visuals
+        world.entity_mut(disabled).remove::<Disabled>();
+        schedule.run(&mut world);
+        assert_eq!(
+            visuals(&world, disabled),
+            (
+                style.pressed.background,
+                BorderColor::all(style.pressed.border)
+            )
+        );
+    }
+}
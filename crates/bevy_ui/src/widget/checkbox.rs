@@ -0,0 +1,197 @@
+use crate::{
+    node_bundles::NodeBundle, widget::ButtonPressed, BackgroundColor, BorderRadius, Display,
+    PositionType, Style, Val,
+};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_hierarchy::{BuildChildren, Children};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::default;
+
+/// How a [`Checkbox`] displays its state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum CheckboxKind {
+    /// A box with a mark filling its center while checked.
+    #[default]
+    Check,
+    /// A switch with a knob that is on the left while unchecked and on the right while checked.
+    Toggle,
+}
+
+/// A checkbox or toggle switch.
+///
+/// The checkbox is toggled when its [`Button`](crate::widget::Button) is pressed, which sends a [`CheckboxChanged`]
+/// event. [`Disabled`](crate::Disabled) checkboxes can't be toggled.
+///
+/// The mark or knob is displayed by a child node, spawned when the [`Checkbox`] is added.
+///
+/// See [`CheckboxBundle`](crate::node_bundles::CheckboxBundle).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Checkbox {
+    /// Whether the checkbox is checked.
+    pub checked: bool,
+    /// How the checkbox displays its state.
+    pub kind: CheckboxKind,
+    /// The color of the mark or knob.
+    pub mark_color: Color,
+}
+
+impl Checkbox {
+    /// Creates a [`Checkbox`] that is checked or not.
+    pub fn new(checked: bool) -> Self {
+        Self {
+            checked,
+            ..default()
+        }
+    }
+
+    /// Creates a toggle switch that is on or off.
+    pub fn toggle(checked: bool) -> Self {
+        Self {
+            checked,
+            kind: CheckboxKind::Toggle,
+            ..default()
+        }
+    }
+}
+
+impl Default for Checkbox {
+    fn default() -> Self {
+        Self {
+            checked: false,
+            kind: CheckboxKind::Check,
+            mark_color: Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// Sent when a [`Checkbox`] is toggled by pressing it.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckboxChanged {
+    /// The checkbox entity.
+    pub entity: Entity,
+    /// Whether the checkbox is now checked.
+    pub checked: bool,
+}
+
+/// Marker for the node displaying the mark or knob of a [`Checkbox`].
+#[derive(Component)]
+pub struct CheckboxMark;
+
+/// Spawns the mark nodes of new [`Checkbox`]es.
+pub fn spawn_checkbox_children(mut commands: Commands, checkboxes: Query<Entity, Added<Checkbox>>) {
+    for entity in &checkboxes {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        ..default()
+                    },
+                    ..default()
+                },
+                CheckboxMark,
+            ));
+        });
+    }
+}
+
+/// Toggles [`Checkbox`]es when their button is pressed and sends [`CheckboxChanged`] events.
+pub fn checkbox_toggle_system(
+    mut pressed_events: EventReader<ButtonPressed>,
+    mut checkboxes: Query<&mut Checkbox>,
+    mut changed_events: EventWriter<CheckboxChanged>,
+) {
+    for &ButtonPressed(entity) in pressed_events.read() {
+        if let Ok(mut checkbox) = checkboxes.get_mut(entity) {
+            checkbox.checked = !checkbox.checked;
+            changed_events.send(CheckboxChanged {
+                entity,
+                checked: checkbox.checked,
+            });
+        }
+    }
+}
+
+/// Displays the state of changed [`Checkbox`]es with their mark node.
+pub fn update_checkbox_children(
+    checkboxes: Query<(&Checkbox, &Children), Changed<Checkbox>>,
+    mut marks: Query<(&mut Style, &mut BackgroundColor, &mut BorderRadius), With<CheckboxMark>>,
+) {
+    for (checkbox, children) in &checkboxes {
+        let mut iter = marks.iter_many_mut(children);
+        while let Some((mut style, mut background_color, mut border_radius)) = iter.fetch_next() {
+            background_color.0 = checkbox.mark_color;
+            match checkbox.kind {
+                CheckboxKind::Check => {
+                    style.display = if checkbox.checked {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    };
+                    style.left = Val::Percent(25.);
+                    style.top = Val::Percent(25.);
+                    style.width = Val::Percent(50.);
+                    style.height = Val::Percent(50.);
+                    *border_radius = BorderRadius::ZERO;
+                }
+                CheckboxKind::Toggle => {
+                    style.display = Display::Flex;
+                    style.left = Val::Percent(if checkbox.checked { 50. } else { 0. });
+                    style.top = Val::ZERO;
+                    style.width = Val::Percent(50.);
+                    style.height = Val::Percent(100.);
+                    *border_radius = BorderRadius::MAX;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checkbox_toggle_system, Checkbox, CheckboxChanged};
+    use crate::widget::ButtonPressed;
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    #[test]
+    fn pressed_checkboxes_are_toggled() {
+        let mut world = World::new();
+        world.init_resource::<Events<ButtonPressed>>();
+        world.init_resource::<Events<CheckboxChanged>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(checkbox_toggle_system);
+
+        let checkbox = world.spawn(Checkbox::new(false)).id();
+        let toggle = world.spawn(Checkbox::toggle(true)).id();
+        let other = world.spawn_empty().id();
+        for entity in [checkbox, toggle, other] {
+            world.send_event(ButtonPressed(entity));
+        }
+        schedule.run(&mut world);
+
+        assert!(world.get::<Checkbox>(checkbox).unwrap().checked);
+        assert!(!world.get::<Checkbox>(toggle).unwrap().checked);
+        let events = world.resource::<Events<CheckboxChanged>>();
+        assert_eq!(
+            events
+                .get_reader()
+                .read(events)
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![
+                CheckboxChanged {
+                    entity: checkbox,
+                    checked: true
+                },
+                CheckboxChanged {
+                    entity: toggle,
+                    checked: false
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,564 @@
+use std::borrow::Cow;
+
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, Display, FlexDirection, Interaction, Style, UiKeyboardCapture,
+    UiRect, Val,
+};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, Children, Parent};
+use bevy_input::{
+    keyboard::{Key, KeyboardInput},
+    ButtonState,
+};
+use bevy_text::{Text, TextStyle};
+use bevy_utils::default;
+
+/// The font size of the text of a [`CommandPalette`].
+const FONT_SIZE: f32 = 18.;
+
+/// The background color of the [`CommandPalette`] panel.
+const PANEL_COLOR: Color = Color::srgb(0.12, 0.12, 0.12);
+
+/// The background color of the selected [`CommandPalette`] result.
+const SELECTED_COLOR: Color = Color::srgb(0.25, 0.3, 0.45);
+
+/// A command that can be chosen from a [`CommandPalette`].
+#[derive(Clone, Debug)]
+pub struct PaletteCommand {
+    /// The identifier sent in [`CommandPaletteSelection`] when the command is chosen.
+    pub id: Cow<'static, str>,
+    /// The text that is displayed and searched.
+    pub label: Cow<'static, str>,
+}
+
+/// The commands listed by every [`CommandPalette`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct CommandRegistry {
+    commands: Vec<PaletteCommand>,
+}
+
+impl CommandRegistry {
+    /// Adds a command, replacing the label of any existing command with the same id.
+    pub fn register(
+        &mut self,
+        id: impl Into<Cow<'static, str>>,
+        label: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        let id = id.into();
+        let label = label.into();
+        match self.commands.iter_mut().find(|command| command.id == id) {
+            Some(command) => command.label = label,
+            None => self.commands.push(PaletteCommand { id, label }),
+        }
+        self
+    }
+
+    /// Removes the command with the given id, returning it if it was registered.
+    pub fn unregister(&mut self, id: &str) -> Option<PaletteCommand> {
+        let index = self.commands.iter().position(|command| command.id == id)?;
+        Some(self.commands.remove(index))
+    }
+
+    /// The registered commands, in registration order.
+    pub fn commands(&self) -> &[PaletteCommand] {
+        &self.commands
+    }
+}
+
+/// Sent when a command is chosen from a [`CommandPalette`].
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct CommandPaletteSelection {
+    /// The [`CommandPalette`] entity.
+    pub palette: Entity,
+    /// The [`PaletteCommand::id`] of the chosen command.
+    pub id: Cow<'static, str>,
+}
+
+/// A modal search box listing the commands of the [`CommandRegistry`] that fuzzy match the typed query.
+///
+/// While open, the palette captures the keyboard with [`UiKeyboardCapture`]: typed characters edit the query,
+/// the up and down arrows move the selection, enter chooses the selected command and escape closes the palette.
+/// The capture is updated before [`UiSystem::Focus`](crate::UiSystem::Focus), so focus navigation and text inputs
+/// ignore the keys in the same frame. The systems of the app can check the capture with the
+/// [`ui_has_keyboard_capture`](crate::ui_has_keyboard_capture) run condition.
+/// Results can also be chosen by clicking them. Choosing a command sends a [`CommandPaletteSelection`] event and closes the palette.
+///
+/// Only [`CommandPalette::max_results`] result rows are spawned, they are reused as the selection scrolls through the matches.
+///
+/// See [`CommandPaletteBundle`](crate::node_bundles::CommandPaletteBundle).
+#[derive(Component, Clone, Debug)]
+pub struct CommandPalette {
+    /// The number of results shown at once.
+    pub max_results: usize,
+    open: bool,
+    query: String,
+    /// Indices into the [`CommandRegistry`] commands of the matching commands, best match first.
+    matches: Vec<usize>,
+    selected: usize,
+    scroll: usize,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self {
+            max_results: 8,
+            open: false,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            scroll: 0,
+        }
+    }
+}
+
+impl CommandPalette {
+    /// Shows the palette with an empty query.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.select_first();
+    }
+
+    /// Hides the palette.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Opens the palette if it is closed and closes it if it is open.
+    pub fn toggle(&mut self) {
+        if self.open {
+            self.close();
+        } else {
+            self.open();
+        }
+    }
+
+    /// Whether the palette is shown.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// The text typed into the palette.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    fn select_first(&mut self) {
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.matches.len().saturating_sub(1));
+        // Scroll the results so that the selected one is visible
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.max_results > 0 && self.max_results <= self.selected - self.scroll {
+            self.scroll = self.selected + 1 - self.max_results;
+        }
+    }
+}
+
+/// Marker for the text node displaying the query of a [`CommandPalette`].
+#[derive(Component)]
+pub struct CommandPaletteQuery;
+
+/// A row displaying the result at the given offset from the first visible result of a [`CommandPalette`].
+#[derive(Component)]
+pub struct CommandPaletteRow(pub usize);
+
+/// Scores how well `query` matches `candidate`.
+///
+/// Returns `None` unless every character of `query` appears in `candidate` in order, ignoring case.
+/// Higher scores are better: consecutive matches and matches at the start of words score higher,
+/// and unmatched characters in `candidate` lower the score.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<i32> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut previous_matched = false;
+    let mut previous: Option<char> = None;
+    for c in candidate.chars() {
+        let word_start = match previous {
+            Some(previous) => {
+                !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+            }
+            None => true,
+        };
+        previous = Some(c);
+        let Some(&next) = query.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(std::iter::once(next)) {
+            query.next();
+            score += 1;
+            if previous_matched {
+                score += 4;
+            }
+            if word_start {
+                score += 8;
+            }
+            previous_matched = true;
+        } else {
+            score -= 1;
+            previous_matched = false;
+        }
+    }
+    query.peek().is_none().then_some(score)
+}
+
+/// Spawns the query text and result rows of new [`CommandPalette`]s.
+pub fn spawn_command_palette_children(
+    mut commands: Commands,
+    palettes: Query<(Entity, &CommandPalette), Added<CommandPalette>>,
+) {
+    let text_style = TextStyle {
+        font_size: FONT_SIZE,
+        color: Color::WHITE,
+        ..default()
+    };
+    for (entity, palette) in &palettes {
+        commands.entity(entity).with_children(|builder| {
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Stretch,
+                        width: Val::Px(500.),
+                        padding: UiRect::all(Val::Px(8.)),
+                        row_gap: Val::Px(4.),
+                        ..default()
+                    },
+                    background_color: PANEL_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|builder| {
+                    builder.spawn((
+                        TextBundle::from_section("", text_style.clone()),
+                        CommandPaletteQuery,
+                    ));
+                    for row in 0..palette.max_results {
+                        builder.spawn((
+                            TextBundle::from_section("", text_style.clone()),
+                            Interaction::default(),
+                            CommandPaletteRow(row),
+                        ));
+                    }
+                });
+        });
+    }
+}
+
+/// Edits the query and moves the selection of open [`CommandPalette`]s with keyboard input and clicks on their results.
+///
+/// Open palettes capture the keyboard with [`UiKeyboardCapture`], and closed or despawned palettes release it.
+/// A palette only reads the keys while it is the [`UiKeyboardCapture::owner`].
+#[allow(clippy::too_many_arguments)]
+pub fn command_palette_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut keyboard_capture: ResMut<UiKeyboardCapture>,
+    mut selections: EventWriter<CommandPaletteSelection>,
+    registry: Res<CommandRegistry>,
+    mut palettes: Query<(Entity, &mut CommandPalette)>,
+    mut removed_palettes: RemovedComponents<CommandPalette>,
+    rows: Query<(&Interaction, &CommandPaletteRow, &Parent), Changed<Interaction>>,
+    parents: Query<&Parent>,
+) {
+    let events = keyboard_input
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for entity in removed_palettes.read() {
+        if keyboard_capture.has_captured(entity) {
+            keyboard_capture.release(entity);
+        }
+    }
+    // Palettes opened or closed by other systems since the last frame
+    update_keyboard_capture(&mut keyboard_capture, &palettes);
+
+    let mut choose = |entity: Entity, palette: &mut CommandPalette, index: usize| {
+        if let Some(command) = palette
+            .matches
+            .get(index)
+            .and_then(|&index| registry.commands.get(index))
+        {
+            selections.send(CommandPaletteSelection {
+                palette: entity,
+                id: command.id.clone(),
+            });
+            palette.close();
+        }
+    };
+
+    for (interaction, row, panel) in &rows {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok(palette_entity) = parents.get(panel.get()).map(Parent::get) else {
+            continue;
+        };
+        if let Ok((entity, mut palette)) = palettes.get_mut(palette_entity) {
+            if palette.open {
+                let index = palette.scroll + row.0;
+                choose(entity, &mut palette, index);
+            }
+        }
+    }
+
+    for (entity, mut palette) in &mut palettes {
+        if !palette.open || keyboard_capture.owner() != Some(entity) {
+            continue;
+        }
+        for event in &events {
+            match &event.logical_key {
+                Key::Escape => palette.close(),
+                Key::Enter => {
+                    let selected = palette.selected;
+                    choose(entity, &mut palette, selected);
+                }
+                Key::ArrowUp => {
+                    let selected = palette.selected.saturating_sub(1);
+                    palette.select(selected);
+                }
+                Key::ArrowDown => {
+                    let selected = palette.selected + 1;
+                    palette.select(selected);
+                }
+                Key::Backspace => {
+                    palette.query.pop();
+                    palette.select_first();
+                }
+                Key::Space => {
+                    palette.query.push(' ');
+                    palette.select_first();
+                }
+                Key::Character(characters) => {
+                    if !characters.chars().any(char::is_control) {
+                        palette.query.push_str(characters);
+                        palette.select_first();
+                    }
+                }
+                _ => continue,
+            }
+            if !palette.open {
+                break;
+            }
+        }
+    }
+
+    // Release the captures of the palettes closed this frame
+    update_keyboard_capture(&mut keyboard_capture, &palettes);
+}
+
+/// Captures the keyboard for open palettes and releases it for closed ones.
+fn update_keyboard_capture(
+    keyboard_capture: &mut ResMut<UiKeyboardCapture>,
+    palettes: &Query<(Entity, &mut CommandPalette)>,
+) {
+    for (entity, palette) in palettes {
+        if palette.open != keyboard_capture.has_captured(entity) {
+            if palette.open {
+                keyboard_capture.capture(entity);
+            } else {
+                keyboard_capture.release(entity);
+            }
+        }
+    }
+}
+
+/// Updates the matches, the displayed query and the result rows of changed [`CommandPalette`]s.
+pub fn update_command_palettes(
+    registry: Res<CommandRegistry>,
+    mut palettes: Query<(&mut CommandPalette, &mut Style, &Children)>,
+    children_query: Query<&Children>,
+    mut query_texts: Query<&mut Text, (With<CommandPaletteQuery>, Without<CommandPaletteRow>)>,
+    mut rows: Query<
+        (
+            &CommandPaletteRow,
+            &mut Text,
+            &mut Style,
+            &mut BackgroundColor,
+        ),
+        Without<CommandPalette>,
+    >,
+) {
+    for (mut palette, mut style, children) in &mut palettes {
+        if !palette.is_changed() && !registry.is_changed() {
+            continue;
+        }
+        let display = if palette.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+        if !palette.open {
+            continue;
+        }
+
+        let mut matches = registry
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                fuzzy_match(&palette.query, &command.label).map(|score| (index, score))
+            })
+            .collect::<Vec<_>>();
+        // The sort is stable so commands with equal scores stay in registration order
+        matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        // The matches and the clamped selection are derived from the query and the registry, so they are updated
+        // without marking the palette as changed, which would update it again next frame
+        let palette = palette.bypass_change_detection();
+        palette.matches = matches.into_iter().map(|(index, _)| index).collect();
+        palette.select(palette.selected);
+
+        for panel_child in children
+            .iter()
+            .filter_map(|&child| children_query.get(child).ok())
+            .flatten()
+        {
+            if let Ok(mut text) = query_texts.get_mut(*panel_child) {
+                text.sections[0].value = format!("> {}", palette.query);
+            }
+            let Ok((row, mut text, mut style, mut background_color)) = rows.get_mut(*panel_child)
+            else {
+                continue;
+            };
+            let index = palette.scroll + row.0;
+            match palette
+                .matches
+                .get(index)
+                .and_then(|&index| registry.commands.get(index))
+            {
+                Some(command) => {
+                    style.display = Display::Flex;
+                    text.sections[0].value = command.label.to_string();
+                    background_color.0 = if index == palette.selected {
+                        SELECTED_COLOR
+                    } else {
+                        Color::NONE
+                    };
+                }
+                None => style.display = Display::None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_input::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        ButtonState,
+    };
+
+    use super::{
+        command_palette_input, fuzzy_match, update_command_palettes, CommandPalette,
+        CommandPaletteSelection, CommandRegistry,
+    };
+    use crate::{Style, UiKeyboardCapture};
+
+    #[test]
+    fn fuzzy_match_requires_all_query_characters_in_order() {
+        assert!(fuzzy_match("", "Open File").is_some());
+        assert!(fuzzy_match("opf", "Open File").is_some());
+        assert!(fuzzy_match("OPEN", "open file").is_some());
+        assert!(fuzzy_match("fo", "Open File").is_none());
+        assert!(fuzzy_match("open files", "Open File").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_word_starts_and_consecutive_matches() {
+        let word_starts = fuzzy_match("of", "Open File").unwrap();
+        let inside_words = fuzzy_match("of", "Proof").unwrap();
+        assert!(word_starts > inside_words);
+
+        let consecutive = fuzzy_match("save", "Save All").unwrap();
+        let scattered = fuzzy_match("save", "Show Unsaved Changes").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn open_palettes_capture_the_keyboard() {
+        let mut world = World::new();
+        world.init_resource::<CommandRegistry>();
+        world.init_resource::<UiKeyboardCapture>();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<Events<CommandPaletteSelection>>();
+        let mut palette = CommandPalette::default();
+        palette.open();
+        let palette = world.spawn(palette).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(command_palette_input);
+        let mut press = |world: &mut World, key_code: KeyCode, logical_key: Key| {
+            world.send_event(KeyboardInput {
+                key_code,
+                logical_key,
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            });
+            schedule.run(world);
+        };
+
+        press(&mut world, KeyCode::KeyA, Key::Character("a".into()));
+        assert_eq!(world.get::<CommandPalette>(palette).unwrap().query(), "a");
+        assert_eq!(world.resource::<UiKeyboardCapture>().owner(), Some(palette));
+        // The keyboard input is left for the rest of the app
+        assert!(!world.resource::<Events<KeyboardInput>>().is_empty());
+
+        // Closing the palette releases the keyboard in the same frame
+        press(&mut world, KeyCode::Escape, Key::Escape);
+        assert!(!world.get::<CommandPalette>(palette).unwrap().is_open());
+        assert!(!world.resource::<UiKeyboardCapture>().is_captured());
+
+        world.get_mut::<CommandPalette>(palette).unwrap().open();
+        schedule.run(&mut world);
+        assert!(world.resource::<UiKeyboardCapture>().is_captured());
+        world.despawn(palette);
+        schedule.run(&mut world);
+        assert!(!world.resource::<UiKeyboardCapture>().is_captured());
+    }
+
+    #[test]
+    fn updating_matches_does_not_change_palettes() {
+        #[derive(Resource, Default)]
+        struct ChangedCount(usize);
+
+        let mut world = World::new();
+        world.init_resource::<ChangedCount>();
+        let mut registry = CommandRegistry::default();
+        registry.register("open", "Open File");
+        world.insert_resource(registry);
+        let mut palette = CommandPalette::default();
+        palette.open();
+        world
+            .spawn((palette, Style::default()))
+            .with_children(|builder| {
+                builder.spawn_empty();
+            });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                update_command_palettes,
+                |palettes: Query<(), Changed<CommandPalette>>, mut count: ResMut<ChangedCount>| {
+                    count.0 += palettes.iter().count();
+                },
+            )
+                .chain(),
+        );
+        for _ in 0..3 {
+            schedule.run(&mut world);
+        }
+        // Only the spawn is detected
+        assert_eq!(world.resource::<ChangedCount>().0, 1);
+    }
+}
@@ -1,13 +1,16 @@
 use crate::{
-    measurement::AvailableSpace, ContentSize, Measure, Node, NodeMeasure, UiImage, UiScale,
+    measurement::AvailableSpace, ContentSize, DefaultUiCamera, Measure, Node, NodeMeasure,
+    TargetCamera, UiImage, UiRootScale, UiScale,
 };
 use bevy_asset::Assets;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::EntityHashSet, prelude::*};
 use bevy_math::{UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::texture::Image;
+use bevy_render::{camera::Camera, texture::Image};
 use bevy_sprite::{TextureAtlas, TextureAtlasLayout};
-use bevy_window::{PrimaryWindow, Window};
+use bevy_time::Time;
+use bevy_utils::HashMap;
+use std::ops::RangeInclusive;
 use taffy::{MaybeMath, MaybeResolve};
 
 /// The size of the image's texture
@@ -94,47 +97,274 @@ type UpdateImageFilter = (With<Node>, Without<bevy_text::Text>);
 type UpdateImageFilter = With<Node>;
 
 /// Updates content size of the node based on the image provided
+///
+/// Images are measured in the physical pixels of each node's target camera, scaled by the node's [`UiRootScale`].
+#[allow(clippy::too_many_arguments)]
 pub fn update_image_content_size_system(
-    mut previous_combined_scale_factor: Local<f32>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    mut previous_combined_scale_factors: Local<HashMap<Option<Entity>, f32>>,
+    camera_query: Query<(Entity, &Camera)>,
+    default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
     textures: Res<Assets<Image>>,
 
     atlases: Res<Assets<TextureAtlasLayout>>,
     mut query: Query<
         (
+            Entity,
             &mut ContentSize,
             &UiImage,
             &mut UiImageSize,
             Option<&TextureAtlas>,
+            Option<&TargetCamera>,
+            Option<Ref<UiRootScale>>,
         ),
         UpdateImageFilter,
     >,
+    mut removed_root_scales: RemovedComponents<UiRootScale>,
 ) {
-    let combined_scale_factor = windows
-        .get_single()
-        .map(|window| window.resolution.scale_factor())
-        .unwrap_or(1.)
-        * ui_scale.0;
+    // Nodes without a camera are measured using `UiScale` alone.
+    let combined_scale_factors: HashMap<Option<Entity>, f32> = camera_query
+        .iter()
+        .map(|(entity, camera)| {
+            (
+                Some(entity),
+                camera.target_scaling_factor().unwrap_or(1.) * ui_scale.0,
+            )
+        })
+        .chain([(None, ui_scale.0)])
+        .collect();
+    let default_camera = default_ui_camera.get();
+    let removed_root_scales: EntityHashSet = removed_root_scales.read().collect();
+
+    for (entity, mut content_size, image, mut image_size, atlas_image, target_camera, root_scale) in
+        &mut query
+    {
+        let camera_entity = target_camera.map(TargetCamera::entity).or(default_camera);
+        let combined_scale_factor = combined_scale_factors
+            .get(&camera_entity)
+            .copied()
+            .unwrap_or(ui_scale.0);
+        let previous_combined_scale_factor =
+            previous_combined_scale_factors.get(&camera_entity).copied();
 
-    for (mut content_size, image, mut image_size, atlas_image) in &mut query {
         if let Some(size) = match atlas_image {
             Some(atlas) => atlas.texture_rect(&atlases).map(|t| t.size()),
             None => textures.get(&image.texture).map(|t| t.size()),
         } {
             // Update only if size or scale factor has changed to avoid needless layout calculations
             if size != image_size.size
-                || combined_scale_factor != *previous_combined_scale_factor
+                || Some(combined_scale_factor) != previous_combined_scale_factor
                 || content_size.is_added()
+                || root_scale.as_ref().is_some_and(DetectChanges::is_changed)
+                || removed_root_scales.contains(&entity)
             {
                 image_size.size = size;
                 content_size.set(NodeMeasure::Image(ImageMeasure {
                     // multiply the image size by the scale factor to get the physical size
-                    size: size.as_vec2() * combined_scale_factor,
+                    size: size.as_vec2()
+                        * combined_scale_factor
+                        * root_scale.map_or(1., |root_scale| root_scale.0),
                 }));
             }
         }
     }
 
-    *previous_combined_scale_factor = combined_scale_factor;
+    *previous_combined_scale_factors = combined_scale_factors;
+}
+
+/// Animates a UI image by cycling the index of its [`TextureAtlas`] through a sequence of frames.
+///
+/// [`ui_atlas_animation_system`] advances the animation each frame, and only writes the atlas index when the
+/// displayed frame changes.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_sprite::{TextureAtlas, TextureAtlasLayout};
+/// # use bevy_ui::{prelude::*, widget::UiAtlasAnimation};
+/// fn spawn_spinner(mut commands: Commands, layout: Handle<TextureAtlasLayout>) {
+///     commands.spawn((
+///         ImageBundle::default(),
+///         TextureAtlas { layout, index: 0 },
+///         UiAtlasAnimation::from_range(0..=7, 12.),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct UiAtlasAnimation {
+    /// The atlas indices of the frames, in the order they are shown.
+    pub frames: Vec<usize>,
+    /// The number of frames shown per second.
+    pub fps: f32,
+    /// Whether the animation restarts from its first frame after its last, instead of stopping on it.
+    pub looping: bool,
+    /// Whether the animation is paused on its current frame.
+    pub paused: bool,
+    /// The position of the displayed frame in `frames`.
+    frame: usize,
+    /// The time the current frame has been displayed for, in seconds.
+    elapsed: f32,
+}
+
+impl Default for UiAtlasAnimation {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            fps: 12.,
+            looping: true,
+            paused: false,
+            frame: 0,
+            elapsed: 0.,
+        }
+    }
+}
+
+impl UiAtlasAnimation {
+    /// A looping animation showing the atlas indices `frames` in order, at `fps` frames per second.
+    pub fn new(frames: impl Into<Vec<usize>>, fps: f32) -> Self {
+        Self {
+            frames: frames.into(),
+            fps,
+            ..Default::default()
+        }
+    }
+
+    /// A looping animation showing each atlas index in `range` in order, at `fps` frames per second.
+    pub fn from_range(range: RangeInclusive<usize>, fps: f32) -> Self {
+        Self::new(range.collect::<Vec<_>>(), fps)
+    }
+
+    /// Sets whether the animation restarts from its first frame after its last.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Returns the atlas index of the displayed frame, or `None` if the animation has no frames.
+    pub fn current_index(&self) -> Option<usize> {
+        self.frames.get(self.frame).copied()
+    }
+
+    /// Returns `true` if the animation doesn't loop and is displaying its last frame.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.frame + 1 >= self.frames.len()
+    }
+
+    /// Restarts the animation from its first frame.
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.elapsed = 0.;
+    }
+
+    /// Advances the animation by `delta` seconds.
+    fn advance(&mut self, delta: f32) {
+        let len = self.frames.len();
+        if len == 0 || self.paused || self.fps <= 0. {
+            return;
+        }
+        // The frames may have been replaced with fewer frames
+        self.frame = self.frame.min(len - 1);
+        if self.is_finished() {
+            return;
+        }
+
+        self.elapsed += delta;
+        let frame_duration = self.fps.recip();
+        let steps = (self.elapsed / frame_duration).floor();
+        self.elapsed -= steps * frame_duration;
+        let frame = self.frame + steps as usize;
+        self.frame = if self.looping {
+            frame % len
+        } else {
+            frame.min(len - 1)
+        };
+    }
+}
+
+/// Advances the [`UiAtlasAnimation`]s of UI images, updating the index of their [`TextureAtlas`].
+pub fn ui_atlas_animation_system(
+    time: Res<Time>,
+    mut query: Query<(&mut UiAtlasAnimation, &mut TextureAtlas)>,
+) {
+    let delta = time.delta_seconds();
+    for (mut animation, mut atlas) in &mut query {
+        // Only the atlas index is written, and only when it changes, to avoid triggering change detection
+        // every frame.
+        let animation = animation.bypass_change_detection();
+        animation.advance(delta);
+        if let Some(index) = animation.current_index() {
+            if atlas.index != index {
+                atlas.index = index;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Handle;
+    use bevy_ecs::prelude::*;
+    use bevy_sprite::TextureAtlas;
+    use bevy_time::Time;
+    use bevy_utils::Duration;
+
+    use super::{ui_atlas_animation_system, UiAtlasAnimation};
+
+    #[test]
+    fn atlas_animation_advances_frames() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let looping = world
+            .spawn((
+                TextureAtlas {
+                    layout: Handle::default(),
+                    index: 0,
+                },
+                UiAtlasAnimation::new([3, 4, 5], 10.),
+            ))
+            .id();
+        let once = world
+            .spawn((
+                TextureAtlas {
+                    layout: Handle::default(),
+                    index: 0,
+                },
+                UiAtlasAnimation::from_range(0..=2, 10.).with_looping(false),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_atlas_animation_system);
+        let mut advance = |world: &mut World, millis: u64| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            schedule.run(world);
+        };
+        let index = |world: &World, entity| world.get::<TextureAtlas>(entity).unwrap().index;
+
+        // The first frame is displayed immediately
+        advance(&mut world, 0);
+        assert_eq!(index(&world, looping), 3);
+        assert_eq!(index(&world, once), 0);
+
+        advance(&mut world, 150);
+        assert_eq!(index(&world, looping), 4);
+        assert_eq!(index(&world, once), 1);
+
+        // Looping animations wrap around, others stop on their last frame
+        advance(&mut world, 200);
+        assert_eq!(index(&world, looping), 3);
+        assert_eq!(index(&world, once), 2);
+        assert!(world.get::<UiAtlasAnimation>(once).unwrap().is_finished());
+
+        advance(&mut world, 100);
+        assert_eq!(index(&world, looping), 4);
+        assert_eq!(index(&world, once), 2);
+
+        world.get_mut::<UiAtlasAnimation>(looping).unwrap().paused = true;
+        advance(&mut world, 500);
+        assert_eq!(index(&world, looping), 4);
+    }
 }
@@ -1,13 +1,35 @@
 //! This module contains the basic building blocks of Bevy's UI
 
 mod button;
+mod checkbox;
+#[cfg(feature = "bevy_text")]
+mod command_palette;
 mod image;
 mod label;
+mod progress_bar;
+mod radio;
+mod scrollbar;
+mod slider;
+mod tab_view;
 #[cfg(feature = "bevy_text")]
 mod text;
+#[cfg(feature = "bevy_text")]
+mod text_input;
+mod tooltip;
 
 pub use button::*;
+pub use checkbox::*;
+#[cfg(feature = "bevy_text")]
+pub use command_palette::*;
 pub use image::*;
 pub use label::*;
+pub use progress_bar::*;
+pub use radio::*;
+pub use scrollbar::*;
+pub use slider::*;
+pub use tab_view::*;
 #[cfg(feature = "bevy_text")]
 pub use text::*;
+#[cfg(feature = "bevy_text")]
+pub use text_input::*;
+pub use tooltip::*;
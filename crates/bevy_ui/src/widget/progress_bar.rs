@@ -0,0 +1,117 @@
+use crate::{node_bundles::NodeBundle, BackgroundColor, PositionType, Style, Val};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_hierarchy::{BuildChildren, Children};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::default;
+
+/// A horizontal bar filled in proportion to the progress of a task.
+///
+/// The progress bar node itself is the background of the bar. The filled part is displayed by a child node with a
+/// [`BackgroundColor`], spawned when the [`ProgressBar`] is added. A [`ProgressBarChanged`] event is sent
+/// whenever the fill is updated for a new progress.
+///
+/// See [`ProgressBarBundle`](crate::node_bundles::ProgressBarBundle).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ProgressBar {
+    progress: f32,
+    /// The color of the filled part of the bar.
+    pub fill_color: Color,
+}
+
+impl ProgressBar {
+    /// Creates a [`ProgressBar`] with the given progress.
+    pub fn new(progress: f32) -> Self {
+        Self {
+            progress: progress.clamp(0., 1.),
+            ..default()
+        }
+    }
+
+    /// The progress, from `0.` for an empty bar to `1.` for a full one.
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Sets the progress, clamped between `0.` and `1.`.
+    pub fn set_progress(&mut self, progress: f32) {
+        self.progress = progress.clamp(0., 1.);
+    }
+
+    /// Returns `true` if the bar is full.
+    pub fn is_complete(&self) -> bool {
+        1. <= self.progress
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            progress: 0.,
+            fill_color: Color::srgb(0.35, 0.75, 0.35),
+        }
+    }
+}
+
+/// Sent when the progress of a [`ProgressBar`] changes.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ProgressBarChanged {
+    /// The progress bar entity.
+    pub entity: Entity,
+    /// The new progress of the bar.
+    pub progress: f32,
+}
+
+/// Marker for the node displaying the filled part of a [`ProgressBar`].
+#[derive(Component)]
+pub struct ProgressBarFill;
+
+/// Spawns the fill nodes of new [`ProgressBar`]s.
+pub fn spawn_progress_bar_children(
+    mut commands: Commands,
+    progress_bars: Query<(Entity, &ProgressBar), Added<ProgressBar>>,
+) {
+    for (entity, progress_bar) in &progress_bars {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::ZERO,
+                        top: Val::ZERO,
+                        bottom: Val::ZERO,
+                        width: Val::Percent(100. * progress_bar.progress),
+                        ..default()
+                    },
+                    background_color: progress_bar.fill_color.into(),
+                    ..default()
+                },
+                ProgressBarFill,
+            ));
+        });
+    }
+}
+
+/// Resizes the fill nodes of changed [`ProgressBar`]s and sends [`ProgressBarChanged`] events.
+pub fn update_progress_bar_children(
+    progress_bars: Query<(Entity, &ProgressBar, &Children), Changed<ProgressBar>>,
+    mut fills: Query<(&mut Style, &mut BackgroundColor), With<ProgressBarFill>>,
+    mut changed_events: EventWriter<ProgressBarChanged>,
+) {
+    for (entity, progress_bar, children) in &progress_bars {
+        let width = Val::Percent(100. * progress_bar.progress);
+        let mut iter = fills.iter_many_mut(children);
+        while let Some((mut style, mut background_color)) = iter.fetch_next() {
+            if style.width != width {
+                style.width = width;
+                changed_events.send(ProgressBarChanged {
+                    entity,
+                    progress: progress_bar.progress,
+                });
+            }
+            background_color.0 = progress_bar.fill_color;
+        }
+    }
+}
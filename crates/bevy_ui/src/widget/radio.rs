@@ -0,0 +1,224 @@
+use crate::{
+    node_bundles::NodeBundle, widget::ButtonPressed, BackgroundColor, BorderRadius, Display,
+    PositionType, Style, Val,
+};
+use bevy_color::Color;
+use bevy_ecs::entity::{EntityMapper, MapEntities};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
+use bevy_hierarchy::{BuildChildren, Children, HierarchyQueryExt, Parent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::default;
+
+/// A group of [`RadioButton`]s, only one of which can be selected at a time.
+///
+/// The radio buttons of a group are its descendants. Groups shouldn't be nested.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq, MapEntities)]
+pub struct RadioGroup {
+    /// The selected radio button, if any.
+    pub selected: Option<Entity>,
+}
+
+impl MapEntities for RadioGroup {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let Some(entity) = &mut self.selected {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+impl RadioGroup {
+    /// Creates a [`RadioGroup`] with `selected` selected.
+    pub const fn new(selected: Entity) -> Self {
+        Self {
+            selected: Some(selected),
+        }
+    }
+}
+
+/// A radio button in a [`RadioGroup`].
+///
+/// The radio button is selected when its [`Button`](crate::widget::Button) is pressed, which sends a
+/// [`SelectionChanged`] event for its group. [`Disabled`](crate::Disabled) radio buttons can't be selected.
+///
+/// The mark is displayed by a child node, spawned when the [`RadioButton`] is added.
+///
+/// See [`RadioButtonBundle`](crate::node_bundles::RadioButtonBundle).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct RadioButton {
+    /// Whether the radio button is selected, updated from its [`RadioGroup`].
+    pub selected: bool,
+    /// The color of the mark.
+    pub mark_color: Color,
+}
+
+impl Default for RadioButton {
+    fn default() -> Self {
+        Self {
+            selected: false,
+            mark_color: Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// Sent when the selection of a [`RadioGroup`] or [`TabView`](crate::widget::TabView) is changed by pressing one of
+/// its buttons.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelectionChanged {
+    /// The radio group or tab view entity.
+    pub entity: Entity,
+    /// The selected radio button or tab.
+    pub selected: Entity,
+}
+
+/// Marker for the node displaying the mark of a [`RadioButton`].
+#[derive(Component)]
+pub struct RadioButtonMark;
+
+/// Spawns the mark nodes of new [`RadioButton`]s.
+pub fn spawn_radio_button_children(
+    mut commands: Commands,
+    radio_buttons: Query<Entity, Added<RadioButton>>,
+) {
+    for entity in &radio_buttons {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(25.),
+                        top: Val::Percent(25.),
+                        width: Val::Percent(50.),
+                        height: Val::Percent(50.),
+                        ..default()
+                    },
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                RadioButtonMark,
+            ));
+        });
+    }
+}
+
+/// Selects [`RadioButton`]s in their [`RadioGroup`] when their button is pressed and sends [`SelectionChanged`]
+/// events.
+pub fn radio_button_select_system(
+    mut pressed_events: EventReader<ButtonPressed>,
+    radio_buttons: Query<(), With<RadioButton>>,
+    parents: Query<&Parent>,
+    mut groups: Query<&mut RadioGroup>,
+    mut changed_events: EventWriter<SelectionChanged>,
+) {
+    for &ButtonPressed(entity) in pressed_events.read() {
+        if !radio_buttons.contains(entity) {
+            continue;
+        }
+        let Some(group_entity) = parents
+            .iter_ancestors(entity)
+            .find(|ancestor| groups.contains(*ancestor))
+        else {
+            continue;
+        };
+        let Ok(mut group) = groups.get_mut(group_entity) else {
+            continue;
+        };
+        if group.selected != Some(entity) {
+            group.selected = Some(entity);
+            changed_events.send(SelectionChanged {
+                entity: group_entity,
+                selected: entity,
+            });
+        }
+    }
+}
+
+/// Updates the [`RadioButton`]s of changed [`RadioGroup`]s.
+pub fn update_radio_buttons(
+    groups: Query<(Entity, &RadioGroup), Changed<RadioGroup>>,
+    children: Query<&Children>,
+    mut radio_buttons: Query<&mut RadioButton>,
+) {
+    for (group_entity, group) in &groups {
+        for entity in children.iter_descendants(group_entity) {
+            if let Ok(mut radio_button) = radio_buttons.get_mut(entity) {
+                radio_button.selected = group.selected == Some(entity);
+            }
+        }
+    }
+}
+
+/// Displays the state of changed [`RadioButton`]s with their mark node.
+pub fn update_radio_button_children(
+    radio_buttons: Query<(&RadioButton, &Children), Changed<RadioButton>>,
+    mut marks: Query<(&mut Style, &mut BackgroundColor), With<RadioButtonMark>>,
+) {
+    for (radio_button, children) in &radio_buttons {
+        let mut iter = marks.iter_many_mut(children);
+        while let Some((mut style, mut background_color)) = iter.fetch_next() {
+            background_color.0 = radio_button.mark_color;
+            style.display = if radio_button.selected {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        radio_button_select_system, update_radio_buttons, RadioButton, RadioGroup, SelectionChanged,
+    };
+    use crate::widget::ButtonPressed;
+    use bevy_ecs::{event::Events, schedule::IntoSystemConfigs, schedule::Schedule, world::World};
+    use bevy_hierarchy::BuildWorldChildren;
+
+    #[test]
+    fn radio_buttons_are_exclusive() {
+        let mut world = World::new();
+        world.init_resource::<Events<ButtonPressed>>();
+        world.init_resource::<Events<SelectionChanged>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((radio_button_select_system, update_radio_buttons).chain());
+
+        let group = world.spawn(RadioGroup::default()).id();
+        let mut buttons = Vec::new();
+        world.entity_mut(group).with_children(|parent| {
+            buttons.extend((0..3).map(|_| parent.spawn(RadioButton::default()).id()));
+        });
+        let selected = |world: &World| {
+            buttons
+                .iter()
+                .map(|button| world.get::<RadioButton>(*button).unwrap().selected)
+                .collect::<Vec<_>>()
+        };
+
+        world.send_event(ButtonPressed(buttons[1]));
+        schedule.run(&mut world);
+        assert_eq!(selected(&world), [false, true, false]);
+        assert_eq!(
+            world.get::<RadioGroup>(group).unwrap().selected,
+            Some(buttons[1])
+        );
+
+        // Pressing the selected button again doesn't change the selection
+        world.send_event(ButtonPressed(buttons[2]));
+        world.send_event(ButtonPressed(buttons[2]));
+        schedule.run(&mut world);
+        assert_eq!(selected(&world), [false, false, true]);
+        assert_eq!(
+            world
+                .resource_mut::<Events<SelectionChanged>>()
+                .drain()
+                .collect::<Vec<_>>(),
+            [buttons[1], buttons[2]].map(|selected| SelectionChanged {
+                entity: group,
+                selected
+            })
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use crate::{
+    node_bundles::NodeBundle, BackgroundColor, Disabled, Interaction, Node, PositionType,
+    RelativeCursorPosition, ScrollPosition, Style, Val,
+};
+use bevy_color::Color;
+use bevy_ecs::entity::EntityHashMap;
+use bevy_ecs::entity::{EntityMapper, MapEntities};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
+use bevy_hierarchy::{BuildChildren, Children};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::view::Visibility;
+use bevy_time::Time;
+use bevy_utils::default;
+
+/// The axis along which a [`Scrollbar`] scrolls its target.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum ScrollbarAxis {
+    /// The scrollbar is horizontal and scrolls the target's content left and right.
+    Horizontal,
+    /// The scrollbar is vertical and scrolls the target's content up and down.
+    #[default]
+    Vertical,
+}
+
+impl ScrollbarAxis {
+    /// Returns the component of `vec` along this axis.
+    fn get(self, vec: Vec2) -> f32 {
+        match self {
+            ScrollbarAxis::Horizontal => vec.x,
+            ScrollbarAxis::Vertical => vec.y,
+        }
+    }
+}
+
+/// A scrollbar displaying and controlling the [`ScrollPosition`] of a target scroll container.
+///
+/// The scrollbar node itself is the track, and the thumb is displayed by a child node spawned when the
+/// [`Scrollbar`] is added. The length of the thumb is proportional to the visible part of the target's content.
+/// Pressing the thumb and dragging it scrolls the target, pressing the track outside of the thumb first centers
+/// the thumb on the cursor.
+///
+/// The scrollbar is hidden while all of the target's content is visible, and if [`Scrollbar::auto_hide`] is set,
+/// while the target hasn't been scrolled and the scrollbar hasn't been interacted with for that many seconds.
+///
+/// See [`ScrollbarBundle`](crate::node_bundles::ScrollbarBundle).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq, MapEntities)]
+pub struct Scrollbar {
+    /// The scroll container controlled by the scrollbar, a node with a [`ScrollPosition`].
+    pub target: Entity,
+    /// The axis along which the target is scrolled.
+    pub axis: ScrollbarAxis,
+    /// The minimum length of the thumb, in logical pixels.
+    pub min_thumb_length: f32,
+    /// The color of the thumb.
+    pub thumb_color: Color,
+    /// The number of seconds of inactivity after which the scrollbar is hidden, or `None` to keep it visible.
+    pub auto_hide: Option<f32>,
+}
+
+impl MapEntities for Scrollbar {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = entity_mapper.map_entity(self.target);
+    }
+}
+
+impl Scrollbar {
+    /// Creates a [`Scrollbar`] scrolling `target` along `axis`.
+    pub fn new(target: Entity, axis: ScrollbarAxis) -> Self {
+        Self {
+            target,
+            axis,
+            ..default()
+        }
+    }
+
+    /// Returns this [`Scrollbar`] hidden after `seconds` of inactivity.
+    pub fn with_auto_hide(mut self, seconds: f32) -> Self {
+        self.auto_hide = Some(seconds);
+        self
+    }
+}
+
+impl Default for Scrollbar {
+    /// Returns a vertical [`Scrollbar`] with an [`Entity::PLACEHOLDER`] target.
+    fn default() -> Self {
+        Self {
+            target: Entity::PLACEHOLDER,
+            axis: ScrollbarAxis::Vertical,
+            min_thumb_length: 16.,
+            thumb_color: Color::srgb(0.6, 0.6, 0.6),
+            auto_hide: None,
+        }
+    }
+}
+
+/// Marker for the node displaying the thumb of a [`Scrollbar`].
+#[derive(Component)]
+pub struct ScrollbarThumb;
+
+/// The position of the thumb of a scrollbar as fractions of the length of its track.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ThumbGeometry {
+    start: f32,
+    length: f32,
+}
+
+impl ThumbGeometry {
+    /// Computes the thumb of a scrollbar with a track of `track_length` for a container of `container_length`,
+    /// with content of `content_length` scrolled by `offset`.
+    ///
+    /// Returns `None` if all of the content is visible.
+    fn new(
+        container_length: f32,
+        content_length: f32,
+        offset: f32,
+        track_length: f32,
+        min_thumb_length: f32,
+    ) -> Option<Self> {
+        let max_offset = content_length - container_length;
+        if max_offset <= 0. || track_length <= 0. {
+            return None;
+        }
+        let length = (container_length / content_length)
+            .max(min_thumb_length / track_length)
+            .min(1.);
+        let start = (offset / max_offset).clamp(0., 1.) * (1. - length);
+        Some(Self { start, length })
+    }
+
+    /// Returns the offset scrolling the content so that the thumb starts at `start`.
+    fn offset_at(&self, start: f32, container_length: f32, content_length: f32) -> f32 {
+        let travel = 1. - self.length;
+        if travel <= 0. {
+            return 0.;
+        }
+        (start / travel).clamp(0., 1.) * (content_length - container_length)
+    }
+}
+
+/// Spawns the thumb nodes of new [`Scrollbar`]s.
+pub fn spawn_scrollbar_children(
+    mut commands: Commands,
+    scrollbars: Query<(Entity, &Scrollbar), Added<Scrollbar>>,
+) {
+    for (entity, scrollbar) in &scrollbars {
+        let style = match scrollbar.axis {
+            ScrollbarAxis::Horizontal => Style {
+                position_type: PositionType::Absolute,
+                top: Val::ZERO,
+                bottom: Val::ZERO,
+                ..default()
+            },
+            ScrollbarAxis::Vertical => Style {
+                position_type: PositionType::Absolute,
+                left: Val::ZERO,
+                right: Val::ZERO,
+                ..default()
+            },
+        };
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                NodeBundle {
+                    style,
+                    background_color: scrollbar.thumb_color.into(),
+                    ..default()
+                },
+                ScrollbarThumb,
+            ));
+        });
+    }
+}
+
+/// Scrolls the targets of pressed [`Scrollbar`]s as their thumb is dragged.
+///
+/// [`Disabled`] scrollbars are ignored.
+pub fn scrollbar_drag_system(
+    mut drags: Local<EntityHashMap<f32>>,
+    scrollbars: Query<
+        (
+            Entity,
+            &Scrollbar,
+            &Node,
+            &Interaction,
+            &RelativeCursorPosition,
+        ),
+        Without<Disabled>,
+    >,
+    mut targets: Query<(&Node, &mut ScrollPosition)>,
+) {
+    drags.retain(|entity, _| {
+        scrollbars
+            .get(*entity)
+            .is_ok_and(|(.., interaction, _)| *interaction == Interaction::Pressed)
+    });
+
+    for (entity, scrollbar, track, interaction, relative_cursor_position) in &scrollbars {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(cursor) = relative_cursor_position.normalized else {
+            continue;
+        };
+        let Ok((container, mut scroll_position)) = targets.get_mut(scrollbar.target) else {
+            continue;
+        };
+        let axis = scrollbar.axis;
+        let container_length = axis.get(container.size());
+        let content_length = axis.get(container.content_size());
+        let offset = axis.get(scroll_position.offset());
+        let Some(thumb) = ThumbGeometry::new(
+            container_length,
+            content_length,
+            offset,
+            axis.get(track.size()),
+            scrollbar.min_thumb_length,
+        ) else {
+            continue;
+        };
+
+        let cursor = axis.get(cursor);
+        // The position of the cursor on the thumb when the drag started
+        let grab = *drags.entry(entity).or_insert_with(|| {
+            if (thumb.start..=thumb.start + thumb.length).contains(&cursor) {
+                cursor - thumb.start
+            } else {
+                0.5 * thumb.length
+            }
+        });
+        let new_offset = thumb.offset_at(cursor - grab, container_length, content_length);
+        if new_offset != offset {
+            match axis {
+                ScrollbarAxis::Horizontal => scroll_position.offset_x = new_offset,
+                ScrollbarAxis::Vertical => scroll_position.offset_y = new_offset,
+            }
+        }
+    }
+}
+
+/// Sizes and positions the thumbs of [`Scrollbar`]s for the scroll position of their targets, and hides the
+/// scrollbars with nothing to scroll or that are inactive.
+#[allow(clippy::type_complexity)]
+pub fn update_scrollbar_thumbs(
+    mut last_active: Local<EntityHashMap<f32>>,
+    time: Res<Time>,
+    mut scrollbars: Query<(
+        Entity,
+        &Scrollbar,
+        &Node,
+        Option<&Interaction>,
+        &Children,
+        &mut Visibility,
+    )>,
+    targets: Query<(&Node, Ref<ScrollPosition>)>,
+    mut thumbs: Query<(&mut Style, &mut BackgroundColor), With<ScrollbarThumb>>,
+) {
+    let now = time.elapsed_seconds();
+    last_active.retain(|entity, _| scrollbars.contains(*entity));
+
+    for (entity, scrollbar, track, interaction, children, mut visibility) in &mut scrollbars {
+        let axis = scrollbar.axis;
+        let thumb = targets
+            .get(scrollbar.target)
+            .ok()
+            .and_then(|(container, scroll_position)| {
+                let thumb = ThumbGeometry::new(
+                    axis.get(container.size()),
+                    axis.get(container.content_size()),
+                    axis.get(scroll_position.offset()),
+                    axis.get(track.size()),
+                    scrollbar.min_thumb_length,
+                )?;
+                Some((thumb, scroll_position.is_changed()))
+            });
+
+        let mut visible = thumb.is_some();
+        if let (Some(delay), Some((_, scrolled))) = (scrollbar.auto_hide, thumb) {
+            let active = scrolled
+                || interaction.is_some_and(|interaction| *interaction != Interaction::None);
+            let last_active = last_active.entry(entity).or_insert(now);
+            if active {
+                *last_active = now;
+            }
+            visible = now - *last_active <= delay;
+        }
+        let new_visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        visibility.set_if_neq(new_visibility);
+
+        let Some((thumb, _)) = thumb else {
+            continue;
+        };
+        let (start, length) = (
+            Val::Percent(100. * thumb.start),
+            Val::Percent(100. * thumb.length),
+        );
+        let mut iter = thumbs.iter_many_mut(children);
+        while let Some((mut style, mut background_color)) = iter.fetch_next() {
+            let (position, size) = match axis {
+                ScrollbarAxis::Horizontal => (style.left, style.width),
+                ScrollbarAxis::Vertical => (style.top, style.height),
+            };
+            if position != start || size != length {
+                match axis {
+                    ScrollbarAxis::Horizontal => {
+                        style.left = start;
+                        style.width = length;
+                    }
+                    ScrollbarAxis::Vertical => {
+                        style.top = start;
+                        style.height = length;
+                    }
+                }
+            }
+            if background_color.0 != scrollbar.thumb_color {
+                background_color.0 = scrollbar.thumb_color;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ThumbGeometry;
+
+    #[test]
+    fn scrollbar_thumb_geometry() {
+        // All of the content is visible
+        assert_eq!(ThumbGeometry::new(100., 100., 0., 100., 10.), None);
+
+        // A quarter of the content is visible, scrolled halfway
+        let thumb = ThumbGeometry::new(100., 400., 150., 200., 10.).unwrap();
+        assert_eq!(
+            thumb,
+            ThumbGeometry {
+                start: 0.375,
+                length: 0.25
+            }
+        );
+        assert_eq!(thumb.offset_at(0.375, 100., 400.), 150.);
+        // Dragging past the end of the track scrolls to the end of the content
+        assert_eq!(thumb.offset_at(0.9, 100., 400.), 300.);
+
+        // The thumb is never shorter than its minimum length
+        let thumb = ThumbGeometry::new(100., 10000., 9900., 200., 20.).unwrap();
+        assert_eq!(
+            thumb,
+            ThumbGeometry {
+                start: 0.9,
+                length: 0.1
+            }
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    node_bundles::NodeBundle, BackgroundColor, Disabled, Interaction, PositionType,
+    RelativeCursorPosition, Style, UiRect, Val, ZIndex,
+};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_hierarchy::{BuildChildren, Children};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::default;
+
+/// The width of the handle of a [`Slider`], in logical pixels.
+const HANDLE_WIDTH: f32 = 12.;
+
+/// How far the handle of a [`Slider`] extends above and below its track, in logical pixels.
+const HANDLE_OVERHANG: f32 = 4.;
+
+/// A horizontal slider selecting a value in a range.
+///
+/// Pressing the node moves the value to the cursor, and it follows the cursor while the press is held.
+/// A [`SliderChanged`] event is sent whenever the value is changed this way.
+///
+/// The slider node itself is the track. The filled part of the track and the handle are displayed by child nodes,
+/// spawned when the [`Slider`] is added, which need the slider to have a [`RelativeCursorPosition`].
+///
+/// See [`SliderBundle`](crate::node_bundles::SliderBundle).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Slider {
+    value: f32,
+    min: f32,
+    max: f32,
+    /// The distance between selectable values, or zero if any value in the range can be selected.
+    step: f32,
+    /// The color of the part of the track before the handle.
+    pub fill_color: Color,
+    /// The color of the handle.
+    pub handle_color: Color,
+}
+
+impl Slider {
+    /// Creates a [`Slider`] selecting any value in `range`, starting at its start.
+    ///
+    /// A reversed range, like `10.0..=0.`, is treated as the same range in increasing order.
+    pub fn new(range: RangeInclusive<f32>) -> Self {
+        let (start, end) = range.into_inner();
+        let (min, max) = if end < start {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        Self {
+            value: min,
+            min,
+            max,
+            ..default()
+        }
+    }
+
+    /// Returns this [`Slider`] with values limited to multiples of `step` from the start of its range.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step.max(0.);
+        self.set_value(self.value);
+        self
+    }
+
+    /// Returns this [`Slider`] with the given value.
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// The selected value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// The range of selectable values.
+    pub fn range(&self) -> RangeInclusive<f32> {
+        self.min..=self.max
+    }
+
+    /// The distance between selectable values, or zero if any value in the range can be selected.
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Sets the value, clamped to the range of the slider and rounded to the nearest step.
+    ///
+    /// This doesn't send a [`SliderChanged`] event.
+    pub fn set_value(&mut self, value: f32) {
+        // Unlike `clamp`, this doesn't panic if the range was made invalid through reflection
+        let mut value = value.max(self.min).min(self.max);
+        if 0. < self.step {
+            value = self.min + ((value - self.min) / self.step).round() * self.step;
+            if self.max < value {
+                value -= self.step;
+            }
+        }
+        self.value = value;
+    }
+
+    /// The position of the value in the range, from `0.` at its start to `1.` at its end.
+    pub fn fraction(&self) -> f32 {
+        if self.max <= self.min {
+            0.
+        } else {
+            (self.value - self.min) / (self.max - self.min)
+        }
+    }
+
+    /// Sets the value to the position `fraction` in the range, from `0.` at its start to `1.` at its end.
+    pub fn set_fraction(&mut self, fraction: f32) {
+        self.set_value(self.min + fraction.clamp(0., 1.) * (self.max - self.min));
+    }
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Self {
+            value: 0.,
+            min: 0.,
+            max: 1.,
+            step: 0.,
+            fill_color: Color::srgb(0.35, 0.55, 0.85),
+            handle_color: Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// Sent when the value of a [`Slider`] is changed by pressing or dragging it.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct SliderChanged {
+    /// The slider entity.
+    pub entity: Entity,
+    /// The new value of the slider.
+    pub value: f32,
+}
+
+/// Marker for the node displaying the filled part of the track of a [`Slider`].
+#[derive(Component)]
+pub struct SliderFill;
+
+/// Marker for the node displaying the handle of a [`Slider`].
+#[derive(Component)]
+pub struct SliderHandle;
+
+/// Spawns the fill and handle nodes of new [`Slider`]s.
+pub fn spawn_slider_children(mut commands: Commands, sliders: Query<Entity, Added<Slider>>) {
+    for entity in &sliders {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::ZERO,
+                        top: Val::ZERO,
+                        bottom: Val::ZERO,
+                        ..default()
+                    },
+                    ..default()
+                },
+                SliderFill,
+            ));
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(HANDLE_WIDTH),
+                        top: Val::Px(-HANDLE_OVERHANG),
+                        bottom: Val::Px(-HANDLE_OVERHANG),
+                        margin: UiRect::left(Val::Px(-0.5 * HANDLE_WIDTH)),
+                        ..default()
+                    },
+                    z_index: ZIndex::Local(1),
+                    ..default()
+                },
+                SliderHandle,
+            ));
+        });
+    }
+}
+
+/// Moves the value of pressed [`Slider`]s to the cursor and sends [`SliderChanged`] events.
+///
+/// [`Disabled`] sliders are ignored.
+pub fn slider_drag_system(
+    mut sliders: Query<
+        (Entity, &Interaction, &RelativeCursorPosition, &mut Slider),
+        Without<Disabled>,
+    >,
+    mut changed_events: EventWriter<SliderChanged>,
+) {
+    for (entity, interaction, relative_cursor_position, mut slider) in &mut sliders {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(normalized) = relative_cursor_position.normalized else {
+            continue;
+        };
+        let mut new_slider = *slider;
+        new_slider.set_fraction(normalized.x);
+        if new_slider.value != slider.value {
+            slider.value = new_slider.value;
+            changed_events.send(SliderChanged {
+                entity,
+                value: slider.value,
+            });
+        }
+    }
+}
+
+/// Positions the fill and handle nodes of changed [`Slider`]s.
+#[allow(clippy::type_complexity)]
+pub fn update_slider_children(
+    sliders: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fills: Query<(&mut Style, &mut BackgroundColor), (With<SliderFill>, Without<SliderHandle>)>,
+    mut handles: Query<
+        (&mut Style, &mut BackgroundColor),
+        (With<SliderHandle>, Without<SliderFill>),
+    >,
+) {
+    for (slider, children) in &sliders {
+        let position = Val::Percent(100. * slider.fraction());
+        for &child in children {
+            if let Ok((mut style, mut background_color)) = fills.get_mut(child) {
+                style.width = position;
+                background_color.0 = slider.fill_color;
+            } else if let Ok((mut style, mut background_color)) = handles.get_mut(child) {
+                style.left = position;
+                background_color.0 = slider.handle_color;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{slider_drag_system, Slider, SliderChanged};
+    use crate::{Interaction, RelativeCursorPosition};
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn slider_values_are_clamped_to_steps() {
+        let mut slider = Slider::new(0.0..=10.).with_step(3.);
+        slider.set_value(4.);
+        assert_eq!(slider.value(), 3.);
+        slider.set_value(5.);
+        assert_eq!(slider.value(), 6.);
+        // The end of the range isn't a step, so the last step is the largest value
+        slider.set_value(10.);
+        assert_eq!(slider.value(), 9.);
+        slider.set_value(-5.);
+        assert_eq!(slider.value(), 0.);
+        slider.set_fraction(0.5);
+        assert_eq!(slider.value(), 6.);
+        assert_eq!(slider.fraction(), 0.6);
+    }
+
+    #[test]
+    fn reversed_slider_ranges_are_normalized() {
+        let mut slider = Slider::new(10.0..=0.).with_step(2.);
+        assert_eq!(slider.range(), 0.0..=10.);
+        assert_eq!(slider.value(), 0.);
+        slider.set_value(7.);
+        assert_eq!(slider.value(), 8.);
+        slider.set_value(20.);
+        assert_eq!(slider.value(), 10.);
+        slider.set_fraction(0.25);
+        assert_eq!(slider.value(), 2.);
+    }
+
+    #[test]
+    fn pressed_sliders_follow_the_cursor() {
+        let mut world = World::new();
+        world.init_resource::<Events<SliderChanged>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(slider_drag_system);
+
+        let cursor = |x| RelativeCursorPosition {
+            normalized_visible_node_rect: Default::default(),
+            normalized: Some(Vec2::new(x, 0.5)),
+        };
+        let slider = world
+            .spawn((
+                Slider::new(0.0..=100.).with_step(1.),
+                Interaction::Pressed,
+                cursor(0.25),
+            ))
+            .id();
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Slider>(slider).unwrap().value(), 25.);
+
+        // Dragging past the end of the track selects the end of the range
+        *world.get_mut::<RelativeCursorPosition>(slider).unwrap() = cursor(1.5);
+        schedule.run(&mut world);
+        // Holding the cursor still doesn't send another event
+        schedule.run(&mut world);
+        assert_eq!(world.get::<Slider>(slider).unwrap().value(), 100.);
+
+        let events = world.resource::<Events<SliderChanged>>();
+        assert_eq!(
+            events
+                .get_reader()
+                .read(events)
+                .map(|event| event.value)
+                .collect::<Vec<_>>(),
+            vec![25., 100.]
+        );
+    }
+}
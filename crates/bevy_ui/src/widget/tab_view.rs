@@ -0,0 +1,169 @@
+use crate::{widget::ButtonPressed, widget::SelectionChanged, Display, Style};
+use bevy_ecs::entity::{EntityMapper, MapEntities};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
+use bevy_hierarchy::{Children, HierarchyQueryExt, Parent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// A view showing the page of its selected [`Tab`], with the pages of the other tabs hidden with
+/// [`Display::None`].
+///
+/// The tabs of a view are its descendants, usually the children of a tab bar node, and their pages can be anywhere
+/// in the UI. If no tab is selected, the first tab is selected. Tab views shouldn't be nested.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq, MapEntities)]
+pub struct TabView {
+    /// The selected tab.
+    pub selected: Option<Entity>,
+}
+
+impl MapEntities for TabView {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let Some(entity) = &mut self.selected {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+/// A tab in a [`TabView`], showing its page while it is selected.
+///
+/// The tab is selected when its [`Button`](crate::widget::Button) is pressed, which sends a [`SelectionChanged`]
+/// event for its view. [`Disabled`](crate::Disabled) tabs can't be selected.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, PartialEq, MapEntities)]
+pub struct Tab {
+    /// The page node shown while the tab is selected.
+    pub page: Entity,
+    /// The [`Display`] of the page while it is shown.
+    pub page_display: Display,
+    /// Whether the tab is selected, updated from its [`TabView`].
+    pub selected: bool,
+}
+
+impl MapEntities for Tab {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.page = entity_mapper.map_entity(self.page);
+    }
+}
+
+impl Tab {
+    /// Creates a [`Tab`] showing `page` with [`Display::Flex`].
+    pub const fn new(page: Entity) -> Self {
+        Self {
+            page,
+            page_display: Display::Flex,
+            selected: false,
+        }
+    }
+}
+
+/// Selects [`Tab`]s in their [`TabView`] when their button is pressed and sends [`SelectionChanged`] events.
+pub fn tab_select_system(
+    mut pressed_events: EventReader<ButtonPressed>,
+    tabs: Query<(), With<Tab>>,
+    parents: Query<&Parent>,
+    mut views: Query<&mut TabView>,
+    mut changed_events: EventWriter<SelectionChanged>,
+) {
+    for &ButtonPressed(entity) in pressed_events.read() {
+        if !tabs.contains(entity) {
+            continue;
+        }
+        let Some(view_entity) = parents
+            .iter_ancestors(entity)
+            .find(|ancestor| views.contains(*ancestor))
+        else {
+            continue;
+        };
+        let Ok(mut view) = views.get_mut(view_entity) else {
+            continue;
+        };
+        if view.selected != Some(entity) {
+            view.selected = Some(entity);
+            changed_events.send(SelectionChanged {
+                entity: view_entity,
+                selected: entity,
+            });
+        }
+    }
+}
+
+/// Updates the [`Tab`]s of changed [`TabView`]s and shows the page of the selected tab.
+pub fn update_tab_views(
+    mut views: Query<(Entity, &mut TabView), Changed<TabView>>,
+    children: Query<&Children>,
+    mut tabs: Query<(Entity, &mut Tab)>,
+    mut pages: Query<&mut Style>,
+) {
+    for (view_entity, mut view) in &mut views {
+        if view.selected.is_none() {
+            view.selected = children
+                .iter_descendants(view_entity)
+                .find(|entity| tabs.contains(*entity));
+        }
+        let mut iter = tabs.iter_many_mut(children.iter_descendants(view_entity));
+        while let Some((entity, mut tab)) = iter.fetch_next() {
+            tab.selected = view.selected == Some(entity);
+            if let Ok(mut style) = pages.get_mut(tab.page) {
+                style.display = if tab.selected {
+                    tab.page_display
+                } else {
+                    Display::None
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tab_select_system, update_tab_views, Tab, TabView};
+    use crate::{
+        widget::{ButtonPressed, SelectionChanged},
+        Display, Style,
+    };
+    use bevy_ecs::{event::Events, schedule::IntoSystemConfigs, schedule::Schedule, world::World};
+    use bevy_hierarchy::BuildWorldChildren;
+
+    fn selection_events(world: &mut World) -> Vec<SelectionChanged> {
+        world
+            .resource_mut::<Events<SelectionChanged>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn tab_views_show_the_selected_page() {
+        let mut world = World::new();
+        world.init_resource::<Events<ButtonPressed>>();
+        world.init_resource::<Events<SelectionChanged>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((tab_select_system, update_tab_views).chain());
+
+        let pages = [(); 2].map(|_| world.spawn(Style::default()).id());
+        let view = world.spawn(TabView::default()).id();
+        let mut tabs = Vec::new();
+        world.entity_mut(view).with_children(|parent| {
+            parent.spawn_empty().with_children(|tab_bar| {
+                tabs.extend(pages.map(|page| tab_bar.spawn(Tab::new(page)).id()));
+            });
+        });
+        let displays = |world: &World| pages.map(|page| world.get::<Style>(page).unwrap().display);
+
+        // The first tab is selected by default
+        schedule.run(&mut world);
+        assert_eq!(displays(&world), [Display::Flex, Display::None]);
+        assert!(world.get::<Tab>(tabs[0]).unwrap().selected);
+
+        world.send_event(ButtonPressed(tabs[1]));
+        schedule.run(&mut world);
+        assert_eq!(displays(&world), [Display::None, Display::Flex]);
+        assert_eq!(
+            selection_events(&mut world),
+            [SelectionChanged {
+                entity: view,
+                selected: tabs[1]
+            }]
+        );
+    }
+}
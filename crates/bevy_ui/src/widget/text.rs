@@ -1,26 +1,45 @@
+#[cfg(feature = "pseudo_locale")]
+use crate::pseudo_locale::PseudoLocale;
 use crate::{
-    ContentSize, DefaultUiCamera, FixedMeasure, Measure, Node, NodeMeasure, TargetCamera, UiScale,
+    ContentSize, DefaultUiCamera, Display, FixedMeasure, Measure, Node, NodeMeasure, PositionType,
+    Style, TargetCamera, UiRootScale, UiScale, Val,
 };
 use bevy_asset::Assets;
 use bevy_ecs::{
-    entity::{Entity, EntityHashMap},
-    prelude::{Component, DetectChanges},
+    entity::{Entity, EntityHashMap, EntityHashSet, EntityMapper, MapEntities},
+    prelude::{Component, DetectChanges, Resource},
     query::With,
-    reflect::ReflectComponent,
+    reflect::{ReflectComponent, ReflectMapEntities},
+    removal_detection::RemovedComponents,
     system::{Local, Query, Res, ResMut},
     world::{Mut, Ref},
 };
+use bevy_hierarchy::Parent;
 use bevy_math::Vec2;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{camera::Camera, texture::Image};
 use bevy_sprite::TextureAtlasLayout;
 use bevy_text::{
-    scale_value, BreakLineOn, Font, FontAtlasSets, Text, TextError, TextLayoutInfo,
-    TextMeasureInfo, TextPipeline, TextSettings, YAxisOrientation,
+    scale_value, BreakLineOn, Font, FontAtlasSets, OverflowBehavior, Text, TextError,
+    TextLayoutInfo, TextLayoutOptions, TextMeasureInfo, TextPipeline, TextSettings,
+    YAxisOrientation,
 };
+use bevy_transform::components::Transform;
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
 use bevy_utils::Entry;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
 use taffy::style::AvailableSpace;
 
+/// The number of widths for which a [`TextMeasure`] keeps the computed size.
+const TEXT_MEASURE_CACHE_CAPACITY: usize = 8;
+
+/// The number of subdivisions of a physical pixel to which widths are rounded to look up the [`TextMeasure`] cache.
+const TEXT_MEASURE_CACHE_PRECISION: f32 = 64.;
+
 /// Text system flags
 ///
 /// Used internally by [`measure_text_system`] and [`text_system`] to schedule text for processing.
@@ -42,9 +61,111 @@ impl Default for TextFlags {
     }
 }
 
+/// Measures a wrapping text node.
+///
+/// Without a known width, the min-content width is the width of the longest word and the max-content width is
+/// the width of the text without any soft line breaks. A definite available width is clamped between the two,
+/// and the height is computed by laying the text out at the resulting width.
+///
+/// Laying out the text is expensive, and the layout algorithm measures a node several times with the same
+/// constraints, so the sizes computed for the last few widths are cached. Widths are rounded to a fraction of a
+/// physical pixel to look up the cache. A new measure, with an empty cache, is created whenever the text or its
+/// scale factor changes.
+///
+/// Vertical text, see [`WritingMode`](bevy_text::WritingMode), wraps into more columns at the height of the node
+/// if it is known, and is as tall as its longest column otherwise.
+///
+/// Text that doesn't wrap is measured with a [`FixedMeasure`] of its max-content size instead.
 #[derive(Clone)]
 pub struct TextMeasure {
+    /// The sections and fonts of the text, and its min-content and max-content sizes.
     pub info: TextMeasureInfo,
+    cache: TextMeasureCache,
+}
+
+impl TextMeasure {
+    /// Creates a [`TextMeasure`] for `info`, counting its cache hits and misses in `stats`.
+    pub fn new(info: TextMeasureInfo, stats: &TextMeasureCacheStats) -> Self {
+        Self {
+            info,
+            cache: TextMeasureCache {
+                sizes: Default::default(),
+                stats: stats.clone(),
+            },
+        }
+    }
+
+    /// Computes the size of the text laid out at `width`, or returns it from the cache.
+    fn compute_size(&self, width: f32) -> Vec2 {
+        let key = (width * TEXT_MEASURE_CACHE_PRECISION).round() as i64;
+        let mut sizes = self.cache.sizes.lock().unwrap();
+        if let Some(&(_, size)) = sizes.iter().find(|(cached, _)| *cached == key) {
+            self.cache
+                .stats
+                .counters
+                .hits
+                .fetch_add(1, Ordering::Relaxed);
+            return size;
+        }
+        self.cache
+            .stats
+            .counters
+            .misses
+            .fetch_add(1, Ordering::Relaxed);
+        let size = self.info.compute_size(Vec2::new(width, f32::MAX));
+        if sizes.len() == TEXT_MEASURE_CACHE_CAPACITY {
+            sizes.remove(0);
+        }
+        sizes.push((key, size));
+        size
+    }
+}
+
+/// The sizes computed by a [`TextMeasure`], keyed by the rounded width.
+struct TextMeasureCache {
+    sizes: Mutex<Vec<(i64, Vec2)>>,
+    stats: TextMeasureCacheStats,
+}
+
+impl Clone for TextMeasureCache {
+    fn clone(&self) -> Self {
+        Self {
+            sizes: Mutex::new(self.sizes.lock().unwrap().clone()),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct TextMeasureCacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Counts how often [`TextMeasure`]s found the size of their text in their cache during the last layout update.
+///
+/// The counters are reset by [`measure_text_system`] every frame.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct TextMeasureCacheStats {
+    counters: Arc<TextMeasureCacheCounters>,
+}
+
+impl TextMeasureCacheStats {
+    /// The number of measurements that were found in the cache.
+    pub fn hits(&self) -> u64 {
+        self.counters.hits.load(Ordering::Relaxed)
+    }
+
+    /// The number of measurements that needed the text to be laid out.
+    pub fn misses(&self) -> u64 {
+        self.counters.misses.load(Ordering::Relaxed)
+    }
+
+    /// Sets both counters to zero.
+    pub fn reset(&self) {
+        self.counters.hits.store(0, Ordering::Relaxed);
+        self.counters.misses.store(0, Ordering::Relaxed);
+    }
 }
 
 impl Measure for TextMeasure {
@@ -56,6 +177,13 @@ impl Measure for TextMeasure {
         _available_height: AvailableSpace,
         _style: &taffy::Style,
     ) -> Vec2 {
+        if self.info.writing_mode.is_vertical() {
+            // Vertical text wraps into more columns at the height of the node, which isn't cached
+            let size = height.map_or(self.info.max, |y| {
+                self.info.compute_size(Vec2::new(f32::INFINITY, y))
+            });
+            return Vec2::new(width.unwrap_or(size.x), height.unwrap_or(size.y)).ceil();
+        }
         let x = width.unwrap_or_else(|| match available_width {
             AvailableSpace::Definite(x) => {
                 // It is possible for the "min content width" to be larger than
@@ -71,7 +199,7 @@ impl Measure for TextMeasure {
         height
             .map_or_else(
                 || match available_width {
-                    AvailableSpace::Definite(_) => self.info.compute_size(Vec2::new(x, f32::MAX)),
+                    AvailableSpace::Definite(_) => self.compute_size(x),
                     AvailableSpace::MinContent => Vec2::new(x, self.info.min.y),
                     AvailableSpace::MaxContent => Vec2::new(x, self.info.max.y),
                 },
@@ -84,17 +212,21 @@ impl Measure for TextMeasure {
 #[inline]
 fn create_text_measure(
     fonts: &Assets<Font>,
+    stats: &TextMeasureCacheStats,
     scale_factor: f32,
-    text: Ref<Text>,
+    text: &Text,
     mut content_size: Mut<ContentSize>,
     mut text_flags: Mut<TextFlags>,
 ) {
-    match TextMeasureInfo::from_text(&text, fonts, scale_factor) {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("create_text_measure", sections = text.sections.len()).entered();
+
+    match TextMeasureInfo::from_text(text, fonts, scale_factor) {
         Ok(measure) => {
             if text.linebreak_behavior == BreakLineOn::NoWrap {
                 content_size.set(NodeMeasure::Fixed(FixedMeasure { size: measure.max }));
             } else {
-                content_size.set(NodeMeasure::Text(TextMeasure { info: measure }));
+                content_size.set(NodeMeasure::Text(TextMeasure::new(measure, stats)));
             }
 
             // Text measure func created successfully, so set `TextFlags` to schedule a recompute
@@ -116,29 +248,50 @@ fn create_text_measure(
 /// to provide for the text given the fonts, the text itself and the constraints of the layout.
 ///
 /// * Measures are regenerated if the target camera's scale factor (or primary window if no specific target) or [`UiScale`] is changed.
+/// * Measures are regenerated if the node's [`UiRootScale`] is changed or removed.
 /// * Changes that only modify the colors of a `Text` do not require a new `Measure`. This system
 ///     is only able to detect that a `Text` component has changed and will regenerate the `Measure` on
 ///     color changes. This can be expensive, particularly for large blocks of text, and the [`bypass_change_detection`](bevy_ecs::change_detection::DetectChangesMut::bypass_change_detection)
 ///     method should be called when only changing the `Text`'s colors.
+/// * With the `pseudo_locale` feature, measures are regenerated when the [`PseudoLocale`] resource changes.
+///
+/// The [`TextMeasureCacheStats`] are reset before each layout update.
+#[allow(clippy::too_many_arguments)]
 pub fn measure_text_system(
     mut last_scale_factors: Local<EntityHashMap<f32>>,
     fonts: Res<Assets<Font>>,
+    cache_stats: Res<TextMeasureCacheStats>,
     camera_query: Query<(Entity, &Camera)>,
     default_ui_camera: DefaultUiCamera,
     ui_scale: Res<UiScale>,
     mut text_query: Query<
         (
+            Entity,
             Ref<Text>,
             &mut ContentSize,
             &mut TextFlags,
             Option<&TargetCamera>,
+            Option<Ref<UiRootScale>>,
         ),
         With<Node>,
     >,
+    mut removed_root_scales: RemovedComponents<UiRootScale>,
+    #[cfg(feature = "pseudo_locale")] pseudo_locale: Res<PseudoLocale>,
 ) {
+    #[cfg(not(feature = "pseudo_locale"))]
+    let pseudo_locale_changed = false;
+    #[cfg(feature = "pseudo_locale")]
+    let pseudo_locale_changed = pseudo_locale.is_changed();
+
+    #[cfg(feature = "trace")]
+    let _span = info_span!("measure_text", text_nodes = text_query.iter().len()).entered();
+
+    cache_stats.reset();
+
     let mut scale_factors: EntityHashMap<f32> = EntityHashMap::default();
+    let removed_root_scales: EntityHashSet = removed_root_scales.read().collect();
 
-    for (text, content_size, text_flags, camera) in &mut text_query {
+    for (entity, text, content_size, text_flags, camera, root_scale) in &mut text_query {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
             continue;
@@ -158,8 +311,20 @@ pub fn measure_text_system(
             || text.is_changed()
             || text_flags.needs_new_measure_func
             || content_size.is_added()
+            || pseudo_locale_changed
+            || root_scale.as_ref().is_some_and(DetectChanges::is_changed)
+            || removed_root_scales.contains(&entity)
         {
-            create_text_measure(&fonts, scale_factor, text, content_size, text_flags);
+            #[cfg(feature = "pseudo_locale")]
+            let text = pseudo_locale.localize_text(&text);
+            create_text_measure(
+                &fonts,
+                &cache_stats,
+                scale_factor * root_scale.map_or(1., |root_scale| root_scale.0),
+                &text,
+                content_size,
+                text_flags,
+            );
         }
     }
     *last_scale_factors = scale_factors;
@@ -176,15 +341,22 @@ fn queue_text(
     text_settings: &TextSettings,
     scale_factor: f32,
     inverse_scale_factor: f32,
+    root_scale: f32,
     text: &Text,
     node: Ref<Node>,
     mut text_flags: Mut<TextFlags>,
     mut text_layout_info: Mut<TextLayoutInfo>,
 ) {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("queue_text", sections = text.sections.len()).entered();
+
     // Skip the text node if it is waiting for a new measure func
     if !text_flags.needs_new_measure_func {
-        let physical_node_size = if text.linebreak_behavior == BreakLineOn::NoWrap {
+        let physical_node_size = if text.linebreak_behavior == BreakLineOn::NoWrap
+            && text.overflow == OverflowBehavior::Visible
+        {
             // With `NoWrap` set, no constraints are placed on the width of the text.
+            // Unless the text overflow is truncated, the node size isn't needed either.
             Vec2::splat(f32::INFINITY)
         } else {
             // `scale_factor` is already multiplied by `UiScale`
@@ -194,18 +366,19 @@ fn queue_text(
             )
         };
 
+        // The text is laid out at the scale of its UI root, in the node's physical size
         match text_pipeline.queue_text(
             fonts,
             &text.sections,
-            scale_factor,
-            text.justify,
-            text.linebreak_behavior,
+            scale_factor * root_scale,
+            &TextLayoutOptions::from_text(text),
             physical_node_size,
             font_atlas_sets,
             texture_atlases,
             textures,
             text_settings,
             YAxisOrientation::TopToBottom,
+            text.render_mode,
         ) {
             Err(TextError::NoSuchFont) => {
                 // There was an error processing the text layout, try again next frame
@@ -251,9 +424,13 @@ pub fn text_system(
         &mut TextFlags,
         Option<&TargetCamera>,
     )>,
+    #[cfg(feature = "pseudo_locale")] pseudo_locale: Res<PseudoLocale>,
 ) {
     let mut scale_factors: EntityHashMap<f32> = EntityHashMap::default();
 
+    #[cfg(feature = "trace")]
+    let _span = info_span!("text_layout", text_nodes = text_query.iter().len()).entered();
+
     for (node, text, text_layout_info, text_flags, camera) in &mut text_query {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
@@ -276,6 +453,8 @@ pub fn text_system(
             || node.is_changed()
             || text_flags.needs_recompute
         {
+            #[cfg(feature = "pseudo_locale")]
+            let text = &*pseudo_locale.localize_text(text);
             queue_text(
                 &fonts,
                 &mut text_pipeline,
@@ -285,6 +464,7 @@ pub fn text_system(
                 &text_settings,
                 scale_factor,
                 inverse_scale_factor,
+                node.root_scale(),
                 text,
                 node,
                 text_flags,
@@ -294,3 +474,205 @@ pub fn text_system(
     }
     *last_scale_factors = scale_factors;
 }
+
+/// Positions and sizes a node over the box of a placeholder [`TextSection`](bevy_text::TextSection) of a text
+/// node, to display an image or widget inline with the text.
+///
+/// The node must be a sibling of the text node, since text nodes are measured as leaves and can't have children.
+/// [`update_text_placeholder_nodes`] makes the node absolutely positioned and sets its position and size from the
+/// text's [`TextLayoutInfo::placeholders`], the node is hidden while the placeholder isn't laid out.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq, MapEntities)]
+pub struct TextPlaceholderNode {
+    /// The text node.
+    pub text: Entity,
+    /// The index of the placeholder section in the text node's [`Text`].
+    pub section_index: usize,
+}
+
+impl MapEntities for TextPlaceholderNode {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.text = entity_mapper.map_entity(self.text);
+    }
+}
+
+/// Updates the [`Style`] of [`TextPlaceholderNode`]s from the layout of their text.
+pub fn update_text_placeholder_nodes(
+    mut placeholder_nodes: Query<(&TextPlaceholderNode, &Parent, &mut Style)>,
+    texts: Query<(
+        &Node,
+        &Transform,
+        &TextLayoutInfo,
+        &Parent,
+        Option<&TargetCamera>,
+    )>,
+    parents: Query<&Node>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+) {
+    for (placeholder_node, parent, mut style) in &mut placeholder_nodes {
+        let Ok((text_node, text_transform, text_layout_info, text_parent, camera)) =
+            texts.get(placeholder_node.text)
+        else {
+            continue;
+        };
+        let Ok(parent_node) = parents.get(parent.get()) else {
+            continue;
+        };
+        if text_parent.get() != parent.get() {
+            continue;
+        }
+        let Some(placeholder) = text_layout_info
+            .placeholders
+            .iter()
+            .find(|placeholder| placeholder.section_index == placeholder_node.section_index)
+        else {
+            if style.display != Display::None {
+                style.display = Display::None;
+            }
+            continue;
+        };
+        let inverse_scale_factor = (camera
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|camera_entity| camera_query.get(camera_entity).ok())
+            .and_then(Camera::target_scaling_factor)
+            .unwrap_or(1.)
+            * ui_scale.0)
+            .recip();
+        // The offset of the text from the top left corner of the parent.
+        // The layout sets the translation of a node relative to the center of its parent.
+        let offset =
+            text_transform.translation.truncate() + 0.5 * (parent_node.size() - text_node.size());
+        let min = offset + placeholder.rect.min * inverse_scale_factor;
+        let size = placeholder.rect.size() * inverse_scale_factor;
+
+        let (display, position_type, left, top, width, height) = (
+            Display::Flex,
+            PositionType::Absolute,
+            Val::Px(min.x),
+            Val::Px(min.y),
+            Val::Px(size.x),
+            Val::Px(size.y),
+        );
+        if (
+            style.display,
+            style.position_type,
+            style.left,
+            style.top,
+            style.width,
+            style.height,
+        ) != (display, position_type, left, top, width, height)
+        {
+            style.display = display;
+            style.position_type = position_type;
+            style.left = left;
+            style.top = top;
+            style.width = width;
+            style.height = height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextMeasure, TextMeasureCacheStats};
+    use crate::{measurement::AvailableSpace, Measure};
+    use bevy_math::Vec2;
+    use bevy_text::{BreakLineOn, JustifyText, TextMeasureInfo, WritingMode};
+
+    fn text_measure(min: Vec2, max: Vec2, stats: &TextMeasureCacheStats) -> TextMeasure {
+        TextMeasure::new(
+            TextMeasureInfo {
+                fonts: Box::new([]),
+                sections: Box::new([]),
+                justification: JustifyText::Left,
+                linebreak_behavior: BreakLineOn::WordBoundary,
+                paragraph_spacing: 0.,
+                writing_mode: WritingMode::HorizontalTb,
+                min,
+                max,
+            },
+            stats,
+        )
+    }
+
+    #[test]
+    fn text_measure_content_sizes() {
+        let measure = text_measure(
+            Vec2::new(40., 60.),
+            Vec2::new(200., 20.),
+            &TextMeasureCacheStats::default(),
+        );
+        let style = taffy::Style::default();
+        let measure_with = |width, height, available_width| {
+            measure.measure(
+                width,
+                height,
+                available_width,
+                AvailableSpace::MaxContent,
+                &style,
+            )
+        };
+
+        assert_eq!(
+            measure_with(None, None, AvailableSpace::MinContent),
+            Vec2::new(40., 60.)
+        );
+        assert_eq!(
+            measure_with(None, None, AvailableSpace::MaxContent),
+            Vec2::new(200., 20.)
+        );
+        // Known dimensions are returned unchanged, rounded up to whole pixels
+        assert_eq!(
+            measure_with(Some(99.5), Some(30.), AvailableSpace::MinContent),
+            Vec2::new(100., 30.)
+        );
+        // A definite width is clamped between the min-content and max-content widths
+        for (available, expected) in [(10., 40.), (120., 120.), (500., 200.)] {
+            assert_eq!(
+                measure_with(None, Some(30.), AvailableSpace::Definite(available)),
+                Vec2::new(expected, 30.)
+            );
+        }
+    }
+
+    #[test]
+    fn text_measure_caches_sizes_by_width() {
+        let stats = TextMeasureCacheStats::default();
+        let measure = text_measure(Vec2::new(40., 60.), Vec2::new(200., 20.), &stats);
+        let measure_at = |available_width: f32| {
+            measure.measure(
+                None,
+                None,
+                AvailableSpace::Definite(available_width),
+                AvailableSpace::MaxContent,
+                &taffy::Style::default(),
+            )
+        };
+
+        let size = measure_at(120.);
+        assert_eq!((stats.hits(), stats.misses()), (0, 1));
+        assert_eq!(measure_at(120.), size);
+        // Widths that round to the same key share an entry
+        measure_at(120.001);
+        assert_eq!((stats.hits(), stats.misses()), (2, 1));
+        measure_at(150.);
+        assert_eq!((stats.hits(), stats.misses()), (2, 2));
+
+        // Clones keep the cached sizes
+        let clone = measure.clone();
+        clone.measure(
+            None,
+            None,
+            AvailableSpace::Definite(150.),
+            AvailableSpace::MaxContent,
+            &taffy::Style::default(),
+        );
+        assert_eq!((stats.hits(), stats.misses()), (3, 2));
+
+        stats.reset();
+        assert_eq!((stats.hits(), stats.misses()), (0, 0));
+    }
+}
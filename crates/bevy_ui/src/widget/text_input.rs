@@ -0,0 +1,937 @@
+use std::ops::Range;
+
+use crate::{
+    clipboard::clipboard_modifier_pressed,
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, ClipboardSource, DefaultUiCamera, Display, GamepadCursor, Interaction, Node,
+    PasteIntoFocused, PositionType, RelativeCursorPosition, Style, TargetCamera, UiKeyboardCapture,
+    UiScale, Val, ZIndex,
+};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{BuildChildren, Children, DespawnRecursiveExt};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    ButtonInput, ButtonState,
+};
+use bevy_math::Vec2;
+use bevy_render::camera::{Camera, NormalizedRenderTarget};
+use bevy_render::view::Visibility;
+use bevy_text::{Text, TextLayoutInfo, TextStyle};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::{default, Duration};
+use bevy_window::{Ime, PrimaryWindow, Window};
+
+/// The width of the caret of a focused [`TextInput`], in logical pixels.
+const CARET_WIDTH: f32 = 2.;
+
+/// How long the caret of a focused [`TextInput`] stays shown, then hidden, while blinking.
+const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+/// The color of the selection highlight of a [`TextInput`].
+const SELECTION_COLOR: Color = Color::srgba(0.3, 0.45, 0.8, 0.5);
+
+/// The thickness of the underline drawn below the [`ImeComposition`] of a [`TextInput`], in logical pixels.
+const COMPOSITION_UNDERLINE_WIDTH: f32 = 2.;
+
+/// A single line text field.
+///
+/// Pressing the node focuses it and places the caret, dragging selects text.
+/// While focused, typed characters are inserted at the caret and the following keys are handled:
+/// - left and right arrows move the caret, home and end move it to the start or end of the text,
+///   holding shift extends the selection,
+/// - backspace and delete remove the selection or the character before or after the caret,
+/// - control (or command on macOS) with `A` selects all, and with `C`, `X` and `V` copies, cuts and pastes
+///   using the [`TextInputClipboard`], or the system clipboard when a [`ClipboardSource`] is set, pastes from the
+///   system clipboard are inserted with [`PasteIntoFocused`] events, copying and cutting are disabled for masked
+///   inputs,
+/// - enter sends a [`TextInputSubmitted`] event,
+/// - escape removes the focus.
+///
+/// The focused input captures the keyboard with [`UiKeyboardCapture`], so focus navigation doesn't also respond to
+/// these keys.
+///
+/// Text entered with an input method editor (IME) is composed in the input's [`ImeComposition`] and inserted
+/// once committed.
+///
+/// The text is displayed by a child text node, spawned along with the caret when the [`TextInput`] is added.
+/// The caret blinks while the input is focused, and is shown again whenever the text or caret changes.
+///
+/// See [`TextInputBundle`](crate::node_bundles::TextInputBundle).
+#[derive(Component, Clone, Debug, Default)]
+pub struct TextInput {
+    /// The style of the displayed text.
+    pub text_style: TextStyle,
+    /// The character displayed in place of each grapheme of the text, for password fields.
+    pub mask: Option<char>,
+    value: String,
+    /// The byte offset of the caret in `value`.
+    cursor: usize,
+    /// The byte offset of the other end of the selection in `value`, equal to `cursor` when nothing is selected.
+    anchor: usize,
+}
+
+impl TextInput {
+    /// Creates a [`TextInput`] containing `value` with the caret at the end.
+    pub fn new(value: impl Into<String>, text_style: TextStyle) -> Self {
+        let mut text_input = Self {
+            text_style,
+            ..default()
+        };
+        text_input.set_value(value);
+        text_input
+    }
+
+    /// Returns the input with its text displayed masked with `mask`, see [`TextInput::mask`].
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// The text of the input.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text of the input and moves the caret to the end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+        self.anchor = self.cursor;
+    }
+
+    /// The byte offset of the caret.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The byte range of the selected text, empty if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// The selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.value[self.selection()]
+    }
+
+    /// Selects the whole text.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.value.len();
+    }
+
+    /// Moves the caret to the character boundary at or before `byte_offset`.
+    ///
+    /// If `extend_selection` is false, the selection is cleared.
+    pub fn move_cursor(&mut self, byte_offset: usize, extend_selection: bool) {
+        let mut byte_offset = byte_offset.min(self.value.len());
+        while !self.value.is_char_boundary(byte_offset) {
+            byte_offset -= 1;
+        }
+        self.cursor = byte_offset;
+        if !extend_selection {
+            self.anchor = byte_offset;
+        }
+    }
+
+    /// Replaces the selection with `text` and places the caret after it.
+    pub fn insert(&mut self, text: &str) {
+        let selection = self.selection();
+        self.value.replace_range(selection.clone(), text);
+        self.cursor = selection.start + text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Removes the selection, or the character before the caret if nothing is selected.
+    pub fn delete_backward(&mut self) {
+        if self.selection().is_empty() {
+            self.anchor = self.previous_boundary();
+        }
+        self.insert("");
+    }
+
+    /// Removes the selection, or the character after the caret if nothing is selected.
+    pub fn delete_forward(&mut self) {
+        if self.selection().is_empty() {
+            self.anchor = self.next_boundary();
+        }
+        self.insert("");
+    }
+
+    /// The value with the `composition`'s preedit text in place of the selection, and the byte range of the
+    /// preedit text in it.
+    fn composed_value(&self, composition: &ImeComposition) -> (String, Range<usize>) {
+        let selection = self.selection();
+        let mut value = self.value.clone();
+        value.replace_range(selection.clone(), &composition.preedit);
+        (
+            value,
+            selection.start..selection.start + composition.preedit.len(),
+        )
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.cursor
+            + self.value[self.cursor..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8)
+    }
+}
+
+/// The [`TextInput`] that receives keyboard input, if any.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextInputFocus(pub Option<Entity>);
+
+/// The in-progress input method editor (IME) composition of a [`TextInput`].
+///
+/// When typing Chinese, Japanese or Korean text for example, the IME sends preedit text that is displayed
+/// underlined in place of the selection but isn't part of the [`TextInput`]'s value until it is committed.
+///
+/// Inserted on [`TextInput`]s when they are added and updated from [`Ime`] events while the input is focused.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImeComposition {
+    preedit: String,
+    cursor: Option<Range<usize>>,
+}
+
+impl ImeComposition {
+    /// The text being composed.
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// The byte range of the IME's cursor in the preedit text, `None` if the cursor should be hidden.
+    pub fn cursor(&self) -> Option<Range<usize>> {
+        self.cursor.clone()
+    }
+
+    /// Returns true if there is preedit text.
+    pub fn is_composing(&self) -> bool {
+        !self.preedit.is_empty()
+    }
+}
+
+/// The text copied or cut from [`TextInput`]s, and pasted into them.
+///
+/// This clipboard is local to the app. When a [`ClipboardSource`] is set, copied and cut text is also written to the
+/// system clipboard, and pastes read the system clipboard instead.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct TextInputClipboard(pub String);
+
+/// Sent when enter is pressed while a [`TextInput`] is focused.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TextInputSubmitted {
+    /// The [`TextInput`] entity.
+    pub entity: Entity,
+    /// The text of the input.
+    pub value: String,
+}
+
+/// Marker for the text node displaying the value of a [`TextInput`].
+#[derive(Component)]
+pub struct TextInputText;
+
+/// Marker for the caret node of a [`TextInput`].
+#[derive(Component)]
+pub struct TextInputCaret;
+
+/// Marker for the nodes highlighting the selected text of a [`TextInput`].
+#[derive(Component)]
+pub struct TextInputSelection;
+
+/// Marker for the nodes underlining the [`ImeComposition`] of a [`TextInput`].
+#[derive(Component)]
+pub struct TextInputComposition;
+
+/// Spawns the text and caret nodes of new [`TextInput`]s and inserts their [`ImeComposition`].
+pub fn spawn_text_input_children(
+    mut commands: Commands,
+    text_inputs: Query<(Entity, &TextInput, Has<ImeComposition>), Added<TextInput>>,
+) {
+    for (entity, text_input, has_composition) in &text_inputs {
+        if !has_composition {
+            commands.entity(entity).insert(ImeComposition::default());
+        }
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                TextBundle::from_section(text_input.value.clone(), text_input.text_style.clone())
+                    .with_no_wrap(),
+                RelativeCursorPosition::default(),
+                TextInputText,
+            ));
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(CARET_WIDTH),
+                        ..default()
+                    },
+                    background_color: text_input.text_style.color.into(),
+                    z_index: ZIndex::Local(1),
+                    ..default()
+                },
+                TextInputCaret,
+            ));
+        });
+    }
+}
+
+/// Focuses pressed [`TextInput`]s and places their caret or selects text with the mouse.
+///
+/// Pressing anywhere outside of a [`TextInput`] removes the focus.
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_mouse_system(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    gamepad_cursor: Option<Res<GamepadCursor>>,
+    mut focus: ResMut<TextInputFocus>,
+    mut text_inputs: Query<(Entity, &Interaction, &mut TextInput, &Children)>,
+    texts: Query<(&Node, &RelativeCursorPosition, &TextLayoutInfo), With<TextInputText>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    target_cameras: Query<&TargetCamera>,
+    ui_scale: Res<UiScale>,
+) {
+    let just_pressed = mouse_button_input.just_pressed(MouseButton::Left)
+        || gamepad_cursor.is_some_and(|cursor| cursor.just_pressed());
+    if just_pressed {
+        let pressed = text_inputs
+            .iter()
+            .find(|(_, interaction, ..)| **interaction == Interaction::Pressed)
+            .map(|(entity, ..)| entity);
+        focus.set_if_neq(TextInputFocus(pressed));
+    }
+
+    let Some(focused) = focus.0 else {
+        return;
+    };
+    let Ok((entity, interaction, mut text_input, children)) = text_inputs.get_mut(focused) else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    let Some((node, relative_cursor_position, text_layout_info)) =
+        children.iter().find_map(|child| texts.get(*child).ok())
+    else {
+        return;
+    };
+    let Some(normalized) = relative_cursor_position.normalized else {
+        return;
+    };
+    let scale_factor = target_cameras
+        .get(entity)
+        .ok()
+        .map(TargetCamera::entity)
+        .or(default_ui_camera.get())
+        .and_then(|camera_entity| camera_query.get(camera_entity).ok())
+        .and_then(Camera::target_scaling_factor)
+        .unwrap_or(1.)
+        * ui_scale.0;
+    let physical_position = normalized * node.size() * scale_factor;
+    let byte_offset = text_layout_info.byte_offset_at(physical_position);
+    if byte_offset != text_input.cursor {
+        text_input.move_cursor(byte_offset, !just_pressed);
+    } else if just_pressed {
+        text_input.anchor = byte_offset;
+    }
+}
+
+/// Edits the focused [`TextInput`] with keyboard input.
+///
+/// Keyboard input is ignored while the input is composing text with an IME, and while another node, like an open
+/// [`CommandPalette`](super::CommandPalette), has captured the keyboard with [`UiKeyboardCapture`].
+#[allow(clippy::too_many_arguments)]
+pub fn text_input_keyboard_system(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    keyboard_capture: Res<UiKeyboardCapture>,
+    mut focus: ResMut<TextInputFocus>,
+    mut clipboard: ResMut<TextInputClipboard>,
+    clipboard_source: Res<ClipboardSource>,
+    mut submitted: EventWriter<TextInputSubmitted>,
+    mut text_inputs: Query<(&mut TextInput, Option<&ImeComposition>)>,
+) {
+    let Some(entity) = focus.0 else {
+        keyboard_input.clear();
+        return;
+    };
+    let Ok((mut text_input, composition)) = text_inputs.get_mut(entity) else {
+        focus.0 = None;
+        keyboard_input.clear();
+        return;
+    };
+    if composition.is_some_and(ImeComposition::is_composing)
+        || keyboard_capture
+            .owner()
+            .is_some_and(|owner| owner != entity)
+    {
+        keyboard_input.clear();
+        return;
+    }
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let control = clipboard_modifier_pressed(&keys);
+
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if control {
+            match event.key_code {
+                KeyCode::KeyA => text_input.select_all(),
+                KeyCode::KeyC if text_input.mask.is_none() => {
+                    clipboard.0 = text_input.selected_text().to_string();
+                    clipboard_source.write(clipboard.0.clone());
+                }
+                KeyCode::KeyX if text_input.mask.is_none() => {
+                    clipboard.0 = text_input.selected_text().to_string();
+                    clipboard_source.write(clipboard.0.clone());
+                    text_input.insert("");
+                }
+                KeyCode::KeyV if !clipboard_source.is_set() => text_input.insert(&clipboard.0),
+                _ => {}
+            }
+            continue;
+        }
+        match &event.logical_key {
+            Key::ArrowLeft => {
+                let selection = text_input.selection();
+                let cursor = if selection.is_empty() || shift {
+                    text_input.previous_boundary()
+                } else {
+                    selection.start
+                };
+                text_input.move_cursor(cursor, shift);
+            }
+            Key::ArrowRight => {
+                let selection = text_input.selection();
+                let cursor = if selection.is_empty() || shift {
+                    text_input.next_boundary()
+                } else {
+                    selection.end
+                };
+                text_input.move_cursor(cursor, shift);
+            }
+            Key::Home => text_input.move_cursor(0, shift),
+            Key::End => {
+                let end = text_input.value.len();
+                text_input.move_cursor(end, shift);
+            }
+            Key::Backspace => text_input.delete_backward(),
+            Key::Delete => text_input.delete_forward(),
+            Key::Space => text_input.insert(" "),
+            Key::Enter => {
+                submitted.send(TextInputSubmitted {
+                    entity,
+                    value: text_input.value.clone(),
+                });
+            }
+            Key::Escape => {
+                focus.0 = None;
+                break;
+            }
+            Key::Character(characters) if !characters.chars().any(char::is_control) => {
+                text_input.insert(characters);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Captures the keyboard with [`UiKeyboardCapture`] for the focused [`TextInput`], and releases it when the input
+/// loses the focus.
+///
+/// While a text input is focused, the arrow keys move its caret and enter submits it, so focus navigation ignores
+/// the keyboard.
+pub fn text_input_keyboard_capture_system(
+    focus: Res<TextInputFocus>,
+    mut keyboard_capture: ResMut<UiKeyboardCapture>,
+    mut captured: Local<Option<Entity>>,
+) {
+    if *captured == focus.0 {
+        return;
+    }
+    if let Some(previous) = captured.take() {
+        keyboard_capture.release(previous);
+    }
+    if let Some(entity) = focus.0 {
+        keyboard_capture.capture(entity);
+    }
+    *captured = focus.0;
+}
+
+/// Inserts the text of [`PasteIntoFocused`] events into their [`TextInput`] if it's still focused.
+///
+/// Line breaks and other control characters are removed, since the input is single line.
+pub fn text_input_paste_system(
+    mut paste_events: EventReader<PasteIntoFocused>,
+    focus: Res<TextInputFocus>,
+    mut text_inputs: Query<&mut TextInput>,
+) {
+    for PasteIntoFocused { entity, text } in paste_events.read() {
+        if focus.0 != Some(*entity) {
+            continue;
+        }
+        if let Ok(mut text_input) = text_inputs.get_mut(*entity) {
+            let text: String = text.chars().filter(|c| !c.is_control()).collect();
+            text_input.insert(&text);
+        }
+    }
+}
+
+/// Updates the [`ImeComposition`] of the focused [`TextInput`] from [`Ime`] events and inserts committed text.
+///
+/// The compositions of unfocused inputs are cleared.
+pub fn text_input_ime_system(
+    mut ime_events: EventReader<Ime>,
+    focus: Res<TextInputFocus>,
+    mut text_inputs: Query<(Entity, &mut TextInput, &mut ImeComposition)>,
+) {
+    for (entity, _, mut composition) in &mut text_inputs {
+        if focus.0 != Some(entity) && composition.is_composing() {
+            *composition = ImeComposition::default();
+        }
+    }
+
+    let Some(Ok((_, mut text_input, mut composition))) =
+        focus.0.map(|entity| text_inputs.get_mut(entity))
+    else {
+        ime_events.clear();
+        return;
+    };
+    for event in ime_events.read() {
+        match event {
+            Ime::Preedit { value, cursor, .. } => {
+                composition.set_if_neq(ImeComposition {
+                    preedit: value.clone(),
+                    cursor: cursor.map(|(start, end)| start..end),
+                });
+            }
+            Ime::Commit { value, .. } => {
+                text_input.insert(value);
+                composition.set_if_neq(ImeComposition::default());
+            }
+            Ime::Disabled { .. } => {
+                composition.set_if_neq(ImeComposition::default());
+            }
+            Ime::Enabled { .. } => {}
+        }
+    }
+}
+
+/// Copies the value, style and mask of changed [`TextInput`]s to their text node.
+///
+/// The preedit text of an [`ImeComposition`] is displayed in place of the selection.
+#[allow(clippy::type_complexity)]
+pub fn update_text_input_text(
+    text_inputs: Query<
+        (&TextInput, Option<&ImeComposition>, &Children),
+        Or<(Changed<TextInput>, Changed<ImeComposition>)>,
+    >,
+    mut texts: Query<&mut Text, With<TextInputText>>,
+) {
+    for (text_input, composition, children) in &text_inputs {
+        let value = match composition.filter(|composition| composition.is_composing()) {
+            Some(composition) => text_input.composed_value(composition).0,
+            None => text_input.value.clone(),
+        };
+        let mut texts = texts.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if text.sections.len() != 1 {
+                *text = Text::from_section("", text_input.text_style.clone());
+            }
+            if text.mask != text_input.mask {
+                text.mask = text_input.mask;
+            }
+            let section = &mut text.sections[0];
+            if section.value != value {
+                section.value.clone_from(&value);
+            }
+            if section.style.font != text_input.text_style.font
+                || section.style.font_size != text_input.text_style.font_size
+                || section.style.color != text_input.text_style.color
+            {
+                section.style = text_input.text_style.clone();
+            }
+        }
+    }
+}
+
+/// Positions the caret, selection highlight and composition underline nodes of [`TextInput`]s from the layout
+/// of their text, and blinks the caret of the focused input.
+#[allow(clippy::too_many_arguments)]
+pub fn update_text_input_caret(
+    mut commands: Commands,
+    focus: Res<TextInputFocus>,
+    time: Res<Time>,
+    mut blink_start: Local<Option<(Entity, Duration)>>,
+    text_inputs: Query<(
+        Entity,
+        Ref<TextInput>,
+        Option<&ImeComposition>,
+        &Node,
+        &Children,
+    )>,
+    texts: Query<(&Node, &Transform, &TextLayoutInfo), With<TextInputText>>,
+    mut carets: Query<(&mut Style, &mut Visibility), With<TextInputCaret>>,
+    selections: Query<Entity, With<TextInputSelection>>,
+    compositions: Query<Entity, With<TextInputComposition>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    target_cameras: Query<&TargetCamera>,
+    ui_scale: Res<UiScale>,
+) {
+    for (entity, text_input, composition, node, children) in &text_inputs {
+        let Some((text_node, text_transform, text_layout_info)) =
+            children.iter().find_map(|child| texts.get(*child).ok())
+        else {
+            continue;
+        };
+        let inverse_scale_factor = (target_cameras
+            .get(entity)
+            .ok()
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|camera_entity| camera_query.get(camera_entity).ok())
+            .and_then(Camera::target_scaling_factor)
+            .unwrap_or(1.)
+            * ui_scale.0)
+            .recip();
+        // The offset of the text from the top left corner of the input.
+        // The layout sets the translation of a node relative to the center of its parent.
+        let offset = text_transform.translation.truncate() + 0.5 * (node.size() - text_node.size());
+        let focused = focus.0 == Some(entity);
+        if focused
+            && (text_input.is_changed()
+                || blink_start.map(|(blinking, _)| blinking) != Some(entity))
+        {
+            *blink_start = Some((entity, time.elapsed()));
+        }
+        // The caret is shown for the first half of each blink period
+        let caret_shown = match *blink_start {
+            Some((_, start)) => {
+                let interval = CARET_BLINK_INTERVAL.as_secs_f32();
+                time.elapsed().saturating_sub(start).as_secs_f32() % (2. * interval) < interval
+            }
+            None => true,
+        };
+        // The byte range of the preedit text in the displayed text.
+        let composition = composition
+            .filter(|composition| focused && composition.is_composing())
+            .map(|composition| {
+                let preedit_range = text_input.composed_value(composition).1;
+                let cursor = preedit_range.start
+                    + composition
+                        .cursor
+                        .as_ref()
+                        .map_or(composition.preedit.len(), |cursor| cursor.start);
+                (preedit_range, cursor)
+            });
+        let cursor = composition
+            .as_ref()
+            .map_or(text_input.cursor, |(_, cursor)| *cursor);
+
+        let mut carets = carets.iter_many_mut(children);
+        while let Some((mut style, mut visibility)) = carets.fetch_next() {
+            let display = if focused {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            if style.display != display {
+                style.display = display;
+            }
+            if !focused {
+                continue;
+            }
+            visibility.set_if_neq(if caret_shown {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            let (position, height) = match text_layout_info.caret_rect(cursor) {
+                Some(rect) => (
+                    rect.min * inverse_scale_factor,
+                    rect.height() * inverse_scale_factor,
+                ),
+                None => (Vec2::ZERO, text_input.text_style.font_size),
+            };
+            let position = offset + position;
+            let (left, top, height) = (Val::Px(position.x), Val::Px(position.y), Val::Px(height));
+            if style.left != left || style.top != top || style.height != height {
+                style.left = left;
+                style.top = top;
+                style.height = height;
+            }
+        }
+
+        // The selection is replaced by the preedit text while composing.
+        let selection_rects = if focused && composition.is_none() {
+            text_layout_info.selection_rects(text_input.selection())
+        } else {
+            Vec::new()
+        };
+        let old_selections = selections.iter_many(children).collect::<Vec<_>>();
+        if !old_selections.is_empty() || !selection_rects.is_empty() {
+            for old_selection in old_selections {
+                commands.entity(old_selection).despawn_recursive();
+            }
+            commands.entity(entity).with_children(|builder| {
+                for rect in selection_rects {
+                    let min = offset + rect.min * inverse_scale_factor;
+                    let size = rect.size() * inverse_scale_factor;
+                    builder.spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(min.x),
+                                top: Val::Px(min.y),
+                                width: Val::Px(size.x),
+                                height: Val::Px(size.y),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(SELECTION_COLOR),
+                            ..default()
+                        },
+                        TextInputSelection,
+                    ));
+                }
+            });
+        }
+
+        let underline_rects = composition.map_or(Vec::new(), |(preedit_range, _)| {
+            text_layout_info.selection_rects(preedit_range)
+        });
+        let old_underlines = compositions.iter_many(children).collect::<Vec<_>>();
+        if old_underlines.is_empty() && underline_rects.is_empty() {
+            continue;
+        }
+        for old_underline in old_underlines {
+            commands.entity(old_underline).despawn_recursive();
+        }
+        commands.entity(entity).with_children(|builder| {
+            for rect in underline_rects {
+                let min = offset + rect.min * inverse_scale_factor;
+                let size = rect.size() * inverse_scale_factor;
+                builder.spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(min.x),
+                            top: Val::Px(min.y + size.y - COMPOSITION_UNDERLINE_WIDTH),
+                            width: Val::Px(size.x),
+                            height: Val::Px(COMPOSITION_UNDERLINE_WIDTH),
+                            ..default()
+                        },
+                        background_color: text_input.text_style.color.into(),
+                        z_index: ZIndex::Local(1),
+                        ..default()
+                    },
+                    TextInputComposition,
+                ));
+            }
+        });
+    }
+}
+
+/// Enables IME on the window displaying the focused [`TextInput`] and places the IME candidate box below its
+/// caret, and disables IME when the focus is removed.
+#[allow(clippy::too_many_arguments)]
+pub fn update_text_input_ime_window(
+    focus: Res<TextInputFocus>,
+    text_inputs: Query<(&Node, &GlobalTransform, &Children), With<TextInput>>,
+    carets: Query<&Style, With<TextInputCaret>>,
+    camera_query: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    target_cameras: Query<&TargetCamera>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut windows: Query<&mut Window>,
+    ui_scale: Res<UiScale>,
+    mut ime_window: Local<Option<Entity>>,
+) {
+    let target = focus.0.and_then(|entity| {
+        let (node, transform, children) = text_inputs.get(entity).ok()?;
+        let camera = target_cameras
+            .get(entity)
+            .ok()
+            .map(TargetCamera::entity)
+            .or(default_ui_camera.get())
+            .and_then(|camera_entity| camera_query.get(camera_entity).ok())?;
+        let Some(NormalizedRenderTarget::Window(window_ref)) =
+            camera.target.normalize(primary_window.get_single().ok())
+        else {
+            return None;
+        };
+        let caret = carets
+            .iter_many(children)
+            .next()
+            .and_then(|style| match (style.left, style.top, style.height) {
+                (Val::Px(left), Val::Px(top), Val::Px(height)) => {
+                    Some(Vec2::new(left, top + height))
+                }
+                _ => None,
+            })
+            .unwrap_or(Vec2::new(0., node.size().y));
+        let position = (transform.translation().truncate() - 0.5 * node.size() + caret)
+            * ui_scale.0
+            + camera
+                .logical_viewport_rect()
+                .map(|rect| rect.min)
+                .unwrap_or_default();
+        Some((window_ref.entity(), position))
+    });
+
+    let target_window = target.map(|(window, _)| window);
+    if *ime_window != target_window {
+        if let Some(mut window) = ime_window.and_then(|window| windows.get_mut(window).ok()) {
+            window.ime_enabled = false;
+        }
+        *ime_window = target_window;
+    }
+    let Some((window, position)) = target else {
+        return;
+    };
+    let Ok(mut window) = windows.get_mut(window) else {
+        return;
+    };
+    // Avoid triggering change detection, which would update the window every frame.
+    if !window.ime_enabled {
+        window.ime_enabled = true;
+    }
+    if window.ime_position != position {
+        window.ime_position = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        text_input_ime_system, text_input_keyboard_capture_system, ImeComposition, TextInput,
+        TextInputFocus,
+    };
+    use crate::UiKeyboardCapture;
+    use bevy_ecs::prelude::*;
+    use bevy_window::Ime;
+
+    #[test]
+    fn text_input_edits() {
+        let mut text_input = TextInput::new("héllo", Default::default());
+        assert_eq!(text_input.cursor(), 6);
+
+        text_input.delete_backward();
+        assert_eq!(text_input.value(), "héll");
+
+        text_input.move_cursor(3, false);
+        assert_eq!(text_input.cursor(), 3);
+        // offsets inside a character snap to its start
+        text_input.move_cursor(2, false);
+        assert_eq!(text_input.cursor(), 1);
+
+        text_input.delete_forward();
+        assert_eq!(text_input.value(), "hll");
+
+        text_input.insert("e");
+        assert_eq!(text_input.value(), "hell");
+        assert_eq!(text_input.cursor(), 2);
+
+        text_input.move_cursor(4, true);
+        assert_eq!(text_input.selection(), 2..4);
+        assert_eq!(text_input.selected_text(), "ll");
+
+        text_input.insert("y");
+        assert_eq!(text_input.value(), "hey");
+        assert!(text_input.selection().is_empty());
+
+        text_input.select_all();
+        text_input.delete_backward();
+        assert_eq!(text_input.value(), "");
+        assert_eq!(text_input.cursor(), 0);
+    }
+
+    #[test]
+    fn ime_composition_is_committed_to_focused_input() {
+        let mut world = World::new();
+        world.init_resource::<Events<Ime>>();
+        let window = world.spawn_empty().id();
+        let entity = world
+            .spawn((
+                TextInput::new("ab", Default::default()),
+                ImeComposition::default(),
+            ))
+            .id();
+        world.insert_resource(TextInputFocus(Some(entity)));
+        let mut system = IntoSystem::into_system(text_input_ime_system);
+        system.initialize(&mut world);
+
+        world.send_event(Ime::Preedit {
+            window,
+            value: "にほん".to_string(),
+            cursor: Some((3, 3)),
+        });
+        system.run((), &mut world);
+        let composition = world.get::<ImeComposition>(entity).unwrap();
+        assert_eq!(composition.preedit(), "にほん");
+        assert_eq!(composition.cursor(), Some(3..3));
+        let text_input = world.get::<TextInput>(entity).unwrap();
+        assert_eq!(text_input.value(), "ab");
+        assert_eq!(
+            text_input.composed_value(composition),
+            ("abにほん".to_string(), 2..11)
+        );
+
+        world.send_event(Ime::Commit {
+            window,
+            value: "日本".to_string(),
+        });
+        system.run((), &mut world);
+        assert!(!world.get::<ImeComposition>(entity).unwrap().is_composing());
+        assert_eq!(world.get::<TextInput>(entity).unwrap().value(), "ab日本");
+
+        world.send_event(Ime::Preedit {
+            window,
+            value: "ご".to_string(),
+            cursor: None,
+        });
+        system.run((), &mut world);
+        world.resource_mut::<TextInputFocus>().0 = None;
+        system.run((), &mut world);
+        assert!(!world.get::<ImeComposition>(entity).unwrap().is_composing());
+        assert_eq!(world.get::<TextInput>(entity).unwrap().value(), "ab日本");
+    }
+
+    #[test]
+    fn focused_text_inputs_capture_the_keyboard() {
+        let mut world = World::new();
+        world.init_resource::<TextInputFocus>();
+        world.init_resource::<UiKeyboardCapture>();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(text_input_keyboard_capture_system);
+
+        world.resource_mut::<TextInputFocus>().0 = Some(first);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<UiKeyboardCapture>().owner(), Some(first));
+
+        world.resource_mut::<TextInputFocus>().0 = Some(second);
+        schedule.run(&mut world);
+        let capture = world.resource::<UiKeyboardCapture>();
+        assert_eq!(capture.owner(), Some(second));
+        assert!(!capture.has_captured(first));
+
+        world.resource_mut::<TextInputFocus>().0 = None;
+        schedule.run(&mut world);
+        assert!(!world.resource::<UiKeyboardCapture>().is_captured());
+    }
+}
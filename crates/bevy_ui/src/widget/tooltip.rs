@@ -0,0 +1,372 @@
+use bevy_color::Color;
+use bevy_ecs::{
+    entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::{ReflectComponent, ReflectMapEntities},
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{camera::Camera, view::Visibility};
+use bevy_time::Time;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{default, Duration};
+
+use crate::{
+    geometry::ui_logical_viewport_size, node_bundles::NodeBundle, BorderRadius, Display,
+    Interaction, Node, PositionType, Style, TargetCamera, UiRect, UiScale, UiStacks, Val, ZIndex,
+};
+
+/// Shows a tooltip next to a UI node after the cursor hovers over it for [`Tooltip::delay`].
+///
+/// The node needs an [`Interaction`] component. The tooltip is hidden when the cursor leaves the node or presses
+/// it, and is drawn over all other nodes with [`ZIndex::TOOLTIP`].
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq, MapEntities)]
+pub struct Tooltip {
+    /// What the tooltip shows.
+    pub content: TooltipContent,
+    /// How long the cursor has to hover over the node before the tooltip is shown.
+    pub delay: Duration,
+    /// The side of the node the tooltip is placed on, if it fits in the viewport.
+    pub placement: TooltipPlacement,
+    /// The distance between the node and the tooltip, in logical pixels.
+    pub gap: f32,
+}
+
+impl MapEntities for Tooltip {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let TooltipContent::Node(node) = &mut self.content {
+            *node = entity_mapper.map_entity(*node);
+        }
+    }
+}
+
+impl Tooltip {
+    /// A tooltip showing `text`.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            content: TooltipContent::Text(text.into()),
+            ..default()
+        }
+    }
+
+    /// A tooltip showing the root UI node `node`.
+    pub fn node(node: Entity) -> Self {
+        Self {
+            content: TooltipContent::Node(node),
+            ..default()
+        }
+    }
+
+    /// Returns the tooltip with the hover delay set to `delay`.
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns the tooltip placed on the `placement` side of its node.
+    pub const fn with_placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self {
+            content: TooltipContent::Text(String::new()),
+            delay: Duration::from_millis(500),
+            placement: TooltipPlacement::Below,
+            gap: 4.,
+        }
+    }
+}
+
+/// The content of a [`Tooltip`].
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(PartialEq)]
+pub enum TooltipContent {
+    /// A tooltip node with the text is spawned while the tooltip is shown.
+    ///
+    /// The text is only shown with the `bevy_text` feature enabled.
+    Text(String),
+    /// A root UI node, absolutely positioned and shown while the tooltip is shown and hidden with
+    /// [`Display::None`] otherwise.
+    Node(Entity),
+}
+
+/// The side of its node a [`Tooltip`] is placed on.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum TooltipPlacement {
+    /// Above the node, centered horizontally.
+    Above,
+    /// Below the node, centered horizontally.
+    #[default]
+    Below,
+    /// Left of the node, centered vertically.
+    Left,
+    /// Right of the node, centered vertically.
+    Right,
+}
+
+impl TooltipPlacement {
+    /// The placement on the opposite side.
+    pub const fn flip(self) -> Self {
+        match self {
+            Self::Above => Self::Below,
+            Self::Below => Self::Above,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Marks the tooltip nodes spawned for [`TooltipContent::Text`] tooltips.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq, MapEntities)]
+pub struct TooltipNode {
+    /// The node with the [`Tooltip`].
+    pub owner: Entity,
+}
+
+impl MapEntities for TooltipNode {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.owner = entity_mapper.map_entity(self.owner);
+    }
+}
+
+/// Returns the position of the top left corner of a tooltip of `size` for a node with the rect `anchor`, in a
+/// viewport of `viewport_size`.
+///
+/// The tooltip is placed on the `placement` side of the node, or flipped to the opposite side if it doesn't fit on
+/// that side and fits better on the other. It is then shifted along the side to keep it inside the viewport.
+pub fn place_tooltip(
+    anchor: Rect,
+    size: Vec2,
+    viewport_size: Vec2,
+    placement: TooltipPlacement,
+    gap: f32,
+) -> Vec2 {
+    // The space left over for the tooltip on the side of the anchor, negative if it doesn't fit
+    let space = |placement| match placement {
+        TooltipPlacement::Above => anchor.min.y - gap - size.y,
+        TooltipPlacement::Below => viewport_size.y - anchor.max.y - gap - size.y,
+        TooltipPlacement::Left => anchor.min.x - gap - size.x,
+        TooltipPlacement::Right => viewport_size.x - anchor.max.x - gap - size.x,
+    };
+    let placement = if space(placement) < 0. && space(placement.flip()) > space(placement) {
+        placement.flip()
+    } else {
+        placement
+    };
+    let center = anchor.center() - 0.5 * size;
+    let position = match placement {
+        TooltipPlacement::Above => Vec2::new(center.x, anchor.min.y - gap - size.y),
+        TooltipPlacement::Below => Vec2::new(center.x, anchor.max.y + gap),
+        TooltipPlacement::Left => Vec2::new(anchor.min.x - gap - size.x, center.y),
+        TooltipPlacement::Right => Vec2::new(anchor.max.x + gap, center.y),
+    };
+    position.min(viewport_size - size).max(Vec2::ZERO)
+}
+
+/// A tooltip being shown by [`tooltip_system`].
+struct ShownTooltip {
+    owner: Entity,
+    node: Entity,
+    /// Whether the node was spawned for the tooltip, and is despawned when it's hidden.
+    spawned: bool,
+}
+
+/// The state of [`tooltip_system`].
+#[derive(Default)]
+pub struct TooltipState {
+    /// The node with a [`Tooltip`] under the cursor, and the [`Time::elapsed`] when the cursor started hovering over
+    /// it.
+    hovered: Option<(Entity, Duration)>,
+    shown: Option<ShownTooltip>,
+}
+
+/// Shows the [`Tooltip`] of the hovered node after its delay, and places it next to the node inside the viewport.
+///
+/// Tooltips are placed using the size of their nodes in the previous layout, and stay hidden until they have been
+/// laid out once.
+#[allow(clippy::too_many_arguments)]
+pub fn tooltip_system(
+    mut commands: Commands,
+    mut state: Local<TooltipState>,
+    time: Res<Time>,
+    ui_scale: Res<UiScale>,
+    ui_stacks: UiStacks,
+    camera_query: Query<&Camera>,
+    owners: Query<(Entity, &Tooltip, &Interaction, &Node, &GlobalTransform)>,
+    mut tooltip_nodes: Query<(&Node, &mut Style, Option<&mut Visibility>), Without<Tooltip>>,
+) {
+    let state = &mut *state;
+    let now = time.elapsed();
+    let hovered = owners
+        .iter()
+        .find(|(.., interaction, _, _)| **interaction == Interaction::Hovered)
+        .map(|(entity, ..)| entity);
+    if state.hovered.map(|(entity, _)| entity) != hovered {
+        state.hovered = hovered.map(|entity| (entity, now));
+    }
+
+    if let Some(shown) = state
+        .shown
+        .as_ref()
+        .filter(|shown| Some(shown.owner) != hovered)
+    {
+        if shown.spawned {
+            if let Some(node) = commands.get_entity(shown.node) {
+                node.despawn_recursive();
+            }
+        } else if let Ok((_, mut style, _)) = tooltip_nodes.get_mut(shown.node) {
+            style.display = Display::None;
+        }
+        state.shown = None;
+    }
+
+    let Some((owner, hover_start)) = state.hovered else {
+        return;
+    };
+    let Ok((_, tooltip, _, owner_node, owner_transform)) = owners.get(owner) else {
+        return;
+    };
+    let Some(camera) = ui_stacks.camera_of(owner) else {
+        return;
+    };
+
+    let shown = match state.shown.as_ref() {
+        Some(shown) => shown,
+        None if now.saturating_sub(hover_start) < tooltip.delay => return,
+        None => state.shown.insert(show_tooltip(
+            &mut commands,
+            &mut tooltip_nodes,
+            owner,
+            camera,
+            &tooltip.content,
+        )),
+    };
+
+    let Ok((node, mut style, visibility)) = tooltip_nodes.get_mut(shown.node) else {
+        return;
+    };
+    if node.size() == Vec2::ZERO {
+        // Wait for the tooltip to be laid out
+        return;
+    }
+    let viewport_size = ui_logical_viewport_size(camera_query.get(camera).ok(), ui_scale.0);
+    let position = place_tooltip(
+        owner_node.logical_rect(owner_transform),
+        node.size(),
+        viewport_size,
+        tooltip.placement,
+        tooltip.gap,
+    );
+    style.left = Val::Px(position.x);
+    style.top = Val::Px(position.y);
+    if let Some(mut visibility) = visibility {
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
+/// Shows the node of a tooltip hidden until it's placed, spawning it for [`TooltipContent::Text`] tooltips.
+fn show_tooltip(
+    commands: &mut Commands,
+    tooltip_nodes: &mut Query<(&Node, &mut Style, Option<&mut Visibility>), Without<Tooltip>>,
+    owner: Entity,
+    camera: Entity,
+    content: &TooltipContent,
+) -> ShownTooltip {
+    match content {
+        TooltipContent::Text(text) => {
+            let node = commands
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            padding: UiRect::axes(Val::Px(6.), Val::Px(3.)),
+                            ..default()
+                        },
+                        background_color: Color::srgba(0.1, 0.1, 0.1, 0.9).into(),
+                        border_radius: BorderRadius::all(Val::Px(3.)),
+                        visibility: Visibility::Hidden,
+                        z_index: ZIndex::TOOLTIP,
+                        ..default()
+                    },
+                    TargetCamera(camera),
+                    TooltipNode { owner },
+                ))
+                .id();
+            #[cfg(feature = "bevy_text")]
+            {
+                use bevy_hierarchy::BuildChildren;
+                commands.entity(node).with_children(|builder| {
+                    builder.spawn(crate::node_bundles::TextBundle::from_section(
+                        text.clone(),
+                        default(),
+                    ));
+                });
+            }
+            #[cfg(not(feature = "bevy_text"))]
+            let _ = text;
+            ShownTooltip {
+                owner,
+                node,
+                spawned: true,
+            }
+        }
+        &TooltipContent::Node(node) => {
+            if let Ok((_, mut style, visibility)) = tooltip_nodes.get_mut(node) {
+                style.display = Display::Flex;
+                style.position_type = PositionType::Absolute;
+                if let Some(mut visibility) = visibility {
+                    *visibility = Visibility::Hidden;
+                }
+            }
+            if let Some(mut node) = commands.get_entity(node) {
+                node.insert((ZIndex::TOOLTIP, TargetCamera(camera)));
+            }
+            ShownTooltip {
+                owner,
+                node,
+                spawned: false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Rect, Vec2};
+
+    use super::{place_tooltip, TooltipPlacement};
+
+    #[test]
+    fn tooltips_are_kept_inside_the_viewport() {
+        let viewport = Vec2::new(400., 300.);
+        let size = Vec2::new(100., 20.);
+
+        // Centered below the anchor
+        let anchor = Rect::new(150., 100., 250., 130.);
+        let position = place_tooltip(anchor, size, viewport, TooltipPlacement::Below, 4.);
+        assert_eq!(position, Vec2::new(150., 134.));
+
+        // Flipped above an anchor at the bottom of the viewport
+        let anchor = Rect::new(150., 270., 250., 300.);
+        let position = place_tooltip(anchor, size, viewport, TooltipPlacement::Below, 4.);
+        assert_eq!(position, Vec2::new(150., 246.));
+
+        // Flipped to the left of an anchor at the right edge, and shifted down from the top edge
+        let anchor = Rect::new(360., 0., 400., 10.);
+        let position = place_tooltip(anchor, size, viewport, TooltipPlacement::Right, 4.);
+        assert_eq!(position, Vec2::new(256., 0.));
+
+        // Shifted inside the viewport from the left edge
+        let anchor = Rect::new(0., 100., 20., 120.);
+        let position = place_tooltip(anchor, size, viewport, TooltipPlacement::Above, 4.);
+        assert_eq!(position, Vec2::new(0., 76.));
+    }
+}
@@ -265,7 +265,7 @@ fn add_button_for_value(
                 margin: UiRect::right(Val::Px(12.0)),
                 ..default()
             },
-            border_color: BorderColor(Color::WHITE),
+            border_color: BorderColor::all(Color::WHITE),
             border_radius: BorderRadius::MAX,
             image: UiImage::default().with_color(Color::BLACK),
             ..default()
@@ -346,6 +346,7 @@ fn add_text<'a>(
             font: font.clone(),
             font_size: 18.0,
             color,
+            ..default()
         },
     ))
 }
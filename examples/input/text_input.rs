@@ -43,6 +43,7 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone_weak(),
                     ..default()
                 },
+                ..default()
             },
             TextSection {
                 value: "false\n".to_string(),
@@ -51,6 +52,7 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font_size: 30.0,
                     ..default()
                 },
+                ..default()
             },
             TextSection {
                 value: "IME Active: ".to_string(),
@@ -58,6 +60,7 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone_weak(),
                     ..default()
                 },
+                ..default()
             },
             TextSection {
                 value: "false\n".to_string(),
@@ -66,6 +69,7 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font_size: 30.0,
                     ..default()
                 },
+                ..default()
             },
             TextSection {
                 value: "click to toggle IME, press return to start a new line\n\n".to_string(),
@@ -74,6 +78,7 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font_size: 18.0,
                     ..default()
                 },
+                ..default()
             },
             TextSection {
                 value: "".to_string(),
@@ -82,6 +87,7 @@ fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font_size: 25.0,
                     ..default()
                 },
+                ..default()
             },
         ])
         .with_style(Style {
@@ -0,0 +1,155 @@
+//! Stress tests the layout, measurement and rendering of many UI text nodes.
+//!
+//! Each node's text is measured by its measure function during the UI layout, laid out by the text pipeline and
+//! its glyphs are extracted for rendering every frame.
+//!
+//! To measure and lay out all the text each frame run
+//! `cargo run --example many_text_nodes --release -- --recompute-text`
+
+use argh::FromArgs;
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    window::{PresentMode, WindowResolution},
+    winit::{UpdateMode, WinitSettings},
+};
+
+const WORDS: [&str; 8] = [
+    "text", "nodes", "measure", "layout", "glyph", "atlas", "extract", "render",
+];
+
+#[derive(FromArgs, Resource)]
+/// `many_text_nodes` stress tests the layout, measurement and rendering of many UI text nodes
+struct Args {
+    /// how many text nodes per row and column of the grid
+    #[argh(option, default = "60")]
+    nodes: usize,
+
+    /// how many words in the text of each node
+    #[argh(option, default = "6")]
+    words: usize,
+
+    /// the font size of the text
+    #[argh(option, default = "8.")]
+    font_size: f32,
+
+    /// whether to measure and lay out all the text each frame
+    #[argh(switch)]
+    recompute_text: bool,
+
+    /// whether to change the value of every text each frame, adding new glyphs to the atlases as the font size
+    /// changes
+    #[argh(switch)]
+    update_text: bool,
+
+    /// whether to perform a full relayout each frame, calling the measure function of every node
+    #[argh(switch)]
+    relayout: bool,
+}
+
+fn main() {
+    // `from_env` panics on the web
+    #[cfg(not(target_arch = "wasm32"))]
+    let args: Args = argh::from_env();
+    #[cfg(target_arch = "wasm32")]
+    let args = Args::from_args(&[], &[]).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins((
+        DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                present_mode: PresentMode::AutoNoVsync,
+                resolution: WindowResolution::new(1920.0, 1080.0).with_scale_factor_override(1.0),
+                ..default()
+            }),
+            ..default()
+        }),
+        FrameTimeDiagnosticsPlugin,
+        LogDiagnosticsPlugin::default(),
+    ))
+    .insert_resource(WinitSettings {
+        focused_mode: UpdateMode::Continuous,
+        unfocused_mode: UpdateMode::Continuous,
+    })
+    .add_systems(Startup, setup);
+
+    if args.recompute_text {
+        app.add_systems(Update, |mut text_query: Query<&mut Text>| {
+            text_query
+                .iter_mut()
+                .for_each(|mut text| text.set_changed());
+        });
+    }
+
+    if args.update_text {
+        app.add_systems(Update, update_text);
+    }
+
+    if args.relayout {
+        app.add_systems(Update, |mut style_query: Query<&mut Style>| {
+            style_query
+                .iter_mut()
+                .for_each(|mut style| style.set_changed());
+        });
+    }
+
+    app.insert_resource(args).run();
+}
+
+fn setup(mut commands: Commands, args: Res<Args>) {
+    warn!(include_str!("warning_string.txt"));
+
+    commands.spawn(Camera2dBundle::default());
+    let size = 100. / args.nodes as f32;
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Grid,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                grid_template_columns: RepeatedGridTrack::flex(args.nodes as u16, 1.),
+                grid_template_rows: RepeatedGridTrack::flex(args.nodes as u16, 1.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|commands| {
+            for index in 0..args.nodes * args.nodes {
+                commands.spawn(TextBundle {
+                    text: Text::from_section(
+                        text_value(index, args.words),
+                        TextStyle {
+                            font_size: args.font_size,
+                            color: Color::hsl(index as f32 * 7. % 360., 0.8, 0.75),
+                            ..default()
+                        },
+                    ),
+                    style: Style {
+                        max_width: Val::Vw(size),
+                        max_height: Val::Vh(size),
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    ..default()
+                });
+            }
+        });
+}
+
+fn text_value(index: usize, words: usize) -> String {
+    (0..words)
+        .map(|word| WORDS[(index + word) % WORDS.len()])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn update_text(mut text_query: Query<&mut Text>, args: Res<Args>, mut frame: Local<usize>) {
+    *frame += 1;
+    // Cycle through a few sizes, so glyphs are added to the atlases until all the sizes are cached
+    let font_size = args.font_size + (*frame % 4) as f32;
+    for (index, mut text) in text_query.iter_mut().enumerate() {
+        let section = &mut text.sections[0];
+        section.value = text_value(index + *frame, args.words);
+        section.style.font_size = font_size;
+    }
+}
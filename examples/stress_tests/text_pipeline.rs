@@ -48,7 +48,9 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraMono-Medium.ttf"),
                         font_size: (4 + i % 10) as f32,
                         color: BLUE.into(),
+                        ..default()
                     },
+                    ..default()
                 },
                 TextSection {
                     value: "pipeline".repeat(i),
@@ -56,7 +58,9 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: (4 + i % 11) as f32,
                         color: YELLOW.into(),
+                        ..default()
                     },
+                    ..default()
                 },
             ]
         })
@@ -66,6 +70,7 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
             sections,
             justify: JustifyText::Center,
             linebreak_behavior: BreakLineOn::AnyCharacter,
+            ..default()
         },
         ..Default::default()
     });
@@ -30,17 +30,17 @@ fn button_system(
             Interaction::Pressed => {
                 text.sections[0].value = "Press".to_string();
                 image.color = PRESSED_BUTTON;
-                border_color.0 = RED.into();
+                border_color.set_all(RED);
             }
             Interaction::Hovered => {
                 text.sections[0].value = "Hover".to_string();
                 image.color = HOVERED_BUTTON;
-                border_color.0 = Color::WHITE;
+                border_color.set_all(Color::WHITE);
             }
             Interaction::None => {
                 text.sections[0].value = "Button".to_string();
                 image.color = NORMAL_BUTTON;
-                border_color.0 = Color::BLACK;
+                border_color.set_all(Color::BLACK);
             }
         }
     }
@@ -73,7 +73,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         align_items: AlignItems::Center,
                         ..default()
                     },
-                    border_color: BorderColor(Color::BLACK),
+                    border_color: BorderColor::all(Color::BLACK),
                     border_radius: BorderRadius::MAX,
                     image: UiImage::default().with_color(NORMAL_BUTTON),
                     ..default()
@@ -85,6 +85,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                             font_size: 40.0,
                             color: Color::srgb(0.9, 0.9, 0.9),
+                            ..default()
                         },
                     ));
                 });
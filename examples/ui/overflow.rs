@@ -94,11 +94,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                         ..Default::default()
                                     },
                                     Interaction::default(),
-                                    Outline {
-                                        width: Val::Px(2.),
-                                        offset: Val::Px(2.),
-                                        color: Color::NONE,
-                                    },
+                                    Outline::new(Val::Px(2.), Val::Px(2.), Color::NONE),
                                 ));
                             });
                     });
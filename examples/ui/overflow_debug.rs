@@ -261,14 +261,17 @@ fn toggle_overflow(
             Overflow {
                 x: OverflowAxis::Visible,
                 y: OverflowAxis::Visible,
+                ..
             } => Overflow::clip_y(),
             Overflow {
                 x: OverflowAxis::Visible,
                 y: OverflowAxis::Clip,
+                ..
             } => Overflow::clip_x(),
             Overflow {
                 x: OverflowAxis::Clip,
                 y: OverflowAxis::Visible,
+                ..
             } => Overflow::clip(),
             _ => Overflow::visible(),
         };
@@ -143,11 +143,7 @@ fn setup(mut commands: Commands) {
                     border_radius,
                     ..Default::default()
                 },
-                Outline {
-                    width: Val::Px(6.),
-                    offset: Val::Px(6.),
-                    color: Color::WHITE,
-                },
+                Outline::new(Val::Px(6.), Val::Px(6.), Color::WHITE),
             ))
             .add_child(inner_spot)
             .id();
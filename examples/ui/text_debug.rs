@@ -68,6 +68,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 30.0,
                     color: YELLOW.into(),
+                    ..default()
                 },
             )
             .with_text_justify(JustifyText::Right)
@@ -110,6 +111,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 40.0,
                     color: Color::srgb(0.8, 0.2, 0.7),
+                    ..default()
                 },
             )
             .with_text_justify(JustifyText::Center)
@@ -126,6 +128,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                     font: font.clone(),
                     font_size: 35.0,
                     color: YELLOW.into(),
+                    ..default()
                 },
             )
             .with_text_justify(JustifyText::Left)
@@ -151,12 +154,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: RED.into(),
+                        ..default()
                     },
                 ),
                 TextSection::from_style(TextStyle {
                     font: font.clone(),
                     font_size: 25.0,
                     color: ORANGE_RED.into(),
+                    ..default()
                 }),
                 TextSection::new(
                     " fps, ",
@@ -164,12 +169,14 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: YELLOW.into(),
+                        ..default()
                     },
                 ),
                 TextSection::from_style(TextStyle {
                     font: font.clone(),
                     font_size: 25.0,
                     color: LIME.into(),
+                    ..default()
                 }),
                 TextSection::new(
                     " ms/frame",
@@ -177,6 +184,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                         font: font.clone(),
                         font_size: 25.0,
                         color: BLUE.into(),
+                        ..default()
                     },
                 ),
             ]),
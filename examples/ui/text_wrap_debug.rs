@@ -70,6 +70,7 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
     for linebreak_behavior in [
         BreakLineOn::AnyCharacter,
         BreakLineOn::WordBoundary,
+        BreakLineOn::BreakWord,
         BreakLineOn::NoWrap,
     ] {
         let row_id = commands
@@ -79,7 +80,7 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                     justify_content: JustifyContent::SpaceAround,
                     align_items: AlignItems::Center,
                     width: Val::Percent(100.),
-                    height: Val::Percent(50.),
+                    height: Val::Percent(25.),
                     ..Default::default()
                 },
                 ..Default::default()
@@ -124,9 +125,11 @@ fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                     sections: vec![TextSection {
                         value: message.clone(),
                         style: text_style.clone(),
+                        ..default()
                     }],
                     justify: JustifyText::Left,
                     linebreak_behavior,
+                    ..default()
                 };
                 let text_id = commands
                     .spawn(TextBundle {
@@ -6,7 +6,6 @@ use bevy::{
         AccessibilityNode,
     },
     color::palettes::basic::LIME,
-    input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
     winit::WinitSettings,
 };
@@ -16,8 +15,7 @@ fn main() {
     app.add_plugins(DefaultPlugins)
         // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
         .insert_resource(WinitSettings::desktop_app())
-        .add_systems(Startup, setup)
-        .add_systems(Update, mouse_scroll);
+        .add_systems(Startup, setup);
 
     #[cfg(feature = "bevy_dev_tools")]
     {
@@ -138,19 +136,22 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         ),
                         Label,
                     ));
-                    // List with hidden overflow
+                    // List with hidden overflow, scrolled with the mouse wheel
                     parent
-                        .spawn(NodeBundle {
-                            style: Style {
-                                flex_direction: FlexDirection::Column,
-                                align_self: AlignSelf::Stretch,
-                                height: Val::Percent(50.),
-                                overflow: Overflow::clip_y(),
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    align_self: AlignSelf::Stretch,
+                                    height: Val::Percent(50.),
+                                    overflow: Overflow::clip_y(),
+                                    ..default()
+                                },
+                                background_color: Color::srgb(0.10, 0.10, 0.10).into(),
                                 ..default()
                             },
-                            background_color: Color::srgb(0.10, 0.10, 0.10).into(),
-                            ..default()
-                        })
+                            ScrollPosition::default(),
+                        ))
                         .with_children(|parent| {
                             // Moving panel
                             parent
@@ -163,7 +164,6 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                                         },
                                         ..default()
                                     },
-                                    ScrollingList::default(),
                                     AccessibilityNode(NodeBuilder::new(Role::List)),
                                 ))
                                 .with_children(|parent| {
@@ -336,44 +336,18 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         });
 }
 
-#[derive(Component, Default)]
-struct ScrollingList {
-    position: f32,
-}
-
-fn mouse_scroll(
-    mut mouse_wheel_events: EventReader<MouseWheel>,
-    mut query_list: Query<(&mut ScrollingList, &mut Style, &Parent, &Node)>,
-    query_node: Query<&Node>,
-) {
-    for mouse_wheel_event in mouse_wheel_events.read() {
-        for (mut scrolling_list, mut style, parent, list_node) in &mut query_list {
-            let items_height = list_node.size().y;
-            let container_height = query_node.get(parent.get()).unwrap().size().y;
-
-            let max_scroll = (items_height - container_height).max(0.);
-
-            let dy = match mouse_wheel_event.unit {
-                MouseScrollUnit::Line => mouse_wheel_event.y * 20.,
-                MouseScrollUnit::Pixel => mouse_wheel_event.y,
-            };
-
-            scrolling_list.position += dy;
-            scrolling_list.position = scrolling_list.position.clamp(-max_scroll, 0.);
-            style.top = Val::Px(scrolling_list.position);
-        }
-    }
-}
-
 #[cfg(feature = "bevy_dev_tools")]
 // The system that will enable/disable the debug outlines around the nodes
 fn toggle_overlay(
     input: Res<ButtonInput<KeyCode>>,
     mut options: ResMut<bevy::dev_tools::ui_debug_overlay::UiDebugOptions>,
 ) {
-    info_once!("The debug outlines are enabled, press Space to turn them on/off");
+    info_once!("The debug outlines are enabled, press Space to turn them on/off, press H to show the hit test geometry");
     if input.just_pressed(KeyCode::Space) {
         // The toggle method will enable the debug_overlay if disabled and disable if enabled
         options.toggle();
     }
+    if input.just_pressed(KeyCode::KeyH) {
+        options.toggle_hit_test();
+    }
 }